@@ -7,7 +7,7 @@ use anthropic::{stream_completion, Request, RequestMessage};
 use anyhow::{anyhow, Result};
 use editor::{Editor, EditorElement, EditorStyle};
 use futures::{future::BoxFuture, stream::BoxStream, FutureExt, StreamExt};
-use gpui::{AnyView, AppContext, FontStyle, Task, TextStyle, View, WhiteSpace};
+use gpui::{AnyView, AppContext, FontStyle, Task, TextAlign, TextStyle, View, WhiteSpace};
 use http::HttpClient;
 use settings::Settings;
 use std::time::Duration;
@@ -292,6 +292,7 @@ impl AuthenticationPrompt {
             color: cx.theme().colors().text,
             font_family: settings.ui_font.family.clone(),
             font_features: settings.ui_font.features.clone(),
+            font_fallbacks: settings.ui_font.fallbacks.clone(),
             font_size: rems(0.875).into(),
             font_weight: settings.ui_font.weight,
             font_style: FontStyle::Normal,
@@ -300,6 +301,7 @@ impl AuthenticationPrompt {
             underline: None,
             strikethrough: None,
             white_space: WhiteSpace::Normal,
+            text_align: TextAlign::Start,
         };
         EditorElement::new(
             &self.api_key,
@@ -19,8 +19,8 @@ use fs::Fs;
 use futures::{channel::mpsc, SinkExt, Stream, StreamExt};
 use gpui::{
     point, AppContext, EventEmitter, FocusHandle, FocusableView, FontStyle, FontWeight, Global,
-    HighlightStyle, Model, ModelContext, Subscription, Task, TextStyle, UpdateGlobal, View,
-    ViewContext, WeakView, WhiteSpace, WindowContext,
+    HighlightStyle, Model, ModelContext, Subscription, Task, TextAlign, TextStyle, UpdateGlobal,
+    View, ViewContext, WeakView, WhiteSpace, WindowContext,
 };
 use language::{Buffer, Point, Selection, TransactionId};
 use multi_buffer::MultiBufferRow;
@@ -1729,6 +1729,7 @@ impl PromptEditor {
             },
             font_family: settings.ui_font.family.clone(),
             font_features: settings.ui_font.features.clone(),
+            font_fallbacks: settings.ui_font.fallbacks.clone(),
             font_size: rems(0.875).into(),
             font_weight: FontWeight::NORMAL,
             font_style: FontStyle::Normal,
@@ -1737,6 +1738,7 @@ impl PromptEditor {
             underline: None,
             strikethrough: None,
             white_space: WhiteSpace::Normal,
+            text_align: TextAlign::Start,
         };
         EditorElement::new(
             &self.editor,
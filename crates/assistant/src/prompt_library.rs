@@ -856,6 +856,10 @@ impl PromptLibrary {
                                                             .ui_font
                                                             .features
                                                             .clone(),
+                                                        font_fallbacks: settings
+                                                            .ui_font
+                                                            .fallbacks
+                                                            .clone(),
                                                         font_size: HeadlineSize::Large
                                                             .size()
                                                             .into(),
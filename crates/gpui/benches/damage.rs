@@ -0,0 +1,63 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use gpui::{div, prelude::*, px, rgb, FrameDamage, TestAppContext, TestDispatcher, ViewContext};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+/// Rows that don't change, except for the first one, which blinks.
+struct Rows {
+    count: usize,
+    blink: bool,
+}
+
+impl Render for Rows {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        div().flex().flex_col().children((0..self.count).map(|ix| {
+            let color = if ix == 0 && self.blink {
+                rgb(0xff0000)
+            } else {
+                rgb(0x0000ff)
+            };
+            div().w(px(200.)).h(px(1.)).bg(color)
+        }))
+    }
+}
+
+fn damage_benchmark(c: &mut Criterion) {
+    let mut cx = TestAppContext::new(TestDispatcher::new(StdRng::seed_from_u64(0)), None);
+
+    let mut group = c.benchmark_group("damage");
+    let count = 1000;
+    let frames = 10;
+    group.throughput(Throughput::Elements(frames as u64));
+    let (view, cx) = cx.add_window_view(|_| Rows {
+        count,
+        blink: false,
+    });
+    cx.update(|cx| cx.frame_diagnostics_mut().set_enabled(true));
+    group.bench_function(BenchmarkId::new("one changing row", count), |b| {
+        b.iter(|| {
+            for _ in 0..frames {
+                view.update(cx, |view, cx| {
+                    view.blink = !view.blink;
+                    cx.notify();
+                });
+                cx.run_until_parked();
+            }
+        })
+    });
+    group.finish();
+
+    // Only the row that blinked changed, not the rest of the window.
+    let (damage, viewport_size) = cx.update(|cx| {
+        let damage = cx.frame_diagnostics().last_frame().unwrap().damage.clone();
+        (damage, cx.viewport_size())
+    });
+    assert!(
+        matches!(damage, FrameDamage::Partial(ref rects) if rects.len() == 1),
+        "expected one changed row: {damage:?}"
+    );
+    let repainted = damage.repainted_fraction(viewport_size);
+    assert!(repainted < 0.01, "{:.2}% changed", repainted * 100.);
+}
+
+criterion_group!(benches, damage_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use gpui::{div, prelude::*, px, TestAppContext, TestDispatcher, ViewContext};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+/// A list whose items either all share one style or all have a different one.
+struct List {
+    item_count: usize,
+    distinct_styles: bool,
+}
+
+impl Render for List {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        let distinct_styles = self.distinct_styles;
+        div()
+            .flex()
+            .flex_col()
+            .children((0..self.item_count).map(move |ix| {
+                let height = if distinct_styles {
+                    20. + ix as f32
+                } else {
+                    20.
+                };
+                div().flex().p_2().h(px(height))
+            }))
+    }
+}
+
+fn layout_benchmark(c: &mut Criterion) {
+    let mut cx = TestAppContext::new(TestDispatcher::new(StdRng::seed_from_u64(0)), None);
+
+    let mut group = c.benchmark_group("layout nodes");
+    for item_count in [100, 500, 2000] {
+        group.throughput(Throughput::Elements(item_count as u64));
+        for distinct_styles in [false, true] {
+            let name = if distinct_styles {
+                "distinct styles"
+            } else {
+                "identical styles"
+            };
+            let (_, cx) = cx.add_window_view(|_| List {
+                item_count,
+                distinct_styles,
+            });
+            group.bench_function(BenchmarkId::new(name, item_count), |b| {
+                b.iter(|| cx.update(|cx| cx.refresh()))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, layout_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use gpui::{div, prelude::*, TestAppContext, TestDispatcher, ViewContext};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+/// Labels that all share one text style.
+struct Labels {
+    count: usize,
+}
+
+impl Render for Labels {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .children((0..self.count).map(|ix| div().child(format!("Label {ix}"))))
+    }
+}
+
+fn text_layout_benchmark(c: &mut Criterion) {
+    let mut cx = TestAppContext::new(TestDispatcher::new(StdRng::seed_from_u64(0)), None);
+
+    let mut group = c.benchmark_group("text layout");
+    let count = 1000;
+    let frames = 10;
+    group.throughput(Throughput::Elements((count * frames) as u64));
+    let (_, cx) = cx.add_window_view(|_| Labels { count });
+    cx.update(|cx| cx.frame_diagnostics_mut().set_enabled(true));
+    group.bench_function(BenchmarkId::new("identical style labels", count), |b| {
+        b.iter(|| {
+            for _ in 0..frames {
+                cx.update(|cx| cx.refresh());
+                cx.run_until_parked();
+            }
+        })
+    });
+    group.finish();
+
+    // Once the first frame has shaped the labels, every later frame should find them cached.
+    let stats = cx.update(|cx| cx.frame_diagnostics().last_frame().unwrap().text_layouts);
+    assert_eq!(stats.misses, 0, "labels were shaped again: {stats:?}");
+    assert!(stats.hits >= count, "labels weren't laid out: {stats:?}");
+}
+
+criterion_group!(benches, text_layout_benchmark);
+criterion_main!(benches);
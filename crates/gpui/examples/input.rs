@@ -1,7 +1,6 @@
 use std::ops::Range;
 
 use gpui::*;
-use unicode_segmentation::*;
 
 actions!(
     text_input,
@@ -21,147 +20,59 @@ actions!(
 
 struct TextInput {
     focus_handle: FocusHandle,
-    content: SharedString,
-    selected_range: Range<usize>,
-    selection_reversed: bool,
-    marked_range: Option<Range<usize>>,
+    text: EditableText,
     last_layout: Option<ShapedLine>,
 }
 
 impl TextInput {
     fn left(&mut self, _: &Left, cx: &mut ViewContext<Self>) {
-        if self.selected_range.is_empty() {
-            self.move_to(self.previous_boundary(self.cursor_offset()), cx);
-        } else {
-            self.move_to(self.selected_range.start, cx)
-        }
+        self.text.move_left();
+        cx.notify();
     }
 
     fn right(&mut self, _: &Right, cx: &mut ViewContext<Self>) {
-        if self.selected_range.is_empty() {
-            self.move_to(self.next_boundary(self.selected_range.end), cx);
-        } else {
-            self.move_to(self.selected_range.end, cx)
-        }
+        self.text.move_right();
+        cx.notify();
     }
 
     fn select_left(&mut self, _: &SelectLeft, cx: &mut ViewContext<Self>) {
-        self.select_to(self.previous_boundary(self.cursor_offset()), cx);
+        self.text.select_left();
+        cx.notify();
     }
 
     fn select_right(&mut self, _: &SelectRight, cx: &mut ViewContext<Self>) {
-        self.select_to(self.next_boundary(self.cursor_offset()), cx);
+        self.text.select_right();
+        cx.notify();
     }
 
     fn select_all(&mut self, _: &SelectAll, cx: &mut ViewContext<Self>) {
-        self.move_to(0, cx);
-        self.select_to(self.content.len(), cx)
+        self.text.select_all();
+        cx.notify();
     }
 
     fn home(&mut self, _: &Home, cx: &mut ViewContext<Self>) {
-        self.move_to(0, cx);
+        self.text.move_to(0);
+        cx.notify();
     }
 
     fn end(&mut self, _: &End, cx: &mut ViewContext<Self>) {
-        self.move_to(self.content.len(), cx);
+        self.text.move_to(self.text.content().len());
+        cx.notify();
     }
 
     fn backspace(&mut self, _: &Backspace, cx: &mut ViewContext<Self>) {
-        if self.selected_range.is_empty() {
-            self.select_to(self.previous_boundary(self.cursor_offset()), cx)
-        }
-        self.replace_text_in_range(None, "", cx)
+        self.text.backspace();
+        cx.notify();
     }
 
     fn delete(&mut self, _: &Delete, cx: &mut ViewContext<Self>) {
-        if self.selected_range.is_empty() {
-            self.select_to(self.next_boundary(self.cursor_offset()), cx)
-        }
-        self.replace_text_in_range(None, "", cx)
+        self.text.delete();
+        cx.notify();
     }
 
     fn show_character_palette(&mut self, _: &ShowCharacterPalette, cx: &mut ViewContext<Self>) {
         cx.show_character_palette();
     }
-
-    fn move_to(&mut self, offset: usize, cx: &mut ViewContext<Self>) {
-        self.selected_range = offset..offset;
-        cx.notify()
-    }
-
-    fn cursor_offset(&self) -> usize {
-        if self.selection_reversed {
-            self.selected_range.start
-        } else {
-            self.selected_range.end
-        }
-    }
-
-    fn select_to(&mut self, offset: usize, cx: &mut ViewContext<Self>) {
-        if self.selection_reversed {
-            self.selected_range.start = offset
-        } else {
-            self.selected_range.end = offset
-        };
-        if self.selected_range.end < self.selected_range.start {
-            self.selection_reversed = !self.selection_reversed;
-            self.selected_range = self.selected_range.end..self.selected_range.start;
-        }
-        cx.notify()
-    }
-
-    fn offset_from_utf16(&self, offset: usize) -> usize {
-        let mut utf8_offset = 0;
-        let mut utf16_count = 0;
-
-        for ch in self.content.chars() {
-            if utf16_count >= offset {
-                break;
-            }
-            utf16_count += ch.len_utf16();
-            utf8_offset += ch.len_utf8();
-        }
-
-        utf8_offset
-    }
-
-    fn offset_to_utf16(&self, offset: usize) -> usize {
-        let mut utf16_offset = 0;
-        let mut utf8_count = 0;
-
-        for ch in self.content.chars() {
-            if utf8_count >= offset {
-                break;
-            }
-            utf8_count += ch.len_utf8();
-            utf16_offset += ch.len_utf16();
-        }
-
-        utf16_offset
-    }
-
-    fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
-        self.offset_to_utf16(range.start)..self.offset_to_utf16(range.end)
-    }
-
-    fn range_from_utf16(&self, range_utf16: &Range<usize>) -> Range<usize> {
-        self.offset_from_utf16(range_utf16.start)..self.offset_from_utf16(range_utf16.end)
-    }
-
-    fn previous_boundary(&self, offset: usize) -> usize {
-        self.content
-            .grapheme_indices(true)
-            .rev()
-            .find_map(|(idx, _)| (idx < offset).then_some(idx))
-            .unwrap_or(0)
-    }
-
-    fn next_boundary(&self, offset: usize) -> usize {
-        self.content
-            .grapheme_indices(true)
-            .find_map(|(idx, _)| (idx > offset).then_some(idx))
-            .unwrap_or(self.content.len())
-    }
 }
 
 impl ViewInputHandler for TextInput {
@@ -170,22 +81,19 @@ impl ViewInputHandler for TextInput {
         range_utf16: Range<usize>,
         _cx: &mut ViewContext<Self>,
     ) -> Option<String> {
-        let range = self.range_from_utf16(&range_utf16);
-        Some(self.content[range].to_string())
+        Some(self.text.text_for_range(range_utf16))
     }
 
     fn selected_text_range(&mut self, _cx: &mut ViewContext<Self>) -> Option<Range<usize>> {
-        Some(self.range_to_utf16(&self.selected_range))
+        Some(self.text.selected_range_utf16())
     }
 
     fn marked_text_range(&self, _cx: &mut ViewContext<Self>) -> Option<Range<usize>> {
-        self.marked_range
-            .as_ref()
-            .map(|range| self.range_to_utf16(range))
+        self.text.marked_range_utf16()
     }
 
     fn unmark_text(&mut self, _cx: &mut ViewContext<Self>) {
-        self.marked_range = None;
+        self.text.unmark_text();
     }
 
     fn replace_text_in_range(
@@ -194,17 +102,7 @@ impl ViewInputHandler for TextInput {
         new_text: &str,
         cx: &mut ViewContext<Self>,
     ) {
-        let range = range_utf16
-            .as_ref()
-            .map(|range_utf16| self.range_from_utf16(range_utf16))
-            .or(self.marked_range.clone())
-            .unwrap_or(self.selected_range.clone());
-
-        self.content =
-            (self.content[0..range.start].to_owned() + new_text + &self.content[range.end..])
-                .into();
-        self.selected_range = range.start + new_text.len()..range.start + new_text.len();
-        self.marked_range.take();
+        self.text.replace_text_in_range(range_utf16, new_text);
         cx.notify();
     }
 
@@ -215,22 +113,8 @@ impl ViewInputHandler for TextInput {
         new_selected_range_utf16: Option<Range<usize>>,
         cx: &mut ViewContext<Self>,
     ) {
-        let range = range_utf16
-            .as_ref()
-            .map(|range_utf16| self.range_from_utf16(range_utf16))
-            .or(self.marked_range.clone())
-            .unwrap_or(self.selected_range.clone());
-
-        self.content =
-            (self.content[0..range.start].to_owned() + new_text + &self.content[range.end..])
-                .into();
-        self.marked_range = Some(range.start..range.start + new_text.len());
-        self.selected_range = new_selected_range_utf16
-            .as_ref()
-            .map(|range_utf16| self.range_from_utf16(range_utf16))
-            .map(|new_range| new_range.start + range.start..new_range.end + range.end)
-            .unwrap_or_else(|| range.start + new_text.len()..range.start + new_text.len());
-
+        self.text
+            .replace_and_mark_text_in_range(range_utf16, new_text, new_selected_range_utf16);
         cx.notify();
     }
 
@@ -243,7 +127,7 @@ impl ViewInputHandler for TextInput {
         let Some(last_layout) = self.last_layout.as_ref() else {
             return None;
         };
-        let range = self.range_from_utf16(&range_utf16);
+        let range = self.text.range_from_utf16(&range_utf16);
         Some(Bounds::from_corners(
             point(
                 bounds.left() + last_layout.x_for_index(range.start),
@@ -303,19 +187,19 @@ impl Element for TextElement {
         cx: &mut WindowContext,
     ) -> Self::PrepaintState {
         let input = self.input.read(cx);
-        let content = input.content.clone();
-        let selected_range = input.selected_range.clone();
-        let cursor = input.cursor_offset();
+        let content = input.text.content().clone();
+        let selected_range = input.text.selected_range();
+        let cursor = input.text.cursor_offset();
         let style = cx.text_style();
         let run = TextRun {
-            len: input.content.len(),
+            len: content.len(),
             font: style.font(),
             color: style.color,
             background_color: None,
             underline: None,
             strikethrough: None,
         };
-        let runs = if let Some(marked_range) = input.marked_range.as_ref() {
+        let runs = if let Some(marked_range) = input.text.marked_range() {
             vec![
                 TextRun {
                     len: marked_range.start,
@@ -331,7 +215,7 @@ impl Element for TextElement {
                     ..run.clone()
                 },
                 TextRun {
-                    len: input.content.len() - marked_range.end,
+                    len: content.len() - marked_range.end,
                     ..run.clone()
                 },
             ]
@@ -470,10 +354,7 @@ fn main() {
                 |cx| {
                     cx.new_view(|cx| TextInput {
                         focus_handle: cx.focus_handle(),
-                        content: "".into(),
-                        selected_range: 0..0,
-                        selection_reversed: false,
-                        marked_range: None,
+                        text: EditableText::default(),
                         last_layout: None,
                     })
                 },
@@ -1,11 +1,11 @@
 use crate::{
-    Action, AnyView, AnyWindowHandle, AppCell, AppContext, AsyncAppContext, AvailableSpace,
-    BackgroundExecutor, BorrowAppContext, Bounds, ClipboardItem, Context, DrawPhase, Drawable,
-    Element, Empty, Entity, EventEmitter, ForegroundExecutor, Global, InputEvent, Keystroke, Model,
-    ModelContext, Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent,
-    MouseUpEvent, Pixels, Platform, Point, Render, Result, Size, Task, TestDispatcher,
-    TestPlatform, TestWindow, TextSystem, View, ViewContext, VisualContext, WindowBounds,
-    WindowContext, WindowHandle, WindowOptions,
+    Action, AnyView, AnyWindowHandle, AppCell, AppContext, AssetSource, AsyncAppContext,
+    AvailableSpace, BackgroundExecutor, BorrowAppContext, Bounds, ClipboardItem, Context,
+    DrawPhase, Drawable, Edges, Element, Empty, Entity, EventEmitter, ForegroundExecutor, Global,
+    InputEvent, Keystroke, Model, ModelContext, Modifiers, ModifiersChangedEvent, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Platform, Point, Render, Result, Size,
+    SvgRenderer, Task, TestDispatcher, TestPlatform, TestWindow, TextSystem, View, ViewContext,
+    VisualContext, WindowBounds, WindowContext, WindowHandle, WindowOptions,
 };
 use anyhow::{anyhow, bail};
 use futures::{channel::oneshot, Stream, StreamExt};
@@ -129,6 +129,14 @@ impl TestAppContext {
         self.test_platform.did_prompt_for_new_path()
     }
 
+    /// Load the app's assets from the given source, in place of the empty one tests start with.
+    pub fn set_asset_source(&mut self, asset_source: impl AssetSource) {
+        let mut cx = self.app.borrow_mut();
+        let asset_source = Arc::new(asset_source);
+        cx.asset_source = asset_source.clone();
+        cx.svg_renderer = SvgRenderer::new(asset_source);
+    }
+
     /// returns a new `TestAppContext` re-using the same executors to interleave tasks.
     pub fn new_app(&self) -> TestAppContext {
         Self::new(self.dispatcher.clone(), self.fn_name)
@@ -287,6 +295,18 @@ impl TestAppContext {
         self.test_window(window_handle).simulate_resize(size);
     }
 
+    /// Simulates the given window moving to a display with a different scale factor.
+    pub fn simulate_window_scale_factor(&self, window_handle: AnyWindowHandle, scale_factor: f32) {
+        self.test_window(window_handle)
+            .simulate_scale_factor(scale_factor);
+    }
+
+    /// Simulates the platform changing the insets of the given window's content area.
+    pub fn simulate_window_insets(&self, window_handle: AnyWindowHandle, insets: Edges<Pixels>) {
+        self.test_window(window_handle)
+            .simulate_window_insets(insets);
+    }
+
     /// Returns all windows open in the test.
     pub fn windows(&self) -> Vec<AnyWindowHandle> {
         self.app.borrow().windows().clone()
@@ -762,11 +782,41 @@ impl VisualTestContext {
         self.simulate_window_resize(self.window, size)
     }
 
+    /// Simulates the window moving to a display with a different scale factor.
+    pub fn simulate_scale_factor(&self, scale_factor: f32) {
+        self.cx
+            .simulate_window_scale_factor(self.window, scale_factor)
+    }
+
+    /// Simulates the platform changing the insets of the window's content area, as happens when
+    /// entering or exiting fullscreen.
+    pub fn simulate_window_insets(&self, insets: Edges<Pixels>) {
+        self.cx.simulate_window_insets(self.window, insets)
+    }
+
     /// debug_bounds returns the bounds of the element with the given selector.
     pub fn debug_bounds(&mut self, selector: &'static str) -> Option<Bounds<Pixels>> {
         self.update(|cx| cx.window.rendered_frame.debug_bounds.get(selector).copied())
     }
 
+    /// Returns whether anything drawn in the window asked to be updated on the next frame,
+    /// as running animations do.
+    pub fn has_pending_frame_callbacks(&mut self) -> bool {
+        self.update(|cx| !cx.window.next_frame_callbacks.borrow().is_empty())
+    }
+
+    /// Simulates the platform requesting a new frame, running the callbacks registered with
+    /// `on_next_frame` and redrawing the window if that made it dirty.
+    pub fn simulate_frame(&mut self) {
+        self.update(|cx| {
+            let callbacks = cx.window.next_frame_callbacks.take();
+            for callback in callbacks {
+                callback(cx);
+            }
+        });
+        self.background_executor.run_until_parked();
+    }
+
     /// Draw an element to the window. Useful for simulating events or actions
     pub fn draw<E>(
         &mut self,
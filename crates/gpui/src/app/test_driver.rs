@@ -0,0 +1,317 @@
+use itertools::Itertools;
+
+use crate::{
+    point, Bounds, ElementId, GlobalElementId, Modifiers, MouseButton, Pixels, Point, ScrollDelta,
+    ScrollWheelEvent, VisualTestContext, WindowContext,
+};
+
+/// The number of moves a drag is made of between where it starts and where it ends.
+const DRAG_STEPS: usize = 8;
+
+/// How many times the driver scrolls toward an element before giving up on bringing it into view.
+const MAX_SCROLL_ATTEMPTS: usize = 16;
+
+/// Drives a window the way a user would, addressing elements by their ids instead of by positions.
+/// Each step finds its target among the interactive elements of the last frame drawn, scrolling it
+/// into view first if it's been scrolled out of sight, sends the events the platform would send,
+/// and draws the frames they cause before it returns.
+///
+/// Ids are matched against the last component of the elements' global ids, e.g. an element built
+/// with `.id("save")` anywhere in the window is found with `driver.click("save")`.
+pub struct Driver<'a> {
+    cx: &'a mut VisualTestContext,
+}
+
+impl VisualTestContext {
+    /// A [`Driver`] for this window.
+    pub fn driver(&mut self) -> Driver<'_> {
+        Driver { cx: self }
+    }
+}
+
+impl<'a> Driver<'a> {
+    /// Move the mouse to the element with the given id and click it.
+    #[track_caller]
+    pub fn click(&mut self, id: impl Into<ElementId>) -> &mut Self {
+        let position = self.target(&id.into());
+        self.cx
+            .simulate_mouse_move(position, None, Modifiers::none());
+        self.cx.simulate_click(position, Modifiers::none());
+        self.pump()
+    }
+
+    /// Move the mouse over the element with the given id.
+    #[track_caller]
+    pub fn hover(&mut self, id: impl Into<ElementId>) -> &mut Self {
+        let position = self.target(&id.into());
+        self.cx
+            .simulate_mouse_move(position, None, Modifiers::none());
+        self.pump()
+    }
+
+    /// Press the mouse on one element, move it across to another in several steps and release it
+    /// there.
+    #[track_caller]
+    pub fn drag(&mut self, from: impl Into<ElementId>, to: impl Into<ElementId>) -> &mut Self {
+        let start = self.target(&from.into());
+        self.cx.simulate_mouse_move(start, None, Modifiers::none());
+        self.cx
+            .simulate_mouse_down(start, MouseButton::Left, Modifiers::none());
+        self.pump();
+
+        let end = self.target(&to.into());
+        for step in 1..=DRAG_STEPS {
+            let progress = step as f32 / DRAG_STEPS as f32;
+            let position = start + (end - start) * progress;
+            self.cx
+                .simulate_mouse_move(position, MouseButton::Left, Modifiers::none());
+            self.pump();
+        }
+        self.cx
+            .simulate_mouse_up(end, MouseButton::Left, Modifiers::none());
+        self.pump()
+    }
+
+    /// Type the given text into the focused element.
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        self.cx.simulate_input(text);
+        self.pump()
+    }
+
+    /// Press the given keystrokes, separated by spaces, e.g. `"cmd-shift-p"` or `"down enter"`.
+    pub fn press(&mut self, keystrokes: &str) -> &mut Self {
+        self.cx.simulate_keystrokes(keystrokes);
+        self.pump()
+    }
+
+    /// The visible bounds of the element with the given id, scrolling it into view first.
+    #[track_caller]
+    pub fn bounds(&mut self, id: impl Into<ElementId>) -> Bounds<Pixels> {
+        let id = id.into();
+        self.target(&id);
+        match self.cx.update(|cx| resolve(cx, &id)) {
+            Ok(Resolved::Visible(bounds)) => bounds,
+            Ok(Resolved::OutOfView { .. }) => unreachable!(),
+            Err(message) => panic!("{message}"),
+        }
+    }
+
+    /// Draw the frames the last step caused, and run the work they started.
+    fn pump(&mut self) -> &mut Self {
+        self.cx.simulate_frame();
+        self
+    }
+
+    /// Where to point the mouse at the element with the given id, once it's been scrolled into
+    /// view.
+    #[track_caller]
+    fn target(&mut self, id: &ElementId) -> Point<Pixels> {
+        for _ in 0..MAX_SCROLL_ATTEMPTS {
+            match self.cx.update(|cx| resolve(cx, id)) {
+                Ok(Resolved::Visible(bounds)) => return bounds.center(),
+                Ok(Resolved::OutOfView { position, delta }) => {
+                    self.cx
+                        .simulate_mouse_move(position, None, Modifiers::none());
+                    self.cx.simulate_event(ScrollWheelEvent {
+                        position,
+                        delta: ScrollDelta::Pixels(delta),
+                        ..Default::default()
+                    });
+                    self.pump();
+                }
+                Err(message) => panic!("{message}"),
+            }
+        }
+        panic!("`{id}` couldn't be scrolled into view");
+    }
+}
+
+enum Resolved {
+    /// The part of the element that can be seen and clicked.
+    Visible(Bounds<Pixels>),
+    /// The element is out of view, but scrolling by the given delta over the given position should
+    /// bring it closer.
+    OutOfView {
+        position: Point<Pixels>,
+        delta: Point<Pixels>,
+    },
+}
+
+/// Find the interactive element with the given id in the rendered frame.
+fn resolve(cx: &WindowContext, id: &ElementId) -> Result<Resolved, String> {
+    let frame = &cx.window.rendered_frame;
+    let viewport = Bounds::new(Point::default(), cx.viewport_size());
+    let visible_bounds = |element_id: &GlobalElementId| {
+        let region = frame
+            .interactive_regions
+            .iter()
+            .find(|region| region.element_id == *element_id)?;
+        let hitbox = frame
+            .hitboxes
+            .iter()
+            .find(|hitbox| hitbox.id == region.hitbox_id)?;
+        let visible = hitbox
+            .bounds
+            .intersect(&hitbox.content_mask.bounds)
+            .intersect(&viewport);
+        Some((hitbox.bounds, visible))
+    };
+
+    let matches = frame
+        .interactive_regions
+        .iter()
+        .filter(|region| region.element_id.last() == Some(id))
+        .map(|region| &region.element_id)
+        .collect::<Vec<_>>();
+    let element_id = match matches.as_slice() {
+        [element_id] => *element_id,
+        [] => {
+            let exists = frame
+                .element_states
+                .keys()
+                .any(|(element_id, _)| element_id.last() == Some(id));
+            let problem = if exists {
+                "isn't interactive"
+            } else {
+                "doesn't exist"
+            };
+            return Err(format!(
+                "the element `{id}` {problem} in the last frame. The interactive elements are:\n{}",
+                frame
+                    .interactive_regions
+                    .iter()
+                    .map(|region| format!("  {}", path(&region.element_id)))
+                    .join("\n")
+            ));
+        }
+        _ => {
+            return Err(format!(
+                "more than one element has the id `{id}` in the last frame:\n{}",
+                matches
+                    .iter()
+                    .map(|element_id| format!("  {}", path(element_id)))
+                    .join("\n")
+            ))
+        }
+    };
+
+    let Some((bounds, visible)) = visible_bounds(element_id) else {
+        return Err(format!("the element `{}` has no hitbox", path(element_id)));
+    };
+    if !visible.is_empty() {
+        return Ok(Resolved::Visible(visible));
+    }
+
+    // Scroll over the innermost ancestor that can be seen, which is within the scroll container
+    // that's hiding the element.
+    let ancestor = frame
+        .interactive_regions
+        .iter()
+        .filter(|region| {
+            region.element_id.len() < element_id.len() && element_id.starts_with(&region.element_id)
+        })
+        .filter_map(|region| Some(visible_bounds(&region.element_id)?.1))
+        .filter(|visible| !visible.is_empty())
+        .last();
+    let Some(ancestor) = ancestor else {
+        return Err(format!(
+            "the element `{}` is out of view, and isn't in a scroll container that's in view",
+            path(element_id)
+        ));
+    };
+    let delta = point(
+        scroll_delta(
+            bounds.left(),
+            bounds.right(),
+            ancestor.left(),
+            ancestor.right(),
+        ),
+        scroll_delta(
+            bounds.top(),
+            bounds.bottom(),
+            ancestor.top(),
+            ancestor.bottom(),
+        ),
+    );
+    if delta == Point::default() {
+        return Err(format!(
+            "the element `{}` is clipped out of view",
+            path(element_id)
+        ));
+    }
+    Ok(Resolved::OutOfView {
+        position: ancestor.center(),
+        delta,
+    })
+}
+
+/// How far to scroll along one axis to move the range from `start` to `end` within the visible
+/// range from `visible_start` to `visible_end`.
+fn scroll_delta(start: Pixels, end: Pixels, visible_start: Pixels, visible_end: Pixels) -> Pixels {
+    if end > visible_end {
+        (visible_end - end).max(visible_start - start)
+    } else if start < visible_start {
+        visible_start - start
+    } else {
+        Pixels::ZERO
+    }
+}
+
+fn path(element_id: &GlobalElementId) -> String {
+    element_id.iter().join(" > ")
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{self as gpui, div, prelude::*, px, TestAppContext, ViewContext};
+
+    struct List {
+        clicked: Rc<RefCell<Vec<usize>>>,
+    }
+
+    impl Render for List {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("list")
+                .h(px(100.))
+                .overflow_y_scroll()
+                .children((0..20).map(|ix| {
+                    let clicked = self.clicked.clone();
+                    div()
+                        .id(("item", ix))
+                        .h(px(20.))
+                        .on_click(move |_, _| clicked.borrow_mut().push(ix))
+                }))
+        }
+    }
+
+    fn add_list(
+        cx: &mut TestAppContext,
+    ) -> (&mut gpui::VisualTestContext, Rc<RefCell<Vec<usize>>>) {
+        let clicked = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view({
+            let clicked = clicked.clone();
+            |_| List { clicked }
+        });
+        cx.run_until_parked();
+        (cx, clicked)
+    }
+
+    #[gpui::test]
+    fn test_elements_are_scrolled_into_view_before_they_are_clicked(cx: &mut TestAppContext) {
+        let (cx, clicked) = add_list(cx);
+        cx.driver().click(("item", 1usize)).click(("item", 15usize));
+        assert_eq!(*clicked.borrow(), [1, 15]);
+        let bounds = cx.driver().bounds(("item", 15usize));
+        assert!(bounds.top() >= px(0.) && bounds.bottom() <= px(100.));
+    }
+
+    #[gpui::test]
+    #[should_panic(expected = "the element `missing` doesn't exist in the last frame")]
+    fn test_missing_elements_are_reported(cx: &mut TestAppContext) {
+        let (cx, _) = add_list(cx);
+        cx.driver().click("missing");
+    }
+}
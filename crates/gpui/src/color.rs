@@ -451,6 +451,71 @@ impl Hsla {
     pub fn fade_out(&mut self, factor: f32) {
         self.a *= 1.0 - factor.clamp(0., 1.);
     }
+
+    /// Returns a copy of the color with its alpha multiplied by the given factor, which should be
+    /// between 0.0 and 1.0.
+    pub fn opacity(&self, factor: f32) -> Self {
+        Hsla {
+            a: self.a * factor.clamp(0., 1.),
+            ..*self
+        }
+    }
+
+    /// Returns a lighter version of the color, with the given amount (between 0.0 and 1.0) added
+    /// to its perceptual lightness.
+    ///
+    /// The lightness is adjusted in the Oklab color space, so the same amount produces a similar
+    /// visual difference regardless of the hue of the color. Colors that would fall outside of
+    /// the sRGB gamut lose some of their saturation instead.
+    pub fn lighten(&self, amount: f32) -> Self {
+        let mut color = Oklab::from(Rgba::from(*self));
+        color.l = (color.l + amount as f64).clamp(0., 1.);
+        Hsla::from(Rgba::from(color))
+    }
+
+    /// Returns a darker version of the color, with the given amount (between 0.0 and 1.0)
+    /// removed from its perceptual lightness. See [`Hsla::lighten`].
+    pub fn darken(&self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Composites this color over the given background, blending in linear light.
+    ///
+    /// Unlike [`Hsla::blend`], the result takes the alpha of both colors into account, so it is
+    /// only opaque if one of them is.
+    pub fn blend_over(&self, background: Hsla) -> Self {
+        let foreground = Rgba::from(*self);
+        let background = Rgba::from(background);
+
+        let a = foreground.a + background.a * (1. - foreground.a);
+        if a <= 0. {
+            return transparent_black();
+        }
+
+        let background_weight = background.a * (1. - foreground.a);
+        let blend = |foreground_component: f32, background_component: f32| {
+            let linear = (srgb_to_linear(foreground_component) * foreground.a
+                + srgb_to_linear(background_component) * background_weight)
+                / a;
+            linear_to_srgb(linear)
+        };
+
+        Hsla::from(Rgba {
+            r: blend(foreground.r, background.r),
+            g: blend(foreground.g, background.g),
+            b: blend(foreground.b, background.b),
+            a,
+        })
+    }
+
+    /// The relative luminance of the color as defined by WCAG, from 0.0 for black to 1.0 for
+    /// white. The alpha of the color is ignored.
+    pub fn relative_luminance(&self) -> f32 {
+        let color = Rgba::from(*self);
+        0.2126 * srgb_to_linear(color.r)
+            + 0.7152 * srgb_to_linear(color.g)
+            + 0.0722 * srgb_to_linear(color.b)
+    }
 }
 
 impl From<Rgba> for Hsla {
@@ -504,6 +569,146 @@ impl<'de> Deserialize<'de> for Hsla {
     }
 }
 
+/// The contrast ratio that WCAG requires between normal text and its background.
+pub const MIN_TEXT_CONTRAST_RATIO: f32 = 4.5;
+
+/// Returns the WCAG contrast ratio between two colors, from 1.0 for identical luminance to 21.0
+/// for black on white.
+///
+/// Alpha is ignored, so translucent colors should first be composited over whatever they are
+/// drawn on with [`Hsla::blend_over`].
+pub fn contrast_ratio(a: Hsla, b: Hsla) -> f32 {
+    let a = a.relative_luminance();
+    let b = b.relative_luminance();
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Picks a text color that is readable on the given background.
+///
+/// Returns the first of the candidates that meets [`MIN_TEXT_CONTRAST_RATIO`], or the one with
+/// the most contrast if none of them do. Without candidates, black or white is picked.
+pub fn pick_readable_text(background: Hsla, candidates: &[Hsla]) -> Hsla {
+    if candidates.is_empty() {
+        return pick_readable_text(background, &[black(), white()]);
+    }
+
+    candidates
+        .iter()
+        .copied()
+        .find(|candidate| contrast_ratio(*candidate, background) >= MIN_TEXT_CONTRAST_RATIO)
+        .unwrap_or_else(|| {
+            candidates
+                .iter()
+                .copied()
+                .max_by(|a, b| {
+                    contrast_ratio(*a, background).total_cmp(&contrast_ratio(*b, background))
+                })
+                .unwrap()
+        })
+}
+
+fn srgb_to_linear(component: f32) -> f32 {
+    if component <= 0.04045 {
+        component / 12.92
+    } else {
+        ((component + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(component: f32) -> f32 {
+    if component <= 0.0031308 {
+        component * 12.92
+    } else {
+        1.055 * component.powf(1. / 2.4) - 0.055
+    }
+}
+
+/// A color in the Oklab color space, where distances match perceived differences.
+/// See <https://bottosson.github.io/posts/oklab/>.
+#[derive(Clone, Copy, Debug)]
+struct Oklab {
+    l: f64,
+    a: f64,
+    b: f64,
+    alpha: f32,
+}
+
+impl From<Rgba> for Oklab {
+    fn from(color: Rgba) -> Self {
+        let r = srgb_to_linear(color.r) as f64;
+        let g = srgb_to_linear(color.g) as f64;
+        let b = srgb_to_linear(color.b) as f64;
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        Oklab {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+            alpha: color.a,
+        }
+    }
+}
+
+impl Oklab {
+    fn to_linear_rgb(self) -> [f64; 3] {
+        let l = (self.l + 0.3963377774 * self.a + 0.2158037573 * self.b).powi(3);
+        let m = (self.l - 0.1055613458 * self.a - 0.0638541728 * self.b).powi(3);
+        let s = (self.l - 0.0894841775 * self.a - 1.2914855480 * self.b).powi(3);
+
+        [
+            4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+            -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+            -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+        ]
+    }
+
+    fn is_in_srgb_gamut(self) -> bool {
+        const EPSILON: f64 = 1e-4;
+        self.to_linear_rgb()
+            .iter()
+            .all(|component| (-EPSILON..=1. + EPSILON).contains(component))
+    }
+
+    /// Brings the color into the sRGB gamut by reducing its chroma, keeping its lightness and
+    /// hue.
+    fn map_to_srgb_gamut(self) -> Self {
+        if self.is_in_srgb_gamut() {
+            return self;
+        }
+
+        let with_chroma = |scale: f64| Oklab {
+            a: self.a * scale,
+            b: self.b * scale,
+            ..self
+        };
+        let (mut low, mut high) = (0., 1.);
+        for _ in 0..16 {
+            let mid = (low + high) / 2.;
+            if with_chroma(mid).is_in_srgb_gamut() {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        with_chroma(low)
+    }
+}
+
+impl From<Oklab> for Rgba {
+    fn from(color: Oklab) -> Self {
+        let [r, g, b] = color.map_to_srgb_gamut().to_linear_rgb();
+        Rgba {
+            r: linear_to_srgb(r.clamp(0., 1.) as f32),
+            g: linear_to_srgb(g.clamp(0., 1.) as f32),
+            b: linear_to_srgb(b.clamp(0., 1.) as f32),
+            a: color.alpha,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -551,4 +756,111 @@ mod tests {
 
         assert_eq!(actual, rgba(0xdeadbeef))
     }
+
+    #[track_caller]
+    fn assert_approx_eq(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[track_caller]
+    fn assert_colors_approx_eq(actual: Hsla, expected: Hsla) {
+        let (actual, expected) = (Rgba::from(actual), Rgba::from(expected));
+        for (actual, expected) in [
+            (actual.r, expected.r),
+            (actual.g, expected.g),
+            (actual.b, expected.b),
+            (actual.a, expected.a),
+        ] {
+            assert_approx_eq(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert_approx_eq(contrast_ratio(black(), white()), 21.);
+        assert_approx_eq(contrast_ratio(white(), black()), 21.);
+        assert_approx_eq(contrast_ratio(white(), white()), 1.);
+
+        // #767676 is the lightest grey that meets the WCAG AA ratio on white.
+        assert_approx_eq(contrast_ratio(rgb(0x767676).into(), white()), 4.54);
+        assert_approx_eq(contrast_ratio(rgb(0x777777).into(), white()), 4.48);
+        assert_approx_eq(contrast_ratio(rgb(0xff0000).into(), white()), 4.0);
+        assert_approx_eq(contrast_ratio(rgb(0x0000ff).into(), white()), 8.59);
+        assert_approx_eq(contrast_ratio(rgb(0x00ff00).into(), black()), 15.3);
+    }
+
+    #[test]
+    fn test_pick_readable_text() {
+        let dark_grey = Hsla::from(rgb(0x767676));
+        let light_grey = Hsla::from(rgb(0x777777));
+
+        assert_eq!(
+            pick_readable_text(white(), &[dark_grey, black()]),
+            dark_grey
+        );
+        assert_eq!(pick_readable_text(white(), &[light_grey, black()]), black());
+
+        // When no candidate is readable enough, the one with the most contrast is used.
+        let candidates = [rgb(0x333333).into(), rgb(0x444444).into()];
+        assert_eq!(pick_readable_text(black(), &candidates), candidates[1]);
+
+        assert_eq!(pick_readable_text(rgb(0x1a237e).into(), &[]), white());
+        assert_eq!(pick_readable_text(rgb(0xfff59d).into(), &[]), black());
+    }
+
+    #[test]
+    fn test_lighten_and_darken() {
+        let color = Hsla::from(rgb(0x3366cc));
+        assert_colors_approx_eq(color.lighten(0.), color);
+        assert_colors_approx_eq(color.lighten(1.), white());
+        assert_colors_approx_eq(color.darken(1.), black());
+
+        let lighter = color.lighten(0.08);
+        let darker = color.darken(0.08);
+        assert!(lighter.relative_luminance() > color.relative_luminance());
+        assert!(darker.relative_luminance() < color.relative_luminance());
+        assert_approx_eq(lighter.h, color.h);
+
+        // The same amount is a similar perceived change, whatever the hue: lightening
+        // doesn't push yellow, which is already perceptually light, as far as blue.
+        let yellow = Hsla::from(rgb(0x999900));
+        let blue = Hsla::from(rgb(0x000099));
+        let yellow_change = yellow.lighten(0.1).relative_luminance() / yellow.relative_luminance();
+        let blue_change = blue.lighten(0.1).relative_luminance() / blue.relative_luminance();
+        assert!(yellow_change < blue_change);
+
+        let translucent = color.opacity(0.4);
+        assert_approx_eq(translucent.a, 0.4);
+        assert_approx_eq(translucent.lighten(0.1).a, 0.4);
+    }
+
+    #[test]
+    fn test_blend_over() {
+        let red = Hsla::from(rgb(0xff0000));
+        assert_colors_approx_eq(red.blend_over(white()), red);
+        assert_colors_approx_eq(transparent_black().blend_over(red), red);
+
+        // Blending in linear light makes half-transparent white over black lighter than the
+        // sRGB midpoint.
+        let grey = white().opacity(0.5).blend_over(black());
+        assert_colors_approx_eq(
+            grey,
+            Rgba {
+                r: 0.735,
+                g: 0.735,
+                b: 0.735,
+                a: 1.,
+            }
+            .into(),
+        );
+
+        let blended = white().opacity(0.5).blend_over(black().opacity(0.5));
+        assert_approx_eq(blended.a, 0.75);
+        assert_approx_eq(blended.relative_luminance(), 2. / 3.);
+
+        assert_eq!(transparent_black().blend_over(transparent_black()).a, 0.);
+    }
 }
@@ -88,6 +88,13 @@ pub trait Element: 'static + IntoElement {
         cx: &mut WindowContext,
     );
 
+    /// Whether painting this element can be skipped when its bounds are entirely clipped out of
+    /// view. Elements that paint outside of their bounds return false, as do wrappers that share
+    /// their layout with a child, leaving the decision to the child.
+    fn cullable(&self) -> bool {
+        true
+    }
+
     /// Convert this element into a dynamically-typed [`AnyElement`].
     fn into_any(self) -> AnyElement {
         AnyElement::new(self)
@@ -159,6 +166,26 @@ pub trait ParentElement {
         self.extend(children.into_iter().map(|child| child.into_any_element()));
         self
     }
+
+    /// Add a single child element that has already been converted into an [`AnyElement`], e.g.
+    /// when each arm of a `match` produces a different element type.
+    fn child_any(mut self, child: AnyElement) -> Self
+    where
+        Self: Sized,
+    {
+        self.extend(std::iter::once(child));
+        self
+    }
+
+    /// Add multiple child elements that have already been converted into [`AnyElement`]s,
+    /// e.g. when they are collected from several sources with different element types.
+    fn children_any(mut self, children: impl IntoIterator<Item = AnyElement>) -> Self
+    where
+        Self: Sized,
+    {
+        self.extend(children);
+        self
+    }
 }
 
 /// An element for rendering components. An implementation detail of the [`IntoElement`] derive macro
@@ -211,6 +238,10 @@ impl<C: RenderOnce> Element for Component<C> {
     ) {
         element.paint(cx)
     }
+
+    fn cullable(&self) -> bool {
+        false
+    }
 }
 
 impl<C: RenderOnce> IntoElement for Component<C> {
@@ -222,12 +253,14 @@ impl<C: RenderOnce> IntoElement for Component<C> {
 }
 
 /// A globally unique identifier for an element, used to track state across frames.
-#[derive(Deref, DerefMut, Default, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Deref, DerefMut, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct GlobalElementId(pub(crate) SmallVec<[ElementId; 32]>);
 
 trait ElementObject {
     fn inner_element(&mut self) -> &mut dyn Any;
 
+    fn element_id(&self) -> Option<ElementId>;
+
     fn request_layout(&mut self, cx: &mut WindowContext) -> LayoutId;
 
     fn prepaint(&mut self, cx: &mut WindowContext);
@@ -324,13 +357,24 @@ impl<E: Element> Drawable<E> {
                 if let Some(element_id) = self.element.id() {
                     cx.window.element_id_stack.push(element_id);
                     debug_assert_eq!(global_id.as_ref().unwrap().0, cx.window.element_id_stack);
+                    #[cfg(debug_assertions)]
+                    cx.register_element_id(std::any::type_name::<E>());
                 }
 
                 let bounds = cx.layout_bounds(layout_id);
                 let node_id = cx.window.next_frame.dispatch_tree.push_node();
+                // Tracked during prepaint too, so that deferred draws know which element deferred
+                // them.
+                let begun = cx
+                    .window
+                    .frame_diagnostics
+                    .begin_element(std::any::type_name::<E>(), global_id.as_ref());
                 let prepaint =
                     self.element
                         .prepaint(global_id.as_ref(), bounds, &mut request_layout, cx);
+                if begun {
+                    cx.window.frame_diagnostics.end_element();
+                }
                 cx.window.next_frame.dispatch_tree.pop_node();
 
                 if global_id.is_some() {
@@ -367,14 +411,26 @@ impl<E: Element> Drawable<E> {
                     debug_assert_eq!(global_id.as_ref().unwrap().0, cx.window.element_id_stack);
                 }
 
-                cx.window.next_frame.dispatch_tree.set_active_node(node_id);
-                self.element.paint(
-                    global_id.as_ref(),
-                    bounds,
-                    &mut request_layout,
-                    &mut prepaint,
-                    cx,
-                );
+                if self.element.cullable() && cx.can_cull(node_id, bounds) {
+                    cx.cull_element();
+                } else {
+                    cx.window.frame_diagnostics.record_paint(false);
+                    let begun = cx
+                        .window
+                        .frame_diagnostics
+                        .begin_element(std::any::type_name::<E>(), global_id.as_ref());
+                    cx.window.next_frame.dispatch_tree.set_active_node(node_id);
+                    self.element.paint(
+                        global_id.as_ref(),
+                        bounds,
+                        &mut request_layout,
+                        &mut prepaint,
+                        cx,
+                    );
+                    if begun {
+                        cx.window.frame_diagnostics.end_element();
+                    }
+                }
 
                 if global_id.is_some() {
                     cx.window.element_id_stack.pop();
@@ -444,6 +500,10 @@ where
         &mut self.element
     }
 
+    fn element_id(&self) -> Option<ElementId> {
+        self.element.id()
+    }
+
     fn request_layout(&mut self, cx: &mut WindowContext) -> LayoutId {
         Drawable::request_layout(self, cx)
     }
@@ -485,6 +545,11 @@ impl AnyElement {
         self.0.inner_element().downcast_mut::<T>()
     }
 
+    /// The id of the element stored in this `AnyElement`, if it has one.
+    pub fn element_id(&self) -> Option<ElementId> {
+        self.0.element_id()
+    }
+
     /// Request the layout ID of the element stored in this `AnyElement`.
     /// Used for laying out child elements in a parent element.
     pub fn request_layout(&mut self, cx: &mut WindowContext) -> LayoutId {
@@ -565,6 +630,10 @@ impl Element for AnyElement {
     ) {
         self.paint(cx)
     }
+
+    fn cullable(&self) -> bool {
+        false
+    }
 }
 
 impl IntoElement for AnyElement {
@@ -625,3 +694,220 @@ impl Element for Empty {
     ) {
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as gpui, canvas, div, point, px, size, util::FluentBuilder, Empty, InteractiveElement,
+        IntoElement, ParentElement, Render, ScrollHandle, StatefulInteractiveElement, Styled,
+        TestAppContext, View, ViewContext, VisualTestContext,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    // Elements carry no view type, so a plain function can build one and any view can render it.
+    fn spacer() -> impl IntoElement {
+        div().debug_selector(|| "spacer".into()).size(px(8.))
+    }
+
+    struct ColumnView;
+
+    impl Render for ColumnView {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .debug_selector(|| "container".into())
+                .flex()
+                .flex_col()
+                .child(spacer())
+                .child(spacer())
+        }
+    }
+
+    struct RowView {
+        count: usize,
+    }
+
+    impl Render for RowView {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .debug_selector(|| "container".into())
+                .flex()
+                .flex_row()
+                .children((0..self.count).map(|_| spacer()))
+        }
+    }
+
+    #[gpui::test]
+    fn test_element_helpers_are_view_agnostic(cx: &mut TestAppContext) {
+        let (_, column_cx) = cx.add_window_view(|_| ColumnView);
+        assert_eq!(
+            column_cx.debug_bounds("spacer").unwrap().size,
+            size(px(8.), px(8.))
+        );
+        assert_eq!(
+            column_cx.debug_bounds("container").unwrap().size.height,
+            px(16.)
+        );
+
+        let (_, row_cx) = cx.add_window_view(|_| RowView { count: 3 });
+        assert_eq!(
+            row_cx.debug_bounds("spacer").unwrap().size,
+            size(px(8.), px(8.))
+        );
+        assert_eq!(
+            row_cx.debug_bounds("container").unwrap().size.height,
+            px(8.)
+        );
+    }
+
+    enum Row {
+        Spacer,
+        Label(&'static str),
+        Empty,
+    }
+
+    struct MixedChildrenView {
+        rows: Vec<Row>,
+    }
+
+    impl Render for MixedChildrenView {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let rows = self.rows.iter().map(|row| match row {
+                Row::Spacer => spacer().into_any_element(),
+                Row::Label(text) => div().h(px(8.)).child(*text).into_any_element(),
+                Row::Empty => Empty.into_any_element(),
+            });
+
+            div()
+                .debug_selector(|| "container".into())
+                .flex()
+                .flex_col()
+                .child_any(match self.rows.first() {
+                    Some(Row::Label(text)) => (*text).into_any_element(),
+                    _ => spacer().into_any_element(),
+                })
+                .children_any(rows)
+                .children(vec![spacer(), spacer()])
+                .children([spacer()])
+                .children(Some(spacer()))
+                .children(None::<Empty>)
+        }
+    }
+
+    #[gpui::test]
+    fn test_mixed_children(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| MixedChildrenView {
+            rows: vec![Row::Spacer, Row::Label("label"), Row::Empty],
+        });
+
+        // One leading spacer, a spacer and a label from the rows, and four trailing spacers.
+        assert_eq!(
+            cx.debug_bounds("container").unwrap().size.height,
+            px(8. * 7.)
+        );
+    }
+
+    /// A row that records its index when it's painted.
+    fn row(ix: usize, painted: &Rc<RefCell<Vec<usize>>>) -> impl IntoElement {
+        let painted = painted.clone();
+        canvas(|_, _| {}, move |_, _, _| painted.borrow_mut().push(ix))
+            .flex_none()
+            .w_full()
+            .h(px(20.))
+    }
+
+    /// A list of 100 rows scrolled in a 100px high container.
+    struct TallList {
+        scroll_handle: ScrollHandle,
+        painted: Rc<RefCell<Vec<usize>>>,
+    }
+
+    impl Render for TallList {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("list")
+                .flex()
+                .flex_col()
+                .size(px(100.))
+                .overflow_y_scroll()
+                .track_scroll(&self.scroll_handle)
+                .children((0..100).map(|ix| row(ix, &self.painted)))
+        }
+    }
+
+    fn painted_rows(view: &View<TallList>, cx: &mut VisualTestContext) -> Vec<usize> {
+        view.update(cx, |view, cx| {
+            view.painted.borrow_mut().clear();
+            cx.notify();
+        });
+        cx.run_until_parked();
+        view.update(cx, |view, _| view.painted.borrow().clone())
+    }
+
+    #[gpui::test]
+    fn test_offscreen_rows_are_not_painted(cx: &mut TestAppContext) {
+        let scroll_handle = ScrollHandle::new();
+        let (view, cx) = cx.add_window_view({
+            let scroll_handle = scroll_handle.clone();
+            |_| TallList {
+                scroll_handle,
+                painted: Rc::default(),
+            }
+        });
+        cx.update(|cx| cx.frame_diagnostics_mut().set_enabled(true));
+
+        assert_eq!(painted_rows(&view, cx), [0, 1, 2, 3, 4]);
+        cx.update(|cx| {
+            let frame = cx.frame_diagnostics().last_frame().unwrap();
+            assert_eq!(frame.culled_elements, 95);
+        });
+
+        scroll_handle.set_offset(point(px(0.), px(-410.)));
+        assert_eq!(painted_rows(&view, cx), [20, 21, 22, 23, 24, 25]);
+    }
+
+    /// A badge hanging below an element that's above the top of the window.
+    struct Badge {
+        paint_when_clipped: bool,
+        painted: Rc<RefCell<Vec<usize>>>,
+    }
+
+    impl Render for Badge {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let badge = div()
+                .absolute()
+                .top(px(40.))
+                .size(px(20.))
+                .child(row(0, &self.painted));
+            div().size(px(100.)).child(
+                div()
+                    .absolute()
+                    .top(px(-50.))
+                    .w(px(100.))
+                    .h(px(20.))
+                    .when(self.paint_when_clipped, |this| this.paint_when_clipped())
+                    .child(badge),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_paint_when_clipped(cx: &mut TestAppContext) {
+        let painted = Rc::new(RefCell::new(Vec::new()));
+        let (view, cx) = cx.add_window_view({
+            let painted = painted.clone();
+            |_| Badge {
+                paint_when_clipped: false,
+                painted,
+            }
+        });
+        cx.run_until_parked();
+        assert!(painted.borrow().is_empty());
+
+        view.update(cx, |view, cx| {
+            view.paint_when_clipped = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        assert_eq!(*painted.borrow(), [0]);
+    }
+}
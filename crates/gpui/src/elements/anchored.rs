@@ -19,6 +19,7 @@ pub struct Anchored {
     fit_mode: AnchoredFitMode,
     anchor_position: Option<Point<Pixels>>,
     position_mode: AnchoredPositionMode,
+    beside: Option<Bounds<Pixels>>,
 }
 
 /// anchored gives you an element that will avoid overflowing the window bounds.
@@ -30,6 +31,7 @@ pub fn anchored() -> Anchored {
         fit_mode: AnchoredFitMode::SwitchAnchor,
         anchor_position: None,
         position_mode: AnchoredPositionMode::Window,
+        beside: None,
     }
 }
 
@@ -55,6 +57,14 @@ impl Anchored {
         self
     }
 
+    /// Places the anchored element beside the given bounds in window coordinates, such as those
+    /// of the menu item that opened a submenu: to their right and aligned with their top. When
+    /// that would overflow the window, it moves to their left, or aligns with their bottom.
+    pub fn beside(mut self, bounds: Bounds<Pixels>) -> Self {
+        self.beside = Some(bounds);
+        self
+    }
+
     /// Snap to window edge instead of switching anchor corner when an overflow would occur.
     pub fn snap_to_window(mut self) -> Self {
         self.fit_mode = AnchoredFitMode::SnapToWindow;
@@ -130,7 +140,18 @@ impl Element for Anchored {
             size: cx.viewport_size(),
         };
 
-        if self.fit_mode == AnchoredFitMode::SwitchAnchor {
+        if let Some(beside) = self.beside {
+            desired = Bounds {
+                origin: beside.upper_right(),
+                size,
+            };
+            if desired.right() > limits.right() && beside.left() - size.width >= limits.left() {
+                desired.origin.x = beside.left() - size.width;
+            }
+            if desired.bottom() > limits.bottom() && beside.bottom() - size.height >= limits.top() {
+                desired.origin.y = beside.bottom() - size.height;
+            }
+        } else if self.fit_mode == AnchoredFitMode::SwitchAnchor {
             let mut anchor_corner = self.anchor_corner;
 
             if desired.left() < limits.left() || desired.right() > limits.right() {
@@ -193,6 +214,11 @@ impl Element for Anchored {
             child.paint(cx);
         }
     }
+
+    // The children are positioned around the anchor, outside of this element's bounds.
+    fn cullable(&self) -> bool {
+        false
+    }
 }
 
 impl IntoElement for Anchored {
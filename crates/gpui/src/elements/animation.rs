@@ -1,6 +1,11 @@
-use std::time::{Duration, Instant};
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
-use crate::{AnyElement, Element, ElementId, GlobalElementId, IntoElement};
+use crate::{
+    AnyElement, Element, ElementId, FrameReason, GlobalElementId, IntoElement, WindowContext,
+};
 
 pub use easing::*;
 
@@ -13,6 +18,11 @@ pub struct Animation {
     /// A function that takes a delta between 0 and 1 and returns a new delta
     /// between 0 and 1 based on the given easing function.
     pub easing: Box<dyn Fn(f32) -> f32>,
+    /// Whether this animation moves the element, which is replaced with a fade when motion is
+    /// reduced, see [`WindowContext::animation_timing`].
+    pub movement: bool,
+    /// A function to call once a one-shot animation has finished, however long it took.
+    pub on_complete: Option<Rc<dyn Fn(&mut WindowContext)>>,
 }
 
 impl Animation {
@@ -23,6 +33,8 @@ impl Animation {
             duration,
             oneshot: true,
             easing: Box::new(linear),
+            movement: false,
+            on_complete: None,
         }
     }
 
@@ -39,6 +51,45 @@ impl Animation {
         self.easing = Box::new(easing);
         self
     }
+
+    /// Mark the animation as moving the element, e.g. sliding or growing it.
+    pub fn movement(mut self) -> Self {
+        self.movement = true;
+        self
+    }
+
+    /// Call the given function once the animation has finished, however long it took.
+    pub fn on_complete(mut self, on_complete: impl Fn(&mut WindowContext) + 'static) -> Self {
+        self.on_complete = Some(Rc::new(on_complete));
+        self
+    }
+
+    /// How far along the animation is after the given time, and whether it's done, when it takes
+    /// the given duration. Repeating animations that shouldn't move hold still halfway through.
+    fn progress(&self, elapsed: Duration, duration: Duration) -> (f32, bool) {
+        if duration.is_zero() {
+            return (if self.oneshot { 1. } else { 0.5 }, true);
+        }
+        let delta = elapsed.as_secs_f32() / duration.as_secs_f32();
+        if delta < 1. {
+            (delta, false)
+        } else if self.oneshot {
+            (1., true)
+        } else {
+            (delta % 1., false)
+        }
+    }
+
+    fn complete(&self, cx: &mut WindowContext) {
+        if let Some(on_complete) = self.on_complete.clone() {
+            cx.defer(move |cx| on_complete(cx));
+        }
+    }
+}
+
+/// The opacity that stands in for a repeating animation that moves, when motion is reduced.
+fn pulse(delta: f32) -> f32 {
+    1. - 0.6 * bounce(ease_in_out)(delta)
 }
 
 /// An extension trait for adding the animation wrapper to both Elements and Components
@@ -60,6 +111,28 @@ pub trait AnimationExt {
             animation,
         }
     }
+
+    /// Render this component or element with a value that transitions smoothly to `target`
+    /// whenever `target` changes between frames, using the duration and easing of the given
+    /// animation. The animator receives the value to show in the current frame.
+    fn with_transition(
+        self,
+        id: impl Into<ElementId>,
+        target: f32,
+        animation: Animation,
+        animator: impl Fn(Self, f32) -> Self + 'static,
+    ) -> TransitionElement<Self>
+    where
+        Self: Sized,
+    {
+        TransitionElement {
+            id: id.into(),
+            element: Some(self),
+            target,
+            animator: Box::new(animator),
+            animation,
+        }
+    }
 }
 
 impl<E> AnimationExt for E {}
@@ -91,10 +164,11 @@ impl<E: IntoElement + 'static> IntoElement for AnimationElement<E> {
 
 struct AnimationState {
     start: Instant,
+    completed: bool,
 }
 
 impl<E: IntoElement + 'static> Element for AnimationElement<E> {
-    type RequestLayoutState = AnyElement;
+    type RequestLayoutState = (AnyElement, Option<f32>);
     type PrepaintState = ();
 
     fn id(&self) -> Option<ElementId> {
@@ -107,20 +181,23 @@ impl<E: IntoElement + 'static> Element for AnimationElement<E> {
         cx: &mut crate::WindowContext,
     ) -> (crate::LayoutId, Self::RequestLayoutState) {
         cx.with_element_state(global_id.unwrap(), |state, cx| {
-            let state = state.unwrap_or_else(|| AnimationState {
-                start: Instant::now(),
+            let now = cx.background_executor().now();
+            let mut state = state.unwrap_or(AnimationState {
+                start: now,
+                completed: false,
             });
-            let mut delta =
-                state.start.elapsed().as_secs_f32() / self.animation.duration.as_secs_f32();
-
-            let mut done = false;
-            if delta > 1.0 {
-                if self.animation.oneshot {
-                    done = true;
-                    delta = 1.0;
+            let elapsed = now.saturating_duration_since(state.start);
+            let timing = cx.animation_timing(&self.animation);
+            let (delta, mut done) = self.animation.progress(elapsed, timing.duration);
+            let mut opacity = None;
+            if let Some(fade) = timing.fade {
+                let (fade_delta, fade_done) = self.animation.progress(elapsed, fade);
+                opacity = Some(if self.animation.oneshot {
+                    fade_delta
                 } else {
-                    delta = delta % 1.0;
-                }
+                    pulse(fade_delta)
+                });
+                done = fade_done;
             }
             let delta = (self.animation.easing)(delta);
 
@@ -133,17 +210,13 @@ impl<E: IntoElement + 'static> Element for AnimationElement<E> {
             let mut element = (self.animator)(element, delta).into_any_element();
 
             if !done {
-                let parent_id = cx.parent_view_id();
-                cx.on_next_frame(move |cx| {
-                    if let Some(parent_id) = parent_id {
-                        cx.notify(parent_id)
-                    } else {
-                        cx.refresh()
-                    }
-                })
+                cx.request_frame(FrameReason::Animation);
+            } else if self.animation.oneshot && !state.completed {
+                state.completed = true;
+                self.animation.complete(cx);
             }
 
-            ((element.request_layout(cx), element), state)
+            ((element.request_layout(cx), (element, opacity)), state)
         })
     }
 
@@ -151,7 +224,7 @@ impl<E: IntoElement + 'static> Element for AnimationElement<E> {
         &mut self,
         _id: Option<&GlobalElementId>,
         _bounds: crate::Bounds<crate::Pixels>,
-        element: &mut Self::RequestLayoutState,
+        (element, _): &mut Self::RequestLayoutState,
         cx: &mut crate::WindowContext,
     ) -> Self::PrepaintState {
         element.prepaint(cx);
@@ -161,11 +234,136 @@ impl<E: IntoElement + 'static> Element for AnimationElement<E> {
         &mut self,
         _id: Option<&GlobalElementId>,
         _bounds: crate::Bounds<crate::Pixels>,
-        element: &mut Self::RequestLayoutState,
+        (element, opacity): &mut Self::RequestLayoutState,
         _: &mut Self::PrepaintState,
         cx: &mut crate::WindowContext,
     ) {
-        element.paint(cx);
+        cx.with_element_opacity(*opacity, |cx| element.paint(cx));
+    }
+
+    fn cullable(&self) -> bool {
+        false
+    }
+}
+
+/// A GPUI element that transitions a value applied to another element
+pub struct TransitionElement<E> {
+    id: ElementId,
+    element: Option<E>,
+    target: f32,
+    animation: Animation,
+    animator: Box<dyn Fn(E, f32) -> E + 'static>,
+}
+
+impl<E: IntoElement + 'static> IntoElement for TransitionElement<E> {
+    type Element = TransitionElement<E>;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+struct TransitionState {
+    from: f32,
+    to: f32,
+    start: Instant,
+    completed: bool,
+}
+
+impl TransitionState {
+    fn progress(&self, duration: Duration, now: Instant) -> f32 {
+        if duration.is_zero() {
+            return 1.;
+        }
+        let elapsed = now.saturating_duration_since(self.start);
+        (elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.0)
+    }
+
+    fn value(&self, animation: &Animation, duration: Duration, now: Instant) -> f32 {
+        let delta = (animation.easing)(self.progress(duration, now));
+        self.from + (self.to - self.from) * delta
+    }
+}
+
+impl<E: IntoElement + 'static> Element for TransitionElement<E> {
+    type RequestLayoutState = (AnyElement, Option<f32>);
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn request_layout(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        cx: &mut crate::WindowContext,
+    ) -> (crate::LayoutId, Self::RequestLayoutState) {
+        cx.with_element_state(global_id.unwrap(), |state, cx| {
+            let now = cx.background_executor().now();
+            let timing = cx.animation_timing(&self.animation);
+            let mut state = state.unwrap_or(TransitionState {
+                from: self.target,
+                to: self.target,
+                start: now,
+                completed: true,
+            });
+            if state.to != self.target {
+                state = TransitionState {
+                    from: state.value(&self.animation, timing.duration, now),
+                    to: self.target,
+                    start: now,
+                    completed: false,
+                };
+            }
+
+            let element = self.element.take().expect("should only be called once");
+            let value = state.value(&self.animation, timing.duration, now);
+            let mut element = (self.animator)(element, value).into_any_element();
+
+            let mut progress = state.progress(timing.duration, now);
+            let mut opacity = None;
+            if let Some(fade) = timing.fade.filter(|_| !state.completed) {
+                progress = state.progress(fade, now);
+                opacity = Some(progress);
+            }
+            // A transition that's never changed has nothing to play, even though it started in
+            // this frame.
+            if !state.completed {
+                if progress < 1.0 {
+                    cx.request_frame(FrameReason::Animation);
+                } else {
+                    state.completed = true;
+                    self.animation.complete(cx);
+                }
+            }
+
+            ((element.request_layout(cx), (element, opacity)), state)
+        })
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: crate::Bounds<crate::Pixels>,
+        (element, _): &mut Self::RequestLayoutState,
+        cx: &mut crate::WindowContext,
+    ) -> Self::PrepaintState {
+        element.prepaint(cx);
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: crate::Bounds<crate::Pixels>,
+        (element, opacity): &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        cx: &mut crate::WindowContext,
+    ) {
+        cx.with_element_opacity(*opacity, |cx| element.paint(cx));
+    }
+
+    fn cullable(&self) -> bool {
+        false
     }
 }
 
@@ -201,3 +399,177 @@ mod easing {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    use crate::{
+        self as gpui, div, dock_layout, point, prelude::*, px, reorderable_list, Animation,
+        DockPanel, DockPanelHandle, DockSide, Modifiers, MotionPreference, MouseButton, Pixels,
+        TestAppContext, ViewContext, VisualTestContext,
+    };
+
+    struct Panel {
+        shown: bool,
+        widths: Rc<RefCell<Vec<Pixels>>>,
+        completions: Rc<RefCell<usize>>,
+    }
+
+    impl Render for Panel {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let widths = self.widths.clone();
+            let completions = self.completions.clone();
+            div().when(self.shown, |this| {
+                this.child(
+                    div()
+                        .debug_selector(|| "panel".into())
+                        .h(px(10.))
+                        .with_animation(
+                            "grow",
+                            Animation::new(Duration::from_millis(400))
+                                .movement()
+                                .on_complete(move |_| *completions.borrow_mut() += 1),
+                            move |panel, delta| {
+                                let width = px(100. * delta);
+                                widths.borrow_mut().push(width);
+                                panel.w(width)
+                            },
+                        ),
+                )
+            })
+        }
+    }
+
+    struct Dock {
+        left: DockPanelHandle,
+    }
+
+    impl Render for Dock {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            dock_layout("dock", div()).panel(
+                DockPanel::new(
+                    DockSide::Left,
+                    div().debug_selector(|| "left".into()).size_full(),
+                )
+                .default_size(px(100.))
+                .collapsed(true)
+                .animate(Duration::from_millis(400))
+                .track(&self.left),
+            )
+        }
+    }
+
+    struct List;
+
+    impl Render for List {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_full().pt(px(10.)).child(
+                reorderable_list(
+                    "items",
+                    ["a", "b", "c"].map(|item| {
+                        div()
+                            .debug_selector(|| item.to_string())
+                            .h(px(20.))
+                            .child(item)
+                    }),
+                    |_, _| {},
+                )
+                .h(px(100.)),
+            )
+        }
+    }
+
+    /// Draw frames 16ms apart until nothing asks for another, returning how many were drawn.
+    fn frames_until_still(cx: &mut VisualTestContext) -> usize {
+        let mut frames = 0;
+        while cx.has_pending_frame_callbacks() {
+            cx.executor().advance_clock(Duration::from_millis(16));
+            cx.simulate_frame();
+            frames += 1;
+        }
+        frames
+    }
+
+    /// Show the panel under the given preference, and draw frames 16ms apart until the animation
+    /// stops asking for them, returning how many it asked for.
+    fn run(preference: MotionPreference, cx: &mut TestAppContext) -> usize {
+        let widths = Rc::new(RefCell::new(Vec::new()));
+        let completions = Rc::new(RefCell::new(0));
+        let (panel, cx) = cx.add_window_view({
+            let widths = widths.clone();
+            let completions = completions.clone();
+            |_| Panel {
+                shown: false,
+                widths,
+                completions,
+            }
+        });
+        cx.update(|cx| cx.set_motion_preference(Some(preference)));
+        panel.update(cx, |panel, cx| {
+            panel.shown = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+
+        let frames = frames_until_still(cx);
+        assert_eq!(widths.borrow().last(), Some(&px(100.)));
+        assert_eq!(cx.debug_bounds("panel").unwrap().size.width, px(100.));
+        assert_eq!(*completions.borrow(), 1);
+        frames
+    }
+
+    /// Expand a docked panel under the given preference, returning how many frames it asked for.
+    fn run_dock(preference: MotionPreference, cx: &mut TestAppContext) -> usize {
+        let left = DockPanelHandle::new();
+        let (dock, cx) = cx.add_window_view({
+            let left = left.clone();
+            |_| Dock { left }
+        });
+        cx.update(|cx| cx.set_motion_preference(Some(preference)));
+        cx.run_until_parked();
+        left.set_collapsed(false);
+        dock.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+
+        let frames = frames_until_still(cx);
+        assert_eq!(cx.debug_bounds("left").unwrap().origin.x, px(0.));
+        frames
+    }
+
+    /// Drag the first item of a reorderable list below the second under the given preference,
+    /// returning where the second item is drawn once it starts making room, and how many frames
+    /// it asked for to get out of the way.
+    fn run_list(preference: MotionPreference, cx: &mut TestAppContext) -> (Pixels, usize) {
+        let (_, cx) = cx.add_window_view(|_| List);
+        cx.update(|cx| cx.set_motion_preference(Some(preference)));
+        cx.run_until_parked();
+        cx.simulate_mouse_down(point(px(5.), px(15.)), MouseButton::Left, Modifiers::none());
+        for y in [px(25.), px(45.)] {
+            cx.simulate_mouse_move(point(px(5.), y), MouseButton::Left, Modifiers::none());
+        }
+        let start = cx.debug_bounds("b").unwrap().origin.y;
+
+        let frames = frames_until_still(cx);
+        assert_eq!(cx.debug_bounds("b").unwrap().origin.y, px(10.));
+        (start, frames)
+    }
+
+    #[gpui::test]
+    fn test_animations_follow_the_motion_preference(cx: &mut TestAppContext) {
+        // The animated value moves over the whole 400ms, and so does the panel opening, while the
+        // item slides out of the way over 120ms.
+        assert_eq!(run(MotionPreference::Full, cx), 25);
+        assert_eq!(run_dock(MotionPreference::Full, cx), 25);
+        assert_eq!(run_list(MotionPreference::Full, cx), (px(30.), 8));
+        // The panels are drawn at their full size straight away, and fade in over at most 150ms.
+        // So does the item where it ends up.
+        assert_eq!(run(MotionPreference::Reduced, cx), 10);
+        assert_eq!(run_dock(MotionPreference::Reduced, cx), 10);
+        assert_eq!(run_list(MotionPreference::Reduced, cx), (px(10.), 8));
+        // Everything is drawn as it ends up, without asking for any more frames.
+        assert_eq!(run(MotionPreference::None, cx), 0);
+        assert_eq!(run_dock(MotionPreference::None, cx), 0);
+        assert_eq!(run_list(MotionPreference::None, cx), (px(10.), 0));
+    }
+}
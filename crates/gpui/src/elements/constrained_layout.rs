@@ -0,0 +1,522 @@
+//! Rows and columns of panes that share the space along their axis by weight, within each pane's
+//! minimum and maximum sizes.
+//!
+//! When the space is too small for every pane's minimum size, panes collapse in order of their
+//! collapse priority, lowest first, until the rest fit. Collapsed panes take up their collapsed
+//! size, and are told they're collapsed so they can render a slim placeholder instead of their
+//! content. The panes are sized again from scratch every frame, so growing the layout expands
+//! collapsed panes again in the reverse order.
+
+use crate::{
+    point, px, size, AnyElement, AvailableSpace, Axis, Bounds, ContentMask, Element, ElementId,
+    GlobalElementId, IntoElement, LayoutId, Pixels, Style, StyleRefinement, Styled, WindowContext,
+};
+use refineable::Refineable;
+use std::cmp::Reverse;
+
+/// The golden ratio, φ.
+pub const GOLDEN_RATIO: f32 = 1.618_034;
+
+/// Minimum sizes overflowing the space by less than this still fit.
+const EPSILON: f32 = 0.01;
+
+/// Weights that split the space equally between the given number of panes.
+pub fn equal_split(count: usize) -> Vec<f32> {
+    vec![1.; count]
+}
+
+/// Weights that give the focused pane the golden ratio more space than each of the others, so
+/// that two panes split the space at the golden section.
+pub fn golden_ratio_split(count: usize, focused: usize) -> Vec<f32> {
+    (0..count)
+        .map(|ix| if ix == focused { GOLDEN_RATIO } else { 1. })
+        .collect()
+}
+
+/// How a pane of a [`ConstrainedLayout`] is sized.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PaneConstraints {
+    /// The share of the space the pane takes relative to the other panes. Weights that aren't
+    /// finite and positive count as 1.
+    pub weight: f32,
+    /// The smallest size of the pane unless it's collapsed.
+    pub min: Pixels,
+    /// The largest size of the pane.
+    pub max: Pixels,
+    /// Panes with lower priorities are collapsed first when there isn't room for every pane.
+    pub collapse_priority: u32,
+    /// The size of the pane when it's collapsed.
+    pub collapsed_size: Pixels,
+}
+
+impl Default for PaneConstraints {
+    fn default() -> Self {
+        Self {
+            weight: 1.,
+            min: px(0.),
+            max: Pixels::MAX,
+            collapse_priority: 0,
+            collapsed_size: px(0.),
+        }
+    }
+}
+
+/// The size a pane was given by [`solve_pane_sizes`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResolvedPane {
+    /// The size of the pane along the layout's axis.
+    pub size: Pixels,
+    /// Whether the pane was collapsed to make room for the others.
+    pub collapsed: bool,
+}
+
+/// Size panes to share the available space, collapsing the lowest-priority panes first when
+/// their minimum sizes don't all fit. Among panes with the same priority, the later ones collapse
+/// first. A pane is only collapsed if it couldn't be expanded without the minimum sizes
+/// overflowing.
+///
+/// The space is split between the expanded panes by weight, within their minimum and maximum
+/// sizes, so the sizes add up to the available space unless every expanded pane is at its
+/// maximum.
+pub fn solve_pane_sizes(available: Pixels, panes: &[PaneConstraints]) -> Vec<ResolvedPane> {
+    let available = available.0.max(0.);
+    let required = |collapsed: &[bool]| -> f32 {
+        panes
+            .iter()
+            .zip(collapsed)
+            .map(|(pane, collapsed)| {
+                if *collapsed {
+                    pane.collapsed_size.0
+                } else {
+                    pane.min.0
+                }
+            })
+            .sum()
+    };
+
+    let mut collapse_order = (0..panes.len()).collect::<Vec<_>>();
+    collapse_order.sort_by_key(|ix| (panes[*ix].collapse_priority, Reverse(*ix)));
+    let mut collapsed = vec![false; panes.len()];
+    for ix in &collapse_order {
+        if required(&collapsed) <= available + EPSILON {
+            break;
+        }
+        collapsed[*ix] = true;
+    }
+    // Collapsing a pane with a large minimum size may have made room for lower-priority panes
+    // collapsed before it.
+    for ix in collapse_order.iter().rev() {
+        if collapsed[*ix] {
+            collapsed[*ix] = false;
+            if required(&collapsed) > available + EPSILON {
+                collapsed[*ix] = true;
+            }
+        }
+    }
+
+    // Distribute the space like flexbox does: share it by weight, and while that leaves panes
+    // outside their constraints, fix those panes at their limits and share the rest again.
+    let mut sizes = panes
+        .iter()
+        .zip(&collapsed)
+        .map(|(pane, collapsed)| collapsed.then_some(pane.collapsed_size.0))
+        .collect::<Vec<_>>();
+    let weight = |ix: usize| {
+        let weight = panes[ix].weight;
+        if weight.is_finite() && weight > 0. {
+            weight
+        } else {
+            1.
+        }
+    };
+    // Every round fixes at least one pane, so the last round fixes whatever rounding has left.
+    for round in 0..panes.len() {
+        let unresolved = (0..panes.len())
+            .filter(|ix| sizes[*ix].is_none())
+            .collect::<Vec<_>>();
+        if unresolved.is_empty() {
+            break;
+        }
+        let last_round = round + 1 == panes.len();
+        let free = available - sizes.iter().flatten().sum::<f32>();
+        let total_weight = unresolved.iter().map(|ix| weight(*ix)).sum::<f32>();
+        let tentative = unresolved
+            .iter()
+            .map(|ix| {
+                let tentative = free.max(0.) * weight(*ix) / total_weight;
+                let pane = &panes[*ix];
+                let clamped = tentative.max(pane.min.0).min(pane.max.0.max(pane.min.0));
+                (*ix, tentative, clamped)
+            })
+            .collect::<Vec<_>>();
+
+        let violation = tentative
+            .iter()
+            .map(|(_, tentative, clamped)| clamped - tentative)
+            .sum::<f32>();
+        for (ix, tentative, clamped) in tentative {
+            let fix = if last_round || violation == 0. {
+                true
+            } else if violation > 0. {
+                clamped > tentative
+            } else {
+                clamped < tentative
+            };
+            if fix {
+                sizes[ix] = Some(clamped);
+            }
+        }
+    }
+
+    sizes
+        .into_iter()
+        .zip(collapsed)
+        .map(|(size, collapsed)| ResolvedPane {
+            size: px(size.unwrap_or_default()),
+            collapsed,
+        })
+        .collect()
+}
+
+/// Construct a row of panes sized by [`solve_pane_sizes`]. The row fills its parent by default.
+pub fn constrained_row(children: impl IntoIterator<Item = ConstrainedChild>) -> ConstrainedLayout {
+    ConstrainedLayout::new(Axis::Horizontal, children)
+}
+
+/// Construct a column of panes sized by [`solve_pane_sizes`]. The column fills its parent by
+/// default.
+pub fn constrained_column(
+    children: impl IntoIterator<Item = ConstrainedChild>,
+) -> ConstrainedLayout {
+    ConstrainedLayout::new(Axis::Vertical, children)
+}
+
+/// A pane of a [`ConstrainedLayout`], built once its size is known.
+pub struct ConstrainedChild {
+    constraints: PaneConstraints,
+    render: Option<Box<dyn FnOnce(bool, &mut WindowContext) -> AnyElement>>,
+}
+
+impl ConstrainedChild {
+    /// Construct a pane built by the given function, which is told whether the pane is collapsed.
+    pub fn new<E: IntoElement>(
+        render: impl FnOnce(bool, &mut WindowContext) -> E + 'static,
+    ) -> Self {
+        Self {
+            constraints: PaneConstraints::default(),
+            render: Some(Box::new(move |collapsed, cx| {
+                render(collapsed, cx).into_any_element()
+            })),
+        }
+    }
+
+    /// Set the share of the space the pane takes relative to the other panes. The default is 1.
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.constraints.weight = weight;
+        self
+    }
+
+    /// Set the smallest size of the pane unless it's collapsed.
+    pub fn min_size(mut self, size: Pixels) -> Self {
+        self.constraints.min = size;
+        self
+    }
+
+    /// Set the largest size of the pane.
+    pub fn max_size(mut self, size: Pixels) -> Self {
+        self.constraints.max = size;
+        self
+    }
+
+    /// Set the priority of the pane when panes are collapsed to make room. Lower priorities
+    /// collapse first.
+    pub fn collapse_priority(mut self, priority: u32) -> Self {
+        self.constraints.collapse_priority = priority;
+        self
+    }
+
+    /// Set the size of the pane when it's collapsed, to leave room for a placeholder.
+    pub fn collapsed_size(mut self, size: Pixels) -> Self {
+        self.constraints.collapsed_size = size;
+        self
+    }
+}
+
+/// A row or column of panes, see [`constrained_row`] and [`constrained_column`].
+pub struct ConstrainedLayout {
+    axis: Axis,
+    children: Vec<ConstrainedChild>,
+    style: StyleRefinement,
+}
+
+impl ConstrainedLayout {
+    fn new(axis: Axis, children: impl IntoIterator<Item = ConstrainedChild>) -> Self {
+        Self {
+            axis,
+            children: children.into_iter().collect(),
+            style: StyleRefinement::default(),
+        }
+        .size_full()
+    }
+}
+
+impl Styled for ConstrainedLayout {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl Element for ConstrainedLayout {
+    type RequestLayoutState = ();
+    type PrepaintState = Vec<(Bounds<Pixels>, AnyElement)>;
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut style = Style::default();
+        style.refine(&self.style);
+        (cx.request_layout(style, None), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        let constraints = self
+            .children
+            .iter()
+            .map(|child| child.constraints)
+            .collect::<Vec<_>>();
+        let available = match self.axis {
+            Axis::Horizontal => bounds.size.width,
+            Axis::Vertical => bounds.size.height,
+        };
+
+        let mut offset = px(0.);
+        solve_pane_sizes(available, &constraints)
+            .into_iter()
+            .zip(&mut self.children)
+            .filter_map(|(pane, child)| {
+                let pane_bounds = match self.axis {
+                    Axis::Horizontal => Bounds::new(
+                        point(bounds.origin.x + offset, bounds.origin.y),
+                        size(pane.size, bounds.size.height),
+                    ),
+                    Axis::Vertical => Bounds::new(
+                        point(bounds.origin.x, bounds.origin.y + offset),
+                        size(bounds.size.width, pane.size),
+                    ),
+                };
+                offset += pane.size;
+
+                let mut element = (child.render.take()?)(pane.collapsed, cx);
+                cx.with_content_mask(
+                    Some(ContentMask {
+                        bounds: pane_bounds,
+                    }),
+                    |cx| {
+                        element.prepaint_as_root(
+                            pane_bounds.origin,
+                            pane_bounds.size.map(AvailableSpace::Definite),
+                            cx,
+                        )
+                    },
+                );
+                Some((pane_bounds, element))
+            })
+            .collect()
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        for (bounds, element) in prepaint {
+            cx.with_content_mask(Some(ContentMask { bounds: *bounds }), |cx| {
+                element.paint(cx)
+            });
+        }
+    }
+}
+
+impl IntoElement for ConstrainedLayout {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        constrained_row, golden_ratio_split, solve_pane_sizes, ConstrainedChild, PaneConstraints,
+    };
+    use crate::{
+        self as gpui, div, px, size, IntoElement, Pixels, Render, Styled, TestAppContext,
+        ViewContext,
+    };
+    use rand::prelude::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    const TOLERANCE: f32 = 0.1;
+
+    fn random_panes(rng: &mut StdRng) -> Vec<PaneConstraints> {
+        (0..rng.gen_range(1..6))
+            .map(|_| {
+                let min = px(rng.gen_range(0.0..300.0));
+                PaneConstraints {
+                    weight: rng.gen_range(0.0..4.0),
+                    min,
+                    max: if rng.gen_bool(0.3) {
+                        min + px(rng.gen_range(0.0..200.0))
+                    } else {
+                        Pixels::MAX
+                    },
+                    collapse_priority: rng.gen_range(0..3),
+                    collapsed_size: px(rng.gen::<f32>() * min.0.min(24.)),
+                }
+            })
+            .collect()
+    }
+
+    #[gpui::test(iterations = 100)]
+    fn test_random_pane_sizes(mut rng: StdRng) {
+        let panes = random_panes(&mut rng);
+        let available = px(rng.gen_range(0.0..1500.0));
+        let resolved = solve_pane_sizes(available, &panes);
+        assert_eq!(resolved, solve_pane_sizes(available, &panes));
+
+        let required = |collapsed: &dyn Fn(usize) -> bool| -> f32 {
+            panes
+                .iter()
+                .enumerate()
+                .map(|(ix, pane)| {
+                    if collapsed(ix) {
+                        pane.collapsed_size.0
+                    } else {
+                        pane.min.0
+                    }
+                })
+                .sum()
+        };
+        for (ix, (pane, resolved_pane)) in panes.iter().zip(&resolved).enumerate() {
+            if resolved_pane.collapsed {
+                assert_eq!(resolved_pane.size, pane.collapsed_size);
+                // Expanding the pane would overflow the minimum sizes.
+                let expanded =
+                    required(&|other| other != ix && resolved[other].collapsed) > available.0;
+                assert!(expanded, "pane {ix} was collapsed needlessly: {panes:?}");
+            } else {
+                assert!(resolved_pane.size.0 >= pane.min.0 - TOLERANCE);
+                assert!(resolved_pane.size.0 <= pane.max.0.max(pane.min.0) + TOLERANCE);
+            }
+        }
+
+        let total = resolved.iter().map(|pane| pane.size.0).sum::<f32>();
+        let all_at_max = panes
+            .iter()
+            .zip(&resolved)
+            .filter(|(_, resolved)| !resolved.collapsed)
+            .all(|(pane, resolved)| resolved.size.0 >= pane.max.0.max(pane.min.0) - TOLERANCE);
+        if required(&|ix| resolved[ix].collapsed) <= available.0 {
+            assert!(total <= available.0 + TOLERANCE, "{total} > {available}");
+            if !all_at_max {
+                assert!(
+                    (total - available.0).abs() < TOLERANCE,
+                    "{total} != {available}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_golden_ratio_split() {
+        let panes = golden_ratio_split(2, 0)
+            .into_iter()
+            .map(|weight| PaneConstraints {
+                weight,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let resolved = solve_pane_sizes(px(1000.), &panes);
+        assert!((resolved[0].size.0 - 618.034).abs() < TOLERANCE);
+        assert!((resolved[1].size.0 - 381.966).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn test_invalid_weights_count_as_one() {
+        let panes = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY, 0., 1.]
+            .into_iter()
+            .map(|weight| PaneConstraints {
+                weight,
+                max: px(300.),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let resolved = solve_pane_sizes(px(1000.), &panes);
+        for pane in resolved {
+            assert_eq!(pane.size, px(200.));
+        }
+        // Panes that don't fit at their share are still resolved.
+        let resolved = solve_pane_sizes(px(2000.), &panes);
+        for pane in resolved {
+            assert_eq!(pane.size, px(300.));
+        }
+    }
+
+    struct Panes {
+        collapsed: Rc<RefCell<Vec<bool>>>,
+    }
+
+    impl Render for Panes {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            self.collapsed.borrow_mut().clear();
+            constrained_row([2, 0, 1].into_iter().map(|priority| {
+                let collapsed = self.collapsed.clone();
+                ConstrainedChild::new(move |is_collapsed, _| {
+                    collapsed.borrow_mut().push(is_collapsed);
+                    div().size_full()
+                })
+                .min_size(px(200.))
+                .collapse_priority(priority)
+                .collapsed_size(px(20.))
+            }))
+        }
+    }
+
+    #[gpui::test]
+    fn test_resizing_collapses_panes_in_priority_order(cx: &mut TestAppContext) {
+        let collapsed = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view({
+            let collapsed = collapsed.clone();
+            |_| Panes { collapsed }
+        });
+
+        let mut resize = |width: f32| {
+            cx.simulate_resize(size(px(width), px(400.)));
+            cx.run_until_parked();
+            collapsed.borrow().clone()
+        };
+        assert_eq!(resize(800.), [false, false, false]);
+        assert_eq!(resize(500.), [false, true, false]);
+        assert_eq!(resize(300.), [false, true, true]);
+        assert_eq!(resize(100.), [true, true, true]);
+        assert_eq!(resize(300.), [false, true, true]);
+        assert_eq!(resize(500.), [false, true, false]);
+        assert_eq!(resize(800.), [false, false, false]);
+    }
+}
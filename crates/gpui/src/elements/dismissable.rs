@@ -0,0 +1,334 @@
+use std::rc::Rc;
+
+use crate::{
+    AnyElement, Bounds, Element, ElementId, GlobalElementId, IntoElement, LayoutId, Pixels,
+    WindowContext,
+};
+
+/// Builds a `Dismissable` element, which calls `on_dismiss` when the mouse goes down outside of
+/// `content` or escape is pressed, for overlays like popovers, menus and modals. The id tells
+/// dismissables drawn side by side apart, so it should be unique among its siblings.
+///
+/// Only the topmost dismissable, the one painted last, is dismissed by a given click or escape
+/// press. Dismissables drawn within the content, such as a submenu, are nested in it: clicking
+/// one of them doesn't dismiss the outer dismissable, and once they're dismissed, the next
+/// click outside of the outer one dismisses it.
+///
+/// Outside clicks still reach the elements under the mouse after the dismissable is dismissed.
+/// Escape presses are handled before the focused element sees them.
+pub fn dismissable(
+    id: impl Into<ElementId>,
+    on_dismiss: impl Fn(&mut WindowContext) + 'static,
+    content: impl IntoElement,
+) -> Dismissable {
+    Dismissable {
+        id: id.into(),
+        on_dismiss: Rc::new(on_dismiss),
+        content: content.into_any_element(),
+        scope: None,
+        dismiss_parents_on_click: false,
+    }
+}
+
+/// An element which calls a handler when the user clicks outside of it or presses escape.
+pub struct Dismissable {
+    id: ElementId,
+    on_dismiss: Rc<dyn Fn(&mut WindowContext)>,
+    content: AnyElement,
+    scope: Option<Rc<DismissableScope>>,
+    dismiss_parents_on_click: bool,
+}
+
+impl Dismissable {
+    /// When a click outside of this dismissable dismisses it, also dismiss the dismissables it's
+    /// nested in that the click is outside of, innermost first. This lets a click away from a
+    /// menu close it along with all of its open submenus, while escape still closes one at a time.
+    pub fn dismiss_parents_on_click(mut self) -> Self {
+        self.dismiss_parents_on_click = true;
+        self
+    }
+}
+
+/// The dismissables enclosing the elements being drawn, outermost first.
+struct DismissableScope(Rc<[GlobalElementId]>);
+
+/// A dismissable that was painted, see [`WindowContext::insert_dismissable`].
+#[derive(Clone)]
+pub(crate) struct DismissableRegion {
+    /// The region's element and the dismissables it's nested in, outermost first.
+    pub(crate) scope: Rc<[GlobalElementId]>,
+    pub(crate) bounds: Bounds<Pixels>,
+    pub(crate) on_dismiss: Rc<dyn Fn(&mut WindowContext)>,
+    pub(crate) dismiss_parents_on_click: bool,
+}
+
+impl DismissableRegion {
+    pub(crate) fn id(&self) -> &GlobalElementId {
+        self.scope.last().unwrap()
+    }
+
+    /// The id of the dismissable this one is nested in, if any.
+    pub(crate) fn parent_id(&self) -> Option<&GlobalElementId> {
+        self.scope.len().checked_sub(2).map(|ix| &self.scope[ix])
+    }
+}
+
+impl Element for Dismissable {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn request_layout(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let id = id.unwrap();
+        let scope = cx
+            .context::<DismissableScope>()
+            .map(|scope| scope.0.iter())
+            .into_iter()
+            .flatten()
+            .chain([id])
+            .map(|id| GlobalElementId(id.0.clone()))
+            .collect();
+        let scope = Rc::new(DismissableScope(scope));
+        let layout_id = cx.with_context(scope.clone(), |cx| self.content.request_layout(cx));
+        self.scope = Some(scope);
+        (layout_id, ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) {
+        let scope = self.scope.clone().unwrap();
+        cx.with_context(scope, |cx| self.content.prepaint(cx));
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        let scope = self.scope.clone().unwrap();
+        // Inserted before painting the content, so that nested dismissables end up above it.
+        cx.insert_dismissable(DismissableRegion {
+            scope: scope.0.clone(),
+            bounds,
+            on_dismiss: self.on_dismiss.clone(),
+            dismiss_parents_on_click: self.dismiss_parents_on_click,
+        });
+        cx.with_context(scope, |cx| self.content.paint(cx));
+    }
+
+    fn cullable(&self) -> bool {
+        false
+    }
+}
+
+impl IntoElement for Dismissable {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, deferred, dismissable, div, point, px, IntoElement, Modifiers, ParentElement,
+        Render, Styled, TestAppContext, ViewContext, VisualTestContext,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    /// A menu at the top left of the window with a submenu to its right, both dismissable.
+    struct Menus {
+        menu_open: bool,
+        submenu_open: bool,
+        dismiss_parents_on_click: bool,
+        dismissed: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl Render for Menus {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let view = cx.view().downgrade();
+            let dismissed = self.dismissed.clone();
+            let submenu = self.submenu_open.then(|| {
+                let view = view.clone();
+                let dismissed = dismissed.clone();
+                let submenu = dismissable(
+                    "submenu",
+                    move |cx| {
+                        dismissed.borrow_mut().push("submenu");
+                        view.update(cx, |view, cx| {
+                            view.submenu_open = false;
+                            cx.notify();
+                        })
+                        .ok();
+                    },
+                    div().size(px(100.)),
+                );
+                let submenu = if self.dismiss_parents_on_click {
+                    submenu.dismiss_parents_on_click()
+                } else {
+                    submenu
+                };
+                deferred(div().absolute().left(px(100.)).child(submenu))
+            });
+            let menu = self.menu_open.then(|| {
+                dismissable(
+                    "menu",
+                    move |cx| {
+                        dismissed.borrow_mut().push("menu");
+                        view.update(cx, |view, cx| {
+                            view.menu_open = false;
+                            cx.notify();
+                        })
+                        .ok();
+                    },
+                    div().relative().size(px(100.)).children(submenu),
+                )
+            });
+            div().size(px(400.)).children(menu)
+        }
+    }
+
+    fn menus(cx: &mut TestAppContext) -> (Rc<RefCell<Vec<&'static str>>>, &mut VisualTestContext) {
+        menus_dismissing_parents_on_click(cx, false)
+    }
+
+    fn menus_dismissing_parents_on_click(
+        cx: &mut TestAppContext,
+        dismiss_parents_on_click: bool,
+    ) -> (Rc<RefCell<Vec<&'static str>>>, &mut VisualTestContext) {
+        let dismissed = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view({
+            let dismissed = dismissed.clone();
+            move |_| Menus {
+                menu_open: true,
+                submenu_open: true,
+                dismiss_parents_on_click,
+                dismissed,
+            }
+        });
+        (dismissed, cx)
+    }
+
+    #[gpui::test]
+    fn test_clicks_inside_a_nested_dismissable_dismiss_nothing(cx: &mut TestAppContext) {
+        let (dismissed, cx) = menus(cx);
+        cx.simulate_click(point(px(150.), px(50.)), Modifiers::none());
+        assert!(dismissed.borrow().is_empty());
+    }
+
+    #[gpui::test]
+    fn test_outside_clicks_dismiss_one_dismissable_at_a_time(cx: &mut TestAppContext) {
+        let (dismissed, cx) = menus(cx);
+        cx.simulate_click(point(px(300.), px(300.)), Modifiers::none());
+        assert_eq!(*dismissed.borrow(), ["submenu"]);
+        cx.simulate_click(point(px(300.), px(300.)), Modifiers::none());
+        assert_eq!(*dismissed.borrow(), ["submenu", "menu"]);
+    }
+
+    #[gpui::test]
+    fn test_clicking_a_parent_dismisses_its_nested_dismissable(cx: &mut TestAppContext) {
+        let (dismissed, cx) = menus(cx);
+        cx.simulate_click(point(px(50.), px(50.)), Modifiers::none());
+        assert_eq!(*dismissed.borrow(), ["submenu"]);
+        cx.simulate_click(point(px(50.), px(50.)), Modifiers::none());
+        assert_eq!(*dismissed.borrow(), ["submenu"]);
+    }
+
+    #[gpui::test]
+    fn test_escape_dismisses_the_topmost_dismissable(cx: &mut TestAppContext) {
+        let (dismissed, cx) = menus(cx);
+        cx.simulate_keystrokes("escape");
+        assert_eq!(*dismissed.borrow(), ["submenu"]);
+        cx.simulate_keystrokes("escape");
+        assert_eq!(*dismissed.borrow(), ["submenu", "menu"]);
+        cx.simulate_keystrokes("escape");
+        assert_eq!(*dismissed.borrow(), ["submenu", "menu"]);
+    }
+
+    #[gpui::test]
+    fn test_outside_clicks_can_dismiss_parents(cx: &mut TestAppContext) {
+        let (dismissed, cx) = menus_dismissing_parents_on_click(cx, true);
+        // Inside the menu, so only the submenu is dismissed.
+        cx.simulate_click(point(px(50.), px(50.)), Modifiers::none());
+        assert_eq!(*dismissed.borrow(), ["submenu"]);
+
+        let (dismissed, cx) = menus_dismissing_parents_on_click(&mut cx.cx, true);
+        cx.simulate_click(point(px(300.), px(300.)), Modifiers::none());
+        assert_eq!(*dismissed.borrow(), ["submenu", "menu"]);
+    }
+
+    #[gpui::test]
+    fn test_escape_never_dismisses_parents(cx: &mut TestAppContext) {
+        let (dismissed, cx) = menus_dismissing_parents_on_click(cx, true);
+        cx.simulate_keystrokes("escape");
+        assert_eq!(*dismissed.borrow(), ["submenu"]);
+    }
+
+    /// Two dismissables side by side, neither nested in the other.
+    struct Siblings {
+        open: Vec<&'static str>,
+        dismissed: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl Render for Siblings {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let view = cx.view().downgrade();
+            div()
+                .size(px(400.))
+                .flex()
+                .gap(px(100.))
+                .children(self.open.iter().map(|&name| {
+                    let view = view.clone();
+                    let dismissed = self.dismissed.clone();
+                    dismissable(
+                        name,
+                        move |cx| {
+                            dismissed.borrow_mut().push(name);
+                            view.update(cx, |view, cx| {
+                                view.open.retain(|open| *open != name);
+                                cx.notify();
+                            })
+                            .ok();
+                        },
+                        div().size(px(100.)),
+                    )
+                }))
+        }
+    }
+
+    #[gpui::test]
+    fn test_clicks_inside_a_sibling_dismiss_the_topmost(cx: &mut TestAppContext) {
+        let dismissed = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view({
+            let dismissed = dismissed.clone();
+            move |_| Siblings {
+                open: vec!["first", "second"],
+                dismissed,
+            }
+        });
+
+        // The first dismissable is under the mouse, but the second one is on top.
+        cx.simulate_click(point(px(50.), px(50.)), Modifiers::none());
+        assert_eq!(*dismissed.borrow(), ["second"]);
+        cx.simulate_click(point(px(50.), px(50.)), Modifiers::none());
+        assert_eq!(*dismissed.borrow(), ["second"]);
+        cx.simulate_click(point(px(300.), px(300.)), Modifiers::none());
+        assert_eq!(*dismissed.borrow(), ["second", "first"]);
+    }
+}
@@ -16,14 +16,15 @@
 //! constructed by combining these two systems into an all-in-one element.
 
 use crate::{
-    point, px, size, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, AppContext, Bounds,
-    ClickEvent, DispatchPhase, Element, ElementId, FocusHandle, Global, GlobalElementId, Hitbox,
-    HitboxId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, LayoutId,
+    ease_in_out, point, px, size, Action, Animation, AnyDrag, AnyElement, AnyTooltip, AnyView,
+    AppContext, Bounds, ClickEvent, DispatchPhase, Edges, Element, ElementId,
+    ElementScrollWheelEvent, FocusHandle, FrameReason, GlobalElementId, Hitbox, HitboxId,
+    InputModality, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, LayoutId,
     ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
-    ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
-    StyleRefinement, Styled, Task, TooltipId, View, Visibility, WindowContext,
+    ParentElement, Pixels, Point, PointerPosition, Render, ScrollWheelEvent, SharedString, Size,
+    Style, StyleRefinement, Styled, TooltipId, TouchPhase, View, ViewportChangeEvent, Visibility,
+    WindowContext,
 };
-use collections::HashMap;
 use refineable::Refineable;
 use smallvec::SmallVec;
 use std::{
@@ -33,14 +34,23 @@ use std::{
     fmt::Debug,
     marker::PhantomData,
     mem,
-    ops::DerefMut,
     rc::Rc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use taffy::style::Overflow;
 use util::ResultExt;
 
-const DRAG_THRESHOLD: f64 = 2.;
+pub(crate) const DRAG_THRESHOLD: f64 = 2.;
+const SCROLL_ANIMATION_DURATION: Duration = Duration::from_millis(150);
+/// The fraction of its velocity that momentum scrolling keeps after one second.
+const SCROLL_MOMENTUM_FRICTION: f32 = 0.02;
+/// Momentum scrolling stops once it slows below this many pixels per second.
+const MIN_SCROLL_MOMENTUM_VELOCITY: f32 = 20.;
+/// A gesture that rests for longer than this before the fingers lift doesn't continue with momentum.
+const SCROLL_MOMENTUM_TIMEOUT: Duration = Duration::from_millis(50);
+/// How close to the bottom content has to be scrolled for [`ScrollHandle::stick_to_bottom`]
+/// to keep it there.
+const STICK_TO_BOTTOM_TOLERANCE: Pixels = px(1.);
 pub(crate) const TOOLTIP_DELAY: Duration = Duration::from_millis(500);
 
 /// The styling information for a given group.
@@ -267,12 +277,16 @@ impl Interactivity {
     /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
     pub fn on_scroll_wheel(
         &mut self,
-        listener: impl Fn(&ScrollWheelEvent, &mut WindowContext) + 'static,
+        listener: impl Fn(&ElementScrollWheelEvent, &mut WindowContext) + 'static,
     ) {
         self.scroll_wheel_listeners
             .push(Box::new(move |event, phase, hitbox, cx| {
                 if phase == DispatchPhase::Bubble && hitbox.is_hovered(cx) {
-                    (listener)(event, cx);
+                    let event = ElementScrollWheelEvent {
+                        event: event.clone(),
+                        pointer: PointerPosition::new(event.position, hitbox.bounds),
+                    };
+                    (listener)(&event, cx);
                 }
             }));
     }
@@ -403,6 +417,17 @@ impl Interactivity {
             .push(Box::new(move |event, cx| listener(event, cx)));
     }
 
+    /// Bind the given callback to the window's viewport being resized or changing scale factor.
+    /// The imperative API equivalent to [`InteractiveElement::on_viewport_change`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
+    pub fn on_viewport_change(
+        &mut self,
+        listener: impl Fn(&ViewportChangeEvent, &mut WindowContext) + 'static,
+    ) {
+        self.viewport_change_listeners.push(Box::new(listener));
+    }
+
     /// Bind the given callback to drop events of the given type, whether or not the drag started on this element
     /// The imperative API equivalent to [`InteractiveElement::on_drop`]
     ///
@@ -443,7 +468,7 @@ impl Interactivity {
     pub fn on_drag<T, W>(
         &mut self,
         value: T,
-        constructor: impl Fn(&T, &mut WindowContext) -> View<W> + 'static,
+        constructor: impl Fn(&T, &PointerPosition, &mut WindowContext) -> View<W> + 'static,
     ) where
         Self: Sized,
         T: 'static,
@@ -455,7 +480,9 @@ impl Interactivity {
         );
         self.drag_listener = Some((
             Box::new(value),
-            Box::new(move |value, cx| constructor(value.downcast_ref().unwrap(), cx).into()),
+            Box::new(move |value, pointer, cx| {
+                constructor(value.downcast_ref().unwrap(), pointer, cx).into()
+            }),
         ));
     }
 
@@ -475,6 +502,20 @@ impl Interactivity {
         self.hover_listener = Some(Box::new(listener));
     }
 
+    /// Bind the given callback to the mouse resting on this element for the given delay, see
+    /// [`WindowContext::on_hover_delayed`].
+    /// The imperative API equivalent to [`StatefulInteractiveElement::on_hover_delayed`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
+    pub fn on_hover_delayed(
+        &mut self,
+        delay: Duration,
+        listener: impl Fn(&mut WindowContext) + 'static,
+    ) {
+        self.hover_delayed_listeners
+            .push((delay, Rc::new(listener)));
+    }
+
     /// Use the given callback to construct a new tooltip view when the mouse hovers over this element.
     /// The imperative API equivalent to [`InteractiveElement::tooltip`]
     pub fn tooltip(&mut self, build_tooltip: impl Fn(&mut WindowContext) -> AnyView + 'static)
@@ -515,6 +556,43 @@ impl Interactivity {
     pub fn occlude_mouse(&mut self) {
         self.occlude_mouse = true;
     }
+
+    /// Mark this element as a region that moves the window when dragged
+    /// The imperative API equivalent to [`InteractiveElement::window_drag_region`]
+    pub fn window_drag_region(&mut self) {
+        self.window_drag_region = true;
+    }
+
+    /// Paint this element even when its bounds are clipped out of view
+    /// The imperative API equivalent to [`InteractiveElement::paint_when_clipped`]
+    pub fn paint_when_clipped(&mut self) {
+        self.paint_when_clipped = true;
+    }
+
+    /// Let the mouse also hit this element up to the given distance beyond each of its edges
+    /// The imperative API equivalent to [`InteractiveElement::hit_slop`]
+    pub fn hit_slop(&mut self, slop: Edges<Pixels>) {
+        self.hit_slop = Some(slop);
+    }
+
+    /// Let the mouse hit this element within a region at least the given size, centered on it
+    /// The imperative API equivalent to [`InteractiveElement::min_click_size`]
+    pub fn min_click_size(&mut self, size: Pixels) {
+        self.min_click_size = Some(size);
+    }
+
+    fn effective_hit_slop(&self, bounds: Bounds<Pixels>) -> Edges<Pixels> {
+        let mut slop = self.hit_slop.unwrap_or_default();
+        if let Some(min_size) = self.min_click_size {
+            let horizontal = ((min_size - bounds.size.width) / 2.).max(Pixels::ZERO);
+            let vertical = ((min_size - bounds.size.height) / 2.).max(Pixels::ZERO);
+            slop.left = slop.left.max(horizontal);
+            slop.right = slop.right.max(horizontal);
+            slop.top = slop.top.max(vertical);
+            slop.bottom = slop.bottom.max(vertical);
+        }
+        slop
+    }
 }
 
 /// A trait for elements that want to use the standard GPUI event handlers that don't
@@ -721,7 +799,7 @@ pub trait InteractiveElement: Sized {
     /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
     fn on_scroll_wheel(
         mut self,
-        listener: impl Fn(&ScrollWheelEvent, &mut WindowContext) + 'static,
+        listener: impl Fn(&ElementScrollWheelEvent, &mut WindowContext) + 'static,
     ) -> Self {
         self.interactivity().on_scroll_wheel(listener);
         self
@@ -820,6 +898,19 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Bind the given callback to the window's viewport being resized or changing scale factor.
+    /// It's called once per change, before the next frame is laid out.
+    /// The fluent API equivalent to [`Interactivity::on_viewport_change`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
+    fn on_viewport_change(
+        mut self,
+        listener: impl Fn(&ViewportChangeEvent, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.interactivity().on_viewport_change(listener);
+        self
+    }
+
     /// Apply the given style when the given data type is dragged over this element
     fn drag_over<S: 'static>(
         mut self,
@@ -879,6 +970,42 @@ pub trait InteractiveElement: Sized {
         self.interactivity().occlude_mouse();
         self
     }
+
+    /// Let the mouse also hit this element up to the given distance beyond each of its edges,
+    /// without changing its layout or what's painted. Where this overlaps another element, the
+    /// one actually under the mouse wins, and otherwise the nearest one does.
+    /// The fluent API equivalent to [`Interactivity::hit_slop`]
+    fn hit_slop(mut self, slop: impl Into<Edges<Pixels>>) -> Self {
+        self.interactivity().hit_slop(slop.into());
+        self
+    }
+
+    /// Let the mouse hit this element anywhere within a square of at least the given size
+    /// centered on it, so that small controls such as icons stay easy to click. Like
+    /// [`Self::hit_slop`], this doesn't change the element's layout or what's painted.
+    /// The fluent API equivalent to [`Interactivity::min_click_size`]
+    fn min_click_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.interactivity().min_click_size(size.into());
+        self
+    }
+
+    /// Make dragging this element move the window, as in a custom title bar. Double-clicking it
+    /// zooms the window instead. Descendants that handle the mouse themselves, such as buttons,
+    /// are left out of the region so that they remain clickable.
+    /// The fluent API equivalent to [`Interactivity::window_drag_region`]
+    fn window_drag_region(mut self) -> Self {
+        self.interactivity().window_drag_region();
+        self
+    }
+
+    /// Paint this element and its children even when its bounds are entirely clipped out of
+    /// view, which would otherwise skip painting them. Use this for elements that paint outside
+    /// of their bounds, such as ones with a shadow or with children overflowing them.
+    /// The fluent API equivalent to [`Interactivity::paint_when_clipped`]
+    fn paint_when_clipped(mut self) -> Self {
+        self.interactivity().paint_when_clipped();
+        self
+    }
 }
 
 /// A trait for elements that want to use the standard GPUI interactivity features
@@ -961,7 +1088,7 @@ pub trait StatefulInteractiveElement: InteractiveElement {
     fn on_drag<T, W>(
         mut self,
         value: T,
-        constructor: impl Fn(&T, &mut WindowContext) -> View<W> + 'static,
+        constructor: impl Fn(&T, &PointerPosition, &mut WindowContext) -> View<W> + 'static,
     ) -> Self
     where
         Self: Sized,
@@ -985,6 +1112,24 @@ pub trait StatefulInteractiveElement: InteractiveElement {
         self
     }
 
+    /// Bind the given callback to the mouse resting on this element for the given delay. It's
+    /// called once per visit, and right away if the mouse just came from an element whose
+    /// delayed hover fired, see [`WindowContext::on_hover_delayed`].
+    /// The fluent API equivalent to [`Interactivity::on_hover_delayed`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
+    fn on_hover_delayed(
+        mut self,
+        delay: Duration,
+        listener: impl Fn(&mut WindowContext) + 'static,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().on_hover_delayed(delay, listener);
+        self
+    }
+
     /// Use the given callback to construct a new tooltip view when the mouse hovers over this element.
     /// The fluent API equivalent to [`Interactivity::tooltip`]
     fn tooltip(mut self, build_tooltip: impl Fn(&mut WindowContext) -> AnyView + 'static) -> Self
@@ -1029,6 +1174,17 @@ pub trait FocusableElement: InteractiveElement {
         self.interactivity().in_focus_style = Some(Box::new(f(StyleRefinement::default())));
         self
     }
+
+    /// Set the given styles to be applied when this element is focused and the window's last
+    /// input came from the keyboard, see [`WindowContext::input_modality`]. Use this for focus
+    /// rings that shouldn't be shown to someone clicking around with the mouse.
+    fn focus_visible(mut self, f: impl FnOnce(StyleRefinement) -> StyleRefinement) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().focus_visible_style = Some(Box::new(f(StyleRefinement::default())));
+        self
+    }
 }
 
 pub(crate) type MouseDownListener =
@@ -1044,7 +1200,8 @@ pub(crate) type ScrollWheelListener =
 
 pub(crate) type ClickListener = Box<dyn Fn(&ClickEvent, &mut WindowContext) + 'static>;
 
-pub(crate) type DragListener = Box<dyn Fn(&dyn Any, &mut WindowContext) -> AnyView + 'static>;
+pub(crate) type DragListener =
+    Box<dyn Fn(&dyn Any, &PointerPosition, &mut WindowContext) -> AnyView + 'static>;
 
 type DropListener = Box<dyn Fn(&dyn Any, &mut WindowContext) + 'static>;
 
@@ -1064,6 +1221,9 @@ pub(crate) type KeyUpListener =
 pub(crate) type ModifiersChangedListener =
     Box<dyn Fn(&ModifiersChangedEvent, &mut WindowContext) + 'static>;
 
+pub(crate) type ViewportChangeListener =
+    Box<dyn Fn(&ViewportChangeEvent, &mut WindowContext) + 'static>;
+
 pub(crate) type ActionListener = Box<dyn Fn(&dyn Any, DispatchPhase, &mut WindowContext) + 'static>;
 
 /// Construct a new [`Div`] element
@@ -1084,6 +1244,13 @@ pub fn div() -> Div {
     }
 }
 
+/// Construct a [`Div`] that fills its parent and moves the window when dragged,
+/// see [`InteractiveElement::window_drag_region`]
+#[track_caller]
+pub fn window_drag_handle() -> Div {
+    div().size_full().window_drag_region()
+}
+
 /// A [`Div`] element, the all-in-one element for building complex UIs in GPUI
 pub struct Div {
     interactivity: Interactivity,
@@ -1160,15 +1327,30 @@ impl Element for Div {
             bounds.size
         } else if let Some(scroll_handle) = self.interactivity.tracked_scroll_handle.as_ref() {
             let mut state = scroll_handle.0.borrow_mut();
+            // Found in the previous frame's layout with the current offset, so that it follows
+            // any scrolling since then.
+            let anchor = state
+                .anchor_to_top_item
+                .then(|| state.top_item_anchor())
+                .flatten();
             state.child_bounds = Vec::with_capacity(request_layout.child_layout_ids.len());
+            state.child_ids.clear();
             state.bounds = bounds;
             let requested = state.requested_scroll_top.take();
 
-            for (ix, child_layout_id) in request_layout.child_layout_ids.iter().enumerate() {
+            for (ix, (child_layout_id, child)) in request_layout
+                .child_layout_ids
+                .iter()
+                .zip(&self.children)
+                .enumerate()
+            {
                 let child_bounds = cx.layout_bounds(*child_layout_id);
                 child_min = child_min.min(&child_bounds.origin);
                 child_max = child_max.max(&child_bounds.lower_right());
                 state.child_bounds.push(child_bounds);
+                if state.anchor_to_top_item {
+                    state.child_ids.push(child.element_id());
+                }
 
                 if let Some(requested) = requested.as_ref() {
                     if requested.0 == ix {
@@ -1177,6 +1359,17 @@ impl Element for Div {
                     }
                 }
             }
+
+            if let Some((anchor_id, anchor_top)) = anchor.filter(|_| requested.is_none()) {
+                let anchor_ix = state
+                    .child_ids
+                    .iter()
+                    .position(|id| id.as_ref() == Some(&anchor_id));
+                if let Some(anchor_ix) = anchor_ix {
+                    state.offset.borrow_mut().y =
+                        bounds.top() + anchor_top - state.child_bounds[anchor_ix].top();
+                }
+            }
             (child_max - child_min).into()
         } else {
             for child_layout_id in &request_layout.child_layout_ids {
@@ -1218,6 +1411,10 @@ impl Element for Div {
                 }
             });
     }
+
+    fn cullable(&self) -> bool {
+        !self.interactivity.paint_when_clipped
+    }
 }
 
 impl IntoElement for Div {
@@ -1246,12 +1443,14 @@ pub struct Interactivity {
     pub(crate) tracked_focus_handle: Option<FocusHandle>,
     pub(crate) tracked_scroll_handle: Option<ScrollHandle>,
     pub(crate) scroll_offset: Option<Rc<RefCell<Point<Pixels>>>>,
+    pub(crate) scroll_motion: Option<Rc<RefCell<ScrollMotion>>>,
     pub(crate) group: Option<SharedString>,
     /// The base style of the element, before any modifications are applied
     /// by focus, active, etc.
     pub base_style: Box<StyleRefinement>,
     pub(crate) focus_style: Option<Box<StyleRefinement>>,
     pub(crate) in_focus_style: Option<Box<StyleRefinement>>,
+    pub(crate) focus_visible_style: Option<Box<StyleRefinement>>,
     pub(crate) hover_style: Option<Box<StyleRefinement>>,
     pub(crate) group_hover_style: Option<GroupStyle>,
     pub(crate) active_style: Option<Box<StyleRefinement>>,
@@ -1268,14 +1467,20 @@ pub struct Interactivity {
     pub(crate) key_down_listeners: Vec<KeyDownListener>,
     pub(crate) key_up_listeners: Vec<KeyUpListener>,
     pub(crate) modifiers_changed_listeners: Vec<ModifiersChangedListener>,
+    pub(crate) viewport_change_listeners: Vec<ViewportChangeListener>,
     pub(crate) action_listeners: Vec<(TypeId, ActionListener)>,
     pub(crate) drop_listeners: Vec<(TypeId, DropListener)>,
     pub(crate) can_drop_predicate: Option<CanDropPredicate>,
     pub(crate) click_listeners: Vec<ClickListener>,
     pub(crate) drag_listener: Option<(Box<dyn Any>, DragListener)>,
     pub(crate) hover_listener: Option<Box<dyn Fn(&bool, &mut WindowContext)>>,
+    pub(crate) hover_delayed_listeners: Vec<(Duration, Rc<dyn Fn(&mut WindowContext)>)>,
     pub(crate) tooltip_builder: Option<TooltipBuilder>,
     pub(crate) occlude_mouse: bool,
+    pub(crate) hit_slop: Option<Edges<Pixels>>,
+    pub(crate) min_click_size: Option<Pixels>,
+    pub(crate) window_drag_region: bool,
+    pub(crate) paint_when_clipped: bool,
 
     #[cfg(debug_assertions)]
     pub(crate) location: Option<core::panic::Location<'static>>,
@@ -1328,7 +1533,9 @@ impl Interactivity {
                 }
 
                 if let Some(scroll_handle) = self.tracked_scroll_handle.as_ref() {
-                    self.scroll_offset = Some(scroll_handle.0.borrow().offset.clone());
+                    let scroll_handle = scroll_handle.0.borrow();
+                    self.scroll_offset = Some(scroll_handle.offset.clone());
+                    self.scroll_motion = Some(scroll_handle.motion.clone());
                 } else if self.base_style.overflow.x == Some(Overflow::Scroll)
                     || self.base_style.overflow.y == Some(Overflow::Scroll)
                 {
@@ -1339,6 +1546,12 @@ impl Interactivity {
                                 .get_or_insert_with(|| Rc::default())
                                 .clone(),
                         );
+                        self.scroll_motion = Some(
+                            element_state
+                                .scroll_motion
+                                .get_or_insert_with(|| Rc::default())
+                                .clone(),
+                        );
                     }
                 }
 
@@ -1374,24 +1587,28 @@ impl Interactivity {
 
                     if let Some(active_tooltip) = element_state.active_tooltip.as_ref() {
                         if let Some(active_tooltip) = active_tooltip.borrow().as_ref() {
-                            if let Some(tooltip) = active_tooltip.tooltip.clone() {
-                                self.tooltip_id = Some(cx.set_tooltip(tooltip));
-                            }
+                            self.tooltip_id = Some(cx.set_tooltip(active_tooltip.tooltip.clone()));
                         }
                     }
                 }
 
-                cx.with_text_style(style.text_style().cloned(), |cx| {
-                    cx.with_content_mask(style.overflow_mask(bounds, cx.rem_size()), |cx| {
-                        let hitbox = if self.should_insert_hitbox(&style) {
-                            Some(cx.insert_hitbox(bounds, self.occlude_mouse))
-                        } else {
-                            None
-                        };
+                style.with_elevation(cx, |cx| {
+                    cx.with_text_style(style.text_style().cloned(), |cx| {
+                        cx.with_content_mask(style.overflow_mask(bounds, cx.rem_size()), |cx| {
+                            let hitbox = if self.should_insert_hitbox(&style) {
+                                Some(cx.insert_hitbox_with_slop(
+                                    bounds,
+                                    self.occlude_mouse,
+                                    self.effective_hit_slop(bounds),
+                                ))
+                            } else {
+                                None
+                            };
 
-                        let scroll_offset = self.clamp_scroll_position(bounds, &style, cx);
-                        let result = f(&style, scroll_offset, hitbox, cx);
-                        (result, element_state)
+                            let scroll_offset = self.clamp_scroll_position(bounds, &style, cx);
+                            let result = f(&style, scroll_offset, hitbox, cx);
+                            (result, element_state)
+                        })
                     })
                 })
             },
@@ -1414,6 +1631,8 @@ impl Interactivity {
             || self.drag_listener.is_some()
             || !self.drop_listeners.is_empty()
             || self.tooltip_builder.is_some()
+            || !self.hover_delayed_listeners.is_empty()
+            || self.window_drag_region
     }
 
     fn clamp_scroll_position(
@@ -1423,10 +1642,6 @@ impl Interactivity {
         cx: &mut WindowContext,
     ) -> Point<Pixels> {
         if let Some(scroll_offset) = self.scroll_offset.as_ref() {
-            if let Some(scroll_handle) = &self.tracked_scroll_handle {
-                scroll_handle.0.borrow_mut().overflow = style.overflow;
-            }
-
             let rem_size = cx.rem_size();
             let padding_size = size(
                 style
@@ -1447,17 +1662,81 @@ impl Interactivity {
                         .to_pixels(bounds.size.height.into(), rem_size),
             );
             let scroll_max = (self.content_size + padding_size - bounds.size).max(&Size::default());
+            let mut scroll_offset = scroll_offset.borrow_mut();
+            let mut motion = self
+                .scroll_motion
+                .as_ref()
+                .map(|scroll_motion| scroll_motion.borrow_mut());
+            let animation_duration = cx
+                .animation_timing(&Animation::new(SCROLL_ANIMATION_DURATION).movement())
+                .duration;
+            let now = cx.background_executor().now();
+            if let Some(offset) = motion
+                .as_mut()
+                .and_then(|motion| motion.step(*scroll_offset, now, animation_duration))
+            {
+                *scroll_offset = offset;
+            }
+
+            if let Some(scroll_handle) = &self.tracked_scroll_handle {
+                let mut state = scroll_handle.0.borrow_mut();
+                state.overflow = style.overflow;
+                // Compared with the previous frame's extent, so that content the user has
+                // scrolled up from since then stays where they left it.
+                if state.stick_to_bottom
+                    && scroll_offset.y <= -state.scroll_max.height + STICK_TO_BOTTOM_TOLERANCE
+                {
+                    scroll_offset.y = -scroll_max.height;
+                }
+                state.scroll_max = scroll_max;
+            }
+
             // Clamp scroll offset in case scroll max is smaller now (e.g., if children
             // were removed or the bounds became larger).
-            let mut scroll_offset = scroll_offset.borrow_mut();
+            let unclamped_offset = *scroll_offset;
             scroll_offset.x = scroll_offset.x.clamp(-scroll_max.width, px(0.));
             scroll_offset.y = scroll_offset.y.clamp(-scroll_max.height, px(0.));
+
+            if let Some(motion) = motion.as_mut() {
+                // Momentum ends at the edges of the content, including when it shrinks below the
+                // current offset. Animations keep going, towards the clamped target.
+                if *scroll_offset != unclamped_offset
+                    && matches!(**motion, ScrollMotion::Momentum { .. })
+                {
+                    **motion = ScrollMotion::Idle;
+                }
+                if motion.is_moving() {
+                    cx.request_frame(FrameReason::Scroll);
+                }
+            }
             *scroll_offset
         } else {
             Point::default()
         }
     }
 
+    /// Whether the element's content is wider than its content box and cut off by the element.
+    fn clips_content_horizontally(
+        &self,
+        bounds: Bounds<Pixels>,
+        style: &Style,
+        cx: &WindowContext,
+    ) -> bool {
+        if style.overflow.x != Overflow::Hidden {
+            return false;
+        }
+        let rem_size = cx.rem_size();
+        let padding = style
+            .padding
+            .left
+            .to_pixels(bounds.size.width.into(), rem_size)
+            + style
+                .padding
+                .right
+                .to_pixels(bounds.size.width.into(), rem_size);
+        self.content_size.width + padding > bounds.size.width + px(0.5)
+    }
+
     /// Paint this element according to this interactivity state's configured styles
     /// and bind the element's mouse and keyboard events.
     ///
@@ -1491,6 +1770,14 @@ impl Interactivity {
                         .insert(debug_selector.clone(), bounds);
                 }
 
+                if let Some(global_id) = global_id {
+                    cx.record_element_bounds(global_id, bounds);
+                    if cx.is_layout_watched(global_id) {
+                        let text_truncated = self.clips_content_horizontally(bounds, &style, cx);
+                        cx.record_layout(global_id, bounds, text_truncated);
+                    }
+                }
+
                 self.paint_hover_group_handler(cx);
 
                 if style.visibility == Visibility::Hidden {
@@ -1510,21 +1797,28 @@ impl Interactivity {
                                     }
                                 }
 
-                                if let Some(group) = self.group.clone() {
-                                    GroupHitboxes::push(group, hitbox.id, cx);
-                                }
-
                                 self.paint_mouse_listeners(hitbox, element_state.as_mut(), cx);
                                 self.paint_scroll_listener(hitbox, &style, cx);
                             }
 
                             self.paint_keyboard_listeners(cx);
-                            f(&style, cx);
+                            for listener in mem::take(&mut self.viewport_change_listeners) {
+                                cx.on_viewport_change(listener);
+                            }
 
-                            if hitbox.is_some() {
-                                if let Some(group) = self.group.as_ref() {
-                                    GroupHitboxes::pop(group, cx);
+                            match (self.group.clone(), hitbox) {
+                                (Some(name), Some(hitbox)) => {
+                                    let group = InteractiveGroup {
+                                        name,
+                                        hitbox_id: hitbox.id,
+                                        parent: cx
+                                            .context::<InteractiveGroup>()
+                                            .cloned()
+                                            .map(Rc::new),
+                                    };
+                                    cx.with_context(Rc::new(group), |cx| f(&style, cx));
                                 }
+                                _ => f(&style, cx),
                             }
                         });
                     });
@@ -1559,6 +1853,7 @@ impl Interactivity {
                         FONT_SIZE,
                         &[cx.text_style().to_run(str_len)],
                         None,
+                        crate::WrapMode::None,
                     )
                     .ok()
                     .and_then(|mut text| text.pop())
@@ -1670,6 +1965,25 @@ impl Interactivity {
             });
         }
 
+        // Descendants that insert hitboxes of their own are painted on top of this one, so only
+        // start moving the window when the press lands on a part of the region they don't cover.
+        if self.window_drag_region {
+            let hitbox = hitbox.clone();
+            cx.on_mouse_event(move |event: &MouseDownEvent, phase, cx| {
+                if phase == DispatchPhase::Bubble
+                    && event.button == MouseButton::Left
+                    && cx.window.mouse_hit_test.0.first() == Some(&hitbox.id)
+                {
+                    if event.click_count == 2 {
+                        cx.zoom_window();
+                    } else {
+                        cx.start_system_move();
+                    }
+                    cx.stop_propagation();
+                }
+            });
+        }
+
         for listener in self.mouse_down_listeners.drain(..) {
             let hitbox = hitbox.clone();
             cx.on_mouse_event(move |event: &MouseDownEvent, phase, cx| {
@@ -1791,8 +2105,10 @@ impl Interactivity {
                             {
                                 if let Some((drag_value, drag_listener)) = drag_listener.take() {
                                     *clicked_state.borrow_mut() = ElementClickedState::default();
-                                    let cursor_offset = event.position - hitbox.origin;
-                                    let drag = (drag_listener)(drag_value.as_ref(), cx);
+                                    let pointer =
+                                        PointerPosition::new(event.position, hitbox.bounds);
+                                    let cursor_offset = pointer.local_position;
+                                    let drag = (drag_listener)(drag_value.as_ref(), &pointer, cx);
                                     cx.active_drag = Some(AnyDrag {
                                         view: drag,
                                         value: drag_value,
@@ -1815,9 +2131,11 @@ impl Interactivity {
                         // so that it happens even if another event handler stops
                         // propagation.
                         DispatchPhase::Capture => {
-                            let mut pending_mouse_down = pending_mouse_down.borrow_mut();
-                            if pending_mouse_down.is_some() && hitbox.is_hovered(cx) {
-                                captured_mouse_down = pending_mouse_down.take();
+                            // Released elsewhere, the press ends without a click.
+                            if let Some(mouse_down) = pending_mouse_down.borrow_mut().take() {
+                                if cx.is_press_released_on(&hitbox, event.position) {
+                                    captured_mouse_down = Some(mouse_down);
+                                }
                                 cx.refresh();
                             }
                         }
@@ -1827,6 +2145,7 @@ impl Interactivity {
                                 let mouse_click = ClickEvent {
                                     down: mouse_down,
                                     up: event.clone(),
+                                    pointer: PointerPosition::new(event.position, hitbox.bounds),
                                 };
                                 for listener in &click_listeners {
                                     listener(&mouse_click, cx);
@@ -1837,7 +2156,19 @@ impl Interactivity {
                 });
             }
 
+            for (ix, (delay, listener)) in mem::take(&mut self.hover_delayed_listeners)
+                .into_iter()
+                .enumerate()
+            {
+                cx.with_element_namespace(
+                    ElementId::NamedInteger("hover_delayed".into(), ix),
+                    |cx| cx.on_hover_delayed(hitbox, delay, move |cx| listener(cx)),
+                );
+            }
+
+            let mut on_cancel = None;
             if let Some(hover_listener) = self.hover_listener.take() {
+                let hover_listener: Rc<dyn Fn(&bool, &mut WindowContext)> = hover_listener.into();
                 let hitbox = hitbox.clone();
                 let was_hovered = element_state
                     .hover_state
@@ -1848,6 +2179,17 @@ impl Interactivity {
                     .get_or_insert_with(Default::default)
                     .clone();
 
+                // If the element goes away while it's hovered, the mouse leaves it.
+                on_cancel = Some(Rc::new({
+                    let hover_listener = hover_listener.clone();
+                    let was_hovered = was_hovered.clone();
+                    move |cx: &mut WindowContext| {
+                        if mem::take(&mut *was_hovered.borrow_mut()) {
+                            hover_listener(&false, cx);
+                        }
+                    }
+                }) as Rc<dyn Fn(&mut WindowContext)>);
+
                 cx.on_mouse_event(move |_: &MouseMoveEvent, phase, cx| {
                     if phase != DispatchPhase::Bubble {
                         return;
@@ -1865,6 +2207,7 @@ impl Interactivity {
                     }
                 });
             }
+            cx.register_interactive_region(hitbox, on_cancel);
 
             if let Some(tooltip_builder) = self.tooltip_builder.take() {
                 let tooltip_is_hoverable = tooltip_builder.hoverable;
@@ -1879,9 +2222,10 @@ impl Interactivity {
 
                 cx.on_mouse_event({
                     let active_tooltip = active_tooltip.clone();
+                    let pending_mouse_down = pending_mouse_down.clone();
                     let hitbox = hitbox.clone();
                     let tooltip_id = self.tooltip_id;
-                    move |_: &MouseMoveEvent, phase, cx| {
+                    move |_: &MouseMoveEvent, _, cx| {
                         let is_hovered =
                             pending_mouse_down.borrow().is_none() && hitbox.is_hovered(cx);
                         let tooltip_is_hovered =
@@ -1890,39 +2234,25 @@ impl Interactivity {
                             if active_tooltip.borrow_mut().take().is_some() {
                                 cx.refresh();
                             }
-
-                            return;
                         }
+                    }
+                });
 
-                        if phase != DispatchPhase::Bubble {
+                cx.with_element_namespace("tooltip", |cx| {
+                    let active_tooltip = active_tooltip.clone();
+                    let build_tooltip = tooltip_builder.build.clone();
+                    cx.on_hover_delayed(hitbox, TOOLTIP_DELAY, move |cx| {
+                        if pending_mouse_down.borrow().is_some() {
                             return;
                         }
-
-                        if active_tooltip.borrow().is_none() {
-                            let task = cx.spawn({
-                                let active_tooltip = active_tooltip.clone();
-                                let build_tooltip = tooltip_builder.build.clone();
-                                move |mut cx| async move {
-                                    cx.background_executor().timer(TOOLTIP_DELAY).await;
-                                    cx.update(|cx| {
-                                        active_tooltip.borrow_mut().replace(ActiveTooltip {
-                                            tooltip: Some(AnyTooltip {
-                                                view: build_tooltip(cx),
-                                                mouse_position: cx.mouse_position(),
-                                            }),
-                                            _task: None,
-                                        });
-                                        cx.refresh();
-                                    })
-                                    .ok();
-                                }
-                            });
-                            active_tooltip.borrow_mut().replace(ActiveTooltip {
-                                tooltip: None,
-                                _task: Some(task),
-                            });
-                        }
-                    }
+                        active_tooltip.borrow_mut().replace(ActiveTooltip {
+                            tooltip: AnyTooltip {
+                                view: build_tooltip(cx),
+                                mouse_position: cx.mouse_position(),
+                            },
+                        });
+                        cx.refresh();
+                    });
                 });
 
                 cx.on_mouse_event({
@@ -1970,7 +2300,7 @@ impl Interactivity {
                 let active_group_hitbox = self
                     .group_active_style
                     .as_ref()
-                    .and_then(|group_active| GroupHitboxes::get(&group_active.group, cx));
+                    .and_then(|group_active| InteractiveGroup::hitbox_id(&group_active.group, cx));
                 let hitbox = hitbox.clone();
                 cx.on_mouse_event(move |_: &MouseDownEvent, phase, cx| {
                     if phase == DispatchPhase::Bubble && !cx.default_prevented() {
@@ -2029,14 +2359,20 @@ impl Interactivity {
         let group_hitbox = self
             .group_hover_style
             .as_ref()
-            .and_then(|group_hover| GroupHitboxes::get(&group_hover.group, cx));
+            .and_then(|group_hover| InteractiveGroup::hitbox_id(&group_hover.group, cx));
 
         if let Some(group_hitbox) = group_hitbox {
             let was_hovered = group_hitbox.is_hovered(cx);
+            // Only the view drawing this element needs to be redrawn for its style to change.
+            let parent_id = cx.parent_view_id();
             cx.on_mouse_event(move |_: &MouseMoveEvent, phase, cx| {
                 let hovered = group_hitbox.is_hovered(cx);
                 if phase == DispatchPhase::Capture && hovered != was_hovered {
-                    cx.refresh();
+                    if let Some(parent_id) = parent_id {
+                        cx.notify(parent_id)
+                    } else {
+                        cx.refresh()
+                    }
                 }
             });
         }
@@ -2044,6 +2380,7 @@ impl Interactivity {
 
     fn paint_scroll_listener(&self, hitbox: &Hitbox, style: &Style, cx: &mut WindowContext) {
         if let Some(scroll_offset) = self.scroll_offset.clone() {
+            let scroll_motion = self.scroll_motion.clone();
             let overflow = style.overflow;
             let line_height = cx.line_height();
             let hitbox = hitbox.clone();
@@ -2075,8 +2412,19 @@ impl Interactivity {
                         scroll_offset.y += delta_y;
                     }
 
+                    let mut is_moving = false;
+                    if let Some(scroll_motion) = scroll_motion.as_ref() {
+                        let mut scroll_motion = scroll_motion.borrow_mut();
+                        scroll_motion.handle_scroll_event(
+                            event.touch_phase,
+                            *scroll_offset - old_scroll_offset,
+                            cx.background_executor().now(),
+                        );
+                        is_moving = scroll_motion.is_moving();
+                    }
+
                     cx.stop_propagation();
-                    if *scroll_offset != old_scroll_offset {
+                    if *scroll_offset != old_scroll_offset || is_moving {
                         cx.refresh();
                     }
                 }
@@ -2121,13 +2469,19 @@ impl Interactivity {
                     style.refine(focus_style);
                 }
             }
+
+            if let Some(focus_visible_style) = self.focus_visible_style.as_ref() {
+                if focus_handle.is_focused(cx) && cx.input_modality() == InputModality::Keyboard {
+                    style.refine(focus_visible_style);
+                }
+            }
         }
 
         if let Some(hitbox) = hitbox {
             if !cx.has_active_drag() {
                 if let Some(group_hover) = self.group_hover_style.as_ref() {
                     if let Some(group_hitbox_id) =
-                        GroupHitboxes::get(&group_hover.group, cx.deref_mut())
+                        InteractiveGroup::hitbox_id(&group_hover.group, cx)
                     {
                         if group_hitbox_id.is_hovered(cx) {
                             style.refine(&group_hover.style);
@@ -2151,7 +2505,7 @@ impl Interactivity {
                 if can_drop {
                     for (state_type, group_drag_style) in &self.group_drag_over_styles {
                         if let Some(group_hitbox_id) =
-                            GroupHitboxes::get(&group_drag_style.group, cx.deref_mut())
+                            InteractiveGroup::hitbox_id(&group_drag_style.group, cx)
                         {
                             if *state_type == drag.value.as_ref().type_id()
                                 && group_hitbox_id.is_hovered(cx)
@@ -2203,13 +2557,13 @@ pub struct InteractiveElementState {
     pub(crate) hover_state: Option<Rc<RefCell<bool>>>,
     pub(crate) pending_mouse_down: Option<Rc<RefCell<Option<MouseDownEvent>>>>,
     pub(crate) scroll_offset: Option<Rc<RefCell<Point<Pixels>>>>,
+    pub(crate) scroll_motion: Option<Rc<RefCell<ScrollMotion>>>,
     pub(crate) active_tooltip: Option<Rc<RefCell<Option<ActiveTooltip>>>>,
 }
 
 /// The current active tooltip
 pub struct ActiveTooltip {
-    pub(crate) tooltip: Option<AnyTooltip>,
-    pub(crate) _task: Option<Task<()>>,
+    pub(crate) tooltip: AnyTooltip,
 }
 
 /// Whether or not the element or a group that contains it is clicked by the mouse.
@@ -2228,30 +2582,27 @@ impl ElementClickedState {
     }
 }
 
-#[derive(Default)]
-pub(crate) struct GroupHitboxes(HashMap<SharedString, SmallVec<[HitboxId; 1]>>);
-
-impl Global for GroupHitboxes {}
-
-impl GroupHitboxes {
-    pub fn get(name: &SharedString, cx: &mut AppContext) -> Option<HitboxId> {
-        cx.default_global::<Self>()
-            .0
-            .get(name)
-            .and_then(|bounds_stack| bounds_stack.last())
-            .cloned()
-    }
-
-    pub fn push(name: SharedString, hitbox_id: HitboxId, cx: &mut AppContext) {
-        cx.default_global::<Self>()
-            .0
-            .entry(name)
-            .or_default()
-            .push(hitbox_id);
-    }
+/// A group of elements enclosing the element being drawn, provided to the group's descendants
+/// as a context value, see [`InteractiveElement::group`].
+#[derive(Clone)]
+pub(crate) struct InteractiveGroup {
+    name: SharedString,
+    hitbox_id: HitboxId,
+    /// The group enclosing this one.
+    parent: Option<Rc<InteractiveGroup>>,
+}
 
-    pub fn pop(name: &SharedString, cx: &mut AppContext) {
-        cx.default_global::<Self>().0.get_mut(name).unwrap().pop();
+impl InteractiveGroup {
+    /// The hitbox of the innermost group with the given name enclosing the element being drawn.
+    pub fn hitbox_id(name: &SharedString, cx: &WindowContext) -> Option<HitboxId> {
+        let mut group = cx.context::<Self>();
+        while let Some(current) = group {
+            if current.name == *name {
+                return Some(current.hitbox_id);
+            }
+            group = current.parent.as_deref();
+        }
+        None
     }
 }
 
@@ -2322,6 +2673,10 @@ where
     ) {
         self.element.paint(id, bounds, request_layout, prepaint, cx)
     }
+
+    fn cullable(&self) -> bool {
+        self.element.cullable()
+    }
 }
 
 impl<E> IntoElement for Focusable<E>
@@ -2415,6 +2770,10 @@ where
     ) {
         self.element.paint(id, bounds, request_layout, prepaint, cx);
     }
+
+    fn cullable(&self) -> bool {
+        self.element.cullable()
+    }
 }
 
 impl<E> IntoElement for Stateful<E>
@@ -2437,13 +2796,188 @@ where
     }
 }
 
+/// The motion of a scroll offset that continues across frames, independently of scroll events.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum ScrollMotion {
+    /// The offset only changes in response to scroll events.
+    #[default]
+    Idle,
+    /// A trackpad gesture is in progress, and its velocity is being tracked in pixels per second
+    /// so that it can continue with momentum when it ends.
+    Tracking {
+        velocity: Point<Pixels>,
+        last_event: Instant,
+    },
+    /// Coasting after a trackpad gesture ended, slowing down with friction.
+    Momentum {
+        velocity: Point<Pixels>,
+        last_frame: Instant,
+    },
+    /// Easing towards an offset requested with [`ScrollHandle::scroll_to`]. The animation starts
+    /// with the first frame that steps it, so that it follows the window's clock.
+    Animating {
+        from: Point<Pixels>,
+        to: Point<Pixels>,
+        start: Option<Instant>,
+    },
+}
+
+impl ScrollMotion {
+    /// Update the motion for a scroll event that moved the offset by `delta`.
+    fn handle_scroll_event(&mut self, touch_phase: TouchPhase, delta: Point<Pixels>, now: Instant) {
+        *self = match (touch_phase, *self) {
+            (TouchPhase::Started, _) => ScrollMotion::Tracking {
+                velocity: Point::default(),
+                last_event: now,
+            },
+            (
+                TouchPhase::Moved,
+                ScrollMotion::Tracking {
+                    velocity,
+                    last_event,
+                },
+            ) => {
+                let elapsed = now.saturating_duration_since(last_event).as_secs_f32();
+                let velocity = if elapsed > 0. {
+                    // Smooth out the jitter between individual events.
+                    velocity * 0.5 + delta * (0.5 / elapsed)
+                } else {
+                    velocity
+                };
+                ScrollMotion::Tracking {
+                    velocity,
+                    last_event: now,
+                }
+            }
+            (
+                TouchPhase::Ended,
+                ScrollMotion::Tracking {
+                    velocity,
+                    last_event,
+                },
+            ) if now.saturating_duration_since(last_event) < SCROLL_MOMENTUM_TIMEOUT
+                && velocity_magnitude(velocity) > MIN_SCROLL_MOMENTUM_VELOCITY =>
+            {
+                ScrollMotion::Momentum {
+                    velocity,
+                    last_frame: now,
+                }
+            }
+            // Any other scroll event, such as the user touching the trackpad again, a mouse wheel
+            // or the platform's own momentum events, takes over from the current motion.
+            _ => ScrollMotion::Idle,
+        };
+    }
+
+    /// Advance the motion to `now`, returning the new offset if it moved. Animated scrolls take
+    /// the given duration, which is zero when they should jump to where they're going.
+    fn step(
+        &mut self,
+        offset: Point<Pixels>,
+        now: Instant,
+        animation_duration: Duration,
+    ) -> Option<Point<Pixels>> {
+        match *self {
+            ScrollMotion::Idle | ScrollMotion::Tracking { .. } => None,
+            ScrollMotion::Momentum {
+                velocity,
+                last_frame,
+            } => {
+                let elapsed = now.saturating_duration_since(last_frame).as_secs_f32();
+                let decay = SCROLL_MOMENTUM_FRICTION.powf(elapsed);
+                // The distance covered by the exponentially decaying velocity over that time.
+                let distance = velocity * ((decay - 1.) / SCROLL_MOMENTUM_FRICTION.ln());
+                let velocity = velocity * decay;
+                *self = if velocity_magnitude(velocity) < MIN_SCROLL_MOMENTUM_VELOCITY {
+                    ScrollMotion::Idle
+                } else {
+                    ScrollMotion::Momentum {
+                        velocity,
+                        last_frame: now,
+                    }
+                };
+                Some(offset + distance)
+            }
+            ScrollMotion::Animating { from, to, start } => {
+                let start = start.unwrap_or(now);
+                let elapsed = now.saturating_duration_since(start);
+                if elapsed >= animation_duration {
+                    *self = ScrollMotion::Idle;
+                    return Some(to);
+                }
+                *self = ScrollMotion::Animating {
+                    from,
+                    to,
+                    start: Some(start),
+                };
+                let delta = elapsed.as_secs_f32() / animation_duration.as_secs_f32();
+                Some(from + (to - from) * ease_in_out(delta))
+            }
+        }
+    }
+
+    fn is_moving(&self) -> bool {
+        matches!(
+            self,
+            ScrollMotion::Momentum { .. } | ScrollMotion::Animating { .. }
+        )
+    }
+}
+
+fn velocity_magnitude(velocity: Point<Pixels>) -> f32 {
+    velocity.x.0.hypot(velocity.y.0)
+}
+
+/// Whether [`ScrollHandle::scroll_to`] jumps straight to its target or animates there.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScrollBehavior {
+    /// Jump straight to the target.
+    #[default]
+    Instant,
+    /// Ease into the target over a short animation.
+    Animated,
+}
+
+/// Where to scroll to with [`ScrollHandle::scroll_to`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScrollTarget {
+    /// The given scroll offset, see [`ScrollHandle::set_offset`].
+    Offset(Point<Pixels>),
+    /// The closest offset at which the child at the given index is fully visible.
+    Item(usize),
+}
+
 #[derive(Default, Debug)]
 struct ScrollHandleState {
     offset: Rc<RefCell<Point<Pixels>>>,
+    motion: Rc<RefCell<ScrollMotion>>,
     bounds: Bounds<Pixels>,
     child_bounds: Vec<Bounds<Pixels>>,
+    /// The children's element ids, which are only collected while anchoring to the top item.
+    child_ids: Vec<Option<ElementId>>,
     requested_scroll_top: Option<(usize, Pixels)>,
     overflow: Point<Overflow>,
+    /// How far the content could be scrolled in the last frame.
+    scroll_max: Size<Pixels>,
+    anchor_to_top_item: bool,
+    stick_to_bottom: bool,
+}
+
+impl ScrollHandleState {
+    /// The id of the visible child at the top of the last frame's layout, and its top relative
+    /// to the top of the scrolled element at the current offset.
+    fn top_item_anchor(&self) -> Option<(ElementId, Pixels)> {
+        let top = self.bounds.top() - self.offset.borrow().y;
+        let ix = self
+            .child_bounds
+            .iter()
+            .position(|bounds| bounds.bottom() > top)?;
+        let id = self.child_ids.get(ix)?.clone()?;
+        Some((
+            id,
+            self.child_bounds[ix].top() + self.offset.borrow().y - self.bounds.top(),
+        ))
+    }
 }
 
 /// A handle to the scrollable aspects of an element.
@@ -2506,13 +3040,46 @@ impl ScrollHandle {
     /// scroll_to_item scrolls the minimal amount to ensure that the child is
     /// fully visible
     pub fn scroll_to_item(&self, ix: usize) {
-        let state = self.0.borrow();
+        self.scroll_to(ScrollTarget::Item(ix), ScrollBehavior::Instant);
+    }
 
-        let Some(bounds) = state.child_bounds.get(ix) else {
-            return;
+    /// Scroll to the given target, either immediately or by animating there. If the content
+    /// changes during the animation, the offset stays clamped to the new content. As with
+    /// [`Self::set_offset`], the view containing the scrolled element must be notified for the
+    /// scroll to take effect.
+    pub fn scroll_to(&self, target: ScrollTarget, behavior: ScrollBehavior) {
+        let offset = match target {
+            ScrollTarget::Offset(offset) => offset,
+            ScrollTarget::Item(ix) => {
+                let Some(offset) = self.offset_for_item(ix) else {
+                    return;
+                };
+                offset
+            }
         };
 
-        let mut scroll_offset = state.offset.borrow_mut();
+        let state = self.0.borrow();
+        let mut motion = state.motion.borrow_mut();
+        match behavior {
+            ScrollBehavior::Instant => {
+                *motion = ScrollMotion::Idle;
+                *state.offset.borrow_mut() = offset;
+            }
+            ScrollBehavior::Animated => {
+                *motion = ScrollMotion::Animating {
+                    from: *state.offset.borrow(),
+                    to: offset,
+                    start: None,
+                };
+            }
+        }
+    }
+
+    /// The offset that scrolls the minimal amount to ensure that the child is fully visible.
+    fn offset_for_item(&self, ix: usize) -> Option<Point<Pixels>> {
+        let state = self.0.borrow();
+        let bounds = state.child_bounds.get(ix)?;
+        let mut scroll_offset = *state.offset.borrow();
 
         if state.overflow.y == Overflow::Scroll {
             if bounds.top() + scroll_offset.y < state.bounds.top() {
@@ -2529,6 +3096,8 @@ impl ScrollHandle {
                 scroll_offset.x = state.bounds.right() - bounds.right();
             }
         }
+
+        Some(scroll_offset)
     }
 
     /// Set the offset explicitly. The offset is the distance from the top left of the
@@ -2536,6 +3105,7 @@ impl ScrollHandle {
     /// As you scroll further down the offset becomes more negative.
     pub fn set_offset(&self, mut position: Point<Pixels>) {
         let state = self.0.borrow();
+        *state.motion.borrow_mut() = ScrollMotion::Idle;
         *state.offset.borrow_mut() = position;
     }
 
@@ -2558,4 +3128,1048 @@ impl ScrollHandle {
     pub fn set_logical_scroll_top(&self, ix: usize, px: Pixels) {
         self.0.borrow_mut().requested_scroll_top = Some((ix, px));
     }
+
+    /// Keeps the child at the top of the scrolled element where it is on screen when children
+    /// before it are added, removed or resized, e.g. when older messages load into a chat.
+    ///
+    /// Children are told apart by their element ids, so the children of the scrolled element
+    /// need ids, e.g. from [`InteractiveElement::id`]. Content is only anchored to children
+    /// with one.
+    pub fn set_anchor_to_top_item(&self, anchor_to_top_item: bool) {
+        let mut state = self.0.borrow_mut();
+        state.anchor_to_top_item = anchor_to_top_item;
+        if !anchor_to_top_item {
+            state.child_ids.clear();
+        }
+    }
+
+    /// Keeps the content scrolled to the bottom as it grows while it's scrolled to the bottom,
+    /// e.g. to follow a log. Once the user scrolls up, the content stays where they left it
+    /// until they scroll back down to the bottom.
+    pub fn set_stick_to_bottom(&self, stick_to_bottom: bool) {
+        self.0.borrow_mut().stick_to_bottom = stick_to_bottom;
+    }
+
+    /// Whether [`Self::set_stick_to_bottom`] is keeping the content scrolled to the bottom.
+    pub fn is_stuck_to_bottom(&self) -> bool {
+        let state = self.0.borrow();
+        state.stick_to_bottom
+            && state.offset.borrow().y <= -state.scroll_max.height + STICK_TO_BOTTOM_TOLERANCE
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, canvas, div, point, prelude::*, px, rems, size, AnyElement, Bounds,
+        BoundsChangeEvent, BoxShadow, Edges, ElementId, ElevationLevel, ElevationStyles,
+        FocusHandle, FontWeight, GlobalElementId, Hsla, InputModality, LayoutId, Modifiers,
+        MouseButton, MouseDownEvent, Pixels, Point, PointerPosition, Rems, ScrollHandle, Size,
+        Subscription, TestAppContext, TextStyle, TouchPhase, View, ViewContext,
+        ViewportChangeEvent, VisualTestContext, WindowContext,
+    };
+    use smallvec::smallvec;
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+        time::{Duration, Instant},
+    };
+
+    use super::{ScrollMotion, SCROLL_ANIMATION_DURATION};
+
+    struct TitleBar {
+        draggable: bool,
+    }
+
+    impl Render for TitleBar {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .flex()
+                .w_full()
+                .h(px(30.))
+                .when(self.draggable, |this| this.window_drag_region())
+                .child(div().id("button").size(px(20.)).on_click(|_, _| {}))
+                .child(div().size(px(20.)))
+        }
+    }
+
+    fn system_moves_started(cx: &mut VisualTestContext) -> usize {
+        cx.update(|cx| {
+            cx.window
+                .platform_window
+                .as_test()
+                .unwrap()
+                .0
+                .lock()
+                .system_moves_started
+        })
+    }
+
+    #[gpui::test]
+    fn test_window_drag_region(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| TitleBar { draggable: true });
+
+        // Pressing on the non-interactive label starts moving the window.
+        cx.simulate_mouse_down(
+            point(px(30.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(system_moves_started(cx), 1);
+
+        // Pressing on the button does not.
+        cx.simulate_mouse_down(
+            point(px(10.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(system_moves_started(cx), 1);
+
+        // Double-clicking zooms the window.
+        cx.simulate_event(MouseDownEvent {
+            position: point(px(100.), px(25.)),
+            modifiers: Modifiers::none(),
+            button: MouseButton::Left,
+            click_count: 2,
+            first_mouse: false,
+        });
+        assert_eq!(system_moves_started(cx), 1);
+        assert!(cx.update(|cx| cx.is_maximized()));
+
+        // The region goes away along with the element that declared it.
+        view.update(cx, |view, cx| {
+            view.draggable = false;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.simulate_mouse_down(
+            point(px(30.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(system_moves_started(cx), 1);
+    }
+
+    struct ScrolledList {
+        scroll_handle: ScrollHandle,
+        clicks: Rc<RefCell<Vec<Point<Pixels>>>>,
+    }
+
+    impl Render for ScrolledList {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let clicks = self.clicks.clone();
+            div().size_full().pt(px(10.)).pl(px(10.)).child(
+                div()
+                    .id("list")
+                    .w(px(100.))
+                    .h(px(50.))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .child(div().h(px(40.)))
+                    .child(
+                        div()
+                            .id("target")
+                            .w(px(40.))
+                            .h(px(20.))
+                            .on_click(move |event, _| {
+                                clicks.borrow_mut().push(event.pointer.local_position)
+                            }),
+                    )
+                    .child(div().h(px(40.))),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_click_local_position_ignores_scroll_offset(cx: &mut TestAppContext) {
+        let scroll_handle = ScrollHandle::new();
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+        let (view, cx) = cx.add_window_view(|_| ScrolledList {
+            scroll_handle: scroll_handle.clone(),
+            clicks: clicks.clone(),
+        });
+
+        // The target starts 40px into a list that is itself offset by 10px.
+        cx.simulate_click(point(px(15.), px(55.)), Modifiers::none());
+
+        scroll_handle.set_offset(point(px(0.), px(-30.)));
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        cx.simulate_click(point(px(15.), px(25.)), Modifiers::none());
+
+        assert_eq!(
+            *clicks.borrow(),
+            vec![point(px(5.), px(5.)), point(px(5.), px(5.))]
+        );
+    }
+
+    /// Paints its child moved by an offset, the way animated elements are moved without being
+    /// laid out again.
+    struct Translated {
+        offset: Point<Pixels>,
+        child: AnyElement,
+    }
+
+    impl IntoElement for Translated {
+        type Element = Self;
+
+        fn into_element(self) -> Self::Element {
+            self
+        }
+    }
+
+    impl Element for Translated {
+        type RequestLayoutState = ();
+        type PrepaintState = ();
+
+        fn id(&self) -> Option<ElementId> {
+            None
+        }
+
+        fn request_layout(
+            &mut self,
+            _: Option<&GlobalElementId>,
+            cx: &mut WindowContext,
+        ) -> (LayoutId, ()) {
+            (self.child.request_layout(cx), ())
+        }
+
+        fn prepaint(
+            &mut self,
+            _: Option<&GlobalElementId>,
+            _: Bounds<Pixels>,
+            _: &mut (),
+            cx: &mut WindowContext,
+        ) {
+            cx.with_element_offset(self.offset, |cx| self.child.prepaint(cx));
+        }
+
+        fn paint(
+            &mut self,
+            _: Option<&GlobalElementId>,
+            _: Bounds<Pixels>,
+            _: &mut (),
+            _: &mut (),
+            cx: &mut WindowContext,
+        ) {
+            self.child.paint(cx);
+        }
+    }
+
+    struct TranslatedList {
+        scroll_handle: ScrollHandle,
+        clicks: Rc<RefCell<Vec<PointerPosition>>>,
+    }
+
+    impl Render for TranslatedList {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let clicks = self.clicks.clone();
+            div().size_full().pt(px(10.)).pl(px(10.)).child(
+                div()
+                    .id("list")
+                    .w(px(100.))
+                    .h(px(50.))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .child(div().h(px(40.)))
+                    .child(Translated {
+                        offset: point(px(20.), px(0.)),
+                        child: div()
+                            .id("target")
+                            .w(px(40.))
+                            .h(px(20.))
+                            .on_click(move |event, _| clicks.borrow_mut().push(event.pointer))
+                            .into_any_element(),
+                    })
+                    .child(div().h(px(40.))),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_click_on_a_translated_element_hits_where_it_was_painted(cx: &mut TestAppContext) {
+        let scroll_handle = ScrollHandle::new();
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+        let (view, cx) = cx.add_window_view(|_| TranslatedList {
+            scroll_handle: scroll_handle.clone(),
+            clicks: clicks.clone(),
+        });
+        scroll_handle.set_offset(point(px(0.), px(-30.)));
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+
+        // The target was laid out at (10, 50), is scrolled up by 30px and painted 20px to the
+        // right, so where it would have been without the translation isn't a click on it.
+        cx.simulate_click(point(px(15.), px(25.)), Modifiers::none());
+        assert!(clicks.borrow().is_empty());
+
+        cx.simulate_click(point(px(35.), px(25.)), Modifiers::none());
+        assert_eq!(
+            *clicks.borrow(),
+            vec![PointerPosition {
+                window_position: point(px(35.), px(25.)),
+                local_position: point(px(5.), px(5.)),
+                bounds: Bounds::new(point(px(30.), px(20.)), size(px(40.), px(20.))),
+            }]
+        );
+    }
+
+    /// A 100px tall feed of 20px tall items, each with its id as its element id.
+    struct Feed {
+        scroll_handle: ScrollHandle,
+        items: Vec<usize>,
+    }
+
+    impl Render for Feed {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("feed")
+                .w(px(100.))
+                .h(px(100.))
+                .overflow_y_scroll()
+                .track_scroll(&self.scroll_handle)
+                .children(self.items.iter().map(|&item| {
+                    div()
+                        .id(item)
+                        .h(px(20.))
+                        .debug_selector(move || format!("item-{item}"))
+                }))
+        }
+    }
+
+    fn feed<'a>(
+        scroll_handle: &ScrollHandle,
+        items: Vec<usize>,
+        cx: &'a mut TestAppContext,
+    ) -> (View<Feed>, &'a mut VisualTestContext) {
+        let (view, cx) = cx.add_window_view(|_| Feed {
+            scroll_handle: scroll_handle.clone(),
+            items,
+        });
+        cx.run_until_parked();
+        (view, cx)
+    }
+
+    fn set_items(view: &View<Feed>, items: Vec<usize>, cx: &mut VisualTestContext) {
+        view.update(cx, |view, cx| {
+            view.items = items;
+            cx.notify();
+        });
+        cx.run_until_parked();
+    }
+
+    fn scroll_to(view: &View<Feed>, y: Pixels, cx: &mut VisualTestContext) {
+        view.update(cx, |view, cx| {
+            view.scroll_handle.set_offset(point(px(0.), y));
+            cx.notify();
+        });
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn test_anchoring_keeps_the_top_item_in_place(cx: &mut TestAppContext) {
+        let scroll_handle = ScrollHandle::new();
+        scroll_handle.set_anchor_to_top_item(true);
+        let (view, cx) = feed(&scroll_handle, (10..20).collect(), cx);
+        scroll_to(&view, px(-30.), cx);
+        assert_eq!(cx.debug_bounds("item-11").unwrap().origin.y, px(-10.));
+
+        // Older items load in above the viewport.
+        set_items(&view, (0..20).collect(), cx);
+        assert_eq!(cx.debug_bounds("item-11").unwrap().origin.y, px(-10.));
+        assert_eq!(scroll_handle.offset().y, px(-230.));
+
+        // Items above the viewport are removed.
+        set_items(&view, (5..20).collect(), cx);
+        assert_eq!(cx.debug_bounds("item-11").unwrap().origin.y, px(-10.));
+
+        // Without anchoring, the same change shifts the visible items.
+        scroll_handle.set_anchor_to_top_item(false);
+        set_items(&view, (10..20).collect(), cx);
+        assert_ne!(cx.debug_bounds("item-11").unwrap().origin.y, px(-10.));
+    }
+
+    #[gpui::test]
+    fn test_stick_to_bottom_until_scrolled_up(cx: &mut TestAppContext) {
+        let scroll_handle = ScrollHandle::new();
+        scroll_handle.set_stick_to_bottom(true);
+        let (view, cx) = feed(&scroll_handle, (0..10).collect(), cx);
+        assert_eq!(scroll_handle.offset().y, px(-100.));
+
+        set_items(&view, (0..12).collect(), cx);
+        assert_eq!(scroll_handle.offset().y, px(-140.));
+        assert_eq!(cx.debug_bounds("item-11").unwrap().origin.y, px(80.));
+        assert!(scroll_handle.is_stuck_to_bottom());
+
+        // Once the user scrolls up, new items don't move the content.
+        scroll_to(&view, px(-60.), cx);
+        set_items(&view, (0..14).collect(), cx);
+        assert_eq!(scroll_handle.offset().y, px(-60.));
+        assert!(!scroll_handle.is_stuck_to_bottom());
+
+        // Scrolling back to the bottom sticks to it again.
+        scroll_to(&view, px(-180.), cx);
+        set_items(&view, (0..16).collect(), cx);
+        assert_eq!(scroll_handle.offset().y, px(-220.));
+        assert_eq!(cx.debug_bounds("item-15").unwrap().origin.y, px(80.));
+    }
+
+    #[test]
+    fn test_scroll_motion() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let offset = point(px(0.), px(-100.));
+
+        // A flick continues with momentum in the same direction, slowing down until it stops.
+        let mut motion = ScrollMotion::default();
+        motion.handle_scroll_event(TouchPhase::Started, Point::default(), at(0));
+        for ms in [10, 20, 30] {
+            motion.handle_scroll_event(TouchPhase::Moved, point(px(0.), px(-10.)), at(ms));
+        }
+        motion.handle_scroll_event(TouchPhase::Ended, Point::default(), at(40));
+        assert!(motion.is_moving());
+
+        let mut offset_after_momentum = offset;
+        let mut last_step = px(f32::MAX);
+        let mut ms = 40;
+        while motion.is_moving() {
+            ms += 16;
+            let new_offset = motion
+                .step(offset_after_momentum, at(ms), SCROLL_ANIMATION_DURATION)
+                .unwrap();
+            let step = offset_after_momentum.y - new_offset.y;
+            assert!(step > px(0.) && step < last_step);
+            last_step = step;
+            offset_after_momentum = new_offset;
+        }
+        assert!(offset_after_momentum.y < offset.y);
+        assert_eq!(offset_after_momentum.x, px(0.));
+        assert_eq!(
+            motion.step(
+                offset_after_momentum,
+                at(ms + 16),
+                SCROLL_ANIMATION_DURATION
+            ),
+            None
+        );
+
+        // Lifting the fingers after resting on the trackpad doesn't start momentum.
+        let mut motion = ScrollMotion::default();
+        motion.handle_scroll_event(TouchPhase::Started, Point::default(), at(0));
+        motion.handle_scroll_event(TouchPhase::Moved, point(px(0.), px(-10.)), at(10));
+        motion.handle_scroll_event(TouchPhase::Ended, Point::default(), at(500));
+        assert_eq!(motion, ScrollMotion::Idle);
+
+        // Any other scroll event interrupts the momentum.
+        let mut motion = ScrollMotion::default();
+        motion.handle_scroll_event(TouchPhase::Started, Point::default(), at(0));
+        motion.handle_scroll_event(TouchPhase::Moved, point(px(0.), px(-10.)), at(10));
+        motion.handle_scroll_event(TouchPhase::Ended, Point::default(), at(20));
+        assert!(motion.is_moving());
+        motion.handle_scroll_event(TouchPhase::Moved, point(px(0.), px(-1.)), at(30));
+        assert_eq!(motion, ScrollMotion::Idle);
+
+        // Animations ease from their start to their end.
+        let to = point(px(0.), px(-300.));
+        let mut motion = ScrollMotion::Animating {
+            from: offset,
+            to,
+            start: Some(start),
+        };
+        let halfway = motion
+            .step(
+                offset,
+                start + SCROLL_ANIMATION_DURATION / 2,
+                SCROLL_ANIMATION_DURATION,
+            )
+            .unwrap();
+        assert!(halfway.y < offset.y && halfway.y > to.y);
+        assert_eq!(
+            motion.step(
+                halfway,
+                start + SCROLL_ANIMATION_DURATION,
+                SCROLL_ANIMATION_DURATION
+            ),
+            Some(to)
+        );
+        assert_eq!(motion, ScrollMotion::Idle);
+
+        // Animations jump to their end when motion is turned off.
+        let mut motion = ScrollMotion::Animating {
+            from: offset,
+            to,
+            start: Some(start),
+        };
+        assert_eq!(motion.step(offset, start, Duration::ZERO), Some(to));
+        assert_eq!(motion, ScrollMotion::Idle);
+    }
+
+    struct ObservedRow {
+        widths: (Pixels, Pixels),
+        follow_first: bool,
+        changes: Vec<BoundsChangeEvent>,
+        _subscriptions: Vec<Subscription>,
+    }
+
+    impl ObservedRow {
+        fn new(cx: &mut ViewContext<Self>) -> Self {
+            let subscriptions = vec![
+                cx.observe_bounds("first", |this: &mut Self, event, cx| {
+                    this.changes.push(event.clone());
+                    if this.follow_first {
+                        this.widths.1 = event.new_bounds.size.width;
+                        cx.notify();
+                    }
+                }),
+                cx.observe_bounds("second", |this: &mut Self, event, _| {
+                    this.changes.push(event.clone());
+                }),
+            ];
+            Self {
+                widths: (px(10.), px(20.)),
+                follow_first: false,
+                changes: Vec::new(),
+                _subscriptions: subscriptions,
+            }
+        }
+    }
+
+    impl Render for ObservedRow {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .flex()
+                .child(div().id("first").w(self.widths.0).h(px(10.)))
+                .child(div().id("second").w(self.widths.1).h(px(10.)))
+        }
+    }
+
+    #[gpui::test]
+    fn test_observe_bounds(cx: &mut TestAppContext) {
+        let bounds =
+            |x: f32, width: f32| Bounds::new(point(px(x), px(0.)), size(px(width), px(10.)));
+        let (view, cx) = cx.add_window_view(ObservedRow::new);
+        cx.run_until_parked();
+
+        view.update(cx, |view, _| {
+            assert_eq!(
+                view.changes,
+                [
+                    BoundsChangeEvent {
+                        element_id: "first".into(),
+                        old_bounds: None,
+                        new_bounds: bounds(0., 10.),
+                    },
+                    BoundsChangeEvent {
+                        element_id: "second".into(),
+                        old_bounds: None,
+                        new_bounds: bounds(10., 20.),
+                    },
+                ]
+            );
+            view.changes.clear();
+        });
+
+        // Redrawing without changing any bounds doesn't notify the observers.
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        view.update(cx, |view, _| assert!(view.changes.is_empty()));
+
+        // Changes made by an observer are laid out in the next frame, and reported from there.
+        view.update(cx, |view, cx| {
+            view.widths.0 = px(30.);
+            view.follow_first = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        view.update(cx, |view, _| {
+            assert_eq!(
+                view.changes,
+                [
+                    BoundsChangeEvent {
+                        element_id: "first".into(),
+                        old_bounds: Some(bounds(0., 10.)),
+                        new_bounds: bounds(0., 30.),
+                    },
+                    BoundsChangeEvent {
+                        element_id: "second".into(),
+                        old_bounds: Some(bounds(10., 20.)),
+                        new_bounds: bounds(30., 20.),
+                    },
+                    BoundsChangeEvent {
+                        element_id: "second".into(),
+                        old_bounds: Some(bounds(30., 20.)),
+                        new_bounds: bounds(30., 30.),
+                    },
+                ]
+            );
+        });
+    }
+
+    struct ScopedRows {
+        widths: [Pixels; 2],
+        changes: Vec<BoundsChangeEvent>,
+        _subscription: Subscription,
+    }
+
+    impl Render for ScopedRows {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div().children(self.widths.iter().enumerate().map(|(ix, width)| {
+                cx.with_id_scope(("row", ix), |_| {
+                    div().child(div().id("first").w(*width).h(px(10.)))
+                })
+            }))
+        }
+    }
+
+    #[gpui::test]
+    fn test_observe_bounds_in_id_scopes(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|cx| ScopedRows {
+            widths: [px(10.), px(20.)],
+            changes: Vec::new(),
+            _subscription: cx.observe_bounds_at(
+                [ElementId::from(("row", 1usize)), "first".into()],
+                |this: &mut ScopedRows, event, _| this.changes.push(event.clone()),
+            ),
+        });
+        cx.run_until_parked();
+        view.update(cx, |view, _| {
+            assert_eq!(view.changes.len(), 1);
+            assert_eq!(view.changes[0].new_bounds.size.width, px(20.));
+            view.changes.clear();
+        });
+
+        // The element with the same id in the other row isn't observed.
+        view.update(cx, |view, cx| {
+            view.widths[0] = px(30.);
+            cx.notify();
+        });
+        cx.run_until_parked();
+        view.update(cx, |view, _| assert!(view.changes.is_empty()));
+
+        view.update(cx, |view, cx| {
+            view.widths[1] = px(40.);
+            cx.notify();
+        });
+        cx.run_until_parked();
+        view.update(cx, |view, _| {
+            assert_eq!(view.changes.len(), 1);
+            assert_eq!(view.changes[0].element_id, "first".into());
+            assert_eq!(view.changes[0].old_bounds.unwrap().size.width, px(20.));
+            assert_eq!(view.changes[0].new_bounds.size.width, px(40.));
+        });
+    }
+
+    struct FocusRing {
+        focus_handle: FocusHandle,
+    }
+
+    impl Render for FocusRing {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_full().child(
+                div()
+                    .track_focus(&self.focus_handle)
+                    .debug_selector(|| "button".into())
+                    .size(px(20.))
+                    .focus_visible(|style| style.w(px(40.))),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_focus_visible_follows_input_modality(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|cx| FocusRing {
+            focus_handle: cx.focus_handle(),
+        });
+        cx.run_until_parked();
+        let width = |cx: &mut VisualTestContext| cx.debug_bounds("button").unwrap().size.width;
+
+        // Focusing the element with the mouse doesn't show its focus-visible style.
+        cx.simulate_click(point(px(10.), px(10.)), Modifiers::none());
+        assert_eq!(cx.update(|cx| cx.input_modality()), InputModality::Pointer);
+        assert_eq!(width(cx), px(20.));
+
+        cx.simulate_keystrokes("a");
+        assert_eq!(cx.update(|cx| cx.input_modality()), InputModality::Keyboard);
+        assert_eq!(width(cx), px(40.));
+
+        // Moving the mouse while the element stays focused hides it again.
+        cx.simulate_mouse_move(point(px(100.), px(100.)), None, Modifiers::none());
+        assert_eq!(cx.update(|cx| cx.input_modality()), InputModality::Pointer);
+        assert_eq!(width(cx), px(20.));
+    }
+
+    struct ViewportObserver {
+        events: Vec<ViewportChangeEvent>,
+    }
+
+    impl Render for ViewportObserver {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_full().on_viewport_change(
+                cx.listener(|this, event: &ViewportChangeEvent, _| this.events.push(event.clone())),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_viewport_change_is_dispatched_once_per_frame(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| ViewportObserver { events: Vec::new() });
+        cx.run_until_parked();
+        let initial_size = cx.update(|cx| cx.viewport_size());
+
+        // Resizes reported before the next frame are coalesced into one change.
+        resize_within_frame(cx, &[size(px(300.), px(200.)), size(px(400.), px(300.))]);
+        view.update(cx, |view, _| {
+            assert_eq!(
+                view.events,
+                [ViewportChangeEvent {
+                    previous_size: initial_size,
+                    size: size(px(400.), px(300.)),
+                    previous_scale_factor: 2.,
+                    scale_factor: 2.,
+                }]
+            );
+        });
+
+        // Resizing back and forth before the next frame isn't a change.
+        resize_within_frame(cx, &[size(px(500.), px(300.)), size(px(400.), px(300.))]);
+        view.update(cx, |view, _| assert_eq!(view.events.len(), 1));
+
+        cx.simulate_resize(size(px(500.), px(300.)));
+        cx.run_until_parked();
+        view.update(cx, |view, _| assert_eq!(view.events.len(), 2));
+    }
+
+    /// Reports each of the sizes to the window, as happens when the platform sends several resize
+    /// events between two frames.
+    fn resize_within_frame(cx: &mut VisualTestContext, sizes: &[Size<Pixels>]) {
+        cx.update(|cx| {
+            for size in sizes {
+                cx.window
+                    .platform_window
+                    .as_test()
+                    .unwrap()
+                    .0
+                    .lock()
+                    .bounds
+                    .size = *size;
+                cx.bounds_changed();
+            }
+        });
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn test_viewport_change_reports_scale_factor_changes(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| ViewportObserver { events: Vec::new() });
+        cx.run_until_parked();
+
+        cx.simulate_scale_factor(1.);
+        cx.run_until_parked();
+        view.update(cx, |view, _| {
+            assert_eq!(view.events.len(), 1);
+            assert!(view.events[0].scale_factor_changed());
+            assert_eq!(view.events[0].scale_factor, 1.);
+        });
+        cx.update(|cx| assert_eq!(cx.scale_factor(), 1.));
+    }
+
+    struct Rows;
+
+    impl Render for Rows {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            // A row in a larger group of the same name, whose icon follows the inner row.
+            div().group("row").w(px(200.)).h(px(100.)).child(
+                div().group("row").w(px(100.)).h(px(20.)).child(
+                    div()
+                        .size(px(10.))
+                        .bg(gpui::red())
+                        .opacity(0.)
+                        .group_hover("row", |style| style.opacity(1.)),
+                ),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_group_hover_follows_the_nearest_group(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| Rows);
+        cx.run_until_parked();
+        let icon_opacity = |cx: &mut VisualTestContext| {
+            cx.update(|cx| {
+                let quads = &cx.window.rendered_frame.scene.quads;
+                assert_eq!(quads.len(), 1);
+                quads[0].background.a
+            })
+        };
+        assert_eq!(icon_opacity(cx), 0.);
+
+        cx.simulate_mouse_move(point(px(150.), px(50.)), None, Modifiers::none());
+        assert_eq!(icon_opacity(cx), 0.);
+
+        cx.simulate_mouse_move(point(px(50.), px(10.)), None, Modifiers::none());
+        assert_eq!(icon_opacity(cx), 1.);
+
+        cx.simulate_mouse_move(point(px(50.), px(300.)), None, Modifiers::none());
+        assert_eq!(icon_opacity(cx), 0.);
+    }
+
+    const BUTTON_HEIGHT: Rems = rems(1.5);
+
+    struct Toolbar;
+
+    impl Render for Toolbar {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().flex().child(
+                div()
+                    .debug_selector(|| "BUTTON".into())
+                    .h(BUTTON_HEIGHT)
+                    .w(rems(4.))
+                    .bg(crate::blue())
+                    .border_1()
+                    .border_color(crate::red()),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_ui_scale_grows_rems_but_not_pixels(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| Toolbar);
+        cx.run_until_parked();
+        let border_width = |cx: &mut VisualTestContext| {
+            cx.update(|cx| {
+                let quads = &cx.window.rendered_frame.scene.quads;
+                assert_eq!(quads.len(), 1);
+                quads[0].border_widths.top
+            })
+        };
+
+        assert_eq!(cx.update(|cx| cx.ui_scale()), 1.);
+        let button = cx.debug_bounds("BUTTON").unwrap();
+        assert_eq!(button.size, size(px(64.), px(24.)));
+        let hairline = border_width(cx);
+
+        // Only the window's rem size changes, the view isn't notified.
+        cx.update(|cx| cx.set_ui_scale(1.25));
+        cx.run_until_parked();
+        assert_eq!(cx.update(|cx| cx.rem_size()), px(20.));
+        let button = cx.debug_bounds("BUTTON").unwrap();
+        assert_eq!(button.size, size(px(80.), px(30.)));
+        assert_eq!(border_width(cx), hairline);
+    }
+
+    #[derive(Clone, Copy)]
+    enum FirstCard {
+        Shadowed,
+        Raised,
+        RaisedWithZIndex(u8),
+    }
+
+    struct Cards {
+        first: FirstCard,
+    }
+
+    impl Render for Cards {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let card = |color: Hsla| div().size(px(100.)).bg(color);
+            let first = card(crate::red());
+            let first = match self.first {
+                FirstCard::Shadowed => first.shadow(smallvec![card_shadow()]),
+                FirstCard::Raised => first.elevation(ElevationLevel::Raised),
+                FirstCard::RaisedWithZIndex(z_index) => {
+                    first.elevation(ElevationLevel::Raised).z_index(z_index)
+                }
+            };
+            div()
+                .flex()
+                .gap(px(10.))
+                .child(first)
+                .child(card(crate::blue()))
+        }
+    }
+
+    /// Falls halfway across the card next to the one it's under.
+    fn card_shadow() -> BoxShadow {
+        BoxShadow {
+            color: crate::black().opacity(0.5),
+            offset: point(px(60.), px(0.)),
+            blur_radius: px(0.),
+            spread_radius: px(0.),
+        }
+    }
+
+    #[gpui::test]
+    fn test_raised_cards_paint_over_their_neighbors(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let mut styles = ElevationStyles::default();
+            styles.raised.shadow = smallvec![card_shadow()];
+            cx.set_global(styles);
+        });
+        let (view, cx) = cx.add_window_view(|_| Cards {
+            first: FirstCard::Shadowed,
+        });
+        let paint_order = |first, cx: &mut VisualTestContext| {
+            view.update(cx, |view, cx| {
+                view.first = first;
+                cx.notify();
+            });
+            cx.run_until_parked();
+            cx.update(|cx| {
+                let scene = &cx.window.rendered_frame.scene;
+                let mut primitives = scene
+                    .shadows
+                    .iter()
+                    .map(|shadow| (shadow.order, "first shadow"))
+                    .chain(scene.quads.iter().map(|quad| {
+                        let card = if quad.background == crate::red() {
+                            "first card"
+                        } else {
+                            "second card"
+                        };
+                        (quad.order, card)
+                    }))
+                    .collect::<Vec<_>>();
+                primitives.sort();
+                primitives
+                    .into_iter()
+                    .map(|(_, primitive)| primitive)
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // The second card is painted last, so it covers the shadow of the first.
+        assert_eq!(
+            paint_order(FirstCard::Shadowed, cx),
+            ["first shadow", "first card", "second card"]
+        );
+        assert_eq!(
+            paint_order(FirstCard::Raised, cx),
+            ["second card", "first shadow", "first card"]
+        );
+        // An explicit z-index takes precedence over the elevation's.
+        assert_eq!(
+            paint_order(FirstCard::RaisedWithZIndex(0), cx),
+            ["first shadow", "first card", "second card"]
+        );
+    }
+
+    struct Toolbar {
+        clicked: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl Render for Toolbar {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let button = |name: &'static str, width: Pixels| {
+                let clicked = self.clicked.clone();
+                div()
+                    .id(name)
+                    .w(width)
+                    .h(px(12.))
+                    .on_click(move |_, _| clicked.borrow_mut().push(name))
+            };
+            // Icons at x 20..32, 36..48 and 156..168, with a button at 52..152 between them.
+            div()
+                .id("toolbar")
+                .flex()
+                .gap(px(4.))
+                .p(px(20.))
+                .on_click(|_, _| {})
+                .child(button("first", px(12.)).min_click_size(px(24.)))
+                .child(button("second", px(12.)).min_click_size(px(24.)))
+                .child(button("button", px(100.)))
+                .child(button("third", px(12.)).hit_slop(Edges::all(px(6.))))
+        }
+    }
+
+    #[gpui::test]
+    fn test_clicks_hit_the_slop_of_small_elements(cx: &mut TestAppContext) {
+        let clicked = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view({
+            let clicked = clicked.clone();
+            |_| Toolbar { clicked }
+        });
+        cx.run_until_parked();
+        let click = |x: f32, y: f32, cx: &mut VisualTestContext| {
+            cx.simulate_click(point(px(x), px(y)), Modifiers::none());
+            clicked.borrow_mut().drain(..).collect::<Vec<_>>()
+        };
+
+        assert_eq!(click(26., 26., cx), ["first"]);
+        // Below the first icon, in its slop but not its painted bounds.
+        assert_eq!(click(26., 36., cx), ["first"]);
+        assert_eq!(click(26., 39., cx), Vec::<&str>::new());
+
+        // Where the slop of the two icons overlaps, the nearest one wins.
+        assert_eq!(click(33., 26., cx), ["first"]);
+        assert_eq!(click(35., 26., cx), ["second"]);
+
+        // The button wins where the slop of the icons overlaps what it paints...
+        assert_eq!(click(53., 26., cx), ["button"]);
+        assert_eq!(click(151., 26., cx), ["button"]);
+        // ...but not in the gaps beside it.
+        assert_eq!(click(50., 26., cx), ["second"]);
+        assert_eq!(click(154., 26., cx), ["third"]);
+    }
+
+    /// The text style seen by a leaf where it's prepainted and where it's painted.
+    type TextStyles = Rc<RefCell<Vec<(&'static str, TextStyle)>>>;
+
+    fn text_style_probe(name: &'static str, styles: TextStyles) -> impl IntoElement {
+        canvas(
+            {
+                let styles = styles.clone();
+                move |_, cx| styles.borrow_mut().push((name, cx.text_style()))
+            },
+            move |_, _, cx| styles.borrow_mut().push((name, cx.text_style())),
+        )
+    }
+
+    struct Cascade {
+        styles: TextStyles,
+    }
+
+    impl Render for Cascade {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .text_color(crate::red())
+                .text_size(px(20.))
+                .font_family("Zed Mono")
+                .child(
+                    div().text_size(px(14.)).child(
+                        div()
+                            .text_color(crate::blue())
+                            .font_weight(FontWeight::BOLD)
+                            .child(text_style_probe("leaf", self.styles.clone())),
+                    ),
+                )
+                .child(text_style_probe("sibling", self.styles.clone()))
+        }
+    }
+
+    #[gpui::test]
+    fn test_text_style_cascades_to_descendants(cx: &mut TestAppContext) {
+        let styles = TextStyles::default();
+        let (_, cx) = cx.add_window_view({
+            let styles = styles.clone();
+            |_| Cascade { styles }
+        });
+        cx.run_until_parked();
+
+        let styles = styles.borrow();
+        let names = styles.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        assert_eq!(names[..4], ["leaf", "sibling", "leaf", "sibling"]);
+        for (name, style) in styles.iter() {
+            assert_eq!(style.font_family, crate::SharedString::from("Zed Mono"));
+            if *name == "leaf" {
+                assert_eq!(style.color, crate::blue());
+                assert_eq!(style.font_size, px(14.).into());
+                assert_eq!(style.font_weight, FontWeight::BOLD);
+            } else {
+                // The overrides closer to the leaf don't leak into its ancestors' other children.
+                assert_eq!(style.color, crate::red());
+                assert_eq!(style.font_size, px(20.).into());
+                assert_eq!(style.font_weight, FontWeight::default());
+            }
+        }
+        assert!(cx.update(|cx| cx.window.text_style_stack.is_empty()));
+    }
 }
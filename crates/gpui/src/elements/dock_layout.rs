@@ -0,0 +1,754 @@
+//! A layout with panels docked to the left, right and bottom of some center content.
+//!
+//! Each panel has an edge facing the center that can be dragged to resize it between its minimum
+//! and maximum sizes. Dragging the edge well past the minimum collapses the panel, and it only
+//! expands again once it's dragged back out to the minimum, so it doesn't flicker when the cursor
+//! hovers around the boundary. Collapsed panels take up no space. Panels collapsed or expanded
+//! through their [`DockPanelHandle`] can animate open and closed.
+//!
+//! The left and right panels span the whole height of the layout, and the bottom panel spans the
+//! width left between them. The sizes of the panels are decided before anything is laid out, so
+//! the center content is laid out once per frame however the panels change.
+
+use crate::{
+    ease_in_out, point, px, size, Animation, AnimationTiming, AnyElement, AvailableSpace, Bounds,
+    ContentMask, CursorStyle, DispatchPhase, Element, ElementId, FrameReason, GlobalElementId,
+    Hitbox, IntoElement, LayoutId, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    Pixels, Point, Style, StyleRefinement, Styled, WindowContext,
+};
+use collections::HashMap;
+use refineable::Refineable;
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// The thickness of the area around a panel's inner edge that can be dragged to resize it.
+const RESIZE_HANDLE_SIZE: Pixels = px(6.);
+/// A panel dragged to less than this fraction of its minimum size collapses.
+const COLLAPSE_THRESHOLD: f32 = 0.5;
+
+/// Construct a layout with the given center content, to which panels can be docked with
+/// [`DockLayout::panel`]. The layout fills its parent by default.
+pub fn dock_layout(id: impl Into<ElementId>, center: impl IntoElement) -> DockLayout {
+    DockLayout {
+        id: id.into(),
+        center: center.into_any_element(),
+        panels: Vec::new(),
+        on_resize: None,
+        style: StyleRefinement::default(),
+    }
+    .size_full()
+}
+
+/// The side of a [`DockLayout`] a panel is docked to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DockSide {
+    /// The panel spans the height of the layout, left of the center.
+    Left,
+    /// The panel spans the height of the layout, right of the center.
+    Right,
+    /// The panel spans the width of the center, below it.
+    Bottom,
+}
+
+impl DockSide {
+    fn cursor_style(self) -> CursorStyle {
+        match self {
+            DockSide::Left | DockSide::Right => CursorStyle::ResizeLeftRight,
+            DockSide::Bottom => CursorStyle::ResizeUpDown,
+        }
+    }
+
+    /// How much the panel grows when the cursor moves by `delta`.
+    fn growth(self, delta: Point<Pixels>) -> Pixels {
+        match self {
+            DockSide::Left => delta.x,
+            DockSide::Right => -delta.x,
+            DockSide::Bottom => -delta.y,
+        }
+    }
+}
+
+/// A handle to the size and collapsed state of a [`DockPanel`], for reading and changing them from
+/// outside the layout.
+#[derive(Clone, Default)]
+pub struct DockPanelHandle(Rc<RefCell<DockPanelState>>);
+
+impl DockPanelHandle {
+    /// Construct a new handle. The panel starts out with its default size.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The size of the panel when it's expanded, once it's been drawn or set.
+    pub fn size(&self) -> Option<Pixels> {
+        self.0.borrow().size
+    }
+
+    /// Set the size of the panel when it's expanded. It's clamped to the panel's constraints when
+    /// the layout is drawn.
+    pub fn set_size(&self, size: Pixels) {
+        self.0.borrow_mut().size = Some(size);
+    }
+
+    /// Whether the panel is collapsed.
+    pub fn is_collapsed(&self) -> bool {
+        self.0.borrow().collapsed == Some(true)
+    }
+
+    /// Collapse or expand the panel, animating it if the panel is given an animation duration.
+    pub fn set_collapsed(&self, collapsed: bool) {
+        let mut state = self.0.borrow_mut();
+        if state.collapsed != Some(collapsed) {
+            state.collapsed = Some(collapsed);
+            state.transition = Some(Transition {
+                from: state.shown,
+                start: None,
+            });
+        }
+    }
+
+    /// Collapse the panel if it's expanded, or expand it if it's collapsed.
+    pub fn toggle_collapsed(&self) {
+        self.set_collapsed(!self.is_collapsed());
+    }
+}
+
+#[derive(Default)]
+struct DockPanelState {
+    size: Option<Pixels>,
+    collapsed: Option<bool>,
+    /// The fraction of the panel's size that was shown in the last frame.
+    shown: f32,
+    /// The opacity of the panel in the last frame, while it fades in instead of sliding open.
+    opacity: Option<f32>,
+    transition: Option<Transition>,
+}
+
+#[derive(Clone, Copy)]
+struct Transition {
+    from: f32,
+    /// When the panel was first drawn since it was collapsed or expanded.
+    start: Option<Instant>,
+}
+
+impl DockPanelState {
+    fn is_collapsed(&self) -> bool {
+        self.collapsed == Some(true)
+    }
+
+    /// Advance the collapse animation, returning whether it's still running.
+    fn animate(&mut self, timing: Option<AnimationTiming>, now: Instant) -> bool {
+        let target = if self.is_collapsed() { 0. } else { 1. };
+        self.opacity = None;
+        let Some((transition, timing)) = self.transition.as_mut().zip(timing) else {
+            self.transition = None;
+            self.shown = target;
+            return false;
+        };
+        let elapsed = now.saturating_duration_since(*transition.start.get_or_insert(now));
+        if elapsed < timing.duration {
+            let delta = elapsed.as_secs_f32() / timing.duration.as_secs_f32();
+            self.shown = transition.from + (target - transition.from) * ease_in_out(delta);
+        } else {
+            self.shown = target;
+        }
+        let fade = timing.fade.unwrap_or_default();
+        if elapsed < fade {
+            self.opacity = Some(elapsed.as_secs_f32() / fade.as_secs_f32());
+        }
+
+        let running = elapsed < timing.duration.max(fade);
+        if !running {
+            self.transition = None;
+        }
+        running
+    }
+}
+
+/// A panel docked to one side of a [`DockLayout`].
+pub struct DockPanel {
+    side: DockSide,
+    element: AnyElement,
+    default_size: Pixels,
+    min_size: Pixels,
+    max_size: Pixels,
+    collapsed: bool,
+    animation: Option<Duration>,
+    handle: Option<DockPanelHandle>,
+}
+
+impl DockPanel {
+    /// Construct a panel showing the given element on the given side.
+    pub fn new(side: DockSide, element: impl IntoElement) -> Self {
+        Self {
+            side,
+            element: element.into_any_element(),
+            default_size: px(240.),
+            min_size: px(120.),
+            max_size: Pixels::MAX,
+            collapsed: false,
+            animation: None,
+            handle: None,
+        }
+    }
+
+    /// Set the size of the panel until it's resized.
+    pub fn default_size(mut self, size: Pixels) -> Self {
+        self.default_size = size;
+        self
+    }
+
+    /// Set the smallest size the panel can be resized to before it collapses.
+    pub fn min_size(mut self, size: Pixels) -> Self {
+        self.min_size = size;
+        self
+    }
+
+    /// Set the largest size the panel can be resized to.
+    pub fn max_size(mut self, size: Pixels) -> Self {
+        self.max_size = size;
+        self
+    }
+
+    /// Start the panel out collapsed.
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    /// Animate the panel over the given duration when it's collapsed or expanded through its
+    /// handle, following the window's [motion preference](WindowContext::animation_timing).
+    pub fn animate(mut self, duration: Duration) -> Self {
+        self.animation = Some(duration);
+        self
+    }
+
+    /// Track the size and collapsed state of the panel with the given handle. Otherwise they're
+    /// kept in the layout's element state.
+    pub fn track(mut self, handle: &DockPanelHandle) -> Self {
+        self.handle = Some(handle.clone());
+        self
+    }
+
+    fn clamp(&self, size: Pixels) -> Pixels {
+        size.max(self.min_size)
+            .min(self.max_size.max(self.min_size))
+    }
+}
+
+/// A layout with panels docked around its center content, see [`dock_layout`].
+pub struct DockLayout {
+    id: ElementId,
+    center: AnyElement,
+    panels: Vec<DockPanel>,
+    on_resize: Option<Rc<dyn Fn(DockSide, Pixels, &mut WindowContext)>>,
+    style: StyleRefinement,
+}
+
+impl DockLayout {
+    /// Dock a panel to the layout. There can be one panel on each side.
+    pub fn panel(mut self, panel: DockPanel) -> Self {
+        debug_assert!(
+            self.panels
+                .iter()
+                .all(|existing| existing.side != panel.side),
+            "a panel is already docked to the {:?} side",
+            panel.side
+        );
+        self.panels.push(panel);
+        self
+    }
+
+    /// Called with the side and new size of a panel whenever dragging its edge resizes it, so the
+    /// size can be persisted. Collapsing the panel doesn't change its size.
+    pub fn on_resize(
+        mut self,
+        on_resize: impl Fn(DockSide, Pixels, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.on_resize = Some(Rc::new(on_resize));
+        self
+    }
+}
+
+impl Styled for DockLayout {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+#[derive(Default)]
+struct DockLayoutState {
+    panels: HashMap<DockSide, Rc<RefCell<DockPanelState>>>,
+    drag: Option<ResizeDrag>,
+}
+
+#[derive(Clone, Copy)]
+struct ResizeDrag {
+    side: DockSide,
+    start_position: Point<Pixels>,
+    start_size: Pixels,
+}
+
+/// Frame state used by the [`DockLayout`] element.
+pub struct DockLayoutFrameState {
+    state: Rc<RefCell<DockLayoutState>>,
+    panel_states: Vec<Rc<RefCell<DockPanelState>>>,
+}
+
+/// The painted children and resize handles of a [`DockLayout`].
+pub struct DockLayoutPrepaintState {
+    center: Bounds<Pixels>,
+    panels: Vec<Option<Bounds<Pixels>>>,
+    handles: Vec<(DockSide, Hitbox)>,
+}
+
+impl Element for DockLayout {
+    type RequestLayoutState = DockLayoutFrameState;
+    type PrepaintState = DockLayoutPrepaintState;
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn request_layout(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let state = cx.with_element_state(
+            global_id.unwrap(),
+            |state: Option<Rc<RefCell<DockLayoutState>>>, _| {
+                let state = state.unwrap_or_default();
+                (state.clone(), state)
+            },
+        );
+
+        let panel_states = self
+            .panels
+            .iter()
+            .map(|panel| {
+                let panel_state = match panel.handle.as_ref() {
+                    Some(handle) => handle.0.clone(),
+                    None => state
+                        .borrow_mut()
+                        .panels
+                        .entry(panel.side)
+                        .or_default()
+                        .clone(),
+                };
+                {
+                    let mut panel_state = panel_state.borrow_mut();
+                    if panel_state.collapsed.is_none() {
+                        panel_state.collapsed = Some(panel.collapsed);
+                        panel_state.shown = if panel.collapsed { 0. } else { 1. };
+                    }
+                    let size = panel_state.size.unwrap_or(panel.default_size);
+                    panel_state.size = Some(panel.clamp(size));
+                }
+                panel_state
+            })
+            .collect();
+
+        let mut style = Style::default();
+        style.refine(&self.style);
+        let layout_id = cx.request_layout(style, None);
+
+        (
+            layout_id,
+            DockLayoutFrameState {
+                state,
+                panel_states,
+            },
+        )
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        let now = cx.background_executor().now();
+        let mut animating = false;
+        let mut shown_sizes = HashMap::default();
+        for (panel, panel_state) in self.panels.iter().zip(&request_layout.panel_states) {
+            let timing = panel
+                .animation
+                .map(|duration| cx.animation_timing(&Animation::new(duration).movement()));
+            let mut panel_state = panel_state.borrow_mut();
+            animating |= panel_state.animate(timing, now);
+            let full_size = panel_state.size.unwrap_or(panel.default_size);
+            shown_sizes.insert(panel.side, (full_size, full_size * panel_state.shown));
+        }
+        if animating {
+            cx.request_frame(FrameReason::Animation);
+        }
+
+        let shown = |side| shown_sizes.get(&side).map_or(px(0.), |(_, shown)| *shown);
+        let left = shown(DockSide::Left).min(bounds.size.width);
+        let right = shown(DockSide::Right).min(bounds.size.width - left);
+        let column_origin = point(bounds.origin.x + left, bounds.origin.y);
+        let column_width = bounds.size.width - left - right;
+        let bottom = shown(DockSide::Bottom).min(bounds.size.height);
+
+        let center = Bounds::new(
+            column_origin,
+            size(column_width, bounds.size.height - bottom),
+        );
+        self.center
+            .prepaint_as_root(center.origin, center.size.map(AvailableSpace::Definite), cx);
+
+        let mut handle_bounds = Vec::new();
+        let panels = self
+            .panels
+            .iter_mut()
+            .map(|panel| {
+                let (full_size, shown) = shown_sizes[&panel.side];
+                // Panels slide in from their outer edge rather than being laid out at their
+                // animated size, so their content doesn't reflow while they open and close.
+                let (visible, content_origin, content_size, handle) = match panel.side {
+                    DockSide::Left => (
+                        Bounds::new(bounds.origin, size(left, bounds.size.height)),
+                        point(bounds.origin.x + left - full_size, bounds.origin.y),
+                        size(full_size, bounds.size.height),
+                        Bounds::new(
+                            point(
+                                bounds.origin.x + left - RESIZE_HANDLE_SIZE / 2.,
+                                bounds.origin.y,
+                            ),
+                            size(RESIZE_HANDLE_SIZE, bounds.size.height),
+                        ),
+                    ),
+                    DockSide::Right => {
+                        let x = bounds.origin.x + bounds.size.width - right;
+                        (
+                            Bounds::new(point(x, bounds.origin.y), size(right, bounds.size.height)),
+                            point(x, bounds.origin.y),
+                            size(full_size, bounds.size.height),
+                            Bounds::new(
+                                point(x - RESIZE_HANDLE_SIZE / 2., bounds.origin.y),
+                                size(RESIZE_HANDLE_SIZE, bounds.size.height),
+                            ),
+                        )
+                    }
+                    DockSide::Bottom => {
+                        let y = bounds.origin.y + bounds.size.height - bottom;
+                        (
+                            Bounds::new(point(column_origin.x, y), size(column_width, bottom)),
+                            point(column_origin.x, y),
+                            size(column_width, full_size),
+                            Bounds::new(
+                                point(column_origin.x, y - RESIZE_HANDLE_SIZE / 2.),
+                                size(column_width, RESIZE_HANDLE_SIZE),
+                            ),
+                        )
+                    }
+                };
+                handle_bounds.push((panel.side, handle));
+
+                if shown <= px(0.) {
+                    return None;
+                }
+                cx.with_content_mask(Some(ContentMask { bounds: visible }), |cx| {
+                    panel.element.prepaint_as_root(
+                        content_origin,
+                        content_size.map(AvailableSpace::Definite),
+                        cx,
+                    );
+                });
+                Some(visible)
+            })
+            .collect();
+
+        // The handles straddle the edges of the panels, so they're inserted after the panels'
+        // contents to be hovered above them.
+        let handles = handle_bounds
+            .into_iter()
+            .map(|(side, bounds)| (side, cx.insert_hitbox(bounds, false)))
+            .collect();
+
+        DockLayoutPrepaintState {
+            center,
+            panels,
+            handles,
+        }
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        cx.with_content_mask(
+            Some(ContentMask {
+                bounds: prepaint.center,
+            }),
+            |cx| self.center.paint(cx),
+        );
+        for ((panel, visible), panel_state) in self
+            .panels
+            .iter_mut()
+            .zip(&prepaint.panels)
+            .zip(&request_layout.panel_states)
+        {
+            if let Some(visible) = visible {
+                let opacity = panel_state.borrow().opacity;
+                cx.with_element_opacity(opacity, |cx| {
+                    cx.with_content_mask(Some(ContentMask { bounds: *visible }), |cx| {
+                        panel.element.paint(cx)
+                    })
+                });
+            }
+        }
+
+        let state = request_layout.state.clone();
+        let dragging = state.borrow().drag.map(|drag| drag.side);
+        for (side, hitbox) in &prepaint.handles {
+            if dragging.map_or(true, |dragging| dragging == *side) {
+                cx.set_cursor_style(side.cursor_style(), hitbox);
+            }
+        }
+
+        let panels = self
+            .panels
+            .iter()
+            .zip(&request_layout.panel_states)
+            .map(|(panel, panel_state)| {
+                (
+                    panel.side,
+                    (
+                        panel.min_size,
+                        panel.max_size.max(panel.min_size),
+                        panel_state.clone(),
+                    ),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        cx.on_mouse_event({
+            let state = state.clone();
+            let handles = prepaint.handles.clone();
+            let panels = panels.clone();
+            move |event: &MouseDownEvent, phase, cx| {
+                if phase != DispatchPhase::Bubble || event.button != MouseButton::Left {
+                    return;
+                }
+                let Some((side, _)) = handles.iter().find(|(_, hitbox)| hitbox.is_hovered(cx))
+                else {
+                    return;
+                };
+                let (_, _, panel_state) = &panels[side];
+                let panel_state = panel_state.borrow();
+                let start_size = if panel_state.is_collapsed() {
+                    px(0.)
+                } else {
+                    panel_state.size.unwrap_or_default()
+                };
+                state.borrow_mut().drag = Some(ResizeDrag {
+                    side: *side,
+                    start_position: event.position,
+                    start_size,
+                });
+                cx.stop_propagation();
+            }
+        });
+
+        cx.on_mouse_event({
+            let state = state.clone();
+            let on_resize = self.on_resize.clone();
+            move |event: &MouseMoveEvent, phase, cx| {
+                if phase != DispatchPhase::Bubble {
+                    return;
+                }
+                let Some(drag) = state.borrow().drag else {
+                    return;
+                };
+                if !event.dragging() {
+                    state.borrow_mut().drag = None;
+                    return;
+                }
+
+                let (min_size, max_size, panel_state) = &panels[&drag.side];
+                let requested =
+                    drag.start_size + drag.side.growth(event.position - drag.start_position);
+                let mut panel_state = panel_state.borrow_mut();
+                let was_collapsed = panel_state.is_collapsed();
+                let collapsed = if was_collapsed {
+                    requested < *min_size
+                } else {
+                    requested < *min_size * COLLAPSE_THRESHOLD
+                };
+                let old_size = panel_state.size;
+                if !collapsed {
+                    panel_state.size = Some(requested.max(*min_size).min(*max_size));
+                }
+                if collapsed != was_collapsed {
+                    panel_state.collapsed = Some(collapsed);
+                    // Dragging moves the panel's edge with the cursor, without animating it.
+                    panel_state.transition = None;
+                    panel_state.shown = if collapsed { 0. } else { 1. };
+                }
+                let new_size = panel_state.size;
+                drop(panel_state);
+
+                cx.stop_propagation();
+                if collapsed != was_collapsed || new_size != old_size {
+                    cx.refresh();
+                }
+                if let (Some(on_resize), Some(new_size)) = (on_resize.as_ref(), new_size) {
+                    if new_size != old_size.unwrap_or_default() {
+                        on_resize(drag.side, new_size, cx);
+                    }
+                }
+            }
+        });
+
+        cx.on_mouse_event(move |_: &MouseUpEvent, phase, cx| {
+            if phase == DispatchPhase::Capture && state.borrow_mut().drag.take().is_some() {
+                cx.stop_propagation();
+                cx.refresh();
+            }
+        });
+    }
+}
+
+impl IntoElement for DockLayout {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, div, dock_layout, point, px, DockPanel, DockPanelHandle, DockSide,
+        InteractiveElement, IntoElement, Modifiers, MouseButton, ParentElement, Pixels, Render,
+        Styled, TestAppContext, ViewContext, VisualTestContext,
+    };
+
+    struct Workspace {
+        left: DockPanelHandle,
+        resizes: Vec<(DockSide, Pixels)>,
+    }
+
+    impl Render for Workspace {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            dock_layout(
+                "workspace",
+                div().debug_selector(|| "center".into()).size_full(),
+            )
+            .panel(
+                DockPanel::new(
+                    DockSide::Left,
+                    div().debug_selector(|| "left".into()).size_full(),
+                )
+                .default_size(px(100.))
+                .min_size(px(60.))
+                .max_size(px(200.))
+                .track(&self.left),
+            )
+            .panel(
+                DockPanel::new(
+                    DockSide::Bottom,
+                    div().debug_selector(|| "bottom".into()).size_full(),
+                )
+                .default_size(px(50.)),
+            )
+            .on_resize({
+                let view = cx.view().downgrade();
+                move |side, size, cx| {
+                    view.update(cx, |view, _| view.resizes.push((side, size)))
+                        .ok();
+                }
+            })
+        }
+    }
+
+    fn workspace(cx: &mut TestAppContext) -> (gpui::View<Workspace>, &mut VisualTestContext) {
+        let (view, cx) = cx.add_window_view(|_| Workspace {
+            left: DockPanelHandle::new(),
+            resizes: Vec::new(),
+        });
+        cx.run_until_parked();
+        (view, cx)
+    }
+
+    fn drag_left_edge(cx: &mut VisualTestContext, from: Pixels, to: &[Pixels]) {
+        cx.simulate_mouse_down(point(from, px(10.)), MouseButton::Left, Modifiers::none());
+        for x in to {
+            cx.simulate_mouse_move(
+                point(*x, px(10.)),
+                Some(MouseButton::Left),
+                Modifiers::none(),
+            );
+        }
+        let last = to.last().copied().unwrap_or(from);
+        cx.simulate_mouse_up(point(last, px(10.)), MouseButton::Left, Modifiers::none());
+    }
+
+    #[gpui::test]
+    fn test_panels_surround_center(cx: &mut TestAppContext) {
+        let (_, cx) = workspace(cx);
+        let viewport = cx.update(|cx| cx.viewport_size());
+
+        let left = cx.debug_bounds("left").unwrap();
+        assert_eq!(left.origin, point(px(0.), px(0.)));
+        assert_eq!(left.size.width, px(100.));
+        assert_eq!(left.size.height, viewport.height);
+
+        let bottom = cx.debug_bounds("bottom").unwrap();
+        assert_eq!(bottom.origin, point(px(100.), viewport.height - px(50.)));
+        assert_eq!(bottom.size.width, viewport.width - px(100.));
+
+        let center = cx.debug_bounds("center").unwrap();
+        assert_eq!(center.origin, point(px(100.), px(0.)));
+        assert_eq!(center.size.height, viewport.height - px(50.));
+    }
+
+    #[gpui::test]
+    fn test_dragging_edge_resizes_within_constraints(cx: &mut TestAppContext) {
+        let (view, cx) = workspace(cx);
+
+        drag_left_edge(cx, px(100.), &[px(150.)]);
+        assert_eq!(cx.debug_bounds("left").unwrap().size.width, px(150.));
+        assert_eq!(cx.debug_bounds("center").unwrap().origin.x, px(150.));
+
+        drag_left_edge(cx, px(150.), &[px(400.)]);
+        assert_eq!(cx.debug_bounds("left").unwrap().size.width, px(200.));
+
+        view.update(cx, |view, _| {
+            assert_eq!(view.left.size(), Some(px(200.)));
+            assert_eq!(
+                view.resizes,
+                [(DockSide::Left, px(150.)), (DockSide::Left, px(200.))]
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_dragging_past_minimum_collapses_with_hysteresis(cx: &mut TestAppContext) {
+        let (view, cx) = workspace(cx);
+
+        // Between half the minimum and the minimum, the panel stays at its minimum size.
+        drag_left_edge(cx, px(100.), &[px(40.)]);
+        assert_eq!(cx.debug_bounds("left").unwrap().size.width, px(60.));
+
+        // Further than that, it collapses, and stays collapsed until dragged back to the minimum.
+        drag_left_edge(cx, px(60.), &[px(20.), px(40.)]);
+        assert!(view.update(cx, |view, _| view.left.is_collapsed()));
+        assert_eq!(cx.debug_bounds("center").unwrap().origin.x, px(0.));
+
+        drag_left_edge(cx, px(0.), &[px(70.)]);
+        assert!(!view.update(cx, |view, _| view.left.is_collapsed()));
+        assert_eq!(cx.debug_bounds("left").unwrap().size.width, px(70.));
+    }
+}
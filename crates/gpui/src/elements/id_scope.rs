@@ -0,0 +1,153 @@
+use crate::{
+    AnyElement, Bounds, Element, ElementId, GlobalElementId, IntoElement, LayoutId, Pixels,
+    WindowContext,
+};
+
+impl<'a> WindowContext<'a> {
+    /// Build an element within the scope of the given id, which prefixes the ids of the elements
+    /// built in the given function, and of the state they keep, while they're drawn. This gives
+    /// elements built in a loop distinct ids without formatting one for each of them, e.g. a row
+    /// scoped by its key can give its children the same ids as the other rows do.
+    pub fn with_id_scope<E: IntoElement>(
+        &mut self,
+        id: impl Into<ElementId>,
+        f: impl FnOnce(&mut Self) -> E,
+    ) -> IdScope {
+        let id = id.into();
+        let child = self.with_element_namespace(id.clone(), |cx| f(cx).into_any_element());
+        IdScope { id, child }
+    }
+}
+
+/// An element which draws its child within the scope of an id, see
+/// [`WindowContext::with_id_scope`].
+pub struct IdScope {
+    id: ElementId,
+    child: AnyElement,
+}
+
+impl Element for IdScope {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, ()) {
+        (self.child.request_layout(cx), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) {
+        self.child.prepaint(cx);
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        self.child.paint(cx);
+    }
+}
+
+impl IntoElement for IdScope {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        self as gpui, div, point, prelude::*, px, ElementId, Modifiers, MouseMoveEvent,
+        TestAppContext, ViewContext,
+    };
+
+    struct Rows {
+        scoped: bool,
+        hovered: Rc<RefCell<Vec<(usize, bool)>>>,
+    }
+
+    impl Render for Rows {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let row = |key: usize, hovered: Rc<RefCell<Vec<(usize, bool)>>>| {
+                div()
+                    .id("row")
+                    .h(px(20.))
+                    .on_hover(move |is_hovered, _| hovered.borrow_mut().push((key, *is_hovered)))
+            };
+            div().children((0..2).map(|key| {
+                let hovered = self.hovered.clone();
+                if self.scoped {
+                    cx.with_id_scope(ElementId::from("rows").scoped(key), |_| row(key, hovered))
+                        .into_any_element()
+                } else {
+                    row(key, hovered).into_any_element()
+                }
+            }))
+        }
+    }
+
+    #[gpui::test]
+    fn test_scoped_rows_keep_independent_hover_state(cx: &mut TestAppContext) {
+        let hovered = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view({
+            let hovered = hovered.clone();
+            |_| Rows {
+                scoped: true,
+                hovered,
+            }
+        });
+        cx.run_until_parked();
+
+        let move_to = |y: f32, cx: &mut gpui::VisualTestContext| {
+            cx.simulate_event(MouseMoveEvent {
+                position: point(px(10.), px(y)),
+                modifiers: Modifiers::none(),
+                pressed_button: None,
+            });
+            cx.run_until_parked();
+        };
+        move_to(10., cx);
+        assert_eq!(*hovered.borrow(), [(0, true)]);
+        move_to(30., cx);
+        let mut changes = hovered.borrow()[1..].to_vec();
+        changes.sort();
+        assert_eq!(changes, [(0, false), (1, true)]);
+    }
+
+    #[cfg(debug_assertions)]
+    #[gpui::test]
+    fn test_rows_with_the_same_id_are_reported(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| Rows {
+            scoped: false,
+            hovered: Rc::default(),
+        });
+        cx.run_until_parked();
+
+        // Both rows are drawn with the id `row` in the same view, so they'd share their state.
+        let collisions = cx.update(|cx| cx.element_id_collisions().to_vec());
+        assert_eq!(collisions.len(), 1);
+        assert!(collisions[0].path.ends_with(" > row"));
+        assert!(collisions[0].first.contains("Stateful"));
+        assert_eq!(collisions[0].first, collisions[0].second);
+    }
+}
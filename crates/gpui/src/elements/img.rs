@@ -3,15 +3,16 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::{
-    point, px, size, AbsoluteLength, Asset, Bounds, DefiniteLength, DevicePixels, Element,
-    ElementId, GlobalElementId, Hitbox, ImageData, InteractiveElement, Interactivity, IntoElement,
-    LayoutId, Length, Pixels, SharedUri, Size, StyleRefinement, Styled, SvgSize, UriOrPath,
-    WindowContext,
+    point, px, size, AbsoluteLength, Bounds, DecodedImage, DefiniteLength, DevicePixels, Edges,
+    Element, ElementId, GlobalElementId, Hitbox, ImageData, InteractiveElement, Interactivity,
+    IntoElement, LayoutId, Length, Pixels, SharedUri, Size, StyleRefinement, Styled, SvgSize,
+    UriOrPath, WindowContext,
 };
 use futures::{AsyncReadExt, Future};
 use image::{ImageBuffer, ImageError};
 #[cfg(target_os = "macos")]
 use media::core_video::CVImageBuffer;
+use smallvec::SmallVec;
 
 use http;
 use thiserror::Error;
@@ -81,6 +82,7 @@ pub struct Img {
     source: ImageSource,
     grayscale: bool,
     object_fit: ObjectFit,
+    nine_slice: Option<Edges<Pixels>>,
 }
 
 /// Create a new image element.
@@ -90,6 +92,7 @@ pub fn img(source: impl Into<ImageSource>) -> Img {
         source: source.into(),
         grayscale: false,
         object_fit: ObjectFit::Contain,
+        nine_slice: None,
     }
 }
 
@@ -228,6 +231,16 @@ impl Img {
         self.object_fit = object_fit;
         self
     }
+
+    /// Stretch the image over the element's bounds using nine-slice scaling, ignoring its object
+    /// fit. The insets are logical pixels of the image at its natural size, where each pixel of
+    /// the image is one logical pixel. The corners within them are painted at that size, the edges
+    /// between them stretch along one axis and the center stretches along both. When the bounds
+    /// are too small to fit the corners, the corners are scaled down proportionally.
+    pub fn nine_slice(mut self, insets: impl Into<Edges<Pixels>>) -> Self {
+        self.nine_slice = Some(insets.into());
+        self
+    }
 }
 
 impl Element for Img {
@@ -293,9 +306,23 @@ impl Element for Img {
                 let corner_radii = style.corner_radii.to_pixels(bounds.size, cx.rem_size());
 
                 if let Some(data) = source.data(cx) {
-                    let new_bounds = self.object_fit.get_bounds(bounds, data.size());
-                    cx.paint_image(new_bounds, corner_radii, data.clone(), self.grayscale)
-                        .log_err();
+                    if let Some(insets) = self.nine_slice {
+                        let regions =
+                            nine_slice_regions(bounds, data.size(), insets, cx.scale_factor());
+                        for (region_bounds, source_bounds) in regions {
+                            cx.paint_image_region(
+                                region_bounds,
+                                source_bounds,
+                                data.clone(),
+                                self.grayscale,
+                            )
+                            .log_err();
+                        }
+                    } else {
+                        let new_bounds = self.object_fit.get_bounds(bounds, data.size());
+                        cx.paint_image(new_bounds, corner_radii, data.clone(), self.grayscale)
+                            .log_err();
+                    }
                 }
 
                 match source {
@@ -310,6 +337,93 @@ impl Element for Img {
                 }
             })
     }
+
+    fn cullable(&self) -> bool {
+        !self.interactivity.paint_when_clipped
+    }
+}
+
+/// Split the given bounds and an image of the given size into the nine regions used for nine-slice
+/// scaling, returning the bounds to paint each region at along with its source in the image. The
+/// insets are in logical pixels and are rounded to the nearest pixel of the image.
+fn nine_slice_regions(
+    bounds: Bounds<Pixels>,
+    image_size: Size<DevicePixels>,
+    insets: Edges<Pixels>,
+    scale_factor: f32,
+) -> SmallVec<[(Bounds<Pixels>, Bounds<DevicePixels>); 9]> {
+    let image_width = image_size.width.0;
+    let image_height = image_size.height.0;
+    let source_left = (insets.left.0.round() as i32).clamp(0, image_width);
+    let source_right = (insets.right.0.round() as i32).clamp(0, image_width - source_left);
+    let source_top = (insets.top.0.round() as i32).clamp(0, image_height);
+    let source_bottom = (insets.bottom.0.round() as i32).clamp(0, image_height - source_top);
+    let source_xs = [0, source_left, image_width - source_right, image_width];
+    let source_ys = [0, source_top, image_height - source_bottom, image_height];
+
+    let (left, right) = fit_corners(
+        px(source_left as f32),
+        px(source_right as f32),
+        bounds.size.width,
+    );
+    let (top, bottom) = fit_corners(
+        px(source_top as f32),
+        px(source_bottom as f32),
+        bounds.size.height,
+    );
+    // Snap the inner edges to device pixels, so that the corners stay crisp.
+    let snap = |value: Pixels| px((value.0 * scale_factor).round() / scale_factor);
+    let xs = [
+        bounds.left(),
+        snap(bounds.left() + left),
+        snap(bounds.right() - right),
+        bounds.right(),
+    ];
+    let ys = [
+        bounds.top(),
+        snap(bounds.top() + top),
+        snap(bounds.bottom() - bottom),
+        bounds.bottom(),
+    ];
+
+    let mut regions = SmallVec::new();
+    for row in 0..3 {
+        for column in 0..3 {
+            let source = Bounds {
+                origin: point(
+                    DevicePixels(source_xs[column]),
+                    DevicePixels(source_ys[row]),
+                ),
+                size: size(
+                    DevicePixels(source_xs[column + 1] - source_xs[column]),
+                    DevicePixels(source_ys[row + 1] - source_ys[row]),
+                ),
+            };
+            let bounds = Bounds::from_corners(
+                point(xs[column], ys[row]),
+                point(xs[column + 1], ys[row + 1]),
+            );
+            if source.size.width.0 > 0
+                && source.size.height.0 > 0
+                && bounds.size.width > Pixels::ZERO
+                && bounds.size.height > Pixels::ZERO
+            {
+                regions.push((bounds, source));
+            }
+        }
+    }
+    regions
+}
+
+/// Scale down a pair of opposite corner sizes so that they fit within the given length.
+fn fit_corners(start: Pixels, end: Pixels, length: Pixels) -> (Pixels, Pixels) {
+    let total = start + end;
+    if total > length && total > Pixels::ZERO {
+        let scale = length.max(Pixels::ZERO) / total;
+        (start * scale, end * scale)
+    } else {
+        (start, end)
+    }
 }
 
 impl IntoElement for Img {
@@ -335,75 +449,77 @@ impl InteractiveElement for Img {
 impl ImageSource {
     fn data(&self, cx: &mut WindowContext) -> Option<Arc<ImageData>> {
         match self {
-            ImageSource::Uri(_) | ImageSource::File(_) => {
-                let uri_or_path: UriOrPath = match self {
-                    ImageSource::Uri(uri) => uri.clone().into(),
-                    ImageSource::File(path) => path.clone().into(),
-                    _ => unreachable!(),
-                };
-
-                cx.use_cached_asset::<Image>(&uri_or_path)?.log_err()
-            }
-
+            ImageSource::Uri(_) | ImageSource::File(_) => self.load(cx)?.log_err(),
             ImageSource::Data(data) => Some(data.to_owned()),
             #[cfg(target_os = "macos")]
             ImageSource::Surface(_) => None,
         }
     }
-}
 
-#[derive(Clone)]
-enum Image {}
+    /// Whether the image couldn't be loaded, such as when its file doesn't exist or the request
+    /// for it failed. Starts loading the image if it isn't yet, and returns false until it's done.
+    pub fn failed_to_load(&self, cx: &mut WindowContext) -> bool {
+        matches!(self.load(cx), Some(Err(_)))
+    }
 
-impl Asset for Image {
-    type Source = UriOrPath;
-    type Output = Result<Arc<ImageData>, ImageCacheError>;
+    fn load(&self, cx: &mut WindowContext) -> Option<Result<Arc<ImageData>, ImageCacheError>> {
+        let uri_or_path: UriOrPath = match self {
+            ImageSource::Uri(uri) => uri.clone().into(),
+            ImageSource::File(path) => path.clone().into(),
+            _ => return None,
+        };
+        cx.load_image(&uri_or_path)
+    }
+}
 
-    fn load(
-        source: Self::Source,
-        cx: &mut WindowContext,
-    ) -> impl Future<Output = Self::Output> + Send + 'static {
-        let client = cx.http_client();
-        let scale_factor = cx.scale_factor();
-        let svg_renderer = cx.svg_renderer();
-        async move {
-            let bytes = match source.clone() {
-                UriOrPath::Path(uri) => fs::read(uri.as_ref())?,
-                UriOrPath::Uri(uri) => {
-                    let mut response = client.get(uri.as_ref(), ().into(), true).await?;
-                    let mut body = Vec::new();
-                    response.body_mut().read_to_end(&mut body).await?;
-                    if !response.status().is_success() {
-                        return Err(ImageCacheError::BadStatus {
-                            status: response.status(),
-                            body: String::from_utf8_lossy(&body).into_owned(),
-                        });
-                    }
-                    body
+/// Read or fetch the image and decode it, which is done on the background executor by the
+/// [`ImageCache`](crate::ImageCache).
+pub(crate) fn load_image_data(
+    source: UriOrPath,
+    cx: &mut WindowContext,
+) -> impl Future<Output = Result<DecodedImage, ImageCacheError>> + Send + 'static {
+    let client = cx.http_client();
+    let scale_factor = cx.scale_factor();
+    let svg_renderer = cx.svg_renderer();
+    async move {
+        let bytes = match source.clone() {
+            UriOrPath::Path(uri) => fs::read(uri.as_ref())?,
+            UriOrPath::Uri(uri) => {
+                let mut response = client.get(uri.as_ref(), ().into(), true).await?;
+                let mut body = Vec::new();
+                response.body_mut().read_to_end(&mut body).await?;
+                if !response.status().is_success() {
+                    return Err(ImageCacheError::BadStatus {
+                        status: response.status(),
+                        body: String::from_utf8_lossy(&body).into_owned(),
+                    });
                 }
-            };
+                body
+            }
+        };
 
-            let data = if let Ok(format) = image::guess_format(&bytes) {
-                let mut data = image::load_from_memory_with_format(&bytes, format)?.into_rgba8();
+        let (data, scale_dependent) = if let Ok(format) = image::guess_format(&bytes) {
+            let mut data = image::load_from_memory_with_format(&bytes, format)?.into_rgba8();
 
-                // Convert from RGBA to BGRA.
-                for pixel in data.chunks_exact_mut(4) {
-                    pixel.swap(0, 2);
-                }
+            // Convert from RGBA to BGRA.
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
 
-                ImageData::new(data)
-            } else {
-                let pixmap =
-                    svg_renderer.render_pixmap(&bytes, SvgSize::ScaleFactor(scale_factor))?;
+            (ImageData::new(data), false)
+        } else {
+            let pixmap = svg_renderer.render_pixmap(&bytes, SvgSize::ScaleFactor(scale_factor))?;
 
-                let buffer =
-                    ImageBuffer::from_raw(pixmap.width(), pixmap.height(), pixmap.take()).unwrap();
+            let buffer =
+                ImageBuffer::from_raw(pixmap.width(), pixmap.height(), pixmap.take()).unwrap();
 
-                ImageData::new(buffer)
-            };
+            (ImageData::new(buffer), true)
+        };
 
-            Ok(Arc::new(data))
-        }
+        Ok(DecodedImage {
+            data: Arc::new(data),
+            scale_dependent,
+        })
     }
 }
 
@@ -449,3 +565,76 @@ impl From<usvg::Error> for ImageCacheError {
         Self::Usvg(Arc::new(error))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::nine_slice_regions;
+    use crate::{point, px, size, Bounds, DevicePixels, Edges};
+
+    #[test]
+    fn test_nine_slice_regions() {
+        let image_size = size(DevicePixels(30), DevicePixels(30));
+        let insets = Edges::from(10.);
+
+        // Corners keep their size, edges stretch along one axis and the center along both.
+        let regions = nine_slice_regions(
+            Bounds::new(point(px(0.), px(0.)), size(px(100.), px(50.))),
+            image_size,
+            insets,
+            2.,
+        );
+        assert_eq!(regions.len(), 9);
+        let (top_left, top_left_source) = regions[0];
+        assert_eq!(
+            top_left,
+            Bounds::new(point(px(0.), px(0.)), size(px(10.), px(10.)))
+        );
+        assert_eq!(
+            top_left_source,
+            Bounds::new(
+                point(DevicePixels(0), DevicePixels(0)),
+                size(DevicePixels(10), DevicePixels(10))
+            )
+        );
+        let (top, _) = regions[1];
+        assert_eq!(
+            top,
+            Bounds::new(point(px(10.), px(0.)), size(px(80.), px(10.)))
+        );
+        let (center, center_source) = regions[4];
+        assert_eq!(
+            center,
+            Bounds::new(point(px(10.), px(10.)), size(px(80.), px(30.)))
+        );
+        assert_eq!(
+            center_source,
+            Bounds::new(
+                point(DevicePixels(10), DevicePixels(10)),
+                size(DevicePixels(10), DevicePixels(10))
+            )
+        );
+        let (bottom_right, _) = regions[8];
+        assert_eq!(
+            bottom_right,
+            Bounds::new(point(px(90.), px(40.)), size(px(10.), px(10.)))
+        );
+
+        // Bounds that are too small for the corners scale them down instead of overlapping them,
+        // leaving no room for the edges or the center.
+        let regions = nine_slice_regions(
+            Bounds::new(point(px(0.), px(0.)), size(px(10.), px(10.))),
+            image_size,
+            insets,
+            2.,
+        );
+        assert_eq!(regions.len(), 4);
+        assert_eq!(
+            regions[0].0,
+            Bounds::new(point(px(0.), px(0.)), size(px(5.), px(5.)))
+        );
+        assert_eq!(
+            regions[3].0,
+            Bounds::new(point(px(5.), px(5.)), size(px(5.), px(5.)))
+        );
+    }
+}
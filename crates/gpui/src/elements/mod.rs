@@ -1,21 +1,43 @@
 mod anchored;
 mod animation;
 mod canvas;
+mod constrained_layout;
 mod deferred;
+mod dismissable;
 mod div;
+mod dock_layout;
+mod id_scope;
 mod img;
 mod list;
+mod native_view;
+mod provide_context;
+mod reorderable_list;
+mod roving_focus;
+mod safe_area;
 mod svg;
 mod text;
+mod text_area;
+mod toast;
 mod uniform_list;
 
 pub use anchored::*;
 pub use animation::*;
 pub use canvas::*;
+pub use constrained_layout::*;
 pub use deferred::*;
+pub use dismissable::*;
 pub use div::*;
+pub use dock_layout::*;
+pub use id_scope::*;
 pub use img::*;
 pub use list::*;
+pub use native_view::*;
+pub use provide_context::*;
+pub use reorderable_list::*;
+pub use roving_focus::*;
+pub use safe_area::*;
 pub use svg::*;
 pub use text::*;
+pub use text_area::*;
+pub use toast::*;
 pub use uniform_list::*;
@@ -0,0 +1,163 @@
+use refineable::Refineable;
+
+use crate::{
+    Bounds, Element, ElementId, GlobalElementId, Hitbox, IntoElement, LayoutId, NativeViewHandle,
+    Pixels, Style, StyleRefinement, Styled, WindowContext,
+};
+
+/// Construct an element that reserves space for a view of the platform's, such as a video player
+/// or a web view, and keeps the view in it. The element is laid out like any other, and the view is
+/// moved and clipped to match wherever the element is painted, or hidden when it isn't.
+pub fn native_view(handle: &NativeViewHandle) -> NativeView {
+    NativeView {
+        handle: handle.clone(),
+        style: StyleRefinement::default(),
+    }
+}
+
+/// An element showing a native view, see [`native_view`].
+pub struct NativeView {
+    handle: NativeViewHandle,
+    style: StyleRefinement,
+}
+
+impl Styled for NativeView {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl Element for NativeView {
+    type RequestLayoutState = ();
+    type PrepaintState = Hitbox;
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut style = Style::default();
+        style.refine(&self.style);
+        (cx.request_layout(style, None), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        // The native view gets the mouse events over it from the platform, so the elements
+        // behind it shouldn't be hovered.
+        cx.insert_hitbox(bounds, true)
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _hitbox: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        cx.paint_native_view(&self.handle, bounds);
+    }
+}
+
+impl IntoElement for NativeView {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, div, native_view, point, prelude::*, px, size, Bounds, Modifiers,
+        NativeViewFrame, NativeViewHandle, ScrollDelta, ScrollWheelEvent, TestAppContext,
+        TestNativeView, ViewContext,
+    };
+
+    struct Player {
+        handle: NativeViewHandle,
+        shown: bool,
+    }
+
+    impl Render for Player {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("scroller")
+                .w(px(200.))
+                .h(px(200.))
+                .overflow_y_scroll()
+                .child(div().h(px(100.)))
+                .when(self.shown, |this| {
+                    this.child(native_view(&self.handle).w(px(160.)).h(px(90.)))
+                })
+                .child(div().h(px(400.)))
+        }
+    }
+
+    fn frame(y: f32, clip_top: f32, clip_bottom: f32) -> Option<NativeViewFrame> {
+        let bounds = Bounds::new(point(px(0.), px(y)), size(px(160.), px(90.)));
+        let clip = Bounds::from_corners(
+            point(px(0.), px(clip_top)),
+            point(px(160.), px(clip_bottom)),
+        );
+        Some(NativeViewFrame { bounds, clip })
+    }
+
+    #[gpui::test]
+    fn test_native_view_follows_its_element(cx: &mut TestAppContext) {
+        let view = TestNativeView::new();
+        let (player, cx) = cx.add_window_view({
+            let handle = NativeViewHandle::new(view.clone());
+            |_| Player {
+                handle,
+                shown: true,
+            }
+        });
+        cx.run_until_parked();
+        assert_eq!(view.updates(), [frame(100., 100., 190.)]);
+
+        // Scrolling moves the view in the frame that's scrolled.
+        let scroll = |delta: f32, cx: &mut gpui::VisualTestContext| {
+            cx.simulate_event(ScrollWheelEvent {
+                position: point(px(180.), px(10.)),
+                delta: ScrollDelta::Pixels(point(px(0.), px(-delta))),
+                modifiers: Modifiers::none(),
+                ..Default::default()
+            });
+            cx.run_until_parked();
+        };
+        scroll(50., cx);
+        assert_eq!(view.frame(), frame(50., 50., 140.));
+        scroll(100., cx);
+        assert_eq!(view.frame(), frame(-50., 0., 40.));
+
+        // Frames that don't move the view don't update it.
+        let updates = view.updates().len();
+        player.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        assert_eq!(view.updates().len(), updates);
+
+        // The view is hidden when it's scrolled out of sight, or not painted at all.
+        scroll(100., cx);
+        assert_eq!(view.frame(), None);
+        scroll(-250., cx);
+        assert_eq!(view.frame(), frame(100., 100., 190.));
+        player.update(cx, |player, cx| {
+            player.shown = false;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        assert_eq!(view.updates().last(), Some(&None));
+    }
+}
@@ -0,0 +1,164 @@
+use std::rc::Rc;
+
+use crate::{
+    AnyElement, Bounds, Element, GlobalElementId, IntoElement, LayoutId, Pixels, WindowContext,
+};
+
+/// Builds a `ProvideContext` element, which makes `value` available to its child and all of the
+/// child's descendants through [`WindowContext::context`], shadowing any value of the same type
+/// provided further up the tree.
+pub fn provide_context<T: 'static>(value: T, child: impl IntoElement) -> ProvideContext<T> {
+    ProvideContext {
+        value: Rc::new(value),
+        child: child.into_any_element(),
+    }
+}
+
+/// An element which provides a value to the subtree under it while it's drawn.
+pub struct ProvideContext<T> {
+    value: Rc<T>,
+    child: AnyElement,
+}
+
+impl<T: 'static> Element for ProvideContext<T> {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<crate::ElementId> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, ()) {
+        let layout_id = cx.with_context(self.value.clone(), |cx| self.child.request_layout(cx));
+        (layout_id, ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) {
+        cx.with_context(self.value.clone(), |cx| self.child.prepaint(cx));
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        cx.with_context(self.value.clone(), |cx| self.child.paint(cx));
+    }
+
+    fn cullable(&self) -> bool {
+        false
+    }
+}
+
+impl<T: 'static> IntoElement for ProvideContext<T> {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, div, provide_context, px, size, IntoElement, ParentElement, Render,
+        RenderOnce, Styled, TestAppContext, ViewContext, WindowContext,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Depth(usize);
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Compact(bool);
+
+    /// Records the context it sees when it's rendered.
+    #[derive(IntoElement)]
+    struct Probe {
+        seen: Rc<RefCell<Vec<(Option<Depth>, Option<Compact>)>>>,
+    }
+
+    impl RenderOnce for Probe {
+        fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+            let depth = cx.context::<Depth>().copied();
+            let compact = cx.context::<Compact>().copied();
+            self.seen.borrow_mut().push((depth, compact));
+            div().size(px(10.))
+        }
+    }
+
+    struct Tree {
+        seen: Rc<RefCell<Vec<(Option<Depth>, Option<Compact>)>>>,
+    }
+
+    impl Render for Tree {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let probe = || Probe {
+                seen: self.seen.clone(),
+            };
+            div()
+                .child(probe())
+                .child(provide_context(
+                    Depth(1),
+                    div().child(probe()).child(provide_context(
+                        Compact(true),
+                        div()
+                            .child(provide_context(Depth(2), probe()))
+                            .child(probe()),
+                    )),
+                ))
+                .child(probe())
+        }
+    }
+
+    #[gpui::test]
+    fn test_inner_providers_shadow_outer_ones(cx: &mut TestAppContext) {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view(|_| Tree { seen: seen.clone() });
+        cx.run_until_parked();
+
+        assert_eq!(
+            seen.borrow()[..5],
+            [
+                (None, None),
+                (Some(Depth(1)), None),
+                (Some(Depth(2)), Some(Compact(true))),
+                (Some(Depth(1)), Some(Compact(true))),
+                (None, None),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    fn test_context_is_restored_after_early_return(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        cx.draw(Default::default(), size(px(100.), px(100.)), |cx| {
+            let result: Result<(), ()> = cx.with_context(Rc::new(Depth(1)), |cx| {
+                let inner: Result<(), ()> = cx.with_context(Rc::new(Depth(2)), |cx| {
+                    assert_eq!(cx.context::<Depth>(), Some(&Depth(2)));
+                    Err(())?;
+                    Ok(())
+                });
+                assert_eq!(cx.context::<Depth>(), Some(&Depth(1)));
+                inner?;
+                Ok(())
+            });
+            assert!(result.is_err());
+            assert_eq!(cx.context::<Depth>(), None);
+            div()
+        });
+    }
+}
@@ -0,0 +1,736 @@
+//! A vertical list whose items can be dragged into a new order.
+//!
+//! Pressing an item and dragging it past the drag threshold lifts it out of the list and draws it
+//! on top of everything else, following the cursor. The remaining items slide apart to open a gap
+//! where the item would land, and releasing the mouse reports the move to the owner of the list,
+//! which is responsible for reordering its items. Pressing escape during the drag cancels it.
+
+use crate::{
+    ease_in_out, point, px, Animation, AnimationTiming, AnyElement, Bounds, DispatchPhase, Element,
+    ElementId, FrameReason, GlobalElementId, Hitbox, InteractiveElement, Interactivity,
+    IntoElement, LayoutId, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels,
+    Point, StyleRefinement, Styled, WindowContext, DRAG_THRESHOLD,
+};
+use smallvec::SmallVec;
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+use taffy::style::{Display, FlexDirection, Overflow};
+
+const ITEM_ANIMATION_DURATION: Duration = Duration::from_millis(120);
+/// Dragging within this distance of the top or bottom edge of the list scrolls it.
+const AUTOSCROLL_EDGE: Pixels = px(32.);
+/// How fast the list scrolls when the cursor reaches the edge, in pixels per second.
+const MAX_AUTOSCROLL_SPEED: f32 = 800.;
+
+/// Construct a list whose items can be reordered by dragging them. When an item is dropped,
+/// `on_move` is called with its original index and the index it should end up at, in the list
+/// with the item removed. The list can be given a height and will then scroll its items,
+/// scrolling automatically while an item is dragged near its top or bottom edge.
+#[track_caller]
+pub fn reorderable_list(
+    id: impl Into<ElementId>,
+    items: impl IntoIterator<Item = impl IntoElement>,
+    on_move: impl Fn(&ReorderEvent, &mut WindowContext) + 'static,
+) -> ReorderableList {
+    let mut base_style = StyleRefinement::default();
+    base_style.display = Some(Display::Flex);
+    base_style.flex_direction = Some(FlexDirection::Column);
+    base_style.overflow.y = Some(Overflow::Scroll);
+
+    ReorderableList {
+        items: items
+            .into_iter()
+            .map(|item| item.into_any_element())
+            .collect(),
+        on_move: Rc::new(on_move),
+        interactivity: Interactivity {
+            element_id: Some(id.into()),
+            base_style: Box::new(base_style),
+            focusable: true,
+
+            #[cfg(debug_assertions)]
+            location: Some(*core::panic::Location::caller()),
+
+            ..Default::default()
+        },
+    }
+}
+
+/// The event emitted when an item in a [`ReorderableList`] is dropped at a new position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReorderEvent {
+    /// The index of the item that was dragged.
+    pub from: usize,
+    /// The index the item should be moved to, after removing it from `from`.
+    pub to: usize,
+}
+
+/// A list element whose items can be reordered by dragging, see [`reorderable_list`].
+pub struct ReorderableList {
+    items: Vec<AnyElement>,
+    on_move: Rc<dyn Fn(&ReorderEvent, &mut WindowContext)>,
+    interactivity: Interactivity,
+}
+
+/// Frame state used by the [`ReorderableList`].
+pub struct ReorderableListFrameState {
+    child_layout_ids: SmallVec<[LayoutId; 8]>,
+    state: Rc<RefCell<ReorderableListState>>,
+}
+
+/// The bounds of the list's items as they were last laid out, before making room for a drag.
+pub struct ReorderableListPrepaintState {
+    hitbox: Option<Hitbox>,
+    item_bounds: Rc<[Bounds<Pixels>]>,
+    /// The opacity of each item left in the list, while it fades in instead of sliding.
+    item_opacities: SmallVec<[Option<f32>; 8]>,
+}
+
+#[derive(Default)]
+struct ReorderableListState {
+    pending_drag: Option<PendingDrag>,
+    drag: Option<ActiveDrag>,
+    item_offsets: Vec<ItemOffset>,
+}
+
+#[derive(Clone, Copy)]
+struct PendingDrag {
+    ix: usize,
+    position: Point<Pixels>,
+    cursor_offset: Point<Pixels>,
+}
+
+struct ActiveDrag {
+    from: usize,
+    to: usize,
+    cursor_offset: Point<Pixels>,
+    last_autoscroll: Option<Instant>,
+}
+
+/// The vertical offset of an item that is sliding out of the way of a dragged item.
+#[derive(Clone, Copy)]
+struct ItemOffset {
+    from: Pixels,
+    to: Pixels,
+    start: Instant,
+    timing: AnimationTiming,
+}
+
+impl ItemOffset {
+    fn new(now: Instant) -> Self {
+        Self {
+            from: Pixels::ZERO,
+            to: Pixels::ZERO,
+            start: now,
+            timing: AnimationTiming {
+                duration: Duration::ZERO,
+                fade: None,
+            },
+        }
+    }
+
+    /// How the items slide in the given window, see [`WindowContext::animation_timing`].
+    fn timing(cx: &WindowContext) -> AnimationTiming {
+        cx.animation_timing(&Animation::new(ITEM_ANIMATION_DURATION).movement())
+    }
+
+    fn current(&self, now: Instant) -> Pixels {
+        let elapsed = now.saturating_duration_since(self.start);
+        if elapsed >= self.timing.duration {
+            return self.to;
+        }
+        let delta = elapsed.as_secs_f32() / self.timing.duration.as_secs_f32();
+        self.from + (self.to - self.from) * ease_in_out(delta)
+    }
+
+    /// The opacity of the item while it fades in where it ends up instead of sliding there.
+    fn opacity(&self, now: Instant) -> Option<f32> {
+        let fade = self.timing.fade?;
+        let elapsed = now.saturating_duration_since(self.start);
+        (elapsed < fade).then(|| elapsed.as_secs_f32() / fade.as_secs_f32())
+    }
+
+    fn is_moving(&self, now: Instant) -> bool {
+        let fade = self.timing.fade.unwrap_or_default();
+        let length = self.timing.duration.max(fade);
+        now.saturating_duration_since(self.start) < length && self.from != self.to
+    }
+
+    fn animate_to(&mut self, to: Pixels, now: Instant, timing: AnimationTiming) {
+        if to != self.to {
+            self.from = self.current(now);
+            self.to = to;
+            self.start = now;
+            self.timing = timing;
+        }
+    }
+}
+
+impl ReorderableListState {
+    fn cancel_drag(&mut self, now: Instant, timing: AnimationTiming) {
+        self.pending_drag = None;
+        if self.drag.take().is_some() {
+            for offset in &mut self.item_offsets {
+                offset.animate_to(Pixels::ZERO, now, timing);
+            }
+        }
+    }
+}
+
+impl ReorderableList {
+    /// The scroll speed for a drag at the given position, positive when scrolling towards the top.
+    fn autoscroll_speed(bounds: Bounds<Pixels>, position: Point<Pixels>) -> f32 {
+        let edge = AUTOSCROLL_EDGE.min(bounds.size.height / 2.);
+        if edge <= Pixels::ZERO {
+            return 0.;
+        }
+        let top_depth = (bounds.top() + edge - position.y) / edge;
+        let bottom_depth = (position.y - (bounds.bottom() - edge)) / edge;
+        if top_depth > 0. {
+            MAX_AUTOSCROLL_SPEED * top_depth.min(1.)
+        } else if bottom_depth > 0. {
+            -MAX_AUTOSCROLL_SPEED * bottom_depth.min(1.)
+        } else {
+            0.
+        }
+    }
+}
+
+/// The index at which the item dragged from `from` lands when dropped at `y`, given the bounds of
+/// the items before the drag. The dragged item passes another item once the cursor crosses the
+/// other item's middle.
+fn insertion_index(item_bounds: &[Bounds<Pixels>], from: usize, y: Pixels) -> usize {
+    item_bounds
+        .iter()
+        .enumerate()
+        .filter(|(ix, bounds)| *ix != from && bounds.center().y < y)
+        .count()
+}
+
+/// The space taken up by the item at `ix`, including any gap before the next item.
+fn slot_height(item_bounds: &[Bounds<Pixels>], ix: usize) -> Pixels {
+    match item_bounds.get(ix + 1) {
+        Some(next) => next.top() - item_bounds[ix].top(),
+        None => item_bounds[ix].size.height,
+    }
+}
+
+impl Styled for ReorderableList {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.interactivity.base_style
+    }
+}
+
+impl InteractiveElement for ReorderableList {
+    fn interactivity(&mut self) -> &mut Interactivity {
+        &mut self.interactivity
+    }
+}
+
+impl Element for ReorderableList {
+    type RequestLayoutState = ReorderableListFrameState;
+    type PrepaintState = ReorderableListPrepaintState;
+
+    fn id(&self) -> Option<ElementId> {
+        self.interactivity.element_id.clone()
+    }
+
+    fn request_layout(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let state = cx.with_element_state(
+            global_id.unwrap(),
+            |state: Option<Rc<RefCell<ReorderableListState>>>, _| {
+                let state = state.unwrap_or_default();
+                (state.clone(), state)
+            },
+        );
+
+        self.interactivity.on_key_down({
+            let state = state.clone();
+            move |event, cx| {
+                let mut state = state.borrow_mut();
+                if event.keystroke.key == "escape" && state.drag.is_some() {
+                    state.cancel_drag(cx.background_executor().now(), ItemOffset::timing(cx));
+                    cx.stop_propagation();
+                    cx.refresh();
+                }
+            }
+        });
+
+        let mut child_layout_ids = SmallVec::new();
+        let layout_id = self
+            .interactivity
+            .request_layout(global_id, cx, |style, cx| {
+                cx.with_text_style(style.text_style().cloned(), |cx| {
+                    child_layout_ids = self
+                        .items
+                        .iter_mut()
+                        .map(|item| item.request_layout(cx))
+                        .collect::<SmallVec<_>>();
+                    cx.request_layout(style, child_layout_ids.iter().copied())
+                })
+            });
+
+        (
+            layout_id,
+            ReorderableListFrameState {
+                child_layout_ids,
+                state,
+            },
+        )
+    }
+
+    fn prepaint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        let now = cx.background_executor().now();
+        let timing = ItemOffset::timing(cx);
+        let mouse_position = cx.mouse_position();
+        let mut state = request_layout.state.borrow_mut();
+        let state = &mut *state;
+
+        if state
+            .drag
+            .as_ref()
+            .map_or(false, |drag| drag.from >= self.items.len())
+        {
+            state.drag = None;
+        }
+        if state.drag.is_none()
+            && state
+                .item_offsets
+                .iter()
+                .all(|offset| !offset.is_moving(now))
+        {
+            state.item_offsets.clear();
+        }
+        state
+            .item_offsets
+            .resize_with(self.items.len(), || ItemOffset::new(now));
+
+        // Scroll the list while an item is dragged close to its top or bottom edge. This has to
+        // keep going while the cursor rests there, so it's driven by frames rather than events.
+        let scroll_offset_before_autoscroll = self
+            .interactivity
+            .scroll_offset
+            .as_ref()
+            .map(|scroll_offset| *scroll_offset.borrow());
+        if let (Some(drag), Some(scroll_offset)) = (
+            state.drag.as_mut(),
+            self.interactivity.scroll_offset.as_ref(),
+        ) {
+            let speed = Self::autoscroll_speed(bounds, mouse_position);
+            if speed == 0. {
+                drag.last_autoscroll = None;
+            } else {
+                if let Some(last_autoscroll) = drag.last_autoscroll {
+                    let elapsed = now.saturating_duration_since(last_autoscroll).as_secs_f32();
+                    scroll_offset.borrow_mut().y += px(speed * elapsed);
+                }
+                drag.last_autoscroll = Some(now);
+            }
+        }
+
+        let mut child_min = point(Pixels::MAX, Pixels::MAX);
+        let mut child_max = Point::default();
+        let content_size = if request_layout.child_layout_ids.is_empty() {
+            bounds.size
+        } else {
+            for child_layout_id in &request_layout.child_layout_ids {
+                let child_bounds = cx.layout_bounds(*child_layout_id);
+                child_min = child_min.min(&child_bounds.origin);
+                child_max = child_max.max(&child_bounds.lower_right());
+            }
+            (child_max - child_min).into()
+        };
+
+        let items = &mut self.items;
+        let child_layout_ids = &request_layout.child_layout_ids;
+        self.interactivity.prepaint(
+            global_id,
+            bounds,
+            content_size,
+            cx,
+            |_style, scroll_offset, hitbox, cx| {
+                let mut dragged_item = None;
+                let mut is_moving = false;
+                let mut item_opacities = SmallVec::new();
+                let item_bounds = cx.with_element_offset(scroll_offset, |cx| {
+                    let item_bounds = child_layout_ids
+                        .iter()
+                        .map(|layout_id| cx.layout_bounds(*layout_id))
+                        .collect::<Rc<[_]>>();
+
+                    if let Some(drag) = state.drag.as_mut() {
+                        drag.to = insertion_index(&item_bounds, drag.from, mouse_position.y);
+                        let slot = slot_height(&item_bounds, drag.from);
+                        for (ix, offset) in state.item_offsets.iter_mut().enumerate() {
+                            let target = if drag.from < ix && ix <= drag.to {
+                                -slot
+                            } else if drag.to <= ix && ix < drag.from {
+                                slot
+                            } else {
+                                Pixels::ZERO
+                            };
+                            offset.animate_to(target, now, timing);
+                        }
+                    }
+
+                    for (ix, item) in items.iter_mut().enumerate() {
+                        if state.drag.as_ref().map_or(false, |drag| drag.from == ix) {
+                            continue;
+                        }
+                        let offset = state.item_offsets[ix];
+                        is_moving |= offset.is_moving(now);
+                        item_opacities.push(offset.opacity(now));
+                        cx.with_element_offset(point(Pixels::ZERO, offset.current(now)), |cx| {
+                            item.prepaint(cx)
+                        });
+                    }
+
+                    item_bounds
+                });
+
+                if let Some(drag) = state.drag.as_ref() {
+                    // Draw the dragged item on top of everything else, under the cursor.
+                    let item = items.remove(drag.from);
+                    let layout_origin =
+                        cx.layout_bounds(child_layout_ids[drag.from]).origin - cx.element_offset();
+                    let origin = mouse_position - drag.cursor_offset;
+                    cx.defer_draw(item, origin - layout_origin, 1);
+                    dragged_item = Some(drag.from);
+                }
+
+                let autoscrolled = dragged_item.is_some()
+                    && scroll_offset_before_autoscroll
+                        .is_some_and(|offset| offset != scroll_offset);
+                if is_moving || autoscrolled {
+                    cx.request_frame(FrameReason::Drag);
+                }
+
+                ReorderableListPrepaintState {
+                    hitbox,
+                    item_bounds,
+                    item_opacities,
+                }
+            },
+        )
+    }
+
+    fn paint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        let items = &mut self.items;
+        let on_move = self.on_move.clone();
+        let state = request_layout.state.clone();
+        let item_bounds = prepaint.item_bounds.clone();
+        let hitbox = prepaint.hitbox.clone();
+        let item_opacities = &prepaint.item_opacities;
+        self.interactivity
+            .paint(global_id, bounds, prepaint.hitbox.as_ref(), cx, |_, cx| {
+                for (item, opacity) in items.iter_mut().zip(item_opacities) {
+                    cx.with_element_opacity(*opacity, |cx| item.paint(cx));
+                }
+
+                cx.on_mouse_event({
+                    let state = state.clone();
+                    move |event: &MouseDownEvent, phase, cx| {
+                        if phase == DispatchPhase::Bubble
+                            && event.button == MouseButton::Left
+                            && hitbox
+                                .as_ref()
+                                .map_or(false, |hitbox| hitbox.is_hovered(cx))
+                        {
+                            if let Some(ix) = item_bounds
+                                .iter()
+                                .position(|bounds| bounds.contains(&event.position))
+                            {
+                                state.borrow_mut().pending_drag = Some(PendingDrag {
+                                    ix,
+                                    position: event.position,
+                                    cursor_offset: event.position - item_bounds[ix].origin,
+                                });
+                            }
+                        }
+                    }
+                });
+
+                cx.on_mouse_event({
+                    let state = state.clone();
+                    move |event: &MouseMoveEvent, phase, cx| {
+                        if phase != DispatchPhase::Bubble {
+                            return;
+                        }
+                        let mut state = state.borrow_mut();
+                        if state.drag.is_some() {
+                            cx.refresh();
+                        } else if let Some(pending_drag) = state.pending_drag {
+                            if !event.dragging() {
+                                state.pending_drag = None;
+                            } else if !cx.has_active_drag()
+                                && (event.position - pending_drag.position).magnitude()
+                                    > DRAG_THRESHOLD
+                            {
+                                state.pending_drag = None;
+                                state.drag = Some(ActiveDrag {
+                                    from: pending_drag.ix,
+                                    to: pending_drag.ix,
+                                    cursor_offset: pending_drag.cursor_offset,
+                                    last_autoscroll: None,
+                                });
+                                cx.refresh();
+                            }
+                        }
+                    }
+                });
+
+                // Handle the drop during the capture phase so that the items underneath the
+                // cursor don't treat it as the end of a click.
+                cx.on_mouse_event(move |_: &MouseUpEvent, phase, cx| {
+                    if phase != DispatchPhase::Capture {
+                        return;
+                    }
+                    let mut state = state.borrow_mut();
+                    state.pending_drag = None;
+                    if let Some(drag) = state.drag.take() {
+                        state.item_offsets.clear();
+                        drop(state);
+                        if drag.from != drag.to {
+                            on_move(
+                                &ReorderEvent {
+                                    from: drag.from,
+                                    to: drag.to,
+                                },
+                                cx,
+                            );
+                        }
+                        cx.stop_propagation();
+                        cx.refresh();
+                    }
+                });
+            })
+    }
+}
+
+impl IntoElement for ReorderableList {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, div, point, px, reorderable_list, Bounds, InteractiveElement, IntoElement,
+        Modifiers, MouseButton, ParentElement, Pixels, Point, Render, ReorderEvent, Styled,
+        TestAppContext, ViewContext, VisualTestContext,
+    };
+    use std::time::Duration;
+
+    use super::insertion_index;
+
+    struct Items {
+        items: Vec<&'static str>,
+        moves: Vec<ReorderEvent>,
+    }
+
+    impl Render for Items {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_full().pt(px(10.)).child(
+                reorderable_list(
+                    "items",
+                    self.items.iter().map(|item| {
+                        div()
+                            .debug_selector(|| item.to_string())
+                            .h(px(20.))
+                            .child(*item)
+                    }),
+                    cx.listener(|this, event: &ReorderEvent, cx| {
+                        let item = this.items.remove(event.from);
+                        this.items.insert(event.to, item);
+                        this.moves.push(*event);
+                        cx.notify();
+                    }),
+                )
+                .h(px(100.)),
+            )
+        }
+    }
+
+    fn drag(cx: &mut VisualTestContext, positions: &[Point<Pixels>]) {
+        cx.simulate_mouse_down(positions[0], MouseButton::Left, Modifiers::none());
+        for position in &positions[1..] {
+            cx.simulate_mouse_move(*position, MouseButton::Left, Modifiers::none());
+        }
+    }
+
+    #[test]
+    fn test_insertion_index() {
+        let item_bounds = (0..4)
+            .map(|ix| {
+                Bounds::new(
+                    point(px(0.), px(ix as f32 * 20.)),
+                    gpui::size(px(100.), px(20.)),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(insertion_index(&item_bounds, 0, px(5.)), 0);
+        assert_eq!(insertion_index(&item_bounds, 0, px(25.)), 0);
+        assert_eq!(insertion_index(&item_bounds, 0, px(45.)), 1);
+        assert_eq!(insertion_index(&item_bounds, 0, px(200.)), 3);
+        assert_eq!(insertion_index(&item_bounds, 3, px(-10.)), 0);
+        assert_eq!(insertion_index(&item_bounds, 3, px(35.)), 2);
+    }
+
+    #[gpui::test]
+    fn test_drag_to_reorder(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| Items {
+            items: vec!["a", "b", "c", "d", "e"],
+            moves: Vec::new(),
+        });
+        cx.run_until_parked();
+
+        // Dragging "a" past the middle of "b" opens a gap below "b".
+        drag(
+            cx,
+            &[
+                point(px(5.), px(15.)),
+                point(px(5.), px(25.)),
+                point(px(5.), px(45.)),
+            ],
+        );
+        cx.executor().advance_clock(Duration::from_millis(150));
+        cx.simulate_mouse_move(point(px(5.), px(46.)), MouseButton::Left, Modifiers::none());
+        assert_eq!(cx.debug_bounds("b").unwrap().origin.y, px(10.));
+        assert_eq!(cx.debug_bounds("c").unwrap().origin.y, px(50.));
+
+        cx.simulate_mouse_up(point(px(5.), px(46.)), MouseButton::Left, Modifiers::none());
+        view.update(cx, |view, _| {
+            assert_eq!(view.items, ["b", "a", "c", "d", "e"]);
+            assert_eq!(view.moves, [ReorderEvent { from: 0, to: 1 }]);
+        });
+    }
+
+    #[gpui::test]
+    fn test_escape_cancels_drag(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| Items {
+            items: vec!["a", "b", "c"],
+            moves: Vec::new(),
+        });
+        cx.run_until_parked();
+
+        drag(
+            cx,
+            &[
+                point(px(5.), px(15.)),
+                point(px(5.), px(25.)),
+                point(px(5.), px(55.)),
+            ],
+        );
+        cx.simulate_keystrokes("escape");
+        cx.simulate_mouse_up(point(px(5.), px(55.)), MouseButton::Left, Modifiers::none());
+        view.update(cx, |view, _| {
+            assert_eq!(view.items, ["a", "b", "c"]);
+            assert!(view.moves.is_empty());
+        });
+    }
+
+    #[gpui::test]
+    fn test_drag_autoscrolls(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| Items {
+            items: vec!["a", "b", "c", "d", "e", "f", "g", "h"],
+            moves: Vec::new(),
+        });
+        cx.run_until_parked();
+
+        // Resting the dragged item at the bottom edge keeps scrolling the list.
+        drag(
+            cx,
+            &[
+                point(px(5.), px(15.)),
+                point(px(5.), px(25.)),
+                point(px(5.), px(108.)),
+            ],
+        );
+        for _ in 0..4 {
+            cx.executor().advance_clock(Duration::from_millis(100));
+            cx.simulate_mouse_move(
+                point(px(5.), px(108.)),
+                MouseButton::Left,
+                Modifiers::none(),
+            );
+        }
+        assert!(cx.debug_bounds("b").unwrap().origin.y < px(0.));
+
+        cx.simulate_mouse_up(
+            point(px(5.), px(108.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        view.update(cx, |view, _| {
+            assert_eq!(view.moves.len(), 1);
+            assert!(view.moves[0].to > 2);
+        });
+    }
+
+    #[gpui::test]
+    fn test_dragging_past_the_edges_scrolls_to_the_ends(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| Items {
+            items: vec!["a", "b", "c", "d", "e", "f", "g", "h"],
+            moves: Vec::new(),
+        });
+        cx.run_until_parked();
+        let drag_to = |cx: &mut VisualTestContext, y: f32| {
+            cx.executor().advance_clock(Duration::from_millis(20));
+            cx.simulate_mouse_move(point(px(5.), px(y)), MouseButton::Left, Modifiers::none());
+            cx.debug_bounds("b").unwrap().origin.y
+        };
+
+        // Below the list, the list scrolls further with every frame, until it reaches the end.
+        drag(cx, &[point(px(5.), px(15.)), point(px(5.), px(25.))]);
+        let mut last_y = drag_to(cx, 150.);
+        for _ in 0..3 {
+            let y = drag_to(cx, 150.);
+            assert!(y < last_y);
+            last_y = y;
+        }
+        for _ in 0..10 {
+            last_y = drag_to(cx, 150.);
+        }
+        // "b" is laid out at 30px, moved up by the height of "a" and scrolled by the 60px the
+        // items overflow the list by.
+        assert_eq!(last_y, px(-50.));
+
+        // Above the list, it scrolls back to the start.
+        for _ in 0..3 {
+            let y = drag_to(cx, 0.);
+            assert!(y > last_y);
+            last_y = y;
+        }
+        for _ in 0..10 {
+            last_y = drag_to(cx, 0.);
+        }
+        assert_eq!(last_y, px(30.));
+
+        cx.simulate_mouse_up(point(px(5.), px(0.)), MouseButton::Left, Modifiers::none());
+        view.update(cx, |view, _| assert!(view.moves.is_empty()));
+    }
+}
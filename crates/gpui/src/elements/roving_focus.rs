@@ -0,0 +1,942 @@
+//! A container that moves an "active" item through its children with the keyboard.
+//!
+//! The container is focused as a whole, rather than each of its items, so it's a single stop when
+//! moving focus between elements. While it's focused, the arrow keys along its axis move the active
+//! item, home and end jump to the first and last items, page up and page down move by the height of
+//! the container, and enter or space activate the active item. When the items are given labels,
+//! typing selects the next item whose label starts with the typed text. A container that scrolls
+//! scrolls the active item into view whenever it changes.
+//!
+//! Lists too long to lay out every item can track their active item with a [`RovingFocusHandle`]
+//! instead, see [`UniformList::track_active`](crate::UniformList::track_active), and move it
+//! with [`RovingFocusHandle::move_active`] when they're navigated from elsewhere, such as from the
+//! query editor of a picker.
+
+use crate::{
+    point, Along, AnyElement, Axis, Bounds, DispatchPhase, Element, ElementId, GlobalElementId,
+    Hitbox, InteractiveElement, Interactivity, IntoElement, KeyDownEvent, LayoutId, MouseButton,
+    MouseDownEvent, Pixels, Point, SharedString, StyleRefinement, Styled, WindowContext,
+};
+use refineable::Refineable;
+use smallvec::SmallVec;
+use std::{
+    cell::{RefCell, RefMut},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+use taffy::style::{Display, FlexDirection};
+
+/// How long after the last typed character the next one starts a new search instead of extending
+/// the current one.
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Construct a container of `item_count` items built by `render_item`, which is passed the index
+/// of each item and whether it's the active one. The container can be focused, and arrow keys
+/// move the active item while it is.
+#[track_caller]
+pub fn roving_focus<E>(
+    id: impl Into<ElementId>,
+    item_count: usize,
+    render_item: impl Fn(usize, bool, &mut WindowContext) -> E + 'static,
+) -> RovingFocus
+where
+    E: IntoElement + Styled,
+{
+    let mut base_style = StyleRefinement::default();
+    base_style.display = Some(Display::Flex);
+    base_style.flex_direction = Some(FlexDirection::Column);
+
+    RovingFocus {
+        item_count,
+        render_item: Box::new(move |ix, active_style, cx| {
+            let mut item = render_item(ix, active_style.is_some(), cx);
+            if let Some(active_style) = active_style {
+                item.style().refine(active_style);
+            }
+            item.into_any_element()
+        }),
+        active_style: Box::default(),
+        axis: Axis::Vertical,
+        wrap_around: false,
+        selectable: None,
+        item_label: None,
+        on_activate: None,
+        on_active_change: None,
+        handle: None,
+        interactivity: Interactivity {
+            element_id: Some(id.into()),
+            base_style: Box::new(base_style),
+            focusable: true,
+
+            #[cfg(debug_assertions)]
+            location: Some(*core::panic::Location::caller()),
+
+            ..Default::default()
+        },
+    }
+}
+
+/// A handle to the active item of a [`RovingFocus`] container, for reading it or moving it from
+/// outside the container.
+#[derive(Clone, Default)]
+pub struct RovingFocusHandle(Rc<RefCell<RovingFocusState>>);
+
+impl RovingFocusHandle {
+    /// Construct a new handle, with no item active.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The index of the active item, if any.
+    pub fn active_index(&self) -> Option<usize> {
+        self.0.borrow().active
+    }
+
+    /// Make the item at the given index active, scrolling it into view the next time the container
+    /// is drawn.
+    pub fn set_active_index(&self, ix: Option<usize>) {
+        let mut state = self.0.borrow_mut();
+        state.active = ix;
+        state.scroll_to_active = true;
+    }
+
+    /// Move the active item among `item_count` items as the container's keys do, scrolling it
+    /// into view the next time the container is drawn, and return the new active item. Paging
+    /// goes by the size of the container when it was last drawn.
+    pub fn move_active(&self, movement: RovingMovement, item_count: usize) -> Option<usize> {
+        let mut state = self.0.borrow_mut();
+        state.set_item_count(item_count);
+        let active = state.target(movement);
+        state.active = active;
+        state.scroll_to_active = true;
+        active
+    }
+
+    /// Record the layout of a list of `item_count` items of the same size, all of which can be
+    /// active, and take the item to scroll into view, if there is one.
+    pub(crate) fn layout_uniform_items(
+        &self,
+        item_count: usize,
+        item_size: Pixels,
+        viewport: Bounds<Pixels>,
+    ) -> Option<usize> {
+        let mut state = self.0.borrow_mut();
+        state.set_item_count(item_count);
+        state.selectable.clear();
+        state.labels.clear();
+        state.item_bounds.clear();
+        state.uniform_item_size = Some(item_size);
+        state.viewport = viewport;
+        let scroll_to_active = std::mem::take(&mut state.scroll_to_active);
+        state.active.filter(|_| scroll_to_active)
+    }
+
+    /// Handle the navigation keys for a list tracking its active item with this handle.
+    pub(crate) fn handle_key_down(&self, event: &KeyDownEvent, cx: &mut WindowContext) {
+        RovingFocus::handle_key_down(&self.0, None, None, event, cx);
+    }
+}
+
+/// A move of the active item of a [`RovingFocus`] container, or of a list tracking its active
+/// item with a [`RovingFocusHandle`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RovingMovement {
+    /// To the previous item that can be active.
+    Previous,
+    /// To the next item that can be active.
+    Next,
+    /// To the first item that can be active.
+    First,
+    /// To the last item that can be active.
+    Last,
+    /// Back by the size of the container.
+    PageUp,
+    /// Forward by the size of the container.
+    PageDown,
+}
+
+/// A container with one active item that's moved with the keyboard, see [`roving_focus`].
+pub struct RovingFocus {
+    item_count: usize,
+    render_item: Box<dyn Fn(usize, Option<&StyleRefinement>, &mut WindowContext) -> AnyElement>,
+    active_style: Box<StyleRefinement>,
+    axis: Axis,
+    wrap_around: bool,
+    selectable: Option<Box<dyn Fn(usize) -> bool>>,
+    item_label: Option<Box<dyn Fn(usize) -> Option<SharedString>>>,
+    on_activate: Option<Rc<dyn Fn(usize, &mut WindowContext)>>,
+    on_active_change: Option<Rc<dyn Fn(usize, &mut WindowContext)>>,
+    handle: Option<RovingFocusHandle>,
+    interactivity: Interactivity,
+}
+
+impl RovingFocus {
+    /// Lay the items out in a row, moving between them with the left and right arrow keys.
+    pub fn horizontal(mut self) -> Self {
+        self.axis = Axis::Horizontal;
+        self.interactivity.base_style.flex_direction = Some(FlexDirection::Row);
+        self
+    }
+
+    /// Set the style applied to the active item.
+    pub fn active_style(mut self, f: impl FnOnce(StyleRefinement) -> StyleRefinement) -> Self {
+        self.active_style = Box::new(f(StyleRefinement::default()));
+        self
+    }
+
+    /// Move the active item from the last item to the first with the next arrow key, and from the
+    /// first to the last with the previous one, instead of stopping at the ends.
+    pub fn wrap_around(mut self) -> Self {
+        self.wrap_around = true;
+        self
+    }
+
+    /// Only allow the items for which `selectable` returns true to become active. Keyboard
+    /// navigation skips over the others.
+    pub fn selectable(mut self, selectable: impl Fn(usize) -> bool + 'static) -> Self {
+        self.selectable = Some(Box::new(selectable));
+        self
+    }
+
+    /// Give the items labels which typed text is matched against. Items without a label are
+    /// never matched.
+    pub fn item_label(mut self, label: impl Fn(usize) -> Option<SharedString> + 'static) -> Self {
+        self.item_label = Some(Box::new(label));
+        self
+    }
+
+    /// Called with the index of the active item when enter or space is pressed.
+    pub fn on_activate(
+        mut self,
+        on_activate: impl Fn(usize, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.on_activate = Some(Rc::new(on_activate));
+        self
+    }
+
+    /// Called with the index of the newly active item when the keyboard moves the active item,
+    /// for containers whose selection follows the active item, such as radio groups.
+    pub fn on_active_change(
+        mut self,
+        on_active_change: impl Fn(usize, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.on_active_change = Some(Rc::new(on_active_change));
+        self
+    }
+
+    /// Track the active item with the given handle, so it can be read and moved by the owner of
+    /// the container. Otherwise it's kept in the element's state.
+    pub fn track_active(mut self, handle: &RovingFocusHandle) -> Self {
+        self.handle = Some(handle.clone());
+        self
+    }
+}
+
+struct RovingFocusState {
+    active: Option<usize>,
+    scroll_to_active: bool,
+    item_count: usize,
+    axis: Axis,
+    wrap_around: bool,
+    /// Which of the items can be active, or empty when they all can.
+    selectable: Vec<bool>,
+    labels: Vec<Option<SharedString>>,
+    /// The bounds of the items as they were last laid out, before scrolling.
+    item_bounds: Vec<Bounds<Pixels>>,
+    /// The size of each item along the axis, for lists whose items are all the same size and
+    /// aren't all laid out.
+    uniform_item_size: Option<Pixels>,
+    viewport: Bounds<Pixels>,
+    typeahead: String,
+    last_typed: Option<Instant>,
+}
+
+impl Default for RovingFocusState {
+    fn default() -> Self {
+        Self {
+            active: None,
+            scroll_to_active: false,
+            item_count: 0,
+            axis: Axis::Vertical,
+            wrap_around: false,
+            selectable: Vec::new(),
+            labels: Vec::new(),
+            item_bounds: Vec::new(),
+            uniform_item_size: None,
+            viewport: Bounds::default(),
+            typeahead: String::new(),
+            last_typed: None,
+        }
+    }
+}
+
+impl RovingFocusState {
+    fn set_item_count(&mut self, item_count: usize) {
+        if self.selectable.len() != item_count {
+            self.selectable.clear();
+        }
+        self.item_count = item_count;
+        if self.active.map_or(false, |active| active >= item_count) {
+            self.active = self.last();
+        }
+    }
+
+    fn is_selectable(&self, ix: usize) -> bool {
+        ix < self.item_count && self.selectable.get(ix).copied().unwrap_or(true)
+    }
+
+    fn target(&self, movement: RovingMovement) -> Option<usize> {
+        match movement {
+            RovingMovement::Previous => self.previous(),
+            RovingMovement::Next => self.next(),
+            RovingMovement::First => self.first(),
+            RovingMovement::Last => self.last(),
+            RovingMovement::PageUp => self.page(false),
+            RovingMovement::PageDown => self.page(true),
+        }
+    }
+
+    fn first(&self) -> Option<usize> {
+        (0..self.item_count).find(|ix| self.is_selectable(*ix))
+    }
+
+    fn last(&self) -> Option<usize> {
+        (0..self.item_count)
+            .rev()
+            .find(|ix| self.is_selectable(*ix))
+    }
+
+    fn next(&self) -> Option<usize> {
+        let Some(active) = self.active else {
+            return self.first();
+        };
+        let count = self.item_count;
+        if self.wrap_around {
+            (1..count)
+                .map(|offset| (active + offset) % count)
+                .find(|ix| self.is_selectable(*ix))
+                .or(Some(active))
+        } else {
+            (active + 1..count)
+                .find(|ix| self.is_selectable(*ix))
+                .or(Some(active))
+        }
+    }
+
+    fn previous(&self) -> Option<usize> {
+        let Some(active) = self.active else {
+            return self.last();
+        };
+        let count = self.item_count;
+        if self.wrap_around {
+            (1..count)
+                .map(|offset| (active + count - offset) % count)
+                .find(|ix| self.is_selectable(*ix))
+                .or(Some(active))
+        } else {
+            (0..active)
+                .rev()
+                .find(|ix| self.is_selectable(*ix))
+                .or(Some(active))
+        }
+    }
+
+    /// Where the item at the given index starts along the axis, as it was last laid out.
+    fn item_start(&self, ix: usize) -> Option<Pixels> {
+        match self.uniform_item_size {
+            Some(item_size) => Some(item_size * ix),
+            None => Some(self.item_bounds.get(ix)?.origin.along(self.axis)),
+        }
+    }
+
+    /// The item a container's length away from the active one, or the last one in that direction
+    /// when there aren't enough items.
+    fn page(&self, forward: bool) -> Option<usize> {
+        let Some(active) = self.active else {
+            return if forward { self.last() } else { self.first() };
+        };
+        let Some(start) = self.item_start(active) else {
+            return Some(active);
+        };
+        let page = self.viewport.size.along(self.axis);
+        let target = if forward {
+            (active..self.item_count)
+                .filter(|ix| self.is_selectable(*ix))
+                .take_while(|ix| {
+                    self.item_start(*ix)
+                        .map_or(false, |item| item <= start + page)
+                })
+                .last()
+        } else {
+            (0..=active)
+                .filter(|ix| self.is_selectable(*ix))
+                .find(|ix| {
+                    self.item_start(*ix)
+                        .map_or(false, |item| item >= start - page)
+                })
+        };
+        target.or(Some(active))
+    }
+
+    /// Extend the typed text with `text` and find the item to select for it. Typing the same
+    /// character repeatedly cycles through the items starting with it.
+    fn typeahead(&mut self, text: &str, now: Instant) -> Option<usize> {
+        if self.last_typed.map_or(true, |last_typed| {
+            now.duration_since(last_typed) > TYPEAHEAD_TIMEOUT
+        }) {
+            self.typeahead.clear();
+        }
+        self.last_typed = Some(now);
+        self.typeahead.push_str(&text.to_lowercase());
+
+        let mut chars = self.typeahead.chars();
+        let first_char = chars.next()?;
+        let repeated = chars.all(|c| c == first_char);
+        let (query, start) = if repeated {
+            (first_char.to_string(), self.active.map_or(0, |ix| ix + 1))
+        } else {
+            (self.typeahead.clone(), self.active.unwrap_or(0))
+        };
+
+        let count = self.labels.len();
+        (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|ix| {
+                self.is_selectable(*ix)
+                    && self.labels[*ix]
+                        .as_ref()
+                        .map_or(false, |label| label.to_lowercase().starts_with(&query))
+            })
+    }
+}
+
+/// Frame state used by the [`RovingFocus`] container.
+pub struct RovingFocusFrameState {
+    child_layout_ids: SmallVec<[LayoutId; 8]>,
+    items: Vec<AnyElement>,
+    state: Rc<RefCell<RovingFocusState>>,
+}
+
+/// The bounds of the [`RovingFocus`] container's items as they're drawn.
+pub struct RovingFocusPrepaintState {
+    hitbox: Option<Hitbox>,
+    item_bounds: Rc<[Bounds<Pixels>]>,
+}
+
+impl RovingFocus {
+    fn handle_key_down(
+        state: &Rc<RefCell<RovingFocusState>>,
+        on_activate: Option<&Rc<dyn Fn(usize, &mut WindowContext)>>,
+        on_active_change: Option<&Rc<dyn Fn(usize, &mut WindowContext)>>,
+        event: &KeyDownEvent,
+        cx: &mut WindowContext,
+    ) {
+        let keystroke = &event.keystroke;
+        let modifiers = keystroke.modifiers;
+        if modifiers.control || modifiers.alt || modifiers.platform || modifiers.function {
+            return;
+        }
+
+        let mut state = state.borrow_mut();
+        let (previous_key, next_key) = match state.axis {
+            Axis::Vertical => ("up", "down"),
+            Axis::Horizontal => ("left", "right"),
+        };
+        let typing = !state.typeahead.is_empty()
+            && state.last_typed.map_or(false, |last_typed| {
+                cx.background_executor().now().duration_since(last_typed) <= TYPEAHEAD_TIMEOUT
+            });
+
+        let active = match keystroke.key.as_str() {
+            key if key == previous_key => state.target(RovingMovement::Previous),
+            key if key == next_key => state.target(RovingMovement::Next),
+            "home" => state.target(RovingMovement::First),
+            "end" => state.target(RovingMovement::Last),
+            "pageup" => state.target(RovingMovement::PageUp),
+            "pagedown" => state.target(RovingMovement::PageDown),
+            "enter" => {
+                return Self::activate(state, on_activate, cx);
+            }
+            "space" if !typing => {
+                return Self::activate(state, on_activate, cx);
+            }
+            _ => {
+                let text = keystroke
+                    .ime_key
+                    .as_deref()
+                    .or_else(|| (keystroke.key.chars().count() == 1).then_some(&*keystroke.key))
+                    .or_else(|| (keystroke.key == "space").then_some(" "));
+                match text {
+                    Some(text) if !state.labels.is_empty() => {
+                        let now = cx.background_executor().now();
+                        match state.typeahead(text, now) {
+                            Some(ix) => Some(ix),
+                            None => {
+                                cx.stop_propagation();
+                                return;
+                            }
+                        }
+                    }
+                    _ => return,
+                }
+            }
+        };
+
+        cx.stop_propagation();
+        if active != state.active {
+            state.active = active;
+            state.scroll_to_active = true;
+            drop(state);
+            cx.refresh();
+            if let (Some(active), Some(on_active_change)) = (active, on_active_change) {
+                on_active_change(active, cx);
+            }
+        }
+    }
+
+    fn activate(
+        state: RefMut<RovingFocusState>,
+        on_activate: Option<&Rc<dyn Fn(usize, &mut WindowContext)>>,
+        cx: &mut WindowContext,
+    ) {
+        if let (Some(active), Some(on_activate)) = (state.active, on_activate) {
+            drop(state);
+            cx.stop_propagation();
+            on_activate(active, cx);
+        }
+    }
+}
+
+impl Styled for RovingFocus {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.interactivity.base_style
+    }
+}
+
+impl InteractiveElement for RovingFocus {
+    fn interactivity(&mut self) -> &mut Interactivity {
+        &mut self.interactivity
+    }
+}
+
+impl Element for RovingFocus {
+    type RequestLayoutState = RovingFocusFrameState;
+    type PrepaintState = RovingFocusPrepaintState;
+
+    fn id(&self) -> Option<ElementId> {
+        self.interactivity.element_id.clone()
+    }
+
+    fn request_layout(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let state = match self.handle.as_ref() {
+            Some(handle) => handle.0.clone(),
+            None => cx.with_element_state(
+                global_id.unwrap(),
+                |state: Option<Rc<RefCell<RovingFocusState>>>, _| {
+                    let state = state.unwrap_or_default();
+                    (state.clone(), state)
+                },
+            ),
+        };
+
+        {
+            let mut state = state.borrow_mut();
+            let state = &mut *state;
+            state.item_count = self.item_count;
+            state.axis = self.axis;
+            state.wrap_around = self.wrap_around;
+            state.uniform_item_size = None;
+            state.selectable = (0..self.item_count)
+                .map(|ix| self.selectable.as_ref().map_or(true, |f| f(ix)))
+                .collect();
+            state.labels = match self.item_label.as_ref() {
+                Some(item_label) => (0..self.item_count).map(item_label).collect(),
+                None => Vec::new(),
+            };
+            if state
+                .active
+                .map_or(false, |active| active >= self.item_count)
+            {
+                state.active = state.last();
+            }
+        }
+
+        self.interactivity.on_key_down({
+            let state = state.clone();
+            let on_activate = self.on_activate.clone();
+            let on_active_change = self.on_active_change.clone();
+            move |event, cx| {
+                Self::handle_key_down(
+                    &state,
+                    on_activate.as_ref(),
+                    on_active_change.as_ref(),
+                    event,
+                    cx,
+                );
+            }
+        });
+
+        let active = state.borrow().active;
+        let mut items = (0..self.item_count)
+            .map(|ix| {
+                let active_style = (Some(ix) == active).then_some(&*self.active_style);
+                (self.render_item)(ix, active_style, cx)
+            })
+            .collect::<Vec<_>>();
+
+        let mut child_layout_ids = SmallVec::new();
+        let layout_id = self
+            .interactivity
+            .request_layout(global_id, cx, |style, cx| {
+                cx.with_text_style(style.text_style().cloned(), |cx| {
+                    child_layout_ids = items
+                        .iter_mut()
+                        .map(|item| item.request_layout(cx))
+                        .collect::<SmallVec<_>>();
+                    cx.request_layout(style, child_layout_ids.iter().copied())
+                })
+            });
+
+        (
+            layout_id,
+            RovingFocusFrameState {
+                child_layout_ids,
+                items,
+                state,
+            },
+        )
+    }
+
+    fn prepaint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        let child_layout_ids = &request_layout.child_layout_ids;
+        let unscrolled_item_bounds = child_layout_ids
+            .iter()
+            .map(|layout_id| cx.layout_bounds(*layout_id))
+            .collect::<Vec<_>>();
+        let content_size = if unscrolled_item_bounds.is_empty() {
+            bounds.size
+        } else {
+            let mut child_min = point(Pixels::MAX, Pixels::MAX);
+            let mut child_max = Point::default();
+            for child_bounds in &unscrolled_item_bounds {
+                child_min = child_min.min(&child_bounds.origin);
+                child_max = child_max.max(&child_bounds.lower_right());
+            }
+            (child_max - child_min).into()
+        };
+
+        {
+            let mut state = request_layout.state.borrow_mut();
+            let scroll_to_active = std::mem::take(&mut state.scroll_to_active);
+            if let (true, Some(active), Some(scroll_offset)) = (
+                scroll_to_active,
+                state.active,
+                self.interactivity.scroll_offset.as_ref(),
+            ) {
+                if let Some(item_bounds) = unscrolled_item_bounds.get(active) {
+                    // Scroll just far enough to show the whole item.
+                    let axis = self.axis;
+                    let mut scroll_offset = scroll_offset.borrow_mut();
+                    let offset = scroll_offset.along(axis);
+                    let item_start = item_bounds.origin.along(axis);
+                    let item_end = item_start + item_bounds.size.along(axis);
+                    let viewport_start = bounds.origin.along(axis);
+                    let viewport_end = viewport_start + bounds.size.along(axis);
+                    let offset = if item_start + offset < viewport_start {
+                        viewport_start - item_start
+                    } else if item_end + offset > viewport_end {
+                        viewport_end - item_end
+                    } else {
+                        offset
+                    };
+                    *scroll_offset = scroll_offset.apply_along(axis, |_| offset);
+                }
+            }
+            state.item_bounds = unscrolled_item_bounds;
+            state.viewport = bounds;
+        }
+
+        let items = &mut request_layout.items;
+        self.interactivity.prepaint(
+            global_id,
+            bounds,
+            content_size,
+            cx,
+            |_style, scroll_offset, hitbox, cx| {
+                let item_bounds = cx.with_element_offset(scroll_offset, |cx| {
+                    for item in items.iter_mut() {
+                        item.prepaint(cx);
+                    }
+                    child_layout_ids
+                        .iter()
+                        .map(|layout_id| cx.layout_bounds(*layout_id))
+                        .collect::<Rc<[_]>>()
+                });
+                RovingFocusPrepaintState {
+                    hitbox,
+                    item_bounds,
+                }
+            },
+        )
+    }
+
+    fn paint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        let items = &mut request_layout.items;
+        let state = request_layout.state.clone();
+        let item_bounds = prepaint.item_bounds.clone();
+        let hitbox = prepaint.hitbox.clone();
+        self.interactivity
+            .paint(global_id, bounds, prepaint.hitbox.as_ref(), cx, |_, cx| {
+                for item in items.iter_mut() {
+                    item.paint(cx);
+                }
+
+                // Pressing an item makes it active, so keyboard navigation continues from there.
+                cx.on_mouse_event(move |event: &MouseDownEvent, phase, cx| {
+                    if phase == DispatchPhase::Bubble
+                        && event.button == MouseButton::Left
+                        && hitbox
+                            .as_ref()
+                            .map_or(false, |hitbox| hitbox.is_hovered(cx))
+                    {
+                        let mut state = state.borrow_mut();
+                        if let Some(ix) = item_bounds
+                            .iter()
+                            .position(|bounds| bounds.contains(&event.position))
+                            .filter(|ix| state.is_selectable(*ix))
+                        {
+                            if state.active != Some(ix) {
+                                state.active = Some(ix);
+                                cx.refresh();
+                            }
+                        }
+                    }
+                });
+            })
+    }
+}
+
+impl IntoElement for RovingFocus {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, div, point, px, roving_focus, uniform_list, FocusHandle, InteractiveElement,
+        IntoElement, Modifiers, ParentElement, Render, RovingFocusHandle, RovingMovement,
+        SharedString, Styled, TestAppContext, ViewContext,
+    };
+    use std::time::Duration;
+
+    const ITEMS: [&str; 8] = [
+        "apple",
+        "banana",
+        "blueberry",
+        "cherry",
+        "date",
+        "elderberry",
+        "fig",
+        "grape",
+    ];
+
+    struct Fruits {
+        handle: RovingFocusHandle,
+        activated: Vec<usize>,
+    }
+
+    impl Render for Fruits {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_full().child(
+                roving_focus("fruits", ITEMS.len(), |ix, is_active, _| {
+                    div()
+                        .debug_selector(|| ITEMS[ix].to_string())
+                        .h(px(20.))
+                        .child(if is_active { "*" } else { " " })
+                        .child(ITEMS[ix])
+                })
+                .track_active(&self.handle)
+                .selectable(|ix| ITEMS[ix] != "date")
+                .item_label(|ix| Some(SharedString::from(ITEMS[ix])))
+                .on_activate({
+                    let view = cx.view().downgrade();
+                    move |ix, cx| {
+                        view.update(cx, |view, cx| {
+                            view.activated.push(ix);
+                            cx.notify();
+                        })
+                        .ok();
+                    }
+                })
+                .h(px(60.))
+                .overflow_y_scroll(),
+            )
+        }
+    }
+
+    fn fruits(cx: &mut TestAppContext) -> (gpui::View<Fruits>, &mut gpui::VisualTestContext) {
+        let (view, cx) = cx.add_window_view(|_| Fruits {
+            handle: RovingFocusHandle::new(),
+            activated: Vec::new(),
+        });
+        cx.run_until_parked();
+        // Clicking the first item focuses the container and makes it active.
+        cx.simulate_click(point(px(5.), px(5.)), Modifiers::none());
+        (view, cx)
+    }
+
+    #[gpui::test]
+    fn test_arrow_keys_move_active_item(cx: &mut TestAppContext) {
+        let (view, cx) = fruits(cx);
+        let active = |cx: &mut gpui::VisualTestContext| {
+            view.update(cx, |view, _| view.handle.active_index())
+        };
+        assert_eq!(active(cx), Some(0));
+
+        cx.simulate_keystrokes("down down down");
+        assert_eq!(active(cx), Some(3));
+        // Unselectable items are skipped.
+        cx.simulate_keystrokes("down");
+        assert_eq!(active(cx), Some(5));
+        cx.simulate_keystrokes("up");
+        assert_eq!(active(cx), Some(3));
+
+        cx.simulate_keystrokes("end");
+        assert_eq!(active(cx), Some(7));
+        cx.simulate_keystrokes("down");
+        assert_eq!(active(cx), Some(7));
+        cx.simulate_keystrokes("home");
+        assert_eq!(active(cx), Some(0));
+        cx.simulate_keystrokes("pagedown");
+        assert_eq!(active(cx), Some(3));
+
+        cx.simulate_keystrokes("enter");
+        cx.simulate_keystrokes("up space");
+        view.update(cx, |view, _| assert_eq!(view.activated, [3, 2]));
+    }
+
+    #[gpui::test]
+    fn test_typeahead(cx: &mut TestAppContext) {
+        let (view, cx) = fruits(cx);
+        let active = |cx: &mut gpui::VisualTestContext| {
+            view.update(cx, |view, _| view.handle.active_index())
+        };
+
+        cx.simulate_keystrokes("b");
+        assert_eq!(active(cx), Some(1));
+        // Repeating a character cycles through the items starting with it.
+        cx.simulate_keystrokes("b");
+        assert_eq!(active(cx), Some(2));
+        cx.simulate_keystrokes("b");
+        assert_eq!(active(cx), Some(1));
+
+        cx.executor().advance_clock(Duration::from_secs(2));
+        cx.simulate_keystrokes("b l");
+        assert_eq!(active(cx), Some(2));
+
+        // Unselectable items aren't matched.
+        cx.executor().advance_clock(Duration::from_secs(2));
+        cx.simulate_keystrokes("d");
+        assert_eq!(active(cx), Some(2));
+        cx.simulate_keystrokes("space");
+        view.update(cx, |view, _| assert!(view.activated.is_empty()));
+    }
+
+    #[gpui::test]
+    fn test_active_item_is_scrolled_into_view(cx: &mut TestAppContext) {
+        let (view, cx) = fruits(cx);
+
+        cx.simulate_keystrokes("end");
+        let bounds = cx.debug_bounds("grape").unwrap();
+        assert_eq!(bounds.bottom(), px(60.));
+
+        cx.simulate_keystrokes("up up up");
+        let bounds = cx.debug_bounds("cherry").unwrap();
+        assert_eq!(bounds.top(), px(0.));
+
+        view.update(cx, |view, cx| {
+            view.handle.set_active_index(Some(6));
+            cx.notify();
+        });
+        cx.run_until_parked();
+        let bounds = cx.debug_bounds("fig").unwrap();
+        assert_eq!(bounds.bottom(), px(60.));
+    }
+
+    struct Numbers {
+        focus_handle: FocusHandle,
+        handle: RovingFocusHandle,
+    }
+
+    impl Render for Numbers {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_full().child(
+                uniform_list(cx.view().clone(), "numbers", 100, |_, range, _| {
+                    range
+                        .map(|ix| {
+                            div()
+                                .debug_selector(move || format!("number-{ix}"))
+                                .h(px(20.))
+                                .child(format!("{ix}"))
+                        })
+                        .collect()
+                })
+                .track_active(&self.handle)
+                .h(px(60.))
+                .track_focus(&self.focus_handle),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_virtualized_list_tracks_active_item(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|cx| Numbers {
+            focus_handle: cx.focus_handle(),
+            handle: RovingFocusHandle::new(),
+        });
+        view.update(cx, |view, cx| cx.focus(&view.focus_handle));
+        cx.run_until_parked();
+        let handle = view.update(cx, |view, _| view.handle.clone());
+
+        cx.simulate_keystrokes("down down");
+        assert_eq!(handle.active_index(), Some(1));
+        cx.simulate_keystrokes("pagedown");
+        assert_eq!(handle.active_index(), Some(4));
+        assert_eq!(cx.debug_bounds("number-4").unwrap().bottom(), px(60.));
+
+        cx.simulate_keystrokes("end");
+        assert_eq!(handle.active_index(), Some(99));
+        assert_eq!(cx.debug_bounds("number-99").unwrap().bottom(), px(60.));
+        assert!(cx.debug_bounds("number-4").is_none());
+
+        // The active item can be moved from outside the list, such as by a picker's query editor.
+        assert_eq!(handle.move_active(RovingMovement::First, 100), Some(0));
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        assert_eq!(cx.debug_bounds("number-0").unwrap().top(), px(0.));
+        // A list that got shorter moves the active item back into it.
+        handle.set_active_index(Some(99));
+        assert_eq!(handle.move_active(RovingMovement::Next, 10), Some(9));
+    }
+}
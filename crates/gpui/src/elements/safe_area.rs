@@ -0,0 +1,130 @@
+use crate::{
+    Bounds, Element, ElementId, GlobalElementId, IntoElement, LayoutId, Pixels, Styled,
+    WindowContext,
+};
+
+/// Wraps the given element so that it is padded away from the window chrome drawn over the
+/// content area, as reported by [`WindowContext::window_insets`].
+///
+/// The insets are read every time the element is laid out, so the padding follows the window as
+/// it enters and exits fullscreen. On each edge with a non-zero inset, the inset replaces the
+/// element's own padding.
+pub fn safe_area<E: Element + Styled>(element: E) -> SafeArea<E> {
+    SafeArea { element }
+}
+
+/// An element that keeps its child clear of the window chrome, see [`safe_area`].
+pub struct SafeArea<E> {
+    element: E,
+}
+
+impl<E: Element + Styled> Element for SafeArea<E> {
+    type RequestLayoutState = E::RequestLayoutState;
+    type PrepaintState = E::PrepaintState;
+
+    fn id(&self) -> Option<ElementId> {
+        self.element.id()
+    }
+
+    fn request_layout(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let insets = cx.window_insets();
+        let padding = &mut self.element.style().padding;
+        if insets.top > Pixels::ZERO {
+            padding.top = Some(insets.top.into());
+        }
+        if insets.right > Pixels::ZERO {
+            padding.right = Some(insets.right.into());
+        }
+        if insets.bottom > Pixels::ZERO {
+            padding.bottom = Some(insets.bottom.into());
+        }
+        if insets.left > Pixels::ZERO {
+            padding.left = Some(insets.left.into());
+        }
+        self.element.request_layout(id, cx)
+    }
+
+    fn prepaint(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        self.element.prepaint(id, bounds, request_layout, cx)
+    }
+
+    fn paint(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        self.element.paint(id, bounds, request_layout, prepaint, cx)
+    }
+
+    fn cullable(&self) -> bool {
+        self.element.cullable()
+    }
+}
+
+impl<E: Element + Styled> IntoElement for SafeArea<E> {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, div, px, safe_area, Edges, InteractiveElement, IntoElement, ParentElement,
+        Render, Styled, TestAppContext, ViewContext,
+    };
+
+    struct SafeAreaView;
+
+    impl Render for SafeAreaView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            safe_area(
+                div()
+                    .size_full()
+                    .pt(px(4.))
+                    .pl(px(4.))
+                    .child(div().debug_selector(|| "content".into()).size_full()),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_safe_area_follows_window_insets(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| SafeAreaView);
+
+        cx.run_until_parked();
+        let bounds = cx.debug_bounds("content").unwrap();
+        assert_eq!(bounds.origin.x, px(4.));
+        assert_eq!(bounds.origin.y, px(4.));
+
+        cx.simulate_window_insets(Edges {
+            left: px(70.),
+            ..Default::default()
+        });
+        cx.run_until_parked();
+        let bounds = cx.debug_bounds("content").unwrap();
+        assert_eq!(bounds.origin.x, px(70.));
+        assert_eq!(bounds.origin.y, px(4.));
+
+        // The insets go away when the window becomes fullscreen.
+        cx.simulate_window_insets(Edges::default());
+        cx.run_until_parked();
+        let bounds = cx.debug_bounds("content").unwrap();
+        assert_eq!(bounds.origin.x, px(4.));
+    }
+}
@@ -92,6 +92,10 @@ impl Element for Svg {
                 }
             })
     }
+
+    fn cullable(&self) -> bool {
+        !self.interactivity.paint_when_clipped
+    }
 }
 
 impl IntoElement for Svg {
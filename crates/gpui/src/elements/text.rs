@@ -1,8 +1,9 @@
 use crate::{
-    ActiveTooltip, AnyTooltip, AnyView, Bounds, DispatchPhase, Element, ElementId, GlobalElementId,
-    HighlightStyle, Hitbox, IntoElement, LayoutId, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
-    Pixels, Point, SharedString, Size, TextRun, TextStyle, WhiteSpace, WindowContext, WrappedLine,
-    TOOLTIP_DELAY,
+    fill, point, ActiveTooltip, AnyTooltip, AnyView, Bounds, ClipboardItem, ContentMask,
+    DispatchPhase, Element, ElementId, FocusHandle, GlobalElementId, HighlightStyle, Hitbox, Hsla,
+    IntoElement, KeyDownEvent, LayoutId, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    Pixels, Point, SharedString, Size, TextAlign, TextDirection, TextRun, TextStyle, WhiteSpace,
+    WindowContext, WrapMode, WrappedLine, TOOLTIP_DELAY,
 };
 use anyhow::anyhow;
 use parking_lot::{Mutex, MutexGuard};
@@ -30,7 +31,15 @@ impl Element for &'static str {
         cx: &mut WindowContext,
     ) -> (LayoutId, Self::RequestLayoutState) {
         let mut state = TextLayout::default();
-        let layout_id = state.layout(SharedString::from(*self), None, cx);
+        let layout_id = state.layout(
+            SharedString::from(*self),
+            None,
+            None,
+            None,
+            WrapWidthAlignment::default(),
+            None,
+            cx,
+        );
         (layout_id, state)
     }
 
@@ -88,7 +97,15 @@ impl Element for SharedString {
         cx: &mut WindowContext,
     ) -> (LayoutId, Self::RequestLayoutState) {
         let mut state = TextLayout::default();
-        let layout_id = state.layout(self.clone(), None, cx);
+        let layout_id = state.layout(
+            self.clone(),
+            None,
+            None,
+            None,
+            WrapWidthAlignment::default(),
+            None,
+            cx,
+        );
         (layout_id, state)
     }
 
@@ -130,15 +147,40 @@ impl IntoElement for SharedString {
 pub struct StyledText {
     text: SharedString,
     runs: Option<Vec<TextRun>>,
+    wrap_mode: Option<WrapMode>,
+    wrap_width: Option<Pixels>,
+    wrap_width_alignment: WrapWidthAlignment,
+    line_clamp: Option<usize>,
+    on_clamp: Option<Box<dyn Fn(bool, &mut WindowContext)>>,
     layout: TextLayout,
 }
 
+/// Where text wrapped at a [`StyledText::wrap_width`] narrower than the element is placed within
+/// the element's width.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WrapWidthAlignment {
+    /// At the start edge, which is the left in left-to-right text and the right in
+    /// right-to-left text, leaving the rest of the width empty.
+    #[default]
+    Start,
+    /// In the middle, with the remaining width split evenly on either side.
+    Center,
+    /// At the end edge, which is the right in left-to-right text and the left in right-to-left
+    /// text.
+    End,
+}
+
 impl StyledText {
     /// Construct a new styled text element from the given string.
     pub fn new(text: impl Into<SharedString>) -> Self {
         StyledText {
             text: text.into(),
             runs: None,
+            wrap_mode: None,
+            wrap_width: None,
+            wrap_width_alignment: WrapWidthAlignment::default(),
+            line_clamp: None,
+            on_clamp: None,
             layout: TextLayout::default(),
         }
     }
@@ -181,6 +223,42 @@ impl StyledText {
         self.runs = Some(runs);
         self
     }
+
+    /// Set where lines may be broken when the text doesn't fit its wrap width. When unset, text
+    /// wraps between words unless the text style's `white_space` is `Nowrap`.
+    pub fn wrap(mut self, wrap_mode: WrapMode) -> Self {
+        self.wrap_mode = Some(wrap_mode);
+        self
+    }
+
+    /// Wrap lines at the given width, even if the element is laid out wider than that. The text
+    /// is then placed within the element's width according to [`Self::wrap_width_alignment`].
+    pub fn wrap_width(mut self, wrap_width: Pixels) -> Self {
+        self.wrap_width = Some(wrap_width);
+        self
+    }
+
+    /// Set where text wrapped at a [`Self::wrap_width`] is placed when the element is wider, at
+    /// the start by default. The text's own alignment applies within the wrap width.
+    pub fn wrap_width_alignment(mut self, alignment: WrapWidthAlignment) -> Self {
+        self.wrap_width_alignment = alignment;
+        self
+    }
+
+    /// Show at most the given number of visual lines, counting the lines broken by wrapping.
+    /// The element is only as tall as those lines, and the rest of the text isn't painted.
+    /// Whether the text was clamped is known once it's laid out, see [`TextLayout::is_clamped`].
+    pub fn line_clamp(mut self, lines: usize) -> Self {
+        self.line_clamp = Some(lines);
+        self
+    }
+
+    /// Call the given function with whether the text was clamped every time it's prepainted,
+    /// which follows the width the text was laid out at in the same frame.
+    pub fn on_clamp(mut self, on_clamp: impl Fn(bool, &mut WindowContext) + 'static) -> Self {
+        self.on_clamp = Some(Box::new(on_clamp));
+        self
+    }
 }
 
 impl Element for StyledText {
@@ -198,7 +276,15 @@ impl Element for StyledText {
 
         cx: &mut WindowContext,
     ) -> (LayoutId, Self::RequestLayoutState) {
-        let layout_id = self.layout.layout(self.text.clone(), self.runs.take(), cx);
+        let layout_id = self.layout.layout(
+            self.text.clone(),
+            self.runs.take(),
+            self.wrap_mode,
+            self.wrap_width,
+            self.wrap_width_alignment,
+            self.line_clamp,
+            cx,
+        );
         (layout_id, ())
     }
 
@@ -207,9 +293,12 @@ impl Element for StyledText {
         _id: Option<&GlobalElementId>,
         bounds: Bounds<Pixels>,
         _: &mut Self::RequestLayoutState,
-        _cx: &mut WindowContext,
+        cx: &mut WindowContext,
     ) {
-        self.layout.prepaint(bounds, &self.text)
+        self.layout.prepaint(bounds, &self.text);
+        if let Some(on_clamp) = self.on_clamp.as_ref() {
+            on_clamp(self.layout.is_clamped(), cx);
+        }
     }
 
     fn paint(
@@ -240,8 +329,15 @@ struct TextLayoutInner {
     lines: SmallVec<[WrappedLine; 1]>,
     line_height: Pixels,
     wrap_width: Option<Pixels>,
+    /// The width the text was asked to wrap at, and where to place it within a wider element.
+    column: Option<(Pixels, WrapWidthAlignment)>,
     size: Option<Size<Pixels>>,
     bounds: Option<Bounds<Pixels>>,
+    text_align: TextAlign,
+    direction: TextDirection,
+    /// The number of visual lines the text was wrapped into, including those clamped away.
+    line_count: usize,
+    clamped: bool,
 }
 
 impl TextLayout {
@@ -253,6 +349,10 @@ impl TextLayout {
         &mut self,
         text: SharedString,
         runs: Option<Vec<TextRun>>,
+        wrap_mode: Option<WrapMode>,
+        max_wrap_width: Option<Pixels>,
+        wrap_width_alignment: WrapWidthAlignment,
+        line_clamp: Option<usize>,
         cx: &mut WindowContext,
     ) -> LayoutId {
         let text_style = cx.text_style();
@@ -266,18 +366,33 @@ impl TextLayout {
         } else {
             vec![text_style.to_run(text.len())]
         };
+        let wrap_mode = wrap_mode.unwrap_or(match text_style.white_space {
+            WhiteSpace::Normal => WrapMode::Word,
+            WhiteSpace::Nowrap => WrapMode::None,
+        });
+        let text_align = text_style.text_align;
+        let direction = TextDirection::current(cx);
+        let column = max_wrap_width
+            .filter(|_| wrap_mode != WrapMode::None)
+            .map(|max_wrap_width| (max_wrap_width, wrap_width_alignment));
 
         let layout_id = cx.request_measured_layout(Default::default(), {
             let element_state = self.clone();
 
             move |known_dimensions, available_space, cx| {
-                let wrap_width = if text_style.white_space == WhiteSpace::Normal {
-                    known_dimensions.width.or(match available_space.width {
+                let wrap_width = if wrap_mode == WrapMode::None {
+                    None
+                } else {
+                    let layout_width = known_dimensions.width.or(match available_space.width {
                         crate::AvailableSpace::Definite(x) => Some(x),
                         _ => None,
-                    })
-                } else {
-                    None
+                    });
+                    match (layout_width, max_wrap_width) {
+                        (Some(layout_width), Some(max_wrap_width)) => {
+                            Some(layout_width.min(max_wrap_width))
+                        }
+                        (layout_width, max_wrap_width) => layout_width.or(max_wrap_width),
+                    }
                 };
 
                 if let Some(text_layout) = element_state.0.lock().as_ref() {
@@ -295,6 +410,7 @@ impl TextLayout {
                         font_size,
                         &runs,
                         wrap_width, // Wrap if we know the width.
+                        wrap_mode,
                     )
                     .log_err()
                 else {
@@ -302,25 +418,41 @@ impl TextLayout {
                         lines: Default::default(),
                         line_height,
                         wrap_width,
+                        column,
                         size: Some(Size::default()),
                         bounds: None,
+                        text_align,
+                        direction,
+                        line_count: 0,
+                        clamped: false,
                     });
                     return Size::default();
                 };
 
                 let mut size: Size<Pixels> = Size::default();
+                let mut line_count = 0;
                 for line in &lines {
                     let line_size = line.size(line_height);
                     size.height += line_size.height;
                     size.width = size.width.max(line_size.width).ceil();
+                    line_count += line.wrap_boundaries.len() + 1;
+                }
+                let clamped = line_clamp.map_or(false, |line_clamp| line_count > line_clamp);
+                if let Some(line_clamp) = line_clamp.filter(|_| clamped) {
+                    size.height = line_height * line_clamp as f32;
                 }
 
                 element_state.lock().replace(TextLayoutInner {
                     lines,
                     line_height,
                     wrap_width,
+                    column,
                     size: Some(size),
                     bounds: None,
+                    text_align,
+                    direction,
+                    line_count,
+                    clamped,
                 });
 
                 size
@@ -336,7 +468,31 @@ impl TextLayout {
             .as_mut()
             .ok_or_else(|| anyhow!("measurement has not been performed on {}", text))
             .unwrap();
+        // Text wrapped narrower than the element is placed within it as a column, which is where
+        // it's painted and hit tested.
+        let mut bounds = bounds;
+        if let Some((column_width, alignment)) = element_state.column {
+            let leftover = bounds.size.width - column_width;
+            if leftover > Pixels::ZERO {
+                let offset = match (alignment, element_state.direction) {
+                    (WrapWidthAlignment::Center, _) => leftover / 2.,
+                    (WrapWidthAlignment::Start, TextDirection::Ltr)
+                    | (WrapWidthAlignment::End, TextDirection::Rtl) => Pixels::ZERO,
+                    (WrapWidthAlignment::End, TextDirection::Ltr)
+                    | (WrapWidthAlignment::Start, TextDirection::Rtl) => leftover,
+                };
+                bounds.origin.x += offset;
+                bounds.size.width = column_width;
+            }
+        }
         element_state.bounds = Some(bounds);
+        for line in &mut element_state.lines {
+            line.align(
+                element_state.text_align,
+                element_state.direction,
+                bounds.size.width,
+            );
+        }
     }
 
     fn paint(&mut self, text: &str, cx: &mut WindowContext) {
@@ -351,11 +507,18 @@ impl TextLayout {
             .unwrap();
 
         let line_height = element_state.line_height;
-        let mut line_origin = bounds.origin;
-        for line in &element_state.lines {
-            line.paint(line_origin, line_height, cx).log_err();
-            line_origin.y += line.size(line_height).height;
-        }
+        // Clamped lines are cut off at the bottom of the element.
+        let content_mask = element_state.clamped.then_some(ContentMask { bounds });
+        cx.with_content_mask(content_mask, |cx| {
+            let mut line_origin = bounds.origin;
+            for line in &element_state.lines {
+                if line_origin.y >= bounds.bottom() {
+                    break;
+                }
+                line.paint(line_origin, line_height, cx).log_err();
+                line_origin.y += line.size(line_height).height;
+            }
+        });
     }
 
     /// Get the byte index into the input of the pixel position.
@@ -423,11 +586,63 @@ impl TextLayout {
         None
     }
 
+    /// Get the bounds covering the glyphs in the given byte range, one for each visual line the
+    /// range touches, whether that line was broken by a newline or by wrapping.
+    pub fn bounds_for_range(&self, range: Range<usize>) -> SmallVec<[Bounds<Pixels>; 3]> {
+        let mut result = SmallVec::new();
+        let (Some(start), Some(end)) = (
+            self.position_for_index(range.start),
+            self.position_for_index(range.end),
+        ) else {
+            return result;
+        };
+        let bounds = self.bounds();
+        let line_height = self.line_height();
+
+        if start.y == end.y {
+            result.push(Bounds::from_corners(
+                start,
+                point(end.x, end.y + line_height),
+            ));
+        } else {
+            result.push(Bounds::from_corners(
+                start,
+                point(bounds.right(), start.y + line_height),
+            ));
+            if end.y > start.y + line_height {
+                result.push(Bounds::from_corners(
+                    point(bounds.left(), start.y + line_height),
+                    point(bounds.right(), end.y),
+                ));
+            }
+            result.push(Bounds::from_corners(
+                point(bounds.left(), end.y),
+                point(end.x, end.y + line_height),
+            ));
+        }
+
+        result
+    }
+
     /// The bounds of this layout.
     pub fn bounds(&self) -> Bounds<Pixels> {
         self.0.lock().as_ref().unwrap().bounds.unwrap()
     }
 
+    /// Whether lines were left out of this layout to fit its line clamp, see
+    /// [`StyledText::line_clamp`].
+    pub fn is_clamped(&self) -> bool {
+        self.0
+            .lock()
+            .as_ref()
+            .map_or(false, |layout| layout.clamped)
+    }
+
+    /// The number of visual lines the text was wrapped into, including any that were clamped.
+    pub fn line_count(&self) -> usize {
+        self.0.lock().as_ref().map_or(0, |layout| layout.line_count)
+    }
+
     /// The line height for this layout.
     pub fn line_height(&self) -> Pixels {
         self.0.lock().as_ref().unwrap().line_height
@@ -456,6 +671,7 @@ pub struct InteractiveText {
     hover_listener: Option<Box<dyn Fn(Option<usize>, MouseMoveEvent, &mut WindowContext<'_>)>>,
     tooltip_builder: Option<Rc<dyn Fn(usize, &mut WindowContext<'_>) -> Option<AnyView>>>,
     clickable_ranges: Vec<Range<usize>>,
+    selection_background: Option<Hsla>,
 }
 
 struct InteractiveTextClickEvent {
@@ -469,6 +685,40 @@ pub struct InteractiveTextState {
     mouse_down_index: Rc<Cell<Option<usize>>>,
     hovered_index: Rc<Cell<Option<usize>>>,
     active_tooltip: Rc<RefCell<Option<ActiveTooltip>>>,
+    focus_handle: Option<FocusHandle>,
+    selection: Rc<RefCell<Option<TextSelection>>>,
+    is_selecting: Rc<Cell<bool>>,
+}
+
+/// A selected range of an [`InteractiveText`], remembered along with the text it was made in so
+/// that it can be dropped when the text changes.
+struct TextSelection {
+    text: SharedString,
+    anchor: usize,
+    head: usize,
+}
+
+impl TextSelection {
+    fn range(&self) -> Range<usize> {
+        self.anchor.min(self.head)..self.anchor.max(self.head)
+    }
+}
+
+/// Returns the range of the word containing the given byte index, or an empty range at that
+/// index if it doesn't fall on a word.
+pub(crate) fn word_range(text: &str, ix: usize) -> Range<usize> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let start = text[..ix]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_word_char(*c))
+        .last()
+        .map_or(ix, |(start, _)| start);
+    let end = text[ix..]
+        .char_indices()
+        .find(|(_, c)| !is_word_char(*c))
+        .map_or(text.len(), |(end, _)| ix + end);
+    start..end
 }
 
 /// InteractiveTest is a wrapper around StyledText that adds mouse interactions.
@@ -482,9 +732,19 @@ impl InteractiveText {
             hover_listener: None,
             tooltip_builder: None,
             clickable_ranges: Vec::new(),
+            selection_background: None,
         }
     }
 
+    /// Let the user select a range of the text by dragging over it, highlighting it with the
+    /// given color. Shift-click extends the selection, double-click selects a word, and the
+    /// platform's copy keystroke copies the selected text. The selection is cleared when the
+    /// text loses focus or its content changes.
+    pub fn selectable(mut self, selection_background: impl Into<Hsla>) -> Self {
+        self.selection_background = Some(selection_background.into());
+        self
+    }
+
     /// on_click is called when the user clicks on one of the given ranges, passing the index of
     /// the clicked range.
     pub fn on_click(
@@ -556,9 +816,7 @@ impl Element for InteractiveText {
                 if let Some(interactive_state) = interactive_state.as_ref() {
                     if let Some(active_tooltip) = interactive_state.active_tooltip.borrow().as_ref()
                     {
-                        if let Some(tooltip) = active_tooltip.tooltip.clone() {
-                            cx.set_tooltip(tooltip);
-                        }
+                        cx.set_tooltip(active_tooltip.tooltip.clone());
                     }
                 }
 
@@ -654,54 +912,42 @@ impl Element for InteractiveText {
                 });
 
                 if let Some(tooltip_builder) = self.tooltip_builder.clone() {
-                    let hitbox = hitbox.clone();
                     let active_tooltip = interactive_state.active_tooltip.clone();
                     let pending_mouse_down = interactive_state.mouse_down_index.clone();
                     let text_layout = text_layout.clone();
 
-                    cx.on_mouse_event(move |event: &MouseMoveEvent, phase, cx| {
-                        let position = text_layout.index_for_position(event.position).ok();
-                        let is_hovered = position.is_some()
-                            && hitbox.is_hovered(cx)
-                            && pending_mouse_down.get().is_none();
-                        if !is_hovered {
-                            active_tooltip.take();
-                            return;
+                    cx.on_mouse_event({
+                        let hitbox = hitbox.clone();
+                        let active_tooltip = active_tooltip.clone();
+                        let pending_mouse_down = pending_mouse_down.clone();
+                        let text_layout = text_layout.clone();
+                        move |event: &MouseMoveEvent, _, cx| {
+                            let is_hovered = hitbox.is_hovered(cx)
+                                && pending_mouse_down.get().is_none()
+                                && text_layout.index_for_position(event.position).is_ok();
+                            if !is_hovered {
+                                active_tooltip.take();
+                            }
                         }
-                        let position = position.unwrap();
+                    });
 
-                        if phase != DispatchPhase::Bubble {
+                    cx.on_hover_delayed(hitbox, TOOLTIP_DELAY, move |cx| {
+                        if pending_mouse_down.get().is_some() {
                             return;
                         }
-
-                        if active_tooltip.borrow().is_none() {
-                            let task = cx.spawn({
-                                let active_tooltip = active_tooltip.clone();
-                                let tooltip_builder = tooltip_builder.clone();
-
-                                move |mut cx| async move {
-                                    cx.background_executor().timer(TOOLTIP_DELAY).await;
-                                    cx.update(|cx| {
-                                        let new_tooltip =
-                                            tooltip_builder(position, cx).map(|tooltip| {
-                                                ActiveTooltip {
-                                                    tooltip: Some(AnyTooltip {
-                                                        view: tooltip,
-                                                        mouse_position: cx.mouse_position(),
-                                                    }),
-                                                    _task: None,
-                                                }
-                                            });
-                                        *active_tooltip.borrow_mut() = new_tooltip;
-                                        cx.refresh();
-                                    })
-                                    .ok();
-                                }
-                            });
+                        let Some(position) =
+                            text_layout.index_for_position(cx.mouse_position()).ok()
+                        else {
+                            return;
+                        };
+                        if let Some(view) = tooltip_builder(position, cx) {
                             *active_tooltip.borrow_mut() = Some(ActiveTooltip {
-                                tooltip: None,
-                                _task: Some(task),
+                                tooltip: AnyTooltip {
+                                    view,
+                                    mouse_position: cx.mouse_position(),
+                                },
                             });
+                            cx.refresh();
                         }
                     });
 
@@ -711,6 +957,20 @@ impl Element for InteractiveText {
                     });
                 }
 
+                if let Some(selection_background) = self.selection_background {
+                    let focus_handle = interactive_state
+                        .focus_handle
+                        .get_or_insert_with(|| cx.focus_handle())
+                        .clone();
+                    self.paint_selection(
+                        &focus_handle,
+                        &interactive_state,
+                        selection_background,
+                        hitbox,
+                        cx,
+                    );
+                }
+
                 self.text.paint(None, bounds, &mut (), &mut (), cx);
 
                 ((), interactive_state)
@@ -719,6 +979,132 @@ impl Element for InteractiveText {
     }
 }
 
+impl InteractiveText {
+    fn paint_selection(
+        &mut self,
+        focus_handle: &FocusHandle,
+        interactive_state: &InteractiveTextState,
+        selection_background: Hsla,
+        hitbox: &Hitbox,
+        cx: &mut WindowContext,
+    ) {
+        let text = self.text.text.clone();
+        let text_layout = self.text.layout().clone();
+        cx.set_focus_handle(focus_handle);
+
+        {
+            let mut selection = interactive_state.selection.borrow_mut();
+            if selection.as_ref().map_or(false, |selection| {
+                selection.text != text || !focus_handle.is_focused(cx)
+            }) {
+                selection.take();
+            }
+
+            if let Some(selection) = selection.as_ref() {
+                for bounds in text_layout.bounds_for_range(selection.range()) {
+                    cx.paint_quad(fill(bounds, selection_background));
+                }
+            }
+        }
+
+        cx.on_mouse_event({
+            let text = text.clone();
+            let text_layout = text_layout.clone();
+            let selection = interactive_state.selection.clone();
+            let is_selecting = interactive_state.is_selecting.clone();
+            let focus_handle = focus_handle.clone();
+            let hitbox = hitbox.clone();
+            move |event: &MouseDownEvent, phase, cx| {
+                if phase != DispatchPhase::Bubble
+                    || event.button != MouseButton::Left
+                    || !hitbox.is_hovered(cx)
+                {
+                    return;
+                }
+
+                let ix = text_layout
+                    .index_for_position(event.position)
+                    .unwrap_or_else(|ix| ix);
+                let mut selection = selection.borrow_mut();
+                match selection.as_mut() {
+                    Some(selection) if event.modifiers.shift => selection.head = ix,
+                    _ => {
+                        let range = if event.click_count == 2 {
+                            word_range(&text, ix)
+                        } else {
+                            ix..ix
+                        };
+                        *selection = Some(TextSelection {
+                            text: text.clone(),
+                            anchor: range.start,
+                            head: range.end,
+                        });
+                    }
+                }
+                is_selecting.set(event.click_count < 2);
+                cx.focus(&focus_handle);
+                cx.refresh();
+            }
+        });
+
+        cx.on_mouse_event({
+            let text_layout = text_layout.clone();
+            let selection = interactive_state.selection.clone();
+            let is_selecting = interactive_state.is_selecting.clone();
+            move |event: &MouseMoveEvent, phase, cx| {
+                if phase != DispatchPhase::Bubble || !is_selecting.get() {
+                    return;
+                }
+                if event.pressed_button != Some(MouseButton::Left) {
+                    is_selecting.set(false);
+                    return;
+                }
+
+                if let Some(selection) = selection.borrow_mut().as_mut() {
+                    let ix = text_layout
+                        .index_for_position(event.position)
+                        .unwrap_or_else(|ix| ix);
+                    if selection.head != ix {
+                        selection.head = ix;
+                        cx.refresh();
+                    }
+                }
+            }
+        });
+
+        cx.on_mouse_event({
+            let is_selecting = interactive_state.is_selecting.clone();
+            move |_: &MouseUpEvent, phase, _| {
+                if phase == DispatchPhase::Capture {
+                    is_selecting.set(false);
+                }
+            }
+        });
+
+        cx.on_key_event({
+            let selection = interactive_state.selection.clone();
+            move |event: &KeyDownEvent, phase, cx| {
+                if phase != DispatchPhase::Bubble
+                    || event.keystroke.key != "c"
+                    || !event.keystroke.modifiers.secondary()
+                {
+                    return;
+                }
+
+                if let Some(selection) = selection.borrow().as_ref() {
+                    let range = selection.range();
+                    if !range.is_empty() {
+                        cx.write_to_clipboard(ClipboardItem::new(
+                            selection.text[range].to_string(),
+                        ));
+                        cx.stop_propagation();
+                    }
+                }
+            }
+        });
+    }
+}
+
 impl IntoElement for InteractiveText {
     type Element = Self;
 
@@ -726,3 +1112,537 @@ impl IntoElement for InteractiveText {
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, div, hsla, point, provide_context, px, ClipboardItem, HighlightStyle,
+        InteractiveText, IntoElement, Modifiers, MouseButton, MouseDownEvent, MouseUpEvent,
+        ParentElement, Pixels, Point, Render, ScaledPixels, SharedString, Styled, StyledText,
+        TestAppContext, TextAlign, TextDirection, TextLayout, UnderlineStyle, ViewContext,
+        VisualContext, VisualTestContext, WindowContext, WrapWidthAlignment,
+    };
+
+    use super::word_range;
+    use std::{cell::Cell, rc::Rc};
+
+    const COPY: &str = if cfg!(target_os = "macos") {
+        "cmd-c"
+    } else {
+        "ctrl-c"
+    };
+
+    struct SelectableTextView {
+        text: SharedString,
+    }
+
+    impl Render for SelectableTextView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().child(
+                InteractiveText::new("text", StyledText::new(self.text.clone()))
+                    .selectable(hsla(0.6, 0.8, 0.5, 0.3)),
+            )
+        }
+    }
+
+    #[test]
+    fn test_word_range() {
+        assert_eq!(word_range("hello world", 0), 0..5);
+        assert_eq!(word_range("hello world", 3), 0..5);
+        assert_eq!(word_range("hello world", 8), 6..11);
+        assert_eq!(word_range("hello, world", 5), 5..5);
+        assert_eq!(word_range("snake_case", 4), 0..10);
+    }
+
+    #[gpui::test]
+    fn test_double_click_selects_and_copies_word(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| SelectableTextView {
+            text: "Hello world".into(),
+        });
+
+        let position = point(px(1.), px(1.));
+        cx.simulate_event(MouseDownEvent {
+            position,
+            modifiers: Modifiers::none(),
+            button: MouseButton::Left,
+            click_count: 2,
+            first_mouse: false,
+        });
+        cx.simulate_event(MouseUpEvent {
+            position,
+            modifiers: Modifiers::none(),
+            button: MouseButton::Left,
+            click_count: 2,
+        });
+        cx.simulate_keystrokes(COPY);
+        assert_eq!(cx.read_from_clipboard().unwrap().text(), "Hello");
+
+        // Changing the text drops the selection, so there is nothing left to copy.
+        cx.write_to_clipboard(ClipboardItem::new(String::new()));
+        view.update(cx, |view, cx| {
+            view.text = "Goodbye world".into();
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.simulate_keystrokes(COPY);
+        assert_eq!(cx.read_from_clipboard().unwrap().text(), "");
+    }
+
+    struct DecoratedTextView;
+
+    impl Render for DecoratedTextView {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let mut text_style = cx.text_style();
+            text_style.font_family = "Zed Plex Mono".into();
+            text_style.font_size = px(16.).into();
+            text_style.line_height = px(20.).into();
+
+            // Ten monospace glyphs fit on a line, so "cccc" wraps onto the second line.
+            div()
+                .w(px(100.))
+                .font_family("Zed Plex Mono")
+                .text_size(px(16.))
+                .line_height(px(20.))
+                .child(StyledText::new("aaaa bbbb cccc").with_highlights(
+                    &text_style,
+                    [(
+                        5..14,
+                        HighlightStyle {
+                            underline: Some(UnderlineStyle {
+                                wavy: true,
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        },
+                    )],
+                ))
+        }
+    }
+
+    #[gpui::test]
+    fn test_decoration_follows_wrapped_lines(cx: &mut TestAppContext) {
+        add_plex_mono(cx);
+        let (_, cx) = cx.add_window_view(|_| DecoratedTextView);
+        cx.run_until_parked();
+
+        let (underlines, scale_factor) = cx.update(|cx: &mut WindowContext| {
+            (
+                cx.window.rendered_frame.scene.underlines.clone(),
+                cx.scale_factor(),
+            )
+        });
+        let line_height = ScaledPixels(20. * scale_factor);
+
+        // The decoration is split where the line wraps, and only covers the highlighted range.
+        assert_eq!(underlines.len(), 2);
+        let (first, second) = (&underlines[0], &underlines[1]);
+        assert!(first.bounds.origin.x > ScaledPixels(0.));
+        assert_eq!(second.bounds.origin.x, ScaledPixels(0.));
+        assert_eq!(second.bounds.origin.y - first.bounds.origin.y, line_height);
+        assert!(first.bounds.size.width > second.bounds.size.width);
+
+        // It sits below the middle of the first line, with a thickness taken from the font.
+        assert!(first.bounds.origin.y > line_height / 2.);
+        assert!(first.bounds.origin.y < line_height);
+        assert!(first.thickness > ScaledPixels(0.));
+        assert_eq!(first.thickness, second.thickness);
+        assert!(first.wavy && second.wavy);
+    }
+
+    fn add_plex_mono(cx: &mut TestAppContext) {
+        cx.text_system()
+            .add_fonts(vec![std::fs::read(
+                "../../assets/fonts/plex-mono/ZedPlexMono-Regular.ttf",
+            )
+            .unwrap()
+            .into()])
+            .unwrap();
+    }
+
+    // Ten monospace glyphs fit on a line, so this wraps after "cc " and "f ".
+    const PARAGRAPH: &str = "aaa bb cc dddd e f gggggg";
+
+    struct AlignedTextView {
+        align: TextAlign,
+        direction: TextDirection,
+        layout: Option<TextLayout>,
+    }
+
+    impl Render for AlignedTextView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let text = StyledText::new(PARAGRAPH);
+            self.layout = Some(text.layout().clone());
+            provide_context(
+                self.direction,
+                div()
+                    .w(px(100.))
+                    .font_family("Zed Plex Mono")
+                    .text_size(px(16.))
+                    .line_height(px(20.))
+                    .text_align(self.align)
+                    .child(InteractiveText::new("text", text).selectable(hsla(0.6, 0.8, 0.5, 0.3))),
+            )
+        }
+    }
+
+    fn add_aligned_text(
+        align: TextAlign,
+        direction: TextDirection,
+        cx: &mut TestAppContext,
+    ) -> (gpui::View<AlignedTextView>, &mut VisualTestContext) {
+        add_plex_mono(cx);
+        let (view, cx) = cx.add_window_view(|_| AlignedTextView {
+            align,
+            direction,
+            layout: None,
+        });
+        cx.run_until_parked();
+        (view, cx)
+    }
+
+    /// The x of the start of each word, grouped by the visual line it's painted on.
+    fn word_starts(view: &gpui::View<AlignedTextView>, cx: &mut VisualTestContext) -> Vec<String> {
+        let layout = view.update(cx, |view, _| view.layout.clone().unwrap());
+        let origin = layout.bounds().origin;
+        let mut lines = Vec::<(Pixels, Vec<String>)>::new();
+        let mut word_start = 0;
+        for word in PARAGRAPH.split(' ') {
+            let Point { x, y } = layout.position_for_index(word_start).unwrap() - origin;
+            match lines.last_mut() {
+                Some((line_y, words)) if *line_y == y => words.push(format!("{:.1}", f32::from(x))),
+                _ => lines.push((y, vec![format!("{:.1}", f32::from(x))])),
+            }
+            word_start += word.len() + 1;
+        }
+        lines
+            .into_iter()
+            .map(|(_, words)| words.join(" "))
+            .collect()
+    }
+
+    #[gpui::test]
+    fn test_start_aligned_lines(cx: &mut TestAppContext) {
+        let (view, cx) = add_aligned_text(TextAlign::Start, TextDirection::Ltr, cx);
+        assert_eq!(
+            word_starts(&view, cx),
+            ["0.0 38.4 67.2", "0.0 48.0 67.2", "0.0"]
+        );
+    }
+
+    #[gpui::test]
+    fn test_centered_lines(cx: &mut TestAppContext) {
+        let (view, cx) = add_aligned_text(TextAlign::Center, TextDirection::Ltr, cx);
+        assert_eq!(
+            word_starts(&view, cx),
+            ["6.8 45.2 74.0", "11.6 59.6 78.8", "21.2"]
+        );
+    }
+
+    #[gpui::test]
+    fn test_end_aligned_lines(cx: &mut TestAppContext) {
+        let (view, cx) = add_aligned_text(TextAlign::End, TextDirection::Ltr, cx);
+        // The space each line ends with hangs past the end, rather than pushing the words back.
+        assert_eq!(
+            word_starts(&view, cx),
+            ["13.6 52.0 80.8", "23.2 71.2 90.4", "42.4"]
+        );
+    }
+
+    #[gpui::test]
+    fn test_justified_lines(cx: &mut TestAppContext) {
+        let (view, cx) = add_aligned_text(TextAlign::Justify, TextDirection::Ltr, cx);
+        // The last line isn't stretched.
+        assert_eq!(
+            word_starts(&view, cx),
+            ["0.0 45.2 81.6", "0.0 59.6 90.4", "0.0"]
+        );
+    }
+
+    #[gpui::test]
+    fn test_right_to_left_lines_start_at_the_right(cx: &mut TestAppContext) {
+        let (view, cx) = add_aligned_text(TextAlign::Start, TextDirection::Rtl, cx);
+        assert_eq!(
+            word_starts(&view, cx),
+            ["13.6 52.0 80.8", "23.2 71.2 90.4", "42.4"]
+        );
+
+        view.update(cx, |view, cx| {
+            view.align = TextAlign::Justify;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            word_starts(&view, cx),
+            ["0.0 45.2 81.6", "0.0 59.6 90.4", "42.4"]
+        );
+    }
+
+    #[gpui::test]
+    fn test_clicks_hit_the_character_painted_under_them(cx: &mut TestAppContext) {
+        let (_, cx) = add_aligned_text(TextAlign::Center, TextDirection::Ltr, cx);
+
+        // Where "f" is painted on the centered second line, which is past the end of the words
+        // on that line when it isn't centered.
+        let position = point(px(83.), px(30.));
+        cx.simulate_event(MouseDownEvent {
+            position,
+            modifiers: Modifiers::none(),
+            button: MouseButton::Left,
+            click_count: 2,
+            first_mouse: false,
+        });
+        cx.simulate_event(MouseUpEvent {
+            position,
+            modifiers: Modifiers::none(),
+            button: MouseButton::Left,
+            click_count: 2,
+        });
+        cx.simulate_keystrokes(COPY);
+        assert_eq!(cx.read_from_clipboard().unwrap().text(), "f");
+    }
+
+    struct ColumnTextView {
+        alignment: WrapWidthAlignment,
+        direction: TextDirection,
+        layout: Option<TextLayout>,
+    }
+
+    impl Render for ColumnTextView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let text = StyledText::new(PARAGRAPH)
+                .wrap_width(px(100.))
+                .wrap_width_alignment(self.alignment);
+            self.layout = Some(text.layout().clone());
+            provide_context(
+                self.direction,
+                div()
+                    .w(px(200.))
+                    .font_family("Zed Plex Mono")
+                    .text_size(px(16.))
+                    .line_height(px(20.))
+                    .child(InteractiveText::new("text", text).selectable(hsla(0.6, 0.8, 0.5, 0.3))),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_text_is_placed_within_the_leftover_width(cx: &mut TestAppContext) {
+        add_plex_mono(cx);
+        let (view, cx) = cx.add_window_view(|_| ColumnTextView {
+            alignment: WrapWidthAlignment::Start,
+            direction: TextDirection::Ltr,
+            layout: None,
+        });
+        cx.run_until_parked();
+
+        let column = |alignment, direction, cx: &mut VisualTestContext| {
+            view.update(cx, |view, cx| {
+                view.alignment = alignment;
+                view.direction = direction;
+                cx.notify();
+            });
+            cx.run_until_parked();
+            let layout = view.update(cx, |view, _| view.layout.clone().unwrap());
+            // The text still wraps at the wrap width, into three lines.
+            assert_eq!(layout.line_count(), 3);
+            let bounds = layout.bounds();
+            (f32::from(bounds.left()), f32::from(bounds.size.width))
+        };
+        let (ltr, rtl) = (TextDirection::Ltr, TextDirection::Rtl);
+        assert_eq!(column(WrapWidthAlignment::Start, ltr, cx), (0., 100.));
+        assert_eq!(column(WrapWidthAlignment::Center, ltr, cx), (50., 100.));
+        assert_eq!(column(WrapWidthAlignment::End, ltr, cx), (100., 100.));
+        assert_eq!(column(WrapWidthAlignment::Start, rtl, cx), (100., 100.));
+        assert_eq!(column(WrapWidthAlignment::End, rtl, cx), (0., 100.));
+
+        // Clicks hit the characters where they're painted within the centered column.
+        column(WrapWidthAlignment::Center, ltr, cx);
+        let position = point(px(50. + 7. * 9.6 + 1.), px(30.));
+        cx.simulate_event(MouseDownEvent {
+            position,
+            modifiers: Modifiers::none(),
+            button: MouseButton::Left,
+            click_count: 2,
+            first_mouse: false,
+        });
+        cx.simulate_event(MouseUpEvent {
+            position,
+            modifiers: Modifiers::none(),
+            button: MouseButton::Left,
+            click_count: 2,
+        });
+        assert_eq!(copy(cx), "f");
+    }
+
+    /// Where the character at the given column of the given line of [`PARAGRAPH`] is painted,
+    /// when it's start-aligned.
+    fn character_position(column: usize, line: usize) -> Point<Pixels> {
+        point(px(column as f32 * 9.6 + 1.), px(line as f32 * 20. + 10.))
+    }
+
+    fn drag(from: Point<Pixels>, to: Point<Pixels>, cx: &mut VisualTestContext) {
+        cx.simulate_mouse_down(from, MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_move(to, MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_up(to, MouseButton::Left, Modifiers::none());
+    }
+
+    fn copy(cx: &mut VisualTestContext) -> String {
+        cx.simulate_keystrokes(COPY);
+        cx.read_from_clipboard().unwrap().text().clone()
+    }
+
+    #[gpui::test]
+    fn test_dragging_selects_text(cx: &mut TestAppContext) {
+        let (_, cx) = add_aligned_text(TextAlign::Start, TextDirection::Ltr, cx);
+
+        // From "bb" on the first line into "dddd" on the second.
+        drag(character_position(4, 0), character_position(2, 1), cx);
+        assert_eq!(copy(cx), "bb cc dd");
+
+        // Dragging back past where it started selects the text before it instead.
+        cx.simulate_mouse_down(
+            character_position(4, 0),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_move(
+            character_position(2, 1),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_move(
+            character_position(1, 0),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_up(
+            character_position(1, 0),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert_eq!(copy(cx), "aa ");
+
+        // Moving the mouse after it's released doesn't change the selection.
+        cx.simulate_mouse_move(character_position(8, 0), None, Modifiers::none());
+        assert_eq!(copy(cx), "aa ");
+    }
+
+    #[gpui::test]
+    fn test_shift_click_extends_the_selection(cx: &mut TestAppContext) {
+        let (_, cx) = add_aligned_text(TextAlign::Start, TextDirection::Ltr, cx);
+
+        cx.simulate_click(character_position(0, 0), Modifiers::none());
+        cx.simulate_click(character_position(7, 0), Modifiers::shift());
+        assert_eq!(copy(cx), "aaa bb ");
+
+        // The selection is extended from where it started, not from where it was last extended.
+        cx.simulate_click(character_position(3, 1), Modifiers::shift());
+        assert_eq!(copy(cx), "aaa bb cc ddd");
+        cx.simulate_click(character_position(2, 0), Modifiers::shift());
+        assert_eq!(copy(cx), "aa");
+
+        // A click without shift starts over.
+        cx.simulate_click(character_position(4, 1), Modifiers::none());
+        cx.simulate_click(character_position(8, 1), Modifiers::shift());
+        assert_eq!(copy(cx), " e f");
+    }
+
+    #[gpui::test]
+    fn test_selection_is_highlighted_across_wrapped_lines(cx: &mut TestAppContext) {
+        let (_, cx) = add_aligned_text(TextAlign::Start, TextDirection::Ltr, cx);
+
+        // From "bb" on the first line to "gggggg" on the third.
+        drag(character_position(4, 0), character_position(2, 2), cx);
+        let (mut highlights, scale_factor) = cx.update(|cx: &mut WindowContext| {
+            (
+                cx.window
+                    .rendered_frame
+                    .scene
+                    .quads
+                    .iter()
+                    .filter(|quad| quad.background == hsla(0.6, 0.8, 0.5, 0.3))
+                    .map(|quad| quad.bounds)
+                    .collect::<Vec<_>>(),
+                cx.scale_factor(),
+            )
+        });
+        highlights.sort_by(|a, b| a.origin.y.partial_cmp(&b.origin.y).unwrap());
+        let assert_at = |actual: ScaledPixels, pixels: f32| {
+            let expected = pixels * scale_factor;
+            assert!(
+                (actual.0 - expected).abs() < 0.01,
+                "expected {expected}, got {actual:?}"
+            );
+        };
+
+        // The first line is highlighted from the selection's start to the end of the line, the
+        // whole middle line is, and the last line from its start to the selection's end.
+        assert_eq!(highlights.len(), 3);
+        let (first, middle, last) = (highlights[0], highlights[1], highlights[2]);
+        assert_at(first.origin.x, 4. * 9.6);
+        assert_at(first.origin.y, 0.);
+        assert_at(middle.origin.x, 0.);
+        assert_at(middle.origin.y, 20.);
+        assert_eq!(middle.right(), first.right());
+        assert_at(last.origin.x, 0.);
+        assert_at(last.origin.y, 40.);
+        assert_at(last.size.width, 2. * 9.6);
+        for highlight in [first, middle, last] {
+            assert_at(highlight.size.height, 20.);
+        }
+    }
+
+    struct ClampedTextView {
+        width: Pixels,
+        layout: Option<TextLayout>,
+        clamped: Rc<Cell<Option<bool>>>,
+    }
+
+    impl Render for ClampedTextView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let clamped = self.clamped.clone();
+            let text = StyledText::new(PARAGRAPH)
+                .line_clamp(2)
+                .on_clamp(move |is_clamped, _| clamped.set(Some(is_clamped)));
+            self.layout = Some(text.layout().clone());
+            div()
+                .w(self.width)
+                .font_family("Zed Plex Mono")
+                .text_size(px(16.))
+                .line_height(px(20.))
+                .child(text)
+        }
+    }
+
+    #[gpui::test]
+    fn test_line_clamp_follows_the_width(cx: &mut TestAppContext) {
+        add_plex_mono(cx);
+        let clamped = Rc::new(Cell::new(None));
+        let (view, cx) = cx.add_window_view({
+            let clamped = clamped.clone();
+            |_| ClampedTextView {
+                width: px(100.),
+                layout: None,
+                clamped,
+            }
+        });
+        cx.run_until_parked();
+
+        // The paragraph wraps into three lines, of which two are shown.
+        let layout = view.update(cx, |view, _| view.layout.clone().unwrap());
+        assert!(layout.is_clamped());
+        assert_eq!(layout.line_count(), 3);
+        assert_eq!(layout.bounds().size.height, px(40.));
+        assert_eq!(clamped.get(), Some(true));
+
+        view.update(cx, |view, cx| {
+            view.width = px(300.);
+            cx.notify();
+        });
+        cx.run_until_parked();
+        let layout = view.update(cx, |view, _| view.layout.clone().unwrap());
+        assert!(!layout.is_clamped());
+        assert_eq!(layout.bounds().size.height, px(20.));
+        assert_eq!(clamped.get(), Some(false));
+    }
+}
@@ -0,0 +1,676 @@
+//! A multi-line plain text field, for things like commit messages and feedback forms.
+//!
+//! The text lives in a [`TextAreaState`] model owned by the caller, and is edited through the
+//! [`EditableText`] core shared with other text fields. Lines wrap to the width of the element,
+//! which scrolls vertically once its text is taller than it is, keeping the cursor in view.
+
+use super::text::word_range;
+use crate::{
+    fill, point, px, size, Bounds, ClipboardItem, Context, DispatchPhase, EditableText, Element,
+    ElementId, FocusHandle, GlobalElementId, Hitbox, Hsla, InputHandler, InteractiveElement,
+    Interactivity, IntoElement, KeyDownEvent, LayoutId, Model, ModelContext, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, SharedString, StyleRefinement, Styled,
+    StyledText, TextLayout, TextRun, UnderlineStyle, WindowContext,
+};
+use std::ops::Range;
+use taffy::style::Overflow;
+
+const CURSOR_WIDTH: Pixels = px(2.);
+
+/// The text and selection of a [`text_area`], which can be read and changed by its owner.
+pub struct TextAreaState {
+    focus_handle: FocusHandle,
+    text: EditableText,
+    /// The horizontal position, relative to the text, that moving the cursor up and down tries to
+    /// stay at. It's set by the first vertical move and cleared by any other edit or movement.
+    goal_x: Option<Pixels>,
+    scroll_to_cursor: bool,
+    is_selecting: bool,
+}
+
+impl TextAreaState {
+    /// Create the state for an empty text area.
+    pub fn new(cx: &mut WindowContext) -> Model<Self> {
+        let focus_handle = cx.focus_handle();
+        cx.new_model(|_| Self {
+            focus_handle,
+            text: EditableText::default(),
+            goal_x: None,
+            scroll_to_cursor: false,
+            is_selecting: false,
+        })
+    }
+
+    /// The handle that focuses the text area.
+    pub fn focus_handle(&self) -> &FocusHandle {
+        &self.focus_handle
+    }
+
+    /// The text in the text area.
+    pub fn text(&self) -> &SharedString {
+        self.text.content()
+    }
+
+    /// Replace the text in the text area, moving the cursor to its end.
+    pub fn set_text(&mut self, text: impl Into<SharedString>, cx: &mut ModelContext<Self>) {
+        self.text.set_content(text);
+        self.edited(cx);
+    }
+
+    /// The selected byte range, which is empty when there's only a cursor.
+    pub fn selected_range(&self) -> Range<usize> {
+        self.text.selected_range()
+    }
+
+    /// Select the given byte range, with the cursor at its end.
+    pub fn select(&mut self, range: Range<usize>, cx: &mut ModelContext<Self>) {
+        self.text.move_to(range.start);
+        self.text.select_to(range.end);
+        self.edited(cx);
+    }
+
+    fn edited(&mut self, cx: &mut ModelContext<Self>) {
+        self.goal_x = None;
+        self.scroll_to_cursor = true;
+        cx.notify();
+    }
+}
+
+/// Construct a multi-line text area editing the given state. The text area grows with its text
+/// unless it's given a height, in which case it scrolls.
+#[track_caller]
+pub fn text_area(state: &Model<TextAreaState>) -> TextArea {
+    let mut base_style = StyleRefinement::default();
+    base_style.overflow.y = Some(Overflow::Scroll);
+
+    TextArea {
+        state: state.clone(),
+        selection_color: None,
+        interactivity: Interactivity {
+            element_id: Some(ElementId::NamedInteger(
+                "text-area".into(),
+                state.entity_id().as_u64() as usize,
+            )),
+            base_style: Box::new(base_style),
+
+            #[cfg(debug_assertions)]
+            location: Some(*core::panic::Location::caller()),
+
+            ..Default::default()
+        },
+    }
+}
+
+/// A multi-line text field, see [`text_area`].
+pub struct TextArea {
+    state: Model<TextAreaState>,
+    selection_color: Option<Hsla>,
+    interactivity: Interactivity,
+}
+
+impl TextArea {
+    /// Set the color painted behind selected text. Defaults to a translucent version of the
+    /// text color.
+    pub fn selection_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.selection_color = Some(color.into());
+        self
+    }
+}
+
+/// Frame state used by the [`TextArea`].
+pub struct TextAreaFrameState {
+    text: Option<StyledText>,
+    text_layout_id: Option<LayoutId>,
+}
+
+impl Styled for TextArea {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.interactivity.base_style
+    }
+}
+
+impl InteractiveElement for TextArea {
+    fn interactivity(&mut self) -> &mut Interactivity {
+        &mut self.interactivity
+    }
+}
+
+impl Element for TextArea {
+    type RequestLayoutState = TextAreaFrameState;
+    type PrepaintState = Option<Hitbox>;
+
+    fn id(&self) -> Option<ElementId> {
+        self.interactivity.element_id.clone()
+    }
+
+    fn request_layout(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        self.interactivity.tracked_focus_handle = Some(self.state.read(cx).focus_handle.clone());
+
+        let mut text = None;
+        let mut text_layout_id = None;
+        let state = self.state.clone();
+        let layout_id = self
+            .interactivity
+            .request_layout(global_id, cx, |style, cx| {
+                cx.with_text_style(style.text_style().cloned(), |cx| {
+                    let mut styled_text = StyledText::new(state.read(cx).text().clone());
+                    if let Some(runs) = marked_text_runs(state.read(cx), cx) {
+                        styled_text = styled_text.with_runs(runs);
+                    }
+                    let layout_id = styled_text.request_layout(None, cx).0;
+                    text = Some(styled_text);
+                    text_layout_id = Some(layout_id);
+                    cx.request_layout(style, Some(layout_id))
+                })
+            });
+
+        if let Some(text) = text.as_ref() {
+            let text_layout = text.layout().clone();
+            let state = self.state.clone();
+            self.interactivity.on_key_down(move |event, cx| {
+                if handle_key_down(&state, &text_layout, event, cx) {
+                    cx.stop_propagation();
+                    cx.refresh();
+                }
+            });
+        }
+
+        (
+            layout_id,
+            TextAreaFrameState {
+                text,
+                text_layout_id,
+            },
+        )
+    }
+
+    fn prepaint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        let (Some(text), Some(text_layout_id)) =
+            (request_layout.text.as_mut(), request_layout.text_layout_id)
+        else {
+            return None;
+        };
+        let unscrolled_text_bounds = cx.layout_bounds(text_layout_id);
+
+        // Scroll just far enough to show the cursor after it moved. The text has to be placed
+        // without scrolling first to find out where the cursor is.
+        let scroll_to_cursor = self
+            .state
+            .update(cx, |state, _| std::mem::take(&mut state.scroll_to_cursor));
+        if let (true, Some(scroll_offset)) =
+            (scroll_to_cursor, self.interactivity.scroll_offset.as_ref())
+        {
+            text.prepaint(None, unscrolled_text_bounds, &mut (), cx);
+            let text_layout = text.layout();
+            let cursor = self.state.read(cx).text.cursor_offset();
+            if let Some(cursor_position) = text_layout.position_for_index(cursor) {
+                let line_height = text_layout.line_height();
+                let mut scroll_offset = scroll_offset.borrow_mut();
+                if cursor_position.y + scroll_offset.y < bounds.top() {
+                    scroll_offset.y = bounds.top() - cursor_position.y;
+                } else if cursor_position.y + line_height + scroll_offset.y > bounds.bottom() {
+                    scroll_offset.y = bounds.bottom() - cursor_position.y - line_height;
+                }
+            }
+        }
+
+        self.interactivity.prepaint(
+            global_id,
+            bounds,
+            unscrolled_text_bounds.size,
+            cx,
+            |_style, scroll_offset, hitbox, cx| {
+                cx.with_element_offset(scroll_offset, |cx| {
+                    let text_bounds = cx.layout_bounds(text_layout_id);
+                    text.prepaint(None, text_bounds, &mut (), cx);
+                });
+                hitbox
+            },
+        )
+    }
+
+    fn paint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        hitbox: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        let Some(text) = request_layout.text.as_mut() else {
+            return;
+        };
+        let state = self.state.clone();
+        let selection_color = self.selection_color;
+        self.interactivity
+            .paint(global_id, bounds, hitbox.as_ref(), cx, |_style, cx| {
+                let text_layout = text.layout().clone();
+                let text_color = cx.text_style().color;
+                let focus_handle = state.read(cx).focus_handle.clone();
+                let is_focused = focus_handle.is_focused(cx);
+
+                cx.handle_input(
+                    &focus_handle,
+                    TextAreaInputHandler {
+                        state: state.clone(),
+                        text_layout: text_layout.clone(),
+                    },
+                );
+
+                let selected_range = state.read(cx).text.selected_range();
+                if selected_range.is_empty() {
+                    text.paint(None, bounds, &mut (), &mut (), cx);
+                    if is_focused {
+                        let cursor = state.read(cx).text.cursor_offset();
+                        if let Some(position) = text_layout.position_for_index(cursor) {
+                            cx.paint_quad(fill(
+                                Bounds::new(
+                                    position,
+                                    size(CURSOR_WIDTH, text_layout.line_height()),
+                                ),
+                                text_color,
+                            ));
+                        }
+                    }
+                } else {
+                    let selection_color =
+                        selection_color.unwrap_or_else(|| text_color.opacity(0.25));
+                    for bounds in text_layout.bounds_for_range(selected_range) {
+                        cx.paint_quad(fill(bounds, selection_color));
+                    }
+                    text.paint(None, bounds, &mut (), &mut (), cx);
+                }
+
+                if let Some(hitbox) = hitbox.clone() {
+                    paint_mouse_listeners(&state, &text_layout, hitbox, cx);
+                }
+            });
+    }
+}
+
+impl IntoElement for TextArea {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+/// Underline the text that's being composed by an input method, if any.
+fn marked_text_runs(state: &TextAreaState, cx: &WindowContext) -> Option<Vec<TextRun>> {
+    let marked_range = state.text.marked_range()?;
+    let len = state.text().len();
+    let run = cx.text_style().to_run(len);
+    let runs = [
+        TextRun {
+            len: marked_range.start,
+            ..run.clone()
+        },
+        TextRun {
+            len: marked_range.len(),
+            underline: Some(UnderlineStyle {
+                color: Some(run.color),
+                thickness: px(1.),
+                wavy: false,
+            }),
+            ..run.clone()
+        },
+        TextRun {
+            len: len - marked_range.end,
+            ..run
+        },
+    ];
+    Some(runs.into_iter().filter(|run| run.len > 0).collect())
+}
+
+/// Apply the editing or movement for a key press, returning whether it was handled. Plain
+/// characters aren't handled here, they arrive through the [`TextAreaInputHandler`].
+fn handle_key_down(
+    state: &Model<TextAreaState>,
+    text_layout: &TextLayout,
+    event: &KeyDownEvent,
+    cx: &mut WindowContext,
+) -> bool {
+    let keystroke = &event.keystroke;
+    let select = keystroke.modifiers.shift;
+
+    if keystroke.modifiers.secondary() {
+        return match keystroke.key.as_str() {
+            "a" => {
+                state.update(cx, |state, cx| {
+                    state.text.select_all();
+                    state.edited(cx);
+                });
+                true
+            }
+            "c" | "x" => {
+                let selected_text = state.read(cx).text.selected_text().to_string();
+                if !selected_text.is_empty() {
+                    cx.write_to_clipboard(ClipboardItem::new(selected_text));
+                    if keystroke.key == "x" {
+                        state.update(cx, |state, cx| {
+                            state.text.insert("");
+                            state.edited(cx);
+                        });
+                    }
+                }
+                true
+            }
+            "v" => {
+                if let Some(item) = cx.read_from_clipboard() {
+                    state.update(cx, |state, cx| {
+                        state.text.insert(item.text());
+                        state.edited(cx);
+                    });
+                }
+                true
+            }
+            _ => false,
+        };
+    }
+
+    if keystroke.modifiers.control || keystroke.modifiers.alt {
+        return false;
+    }
+
+    match keystroke.key.as_str() {
+        "up" | "down" => {
+            let direction = if keystroke.key == "up" { -1. } else { 1. };
+            state.update(cx, |state, cx| {
+                let cursor = state.text.cursor_offset();
+                let Some(position) = text_layout.position_for_index(cursor) else {
+                    return;
+                };
+                let text_bounds = text_layout.bounds();
+                let line_height = text_layout.line_height();
+                let goal_x = *state.goal_x.get_or_insert(position.x - text_bounds.left());
+                let target = point(
+                    text_bounds.left() + goal_x,
+                    position.y + line_height / 2. + line_height * direction,
+                );
+                let ix = text_layout
+                    .index_for_position(target)
+                    .unwrap_or_else(|ix| ix);
+                if select {
+                    state.text.select_to(ix);
+                } else {
+                    state.text.move_to(ix);
+                }
+                state.scroll_to_cursor = true;
+                cx.notify();
+            });
+        }
+        "left" | "right" | "home" | "end" | "backspace" | "delete" | "enter" => {
+            state.update(cx, |state, cx| {
+                let text = &mut state.text;
+                match (keystroke.key.as_str(), select) {
+                    ("left", false) => text.move_left(),
+                    ("left", true) => text.select_left(),
+                    ("right", false) => text.move_right(),
+                    ("right", true) => text.select_right(),
+                    ("home", false) => text.move_to(text.line_start(text.cursor_offset())),
+                    ("home", true) => text.select_to(text.line_start(text.cursor_offset())),
+                    ("end", false) => text.move_to(text.line_end(text.cursor_offset())),
+                    ("end", true) => text.select_to(text.line_end(text.cursor_offset())),
+                    ("backspace", _) => text.backspace(),
+                    ("delete", _) => text.delete(),
+                    _ => text.insert("\n"),
+                }
+                state.edited(cx);
+            });
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Place the cursor where the text area is clicked, and extend the selection while dragging.
+/// Shift-clicking extends the selection, and double-clicking selects a word.
+fn paint_mouse_listeners(
+    state: &Model<TextAreaState>,
+    text_layout: &TextLayout,
+    hitbox: Hitbox,
+    cx: &mut WindowContext,
+) {
+    cx.on_mouse_event({
+        let state = state.clone();
+        let text_layout = text_layout.clone();
+        move |event: &MouseDownEvent, phase, cx| {
+            if phase != DispatchPhase::Bubble
+                || event.button != MouseButton::Left
+                || !hitbox.is_hovered(cx)
+            {
+                return;
+            }
+
+            let ix = text_layout
+                .index_for_position(event.position)
+                .unwrap_or_else(|ix| ix);
+            state.update(cx, |state, cx| {
+                if event.modifiers.shift {
+                    state.text.select_to(ix);
+                } else if event.click_count == 2 {
+                    let range = word_range(state.text(), ix);
+                    state.text.move_to(range.start);
+                    state.text.select_to(range.end);
+                } else {
+                    state.text.move_to(ix);
+                }
+                state.is_selecting = event.click_count < 2;
+                state.edited(cx);
+            });
+            cx.refresh();
+        }
+    });
+
+    cx.on_mouse_event({
+        let state = state.clone();
+        let text_layout = text_layout.clone();
+        move |event: &MouseMoveEvent, phase, cx| {
+            if phase != DispatchPhase::Bubble || !state.read(cx).is_selecting {
+                return;
+            }
+
+            state.update(cx, |state, cx| {
+                if event.pressed_button != Some(MouseButton::Left) {
+                    state.is_selecting = false;
+                    return;
+                }
+                let ix = text_layout
+                    .index_for_position(event.position)
+                    .unwrap_or_else(|ix| ix);
+                if state.text.cursor_offset() != ix {
+                    state.text.select_to(ix);
+                    state.edited(cx);
+                }
+            });
+            cx.refresh();
+        }
+    });
+
+    cx.on_mouse_event({
+        let state = state.clone();
+        move |_: &MouseUpEvent, phase, cx| {
+            if phase == DispatchPhase::Capture && state.read(cx).is_selecting {
+                state.update(cx, |state, _| state.is_selecting = false);
+            }
+        }
+    });
+}
+
+/// Receives text typed into a focused [`TextArea`], including text composed by an input method.
+struct TextAreaInputHandler {
+    state: Model<TextAreaState>,
+    text_layout: TextLayout,
+}
+
+impl InputHandler for TextAreaInputHandler {
+    fn selected_text_range(&mut self, cx: &mut WindowContext) -> Option<Range<usize>> {
+        Some(self.state.read(cx).text.selected_range_utf16())
+    }
+
+    fn marked_text_range(&mut self, cx: &mut WindowContext) -> Option<Range<usize>> {
+        self.state.read(cx).text.marked_range_utf16()
+    }
+
+    fn text_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        cx: &mut WindowContext,
+    ) -> Option<String> {
+        Some(self.state.read(cx).text.text_for_range(range_utf16))
+    }
+
+    fn replace_text_in_range(
+        &mut self,
+        replacement_range: Option<Range<usize>>,
+        text: &str,
+        cx: &mut WindowContext,
+    ) {
+        self.state.update(cx, |state, cx| {
+            state.text.replace_text_in_range(replacement_range, text);
+            state.edited(cx);
+        });
+        cx.refresh();
+    }
+
+    fn replace_and_mark_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        new_text: &str,
+        new_selected_range: Option<Range<usize>>,
+        cx: &mut WindowContext,
+    ) {
+        self.state.update(cx, |state, cx| {
+            state
+                .text
+                .replace_and_mark_text_in_range(range_utf16, new_text, new_selected_range);
+            state.edited(cx);
+        });
+        cx.refresh();
+    }
+
+    fn unmark_text(&mut self, cx: &mut WindowContext) {
+        self.state.update(cx, |state, _| state.text.unmark_text());
+    }
+
+    fn bounds_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        cx: &mut WindowContext,
+    ) -> Option<Bounds<Pixels>> {
+        let range = self.state.read(cx).text.range_from_utf16(&range_utf16);
+        self.text_layout.bounds_for_range(range).first().copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, div, point, px, text_area, Model, Modifiers, MouseButton, ParentElement,
+        Render, Styled, TestAppContext, TextAreaState, ViewContext, VisualTestContext,
+        WindowContext,
+    };
+
+    struct TextAreaView {
+        state: Model<TextAreaState>,
+    }
+
+    impl Render for TextAreaView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl gpui::IntoElement {
+            div()
+                .w(px(200.))
+                .text_size(px(16.))
+                .line_height(px(20.))
+                .child(text_area(&self.state).h(px(60.)))
+        }
+    }
+
+    fn build_text_area(cx: &mut TestAppContext) -> (Model<TextAreaState>, &mut VisualTestContext) {
+        let (view, cx) = cx.add_window_view(|cx| TextAreaView {
+            state: TextAreaState::new(cx),
+        });
+        let state = view.update(cx, |view, _| view.state.clone());
+        cx.update(|cx: &mut WindowContext| {
+            let focus_handle = state.read(cx).focus_handle().clone();
+            cx.focus(&focus_handle);
+        });
+        cx.run_until_parked();
+        (state, cx)
+    }
+
+    #[gpui::test]
+    fn test_typing_newlines_and_vertical_movement(cx: &mut TestAppContext) {
+        let (state, cx) = build_text_area(cx);
+
+        cx.simulate_input("hello");
+        cx.simulate_keystrokes("enter");
+        cx.simulate_input("he");
+        cx.simulate_keystrokes("enter");
+        cx.simulate_input("hello");
+        cx.read(|cx| assert_eq!(state.read(cx).text().as_ref(), "hello\nhe\nhello"));
+
+        // Moving up through a shorter line stops at its end, but the next line up gets the
+        // original column back.
+        cx.simulate_keystrokes("left up");
+        cx.read(|cx| assert_eq!(state.read(cx).selected_range(), 8..8));
+        cx.simulate_keystrokes("up");
+        cx.read(|cx| assert_eq!(state.read(cx).selected_range(), 4..4));
+        cx.simulate_keystrokes("down down");
+        cx.read(|cx| assert_eq!(state.read(cx).selected_range(), 13..13));
+
+        cx.simulate_keystrokes("shift-up");
+        cx.read(|cx| assert_eq!(state.read(cx).selected_range(), 8..13));
+        cx.simulate_keystrokes("backspace");
+        cx.read(|cx| assert_eq!(state.read(cx).text().as_ref(), "hello\nheo"));
+    }
+
+    #[gpui::test]
+    fn test_click_positions_cursor_on_clicked_line(cx: &mut TestAppContext) {
+        let (state, cx) = build_text_area(cx);
+        cx.update(|cx| {
+            state.update(cx, |state, cx| state.set_text("one\ntwo\nthree", cx));
+        });
+        cx.run_until_parked();
+
+        cx.simulate_click(point(px(0.), px(30.)), Modifiers::none());
+        cx.read(|cx| assert_eq!(state.read(cx).selected_range(), 4..4));
+
+        cx.simulate_mouse_down(point(px(0.), px(10.)), MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_move(
+            point(px(150.), px(50.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_up(
+            point(px(150.), px(50.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.read(|cx| assert_eq!(state.read(cx).selected_range(), 0..13));
+    }
+
+    #[gpui::test]
+    fn test_scrolls_to_keep_cursor_visible(cx: &mut TestAppContext) {
+        let (state, cx) = build_text_area(cx);
+        cx.update(|cx| {
+            state.update(cx, |state, cx| state.set_text("1\n2\n3\n4\n5\n6", cx));
+        });
+        cx.run_until_parked();
+
+        // The cursor is on the sixth line, so clicking the top of the text area lands on the
+        // fourth line once it has scrolled to show the cursor at the bottom.
+        cx.simulate_click(point(px(0.), px(1.)), Modifiers::none());
+        cx.read(|cx| assert_eq!(state.read(cx).selected_range(), 6..6));
+    }
+}
@@ -0,0 +1,501 @@
+//! Notifications that float above a window's content and dismiss themselves.
+//!
+//! Toasts are pushed with [`WindowContext::push_toast`] from anywhere that has access to the
+//! window, such as an event handler, and are drawn by a single [`toast_host`] placed at the root
+//! of the window. The host stacks them in a corner with the newest on top, and each toast goes
+//! away after its duration, or when its dismiss button is clicked. The countdown only starts once
+//! a toast is visible, and pauses while the pointer is over the stack so that toasts don't vanish
+//! while someone is reading them or reaching for their buttons.
+
+use crate::{
+    blue, deferred, div, green, hsla, px, red, white, yellow, AnchorCorner, AnyElement, Bounds,
+    Element, ElementId, GlobalElementId, Hsla, InteractiveElement, IntoElement, LayoutId,
+    ParentElement, Pixels, SharedString, StatefulInteractiveElement, Styled, Task, WindowContext,
+};
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// How long a toast is shown for unless it specifies otherwise.
+pub const DEFAULT_TOAST_DURATION: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_VISIBLE_TOASTS: usize = 3;
+/// Above the menus and popovers that are deferred with priority 1.
+const DEFAULT_TOAST_PRIORITY: usize = 2;
+
+/// How important a [`Toast`] is, which determines its accent color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ToastSeverity {
+    /// Something happened that's worth knowing about.
+    #[default]
+    Info,
+    /// Something the user asked for has completed.
+    Success,
+    /// Something didn't go as expected, but nothing was lost.
+    Warning,
+    /// Something failed.
+    Error,
+}
+
+impl ToastSeverity {
+    fn color(self) -> Hsla {
+        match self {
+            ToastSeverity::Info => blue(),
+            ToastSeverity::Success => green(),
+            ToastSeverity::Warning => yellow(),
+            ToastSeverity::Error => red(),
+        }
+    }
+}
+
+/// Identifies a toast pushed with [`WindowContext::push_toast`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ToastId(usize);
+
+/// A short notification shown by the window's [`toast_host`].
+#[derive(Clone)]
+pub struct Toast {
+    /// The message to show.
+    pub content: SharedString,
+    /// How important the message is.
+    pub severity: ToastSeverity,
+    /// How long the toast is shown before it dismisses itself, or `None` to keep it until it's
+    /// dismissed.
+    pub duration: Option<Duration>,
+    /// A button shown next to the message, which dismisses the toast when clicked.
+    pub action: Option<ToastAction>,
+}
+
+impl Default for Toast {
+    fn default() -> Self {
+        Self {
+            content: SharedString::default(),
+            severity: ToastSeverity::default(),
+            duration: Some(DEFAULT_TOAST_DURATION),
+            action: None,
+        }
+    }
+}
+
+impl Toast {
+    /// Create a toast showing the given message for [`DEFAULT_TOAST_DURATION`].
+    pub fn new(content: impl Into<SharedString>) -> Self {
+        Self {
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set how important the message is.
+    pub fn severity(mut self, severity: ToastSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Set how long the toast is shown for, or `None` to keep it until it's dismissed.
+    pub fn duration(mut self, duration: Option<Duration>) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Add a button with the given label that runs `on_click` and dismisses the toast.
+    pub fn action(
+        mut self,
+        label: impl Into<SharedString>,
+        on_click: impl Fn(&mut WindowContext) + 'static,
+    ) -> Self {
+        self.action = Some(ToastAction {
+            label: label.into(),
+            on_click: Rc::new(on_click),
+        });
+        self
+    }
+}
+
+/// A button on a [`Toast`], see [`Toast::action`].
+#[derive(Clone)]
+pub struct ToastAction {
+    /// The label of the button.
+    pub label: SharedString,
+    on_click: Rc<dyn Fn(&mut WindowContext)>,
+}
+
+/// The toasts of a window, oldest first.
+#[derive(Default)]
+pub(crate) struct ToastQueue {
+    toasts: Vec<QueuedToast>,
+    next_id: usize,
+    paused_at: Option<Instant>,
+    wake_up: Option<(Instant, Task<()>)>,
+}
+
+struct QueuedToast {
+    id: ToastId,
+    toast: Toast,
+    /// When the toast dismisses itself, set once it has been shown.
+    expires_at: Option<Instant>,
+}
+
+impl ToastQueue {
+    pub(crate) fn push(&mut self, toast: Toast) -> ToastId {
+        let id = ToastId(self.next_id);
+        self.next_id += 1;
+        self.toasts.push(QueuedToast {
+            id,
+            toast,
+            expires_at: None,
+        });
+        id
+    }
+
+    pub(crate) fn dismiss(&mut self, id: ToastId) -> bool {
+        let len = self.toasts.len();
+        self.toasts.retain(|toast| toast.id != id);
+        self.toasts.len() != len
+    }
+
+    fn set_paused(&mut self, paused: bool, now: Instant) {
+        if paused {
+            self.paused_at.get_or_insert(now);
+        } else if let Some(paused_at) = self.paused_at.take() {
+            let pause = now.saturating_duration_since(paused_at);
+            for toast in &mut self.toasts {
+                if let Some(expires_at) = toast.expires_at.as_mut() {
+                    *expires_at += pause;
+                }
+            }
+        }
+    }
+
+    /// Drop the toasts that have expired and start the countdown of the ones that became visible,
+    /// returning when the next one expires.
+    fn tick(&mut self, max_visible: usize, now: Instant) -> Option<Instant> {
+        if self.toasts.is_empty() {
+            // The stack is gone, so the pointer can't be over it anymore.
+            self.paused_at = None;
+            return None;
+        }
+        if self.paused_at.is_some() {
+            return None;
+        }
+
+        // Toasts that expire together go away together, even if some were hidden behind the
+        // others, so the visible ones are found after dropping the expired ones.
+        self.toasts
+            .retain(|toast| toast.expires_at.map_or(true, |expires_at| expires_at > now));
+        let visible_start = self.toasts.len().saturating_sub(max_visible);
+        for toast in &mut self.toasts[visible_start..] {
+            if toast.expires_at.is_none() {
+                toast.expires_at = toast.toast.duration.map(|duration| now + duration);
+            }
+        }
+        self.toasts
+            .iter()
+            .filter_map(|toast| toast.expires_at)
+            .min()
+    }
+}
+
+/// Construct the element that shows the window's toasts, which should be placed once, as a child
+/// of the window's root element. It stacks the toasts in the bottom right corner by default.
+pub fn toast_host() -> ToastHost {
+    ToastHost {
+        corner: AnchorCorner::BottomRight,
+        max_visible: DEFAULT_MAX_VISIBLE_TOASTS,
+        priority: DEFAULT_TOAST_PRIORITY,
+        element: None,
+    }
+}
+
+/// Shows the toasts pushed with [`WindowContext::push_toast`], see [`toast_host`].
+pub struct ToastHost {
+    corner: AnchorCorner,
+    max_visible: usize,
+    priority: usize,
+    element: Option<AnyElement>,
+}
+
+impl ToastHost {
+    /// Set the corner of the window the toasts are stacked in.
+    pub fn corner(mut self, corner: AnchorCorner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    /// Set how many toasts are shown at once. Older toasts are collapsed into an indicator
+    /// counting them, and their countdown waits until they're shown.
+    pub fn max_visible(mut self, max_visible: usize) -> Self {
+        self.max_visible = max_visible.max(1);
+        self
+    }
+
+    /// Set the priority the toasts are drawn with, relative to other deferred elements such as
+    /// menus and popovers. Higher priorities are drawn on top.
+    pub fn priority(mut self, priority: usize) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Wake up when the next toast expires so that it gets dismissed even if nothing else causes
+    /// the window to be redrawn.
+    fn schedule_wake_up(deadline: Option<Instant>, now: Instant, cx: &mut WindowContext) {
+        let Some(deadline) = deadline else {
+            cx.window.toasts.wake_up = None;
+            return;
+        };
+        if cx
+            .window
+            .toasts
+            .wake_up
+            .as_ref()
+            .map_or(false, |(scheduled, _)| *scheduled == deadline)
+        {
+            return;
+        }
+
+        let timer = cx
+            .background_executor()
+            .timer(deadline.saturating_duration_since(now));
+        let task = cx.spawn(|mut cx| async move {
+            timer.await;
+            cx.update(|cx| cx.refresh()).ok();
+        });
+        cx.window.toasts.wake_up = Some((deadline, task));
+    }
+
+    fn render_stack(&self, cx: &mut WindowContext) -> AnyElement {
+        let queue = &cx.window.toasts;
+        let visible_start = queue.toasts.len().saturating_sub(self.max_visible);
+        let hidden_count = visible_start;
+        let toasts = queue.toasts[visible_start..]
+            .iter()
+            .rev()
+            .map(|toast| render_toast(toast.id, &toast.toast))
+            .collect::<Vec<_>>();
+
+        let mut stack = div()
+            .id("toasts")
+            .absolute()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .p_4()
+            .on_hover(|hovered, cx| {
+                let now = cx.background_executor().now();
+                cx.window.toasts.set_paused(*hovered, now);
+                cx.refresh();
+            })
+            .children(toasts);
+        stack = match self.corner {
+            AnchorCorner::TopLeft => stack.top_0().left_0().items_start(),
+            AnchorCorner::TopRight => stack.top_0().right_0().items_end(),
+            AnchorCorner::BottomLeft => stack.bottom_0().left_0().items_start(),
+            AnchorCorner::BottomRight => stack.bottom_0().right_0().items_end(),
+        };
+        if hidden_count > 0 {
+            stack = stack.child(
+                div()
+                    .px_3()
+                    .py_1()
+                    .rounded_md()
+                    .bg(hsla(0., 0., 0.15, 0.9))
+                    .text_color(white())
+                    .child(format!("+{hidden_count} more")),
+            );
+        }
+
+        deferred(stack)
+            .with_priority(self.priority)
+            .into_any_element()
+    }
+}
+
+fn render_toast(id: ToastId, toast: &Toast) -> impl IntoElement {
+    let mut card = div()
+        .id(ElementId::NamedInteger("toast".into(), id.0))
+        .occlude()
+        .flex()
+        .items_center()
+        .gap_2()
+        .min_w(px(240.))
+        .max_w(px(400.))
+        .px_3()
+        .py_2()
+        .rounded_md()
+        .shadow_md()
+        .border_l_4()
+        .border_color(toast.severity.color())
+        .bg(hsla(0., 0., 0.15, 1.))
+        .text_color(white())
+        .child(div().flex_1().child(toast.content.clone()));
+
+    if let Some(action) = toast.action.clone() {
+        card = card.child(
+            div()
+                .id("action")
+                .cursor_pointer()
+                .px_2()
+                .rounded_sm()
+                .bg(hsla(0., 0., 1., 0.1))
+                .hover(|style| style.bg(hsla(0., 0., 1., 0.2)))
+                .child(action.label.clone())
+                .on_click(move |_, cx| {
+                    (action.on_click)(cx);
+                    cx.dismiss_toast(id);
+                }),
+        );
+    }
+
+    card.child(
+        div()
+            .id("dismiss")
+            .cursor_pointer()
+            .px_1()
+            .rounded_sm()
+            .hover(|style| style.bg(hsla(0., 0., 1., 0.1)))
+            .child("×")
+            .on_click(move |_, cx| cx.dismiss_toast(id)),
+    )
+}
+
+impl Element for ToastHost {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let now = cx.background_executor().now();
+        let next_expiry = cx.window.toasts.tick(self.max_visible, now);
+        Self::schedule_wake_up(next_expiry, now, cx);
+
+        let mut element = self.render_stack(cx);
+        let layout_id = element.request_layout(cx);
+        self.element = Some(element);
+        (layout_id, ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) {
+        if let Some(element) = self.element.as_mut() {
+            element.prepaint(cx);
+        }
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        if let Some(element) = self.element.as_mut() {
+            element.paint(cx);
+        }
+    }
+}
+
+impl IntoElement for ToastHost {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, div, point, px, toast_host, AnchorCorner, IntoElement, Modifiers,
+        ParentElement, Render, Styled, TestAppContext, Toast, ViewContext, VisualTestContext,
+    };
+    use std::time::Duration;
+
+    struct ToastView;
+
+    impl Render for ToastView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .size_full()
+                .child(toast_host().corner(AnchorCorner::TopLeft).max_visible(3))
+        }
+    }
+
+    fn toast_count(cx: &mut VisualTestContext) -> usize {
+        cx.update(|cx| cx.window.toasts.toasts.len())
+    }
+
+    fn advance_clock(cx: &mut VisualTestContext, duration: Duration) {
+        cx.executor().advance_clock(duration);
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn test_toast_dismisses_itself(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| ToastView);
+        cx.update(|cx| {
+            cx.push_toast(Toast::new("Saved").duration(Some(Duration::from_secs(2))));
+            cx.push_toast(Toast::new("Stays").duration(None));
+        });
+        cx.run_until_parked();
+        assert_eq!(toast_count(cx), 2);
+
+        advance_clock(cx, Duration::from_secs(1));
+        assert_eq!(toast_count(cx), 2);
+        advance_clock(cx, Duration::from_millis(1500));
+        assert_eq!(toast_count(cx), 1);
+        advance_clock(cx, Duration::from_secs(60));
+        assert_eq!(toast_count(cx), 1);
+    }
+
+    #[gpui::test]
+    fn test_hovering_pauses_countdown(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| ToastView);
+        cx.update(|cx| {
+            cx.push_toast(Toast::new("Saved").duration(Some(Duration::from_secs(2))));
+        });
+        cx.run_until_parked();
+
+        cx.simulate_mouse_move(point(px(20.), px(20.)), None, Modifiers::none());
+        advance_clock(cx, Duration::from_secs(5));
+        assert_eq!(toast_count(cx), 1);
+
+        cx.simulate_mouse_move(point(px(600.), px(400.)), None, Modifiers::none());
+        advance_clock(cx, Duration::from_secs(1));
+        assert_eq!(toast_count(cx), 1);
+        advance_clock(cx, Duration::from_millis(1500));
+        assert_eq!(toast_count(cx), 0);
+    }
+
+    #[gpui::test]
+    fn test_hidden_toasts_wait_until_shown(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| ToastView);
+        cx.update(|cx| {
+            for ix in 0..4 {
+                cx.push_toast(
+                    Toast::new(format!("Toast {ix}")).duration(Some(Duration::from_secs(1))),
+                );
+            }
+        });
+        cx.run_until_parked();
+
+        // The three newest toasts expire first, and the oldest one, which was collapsed into the
+        // overflow indicator, only starts counting down once it's shown.
+        advance_clock(cx, Duration::from_millis(1500));
+        assert_eq!(toast_count(cx), 1);
+        advance_clock(cx, Duration::from_secs(1));
+        assert_eq!(toast_count(cx), 0);
+    }
+}
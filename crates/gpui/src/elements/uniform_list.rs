@@ -7,8 +7,8 @@
 use crate::{
     point, px, size, AnyElement, AvailableSpace, Bounds, ContentMask, Element, ElementId,
     GlobalElementId, Hitbox, InteractiveElement, Interactivity, IntoElement, LayoutId,
-    ListSizingBehavior, Pixels, Render, ScrollHandle, Size, StyleRefinement, Styled, View,
-    ViewContext, WindowContext,
+    ListSizingBehavior, Pixels, Render, RovingFocusHandle, ScrollHandle, Size, StyleRefinement,
+    Styled, View, ViewContext, WindowContext,
 };
 use smallvec::SmallVec;
 use std::{cell::RefCell, cmp, ops::Range, rc::Rc};
@@ -56,6 +56,7 @@ where
             ..Default::default()
         },
         scroll_handle: None,
+        active_handle: None,
         sizing_behavior: ListSizingBehavior::default(),
     }
 }
@@ -68,6 +69,7 @@ pub struct UniformList {
         Box<dyn for<'a> Fn(Range<usize>, &'a mut WindowContext) -> SmallVec<[AnyElement; 64]>>,
     interactivity: Interactivity,
     scroll_handle: Option<UniformListScrollHandle>,
+    active_handle: Option<RovingFocusHandle>,
     sizing_behavior: ListSizingBehavior,
 }
 
@@ -105,6 +107,15 @@ impl UniformListScrollHandle {
         self.0.borrow_mut().deferred_scroll_to_item = Some(ix);
     }
 
+    /// Keep the list scrolled to its end as items are added while it's scrolled to the end,
+    /// see [`ScrollHandle::set_stick_to_bottom`].
+    pub fn set_stick_to_bottom(&self, stick_to_bottom: bool) {
+        self.0
+            .borrow()
+            .base_handle
+            .set_stick_to_bottom(stick_to_bottom);
+    }
+
     /// Get the index of the topmost visible child.
     pub fn logical_scroll_top_index(&self) -> usize {
         let this = self.0.borrow();
@@ -134,6 +145,10 @@ impl Element for UniformList {
     ) -> (LayoutId, Self::RequestLayoutState) {
         let max_items = self.item_count;
         let item_size = self.measure_item(None, cx);
+        if let Some(active_handle) = self.active_handle.clone() {
+            self.interactivity
+                .on_key_down(move |event, cx| active_handle.handle_key_down(event, cx));
+        }
         let layout_id = self
             .interactivity
             .request_layout(global_id, cx, |style, cx| match self.sizing_behavior {
@@ -201,11 +216,18 @@ impl Element for UniformList {
         let shared_scroll_offset = self.interactivity.scroll_offset.clone().unwrap();
 
         let item_height = self.measure_item(Some(padded_bounds.size.width), cx).height;
-        let shared_scroll_to_item = self.scroll_handle.as_mut().and_then(|handle| {
-            let mut handle = handle.0.borrow_mut();
-            handle.last_item_height = Some(item_height);
-            handle.deferred_scroll_to_item.take()
+        let active_to_scroll_to = self.active_handle.as_ref().and_then(|handle| {
+            handle.layout_uniform_items(self.item_count, item_height, padded_bounds)
         });
+        let shared_scroll_to_item = self
+            .scroll_handle
+            .as_mut()
+            .and_then(|handle| {
+                let mut handle = handle.0.borrow_mut();
+                handle.last_item_height = Some(item_height);
+                handle.deferred_scroll_to_item.take()
+            })
+            .or(active_to_scroll_to);
 
         self.interactivity.prepaint(
             global_id,
@@ -335,6 +357,15 @@ impl UniformList {
         item_to_measure.layout_as_root(available_space, cx)
     }
 
+    /// Track the active item of this list with the given handle, like a
+    /// [`RovingFocus`](crate::RovingFocus) container but without laying out every item. The active
+    /// item is scrolled into view whenever it changes, and while the list is focused, the arrow
+    /// keys, home, end, page up and page down move it.
+    pub fn track_active(mut self, handle: &RovingFocusHandle) -> Self {
+        self.active_handle = Some(handle.clone());
+        self
+    }
+
     /// Track and render scroll state of this list with reference to the given scroll handle.
     pub fn track_scroll(mut self, handle: UniformListScrollHandle) -> Self {
         self.interactivity.tracked_scroll_handle = Some(handle.0.borrow().base_handle.clone());
@@ -348,3 +379,76 @@ impl InteractiveElement for UniformList {
         &mut self.interactivity
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        self as gpui, div, point, prelude::*, px, uniform_list, Modifiers, ScrollDelta,
+        ScrollWheelEvent, TestAppContext, UniformListScrollHandle, View, ViewContext,
+        VisualTestContext,
+    };
+
+    /// A 100px tall log of 20px tall entries.
+    struct Log {
+        scroll_handle: UniformListScrollHandle,
+        entry_count: usize,
+    }
+
+    impl Render for Log {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            uniform_list(cx.view().clone(), "log", self.entry_count, |_, range, _| {
+                range
+                    .map(|ix| {
+                        div()
+                            .h(px(20.))
+                            .debug_selector(move || format!("entry-{ix}"))
+                    })
+                    .collect()
+            })
+            .w(px(100.))
+            .h(px(100.))
+            .track_scroll(self.scroll_handle.clone())
+        }
+    }
+
+    fn append_entries(view: &View<Log>, count: usize, cx: &mut VisualTestContext) {
+        view.update(cx, |view, cx| {
+            view.entry_count += count;
+            cx.notify();
+        });
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn test_stick_to_bottom_until_scrolled_up(cx: &mut TestAppContext) {
+        let scroll_handle = UniformListScrollHandle::new();
+        scroll_handle.set_stick_to_bottom(true);
+        let (view, cx) = cx.add_window_view(|_| Log {
+            scroll_handle: scroll_handle.clone(),
+            entry_count: 10,
+        });
+        cx.run_until_parked();
+        let base_handle = scroll_handle.0.borrow().base_handle.clone();
+        assert_eq!(base_handle.offset().y, px(-100.));
+
+        // Appended entries are scrolled into view.
+        append_entries(&view, 2, cx);
+        assert_eq!(base_handle.offset().y, px(-140.));
+        assert_eq!(cx.debug_bounds("entry-11").unwrap().origin.y, px(80.));
+        assert!(base_handle.is_stuck_to_bottom());
+
+        // Once the user scrolls up, appended entries don't move the ones in view.
+        cx.simulate_mouse_move(point(px(50.), px(50.)), None, Modifiers::none());
+        cx.simulate_event(ScrollWheelEvent {
+            position: point(px(50.), px(50.)),
+            delta: ScrollDelta::Pixels(point(px(0.), px(40.))),
+            ..Default::default()
+        });
+        cx.run_until_parked();
+        assert_eq!(base_handle.offset().y, px(-100.));
+        append_entries(&view, 2, cx);
+        assert_eq!(base_handle.offset().y, px(-100.));
+        assert_eq!(cx.debug_bounds("entry-5").unwrap().origin.y, px(0.));
+        assert!(!base_handle.is_stuck_to_bottom());
+    }
+}
@@ -2731,7 +2731,7 @@ impl Default for DefiniteLength {
 }
 
 /// A length that can be defined in pixels, rems, percent of parent, or auto.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum Length {
     /// A definite length specified either in pixels, rems, or as a fraction of the parent's size.
     Definite(DefiniteLength),
@@ -2760,12 +2760,12 @@ impl Debug for Length {
 /// # Returns
 ///
 /// A `DefiniteLength` representing the relative length as a fraction of the parent's size.
-pub fn relative(fraction: f32) -> DefiniteLength {
+pub const fn relative(fraction: f32) -> DefiniteLength {
     DefiniteLength::Fraction(fraction)
 }
 
 /// Returns the Golden Ratio, i.e. `~(1.0 + sqrt(5.0)) / 2.0`.
-pub fn phi() -> DefiniteLength {
+pub const fn phi() -> DefiniteLength {
     relative(1.618_034)
 }
 
@@ -2778,7 +2778,7 @@ pub fn phi() -> DefiniteLength {
 /// # Returns
 ///
 /// A `Rems` representing the specified number of rems.
-pub fn rems(rems: f32) -> Rems {
+pub const fn rems(rems: f32) -> Rems {
     Rems(rems)
 }
 
@@ -2806,7 +2806,7 @@ pub const fn px(pixels: f32) -> Pixels {
 /// # Returns
 ///
 /// A `Length` variant set to `Auto`.
-pub fn auto() -> Length {
+pub const fn auto() -> Length {
     Length::Auto
 }
 
@@ -3074,4 +3074,40 @@ mod tests {
         // Test Case 3: Bounds intersecting with themselves
         assert_eq!(bounds1.intersects(&bounds1), true);
     }
+
+    #[test]
+    fn test_length_conversions() {
+        const HAIRLINE: Length =
+            Length::Definite(DefiniteLength::Absolute(AbsoluteLength::Pixels(px(1.))));
+        assert_eq!(Length::from(px(1.)), HAIRLINE);
+        assert_eq!(
+            Length::from(rems(1.5)),
+            Length::Definite(DefiniteLength::Absolute(AbsoluteLength::Rems(Rems(1.5))))
+        );
+        assert_eq!(
+            Length::from(relative(0.5)),
+            Length::Definite(DefiniteLength::Fraction(0.5))
+        );
+        assert_eq!(auto(), Length::Auto);
+        assert_eq!(
+            DefiniteLength::from(AbsoluteLength::from(px(2.))),
+            DefiniteLength::Absolute(AbsoluteLength::Pixels(px(2.)))
+        );
+    }
+
+    #[test]
+    fn test_rems_resolve_against_the_rem_size() {
+        assert_eq!(rems(1.5).to_pixels(px(16.)), px(24.));
+        assert_eq!(rems(1.5).to_pixels(px(20.)), px(30.));
+        assert_eq!(AbsoluteLength::from(rems(2.)).to_pixels(px(20.)), px(40.));
+        assert_eq!(AbsoluteLength::from(px(3.)).to_pixels(px(20.)), px(3.));
+
+        let parent_width = AbsoluteLength::from(rems(10.));
+        assert_eq!(relative(0.25).to_pixels(parent_width, px(16.)), px(40.));
+        assert_eq!(relative(0.25).to_pixels(parent_width, px(20.)), px(50.));
+        assert_eq!(
+            DefiniteLength::from(px(7.)).to_pixels(parent_width, px(20.)),
+            px(7.)
+        );
+    }
 }
@@ -78,6 +78,7 @@ mod elements;
 mod executor;
 mod geometry;
 mod global;
+mod image_cache;
 mod input;
 mod interactive;
 mod key_dispatch;
@@ -94,6 +95,7 @@ mod svg_renderer;
 mod taffy;
 #[cfg(any(test, feature = "test-support"))]
 pub mod test;
+mod text_editing;
 mod text_system;
 mod util;
 mod view;
@@ -128,6 +130,7 @@ pub use executor::*;
 pub use geometry::*;
 pub use global::*;
 pub use gpui_macros::{register_action, test, IntoElement, Render};
+pub use image_cache::*;
 pub use input::*;
 pub use interactive::*;
 use key_dispatch::*;
@@ -146,6 +149,7 @@ use svg_renderer::*;
 pub use taffy::{AvailableSpace, LayoutId};
 #[cfg(any(test, feature = "test-support"))]
 pub use test::*;
+pub use text_editing::*;
 pub use text_system::*;
 pub use util::arc_cow::ArcCow;
 pub use view::*;
@@ -0,0 +1,504 @@
+use std::{
+    mem,
+    rc::{Rc, Weak},
+    sync::Arc,
+};
+
+use collections::FxHashMap;
+use futures::{future::BoxFuture, FutureExt};
+use smallvec::SmallVec;
+
+use crate::{
+    AnyWindowHandle, AppContext, EntityId, ImageCacheError, ImageData, ImageSource, Task,
+    UriOrPath, WindowContext,
+};
+
+/// The number of bytes of decoded images kept in the [`ImageCache`] by default.
+pub const DEFAULT_IMAGE_CACHE_BUDGET: usize = 256 * 1024 * 1024;
+
+/// Loads an image in place of reading it from its file or fetching it from its URI, see
+/// `AppContext::set_image_loader`.
+pub type ImageLoader =
+    Arc<dyn Fn(&ImageSource) -> BoxFuture<'static, Result<Arc<ImageData>, ImageCacheError>>>;
+
+/// How the [`ImageCache`] was used since the stats were last taken.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ImageCacheStats {
+    /// The number of times a decoded image was found in the cache.
+    pub hits: usize,
+    /// The number of times an image wasn't decoded yet, whether or not it was already loading.
+    pub misses: usize,
+    /// The number of images that started loading.
+    pub decodes: usize,
+    /// The number of decoded images dropped to stay within the budget.
+    pub evictions: usize,
+    /// The number of bytes of decoded images in the cache when the stats were taken.
+    pub cached_bytes: usize,
+}
+
+/// The decoded images shared by every window, keyed by the file or URI they were loaded from.
+///
+/// Images are read and decoded on the background executor, and the least recently used ones are
+/// dropped once the decoded images take up more than the budget.
+pub struct ImageCache {
+    budget: usize,
+    loaded: FxHashMap<UriOrPath, CachedImage>,
+    loading: FxHashMap<UriOrPath, PendingImage>,
+    loader: Option<ImageLoader>,
+    cached_bytes: usize,
+    next_use: u64,
+    next_load_id: usize,
+    stats: ImageCacheStats,
+}
+
+struct CachedImage {
+    image: Result<Arc<ImageData>, ImageCacheError>,
+    bytes: usize,
+    last_use: u64,
+    /// The window whose scale factor the image was rasterized at, for SVGs.
+    rasterized_for: Option<AnyWindowHandle>,
+}
+
+struct PendingImage {
+    id: usize,
+    load: Weak<ImageLoad>,
+    /// The window that started loading the image, at whose scale factor it's rasterized if it
+    /// turns out to be an SVG.
+    window: AnyWindowHandle,
+    /// The windows to redraw when the image is done, along with the views that asked for it.
+    waiting: SmallVec<[(AnyWindowHandle, Option<EntityId>); 2]>,
+}
+
+/// An image read and decoded for the [`ImageCache`].
+pub(crate) struct DecodedImage {
+    pub(crate) data: Arc<ImageData>,
+    /// Whether the image was rasterized at the scale factor of the window that loaded it, as
+    /// SVGs are.
+    pub(crate) scale_dependent: bool,
+}
+
+/// An image being loaded, which stays alive for as long as a window's frame asked for the image.
+/// Dropping it cancels the load.
+pub(crate) struct ImageLoad {
+    _task: Task<()>,
+}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self {
+            budget: DEFAULT_IMAGE_CACHE_BUDGET,
+            loaded: FxHashMap::default(),
+            loading: FxHashMap::default(),
+            loader: None,
+            cached_bytes: 0,
+            next_use: 0,
+            next_load_id: 0,
+            stats: ImageCacheStats::default(),
+        }
+    }
+}
+
+impl ImageCache {
+    /// The number of bytes of decoded images kept before the least recently used are dropped.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Set the number of bytes of decoded images to keep, dropping the least recently used
+    /// images if there are more.
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.evict(None);
+    }
+
+    /// The number of bytes of decoded images in the cache.
+    pub fn cached_bytes(&self) -> usize {
+        self.cached_bytes
+    }
+
+    /// Drop every decoded image, and forget the ones being loaded so that they're loaded again
+    /// when they're next asked for.
+    pub fn clear(&mut self) {
+        self.loaded.clear();
+        self.loading.clear();
+        self.cached_bytes = 0;
+    }
+
+    /// Drop the images rasterized at the scale factor of the given window, for when it changes.
+    /// Images decoded independently of the scale factor are kept.
+    pub(crate) fn clear_rasterized_for(&mut self, window: AnyWindowHandle) {
+        let cached_bytes = &mut self.cached_bytes;
+        self.loaded.retain(|_, cached| {
+            if cached.rasterized_for == Some(window) {
+                *cached_bytes -= cached.bytes;
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    pub(crate) fn take_stats(&mut self) -> ImageCacheStats {
+        ImageCacheStats {
+            cached_bytes: self.cached_bytes,
+            ..mem::take(&mut self.stats)
+        }
+    }
+
+    fn get(&mut self, source: &UriOrPath) -> Option<Result<Arc<ImageData>, ImageCacheError>> {
+        let Some(cached) = self.loaded.get_mut(source) else {
+            self.stats.misses += 1;
+            return None;
+        };
+        self.stats.hits += 1;
+        cached.last_use = self.next_use;
+        self.next_use += 1;
+        Some(cached.image.clone())
+    }
+
+    fn finish_load(
+        &mut self,
+        source: UriOrPath,
+        load_id: usize,
+        image: Result<DecodedImage, ImageCacheError>,
+    ) -> SmallVec<[(AnyWindowHandle, Option<EntityId>); 2]> {
+        // The cache was cleared while the image was loading.
+        if self.loading.get(&source).map(|pending| pending.id) != Some(load_id) {
+            return SmallVec::new();
+        }
+        let pending = self.loading.remove(&source).unwrap();
+        let (image, rasterized_for) = match image {
+            Ok(decoded) => (
+                Ok(decoded.data),
+                decoded.scale_dependent.then_some(pending.window),
+            ),
+            Err(error) => (Err(error), None),
+        };
+
+        let bytes = image.as_ref().map_or(0, |data| data.as_bytes().len());
+        self.cached_bytes += bytes;
+        self.loaded.insert(
+            source.clone(),
+            CachedImage {
+                image,
+                bytes,
+                last_use: self.next_use,
+                rasterized_for,
+            },
+        );
+        self.next_use += 1;
+        self.evict(Some(&source));
+        pending.waiting
+    }
+
+    /// Drop the least recently used images until the cache is within its budget, keeping the
+    /// given image.
+    fn evict(&mut self, keep: Option<&UriOrPath>) {
+        while self.cached_bytes > self.budget {
+            let Some(source) = self
+                .loaded
+                .iter()
+                .filter(|(source, _)| Some(*source) != keep)
+                .min_by_key(|(_, cached)| cached.last_use)
+                .map(|(source, _)| source.clone())
+            else {
+                break;
+            };
+            let cached = self.loaded.remove(&source).unwrap();
+            self.cached_bytes -= cached.bytes;
+            self.stats.evictions += 1;
+        }
+    }
+}
+
+impl AppContext {
+    /// The cache of the images decoded for the `img` element.
+    pub fn image_cache(&self) -> &ImageCache {
+        &self.image_cache
+    }
+
+    /// The cache of the images decoded for the `img` element.
+    pub fn image_cache_mut(&mut self) -> &mut ImageCache {
+        &mut self.image_cache
+    }
+
+    /// Load the images that aren't cached yet with the given loader, rather than reading their
+    /// files or fetching their URIs.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn set_image_loader(
+        &mut self,
+        loader: impl Fn(&ImageSource) -> BoxFuture<'static, Result<Arc<ImageData>, ImageCacheError>>
+            + 'static,
+    ) {
+        self.image_cache.loader = Some(Arc::new(loader));
+    }
+}
+
+impl<'a> WindowContext<'a> {
+    /// Get the decoded image for the given source from the image cache, or start loading it on
+    /// the background executor and return `None`. The view being drawn is notified once the image
+    /// is done.
+    ///
+    /// The load is only kept going while frames keep asking for the image, so an element that
+    /// stops being drawn before its image is done cancels the load, and nothing is uploaded for
+    /// it.
+    pub(crate) fn load_image(
+        &mut self,
+        source: &UriOrPath,
+    ) -> Option<Result<Arc<ImageData>, ImageCacheError>> {
+        if let Some(image) = self.image_cache.get(source) {
+            return Some(image);
+        }
+
+        let waiter = (self.window.handle, self.parent_view_id());
+        let load = match self.image_cache.loading.get_mut(source) {
+            Some(pending) if pending.load.strong_count() > 0 => {
+                if !pending.waiting.contains(&waiter) {
+                    pending.waiting.push(waiter);
+                }
+                pending.load.upgrade().unwrap()
+            }
+            _ => self.start_image_load(source.clone(), waiter),
+        };
+        self.window.next_frame.image_loads.push(load);
+        None
+    }
+
+    fn start_image_load(
+        &mut self,
+        source: UriOrPath,
+        waiter: (AnyWindowHandle, Option<EntityId>),
+    ) -> Rc<ImageLoad> {
+        let cache = &mut self.image_cache;
+        cache.stats.decodes += 1;
+        cache
+            .loading
+            .retain(|_, pending| pending.load.strong_count() > 0);
+        let load_id = cache.next_load_id;
+        cache.next_load_id += 1;
+
+        let image = match cache.loader.clone() {
+            Some(loader) => loader(&match source.clone() {
+                UriOrPath::Uri(uri) => ImageSource::Uri(uri),
+                UriOrPath::Path(path) => ImageSource::File(path),
+            })
+            .map(|image| {
+                image.map(|data| DecodedImage {
+                    data,
+                    scale_dependent: false,
+                })
+            })
+            .boxed(),
+            None => crate::load_image_data(source.clone(), self).boxed(),
+        };
+        let image = self.background_executor().spawn(image);
+        let task = self.app.spawn({
+            let source = source.clone();
+            |mut cx| async move {
+                let image = image.await;
+                cx.update(|cx| {
+                    let waiting = cx.image_cache.finish_load(source, load_id, image);
+                    for (window, view_id) in waiting {
+                        window
+                            .update(cx, |_, cx| match view_id {
+                                Some(view_id) => cx.notify(view_id),
+                                None => cx.refresh(),
+                            })
+                            .ok();
+                    }
+                })
+                .ok();
+            }
+        });
+
+        let load = Rc::new(ImageLoad { _task: task });
+        self.image_cache.loading.insert(
+            source,
+            PendingImage {
+                id: load_id,
+                load: Rc::downgrade(&load),
+                window: waiter.0,
+                waiting: SmallVec::from_iter([waiter]),
+            },
+        );
+        load
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        path::{Path, PathBuf},
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering::SeqCst},
+            Arc,
+        },
+    };
+
+    use futures::{channel::oneshot, FutureExt};
+    use image::RgbaImage;
+
+    use crate::{
+        self as gpui, div, img, prelude::*, px, ImageData, TestAppContext, UriOrPath, View,
+        ViewContext, VisualTestContext, WindowContext,
+    };
+
+    const IMAGE_BYTES: usize = 4 * 4 * 4;
+
+    struct Images {
+        sources: Vec<&'static str>,
+    }
+
+    impl Render for Images {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .flex()
+                .children(self.sources.iter().map(|source| img(*source).size(px(20.))))
+        }
+    }
+
+    /// Load every image as a blank 4x4 image, counting the loads.
+    fn count_loads(cx: &mut TestAppContext) -> Arc<AtomicUsize> {
+        let loads = Arc::new(AtomicUsize::new(0));
+        cx.update(|cx| {
+            let loads = loads.clone();
+            cx.set_image_loader(move |_| {
+                loads.fetch_add(1, SeqCst);
+                async { Ok(Arc::new(ImageData::new(RgbaImage::new(4, 4)))) }.boxed()
+            });
+        });
+        loads
+    }
+
+    fn show(sources: &[&'static str], view: &View<Images>, cx: &mut VisualTestContext) {
+        view.update(cx, |view, cx| {
+            view.sources = sources.to_vec();
+            cx.notify();
+        });
+        cx.run_until_parked();
+    }
+
+    fn painted_images(cx: &mut VisualTestContext) -> usize {
+        cx.update(|cx: &mut WindowContext| cx.window.rendered_frame.scene.polychrome_sprites.len())
+    }
+
+    #[gpui::test]
+    fn test_elements_sharing_a_source_decode_it_once(cx: &mut TestAppContext) {
+        let loads = count_loads(cx);
+        let (view, cx) = cx.add_window_view(|_| Images { sources: vec![] });
+        cx.update(|cx| cx.frame_diagnostics_mut().set_enabled(true));
+
+        // The placeholders are painted while the image loads, then both elements paint it.
+        show(&["avatar.png", "avatar.png"], &view, cx);
+        assert_eq!(loads.load(SeqCst), 1);
+        assert_eq!(painted_images(cx), 2);
+
+        show(&["avatar.png", "avatar.png"], &view, cx);
+        assert_eq!(loads.load(SeqCst), 1);
+        let stats = cx.update(|cx| cx.frame_diagnostics().last_frame().unwrap().images);
+        assert_eq!(stats.decodes, 0);
+        assert_eq!(stats.misses, 0);
+        assert!(stats.hits >= 2);
+        assert_eq!(stats.cached_bytes, IMAGE_BYTES);
+    }
+
+    #[gpui::test]
+    fn test_least_recently_used_images_are_evicted(cx: &mut TestAppContext) {
+        let loads = count_loads(cx);
+        cx.update(|cx| cx.image_cache_mut().set_budget(IMAGE_BYTES));
+        let (view, cx) = cx.add_window_view(|_| Images { sources: vec![] });
+
+        show(&["first.png"], &view, cx);
+        show(&["second.png"], &view, cx);
+        assert_eq!(loads.load(SeqCst), 2);
+        assert_eq!(cx.update(|cx| cx.image_cache().cached_bytes()), IMAGE_BYTES);
+
+        show(&["second.png"], &view, cx);
+        assert_eq!(loads.load(SeqCst), 2);
+        show(&["first.png"], &view, cx);
+        assert_eq!(loads.load(SeqCst), 3);
+        assert_eq!(painted_images(cx), 1);
+    }
+
+    struct DropGuard(Arc<AtomicBool>);
+
+    impl Drop for DropGuard {
+        fn drop(&mut self) {
+            self.0.store(true, SeqCst);
+        }
+    }
+
+    #[gpui::test]
+    fn test_load_is_cancelled_when_its_elements_stop_being_drawn(cx: &mut TestAppContext) {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let (_finish_load, load_finished) = oneshot::channel::<()>();
+        let load_finished = load_finished.shared();
+        cx.update(|cx| {
+            let dropped = dropped.clone();
+            cx.set_image_loader(move |_| {
+                let guard = DropGuard(dropped.clone());
+                let load_finished = load_finished.clone();
+                async move {
+                    let _guard = guard;
+                    load_finished.await.ok();
+                    Ok(Arc::new(ImageData::new(RgbaImage::new(4, 4))))
+                }
+                .boxed()
+            });
+        });
+        let (view, cx) = cx.add_window_view(|_| Images { sources: vec![] });
+
+        show(&["avatar.png"], &view, cx);
+        assert!(!dropped.load(SeqCst));
+        show(&[], &view, cx);
+        assert!(dropped.load(SeqCst));
+        assert_eq!(cx.update(|cx| cx.image_cache().cached_bytes()), 0);
+    }
+
+    struct Files {
+        paths: Vec<PathBuf>,
+    }
+
+    impl Render for Files {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().flex().children(
+                self.paths
+                    .iter()
+                    .map(|path| img(path.clone()).size(px(20.))),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_scale_factor_changes_drop_the_window_svgs(cx: &mut TestAppContext) {
+        let icons = Path::new("../../assets/icons");
+        let (first_svg, second_svg) = (icons.join("ai.svg"), icons.join("arrow_circle.svg"));
+        let png = std::env::temp_dir().join("gpui-image-cache-scale-factor.png");
+        RgbaImage::new(4, 4).save(&png).unwrap();
+
+        let first = cx.add_window(|_| Files {
+            paths: vec![first_svg.clone(), png.clone()],
+        });
+        cx.add_window(|_| Files {
+            paths: vec![second_svg.clone()],
+        });
+        cx.run_until_parked();
+        let image_id = |path: &PathBuf, cx: &mut TestAppContext| {
+            cx.update(|cx| {
+                let source = UriOrPath::Path(Arc::new(path.clone()));
+                match &cx.image_cache().loaded[&source].image {
+                    Ok(image) => image.id,
+                    Err(error) => panic!("{path:?} wasn't loaded: {error}"),
+                }
+            })
+        };
+        let ids = [&first_svg, &png, &second_svg].map(|path| image_id(path, cx));
+
+        // Only the SVG drawn in the window is rasterized again at its new scale factor.
+        cx.simulate_window_scale_factor(first.into(), 1.);
+        cx.run_until_parked();
+        assert_ne!(image_id(&first_svg, cx), ids[0]);
+        assert_eq!(image_id(&png, cx), ids[1]);
+        assert_eq!(image_id(&second_svg, cx), ids[2]);
+    }
+}
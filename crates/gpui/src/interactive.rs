@@ -1,5 +1,5 @@
 use crate::{
-    point, seal::Sealed, Empty, IntoElement, Keystroke, Modifiers, Pixels, Point, Render,
+    point, seal::Sealed, Bounds, Empty, IntoElement, Keystroke, Modifiers, Pixels, Point, Render,
     ViewContext,
 };
 use smallvec::SmallVec;
@@ -145,6 +145,36 @@ pub struct ClickEvent {
 
     /// The mouse event when the button was released.
     pub up: MouseUpEvent,
+
+    /// Where the button was released, relative to the clicked element.
+    pub pointer: PointerPosition,
+}
+
+/// Where a pointer event landed, as seen by the element whose listener is handling it.
+///
+/// This is computed when the event is dispatched, from the bounds the element was last painted
+/// at. Those bounds already account for the scroll offsets of any scrolled ancestors, so the
+/// local position of a point on the element doesn't change as its container scrolls.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PointerPosition {
+    /// The position of the pointer in window coordinates.
+    pub window_position: Point<Pixels>,
+
+    /// The position of the pointer relative to the origin of the element's bounds.
+    pub local_position: Point<Pixels>,
+
+    /// The bounds of the element in window coordinates.
+    pub bounds: Bounds<Pixels>,
+}
+
+impl PointerPosition {
+    pub(crate) fn new(window_position: Point<Pixels>, bounds: Bounds<Pixels>) -> Self {
+        Self {
+            window_position,
+            local_position: window_position - bounds.origin,
+            bounds,
+        }
+    }
 }
 
 /// An enum representing the mouse button that was pressed.
@@ -250,6 +280,24 @@ impl InputEvent for ScrollWheelEvent {
 }
 impl MouseEvent for ScrollWheelEvent {}
 
+/// A mouse wheel event, as delivered to an element's scroll wheel listeners.
+#[derive(Clone, Debug, Default)]
+pub struct ElementScrollWheelEvent {
+    /// The scroll wheel event from the platform.
+    pub event: ScrollWheelEvent,
+
+    /// Where the mouse was, relative to the element receiving the event.
+    pub pointer: PointerPosition,
+}
+
+impl Deref for ElementScrollWheelEvent {
+    type Target = ScrollWheelEvent;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
 impl Deref for ScrollWheelEvent {
     type Target = Modifiers;
 
@@ -508,4 +556,62 @@ mod test {
             })
             .unwrap();
     }
+
+    actions!(test, [OuterAction, InnerAction, ViewAction]);
+
+    struct NestedContextView {
+        focus_handle: FocusHandle,
+        handled: Vec<&'static str>,
+    }
+
+    impl Render for NestedContextView {
+        fn render(&mut self, cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
+            div()
+                .on_action(cx.listener(|this, _: &ViewAction, _| this.handled.push("view")))
+                .child(
+                    div()
+                        .key_context("outer")
+                        .on_action(
+                            cx.listener(|this, _: &OuterAction, _| this.handled.push("outer")),
+                        )
+                        .child(
+                            div()
+                                .key_context("inner")
+                                .track_focus(&self.focus_handle)
+                                .on_action(cx.listener(|this, _: &InnerAction, _| {
+                                    this.handled.push("inner")
+                                })),
+                        ),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_nested_contexts(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|cx| NestedContextView {
+            focus_handle: cx.focus_handle(),
+            handled: Vec::new(),
+        });
+
+        cx.update(|cx| {
+            cx.bind_keys(vec![
+                KeyBinding::new("ctrl-k", OuterAction, Some("outer")),
+                KeyBinding::new("ctrl-k", InnerAction, Some("inner")),
+                KeyBinding::new("ctrl-o", OuterAction, Some("outer")),
+                KeyBinding::new("ctrl-v", ViewAction, Some("inner")),
+            ]);
+        });
+        view.update(cx, |view, cx| cx.focus(&view.focus_handle));
+
+        // The binding from the innermost context wins.
+        cx.simulate_keystrokes("ctrl-k");
+        // Bindings from ancestor contexts still apply when nothing closer binds the key.
+        cx.simulate_keystrokes("ctrl-o");
+        // Actions that no element on the focus path handles fall through to the view.
+        cx.simulate_keystrokes("ctrl-v");
+
+        view.update(cx, |view, _| {
+            assert_eq!(view.handled, ["inner", "outer", "view"]);
+        });
+    }
 }
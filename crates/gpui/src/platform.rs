@@ -24,8 +24,8 @@ mod windows;
 
 use crate::{
     point, Action, AnyWindowHandle, AsyncWindowContext, BackgroundExecutor, Bounds, DevicePixels,
-    DispatchEventResult, Font, FontId, FontMetrics, FontRun, ForegroundExecutor, GlyphId, Keymap,
-    LineLayout, Pixels, PlatformInput, Point, RenderGlyphParams, RenderImageParams,
+    DispatchEventResult, Edges, Font, FontId, FontMetrics, FontRun, ForegroundExecutor, GlyphId,
+    Keymap, LineLayout, Pixels, PlatformInput, Point, RenderGlyphParams, RenderImageParams,
     RenderSvgParams, Scene, SharedString, Size, Task, TaskLabel, WindowContext,
     DEFAULT_WINDOW_SIZE,
 };
@@ -164,6 +164,8 @@ pub(crate) trait Platform: 'static {
 
     fn set_cursor_style(&self, style: CursorStyle);
     fn should_auto_hide_scrollbars(&self) -> bool;
+    /// Whether the user has asked the system to reduce motion in the interface.
+    fn should_reduce_motion(&self) -> bool;
 
     #[cfg(target_os = "linux")]
     fn write_to_primary(&self, item: ClipboardItem);
@@ -259,6 +261,12 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     fn start_system_move(&self);
     fn should_render_window_controls(&self) -> bool;
 
+    /// The insets from each edge of the content area that are covered by window chrome drawn on
+    /// top of it, such as the macOS traffic lights in a window with a transparent titlebar.
+    fn window_insets(&self) -> Edges<Pixels> {
+        Edges::default()
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     fn as_test(&mut self) -> Option<&mut TestWindow> {
         None
@@ -535,6 +543,24 @@ pub trait InputHandler: 'static {
     ) -> Option<Bounds<Pixels>>;
 }
 
+/// A view of the platform's own, such as a video player or a web view, shown in a window where a
+/// [`native_view`](crate::native_view) element is painted. The platform code that creates the
+/// view implements this to move, clip and hide it.
+pub trait PlatformNativeView: 'static {
+    /// Show the view in the given frame of its window, or hide it if `None`. This is called after
+    /// a frame is painted and before it's presented, whenever the frame of the view has changed.
+    fn set_frame(&self, frame: Option<NativeViewFrame>);
+}
+
+/// Where a [`PlatformNativeView`] is shown in its window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NativeViewFrame {
+    /// The bounds of the view, relative to the window.
+    pub bounds: Bounds<Pixels>,
+    /// The part of the window the view is clipped to, within its bounds.
+    pub clip: Bounds<Pixels>,
+}
+
 /// The variables that can be configured when creating a new window
 #[derive(Debug)]
 pub struct WindowOptions {
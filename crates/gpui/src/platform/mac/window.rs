@@ -1,6 +1,6 @@
 use super::{ns_string, renderer, MacDisplay, NSRange, NSStringExt};
 use crate::{
-    platform::PlatformInputHandler, point, px, size, AnyWindowHandle, Bounds, DisplayLink,
+    platform::PlatformInputHandler, point, px, size, AnyWindowHandle, Bounds, DisplayLink, Edges,
     ExternalPaths, FileDropEvent, ForegroundExecutor, KeyDownEvent, Keystroke, Modifiers,
     ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels,
     PlatformAtlas, PlatformDisplay, PlatformInput, PlatformWindow, Point, PromptLevel, Size, Timer,
@@ -480,6 +480,39 @@ impl MacWindowState {
         }
     }
 
+    fn window_insets(&self) -> Edges<Pixels> {
+        if self.is_fullscreen() {
+            return Edges::default();
+        }
+
+        unsafe {
+            let style_mask = self.native_window.styleMask();
+            if !style_mask.contains(NSWindowStyleMask::NSFullSizeContentViewWindowMask) {
+                return Edges::default();
+            }
+
+            let close_button: id = msg_send![
+                self.native_window,
+                standardWindowButton: NSWindowButton::NSWindowCloseButton
+            ];
+            let zoom_button: id = msg_send![
+                self.native_window,
+                standardWindowButton: NSWindowButton::NSWindowZoomButton
+            ];
+            let close_button_frame: CGRect = msg_send![close_button, frame];
+            let zoom_button_frame: CGRect = msg_send![zoom_button, frame];
+
+            // Leave the same margin after the traffic lights as there is before them.
+            let left = zoom_button_frame.origin.x
+                + zoom_button_frame.size.width
+                + close_button_frame.origin.x;
+            Edges {
+                left: px(left as f32),
+                ..Default::default()
+            }
+        }
+    }
+
     fn window_bounds(&self) -> WindowBounds {
         if self.is_fullscreen() {
             WindowBounds::Fullscreen(self.fullscreen_restore_bounds)
@@ -1095,11 +1128,24 @@ impl PlatformWindow for MacWindow {
 
     fn show_window_menu(&self, _position: Point<Pixels>) {}
 
-    fn start_system_move(&self) {}
+    fn start_system_move(&self) {
+        // The drag runs a nested event loop that dispatches events to this window, so the lock
+        // mustn't be held while it runs.
+        let window = self.0.lock().native_window;
+        unsafe {
+            let app = NSApplication::sharedApplication(nil);
+            let event: id = msg_send![app, currentEvent];
+            let _: () = msg_send![window, performWindowDragWithEvent: event];
+        }
+    }
 
     fn should_render_window_controls(&self) -> bool {
         false
     }
+
+    fn window_insets(&self) -> Edges<Pixels> {
+        self.0.lock().window_insets()
+    }
 }
 
 impl rwh::HasWindowHandle for MacWindow {
@@ -1,9 +1,11 @@
 mod dispatcher;
 mod display;
+mod native_view;
 mod platform;
 mod window;
 
 pub(crate) use dispatcher::*;
 pub(crate) use display::*;
+pub(crate) use native_view::*;
 pub(crate) use platform::*;
 pub(crate) use window::*;
@@ -0,0 +1,31 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{NativeViewFrame, PlatformNativeView};
+
+/// A [`PlatformNativeView`] for tests, which records the frames it's shown in instead of moving a
+/// view of the platform's.
+#[derive(Clone, Default)]
+pub struct TestNativeView(Rc<RefCell<Vec<Option<NativeViewFrame>>>>);
+
+impl TestNativeView {
+    /// Construct a view that hasn't been shown yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The frame the view is shown in, or `None` if it's hidden or hasn't been shown.
+    pub fn frame(&self) -> Option<NativeViewFrame> {
+        self.0.borrow().last().copied().flatten()
+    }
+
+    /// Every frame the view was given, in order, with `None` where it was hidden.
+    pub fn updates(&self) -> Vec<Option<NativeViewFrame>> {
+        self.0.borrow().clone()
+    }
+}
+
+impl PlatformNativeView for TestNativeView {
+    fn set_frame(&self, frame: Option<NativeViewFrame>) {
+        self.0.borrow_mut().push(frame);
+    }
+}
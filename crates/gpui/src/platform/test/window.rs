@@ -1,7 +1,7 @@
 use crate::{
-    AnyWindowHandle, AtlasKey, AtlasTextureId, AtlasTile, Bounds, DispatchEventResult, Pixels,
-    PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow, Point,
-    Size, TestPlatform, TileId, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    AnyWindowHandle, AtlasKey, AtlasTextureId, AtlasTile, Bounds, DispatchEventResult, Edges,
+    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow,
+    Point, Size, TestPlatform, TileId, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
     WindowParams,
 };
 use collections::HashMap;
@@ -14,6 +14,7 @@ use std::{
 
 pub(crate) struct TestWindowState {
     pub(crate) bounds: Bounds<Pixels>,
+    scale_factor: f32,
     pub(crate) handle: AnyWindowHandle,
     display: Rc<dyn PlatformDisplay>,
     pub(crate) title: Option<String>,
@@ -27,6 +28,9 @@ pub(crate) struct TestWindowState {
     moved_callback: Option<Box<dyn FnMut()>>,
     input_handler: Option<PlatformInputHandler>,
     is_fullscreen: bool,
+    is_maximized: bool,
+    window_insets: Edges<Pixels>,
+    pub(crate) system_moves_started: usize,
 }
 
 #[derive(Clone)]
@@ -57,6 +61,7 @@ impl TestWindow {
     ) -> Self {
         Self(Arc::new(Mutex::new(TestWindowState {
             bounds: params.bounds,
+            scale_factor: 2.0,
             display,
             platform,
             handle,
@@ -70,6 +75,9 @@ impl TestWindow {
             moved_callback: None,
             input_handler: None,
             is_fullscreen: false,
+            is_maximized: false,
+            window_insets: Edges::default(),
+            system_moves_started: 0,
         })))
     }
 
@@ -85,6 +93,32 @@ impl TestWindow {
         self.0.lock().resize_callback = Some(callback);
     }
 
+    pub fn simulate_scale_factor(&mut self, scale_factor: f32) {
+        let mut lock = self.0.lock();
+        let Some(mut callback) = lock.resize_callback.take() else {
+            return;
+        };
+        lock.scale_factor = scale_factor;
+        let size = lock.bounds.size;
+        drop(lock);
+        callback(size, scale_factor);
+        self.0.lock().resize_callback = Some(callback);
+    }
+
+    pub fn simulate_window_insets(&mut self, insets: Edges<Pixels>) {
+        let scale_factor = self.scale_factor();
+        let mut lock = self.0.lock();
+        lock.window_insets = insets;
+        let size = lock.bounds.size;
+        let Some(mut callback) = lock.resize_callback.take() else {
+            return;
+        };
+        drop(lock);
+        // Platforms report inset changes alongside a resize of the content area.
+        callback(size, scale_factor);
+        self.0.lock().resize_callback = Some(callback);
+    }
+
     pub(crate) fn simulate_active_status_change(&self, active: bool) {
         let mut lock = self.0.lock();
         let Some(mut callback) = lock.active_status_change_callback.take() else {
@@ -117,7 +151,7 @@ impl PlatformWindow for TestWindow {
     }
 
     fn is_maximized(&self) -> bool {
-        false
+        self.0.lock().is_maximized
     }
 
     fn content_size(&self) -> Size<Pixels> {
@@ -125,7 +159,7 @@ impl PlatformWindow for TestWindow {
     }
 
     fn scale_factor(&self) -> f32 {
-        2.0
+        self.0.lock().scale_factor
     }
 
     fn appearance(&self) -> WindowAppearance {
@@ -205,7 +239,8 @@ impl PlatformWindow for TestWindow {
     }
 
     fn zoom(&self) {
-        unimplemented!()
+        let mut lock = self.0.lock();
+        lock.is_maximized = !lock.is_maximized;
     }
 
     fn toggle_fullscreen(&self) {
@@ -263,12 +298,16 @@ impl PlatformWindow for TestWindow {
     }
 
     fn start_system_move(&self) {
-        unimplemented!()
+        self.0.lock().system_moves_started += 1;
     }
 
     fn should_render_window_controls(&self) -> bool {
         false
     }
+
+    fn window_insets(&self) -> Edges<Pixels> {
+        self.0.lock().window_insets
+    }
 }
 
 pub(crate) struct TestAtlasState {
@@ -12,11 +12,16 @@ pub(crate) type PathVertex_ScaledPixels = PathVertex<ScaledPixels>;
 
 pub(crate) type DrawOrder = u32;
 
+/// How far the z-index is shifted up into a primitive's draw order, above the order that comes
+/// from painting it after the primitives it overlaps.
+const Z_INDEX_SHIFT: u32 = 24;
+
 #[derive(Default)]
 pub(crate) struct Scene {
     pub(crate) paint_operations: Vec<PaintOperation>,
     primitive_bounds: BoundsTree<ScaledPixels>,
     layer_stack: Vec<DrawOrder>,
+    z_index_stack: Vec<u8>,
     pub(crate) shadows: Vec<Shadow>,
     pub(crate) quads: Vec<Quad>,
     pub(crate) paths: Vec<Path<ScaledPixels>>,
@@ -24,6 +29,9 @@ pub(crate) struct Scene {
     pub(crate) monochrome_sprites: Vec<MonochromeSprite>,
     pub(crate) polychrome_sprites: Vec<PolychromeSprite>,
     pub(crate) surfaces: Vec<Surface>,
+    /// Where the window's debugging overlays start in `paint_operations`. They're left out
+    /// when comparing scenes.
+    overlays_start: Option<usize>,
 }
 
 impl Scene {
@@ -31,6 +39,7 @@ impl Scene {
         self.paint_operations.clear();
         self.primitive_bounds.clear();
         self.layer_stack.clear();
+        self.z_index_stack.clear();
         self.paths.clear();
         self.shadows.clear();
         self.quads.clear();
@@ -38,6 +47,7 @@ impl Scene {
         self.monochrome_sprites.clear();
         self.polychrome_sprites.clear();
         self.surfaces.clear();
+        self.overlays_start = None;
     }
 
     pub fn paths(&self) -> &[Path<ScaledPixels>] {
@@ -48,8 +58,18 @@ impl Scene {
         self.paint_operations.len()
     }
 
+    /// Mark the operations painted from now on as debugging overlays.
+    pub(crate) fn begin_overlays(&mut self) {
+        self.overlays_start = Some(self.paint_operations.len());
+    }
+
+    /// The operations painted before the debugging overlays.
+    pub(crate) fn damage_operations(&self) -> &[PaintOperation] {
+        &self.paint_operations[..self.overlays_start.unwrap_or(self.paint_operations.len())]
+    }
+
     pub fn push_layer(&mut self, bounds: Bounds<ScaledPixels>) {
-        let order = self.primitive_bounds.insert(bounds);
+        let order = self.with_z_index(self.primitive_bounds.insert(bounds));
         self.layer_stack.push(order);
         self.paint_operations
             .push(PaintOperation::StartLayer(bounds));
@@ -60,6 +80,28 @@ impl Scene {
         self.paint_operations.push(PaintOperation::EndLayer);
     }
 
+    /// Order the primitives inserted until the matching [`Self::pop_z_index`] above all of those
+    /// with a lower z-index, wherever they are painted.
+    pub fn push_z_index(&mut self, z_index: u8) {
+        self.z_index_stack.push(z_index);
+        self.paint_operations
+            .push(PaintOperation::StartZIndex(z_index));
+    }
+
+    pub fn pop_z_index(&mut self) {
+        self.z_index_stack.pop();
+        self.paint_operations.push(PaintOperation::EndZIndex);
+    }
+
+    fn with_z_index(&self, order: DrawOrder) -> DrawOrder {
+        debug_assert!(
+            order < 1 << Z_INDEX_SHIFT,
+            "too many overlapping primitives"
+        );
+        let z_index = self.z_index_stack.last().copied().unwrap_or(0);
+        (z_index as DrawOrder) << Z_INDEX_SHIFT | order
+    }
+
     pub fn insert_primitive(&mut self, primitive: impl Into<Primitive>) {
         let mut primitive = primitive.into();
         let clipped_bounds = primitive
@@ -74,7 +116,7 @@ impl Scene {
             .layer_stack
             .last()
             .copied()
-            .unwrap_or_else(|| self.primitive_bounds.insert(clipped_bounds));
+            .unwrap_or_else(|| self.with_z_index(self.primitive_bounds.insert(clipped_bounds)));
         match &mut primitive {
             Primitive::Shadow(shadow) => {
                 shadow.order = order;
@@ -116,6 +158,8 @@ impl Scene {
                 PaintOperation::Primitive(primitive) => self.insert_primitive(primitive.clone()),
                 PaintOperation::StartLayer(bounds) => self.push_layer(*bounds),
                 PaintOperation::EndLayer => self.pop_layer(),
+                PaintOperation::StartZIndex(z_index) => self.push_z_index(*z_index),
+                PaintOperation::EndZIndex => self.pop_z_index(),
             }
         }
     }
@@ -169,10 +213,13 @@ pub(crate) enum PrimitiveKind {
     Surface,
 }
 
+#[derive(PartialEq)]
 pub(crate) enum PaintOperation {
     Primitive(Primitive),
     StartLayer(Bounds<ScaledPixels>),
     EndLayer,
+    StartZIndex(u8),
+    EndZIndex,
 }
 
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq)]
@@ -5,14 +5,14 @@ use std::{
 };
 
 use crate::{
-    black, phi, point, quad, rems, AbsoluteLength, Bounds, ContentMask, Corners, CornersRefinement,
-    CursorStyle, DefiniteLength, Edges, EdgesRefinement, Font, FontFeatures, FontStyle, FontWeight,
-    Hsla, Length, Pixels, Point, PointRefinement, Rgba, SharedString, Size, SizeRefinement, Styled,
-    TextRun, WindowContext,
+    black, hsla, phi, point, px, quad, rems, AbsoluteLength, Bounds, ContentMask, Corners,
+    CornersRefinement, CursorStyle, DefiniteLength, Edges, EdgesRefinement, Font, FontFallbacks,
+    FontFeatures, FontStyle, FontWeight, Global, Hsla, Length, Pixels, Point, PointRefinement,
+    Rgba, SharedString, Size, SizeRefinement, Styled, TextRun, WindowContext,
 };
 use collections::HashSet;
 use refineable::Refineable;
-use smallvec::SmallVec;
+use smallvec::{smallvec, SmallVec};
 pub use taffy::style::{
     AlignContent, AlignItems, AlignSelf, Display, FlexDirection, FlexWrap, JustifyContent,
     Overflow, Position,
@@ -113,6 +113,18 @@ pub struct Style {
     /// Box Shadow of the element
     pub box_shadow: SmallVec<[BoxShadow; 2]>,
 
+    /// How high the element sits above the window, which gives it a shadow and a z-index
+    pub elevation: Option<ElevationLevel>,
+
+    /// The z-index of the element, which takes precedence over the one of its elevation
+    pub z_index: Option<u8>,
+
+    /// How opaque this element and its descendants are, from 0 for fully transparent to 1
+    pub opacity: Option<f32>,
+
+    /// A ring drawn around the outside of this element, which doesn't take up any space
+    pub outline: Option<Outline>,
+
     /// The text style of this element
     pub text: TextStyleRefinement,
 
@@ -145,7 +157,7 @@ pub enum Visibility {
 }
 
 /// The possible values of the box-shadow property
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct BoxShadow {
     /// What color should the shadow have?
     pub color: Hsla,
@@ -157,6 +169,118 @@ pub struct BoxShadow {
     pub spread_radius: Pixels,
 }
 
+/// How high an element sits above the window, set with [`Styled::elevation`]. Each level comes with
+/// a shadow and a z-index, so that elements higher up are painted over the shadows of those below
+/// them, and the top levels are drawn in the overlay layer. What each level looks like is up to the
+/// theme, see [`ElevationStyles`].
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ElevationLevel {
+    /// Level with the window, like panels and editors.
+    #[default]
+    Surface,
+    /// Slightly above the surface, like cards and floating panes.
+    Raised,
+    /// Above the rest of the window, like menus and popovers.
+    Overlay,
+    /// Above menus and popovers, like dialogs.
+    Modal,
+    /// Above everything else.
+    Tooltip,
+}
+
+/// What elements at an [`ElevationLevel`] look like and where they're painted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElevationStyle {
+    /// The shadow of elements at this level that don't have a shadow of their own.
+    pub shadow: SmallVec<[BoxShadow; 2]>,
+    /// The z-index of elements at this level, see [`WindowContext::with_z_index`].
+    pub z_index: u8,
+    /// Whether elements at this level are drawn in the overlay layer, where they aren't clipped
+    /// by the elements they're drawn within.
+    pub overlay: bool,
+}
+
+/// The [`ElevationStyle`] of each [`ElevationLevel`]. The theme sets these as a global so they can
+/// be tuned in one place, and the defaults are used without one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElevationStyles {
+    /// The style of [`ElevationLevel::Surface`].
+    pub surface: ElevationStyle,
+    /// The style of [`ElevationLevel::Raised`].
+    pub raised: ElevationStyle,
+    /// The style of [`ElevationLevel::Overlay`].
+    pub overlay: ElevationStyle,
+    /// The style of [`ElevationLevel::Modal`].
+    pub modal: ElevationStyle,
+    /// The style of [`ElevationLevel::Tooltip`].
+    pub tooltip: ElevationStyle,
+}
+
+impl Global for ElevationStyles {}
+
+impl ElevationStyles {
+    /// The style of the given level.
+    pub fn get(&self, level: ElevationLevel) -> &ElevationStyle {
+        match level {
+            ElevationLevel::Surface => &self.surface,
+            ElevationLevel::Raised => &self.raised,
+            ElevationLevel::Overlay => &self.overlay,
+            ElevationLevel::Modal => &self.modal,
+            ElevationLevel::Tooltip => &self.tooltip,
+        }
+    }
+}
+
+impl Default for ElevationStyles {
+    fn default() -> Self {
+        let shadow = |alpha: f32, y: f32, blur: f32| BoxShadow {
+            color: hsla(0., 0., 0., alpha),
+            offset: point(px(0.), px(y)),
+            blur_radius: px(blur),
+            spread_radius: px(0.),
+        };
+        Self {
+            surface: ElevationStyle {
+                shadow: SmallVec::new(),
+                z_index: 0,
+                overlay: false,
+            },
+            raised: ElevationStyle {
+                shadow: smallvec![shadow(0.12, 2., 3.)],
+                z_index: 1,
+                overlay: false,
+            },
+            overlay: ElevationStyle {
+                shadow: smallvec![shadow(0.12, 2., 3.), shadow(0.08, 3., 6.)],
+                z_index: 2,
+                overlay: true,
+            },
+            modal: ElevationStyle {
+                shadow: smallvec![shadow(0.12, 2., 3.), shadow(0.08, 6., 12.)],
+                z_index: 3,
+                overlay: true,
+            },
+            tooltip: ElevationStyle {
+                shadow: smallvec![shadow(0.12, 1., 3.)],
+                z_index: 4,
+                overlay: true,
+            },
+        }
+    }
+}
+
+/// A ring drawn around the outside of an element, following its rounded corners. Unlike a border,
+/// it isn't part of the element's layout, so adding or removing it doesn't move anything.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Outline {
+    /// How thick the ring is.
+    pub width: AbsoluteLength,
+    /// The gap between the element's bounds and the inside of the ring.
+    pub offset: AbsoluteLength,
+    /// The color of the ring.
+    pub color: Hsla,
+}
+
 /// How to handle whitespace in text
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum WhiteSpace {
@@ -167,6 +291,41 @@ pub enum WhiteSpace {
     Nowrap,
 }
 
+/// How the lines of text are placed within the width of the element
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    /// Lines start at the start edge, which is the left in left-to-right text and the right in
+    /// right-to-left text
+    #[default]
+    Start,
+    /// Lines are centered
+    Center,
+    /// Lines end at the end edge, which is the right in left-to-right text and the left in
+    /// right-to-left text
+    End,
+    /// The space left on each wrapped line is spread across the gaps between its words, so that
+    /// it fills the width. The last line, and lines ending in a hard break, are placed at the start
+    Justify,
+}
+
+/// The direction in which text is read. Elements follow the direction provided to them with
+/// [`provide_context`](crate::provide_context), and are left to right if none is
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TextDirection {
+    /// Left to right, as in English
+    #[default]
+    Ltr,
+    /// Right to left, as in Arabic or Hebrew
+    Rtl,
+}
+
+impl TextDirection {
+    /// The direction provided to the element being drawn
+    pub fn current(cx: &WindowContext) -> Self {
+        cx.context::<Self>().copied().unwrap_or_default()
+    }
+}
+
 /// The properties that can be used to style text in GPUI
 #[derive(Refineable, Clone, Debug, PartialEq)]
 #[refineable(Debug)]
@@ -180,6 +339,9 @@ pub struct TextStyle {
     /// The font features to use
     pub font_features: FontFeatures,
 
+    /// The fonts to fall back to for characters the font family doesn't cover
+    pub font_fallbacks: Option<FontFallbacks>,
+
     /// The font size to use, in pixels or rems.
     pub font_size: AbsoluteLength,
 
@@ -203,6 +365,9 @@ pub struct TextStyle {
 
     /// How to handle whitespace in the text
     pub white_space: WhiteSpace,
+
+    /// How to place the lines of the text within the width of the element
+    pub text_align: TextAlign,
 }
 
 impl Default for TextStyle {
@@ -216,6 +381,7 @@ impl Default for TextStyle {
                 "Helvetica".into()
             },
             font_features: FontFeatures::default(),
+            font_fallbacks: None,
             font_size: rems(1.).into(),
             line_height: phi(),
             font_weight: FontWeight::default(),
@@ -224,6 +390,7 @@ impl Default for TextStyle {
             underline: None,
             strikethrough: None,
             white_space: WhiteSpace::Normal,
+            text_align: TextAlign::Start,
         }
     }
 }
@@ -267,6 +434,7 @@ impl TextStyle {
         Font {
             family: self.font_family.clone(),
             features: self.font_features.clone(),
+            fallbacks: self.font_fallbacks.clone(),
             weight: self.font_weight,
             style: self.font_style,
         }
@@ -284,6 +452,7 @@ impl TextStyle {
             font: Font {
                 family: self.font_family.clone(),
                 features: Default::default(),
+                fallbacks: self.font_fallbacks.clone(),
                 weight: self.font_weight,
                 style: self.font_style,
             },
@@ -405,12 +574,75 @@ impl Style {
         }
     }
 
-    /// Paints the background of an element styled with this style.
+    /// Invoke the given function at the z-index of this style and, for the top elevation levels,
+    /// in the overlay layer. [`Style::paint`] does this for the paint phase, elements call it
+    /// themselves around their prepaint so their hitboxes end up at the same z-index.
+    ///
+    /// The z-index of an element is its explicit [`Style::z_index`] if it has one, and the one of
+    /// its [`ElevationLevel`] otherwise, but never lower than that of the element it's drawn
+    /// within. Setting both is usually a mistake, so the first time it happens is warned about in
+    /// debug builds.
+    pub fn with_elevation<R>(
+        &self,
+        cx: &mut WindowContext,
+        f: impl FnOnce(&mut WindowContext) -> R,
+    ) -> R {
+        let elevation = self.elevation.map(|level| cx.elevation_style(level));
+        #[cfg(debug_assertions)]
+        if let (Some(z_index), Some(level)) = (self.z_index, self.elevation) {
+            static WARNED: std::sync::atomic::AtomicBool =
+                std::sync::atomic::AtomicBool::new(false);
+            if !WARNED.swap(true, std::sync::atomic::Ordering::Relaxed) {
+                log::warn!(
+                    "an element has both an elevation of {level:?} and a z-index of {z_index}, \
+                    its z-index decides what it's painted over"
+                );
+            }
+        }
+
+        let z_index = self
+            .z_index
+            .or(elevation.as_ref().map(|elevation| elevation.z_index))
+            .unwrap_or(0);
+        cx.with_z_index(z_index, |cx| {
+            if elevation.map_or(false, |elevation| elevation.overlay) {
+                let viewport = ContentMask {
+                    bounds: Bounds {
+                        origin: Point::default(),
+                        size: cx.viewport_size(),
+                    },
+                };
+                cx.window.content_mask_stack.push(viewport);
+                let result = f(cx);
+                cx.window.content_mask_stack.pop();
+                result
+            } else {
+                f(cx)
+            }
+        })
+    }
+
+    /// Paints the background of an element styled with this style, and invokes the continuation to
+    /// paint its contents at the z-index of the style, see [`Style::with_elevation`].
     pub fn paint(
         &self,
         bounds: Bounds<Pixels>,
         cx: &mut WindowContext,
         continuation: impl FnOnce(&mut WindowContext),
+    ) {
+        self.with_elevation(cx, |cx| {
+            cx.with_element_opacity(self.opacity, |cx| {
+                self.paint_opaque(bounds, cx, continuation)
+            })
+        })
+    }
+
+    /// Paints the background of an element, leaving its opacity to the caller.
+    fn paint_opaque(
+        &self,
+        bounds: Bounds<Pixels>,
+        cx: &mut WindowContext,
+        continuation: impl FnOnce(&mut WindowContext),
     ) {
         #[cfg(debug_assertions)]
         if self.debug_below {
@@ -424,10 +656,14 @@ impl Style {
 
         let rem_size = cx.rem_size();
 
+        let elevation_shadow = self
+            .elevation
+            .filter(|_| self.box_shadow.is_empty())
+            .map(|level| cx.elevation_style(level).shadow);
         cx.paint_shadows(
             bounds,
             self.corner_radii.to_pixels(bounds.size, rem_size),
-            &self.box_shadow,
+            elevation_shadow.as_ref().unwrap_or(&self.box_shadow),
         );
 
         let background_color = self.background.as_ref().and_then(Fill::color);
@@ -507,6 +743,36 @@ impl Style {
             );
         }
 
+        if let Some(outline) = self
+            .outline
+            .filter(|outline| !outline.width.is_zero() && !outline.color.is_transparent())
+        {
+            let width = outline.width.to_pixels(rem_size);
+            let distance = outline.offset.to_pixels(rem_size) + width;
+            let mut corner_radii = self.corner_radii.to_pixels(bounds.size, rem_size);
+            for radius in [
+                &mut corner_radii.top_left,
+                &mut corner_radii.top_right,
+                &mut corner_radii.bottom_right,
+                &mut corner_radii.bottom_left,
+            ] {
+                if *radius > Pixels::ZERO {
+                    *radius += distance;
+                }
+            }
+            let mut outline_bounds = bounds;
+            outline_bounds.dilate(distance);
+            let mut background = outline.color;
+            background.a = 0.;
+            cx.paint_quad(quad(
+                outline_bounds,
+                corner_radii,
+                background,
+                Edges::all(width),
+                outline.color,
+            ));
+        }
+
         #[cfg(debug_assertions)]
         if self.debug_below {
             cx.remove_global::<DebugBelow>();
@@ -555,6 +821,10 @@ impl Default for Style {
             border_color: None,
             corner_radii: Corners::default(),
             box_shadow: Default::default(),
+            elevation: None,
+            z_index: None,
+            opacity: None,
+            outline: None,
             text: TextStyleRefinement::default(),
             mouse_cursor: None,
 
@@ -570,7 +840,7 @@ impl Default for Style {
 #[derive(Refineable, Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
 #[refineable(Debug)]
 pub struct UnderlineStyle {
-    /// The thickness of the underline.
+    /// The thickness of the underline. When zero, the thickness suggested by the font is used.
     pub thickness: Pixels,
 
     /// The color of the underline.
@@ -584,7 +854,7 @@ pub struct UnderlineStyle {
 #[derive(Refineable, Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
 #[refineable(Debug)]
 pub struct StrikethroughStyle {
-    /// The thickness of the strikethrough.
+    /// The thickness of the strikethrough. When zero, the thickness suggested by the font is used.
     pub thickness: Pixels,
 
     /// The color of the strikethrough.
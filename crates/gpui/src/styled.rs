@@ -1,9 +1,10 @@
 use crate::{
     self as gpui, hsla, point, px, relative, rems, AbsoluteLength, AlignItems, CursorStyle,
-    DefiniteLength, Fill, FlexDirection, FlexWrap, Font, FontStyle, FontWeight, Hsla,
-    JustifyContent, Length, Position, SharedString, StyleRefinement, Visibility, WhiteSpace,
+    DefiniteLength, Fill, FlexDirection, FlexWrap, Font, FontFallbacks, FontStyle, FontWeight,
+    Hsla, JustifyContent, Length, Position, SharedString, StyleRefinement, TextAlign, Visibility,
+    WhiteSpace,
 };
-use crate::{BoxShadow, TextStyleRefinement};
+use crate::{BoxShadow, ElevationLevel, Outline, TextStyleRefinement};
 use smallvec::{smallvec, SmallVec};
 use taffy::style::{AlignContent, Display, Overflow};
 
@@ -250,6 +251,16 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets how the lines of text are placed within the width of the element, this value
+    /// cascades to its child elements.
+    /// [Docs](https://tailwindcss.com/docs/text-align)
+    fn text_align(mut self, align: TextAlign) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .text_align = Some(align);
+        self
+    }
+
     /// Sets the flex direction of the element to `column`.
     /// [Docs](https://tailwindcss.com/docs/flex-direction#column)
     fn flex_col(mut self) -> Self {
@@ -499,6 +510,44 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets how opaque the element and its descendants are, from 0 for fully transparent to 1.
+    /// Images and emoji are painted fully opaque regardless.
+    /// [Docs](https://tailwindcss.com/docs/opacity)
+    fn opacity(mut self, opacity: f32) -> Self {
+        self.style().opacity = Some(opacity);
+        self
+    }
+
+    /// Draws a ring of the given width and color around the outside of the element. The ring isn't
+    /// part of the layout, so it can be shown and hidden without moving anything.
+    /// [Docs](https://tailwindcss.com/docs/outline-width)
+    fn outline(mut self, width: impl Into<AbsoluteLength>, color: impl Into<Hsla>) -> Self {
+        self.style().outline = Some(Outline {
+            width: width.into(),
+            offset: AbsoluteLength::default(),
+            color: color.into(),
+        });
+        self
+    }
+
+    /// Sets how high the element sits above the window. This gives it the shadow of the level
+    /// from the theme, unless it has a shadow of its own, and paints it over everything at lower
+    /// levels. The overlay, modal and tooltip levels are also drawn in the overlay layer, where the
+    /// elements they're drawn within don't clip them.
+    fn elevation(mut self, level: ElevationLevel) -> Self {
+        self.style().elevation = Some(level);
+        self
+    }
+
+    /// Sets the z-index of the element, so that it's painted over and hit before everything with a
+    /// lower z-index, see [`WindowContext::with_z_index`](crate::WindowContext::with_z_index). This
+    /// takes precedence over the z-index of the element's [`elevation`](Self::elevation).
+    /// [Docs](https://tailwindcss.com/docs/z-index)
+    fn z_index(mut self, z_index: u8) -> Self {
+        self.style().z_index = Some(z_index);
+        self
+    }
+
     /// Sets the box shadow of the element.
     /// [Docs](https://tailwindcss.com/docs/box-shadow)
     fn shadow(mut self, shadows: SmallVec<[BoxShadow; 2]>) -> Self {
@@ -715,9 +764,25 @@ pub trait Styled: Sized {
 
     /// Remove the text decoration on this element, this value cascades to its child elements.
     fn text_decoration_none(mut self) -> Self {
-        self.text_style()
-            .get_or_insert_with(Default::default)
-            .underline = None;
+        let style = self.text_style().get_or_insert_with(Default::default);
+        style.underline = None;
+        style.strikethrough = None;
+        self
+    }
+
+    /// Underline the text of this element and its children, this value cascades to its child elements.
+    /// See the [Tailwind Docs](https://tailwindcss.com/docs/text-decoration)
+    fn underline(mut self) -> Self {
+        let style = self.text_style().get_or_insert_with(Default::default);
+        style.underline.get_or_insert_with(Default::default);
+        self
+    }
+
+    /// Draw a line through the text of this element and its children, this value cascades to its child elements.
+    /// See the [Tailwind Docs](https://tailwindcss.com/docs/text-decoration)
+    fn line_through(mut self) -> Self {
+        let style = self.text_style().get_or_insert_with(Default::default);
+        style.strikethrough.get_or_insert_with(Default::default);
         self
     }
 
@@ -745,7 +810,7 @@ pub trait Styled: Sized {
         self
     }
 
-    /// Set the underline to be 0 thickness, see the [Tailwind Docs](https://tailwindcss.com/docs/text-decoration-thickness)
+    /// Set the underline to the thickness suggested by the font, see the [Tailwind Docs](https://tailwindcss.com/docs/text-decoration-thickness)
     fn text_decoration_0(mut self) -> Self {
         let style = self.text_style().get_or_insert_with(Default::default);
         let underline = style.underline.get_or_insert_with(Default::default);
@@ -793,11 +858,23 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Set the fonts to fall back to, in order, for characters that the font of this element and
+    /// its children doesn't cover.
+    fn font_fallbacks<S: Into<String>>(mut self, fallbacks: impl IntoIterator<Item = S>) -> Self {
+        self.text_style()
+            .get_or_insert_with(Default::default)
+            .font_fallbacks = Some(FontFallbacks::from_fonts(
+            fallbacks.into_iter().map(Into::into).collect(),
+        ));
+        self
+    }
+
     /// Change the font of this element and its children.
     fn font(mut self, font: Font) -> Self {
         let Font {
             family,
             features,
+            fallbacks,
             weight,
             style,
         } = font;
@@ -805,6 +882,7 @@ pub trait Styled: Sized {
         let text_style = self.text_style().get_or_insert_with(Default::default);
         text_style.font_family = Some(family);
         text_style.font_features = Some(features);
+        text_style.font_fallbacks = fallbacks;
         text_style.font_weight = Some(weight);
         text_style.font_style = Some(style);
 
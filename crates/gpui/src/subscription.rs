@@ -104,6 +104,16 @@ where
             })
     }
 
+    /// Returns whether there are no subscribers to any emitter.
+    pub fn is_empty(&self) -> bool {
+        self.0.lock().subscribers.is_empty()
+    }
+
+    /// Returns whether there are any subscribers to the given emitter.
+    pub fn has_subscribers(&self, emitter: &EmitterKey) -> bool {
+        self.0.lock().subscribers.contains_key(emitter)
+    }
+
     /// Call the given callback for each subscriber to the given emitter.
     /// If the callback returns false, the subscriber is removed.
     pub fn retain<F>(&self, emitter: &EmitterKey, mut f: F)
@@ -2,12 +2,20 @@ use crate::{
     AbsoluteLength, Bounds, DefiniteLength, Edges, Length, Pixels, Point, Size, Style,
     WindowContext,
 };
-use collections::{FxHashMap, FxHashSet};
+use collections::{FxHashMap, FxHashSet, FxHasher};
 use smallvec::SmallVec;
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    mem,
+    rc::Rc,
+};
 use taffy::{
     geometry::{Point as TaffyPoint, Rect as TaffyRect, Size as TaffySize},
-    style::AvailableSpace as TaffyAvailableSpace,
+    style::{
+        AvailableSpace as TaffyAvailableSpace, Dimension, LengthPercentage, LengthPercentageAuto,
+        Style as TaffyStyle,
+    },
     tree::NodeId,
     TaffyTree, TraversePartialTree as _,
 };
@@ -15,9 +23,12 @@ use taffy::{
 type NodeMeasureFn =
     Box<dyn FnMut(Size<Option<Pixels>>, Size<AvailableSpace>, &mut WindowContext) -> Size<Pixels>>;
 
+/// Clearing the engine at the end of a frame keeps the allocations of the taffy tree and of the maps
+/// below, so the nodes requested in the next frame reuse the storage of the previous one.
 pub struct TaffyLayoutEngine {
     taffy: TaffyTree<()>,
-    styles: FxHashMap<LayoutId, Style>,
+    styles: StyleInterner,
+    node_styles: FxHashMap<LayoutId, Rc<TaffyStyle>>,
     children_to_parents: FxHashMap<LayoutId, LayoutId>,
     absolute_layout_bounds: FxHashMap<LayoutId, Bounds<Pixels>>,
     computed_layouts: FxHashSet<LayoutId>,
@@ -30,7 +41,8 @@ impl TaffyLayoutEngine {
     pub fn new() -> Self {
         TaffyLayoutEngine {
             taffy: TaffyTree::new(),
-            styles: FxHashMap::default(),
+            styles: StyleInterner::default(),
+            node_styles: FxHashMap::default(),
             children_to_parents: FxHashMap::default(),
             absolute_layout_bounds: FxHashMap::default(),
             computed_layouts: FxHashSet::default(),
@@ -44,7 +56,9 @@ impl TaffyLayoutEngine {
         self.absolute_layout_bounds.clear();
         self.computed_layouts.clear();
         self.nodes_to_measure.clear();
-        self.styles.clear();
+        // Keep the styles used in this frame around, since the next frame is likely to use them too.
+        self.styles.retain_used();
+        self.node_styles.clear();
     }
 
     pub fn request_layout(
@@ -53,17 +67,17 @@ impl TaffyLayoutEngine {
         rem_size: Pixels,
         children: &[LayoutId],
     ) -> LayoutId {
-        let taffy_style = style.to_taffy(rem_size);
+        let taffy_style = self.styles.intern(style.to_taffy(rem_size));
         let layout_id = if children.is_empty() {
             self.taffy
-                .new_leaf(taffy_style)
+                .new_leaf(TaffyStyle::clone(&taffy_style))
                 .expect(EXPECT_MESSAGE)
                 .into()
         } else {
             let parent_id = self
                 .taffy
                 // This is safe because LayoutId is repr(transparent) to taffy::tree::NodeId.
-                .new_with_children(taffy_style, unsafe {
+                .new_with_children(TaffyStyle::clone(&taffy_style), unsafe {
                     std::mem::transmute::<&[LayoutId], &[taffy::NodeId]>(children)
                 })
                 .expect(EXPECT_MESSAGE)
@@ -72,7 +86,7 @@ impl TaffyLayoutEngine {
                 .extend(children.into_iter().map(|child_id| (*child_id, parent_id)));
             parent_id
         };
-        self.styles.insert(layout_id, style);
+        self.insert_node_style(layout_id, taffy_style);
         layout_id
     }
 
@@ -83,18 +97,27 @@ impl TaffyLayoutEngine {
         measure: impl FnMut(Size<Option<Pixels>>, Size<AvailableSpace>, &mut WindowContext) -> Size<Pixels>
             + 'static,
     ) -> LayoutId {
-        let taffy_style = style.to_taffy(rem_size);
+        let taffy_style = self.styles.intern(style.to_taffy(rem_size));
 
         let layout_id = self
             .taffy
-            .new_leaf_with_context(taffy_style, ())
+            .new_leaf_with_context(TaffyStyle::clone(&taffy_style), ())
             .expect(EXPECT_MESSAGE)
             .into();
         self.nodes_to_measure.insert(layout_id, Box::new(measure));
-        self.styles.insert(layout_id, style);
+        self.insert_node_style(layout_id, taffy_style);
         layout_id
     }
 
+    fn insert_node_style(&mut self, layout_id: LayoutId, style: Rc<TaffyStyle>) {
+        let previous_style = self.node_styles.insert(layout_id, style);
+        // Recycled nodes must not pick up the style of the node that used them in a previous frame.
+        debug_assert!(
+            previous_style.is_none(),
+            "layout node {layout_id:?} still had the style of a previous node"
+        );
+    }
+
     // Used to understand performance
     #[allow(dead_code)]
     fn count_all_children(&self, parent: LayoutId) -> anyhow::Result<u32> {
@@ -219,6 +242,117 @@ impl TaffyLayoutEngine {
     }
 }
 
+/// Shares a single instance of each distinct resolved style between all the nodes that use it, so
+/// that a style repeated across the items of a list is only stored once.
+#[derive(Default)]
+struct StyleInterner {
+    styles: FxHashMap<u64, SmallVec<[Rc<TaffyStyle>; 1]>>,
+}
+
+impl StyleInterner {
+    fn intern(&mut self, style: TaffyStyle) -> Rc<TaffyStyle> {
+        let mut hasher = FxHasher::default();
+        hash_taffy_style(&style, &mut hasher);
+        let styles = self.styles.entry(hasher.finish()).or_default();
+        // Different styles can hash to the same value, so compare them to find the right one.
+        if let Some(interned) = styles.iter().find(|interned| ***interned == style) {
+            return interned.clone();
+        }
+        let interned = Rc::new(style);
+        styles.push(interned.clone());
+        interned
+    }
+
+    /// Forget the styles that aren't used by any node.
+    fn retain_used(&mut self) {
+        self.styles.retain(|_, styles| {
+            styles.retain(|style| Rc::strong_count(style) > 1);
+            !styles.is_empty()
+        });
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.styles.values().map(|styles| styles.len()).sum()
+    }
+}
+
+/// Hashes the properties that are converted from gpui's [`Style`]. Since the rest always have their
+/// default values, styles that are equal always hash to the same value.
+fn hash_taffy_style(style: &TaffyStyle, state: &mut impl Hasher) {
+    fn hash_f32(value: f32, state: &mut impl Hasher) {
+        value.to_bits().hash(state);
+    }
+
+    fn hash_dimension(dimension: Dimension, state: &mut impl Hasher) {
+        mem::discriminant(&dimension).hash(state);
+        match dimension {
+            Dimension::Length(value) | Dimension::Percent(value) => hash_f32(value, state),
+            Dimension::Auto => {}
+        }
+    }
+
+    fn hash_length_percentage(length: LengthPercentage, state: &mut impl Hasher) {
+        mem::discriminant(&length).hash(state);
+        match length {
+            LengthPercentage::Length(value) | LengthPercentage::Percent(value) => {
+                hash_f32(value, state)
+            }
+        }
+    }
+
+    fn hash_length_percentage_auto(length: LengthPercentageAuto, state: &mut impl Hasher) {
+        mem::discriminant(&length).hash(state);
+        match length {
+            LengthPercentageAuto::Length(value) | LengthPercentageAuto::Percent(value) => {
+                hash_f32(value, state)
+            }
+            LengthPercentageAuto::Auto => {}
+        }
+    }
+
+    fn hash_rect<T: Copy>(rect: &TaffyRect<T>, mut hash: impl FnMut(T)) {
+        hash(rect.top);
+        hash(rect.right);
+        hash(rect.bottom);
+        hash(rect.left);
+    }
+
+    mem::discriminant(&style.display).hash(state);
+    mem::discriminant(&style.overflow.x).hash(state);
+    mem::discriminant(&style.overflow.y).hash(state);
+    hash_f32(style.scrollbar_width, state);
+    mem::discriminant(&style.position).hash(state);
+    hash_rect(&style.inset, |length| {
+        hash_length_percentage_auto(length, state)
+    });
+    for size in [&style.size, &style.min_size, &style.max_size] {
+        hash_dimension(size.width, state);
+        hash_dimension(size.height, state);
+    }
+    style.aspect_ratio.map(f32::to_bits).hash(state);
+    hash_rect(&style.margin, |length| {
+        hash_length_percentage_auto(length, state)
+    });
+    hash_rect(&style.padding, |length| {
+        hash_length_percentage(length, state)
+    });
+    hash_rect(&style.border, |length| {
+        hash_length_percentage(length, state)
+    });
+    style.align_items.map(mem::discriminant).hash(state);
+    style.align_self.map(mem::discriminant).hash(state);
+    style.align_content.map(mem::discriminant).hash(state);
+    style.justify_content.map(mem::discriminant).hash(state);
+    hash_length_percentage(style.gap.width, state);
+    hash_length_percentage(style.gap.height, state);
+    mem::discriminant(&style.flex_direction).hash(state);
+    mem::discriminant(&style.flex_wrap).hash(state);
+    hash_dimension(style.flex_basis, state);
+    hash_f32(style.flex_grow, state);
+    hash_f32(style.flex_shrink, state);
+}
+
 /// A unique identifier for a layout node, generated when requesting a layout from Taffy
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 #[repr(transparent)]
@@ -498,3 +632,107 @@ impl From<Size<Pixels>> for Size<AvailableSpace> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::px;
+
+    fn style_with_width(width: f32) -> Style {
+        let mut style = Style::default();
+        style.size.width = px(width).into();
+        style
+    }
+
+    #[test]
+    fn test_identical_styles_are_shared() {
+        let mut engine = TaffyLayoutEngine::new();
+        let items = (0..10)
+            .map(|_| engine.request_layout(style_with_width(10.), px(16.), &[]))
+            .collect::<Vec<_>>();
+        let other = engine.request_layout(style_with_width(20.), px(16.), &[]);
+        engine.request_layout(Style::default(), px(16.), &items);
+
+        assert_eq!(engine.styles.len(), 3);
+        assert!(items
+            .iter()
+            .all(|item| Rc::ptr_eq(&engine.node_styles[item], &engine.node_styles[&items[0]])));
+        assert!(!Rc::ptr_eq(
+            &engine.node_styles[&other],
+            &engine.node_styles[&items[0]]
+        ));
+
+        // Rems are resolved before interning, so they share the style of the same length in pixels.
+        let mut rem_style = Style::default();
+        rem_style.size.width = crate::rems(0.5).into();
+        let rem_item = engine.request_layout(rem_style, px(20.), &[]);
+        assert!(Rc::ptr_eq(
+            &engine.node_styles[&rem_item],
+            &engine.node_styles[&items[0]]
+        ));
+    }
+
+    #[test]
+    fn test_recycled_nodes_dont_keep_previous_styles() {
+        let mut engine = TaffyLayoutEngine::new();
+        for _ in 0..3 {
+            engine.request_layout(style_with_width(10.), px(16.), &[]);
+        }
+        engine.clear();
+        assert_eq!(engine.styles.len(), 1);
+
+        // The nodes of the next frame reuse the storage of the previous one, but have their own
+        // styles.
+        let node = engine.request_layout(style_with_width(20.), px(16.), &[]);
+        assert_eq!(engine.node_styles.len(), 1);
+        assert_eq!(
+            engine.taffy.style(node.into()).unwrap().size.width,
+            Dimension::Length(20.)
+        );
+        assert_eq!(engine.node_styles[&node].size.width, Dimension::Length(20.));
+
+        // Styles that weren't used in the last frame are forgotten.
+        engine.clear();
+        assert_eq!(engine.styles.len(), 1);
+        engine.clear();
+        assert_eq!(engine.styles.len(), 0);
+    }
+
+    #[test]
+    fn test_reusing_nodes_over_many_frames_keeps_allocations_bounded() {
+        let mut engine = TaffyLayoutEngine::new();
+        let draw_frame = |engine: &mut TaffyLayoutEngine, frame: usize| {
+            // Every other frame lays out its items with a different style, so recycled nodes that
+            // kept the style of the last frame would be noticed.
+            let width = if frame % 2 == 0 { 10. } else { 20. };
+            let items = (0..50)
+                .map(|_| engine.request_layout(style_with_width(width), px(16.), &[]))
+                .collect::<Vec<_>>();
+            engine.request_layout(Style::default(), px(16.), &items);
+            for item in &items {
+                assert_eq!(
+                    engine.taffy.style((*item).into()).unwrap().size.width,
+                    Dimension::Length(width)
+                );
+                assert_eq!(
+                    engine.node_styles[item].size.width,
+                    Dimension::Length(width)
+                );
+            }
+            engine.clear();
+        };
+
+        draw_frame(&mut engine, 0);
+        draw_frame(&mut engine, 1);
+        let node_styles_capacity = engine.node_styles.capacity();
+        let parents_capacity = engine.children_to_parents.capacity();
+        for frame in 2..200 {
+            draw_frame(&mut engine, frame);
+            assert_eq!(engine.taffy.total_node_count(), 0);
+            // Only the styles of the last frame stay interned.
+            assert_eq!(engine.styles.len(), 2);
+            assert_eq!(engine.node_styles.capacity(), node_styles_capacity);
+            assert_eq!(engine.children_to_parents.capacity(), parents_capacity);
+        }
+    }
+}
@@ -0,0 +1,387 @@
+//! The buffer, cursor and selection logic shared by text fields.
+//!
+//! [`EditableText`] knows nothing about layout or windows: it holds the text being edited and the
+//! selection within it, moves the cursor by grapheme or line, and applies the edits requested by
+//! the platform's [`InputHandler`](crate::InputHandler), which talks in UTF-16 offsets. Elements
+//! that edit text, like [`text_area`](crate::text_area), keep one of these and translate key
+//! presses and clicks into calls on it.
+
+use crate::SharedString;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Text that's being edited, along with the selection and the text marked by an input method.
+///
+/// All offsets are byte offsets into the text unless the method says otherwise. The cursor is
+/// the end of the selection that moves when the selection is extended, and an empty selection is
+/// just a cursor.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EditableText {
+    content: SharedString,
+    selected_range: Range<usize>,
+    selection_reversed: bool,
+    marked_range: Option<Range<usize>>,
+}
+
+impl EditableText {
+    /// Create editable text with the cursor at its end.
+    pub fn new(content: impl Into<SharedString>) -> Self {
+        let mut this = Self::default();
+        this.set_content(content);
+        this
+    }
+
+    /// The text being edited.
+    pub fn content(&self) -> &SharedString {
+        &self.content
+    }
+
+    /// Replace the whole text, moving the cursor to its end.
+    pub fn set_content(&mut self, content: impl Into<SharedString>) {
+        self.content = content.into();
+        self.selected_range = self.content.len()..self.content.len();
+        self.selection_reversed = false;
+        self.marked_range = None;
+    }
+
+    /// The selected range, which is empty when there's only a cursor.
+    pub fn selected_range(&self) -> Range<usize> {
+        self.selected_range.clone()
+    }
+
+    /// The selected text.
+    pub fn selected_text(&self) -> &str {
+        &self.content[self.selected_range.clone()]
+    }
+
+    /// The range of text the input method is composing, if any.
+    pub fn marked_range(&self) -> Option<Range<usize>> {
+        self.marked_range.clone()
+    }
+
+    /// The offset of the cursor, which is the start of the selection if it was extended
+    /// backwards and its end otherwise.
+    pub fn cursor_offset(&self) -> usize {
+        if self.selection_reversed {
+            self.selected_range.start
+        } else {
+            self.selected_range.end
+        }
+    }
+
+    /// Move the cursor to the given offset, collapsing the selection.
+    pub fn move_to(&mut self, offset: usize) {
+        self.selected_range = offset..offset;
+        self.selection_reversed = false;
+    }
+
+    /// Move the cursor to the given offset, extending the selection from its other end.
+    pub fn select_to(&mut self, offset: usize) {
+        if self.selection_reversed {
+            self.selected_range.start = offset
+        } else {
+            self.selected_range.end = offset
+        };
+        if self.selected_range.end < self.selected_range.start {
+            self.selection_reversed = !self.selection_reversed;
+            self.selected_range = self.selected_range.end..self.selected_range.start;
+        }
+    }
+
+    /// Select the whole text.
+    pub fn select_all(&mut self) {
+        self.move_to(0);
+        self.select_to(self.content.len());
+    }
+
+    /// Move the cursor one grapheme to the left, or to the start of the selection if there is
+    /// one.
+    pub fn move_left(&mut self) {
+        if self.selected_range.is_empty() {
+            self.move_to(self.previous_boundary(self.cursor_offset()));
+        } else {
+            self.move_to(self.selected_range.start)
+        }
+    }
+
+    /// Move the cursor one grapheme to the right, or to the end of the selection if there is one.
+    pub fn move_right(&mut self) {
+        if self.selected_range.is_empty() {
+            self.move_to(self.next_boundary(self.selected_range.end));
+        } else {
+            self.move_to(self.selected_range.end)
+        }
+    }
+
+    /// Extend the selection one grapheme to the left.
+    pub fn select_left(&mut self) {
+        self.select_to(self.previous_boundary(self.cursor_offset()));
+    }
+
+    /// Extend the selection one grapheme to the right.
+    pub fn select_right(&mut self) {
+        self.select_to(self.next_boundary(self.cursor_offset()));
+    }
+
+    /// Delete the selection, or the grapheme before the cursor if nothing is selected.
+    pub fn backspace(&mut self) {
+        if self.selected_range.is_empty() {
+            self.select_to(self.previous_boundary(self.cursor_offset()))
+        }
+        self.insert("")
+    }
+
+    /// Delete the selection, or the grapheme after the cursor if nothing is selected.
+    pub fn delete(&mut self) {
+        if self.selected_range.is_empty() {
+            self.select_to(self.next_boundary(self.cursor_offset()))
+        }
+        self.insert("")
+    }
+
+    /// Replace the selection with the given text, leaving the cursor after it.
+    pub fn insert(&mut self, text: &str) {
+        let range = self.selected_range.clone();
+        self.replace(range, text);
+    }
+
+    /// The offset of the grapheme boundary before the given offset.
+    pub fn previous_boundary(&self, offset: usize) -> usize {
+        self.content
+            .grapheme_indices(true)
+            .rev()
+            .find_map(|(idx, _)| (idx < offset).then_some(idx))
+            .unwrap_or(0)
+    }
+
+    /// The offset of the grapheme boundary after the given offset.
+    pub fn next_boundary(&self, offset: usize) -> usize {
+        self.content
+            .grapheme_indices(true)
+            .find_map(|(idx, _)| (idx > offset).then_some(idx))
+            .unwrap_or(self.content.len())
+    }
+
+    /// The offset of the start of the line containing the given offset, just after the
+    /// preceding newline.
+    pub fn line_start(&self, offset: usize) -> usize {
+        self.content[..offset].rfind('\n').map_or(0, |ix| ix + 1)
+    }
+
+    /// The offset of the end of the line containing the given offset, just before the next
+    /// newline.
+    pub fn line_end(&self, offset: usize) -> usize {
+        self.content[offset..]
+            .find('\n')
+            .map_or(self.content.len(), |ix| offset + ix)
+    }
+
+    /// The text in the given UTF-16 range, see [`InputHandler::text_for_range`].
+    ///
+    /// [`InputHandler::text_for_range`]: crate::InputHandler::text_for_range
+    pub fn text_for_range(&self, range_utf16: Range<usize>) -> String {
+        let range = self.range_from_utf16(&range_utf16);
+        self.content[range].to_string()
+    }
+
+    /// The selected range in UTF-16 offsets.
+    pub fn selected_range_utf16(&self) -> Range<usize> {
+        self.range_to_utf16(&self.selected_range)
+    }
+
+    /// The marked range in UTF-16 offsets.
+    pub fn marked_range_utf16(&self) -> Option<Range<usize>> {
+        self.marked_range
+            .as_ref()
+            .map(|range| self.range_to_utf16(range))
+    }
+
+    /// Stop composing the marked text, keeping it as it is.
+    pub fn unmark_text(&mut self) {
+        self.marked_range = None;
+    }
+
+    /// Replace the given UTF-16 range with text, see [`InputHandler::replace_text_in_range`].
+    /// Without a range, the marked text is replaced, or the selection if nothing is marked.
+    ///
+    /// [`InputHandler::replace_text_in_range`]: crate::InputHandler::replace_text_in_range
+    pub fn replace_text_in_range(&mut self, range_utf16: Option<Range<usize>>, new_text: &str) {
+        let range = self.range_for_input(range_utf16);
+        self.replace(range, new_text);
+    }
+
+    /// Replace the given UTF-16 range with text that stays marked until the input method is done
+    /// composing it, see [`InputHandler::replace_and_mark_text_in_range`].
+    ///
+    /// [`InputHandler::replace_and_mark_text_in_range`]: crate::InputHandler::replace_and_mark_text_in_range
+    pub fn replace_and_mark_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        new_text: &str,
+        new_selected_range_utf16: Option<Range<usize>>,
+    ) {
+        let range = self.range_for_input(range_utf16);
+        self.content =
+            (self.content[0..range.start].to_owned() + new_text + &self.content[range.end..])
+                .into();
+        self.marked_range = Some(range.start..range.start + new_text.len());
+        self.selected_range = new_selected_range_utf16
+            .map(|range_utf16| {
+                let range_utf16 = range_utf16.start + self.offset_to_utf16(range.start)
+                    ..range_utf16.end + self.offset_to_utf16(range.start);
+                self.range_from_utf16(&range_utf16)
+            })
+            .unwrap_or_else(|| range.start + new_text.len()..range.start + new_text.len());
+        self.selection_reversed = false;
+    }
+
+    /// Convert a UTF-16 offset into the text to a byte offset.
+    pub fn offset_from_utf16(&self, offset: usize) -> usize {
+        let mut utf8_offset = 0;
+        let mut utf16_count = 0;
+
+        for ch in self.content.chars() {
+            if utf16_count >= offset {
+                break;
+            }
+            utf16_count += ch.len_utf16();
+            utf8_offset += ch.len_utf8();
+        }
+
+        utf8_offset
+    }
+
+    /// Convert a byte offset into the text to a UTF-16 offset.
+    pub fn offset_to_utf16(&self, offset: usize) -> usize {
+        let mut utf16_offset = 0;
+        let mut utf8_count = 0;
+
+        for ch in self.content.chars() {
+            if utf8_count >= offset {
+                break;
+            }
+            utf8_count += ch.len_utf8();
+            utf16_offset += ch.len_utf16();
+        }
+
+        utf16_offset
+    }
+
+    /// Convert a byte range to a UTF-16 range.
+    pub fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
+        self.offset_to_utf16(range.start)..self.offset_to_utf16(range.end)
+    }
+
+    /// Convert a UTF-16 range to a byte range.
+    pub fn range_from_utf16(&self, range_utf16: &Range<usize>) -> Range<usize> {
+        self.offset_from_utf16(range_utf16.start)..self.offset_from_utf16(range_utf16.end)
+    }
+
+    fn range_for_input(&self, range_utf16: Option<Range<usize>>) -> Range<usize> {
+        range_utf16
+            .as_ref()
+            .map(|range_utf16| self.range_from_utf16(range_utf16))
+            .or(self.marked_range.clone())
+            .unwrap_or(self.selected_range.clone())
+    }
+
+    fn replace(&mut self, range: Range<usize>, new_text: &str) {
+        self.content =
+            (self.content[0..range.start].to_owned() + new_text + &self.content[range.end..])
+                .into();
+        self.move_to(range.start + new_text.len());
+        self.marked_range = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EditableText;
+
+    #[test]
+    fn test_grapheme_movement() {
+        let mut text = EditableText::new("ae\u{301}o");
+        assert_eq!(text.cursor_offset(), 5);
+
+        text.move_left();
+        assert_eq!(text.cursor_offset(), 4);
+        text.move_left();
+        assert_eq!(
+            text.cursor_offset(),
+            1,
+            "the accent is part of the grapheme"
+        );
+        text.move_right();
+        assert_eq!(text.cursor_offset(), 4);
+    }
+
+    #[test]
+    fn test_selection_reverses_when_extended_past_anchor() {
+        let mut text = EditableText::new("hello");
+        text.move_to(2);
+        text.select_right();
+        text.select_right();
+        assert_eq!(text.selected_range(), 2..4);
+        assert_eq!(text.selected_text(), "ll");
+
+        text.select_left();
+        text.select_left();
+        text.select_left();
+        assert_eq!(text.selected_range(), 1..2);
+        assert_eq!(text.cursor_offset(), 1);
+
+        text.move_right();
+        assert_eq!(text.selected_range(), 2..2);
+    }
+
+    #[test]
+    fn test_backspace_and_delete() {
+        let mut text = EditableText::new("hello");
+        text.backspace();
+        assert_eq!(text.content().as_ref(), "hell");
+
+        text.move_to(0);
+        text.delete();
+        assert_eq!(text.content().as_ref(), "ell");
+
+        text.select_all();
+        text.backspace();
+        assert_eq!(text.content().as_ref(), "");
+        text.backspace();
+        assert_eq!(text.content().as_ref(), "");
+    }
+
+    #[test]
+    fn test_line_boundaries() {
+        let text = EditableText::new("one\ntwo\n\nthree");
+        assert_eq!(text.line_start(0), 0);
+        assert_eq!(text.line_end(0), 3);
+        assert_eq!(text.line_start(5), 4);
+        assert_eq!(text.line_end(5), 7);
+        assert_eq!(text.line_start(8), 8);
+        assert_eq!(text.line_end(8), 8);
+        assert_eq!(text.line_start(14), 9);
+        assert_eq!(text.line_end(14), 14);
+    }
+
+    #[test]
+    fn test_input_in_utf16() {
+        let mut text = EditableText::new("😀a");
+        assert_eq!(text.selected_range_utf16(), 3..3);
+        assert_eq!(text.text_for_range(0..2), "😀");
+
+        text.replace_text_in_range(Some(2..3), "b");
+        assert_eq!(text.content().as_ref(), "😀b");
+        assert_eq!(text.selected_range(), 5..5);
+
+        text.replace_and_mark_text_in_range(None, "ni", Some(1..2));
+        assert_eq!(text.content().as_ref(), "😀bni");
+        assert_eq!(text.marked_range(), Some(5..7));
+        assert_eq!(text.selected_range(), 6..7);
+
+        text.replace_text_in_range(None, "你");
+        assert_eq!(text.content().as_ref(), "😀b你");
+        assert_eq!(text.marked_range(), None);
+        assert_eq!(text.cursor_offset(), text.content().len());
+    }
+}
@@ -1,8 +1,10 @@
+mod font_fallbacks;
 mod font_features;
 mod line;
 mod line_layout;
 mod line_wrapper;
 
+pub use font_fallbacks::*;
 pub use font_features::*;
 pub use line::*;
 pub use line_layout::*;
@@ -27,7 +29,10 @@ use std::{
     fmt::{Debug, Display, Formatter},
     hash::{Hash, Hasher},
     ops::{Deref, DerefMut, Range},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering::SeqCst},
+        Arc,
+    },
 };
 
 /// An opaque identifier for a specific font.
@@ -49,7 +54,11 @@ pub struct TextSystem {
     raster_bounds: RwLock<FxHashMap<RenderGlyphParams, Bounds<DevicePixels>>>,
     wrapper_pool: Mutex<FxHashMap<FontIdWithSize, Vec<LineWrapper>>>,
     font_runs_pool: Mutex<Vec<Vec<FontRun>>>,
+    /// The font each character is shaped in, by the font with fallbacks of its run.
+    fallback_fonts_by_char: RwLock<FxHashMap<Font, FxHashMap<char, FontId>>>,
     fallback_font_stack: SmallVec<[Font; 2]>,
+    /// Incremented whenever fonts are added, as text may then be shaped differently.
+    font_generation: AtomicUsize,
 }
 
 impl TextSystem {
@@ -61,6 +70,7 @@ impl TextSystem {
             font_ids_by_font: RwLock::default(),
             wrapper_pool: Mutex::default(),
             font_runs_pool: Mutex::default(),
+            fallback_fonts_by_char: RwLock::default(),
             fallback_font_stack: smallvec![
                 // TODO: This is currently Zed-specific.
                 // We should allow GPUI users to provide their own fallback font stack.
@@ -71,6 +81,7 @@ impl TextSystem {
                 font("Noto Sans"), // KDE
                 font("DejaVu Sans")
             ],
+            font_generation: AtomicUsize::new(0),
         }
     }
 
@@ -92,7 +103,10 @@ impl TextSystem {
 
     /// Add a font's data to the text system.
     pub fn add_fonts(&self, fonts: Vec<Cow<'static, [u8]>>) -> Result<()> {
-        self.platform_text_system.add_fonts(fonts)
+        self.platform_text_system.add_fonts(fonts)?;
+        self.fallback_fonts_by_char.write().clear();
+        self.font_generation.fetch_add(1, SeqCst);
+        Ok(())
     }
 
     /// Get the FontId for the configure font family and style.
@@ -157,6 +171,77 @@ impl TextSystem {
         );
     }
 
+    /// Appends the runs for shaping `text` in the given font to `font_runs`. When the font has
+    /// fallbacks, each character that the font has no glyph for is assigned to the first fallback
+    /// that does. Characters that none of them cover are left to the platform's fallback chain.
+    pub(crate) fn push_font_runs(&self, text: &str, font: &Font, font_runs: &mut Vec<FontRun>) {
+        let mut push_run = |len: usize, font_id: FontId| {
+            if let Some(last_run) = font_runs.last_mut() {
+                if last_run.font_id == font_id {
+                    last_run.len += len;
+                    return;
+                }
+            }
+            font_runs.push(FontRun { len, font_id });
+        };
+
+        let font_id = self.resolve_font(font);
+        let fallback_ids = font
+            .fallbacks
+            .iter()
+            .flat_map(|fallbacks| fallbacks.fallback_list())
+            .filter_map(|family| {
+                self.font_id(&Font {
+                    family: family.clone().into(),
+                    fallbacks: None,
+                    ..font.clone()
+                })
+                .ok()
+            })
+            .collect::<SmallVec<[FontId; 4]>>();
+        if fallback_ids.is_empty() {
+            push_run(text.len(), font_id);
+            return;
+        }
+
+        let mut fallback_fonts_by_char = self.fallback_fonts_by_char.write();
+        if !fallback_fonts_by_char.contains_key(font) {
+            fallback_fonts_by_char.insert(font.clone(), FxHashMap::default());
+        }
+        let fonts_by_char = fallback_fonts_by_char.get_mut(font).unwrap();
+        let mut current_font_id = font_id;
+        for ch in text.chars() {
+            // Joiners, variation selectors and skin tone modifiers must be shaped in the same font
+            // as the character they modify.
+            let continues_cluster = matches!(
+                ch,
+                '\u{200d}' | '\u{fe00}'..='\u{fe0f}' | '\u{1f3fb}'..='\u{1f3ff}'
+            );
+            if !continues_cluster {
+                current_font_id = *fonts_by_char.entry(ch).or_insert_with(|| {
+                    if self
+                        .platform_text_system
+                        .glyph_for_char(font_id, ch)
+                        .is_some()
+                    {
+                        font_id
+                    } else {
+                        fallback_ids
+                            .iter()
+                            .copied()
+                            .find(|fallback_id| {
+                                self.platform_text_system
+                                    .glyph_for_char(*fallback_id, ch)
+                                    .is_some()
+                            })
+                            .unwrap_or(font_id)
+                    }
+                });
+            }
+            push_run(ch.len_utf8(), current_font_id);
+        }
+    }
+
     /// Get the bounding box for the given font and font size.
     /// A font's bounding box is the smallest rectangle that could enclose all glyphs
     /// in the font. superimposed over one another.
@@ -223,6 +308,17 @@ impl TextSystem {
         self.read_metrics(font_id, |metrics| metrics.descent(font_size))
     }
 
+    /// Get the suggested distance from the baseline to the top of an underline in the given font
+    /// and size. Negative values are below the baseline.
+    pub fn underline_position(&self, font_id: FontId, font_size: Pixels) -> Pixels {
+        self.read_metrics(font_id, |metrics| metrics.underline_position(font_size))
+    }
+
+    /// Get the suggested thickness of an underline in the given font and size.
+    pub fn underline_thickness(&self, font_id: FontId, font_size: Pixels) -> Pixels {
+        self.read_metrics(font_id, |metrics| metrics.underline_thickness(font_size))
+    }
+
     /// Get the recommended baseline offset for the given font and line height.
     pub fn baseline_offset(
         &self,
@@ -294,6 +390,8 @@ impl TextSystem {
 #[derive(Deref)]
 pub struct WindowTextSystem {
     line_layout_cache: LineLayoutCache,
+    /// The text system's font generation when the layout cache was last cleared.
+    font_generation: AtomicUsize,
     #[deref]
     text_system: Arc<TextSystem>,
 }
@@ -302,6 +400,7 @@ impl WindowTextSystem {
     pub(crate) fn new(text_system: Arc<TextSystem>) -> Self {
         Self {
             line_layout_cache: LineLayoutCache::new(text_system.platform_text_system.clone()),
+            font_generation: AtomicUsize::new(text_system.font_generation.load(SeqCst)),
             text_system,
         }
     }
@@ -367,13 +466,15 @@ impl WindowTextSystem {
 
     /// Shape a multi line string of text, at the given font_size, for painting to the screen.
     /// Subsets of the text can be styled independently with the `runs` parameter.
-    /// If `wrap_width` is provided, the line breaks will be adjusted to fit within the given width.
+    /// If `wrap_width` is provided, the line breaks will be adjusted to fit within the given width,
+    /// breaking lines where allowed by `wrap_mode`.
     pub fn shape_text(
         &self,
         text: SharedString,
         font_size: Pixels,
         runs: &[TextRun],
         wrap_width: Option<Pixels>,
+        wrap_mode: WrapMode,
     ) -> Result<SmallVec<[WrappedLine; 1]>> {
         let mut runs = runs.iter().cloned().peekable();
         let mut font_runs = self.font_runs_pool.lock().pop().unwrap_or_default();
@@ -384,7 +485,6 @@ impl WindowTextSystem {
         let mut process_line = |line_text: SharedString| {
             let line_end = line_start + line_text.len();
 
-            let mut last_font: Option<Font> = None;
             let mut decoration_runs = SmallVec::<[DecorationRun; 32]>::new();
             let mut run_start = line_start;
             while run_start < line_end {
@@ -394,15 +494,12 @@ impl WindowTextSystem {
 
                 let run_len_within_line = cmp::min(line_end, run_start + run.len) - run_start;
 
-                if last_font == Some(run.font.clone()) {
-                    font_runs.last_mut().unwrap().len += run_len_within_line;
-                } else {
-                    last_font = Some(run.font.clone());
-                    font_runs.push(FontRun {
-                        len: run_len_within_line,
-                        font_id: self.resolve_font(&run.font),
-                    });
-                }
+                let run_text_start = run_start - line_start;
+                self.push_font_runs(
+                    &line_text[run_text_start..run_text_start + run_len_within_line],
+                    &run.font,
+                    &mut font_runs,
+                );
 
                 if decoration_runs.last().map_or(false, |last_run| {
                     last_run.color == run.color
@@ -432,12 +529,13 @@ impl WindowTextSystem {
 
             let layout = self
                 .line_layout_cache
-                .layout_wrapped_line(&line_text, font_size, &font_runs, wrap_width);
+                .layout_wrapped_line(&line_text, font_size, &font_runs, wrap_width, wrap_mode);
 
             lines.push(WrappedLine {
                 layout,
                 decoration_runs,
                 text: line_text,
+                alignment: None,
             });
 
             // Skip `\n` character.
@@ -476,7 +574,21 @@ impl WindowTextSystem {
     }
 
     pub(crate) fn finish_frame(&self) {
-        self.line_layout_cache.finish_frame()
+        self.line_layout_cache.finish_frame();
+        let font_generation = self.text_system.font_generation.load(SeqCst);
+        if self.font_generation.swap(font_generation, SeqCst) != font_generation {
+            self.line_layout_cache.clear();
+        }
+    }
+
+    /// Drop every cached text layout, so that all text is shaped again.
+    pub(crate) fn clear_layout_cache(&self) {
+        self.line_layout_cache.clear();
+    }
+
+    /// How often text layouts were found in the cache since the last call.
+    pub(crate) fn take_layout_cache_stats(&self) -> TextLayoutCacheStats {
+        self.line_layout_cache.take_stats()
     }
 
     /// Layout the given line of text, at the given font_size.
@@ -490,18 +602,11 @@ impl WindowTextSystem {
         runs: &[TextRun],
     ) -> Result<Arc<LineLayout>> {
         let mut font_runs = self.font_runs_pool.lock().pop().unwrap_or_default();
+        let mut run_start = 0;
         for run in runs.iter() {
-            let font_id = self.resolve_font(&run.font);
-            if let Some(last_run) = font_runs.last_mut() {
-                if last_run.font_id == font_id {
-                    last_run.len += run.len;
-                    continue;
-                }
-            }
-            font_runs.push(FontRun {
-                len: run.len,
-                font_id,
-            });
+            let run_end = (run_start + run.len).min(text.len());
+            self.push_font_runs(&text[run_start..run_end], &run.font, &mut font_runs);
+            run_start = run_end;
         }
 
         let layout = self
@@ -689,6 +794,9 @@ pub struct Font {
     /// The font features to use.
     pub features: FontFeatures,
 
+    /// The fonts to fall back to for characters this font doesn't cover.
+    pub fallbacks: Option<FontFallbacks>,
+
     /// The font weight.
     pub weight: FontWeight,
 
@@ -701,6 +809,7 @@ pub fn font(family: impl Into<SharedString>) -> Font {
     Font {
         family: family.into(),
         features: FontFeatures::default(),
+        fallbacks: None,
         weight: FontWeight::default(),
         style: FontStyle::default(),
     }
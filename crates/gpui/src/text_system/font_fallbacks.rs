@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use schemars::schema::{InstanceType, SchemaObject};
+use serde::{Deserialize, Serialize};
+
+/// The font families to try, in order, for characters that the primary font has no glyphs for.
+///
+/// Characters that none of them cover fall back to the platform's own fallback chain, which
+/// includes the system emoji font.
+#[derive(Default, Clone, Eq, PartialEq, Hash, Debug, Deserialize, Serialize)]
+pub struct FontFallbacks(pub Arc<Vec<String>>);
+
+impl FontFallbacks {
+    /// Get the fallback font family names, in the order they are tried.
+    pub fn fallback_list(&self) -> &[String] {
+        self.0.as_slice()
+    }
+
+    /// Create font fallbacks from a list of font family names.
+    pub fn from_fonts(fonts: Vec<String>) -> Self {
+        Self(Arc::new(fonts))
+    }
+}
+
+impl schemars::JsonSchema for FontFallbacks {
+    fn schema_name() -> String {
+        "FontFallbacks".into()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let mut schema = SchemaObject::default();
+        schema.instance_type = Some(InstanceType::Array.into());
+        schema.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{font, Font, FontRun, TestAppContext, TestDispatcher};
+    use rand::prelude::*;
+
+    #[test]
+    fn test_font_fallbacks() {
+        let dispatcher = TestDispatcher::new(StdRng::seed_from_u64(0));
+        let cx = TestAppContext::new(dispatcher, None);
+        let text_system = cx.text_system().clone();
+        text_system
+            .add_fonts(vec![
+                std::fs::read("../../assets/fonts/plex-mono/ZedPlexMono-Regular.ttf")
+                    .unwrap()
+                    .into(),
+                std::fs::read("../../assets/fonts/plex-sans/ZedPlexSans-Regular.ttf")
+                    .unwrap()
+                    .into(),
+            ])
+            .unwrap();
+        let mono = text_system.font_id(&font("Zed Plex Mono")).unwrap();
+        let sans = text_system.font_id(&font("Zed Plex Sans")).unwrap();
+
+        // Greek is only covered by the sans font. Neither font covers CJK or emoji, so those
+        // are left to the platform's fallback chain in the primary font's run.
+        let text = "ab Ωλ 中😀";
+        let mono_with_fallbacks = Font {
+            fallbacks: Some(FontFallbacks::from_fonts(vec!["Zed Plex Sans".into()])),
+            ..font("Zed Plex Mono")
+        };
+        let mut font_runs = Vec::new();
+        text_system.push_font_runs(text, &mono_with_fallbacks, &mut font_runs);
+        assert_eq!(
+            font_runs,
+            &[
+                FontRun {
+                    len: "ab ".len(),
+                    font_id: mono
+                },
+                FontRun {
+                    len: "Ωλ".len(),
+                    font_id: sans
+                },
+                FontRun {
+                    len: " 中😀".len(),
+                    font_id: mono
+                },
+            ]
+        );
+
+        // The font of each character is only looked up once.
+        let fallback_fonts_by_char = text_system.fallback_fonts_by_char.read();
+        let fonts_by_char = &fallback_fonts_by_char[&mono_with_fallbacks];
+        assert_eq!(fonts_by_char.len(), "ab Ωλ中😀".chars().count());
+        assert_eq!(fonts_by_char[&'λ'], sans);
+        drop(fallback_fonts_by_char);
+
+        // Without fallbacks, the whole text is shaped in a single run.
+        font_runs.clear();
+        text_system.push_font_runs(text, &font("Zed Plex Mono"), &mut font_runs);
+        assert_eq!(
+            font_runs,
+            &[FontRun {
+                len: text.len(),
+                font_id: mono
+            }]
+        );
+    }
+}
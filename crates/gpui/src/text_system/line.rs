@@ -1,6 +1,7 @@
 use crate::{
-    black, fill, point, px, size, Bounds, Hsla, LineLayout, Pixels, Point, Result, SharedString,
-    StrikethroughStyle, UnderlineStyle, WindowContext, WrapBoundary, WrappedLineLayout,
+    black, fill, point, px, size, Bounds, FontId, Hsla, LineLayout, PaintPrimitive, Pixels, Point,
+    Result, ShapedGlyph, SharedString, StrikethroughStyle, TextAlign, TextDirection, TextSystem,
+    UnderlineStyle, WindowContext, WrapBoundary, WrappedLineLayout,
 };
 use derive_more::{Deref, DerefMut};
 use smallvec::SmallVec;
@@ -56,6 +57,7 @@ impl ShapedLine {
             line_height,
             &self.decoration_runs,
             &[],
+            None,
             cx,
         )?;
 
@@ -72,6 +74,31 @@ pub struct WrappedLine {
     /// The text that was shaped for this line.
     pub text: SharedString,
     pub(crate) decoration_runs: SmallVec<[DecorationRun; 32]>,
+    pub(crate) alignment: Option<Arc<LineAlignment>>,
+}
+
+/// Where the glyphs of a [`WrappedLine`] are placed once it's been aligned within a width, as
+/// offsets from the start of that width.
+#[derive(Debug)]
+pub(crate) struct LineAlignment {
+    width: Pixels,
+    glyphs: Vec<AlignedGlyph>,
+    lines: SmallVec<[AlignedLine; 1]>,
+}
+
+#[derive(Debug)]
+struct AlignedGlyph {
+    index: usize,
+    x: Pixels,
+}
+
+/// A visual line of an aligned [`WrappedLine`].
+#[derive(Debug)]
+struct AlignedLine {
+    /// The index in `LineAlignment::glyphs` of the first glyph on the line.
+    first_glyph: usize,
+    start_x: Pixels,
+    end_x: Pixels,
 }
 
 impl WrappedLine {
@@ -81,6 +108,195 @@ impl WrappedLine {
         self.layout.len()
     }
 
+    /// Place each visual line of this line within the given width, which is used when it's
+    /// painted and when mapping between positions and indices. Whitespace at the end of a visual
+    /// line hangs past the end of it, and lines wider than the width are placed at the left.
+    ///
+    /// Justified lines are stretched by widening the whitespace between their words, except for
+    /// the last visual line, which ends in the hard break that ended this line.
+    pub fn align(&mut self, align: TextAlign, direction: TextDirection, width: Pixels) {
+        if align == TextAlign::Start && direction == TextDirection::Ltr {
+            self.alignment = None;
+            return;
+        }
+
+        let layout = &self.layout.unwrapped_layout;
+        let text = &self.text;
+        let is_space = |glyph: &ShapedGlyph| text[glyph.index..].starts_with(char::is_whitespace);
+        let all_glyphs = layout
+            .runs
+            .iter()
+            .enumerate()
+            .flat_map(|(run_ix, run)| {
+                run.glyphs
+                    .iter()
+                    .enumerate()
+                    .map(move |(glyph_ix, glyph)| (WrapBoundary { run_ix, glyph_ix }, glyph))
+            })
+            .collect::<Vec<_>>();
+
+        let mut line_starts = SmallVec::<[usize; 1]>::new();
+        line_starts.push(0);
+        let mut wraps = self.layout.wrap_boundaries.iter().peekable();
+        for (ix, (boundary, _)) in all_glyphs.iter().enumerate() {
+            if wraps.peek() == Some(&boundary) {
+                wraps.next();
+                line_starts.push(ix);
+            }
+        }
+
+        let mut glyphs = Vec::with_capacity(all_glyphs.len());
+        let mut lines = SmallVec::new();
+        for (line_ix, &first_glyph) in line_starts.iter().enumerate() {
+            let is_last_line = line_ix + 1 == line_starts.len();
+            let end_glyph = line_starts
+                .get(line_ix + 1)
+                .copied()
+                .unwrap_or(all_glyphs.len());
+            let line_glyphs = &all_glyphs[first_glyph..end_glyph];
+            let line_start_x = line_glyphs
+                .first()
+                .map_or(Pixels::ZERO, |(_, glyph)| glyph.position.x);
+            let line_end_x = all_glyphs
+                .get(end_glyph)
+                .map_or(layout.width, |(_, glyph)| glyph.position.x);
+
+            let content_start = line_glyphs
+                .iter()
+                .position(|(_, glyph)| !is_space(glyph))
+                .unwrap_or(line_glyphs.len());
+            let content_end = line_glyphs
+                .iter()
+                .rposition(|(_, glyph)| !is_space(glyph))
+                .map_or(0, |ix| ix + 1);
+            let content_end_x = line_glyphs
+                .get(content_end)
+                .map_or(line_end_x, |(_, glyph)| glyph.position.x);
+            let is_gap = |ix: usize, glyph: &ShapedGlyph| {
+                content_start <= ix && ix < content_end && is_space(glyph)
+            };
+            let gap_count = line_glyphs
+                .iter()
+                .enumerate()
+                .filter(|(ix, (_, glyph))| is_gap(*ix, glyph))
+                .count();
+            let slack = (width - (content_end_x - line_start_x)).max(Pixels::ZERO);
+
+            let align = if align == TextAlign::Justify && (is_last_line || gap_count == 0) {
+                TextAlign::Start
+            } else {
+                align
+            };
+            let (offset, gap_width) =
+                match (align, direction) {
+                    (TextAlign::Start, TextDirection::Ltr)
+                    | (TextAlign::End, TextDirection::Rtl) => (Pixels::ZERO, Pixels::ZERO),
+                    (TextAlign::Start, TextDirection::Rtl)
+                    | (TextAlign::End, TextDirection::Ltr) => (slack, Pixels::ZERO),
+                    (TextAlign::Center, _) => (slack / 2., Pixels::ZERO),
+                    (TextAlign::Justify, _) => (Pixels::ZERO, slack / gap_count as f32),
+                };
+
+            let mut expansion = Pixels::ZERO;
+            for (ix, (_, glyph)) in line_glyphs.iter().enumerate() {
+                glyphs.push(AlignedGlyph {
+                    index: glyph.index,
+                    x: offset + glyph.position.x - line_start_x + expansion,
+                });
+                if is_gap(ix, glyph) {
+                    expansion += gap_width;
+                }
+            }
+            lines.push(AlignedLine {
+                first_glyph,
+                start_x: offset,
+                end_x: offset + line_end_x - line_start_x + expansion,
+            });
+        }
+
+        self.alignment = Some(Arc::new(LineAlignment {
+            width,
+            glyphs,
+            lines,
+        }));
+    }
+
+    /// The index corresponding to a given position relative to the origin this line is painted
+    /// at, for the given line height. See [`WrappedLineLayout::index_for_position`].
+    pub fn index_for_position(
+        &self,
+        position: Point<Pixels>,
+        line_height: Pixels,
+    ) -> Result<usize, usize> {
+        let Some(alignment) = self.alignment.as_ref() else {
+            return self.layout.index_for_position(position, line_height);
+        };
+
+        let line_ix = (position.y / line_height) as usize;
+        let Some(line) = alignment.lines.get(line_ix) else {
+            return Err(0);
+        };
+        let end_glyph = alignment
+            .lines
+            .get(line_ix + 1)
+            .map_or(alignment.glyphs.len(), |line| line.first_glyph);
+        let line_start_index = if line_ix == 0 {
+            0
+        } else {
+            alignment.glyphs[line.first_glyph].index
+        };
+        let line_end_index = alignment
+            .glyphs
+            .get(end_glyph)
+            .map_or(self.len(), |glyph| glyph.index);
+
+        if position.x < line.start_x {
+            Err(line_start_index)
+        } else if position.x >= line.end_x {
+            Err(line_end_index)
+        } else {
+            Ok(alignment.glyphs[line.first_glyph..end_glyph]
+                .iter()
+                .rev()
+                .find(|glyph| glyph.x <= position.x)
+                .map_or(line_start_index, |glyph| glyph.index))
+        }
+    }
+
+    /// Returns the position of the given byte index relative to the origin this line is painted
+    /// at. See [`WrappedLineLayout::position_for_index`].
+    pub fn position_for_index(&self, index: usize, line_height: Pixels) -> Option<Point<Pixels>> {
+        let Some(alignment) = self.alignment.as_ref() else {
+            return self.layout.position_for_index(index, line_height);
+        };
+
+        let mut line_start_index = 0;
+        for (line_ix, line) in alignment.lines.iter().enumerate() {
+            let end_glyph = alignment
+                .lines
+                .get(line_ix + 1)
+                .map_or(alignment.glyphs.len(), |line| line.first_glyph);
+            let line_end_index = alignment
+                .glyphs
+                .get(end_glyph)
+                .map_or(self.len(), |glyph| glyph.index);
+            if index < line_start_index {
+                break;
+            } else if index > line_end_index {
+                line_start_index = line_end_index;
+                continue;
+            }
+
+            let x = alignment.glyphs[line.first_glyph..end_glyph]
+                .iter()
+                .find(|glyph| glyph.index >= index)
+                .map_or(line.end_x, |glyph| glyph.x);
+            return Some(point(x, line_ix as f32 * line_height));
+        }
+
+        None
+    }
+
     /// Paint this line of text to the window.
     pub fn paint(
         &self,
@@ -94,6 +310,7 @@ impl WrappedLine {
             line_height,
             &self.decoration_runs,
             &self.wrap_boundaries,
+            self.alignment.as_deref(),
             cx,
         )?;
 
@@ -107,9 +324,11 @@ fn paint_line(
     line_height: Pixels,
     decoration_runs: &[DecorationRun],
     wrap_boundaries: &[WrapBoundary],
+    alignment: Option<&LineAlignment>,
     cx: &mut WindowContext,
 ) -> Result<()> {
-    let line_bounds = Bounds::new(origin, size(layout.width, line_height));
+    let line_width = alignment.map_or(layout.width, |alignment| alignment.width.max(layout.width));
+    let line_bounds = Bounds::new(origin, size(line_width, line_height));
     cx.paint_layer(line_bounds, |cx| {
         let padding_top = (line_height - layout.ascent - layout.descent) / 2.;
         let baseline_offset = point(px(0.), padding_top + layout.ascent);
@@ -123,33 +342,50 @@ fn paint_line(
         let text_system = cx.text_system().clone();
         let mut glyph_origin = origin;
         let mut prev_glyph_position = Point::default();
+        let mut aligned_glyphs = alignment.map(|alignment| alignment.glyphs.iter());
+        let mut line_ix = 0;
         for (run_ix, run) in layout.runs.iter().enumerate() {
+            cx.window
+                .frame_diagnostics
+                .record_primitive(PaintPrimitive::GlyphRun);
             let max_glyph_size = text_system.bounding_box(run.font_id, layout.font_size).size;
 
             for (glyph_ix, glyph) in run.glyphs.iter().enumerate() {
-                glyph_origin.x += glyph.position.x - prev_glyph_position.x;
+                let line_x = glyph_origin.x + glyph.position.x - prev_glyph_position.x;
+                let aligned_x = aligned_glyphs
+                    .as_mut()
+                    .and_then(|glyphs| glyphs.next())
+                    .map(|aligned_glyph| origin.x + aligned_glyph.x);
+                glyph_origin.x = aligned_x.unwrap_or(line_x);
 
                 if wraps.peek() == Some(&&WrapBoundary { run_ix, glyph_ix }) {
                     wraps.next();
+                    // Finish the decorations at the end of this visual line, and pick them up at
+                    // the start of the next one.
+                    let line_end_x = alignment.map_or(line_x, |alignment| {
+                        origin.x + alignment.lines[line_ix].end_x
+                    });
+                    let next_line_x = aligned_x.unwrap_or(origin.x);
+                    line_ix += 1;
                     if let Some((background_origin, background_color)) = current_background.as_mut()
                     {
                         cx.paint_quad(fill(
                             Bounds {
                                 origin: *background_origin,
-                                size: size(glyph_origin.x - background_origin.x, line_height),
+                                size: size(line_end_x - background_origin.x, line_height),
                             },
                             *background_color,
                         ));
-                        background_origin.x = origin.x;
+                        background_origin.x = next_line_x;
                         background_origin.y += line_height;
                     }
                     if let Some((underline_origin, underline_style)) = current_underline.as_mut() {
                         cx.paint_underline(
                             *underline_origin,
-                            glyph_origin.x - underline_origin.x,
+                            line_end_x - underline_origin.x,
                             underline_style,
                         );
-                        underline_origin.x = origin.x;
+                        underline_origin.x = next_line_x;
                         underline_origin.y += line_height;
                     }
                     if let Some((strikethrough_origin, strikethrough_style)) =
@@ -157,14 +393,14 @@ fn paint_line(
                     {
                         cx.paint_strikethrough(
                             *strikethrough_origin,
-                            glyph_origin.x - strikethrough_origin.x,
+                            line_end_x - strikethrough_origin.x,
                             strikethrough_style,
                         );
-                        strikethrough_origin.x = origin.x;
+                        strikethrough_origin.x = next_line_x;
                         strikethrough_origin.y += line_height;
                     }
 
-                    glyph_origin.x = origin.x;
+                    glyph_origin.x = next_line_x;
                     glyph_origin.y += line_height;
                 }
                 prev_glyph_position = glyph.position;
@@ -192,17 +428,26 @@ fn paint_line(
                             }
                         }
                         if let Some(run_underline) = style_run.underline.as_ref() {
-                            current_underline.get_or_insert((
-                                point(
-                                    glyph_origin.x,
-                                    glyph_origin.y + baseline_offset.y + (layout.descent * 0.618),
-                                ),
-                                UnderlineStyle {
-                                    color: Some(run_underline.color.unwrap_or(style_run.color)),
-                                    thickness: run_underline.thickness,
-                                    wavy: run_underline.wavy,
-                                },
-                            ));
+                            current_underline.get_or_insert_with(|| {
+                                let underline_position =
+                                    text_system.underline_position(run.font_id, layout.font_size);
+                                (
+                                    point(
+                                        glyph_origin.x,
+                                        glyph_origin.y + baseline_offset.y - underline_position,
+                                    ),
+                                    UnderlineStyle {
+                                        color: Some(run_underline.color.unwrap_or(style_run.color)),
+                                        thickness: decoration_thickness(
+                                            run_underline.thickness,
+                                            run.font_id,
+                                            layout.font_size,
+                                            &text_system,
+                                        ),
+                                        wavy: run_underline.wavy,
+                                    },
+                                )
+                            });
                         }
                         if let Some((_, strikethrough_style)) = &mut current_strikethrough {
                             if style_run.strikethrough.as_ref() != Some(strikethrough_style) {
@@ -210,17 +455,29 @@ fn paint_line(
                             }
                         }
                         if let Some(run_strikethrough) = style_run.strikethrough.as_ref() {
-                            current_strikethrough.get_or_insert((
-                                point(
-                                    glyph_origin.x,
-                                    glyph_origin.y
-                                        + (((layout.ascent * 0.5) + baseline_offset.y) * 0.5),
-                                ),
-                                StrikethroughStyle {
-                                    color: Some(run_strikethrough.color.unwrap_or(style_run.color)),
-                                    thickness: run_strikethrough.thickness,
-                                },
-                            ));
+                            current_strikethrough.get_or_insert_with(|| {
+                                let thickness = decoration_thickness(
+                                    run_strikethrough.thickness,
+                                    run.font_id,
+                                    layout.font_size,
+                                    &text_system,
+                                );
+                                // Strike through the middle of the lowercase letters.
+                                let x_height = text_system.x_height(run.font_id, layout.font_size);
+                                (
+                                    point(
+                                        glyph_origin.x,
+                                        glyph_origin.y + baseline_offset.y
+                                            - (x_height + thickness) / 2.,
+                                    ),
+                                    StrikethroughStyle {
+                                        color: Some(
+                                            run_strikethrough.color.unwrap_or(style_run.color),
+                                        ),
+                                        thickness,
+                                    },
+                                )
+                            });
                         }
 
                         run_end += style_run.len as usize;
@@ -287,7 +544,9 @@ fn paint_line(
         }
 
         let mut last_line_end_x = origin.x + layout.width;
-        if let Some(boundary) = wrap_boundaries.last() {
+        if let Some(line) = alignment.and_then(|alignment| alignment.lines.last()) {
+            last_line_end_x = origin.x + line.end_x;
+        } else if let Some(boundary) = wrap_boundaries.last() {
             let run = &layout.runs[boundary.run_ix];
             let glyph = &run.glyphs[boundary.glyph_ix];
             last_line_end_x -= glyph.position.x;
@@ -322,3 +581,20 @@ fn paint_line(
         Ok(())
     })
 }
+
+/// Decorations without an explicit thickness use the one suggested by the font, so that they
+/// scale along with the text.
+fn decoration_thickness(
+    thickness: Pixels,
+    font_id: FontId,
+    font_size: Pixels,
+    text_system: &TextSystem,
+) -> Pixels {
+    if thickness > Pixels::ZERO {
+        thickness
+    } else {
+        text_system
+            .underline_thickness(font_id, font_size)
+            .max(px(1.))
+    }
+}
@@ -6,7 +6,10 @@ use std::{
     borrow::Borrow,
     hash::{Hash, Hasher},
     ops::Range,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering::SeqCst},
+        Arc,
+    },
 };
 
 /// A laid out and styled line of text
@@ -119,6 +122,7 @@ impl LineLayout {
         &self,
         text: &str,
         wrap_width: Pixels,
+        wrap_mode: WrapMode,
     ) -> SmallVec<[WrapBoundary; 1]> {
         let mut boundaries = SmallVec::new();
 
@@ -152,7 +156,11 @@ impl LineLayout {
                 continue;
             }
 
-            if prev_ch == ' ' && ch != ' ' && first_non_whitespace_ix.is_some() {
+            if wrap_mode == WrapMode::Word
+                && prev_ch == ' '
+                && ch != ' '
+                && first_non_whitespace_ix.is_some()
+            {
                 last_candidate_ix = Some(boundary);
                 last_candidate_x = x;
             }
@@ -181,6 +189,19 @@ impl LineLayout {
     }
 }
 
+/// Where a line of text may be broken when it doesn't fit its wrap width.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WrapMode {
+    /// Break between words, falling back to breaking between characters when a single word is
+    /// wider than the wrap width.
+    #[default]
+    Word,
+    /// Break between any two characters.
+    Char,
+    /// Never break, letting the line overflow.
+    None,
+}
+
 /// A line of text that has been wrapped to fit a given width
 #[derive(Default, Debug)]
 pub struct WrappedLineLayout {
@@ -333,9 +354,16 @@ impl WrappedLineLayout {
     }
 }
 
+/// The number of layouts of each kind kept after they've gone unused for a frame, before the
+/// least recently used ones are evicted.
+const RETAINED_LAYOUT_BUDGET: usize = 4096;
+
 pub(crate) struct LineLayoutCache {
     previous_frame: Mutex<FrameCache>,
     current_frame: RwLock<FrameCache>,
+    retained: Mutex<RetainedCache>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
     platform_text_system: Arc<dyn PlatformTextSystem>,
 }
 
@@ -347,6 +375,67 @@ struct FrameCache {
     used_wrapped_lines: Vec<Arc<CacheKey>>,
 }
 
+/// Layouts that went unused for a whole frame, each with a stamp that orders the layouts by when
+/// they were last used.
+#[derive(Default)]
+struct RetainedCache {
+    lines: FxHashMap<Arc<CacheKey>, (Arc<LineLayout>, u64)>,
+    wrapped_lines: FxHashMap<Arc<CacheKey>, (Arc<WrappedLineLayout>, u64)>,
+    stamp: u64,
+}
+
+impl RetainedCache {
+    fn clear(&mut self) {
+        self.lines.clear();
+        self.wrapped_lines.clear();
+    }
+
+    /// Retain the layouts of a frame that went unused in the frame after it, stamped in the order
+    /// they were last used in their frame. Layouts that were laid out but never used come first.
+    fn retain<V>(
+        entries: &mut FxHashMap<Arc<CacheKey>, (V, u64)>,
+        stamp: &mut u64,
+        unused: &mut FxHashMap<Arc<CacheKey>, V>,
+        used: &[Arc<CacheKey>],
+    ) {
+        let mut by_last_use = Vec::new();
+        for key in used.iter().rev() {
+            if let Some(entry) = unused.remove_entry(key) {
+                by_last_use.push(entry);
+            }
+        }
+        for (key, layout) in unused.drain().chain(by_last_use.into_iter().rev()) {
+            *stamp += 1;
+            entries.insert(key, (layout, *stamp));
+        }
+    }
+
+    /// Evict the least recently used layouts past the budget.
+    fn evict<V>(entries: &mut FxHashMap<Arc<CacheKey>, (V, u64)>) {
+        if entries.len() <= RETAINED_LAYOUT_BUDGET {
+            return;
+        }
+        let excess = entries.len() - RETAINED_LAYOUT_BUDGET;
+        let mut stamps = entries
+            .values()
+            .map(|(_, stamp)| *stamp)
+            .collect::<Vec<_>>();
+        let (_, cutoff, _) = stamps.select_nth_unstable(excess - 1);
+        let cutoff = *cutoff;
+        entries.retain(|_, (_, stamp)| *stamp > cutoff);
+    }
+}
+
+/// How often text layouts were found in a [`LineLayoutCache`] since the stats were last taken.
+/// A hit is any layout that didn't require shaping text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextLayoutCacheStats {
+    /// The number of layouts found in the cache.
+    pub hits: usize,
+    /// The number of layouts that had to be shaped.
+    pub misses: usize,
+}
+
 #[derive(Clone, Default)]
 pub(crate) struct LineLayoutIndex {
     lines_index: usize,
@@ -358,10 +447,33 @@ impl LineLayoutCache {
         Self {
             previous_frame: Mutex::default(),
             current_frame: RwLock::default(),
+            retained: Mutex::default(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
             platform_text_system,
         }
     }
 
+    /// Return the hit and miss counts since the last call, and reset them.
+    pub fn take_stats(&self) -> TextLayoutCacheStats {
+        TextLayoutCacheStats {
+            hits: self.hits.swap(0, SeqCst),
+            misses: self.misses.swap(0, SeqCst),
+        }
+    }
+
+    /// Drop every cached layout, for when the layouts may have changed, e.g. because fonts
+    /// were added.
+    pub fn clear(&self) {
+        let mut prev_frame = self.previous_frame.lock();
+        let mut curr_frame = self.current_frame.write();
+        prev_frame.lines.clear();
+        prev_frame.wrapped_lines.clear();
+        curr_frame.lines.clear();
+        curr_frame.wrapped_lines.clear();
+        self.retained.lock().clear();
+    }
+
     pub fn layout_index(&self) -> LineLayoutIndex {
         let frame = self.current_frame.read();
         LineLayoutIndex {
@@ -403,8 +515,27 @@ impl LineLayoutCache {
         let mut prev_frame = self.previous_frame.lock();
         let mut curr_frame = self.current_frame.write();
         std::mem::swap(&mut *prev_frame, &mut *curr_frame);
-        curr_frame.lines.clear();
-        curr_frame.wrapped_lines.clear();
+
+        // Layouts from the frame before last that weren't used in the last frame are retained
+        // rather than dropped, so text that comes back after a while needn't be reshaped.
+        let mut retained = self.retained.lock();
+        let retained = &mut *retained;
+        let curr_frame = &mut *curr_frame;
+        RetainedCache::retain(
+            &mut retained.lines,
+            &mut retained.stamp,
+            &mut curr_frame.lines,
+            &curr_frame.used_lines,
+        );
+        RetainedCache::retain(
+            &mut retained.wrapped_lines,
+            &mut retained.stamp,
+            &mut curr_frame.wrapped_lines,
+            &curr_frame.used_wrapped_lines,
+        );
+        RetainedCache::evict(&mut retained.lines);
+        RetainedCache::evict(&mut retained.wrapped_lines);
+
         curr_frame.used_lines.clear();
         curr_frame.used_wrapped_lines.clear();
     }
@@ -415,21 +546,34 @@ impl LineLayoutCache {
         font_size: Pixels,
         runs: &[FontRun],
         wrap_width: Option<Pixels>,
+        wrap_mode: WrapMode,
     ) -> Arc<WrappedLineLayout> {
+        let wrap_width = wrap_width.filter(|_| wrap_mode != WrapMode::None);
         let key = &CacheKeyRef {
             text,
             font_size,
             runs,
             wrap_width,
+            wrap_mode,
         } as &dyn AsCacheKeyRef;
 
         let current_frame = self.current_frame.upgradable_read();
         if let Some(layout) = current_frame.wrapped_lines.get(key) {
+            self.hits.fetch_add(1, SeqCst);
             return layout.clone();
         }
 
-        let previous_frame_entry = self.previous_frame.lock().wrapped_lines.remove_entry(key);
+        let previous_frame_entry = self
+            .previous_frame
+            .lock()
+            .wrapped_lines
+            .remove_entry(key)
+            .or_else(|| {
+                let (key, (layout, _)) = self.retained.lock().wrapped_lines.remove_entry(key)?;
+                Some((key, layout))
+            });
         if let Some((key, layout)) = previous_frame_entry {
+            self.hits.fetch_add(1, SeqCst);
             let mut current_frame = RwLockUpgradableReadGuard::upgrade(current_frame);
             current_frame
                 .wrapped_lines
@@ -439,9 +583,10 @@ impl LineLayoutCache {
         } else {
             drop(current_frame);
 
+            // Only count a miss if the line itself needs shaping, rewrapping it is cheap.
             let unwrapped_layout = self.layout_line(text, font_size, runs);
             let wrap_boundaries = if let Some(wrap_width) = wrap_width {
-                unwrapped_layout.compute_wrap_boundaries(text.as_ref(), wrap_width)
+                unwrapped_layout.compute_wrap_boundaries(text.as_ref(), wrap_width, wrap_mode)
             } else {
                 SmallVec::new()
             };
@@ -455,6 +600,7 @@ impl LineLayoutCache {
                 font_size,
                 runs: SmallVec::from(runs),
                 wrap_width,
+                wrap_mode,
             });
 
             let mut current_frame = self.current_frame.write();
@@ -473,25 +619,39 @@ impl LineLayoutCache {
             font_size,
             runs,
             wrap_width: None,
+            wrap_mode: WrapMode::default(),
         } as &dyn AsCacheKeyRef;
 
         let current_frame = self.current_frame.upgradable_read();
         if let Some(layout) = current_frame.lines.get(key) {
+            self.hits.fetch_add(1, SeqCst);
             return layout.clone();
         }
 
         let mut current_frame = RwLockUpgradableReadGuard::upgrade(current_frame);
-        if let Some((key, layout)) = self.previous_frame.lock().lines.remove_entry(key) {
+        let previous_frame_entry =
+            self.previous_frame
+                .lock()
+                .lines
+                .remove_entry(key)
+                .or_else(|| {
+                    let (key, (layout, _)) = self.retained.lock().lines.remove_entry(key)?;
+                    Some((key, layout))
+                });
+        if let Some((key, layout)) = previous_frame_entry {
+            self.hits.fetch_add(1, SeqCst);
             current_frame.lines.insert(key.clone(), layout.clone());
             current_frame.used_lines.push(key);
             layout
         } else {
+            self.misses.fetch_add(1, SeqCst);
             let layout = Arc::new(self.platform_text_system.layout_line(text, font_size, runs));
             let key = Arc::new(CacheKey {
                 text: text.into(),
                 font_size,
                 runs: SmallVec::from(runs),
                 wrap_width: None,
+                wrap_mode: WrapMode::default(),
             });
             current_frame.lines.insert(key.clone(), layout.clone());
             current_frame.used_lines.push(key);
@@ -517,6 +677,7 @@ struct CacheKey {
     font_size: Pixels,
     runs: SmallVec<[FontRun; 1]>,
     wrap_width: Option<Pixels>,
+    wrap_mode: WrapMode,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
@@ -525,6 +686,7 @@ struct CacheKeyRef<'a> {
     font_size: Pixels,
     runs: &'a [FontRun],
     wrap_width: Option<Pixels>,
+    wrap_mode: WrapMode,
 }
 
 impl<'a> PartialEq for (dyn AsCacheKeyRef + 'a) {
@@ -548,6 +710,7 @@ impl AsCacheKeyRef for CacheKey {
             font_size: self.font_size,
             runs: self.runs.as_slice(),
             wrap_width: self.wrap_width,
+            wrap_mode: self.wrap_mode,
         }
     }
 }
@@ -575,3 +738,159 @@ impl<'a> AsCacheKeyRef for CacheKeyRef<'a> {
         *self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as gpui, div, font, px, IntoElement, ParentElement, Render, TestAppContext,
+        TestDispatcher, ViewContext, VisualTestContext, WindowTextSystem,
+    };
+    use rand::prelude::*;
+
+    fn text_system() -> (TestAppContext, FontId) {
+        let dispatcher = TestDispatcher::new(StdRng::seed_from_u64(0));
+        let cx = TestAppContext::new(dispatcher, None);
+        cx.text_system()
+            .add_fonts(vec![std::fs::read(
+                "../../assets/fonts/plex-mono/ZedPlexMono-Regular.ttf",
+            )
+            .unwrap()
+            .into()])
+            .unwrap();
+        let font_id = cx.text_system().font_id(&font("Zed Plex Mono")).unwrap();
+        (cx, font_id)
+    }
+
+    fn layout(cache: &LineLayoutCache, text: &str, font_id: FontId) {
+        let runs = [FontRun {
+            len: text.len(),
+            font_id,
+        }];
+        cache.layout_line(text, px(16.), &runs);
+    }
+
+    #[test]
+    fn test_layouts_are_retained_across_frames() {
+        let (cx, font_id) = text_system();
+        let cache = LineLayoutCache::new(cx.text_system().platform_text_system.clone());
+
+        layout(&cache, "retained", font_id);
+        for _ in 0..10 {
+            cache.finish_frame();
+        }
+        cache.take_stats();
+
+        layout(&cache, "retained", font_id);
+        assert_eq!(
+            cache.take_stats(),
+            TextLayoutCacheStats { hits: 1, misses: 0 }
+        );
+    }
+
+    #[test]
+    fn test_least_recently_used_layouts_are_evicted_past_the_budget() {
+        let (cx, font_id) = text_system();
+        let cache = LineLayoutCache::new(cx.text_system().platform_text_system.clone());
+
+        for ix in 0..RETAINED_LAYOUT_BUDGET {
+            layout(&cache, &format!("old {ix}"), font_id);
+        }
+        cache.finish_frame();
+        layout(&cache, "new", font_id);
+        // The old layouts went unused for a frame and are retained, which fills the budget.
+        cache.finish_frame();
+        assert_eq!(cache.retained.lock().lines.len(), RETAINED_LAYOUT_BUDGET);
+
+        // Retaining one more evicts only the layout that was used the longest ago.
+        cache.finish_frame();
+        assert_eq!(cache.retained.lock().lines.len(), RETAINED_LAYOUT_BUDGET);
+        cache.take_stats();
+        layout(&cache, "new", font_id);
+        layout(&cache, "old 1", font_id);
+        layout(&cache, "old 0", font_id);
+        assert_eq!(
+            cache.take_stats(),
+            TextLayoutCacheStats { hits: 2, misses: 1 }
+        );
+
+        // Unrelated layouts coming and going keep the retained layouts at the budget, evicting
+        // the old layouts that haven't been used since.
+        for ix in 0..10 {
+            layout(&cache, &format!("unrelated {ix}"), font_id);
+            cache.finish_frame();
+        }
+        cache.finish_frame();
+        cache.finish_frame();
+        assert_eq!(cache.retained.lock().lines.len(), RETAINED_LAYOUT_BUDGET);
+        cache.take_stats();
+        layout(&cache, "old 1", font_id);
+        layout(&cache, "unrelated 0", font_id);
+        layout(&cache, "old 2", font_id);
+        assert_eq!(
+            cache.take_stats(),
+            TextLayoutCacheStats { hits: 2, misses: 1 }
+        );
+    }
+
+    #[test]
+    fn test_layouts_are_dropped_when_fonts_change() {
+        let (cx, font_id) = text_system();
+        let text_system = WindowTextSystem::new(cx.text_system().clone());
+
+        layout(&text_system.line_layout_cache, "font", font_id);
+        text_system.finish_frame();
+        text_system.take_layout_cache_stats();
+        layout(&text_system.line_layout_cache, "font", font_id);
+        assert_eq!(text_system.take_layout_cache_stats().misses, 0);
+
+        cx.text_system()
+            .add_fonts(vec![std::fs::read(
+                "../../assets/fonts/plex-mono/ZedPlexMono-Bold.ttf",
+            )
+            .unwrap()
+            .into()])
+            .unwrap();
+        text_system.finish_frame();
+        layout(&text_system.line_layout_cache, "font", font_id);
+        assert_eq!(text_system.take_layout_cache_stats().misses, 1);
+    }
+
+    struct Label;
+
+    impl Render for Label {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().child("Hello")
+        }
+    }
+
+    #[gpui::test]
+    fn test_layouts_are_dropped_when_the_scale_factor_changes(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| Label);
+        cx.update(|cx| cx.frame_diagnostics_mut().set_enabled(true));
+        let frame_count =
+            |cx: &mut VisualTestContext| cx.update(|cx| cx.frame_diagnostics().frames().len());
+        let misses_since = |first_frame: usize, cx: &mut VisualTestContext| {
+            cx.update(|cx| {
+                cx.frame_diagnostics()
+                    .frames()
+                    .skip(first_frame)
+                    .map(|frame| frame.text_layouts.misses)
+                    .sum::<usize>()
+            })
+        };
+
+        cx.update(|cx| cx.refresh());
+        cx.run_until_parked();
+        let first_frame = frame_count(cx);
+        cx.update(|cx| cx.refresh());
+        cx.run_until_parked();
+        assert_eq!(misses_since(first_frame, cx), 0);
+
+        let first_frame = frame_count(cx);
+        cx.simulate_scale_factor(1.);
+        cx.update(|cx| cx.refresh());
+        cx.run_until_parked();
+        assert!(misses_since(first_frame, cx) > 0);
+    }
+}
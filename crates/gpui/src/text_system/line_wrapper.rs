@@ -143,9 +143,9 @@ impl Boundary {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{font, TestAppContext, TestDispatcher};
     #[cfg(target_os = "macos")]
-    use crate::{TextRun, WindowTextSystem, WrapBoundary};
+    use crate::WrapBoundary;
+    use crate::{font, TestAppContext, TestDispatcher, TextRun, WindowTextSystem, WrapMode};
     use rand::prelude::*;
 
     #[test]
@@ -219,6 +219,53 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_wrap_modes() {
+        let dispatcher = TestDispatcher::new(StdRng::seed_from_u64(0));
+        let cx = TestAppContext::new(dispatcher, None);
+        cx.text_system()
+            .add_fonts(vec![std::fs::read(
+                "../../assets/fonts/plex-mono/ZedPlexMono-Regular.ttf",
+            )
+            .unwrap()
+            .into()])
+            .unwrap();
+
+        cx.update(|cx| {
+            let text_system = WindowTextSystem::new(cx.text_system().clone());
+            let text = "aa bbb cccc ddddd eeee";
+            let run = TextRun {
+                len: text.len(),
+                font: font("Zed Plex Mono"),
+                color: Default::default(),
+                underline: Default::default(),
+                strikethrough: None,
+                background_color: None,
+            };
+            let wrap_boundaries = |wrap_mode| {
+                let lines = text_system
+                    .shape_text(
+                        text.into(),
+                        px(16.),
+                        &[run.clone()],
+                        Some(px(72.)),
+                        wrap_mode,
+                    )
+                    .unwrap();
+                lines[0]
+                    .layout
+                    .wrap_boundaries()
+                    .iter()
+                    .map(|boundary| boundary.glyph_ix)
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(wrap_boundaries(WrapMode::Word), &[7, 12, 18]);
+            assert_eq!(wrap_boundaries(WrapMode::Char), &[7, 14, 21]);
+            assert_eq!(wrap_boundaries(WrapMode::None), &[] as &[usize]);
+        });
+    }
+
     // For compatibility with the test macro
     #[cfg(target_os = "macos")]
     use crate as gpui;
@@ -268,6 +315,7 @@ mod tests {
                         normal.with_len(7),
                     ],
                     Some(px(72.)),
+                    WrapMode::Word,
                 )
                 .unwrap();
 
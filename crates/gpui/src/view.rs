@@ -34,6 +34,7 @@ struct ViewCacheKey {
     bounds: Bounds<Pixels>,
     content_mask: ContentMask<Pixels>,
     text_style: TextStyle,
+    rem_size: Pixels,
 }
 
 impl<V: 'static> Entity<V> for View<V> {
@@ -103,8 +104,12 @@ impl<V: Render> Element for View<V> {
         _id: Option<&GlobalElementId>,
         cx: &mut WindowContext,
     ) -> (LayoutId, Self::RequestLayoutState) {
+        cx.window.frame_diagnostics.begin_view();
         let mut element = self.update(cx, |view, cx| view.render(cx).into_any_element());
         let layout_id = element.request_layout(cx);
+        cx.window
+            .frame_diagnostics
+            .end_view(self.entity_id(), type_name::<V>());
         (layout_id, element)
     }
 
@@ -116,7 +121,11 @@ impl<V: Render> Element for View<V> {
         cx: &mut WindowContext,
     ) {
         cx.set_view_id(self.entity_id());
+        cx.window.frame_diagnostics.begin_view();
         element.prepaint(cx);
+        cx.window
+            .frame_diagnostics
+            .end_view(self.entity_id(), type_name::<V>());
     }
 
     fn paint(
@@ -127,7 +136,15 @@ impl<V: Render> Element for View<V> {
         _: &mut Self::PrepaintState,
         cx: &mut WindowContext,
     ) {
+        cx.window.frame_diagnostics.begin_view();
         element.paint(cx);
+        cx.window
+            .frame_diagnostics
+            .end_view(self.entity_id(), type_name::<V>());
+    }
+
+    fn cullable(&self) -> bool {
+        false
     }
 }
 
@@ -225,6 +242,7 @@ impl<V> Eq for WeakView<V> {}
 #[derive(Clone, Debug)]
 pub struct AnyView {
     model: AnyModel,
+    type_name: &'static str,
     render: fn(&AnyView, &mut WindowContext) -> AnyElement,
     cached_style: Option<StyleRefinement>,
 }
@@ -242,6 +260,7 @@ impl AnyView {
     pub fn downgrade(&self) -> AnyWeakView {
         AnyWeakView {
             model: self.model.downgrade(),
+            type_name: self.type_name,
             render: self.render,
         }
     }
@@ -274,6 +293,7 @@ impl<V: Render> From<View<V>> for AnyView {
     fn from(value: View<V>) -> Self {
         AnyView {
             model: value.model.into_any(),
+            type_name: type_name::<V>(),
             render: any_view::render::<V>,
             cached_style: None,
         }
@@ -307,8 +327,12 @@ impl Element for AnyView {
             let layout_id = cx.request_layout(root_style, None);
             (layout_id, None)
         } else {
+            cx.window.frame_diagnostics.begin_view();
             let mut element = (self.render)(self, cx);
             let layout_id = element.request_layout(cx);
+            cx.window
+                .frame_diagnostics
+                .end_view(self.entity_id(), self.type_name);
             (layout_id, Some(element))
         }
     }
@@ -321,15 +345,18 @@ impl Element for AnyView {
         cx: &mut WindowContext,
     ) -> Option<AnyElement> {
         cx.set_view_id(self.entity_id());
-        if self.cached_style.is_some() {
+        cx.window.frame_diagnostics.begin_view();
+        let element = if self.cached_style.is_some() {
             cx.with_element_state::<AnyViewState, _>(global_id.unwrap(), |element_state, cx| {
                 let content_mask = cx.content_mask();
                 let text_style = cx.text_style();
+                let rem_size = cx.rem_size();
 
                 if let Some(mut element_state) = element_state {
                     if element_state.cache_key.bounds == bounds
                         && element_state.cache_key.content_mask == content_mask
                         && element_state.cache_key.text_style == text_style
+                        && element_state.cache_key.rem_size == rem_size
                         && !cx.window.dirty_views.contains(&self.entity_id())
                         && !cx.window.refreshing
                     {
@@ -356,6 +383,7 @@ impl Element for AnyView {
                             bounds,
                             content_mask,
                             text_style,
+                            rem_size,
                         },
                     },
                 )
@@ -364,7 +392,11 @@ impl Element for AnyView {
             let mut element = element.take().unwrap();
             element.prepaint(cx);
             Some(element)
-        }
+        };
+        cx.window
+            .frame_diagnostics
+            .end_view(self.entity_id(), self.type_name);
+        element
     }
 
     fn paint(
@@ -375,6 +407,7 @@ impl Element for AnyView {
         element: &mut Self::PrepaintState,
         cx: &mut WindowContext,
     ) {
+        cx.window.frame_diagnostics.begin_view();
         if self.cached_style.is_some() {
             cx.with_element_state::<AnyViewState, _>(global_id.unwrap(), |element_state, cx| {
                 let mut element_state = element_state.unwrap();
@@ -395,6 +428,13 @@ impl Element for AnyView {
         } else {
             element.as_mut().unwrap().paint(cx);
         }
+        cx.window
+            .frame_diagnostics
+            .end_view(self.entity_id(), self.type_name);
+    }
+
+    fn cullable(&self) -> bool {
+        false
     }
 }
 
@@ -417,6 +457,7 @@ impl IntoElement for AnyView {
 /// A weak, dynamically-typed view handle that does not prevent the view from being released.
 pub struct AnyWeakView {
     model: AnyWeakModel,
+    type_name: &'static str,
     render: fn(&AnyView, &mut WindowContext) -> AnyElement,
 }
 
@@ -426,6 +467,7 @@ impl AnyWeakView {
         let model = self.model.upgrade()?;
         Some(AnyView {
             model,
+            type_name: self.type_name,
             render: self.render,
             cached_style: None,
         })
@@ -436,6 +478,7 @@ impl<V: 'static + Render> From<WeakView<V>> for AnyWeakView {
     fn from(view: WeakView<V>) -> Self {
         Self {
             model: view.model.into(),
+            type_name: type_name::<V>(),
             render: any_view::render::<V>,
         }
     }
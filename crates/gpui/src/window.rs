@@ -2,18 +2,18 @@ use crate::{
     hash, point, prelude::*, px, size, transparent_black, Action, AnyDrag, AnyElement, AnyTooltip,
     AnyView, AppContext, Arena, Asset, AsyncWindowContext, AvailableSpace, Bounds, BoxShadow,
     Context, Corners, CursorStyle, DevicePixels, DispatchActionListener, DispatchNodeId,
-    DispatchTree, DisplayId, Edges, Effect, Entity, EntityId, EventEmitter, FileDropEvent, Flatten,
-    FontId, Global, GlobalElementId, GlyphId, Hsla, ImageData, InputHandler, IsZero, KeyBinding,
-    KeyContext, KeyDownEvent, KeyEvent, KeyMatch, KeymatchResult, Keystroke, KeystrokeEvent,
-    LayoutId, LineLayoutIndex, Model, ModelContext, Modifiers, ModifiersChangedEvent,
-    MonochromeSprite, MouseButton, MouseEvent, MouseMoveEvent, MouseUpEvent, Path, Pixels,
-    PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow, Point,
-    PolychromeSprite, PromptLevel, Quad, Render, RenderGlyphParams, RenderImageParams,
-    RenderSvgParams, ScaledPixels, Scene, Shadow, SharedString, Size, StrikethroughStyle, Style,
-    SubscriberSet, Subscription, TaffyLayoutEngine, Task, TextStyle, TextStyleRefinement,
-    TransformationMatrix, Underline, UnderlineStyle, View, VisualContext, WeakView,
-    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowOptions, WindowParams,
-    WindowTextSystem, SUBPIXEL_VARIANTS,
+    DispatchTree, DisplayId, Edges, Effect, ElevationLevel, ElevationStyle, ElevationStyles,
+    Entity, EntityId, EventEmitter, FileDropEvent, Flatten, FontId, Global, GlobalElementId,
+    GlyphId, Hsla, ImageData, ImageLoad, InputHandler, IsZero, KeyBinding, KeyContext,
+    KeyDownEvent, KeyEvent, KeyMatch, KeymatchResult, Keystroke, KeystrokeEvent, LayoutId,
+    LineLayoutIndex, Model, ModelContext, Modifiers, ModifiersChangedEvent, MonochromeSprite,
+    MouseButton, MouseEvent, MouseMoveEvent, MouseUpEvent, Path, Pixels, PlatformAtlas,
+    PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow, Point, PolychromeSprite,
+    PromptLevel, Quad, Render, RenderGlyphParams, RenderImageParams, RenderSvgParams, ScaledPixels,
+    Scene, Shadow, SharedString, Size, StrikethroughStyle, Style, SubscriberSet, Subscription,
+    TaffyLayoutEngine, Task, TextStyle, TextStyleRefinement, TransformationMatrix, Underline,
+    UnderlineStyle, View, VisualContext, WeakView, WindowAppearance, WindowBackgroundAppearance,
+    WindowBounds, WindowOptions, WindowParams, WindowTextSystem, SUBPIXEL_VARIANTS,
 };
 use anyhow::{anyhow, Context as _, Result};
 use collections::{FxHashMap, FxHashSet};
@@ -48,9 +48,33 @@ use util::post_inc;
 use util::{measure, ResultExt};
 use uuid::Uuid;
 
+mod damage;
+#[cfg(debug_assertions)]
+mod element_ids;
+mod frame_diagnostics;
+mod frame_scheduler;
+mod hover_scheduler;
+mod interactive_regions;
+mod layout_snapshots;
+mod motion;
+mod mounts;
+mod native_views;
 mod prompts;
-
+mod window_globals;
+
+pub use damage::*;
+#[cfg(debug_assertions)]
+pub(crate) use element_ids::*;
+pub use frame_diagnostics::*;
+pub use frame_scheduler::*;
+pub use hover_scheduler::*;
+pub use interactive_regions::*;
+pub use layout_snapshots::*;
+pub use motion::*;
+pub use mounts::*;
+pub use native_views::*;
 pub use prompts::*;
+pub use window_globals::*;
 
 pub(crate) const DEFAULT_WINDOW_SIZE: Size<Pixels> = size(px(1024.), px(700.));
 
@@ -83,11 +107,34 @@ impl DispatchPhase {
     }
 }
 
+/// The kind of device a window last received input from, see [`WindowContext::input_modality`].
+#[derive(Default, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InputModality {
+    /// The mouse or trackpad was last used to press, move or scroll.
+    #[default]
+    Pointer,
+    /// A key was last pressed.
+    Keyboard,
+}
+
 type AnyObserver = Box<dyn FnMut(&mut WindowContext) -> bool + 'static>;
 
 type AnyWindowFocusListener =
     Box<dyn FnMut(&WindowFocusEvent, &mut WindowContext) -> bool + 'static>;
 
+type AnyElementBoundsObserver =
+    Box<dyn FnMut(&BoundsChangeEvent, &mut WindowContext) -> bool + 'static>;
+
+/// How many consecutive frames an observed element's bounds can flip back and forth before we warn
+/// that its observer is probably changing them.
+const BOUNDS_OSCILLATION_LIMIT: usize = 8;
+
+/// The size of a rem in a window whose UI isn't scaled.
+pub const DEFAULT_REM_SIZE: Pixels = px(16.);
+
+/// The z-index of the prompts, dragged elements and tooltips the window draws above its root view.
+pub const TOP_Z_INDEX: u8 = u8::MAX;
+
 struct WindowFocusEvent {
     previous_focus_path: SmallVec<[FocusId; 8]>,
     current_focus_path: SmallVec<[FocusId; 8]>,
@@ -103,6 +150,24 @@ impl WindowFocusEvent {
     }
 }
 
+/// This is provided when subscribing for `ViewContext::observe_bounds` events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundsChangeEvent {
+    /// The id of the element whose bounds changed.
+    pub element_id: ElementId,
+    /// The bounds of the element in the previous frame, if it was painted in that frame.
+    pub old_bounds: Option<Bounds<Pixels>>,
+    /// The bounds of the element in the frame that was just drawn.
+    pub new_bounds: Bounds<Pixels>,
+}
+
+/// The bounds recorded for an observed element, used to detect changes between frames.
+struct ObservedBounds {
+    bounds: Bounds<Pixels>,
+    previous_bounds: Option<Bounds<Pixels>>,
+    oscillations: usize,
+}
+
 /// This is provided when subscribing for `ViewContext::on_focus_out` events.
 pub struct FocusOutEvent {
     /// A weak focus handle representing what was blurred.
@@ -311,9 +376,35 @@ pub struct DismissEvent;
 
 type FrameCallback = Box<dyn FnOnce(&mut WindowContext)>;
 
+/// A value provided to a subtree of elements, see [`WindowContext::with_context`].
+pub(crate) type ProvidedContext = (TypeId, Rc<dyn Any>);
+
 pub(crate) type AnyMouseListener =
     Box<dyn FnMut(&dyn Any, DispatchPhase, &mut WindowContext) + 'static>;
 
+pub(crate) type AnyViewportChangeListener = Rc<dyn Fn(&ViewportChangeEvent, &mut WindowContext)>;
+
+/// The window's viewport was resized or moved to a display with a different scale factor, see
+/// [`WindowContext::on_viewport_change`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewportChangeEvent {
+    /// The size of the viewport when the listeners were last notified.
+    pub previous_size: Size<Pixels>,
+    /// The new size of the viewport.
+    pub size: Size<Pixels>,
+    /// The scale factor when the listeners were last notified.
+    pub previous_scale_factor: f32,
+    /// The new scale factor.
+    pub scale_factor: f32,
+}
+
+impl ViewportChangeEvent {
+    /// Whether the scale factor changed, so anything rasterized for the window is out of date.
+    pub fn scale_factor_changed(&self) -> bool {
+        self.scale_factor != self.previous_scale_factor
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct CursorStyleRequest {
     pub(crate) hitbox_id: HitboxId,
@@ -344,6 +435,12 @@ pub struct Hitbox {
     pub content_mask: ContentMask<Pixels>,
     /// Whether the hitbox occludes other hitboxes inserted prior.
     pub opaque: bool,
+    /// The z-index when the hitbox was inserted. Hitboxes with a higher z-index are hit before
+    /// those with a lower one, wherever they were inserted.
+    pub z_index: u8,
+    /// How far beyond each edge of the bounds the hitbox can also be hit, for small controls
+    /// that would otherwise be hard to click. See [WindowContext::insert_hitbox_with_slop].
+    pub slop: Edges<Pixels>,
 }
 
 impl Hitbox {
@@ -351,6 +448,35 @@ impl Hitbox {
     pub fn is_hovered(&self, cx: &WindowContext) -> bool {
         self.id.is_hovered(cx)
     }
+
+    /// The bounds of the hitbox expanded by its slop.
+    pub fn slop_bounds(&self) -> Bounds<Pixels> {
+        Bounds::from_corners(
+            point(
+                self.bounds.left() - self.slop.left,
+                self.bounds.top() - self.slop.top,
+            ),
+            point(
+                self.bounds.right() + self.slop.right,
+                self.bounds.bottom() + self.slop.bottom,
+            ),
+        )
+    }
+
+    fn has_slop(&self) -> bool {
+        self.slop.max() > Pixels::ZERO
+    }
+
+    /// The squared distance from the position to the nearest point of the bounds.
+    fn distance_squared(&self, position: Point<Pixels>) -> f32 {
+        let dx = (self.bounds.left() - position.x)
+            .max(position.x - self.bounds.right())
+            .max(Pixels::ZERO);
+        let dy = (self.bounds.top() - position.y)
+            .max(position.y - self.bounds.bottom())
+            .max(Pixels::ZERO);
+        dx.0 * dx.0 + dy.0 * dy.0
+    }
 }
 
 #[derive(Default, Eq, PartialEq)]
@@ -388,10 +514,15 @@ pub(crate) struct DeferredDraw {
     parent_node: DispatchNodeId,
     element_id_stack: SmallVec<[ElementId; 32]>,
     text_style_stack: Vec<TextStyleRefinement>,
+    provided_context_stack: Vec<ProvidedContext>,
+    z_index: u8,
     element: Option<AnyElement>,
     absolute_offset: Point<Pixels>,
     prepaint_range: Range<PrepaintStateIndex>,
     paint_range: Range<PaintIndex>,
+    /// The element that deferred the draw, which is credited with what it paints in the frame
+    /// diagnostics.
+    paint_source: Option<PaintSource>,
 }
 
 pub(crate) struct Frame {
@@ -407,6 +538,19 @@ pub(crate) struct Frame {
     pub(crate) input_handlers: Vec<Option<PlatformInputHandler>>,
     pub(crate) tooltip_requests: Vec<Option<TooltipRequest>>,
     pub(crate) cursor_styles: Vec<CursorStyleRequest>,
+    pub(crate) observed_element_bounds: Vec<(GlobalElementId, Bounds<Pixels>)>,
+    pub(crate) dismissables: Vec<DismissableRegion>,
+    pub(crate) viewport_change_listeners: Vec<AnyViewportChangeListener>,
+    pub(crate) delayed_hovers: Vec<DelayedHover>,
+    pub(crate) interactive_regions: Vec<InteractiveRegion>,
+    pub(crate) recorded_layouts: Vec<RecordedLayout>,
+    pub(crate) native_views: Vec<PaintedNativeView>,
+    /// The images that were asked for while painting and are still loading, which keeps them
+    /// loading.
+    pub(crate) image_loads: Vec<Rc<ImageLoad>>,
+    /// The element id stacks of the elements that weren't painted because they were out of
+    /// view. The state of the elements drawn within them is kept for the next frame.
+    culled_elements: Vec<GlobalElementId>,
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) debug_bounds: FxHashMap<String, Bounds<Pixels>>,
 }
@@ -427,6 +571,15 @@ pub(crate) struct PaintIndex {
     mouse_listeners_index: usize,
     input_handlers_index: usize,
     cursor_styles_index: usize,
+    observed_element_bounds_index: usize,
+    dismissables_index: usize,
+    viewport_change_listeners_index: usize,
+    delayed_hovers_index: usize,
+    interactive_regions_index: usize,
+    recorded_layouts_index: usize,
+    native_views_index: usize,
+    image_loads_index: usize,
+    culled_elements_index: usize,
     accessed_element_states_index: usize,
     line_layout_index: LineLayoutIndex,
 }
@@ -446,6 +599,15 @@ impl Frame {
             input_handlers: Vec::new(),
             tooltip_requests: Vec::new(),
             cursor_styles: Vec::new(),
+            observed_element_bounds: Vec::new(),
+            dismissables: Vec::new(),
+            viewport_change_listeners: Vec::new(),
+            delayed_hovers: Vec::new(),
+            interactive_regions: Vec::new(),
+            recorded_layouts: Vec::new(),
+            native_views: Vec::new(),
+            image_loads: Vec::new(),
+            culled_elements: Vec::new(),
 
             #[cfg(any(test, feature = "test-support"))]
             debug_bounds: FxHashMap::default(),
@@ -461,22 +623,74 @@ impl Frame {
         self.input_handlers.clear();
         self.tooltip_requests.clear();
         self.cursor_styles.clear();
+        self.observed_element_bounds.clear();
+        self.dismissables.clear();
+        self.viewport_change_listeners.clear();
+        self.delayed_hovers.clear();
+        self.interactive_regions.clear();
+        self.recorded_layouts.clear();
+        self.native_views.clear();
+        self.image_loads.clear();
+        self.culled_elements.clear();
         self.hitboxes.clear();
         self.deferred_draws.clear();
     }
 
     pub(crate) fn hit_test(&self, position: Point<Pixels>) -> HitTest {
-        let mut hit_test = HitTest::default();
-        for hitbox in self.hitboxes.iter().rev() {
-            let bounds = hitbox.bounds.intersect(&hitbox.content_mask.bounds);
-            if bounds.contains(&position) {
-                hit_test.0.push(hitbox.id);
-                if hitbox.opaque {
-                    break;
+        let mut z_indices = self
+            .hitboxes
+            .iter()
+            .map(|hitbox| hitbox.z_index)
+            .collect::<SmallVec<[_; 8]>>();
+        z_indices.sort_unstable();
+        z_indices.dedup();
+
+        let mut hits = SmallVec::<[&Hitbox; 8]>::new();
+        // Hitboxes whose slop contains the position but whose bounds don't, along with where
+        // they'd be placed among the hits.
+        let mut slop_hits = SmallVec::<[(usize, &Hitbox); 4]>::new();
+        'z_indices: for z_index in z_indices.into_iter().rev() {
+            for hitbox in self.hitboxes.iter().rev() {
+                if hitbox.z_index != z_index {
+                    continue;
                 }
+                let mask = &hitbox.content_mask.bounds;
+                if hitbox.bounds.intersect(mask).contains(&position) {
+                    hits.push(hitbox);
+                    if hitbox.opaque {
+                        break 'z_indices;
+                    }
+                } else if hitbox.has_slop()
+                    && hitbox.slop_bounds().intersect(mask).contains(&position)
+                {
+                    slop_hits.push((hits.len(), hitbox));
+                }
+            }
+        }
+
+        // A hitbox whose bounds contain the position wins over the slop of others, unless it
+        // contains them too, like the background of a toolbar does its buttons. Among the
+        // remaining slop, the hitbox nearest to the position wins, then the front-most.
+        let slop_hit = slop_hits
+            .into_iter()
+            .filter(|(_, slop_hitbox)| {
+                hits.iter().all(|hitbox| {
+                    hitbox.bounds.contains(&slop_hitbox.bounds.origin)
+                        && hitbox.bounds.contains(&slop_hitbox.bounds.lower_right())
+                })
+            })
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            });
+        if let Some((ix, hitbox)) = slop_hit {
+            hits.insert(ix, hitbox);
+            if hitbox.opaque {
+                hits.truncate(ix + 1);
             }
         }
-        hit_test
+
+        HitTest(hits.into_iter().map(|hitbox| hitbox.id).collect())
     }
 
     pub(crate) fn focus_path(&self) -> SmallVec<[FocusId; 8]> {
@@ -494,6 +708,20 @@ impl Frame {
             }
         }
 
+        if !self.culled_elements.is_empty() {
+            let culled = self
+                .culled_elements
+                .iter()
+                .map(|id| id.0.as_slice())
+                .collect::<FxHashSet<_>>();
+            for (key, element_state) in prev_frame.element_states.drain() {
+                let ids = key.0 .0.as_slice();
+                if (0..=ids.len()).any(|len| culled.contains(&ids[..len])) {
+                    self.element_states.entry(key).or_insert(element_state);
+                }
+            }
+        }
+
         self.scene.finish();
     }
 }
@@ -514,19 +742,26 @@ pub struct Window {
     /// a given rem size.
     rem_size_override_stack: SmallVec<[Pixels; 8]>,
     pub(crate) viewport_size: Size<Pixels>,
+    /// The viewport size and scale factor when viewport change listeners were last notified.
+    notified_viewport: (Size<Pixels>, f32),
     layout_engine: Option<TaffyLayoutEngine>,
     pub(crate) root_view: Option<AnyView>,
     pub(crate) element_id_stack: SmallVec<[ElementId; 32]>,
     pub(crate) text_style_stack: Vec<TextStyleRefinement>,
+    pub(crate) provided_context_stack: Vec<ProvidedContext>,
     pub(crate) element_offset_stack: Vec<Point<Pixels>>,
     pub(crate) content_mask_stack: Vec<ContentMask<Pixels>>,
+    /// The z-index of the element being drawn, see [`WindowContext::with_z_index`].
+    z_index: u8,
+    /// The opacity the colors of the elements being painted are multiplied by.
+    element_opacity: Option<f32>,
     pub(crate) requested_autoscroll: Option<Bounds<Pixels>>,
     pub(crate) rendered_frame: Frame,
     pub(crate) next_frame: Frame,
     pub(crate) next_hitbox_id: HitboxId,
     pub(crate) next_tooltip_id: TooltipId,
     pub(crate) tooltip_bounds: Option<TooltipBounds>,
-    next_frame_callbacks: Rc<RefCell<Vec<FrameCallback>>>,
+    pub(crate) next_frame_callbacks: Rc<RefCell<Vec<FrameCallback>>>,
     pub(crate) dirty_views: FxHashSet<EntityId>,
     pub(crate) focus_handles: Arc<RwLock<SlotMap<FocusId, AtomicUsize>>>,
     focus_listeners: SubscriberSet<(), AnyWindowFocusListener>,
@@ -535,8 +770,11 @@ pub struct Window {
     mouse_position: Point<Pixels>,
     mouse_hit_test: HitTest,
     modifiers: Modifiers,
+    input_modality: InputModality,
     scale_factor: f32,
     bounds_observers: SubscriberSet<(), AnyObserver>,
+    element_bounds_observers: SubscriberSet<GlobalElementId, AnyElementBoundsObserver>,
+    observed_bounds: FxHashMap<GlobalElementId, ObservedBounds>,
     appearance: WindowAppearance,
     appearance_observers: SubscriberSet<(), AnyObserver>,
     active: Rc<Cell<bool>>,
@@ -552,6 +790,25 @@ pub struct Window {
     pending_modifier: ModifierState,
     pending_input_observers: SubscriberSet<(), AnyObserver>,
     prompt: Option<RenderablePromptHandle>,
+    pub(crate) toasts: ToastQueue,
+    pub(crate) frame_diagnostics: FrameDiagnostics,
+    frame_diagnostics_overlay: Option<MountId>,
+    frame_scheduler: FrameScheduler,
+    globals: WindowGlobals,
+    /// The size of the last scene painted, to tell whether the next one can be compared with it
+    /// for damage.
+    damaged_viewport: Option<Size<ScaledPixels>>,
+    damage_overlay: Option<MountId>,
+    pub(crate) damage_flash: Option<DamageFlash>,
+    damage_flash_task: Option<Task<()>>,
+    hover_scheduler: HoverScheduler,
+    mounts: Mounts,
+    interactive_regions: InteractiveRegions,
+    layout_snapshots: LayoutSnapshots,
+    native_views: NativeViews,
+    #[cfg(debug_assertions)]
+    element_ids: ElementIds,
+    motion: Motion,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -761,6 +1018,9 @@ impl Window {
                 handle
                     .update(&mut cx, |_, cx| {
                         cx.window.active.set(active);
+                        if active {
+                            cx.update_platform_motion_preference();
+                        }
                         cx.window
                             .activation_observers
                             .clone()
@@ -792,15 +1052,19 @@ impl Window {
             display_id,
             sprite_atlas,
             text_system,
-            rem_size: px(16.),
+            rem_size: DEFAULT_REM_SIZE,
             rem_size_override_stack: SmallVec::new(),
             viewport_size: content_size,
+            notified_viewport: (content_size, scale_factor),
             layout_engine: Some(TaffyLayoutEngine::new()),
             root_view: None,
             element_id_stack: SmallVec::default(),
             text_style_stack: Vec::new(),
+            provided_context_stack: Vec::new(),
             element_offset_stack: Vec::new(),
             content_mask_stack: Vec::new(),
+            z_index: 0,
+            element_opacity: None,
             requested_autoscroll: None,
             rendered_frame: Frame::new(DispatchTree::new(cx.keymap.clone(), cx.actions.clone())),
             next_frame: Frame::new(DispatchTree::new(cx.keymap.clone(), cx.actions.clone())),
@@ -816,8 +1080,11 @@ impl Window {
             mouse_position,
             mouse_hit_test: HitTest::default(),
             modifiers,
+            input_modality: InputModality::default(),
             scale_factor,
             bounds_observers: SubscriberSet::new(),
+            element_bounds_observers: SubscriberSet::new(),
+            observed_bounds: FxHashMap::default(),
             appearance,
             appearance_observers: SubscriberSet::new(),
             active,
@@ -833,6 +1100,23 @@ impl Window {
             pending_modifier: ModifierState::default(),
             pending_input_observers: SubscriberSet::new(),
             prompt: None,
+            toasts: ToastQueue::default(),
+            frame_diagnostics: FrameDiagnostics::default(),
+            frame_diagnostics_overlay: None,
+            frame_scheduler: FrameScheduler::default(),
+            globals: WindowGlobals::default(),
+            damaged_viewport: None,
+            damage_overlay: None,
+            damage_flash: None,
+            damage_flash_task: None,
+            hover_scheduler: HoverScheduler::default(),
+            mounts: Mounts::default(),
+            interactive_regions: InteractiveRegions::default(),
+            layout_snapshots: LayoutSnapshots::default(),
+            native_views: NativeViews::default(),
+            #[cfg(debug_assertions)]
+            element_ids: ElementIds::default(),
+            motion: Motion::new(cx.should_reduce_motion()),
         })
     }
     fn new_focus_listener(
@@ -1153,7 +1437,7 @@ impl<'a> WindowContext<'a> {
             .spawn(|app| f(AsyncWindowContext::new(app, self.window.handle)))
     }
 
-    fn bounds_changed(&mut self) {
+    pub(crate) fn bounds_changed(&mut self) {
         self.window.scale_factor = self.window.platform_window.scale_factor();
         self.window.viewport_size = self.window.platform_window.content_size();
         self.window.display_id = self
@@ -1180,6 +1464,13 @@ impl<'a> WindowContext<'a> {
         self.window.platform_window.is_fullscreen()
     }
 
+    /// Returns the insets from each edge of the window's content area that are covered by window
+    /// chrome, such as the macOS traffic lights. These change when the window enters or exits
+    /// fullscreen. See [`safe_area`](crate::safe_area) for an element that keeps clear of them.
+    pub fn window_insets(&self) -> Edges<Pixels> {
+        self.window.platform_window.window_insets()
+    }
+
     pub(crate) fn appearance_changed(&mut self) {
         self.window.appearance = self.window.platform_window.appearance();
 
@@ -1280,8 +1571,28 @@ impl<'a> WindowContext<'a> {
 
     /// Sets the size of an em for the base font of the application. Adjusting this value allows the
     /// UI to scale, just like zooming a web page.
+    ///
+    /// Rems are resolved when elements are laid out, so everything sized in rems reflows on the
+    /// next frame.
     pub fn set_rem_size(&mut self, rem_size: impl Into<Pixels>) {
-        self.window.rem_size = rem_size.into();
+        let rem_size = rem_size.into();
+        if self.window.rem_size != rem_size {
+            self.window.rem_size = rem_size;
+            if self.window.draw_phase == DrawPhase::None {
+                self.window.dirty.set(true);
+            }
+        }
+    }
+
+    /// How much the UI of this window is scaled up from the [`DEFAULT_REM_SIZE`].
+    pub fn ui_scale(&self) -> f32 {
+        self.window.rem_size / DEFAULT_REM_SIZE
+    }
+
+    /// Scales everything sized in rems by setting the rem size to a multiple of the
+    /// [`DEFAULT_REM_SIZE`]. Lengths given in pixels, such as hairline borders, keep their size.
+    pub fn set_ui_scale(&mut self, ui_scale: f32) {
+        self.set_rem_size(DEFAULT_REM_SIZE * ui_scale);
     }
 
     /// Executes the provided function with the specified rem size.
@@ -1356,6 +1667,12 @@ impl<'a> WindowContext<'a> {
         self.window.modifiers
     }
 
+    /// Whether the last input to the window came from the keyboard or from a pointer, which
+    /// decides whether [`focus_visible`](crate::FocusableElement::focus_visible) styles apply.
+    pub fn input_modality(&self) -> InputModality {
+        self.window.input_modality
+    }
+
     fn complete_frame(&self) {
         self.window.platform_window.completed_frame();
     }
@@ -1364,6 +1681,11 @@ impl<'a> WindowContext<'a> {
     /// the contents of the new [Scene], use [present].
     #[profiling::function]
     pub fn draw(&mut self) {
+        self.window.frame_diagnostics.begin_frame();
+        self.begin_layout_snapshot_frame();
+        #[cfg(debug_assertions)]
+        self.begin_element_id_frame();
+        self.dispatch_viewport_change();
         self.window.dirty.set(false);
         self.window.requested_autoscroll = None;
 
@@ -1396,6 +1718,12 @@ impl<'a> WindowContext<'a> {
         }
 
         self.window.layout_engine.as_mut().unwrap().clear();
+        let text_layouts = self.text_system().take_layout_cache_stats();
+        self.window
+            .frame_diagnostics
+            .record_text_layouts(text_layouts);
+        let images = self.image_cache.take_stats();
+        self.window.frame_diagnostics.record_images(images);
         self.text_system().finish_frame();
         self.window
             .next_frame
@@ -1448,13 +1776,209 @@ impl<'a> WindowContext<'a> {
         self.window.refreshing = false;
         self.window.draw_phase = DrawPhase::None;
         self.window.needs_present.set(true);
+        self.window.frame_diagnostics.end_frame();
+
+        self.sync_native_views();
+        self.snapshot_layouts();
+        self.notify_element_bounds_observers();
+        self.cancel_vanished_interactions();
+        self.update_delayed_hovers(false);
+    }
+
+    /// Compare the bounds of the observed elements in the frame that was just drawn with their
+    /// bounds in the previous frame, and notify the observers of the ones that changed in the
+    /// order the elements were painted. Any changes the observers make are drawn in the next frame.
+    fn notify_element_bounds_observers(&mut self) {
+        let mut previous_bounds = mem::take(&mut self.window.observed_bounds);
+        let mut changes = Vec::new();
+        for (element_id, bounds) in &self.window.rendered_frame.observed_element_bounds {
+            if self.window.observed_bounds.contains_key(element_id) {
+                continue;
+            }
+
+            let previous = previous_bounds.remove(element_id);
+            let local_id = element_id.last().unwrap();
+            let old_bounds = previous.as_ref().map(|previous| previous.bounds);
+            let mut oscillations = 0;
+            if old_bounds != Some(*bounds) {
+                if let Some(previous) = previous {
+                    if previous.previous_bounds == Some(*bounds) {
+                        oscillations = previous.oscillations + 1;
+                        if oscillations == BOUNDS_OSCILLATION_LIMIT {
+                            log::warn!(
+                                "the bounds of element {local_id} have alternated between two \
+                                values for {oscillations} frames, possibly because an observer of \
+                                its bounds keeps changing them"
+                            );
+                        }
+                    }
+                }
+                changes.push((
+                    element_id.clone(),
+                    BoundsChangeEvent {
+                        element_id: local_id.clone(),
+                        old_bounds,
+                        new_bounds: *bounds,
+                    },
+                ));
+            }
+
+            self.window.observed_bounds.insert(
+                element_id.clone(),
+                ObservedBounds {
+                    bounds: *bounds,
+                    previous_bounds: old_bounds,
+                    oscillations,
+                },
+            );
+        }
+
+        for (element_id, change) in changes {
+            self.window
+                .element_bounds_observers
+                .clone()
+                .retain(&element_id, |callback| callback(&change, self));
+        }
+    }
+
+    /// Notify the listeners registered with [`Self::on_viewport_change`] in the last frame if the
+    /// viewport changed since they were last notified. This happens before the frame is laid out,
+    /// and only once however many resizes happened since the last frame.
+    fn dispatch_viewport_change(&mut self) {
+        let (previous_size, previous_scale_factor) = self.window.notified_viewport;
+        let event = ViewportChangeEvent {
+            previous_size,
+            size: self.window.viewport_size,
+            previous_scale_factor,
+            scale_factor: self.window.scale_factor,
+        };
+        if event.size == previous_size && !event.scale_factor_changed() {
+            return;
+        }
+        self.window.notified_viewport = (event.size, event.scale_factor);
+
+        if event.scale_factor_changed() {
+            self.text_system().clear_layout_cache();
+            let window = self.window.handle;
+            self.image_cache.clear_rasterized_for(window);
+        }
+        for listener in self.window.rendered_frame.viewport_change_listeners.clone() {
+            listener(&event, self);
+        }
+    }
+
+    /// Register a listener to be called when the window's viewport is resized or its scale
+    /// factor changes, before the next frame is laid out. Elements can use this to drop data
+    /// they derived from the viewport.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn on_viewport_change(
+        &mut self,
+        listener: impl Fn(&ViewportChangeEvent, &mut WindowContext) + 'static,
+    ) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        self.window
+            .next_frame
+            .viewport_change_listeners
+            .push(Rc::new(listener));
+    }
+
+    /// Register a [`dismissable`](crate::dismissable) region. Regions painted later are above the
+    /// ones painted before them.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub(crate) fn insert_dismissable(&mut self, region: DismissableRegion) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        self.window.next_frame.dismissables.push(region);
+    }
+
+    /// Dismiss the topmost dismissable region, unless `position` is given and is inside it or
+    /// any of the regions nested within it. Returns whether a region was dismissed.
+    fn dismiss_topmost(&mut self, position: Option<Point<Pixels>>) -> bool {
+        let dismissables = &self.window.rendered_frame.dismissables;
+        let Some(topmost) = dismissables.last() else {
+            return false;
+        };
+        let contains = |region: &DismissableRegion, position: &Point<Pixels>| {
+            dismissables
+                .iter()
+                .filter(|nested| nested.scope.contains(region.id()))
+                .any(|nested| nested.bounds.contains(position))
+        };
+
+        let mut on_dismiss = vec![topmost.on_dismiss.clone()];
+        if let Some(position) = position {
+            if contains(topmost, &position) {
+                return false;
+            }
+            let mut region = topmost;
+            while region.dismiss_parents_on_click {
+                let Some(parent) = region
+                    .parent_id()
+                    .and_then(|parent_id| dismissables.iter().rfind(|r| r.id() == parent_id))
+                else {
+                    break;
+                };
+                if contains(parent, &position) {
+                    break;
+                }
+                on_dismiss.push(parent.on_dismiss.clone());
+                region = parent;
+            }
+        }
+        for on_dismiss in on_dismiss {
+            on_dismiss(self);
+        }
+        true
+    }
+
+    /// Record the bounds of the element with the given id for the observers registered with
+    /// [`ViewContext::observe_bounds`], if there are any.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub(crate) fn record_element_bounds(
+        &mut self,
+        global_id: &GlobalElementId,
+        bounds: Bounds<Pixels>,
+    ) {
+        if self.window.element_bounds_observers.is_empty() {
+            return;
+        }
+        // Observers refer to elements by their ids from the view they're drawn in down.
+        let view_ix = global_id
+            .iter()
+            .rposition(|id| matches!(id, ElementId::View(_)))
+            .unwrap_or(0);
+        let element_id = GlobalElementId(global_id[view_ix..].iter().cloned().collect());
+        if self
+            .window
+            .element_bounds_observers
+            .has_subscribers(&element_id)
+        {
+            self.window
+                .next_frame
+                .observed_element_bounds
+                .push((element_id, bounds));
+        }
     }
 
     #[profiling::function]
-    fn present(&self) {
+    fn present(&mut self) {
+        let submit_start = Instant::now();
         self.window
             .platform_window
             .draw(&self.window.rendered_frame.scene);
+        self.window
+            .frame_diagnostics
+            .record_submit(submit_start.elapsed());
         self.window.needs_present.set(false);
         profiling::finish_frame!();
     }
@@ -1466,45 +1990,101 @@ impl<'a> WindowContext<'a> {
         // Layout all root elements.
         let mut root_element = self.window.root_view.as_ref().unwrap().clone().into_any();
         root_element.prepaint_as_root(Point::default(), self.window.viewport_size.into(), self);
+        let mounts = self.prepaint_mounts(false);
 
         let mut sorted_deferred_draws =
             (0..self.window.next_frame.deferred_draws.len()).collect::<SmallVec<[_; 8]>>();
         sorted_deferred_draws.sort_by_key(|ix| self.window.next_frame.deferred_draws[*ix].priority);
         self.prepaint_deferred_draws(&sorted_deferred_draws);
 
+        // Prompts, dragged elements and tooltips go above everything in the window.
         let mut prompt_element = None;
         let mut active_drag_element = None;
         let mut tooltip_element = None;
-        if let Some(prompt) = self.window.prompt.take() {
-            let mut element = prompt.view.any_view().into_any();
-            element.prepaint_as_root(Point::default(), self.window.viewport_size.into(), self);
-            prompt_element = Some(element);
-            self.window.prompt = Some(prompt);
-        } else if let Some(active_drag) = self.app.active_drag.take() {
-            let mut element = active_drag.view.clone().into_any();
-            let offset = self.mouse_position() - active_drag.cursor_offset;
-            element.prepaint_as_root(offset, AvailableSpace::min_size(), self);
-            active_drag_element = Some(element);
-            self.app.active_drag = Some(active_drag);
-        } else {
-            tooltip_element = self.prepaint_tooltip();
-        }
+        self.with_z_index(TOP_Z_INDEX, |cx| {
+            if let Some(prompt) = cx.window.prompt.take() {
+                let mut element = prompt.view.any_view().into_any();
+                element.prepaint_as_root(Point::default(), cx.window.viewport_size.into(), cx);
+                prompt_element = Some(element);
+                cx.window.prompt = Some(prompt);
+            } else if let Some(active_drag) = cx.app.active_drag.take() {
+                let mut element = active_drag.view.clone().into_any();
+                let offset = cx.mouse_position() - active_drag.cursor_offset;
+                element.prepaint_as_root(offset, AvailableSpace::min_size(), cx);
+                active_drag_element = Some(element);
+                cx.app.active_drag = Some(active_drag);
+            } else {
+                tooltip_element = cx.prepaint_tooltip();
+            }
+        });
+
+        let overlay_mounts = self.prepaint_mounts(true);
 
         self.window.mouse_hit_test = self.window.next_frame.hit_test(self.window.mouse_position);
 
         // Now actually paint the elements.
         self.window.draw_phase = DrawPhase::Paint;
+        self.window.frame_diagnostics.begin_paint();
         root_element.paint(self);
+        self.paint_mounts(mounts, false);
 
         self.paint_deferred_draws(&sorted_deferred_draws);
 
-        if let Some(mut prompt_element) = prompt_element {
-            prompt_element.paint(self)
-        } else if let Some(mut drag_element) = active_drag_element {
-            drag_element.paint(self);
-        } else if let Some(mut tooltip_element) = tooltip_element {
-            tooltip_element.paint(self);
+        self.with_z_index(TOP_Z_INDEX, |cx| {
+            if let Some(mut prompt_element) = prompt_element {
+                prompt_element.paint(cx)
+            } else if let Some(mut drag_element) = active_drag_element {
+                drag_element.paint(cx);
+            } else if let Some(mut tooltip_element) = tooltip_element {
+                tooltip_element.paint(cx);
+            }
+        });
+
+        self.record_damage();
+        self.paint_mounts(overlay_mounts, true);
+    }
+
+    /// Compare the scene painted so far with the previous frame's scene, and record the areas
+    /// that changed in the frame diagnostics and the damage overlay. What's painted after this,
+    /// the debugging overlays, isn't compared. Nothing is compared while neither is shown, as
+    /// the renderers redraw the whole window anyway.
+    fn record_damage(&mut self) {
+        let scale_factor = self.window.scale_factor;
+        let viewport_size = self.window.viewport_size.scale(scale_factor);
+        self.window.next_frame.scene.begin_overlays();
+        let previous_viewport = self.window.damaged_viewport.replace(viewport_size);
+        if !self.window.frame_diagnostics.enabled() && self.window.damage_overlay.is_none() {
+            return;
         }
+
+        let damage = if previous_viewport == Some(viewport_size) {
+            DamageRegion::between(
+                self.window.rendered_frame.scene.damage_operations(),
+                self.window.next_frame.scene.damage_operations(),
+                Bounds::new(Point::default(), viewport_size),
+            )
+        } else {
+            DamageRegion::full()
+        };
+        let frame_damage = damage.to_frame_damage(scale_factor);
+        if self.window.damage_overlay.is_some() && !damage.is_empty() {
+            let rects = match &frame_damage {
+                FrameDamage::Partial(rects) => rects.clone(),
+                FrameDamage::None | FrameDamage::Full => {
+                    vec![Bounds::new(Point::default(), self.window.viewport_size)]
+                }
+            };
+            self.window.damage_flash = Some(DamageFlash {
+                rects,
+                until: Instant::now() + DAMAGE_FLASH_DURATION,
+            });
+            // Redraw once the flash is over to clear it.
+            self.window.damage_flash_task = Some(self.spawn(|mut cx| async move {
+                cx.background_executor().timer(DAMAGE_FLASH_DURATION).await;
+                cx.update(|cx| cx.refresh()).ok();
+            }));
+        }
+        self.window.frame_diagnostics.record_damage(frame_damage);
     }
 
     fn prepaint_tooltip(&mut self) -> Option<AnyElement> {
@@ -1565,19 +2145,24 @@ impl<'a> WindowContext<'a> {
             self.window
                 .text_style_stack
                 .clone_from(&deferred_draw.text_style_stack);
+            self.window
+                .provided_context_stack
+                .clone_from(&deferred_draw.provided_context_stack);
             self.window
                 .next_frame
                 .dispatch_tree
                 .set_active_node(deferred_draw.parent_node);
 
             let prepaint_start = self.prepaint_index();
-            if let Some(element) = deferred_draw.element.as_mut() {
-                self.with_absolute_element_offset(deferred_draw.absolute_offset, |cx| {
-                    element.prepaint(cx)
-                });
-            } else {
-                self.reuse_prepaint(deferred_draw.prepaint_range.clone());
-            }
+            self.with_z_index(deferred_draw.z_index, |cx| {
+                if let Some(element) = deferred_draw.element.as_mut() {
+                    cx.with_absolute_element_offset(deferred_draw.absolute_offset, |cx| {
+                        element.prepaint(cx)
+                    });
+                } else {
+                    cx.reuse_prepaint(deferred_draw.prepaint_range.clone());
+                }
+            });
             let prepaint_end = self.prepaint_index();
             deferred_draw.prepaint_range = prepaint_start..prepaint_end;
         }
@@ -1589,6 +2174,7 @@ impl<'a> WindowContext<'a> {
         self.window.next_frame.deferred_draws = deferred_draws;
         self.window.element_id_stack.clear();
         self.window.text_style_stack.clear();
+        self.window.provided_context_stack.clear();
     }
 
     fn paint_deferred_draws(&mut self, deferred_draw_indices: &[usize]) {
@@ -1600,22 +2186,34 @@ impl<'a> WindowContext<'a> {
             self.window
                 .element_id_stack
                 .clone_from(&deferred_draw.element_id_stack);
+            self.window
+                .provided_context_stack
+                .clone_from(&deferred_draw.provided_context_stack);
             self.window
                 .next_frame
                 .dispatch_tree
                 .set_active_node(deferred_draw.parent_node);
 
+            let begun = deferred_draw.paint_source.clone().map_or(false, |source| {
+                self.window.frame_diagnostics.push_paint_source(source)
+            });
             let paint_start = self.paint_index();
-            if let Some(element) = deferred_draw.element.as_mut() {
-                element.paint(self);
-            } else {
-                self.reuse_paint(deferred_draw.paint_range.clone());
-            }
+            self.with_z_index(deferred_draw.z_index, |cx| {
+                if let Some(element) = deferred_draw.element.as_mut() {
+                    element.paint(cx);
+                } else {
+                    cx.reuse_paint(deferred_draw.paint_range.clone());
+                }
+            });
             let paint_end = self.paint_index();
+            if begun {
+                self.window.frame_diagnostics.end_element();
+            }
             deferred_draw.paint_range = paint_start..paint_end;
         }
         self.window.next_frame.deferred_draws = deferred_draws;
         self.window.element_id_stack.clear();
+        self.window.provided_context_stack.clear();
     }
 
     pub(crate) fn prepaint_index(&self) -> PrepaintStateIndex {
@@ -1664,11 +2262,14 @@ impl<'a> WindowContext<'a> {
                     parent_node: reused_subtree.refresh_node_id(deferred_draw.parent_node),
                     element_id_stack: deferred_draw.element_id_stack.clone(),
                     text_style_stack: deferred_draw.text_style_stack.clone(),
+                    provided_context_stack: deferred_draw.provided_context_stack.clone(),
+                    z_index: deferred_draw.z_index,
                     priority: deferred_draw.priority,
                     element: None,
                     absolute_offset: deferred_draw.absolute_offset,
                     prepaint_range: deferred_draw.prepaint_range.clone(),
                     paint_range: deferred_draw.paint_range.clone(),
+                    paint_source: deferred_draw.paint_source.clone(),
                 }),
         );
     }
@@ -1679,6 +2280,15 @@ impl<'a> WindowContext<'a> {
             mouse_listeners_index: self.window.next_frame.mouse_listeners.len(),
             input_handlers_index: self.window.next_frame.input_handlers.len(),
             cursor_styles_index: self.window.next_frame.cursor_styles.len(),
+            observed_element_bounds_index: self.window.next_frame.observed_element_bounds.len(),
+            dismissables_index: self.window.next_frame.dismissables.len(),
+            viewport_change_listeners_index: self.window.next_frame.viewport_change_listeners.len(),
+            delayed_hovers_index: self.window.next_frame.delayed_hovers.len(),
+            interactive_regions_index: self.window.next_frame.interactive_regions.len(),
+            recorded_layouts_index: self.window.next_frame.recorded_layouts.len(),
+            native_views_index: self.window.next_frame.native_views.len(),
+            image_loads_index: self.window.next_frame.image_loads.len(),
+            culled_elements_index: self.window.next_frame.culled_elements.len(),
             accessed_element_states_index: self.window.next_frame.accessed_element_states.len(),
             line_layout_index: self.window.text_system.layout_index(),
         }
@@ -1693,6 +2303,62 @@ impl<'a> WindowContext<'a> {
                 .iter()
                 .cloned(),
         );
+        window.next_frame.observed_element_bounds.extend(
+            window.rendered_frame.observed_element_bounds[range.start.observed_element_bounds_index
+                ..range.end.observed_element_bounds_index]
+                .iter()
+                .cloned(),
+        );
+        window.next_frame.dismissables.extend(
+            window.rendered_frame.dismissables
+                [range.start.dismissables_index..range.end.dismissables_index]
+                .iter()
+                .cloned(),
+        );
+        window.next_frame.viewport_change_listeners.extend(
+            window.rendered_frame.viewport_change_listeners[range
+                .start
+                .viewport_change_listeners_index
+                ..range.end.viewport_change_listeners_index]
+                .iter()
+                .cloned(),
+        );
+        window.next_frame.delayed_hovers.extend(
+            window.rendered_frame.delayed_hovers
+                [range.start.delayed_hovers_index..range.end.delayed_hovers_index]
+                .iter()
+                .cloned(),
+        );
+        window.next_frame.interactive_regions.extend(
+            window.rendered_frame.interactive_regions
+                [range.start.interactive_regions_index..range.end.interactive_regions_index]
+                .iter()
+                .cloned(),
+        );
+        window.next_frame.recorded_layouts.extend(
+            window.rendered_frame.recorded_layouts
+                [range.start.recorded_layouts_index..range.end.recorded_layouts_index]
+                .iter()
+                .cloned(),
+        );
+        window.next_frame.native_views.extend(
+            window.rendered_frame.native_views
+                [range.start.native_views_index..range.end.native_views_index]
+                .iter()
+                .cloned(),
+        );
+        window.next_frame.image_loads.extend(
+            window.rendered_frame.image_loads
+                [range.start.image_loads_index..range.end.image_loads_index]
+                .iter()
+                .cloned(),
+        );
+        window.next_frame.culled_elements.extend(
+            window.rendered_frame.culled_elements
+                [range.start.culled_elements_index..range.end.culled_elements_index]
+                .iter()
+                .map(|id| GlobalElementId(id.0.clone())),
+        );
         window.next_frame.input_handlers.extend(
             window.rendered_frame.input_handlers
                 [range.start.input_handlers_index..range.end.input_handlers_index]
@@ -1720,6 +2386,43 @@ impl<'a> WindowContext<'a> {
         );
     }
 
+    /// Provide a value to everything drawn by the given function, which can read it with
+    /// [`WindowContext::context`]. Values provided inside the function shadow this one. This
+    /// method should only be called as part of element drawing.
+    pub fn with_context<T: 'static, R>(
+        &mut self,
+        value: Rc<T>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        debug_assert!(
+            matches!(
+                self.window.draw_phase,
+                DrawPhase::Prepaint | DrawPhase::Paint
+            ),
+            "this method can only be called during request_layout, prepaint, or paint"
+        );
+        // Truncate rather than pop, so that values pushed by the function and never removed
+        // don't leak into the rest of the tree.
+        let depth = self.window.provided_context_stack.len();
+        self.window
+            .provided_context_stack
+            .push((TypeId::of::<T>(), value));
+        let result = f(self);
+        self.window.provided_context_stack.truncate(depth);
+        result
+    }
+
+    /// The innermost value of the given type provided to the element being drawn, with
+    /// [`provide_context`](crate::provide_context) or [`WindowContext::with_context`].
+    pub fn context<T: 'static>(&self) -> Option<&T> {
+        self.window
+            .provided_context_stack
+            .iter()
+            .rev()
+            .find(|(type_id, _)| *type_id == TypeId::of::<T>())
+            .and_then(|(_, value)| value.downcast_ref())
+    }
+
     /// Push a text style onto the stack, and call a function with that style active.
     /// Use [`AppContext::text_style`] to get the current, combined text style. This method
     /// should only be called as part of element drawing.
@@ -1792,6 +2495,11 @@ impl<'a> WindowContext<'a> {
             "this method can only be called during request_layout, prepaint, or paint"
         );
         if let Some(mask) = mask {
+            if self.window.draw_phase == DrawPhase::Paint {
+                self.window
+                    .frame_diagnostics
+                    .record_primitive(PaintPrimitive::Clip);
+            }
             let mask = mask.intersect(&self.content_mask());
             self.window_mut().content_mask_stack.push(mask);
             let result = f(self);
@@ -1802,6 +2510,83 @@ impl<'a> WindowContext<'a> {
         }
     }
 
+    /// Invoke the given function with the given opacity multiplied into the opacity of everything
+    /// it paints, on top of the opacity of the elements it's painted within. This method should
+    /// only be called during the paint phase of element drawing.
+    pub fn with_element_opacity<R>(
+        &mut self,
+        opacity: Option<f32>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        let Some(opacity) = opacity else {
+            return f(self);
+        };
+        let previous = self.window.element_opacity;
+        self.window.element_opacity = Some(previous.unwrap_or(1.) * opacity.clamp(0., 1.));
+        let result = f(self);
+        self.window.element_opacity = previous;
+        result
+    }
+
+    /// The opacity of the element being painted, see [`WindowContext::with_element_opacity`].
+    pub fn element_opacity(&self) -> f32 {
+        self.window.element_opacity.unwrap_or(1.)
+    }
+
+    /// Invoke the given function with everything it paints ordered above everything with a lower
+    /// z-index, however they overlap and whichever was painted last, and its hitboxes hit before
+    /// theirs. Painting order still decides between things at the same z-index.
+    ///
+    /// An element's z-index never drops below that of the element it's drawn within, so a lower
+    /// z-index than the current one has no effect. Elements start at a z-index of 0, and deferred
+    /// draws keep the z-index of where they were deferred.
+    ///
+    /// This method should only be called during the prepaint and paint phases of element drawing,
+    /// and with the same z-index in both.
+    pub fn with_z_index<R>(&mut self, z_index: u8, f: impl FnOnce(&mut Self) -> R) -> R {
+        debug_assert!(
+            matches!(
+                self.window.draw_phase,
+                DrawPhase::Prepaint | DrawPhase::Paint
+            ),
+            "this method can only be called during prepaint, or paint"
+        );
+        let previous = self.window.z_index;
+        if z_index <= previous {
+            return f(self);
+        }
+
+        let paint = self.window.draw_phase == DrawPhase::Paint;
+        self.window.z_index = z_index;
+        if paint {
+            self.window.next_frame.scene.push_z_index(z_index);
+        }
+        let result = f(self);
+        if paint {
+            self.window.next_frame.scene.pop_z_index();
+        }
+        self.window.z_index = previous;
+        result
+    }
+
+    /// The z-index of the element being drawn, see [`WindowContext::with_z_index`].
+    pub fn z_index(&self) -> u8 {
+        self.window.z_index
+    }
+
+    /// The style of the given elevation level, from the [`ElevationStyles`] global if it's set.
+    pub fn elevation_style(&self, level: ElevationLevel) -> ElevationStyle {
+        match self.try_global::<ElevationStyles>() {
+            Some(styles) => styles.get(level).clone(),
+            None => ElevationStyles::default().get(level).clone(),
+        }
+    }
+
     /// Updates the global element offset relative to the current offset. This is used to implement
     /// scrolling. This method should only be called during the prepaint phase of element drawing.
     pub fn with_element_offset<R>(
@@ -1962,18 +2747,16 @@ impl<'a> WindowContext<'a> {
         task.clone().now_or_never().or_else(|| {
             if is_first {
                 let parent_id = self.parent_view_id();
+                let request = FrameRequest {
+                    reason: FrameReason::AsyncCompletion,
+                    element_id: self.window.element_id_stack.last().cloned(),
+                };
                 self.spawn({
                     let task = task.clone();
                     |mut cx| async move {
                         task.await;
 
-                        cx.on_next_frame(move |cx| {
-                            if let Some(parent_id) = parent_id {
-                                cx.notify(parent_id)
-                            } else {
-                                cx.refresh()
-                            }
-                        });
+                        cx.update(|cx| cx.schedule_frame(request, parent_id)).ok();
                     }
                 })
                 .detach();
@@ -2000,6 +2783,34 @@ impl<'a> WindowContext<'a> {
             .unwrap_or_default()
     }
 
+    /// Whether painting an element can be skipped, because its bounds are entirely clipped out
+    /// of view. Elements containing the focused element are always painted, so that it keeps
+    /// handling keystrokes. Elements without a size are painted too, as their children may
+    /// still be visible.
+    pub(crate) fn can_cull(&self, node_id: DispatchNodeId, bounds: Bounds<Pixels>) -> bool {
+        if bounds.is_empty() || bounds.intersects(&self.content_mask().bounds) {
+            return false;
+        }
+        let dispatch_tree = &self.window.next_frame.dispatch_tree;
+        let focused_node_id = self
+            .window
+            .focus
+            .and_then(|focus_id| dispatch_tree.focusable_node_id(focus_id));
+        !focused_node_id.map_or(false, |focused_node_id| {
+            dispatch_tree
+                .dispatch_path(focused_node_id)
+                .contains(&node_id)
+        })
+    }
+
+    /// Record that the element being drawn was skipped by [`Self::can_cull`]. The state of the
+    /// elements drawn within it is kept, so that it's still around once they're back in view.
+    pub(crate) fn cull_element(&mut self) {
+        let id = GlobalElementId(self.window.element_id_stack.clone());
+        self.window.next_frame.culled_elements.push(id);
+        self.window.frame_diagnostics.record_paint(true);
+    }
+
     /// Obtain the current content mask. This method should only be called during element drawing.
     pub fn content_mask(&self) -> ContentMask<Pixels> {
         debug_assert!(
@@ -2181,11 +2992,14 @@ impl<'a> WindowContext<'a> {
             parent_node,
             element_id_stack: window.element_id_stack.clone(),
             text_style_stack: window.text_style_stack.clone(),
+            provided_context_stack: window.provided_context_stack.clone(),
+            z_index: window.z_index,
             priority,
             element: Some(element),
             absolute_offset,
             prepaint_range: PrepaintStateIndex::default()..PrepaintStateIndex::default(),
             paint_range: PaintIndex::default()..PaintIndex::default(),
+            paint_source: window.frame_diagnostics.paint_source(),
         });
     }
 
@@ -2205,6 +3019,9 @@ impl<'a> WindowContext<'a> {
         let content_mask = self.content_mask();
         let clipped_bounds = bounds.intersect(&content_mask.bounds);
         if !clipped_bounds.is_empty() {
+            self.window
+                .frame_diagnostics
+                .record_primitive(PaintPrimitive::Layer);
             self.window
                 .next_frame
                 .scene
@@ -2237,6 +3054,7 @@ impl<'a> WindowContext<'a> {
 
         let scale_factor = self.scale_factor();
         let content_mask = self.content_mask();
+        let opacity = self.element_opacity();
         for shadow in shadows {
             let mut shadow_bounds = bounds;
             shadow_bounds.origin += shadow.offset;
@@ -2247,7 +3065,7 @@ impl<'a> WindowContext<'a> {
                 bounds: shadow_bounds.scale(scale_factor),
                 content_mask: content_mask.scale(scale_factor),
                 corner_radii: corner_radii.scale(scale_factor),
-                color: shadow.color,
+                color: shadow.color.opacity(opacity),
             });
         }
     }
@@ -2264,15 +3082,19 @@ impl<'a> WindowContext<'a> {
             "this method can only be called during paint"
         );
 
+        self.window
+            .frame_diagnostics
+            .record_primitive(PaintPrimitive::Quad);
         let scale_factor = self.scale_factor();
         let content_mask = self.content_mask();
+        let opacity = self.element_opacity();
         self.window.next_frame.scene.insert_primitive(Quad {
             order: 0,
             pad: 0,
             bounds: quad.bounds.scale(scale_factor),
             content_mask: content_mask.scale(scale_factor),
-            background: quad.background,
-            border_color: quad.border_color,
+            background: quad.background.opacity(opacity),
+            border_color: quad.border_color.opacity(opacity),
             corner_radii: quad.corner_radii.scale(scale_factor),
             border_widths: quad.border_widths.scale(scale_factor),
         });
@@ -2291,7 +3113,7 @@ impl<'a> WindowContext<'a> {
         let scale_factor = self.scale_factor();
         let content_mask = self.content_mask();
         path.content_mask = content_mask;
-        path.color = color.into();
+        path.color = color.into().opacity(self.element_opacity());
         self.window
             .next_frame
             .scene
@@ -2330,7 +3152,10 @@ impl<'a> WindowContext<'a> {
             pad: 0,
             bounds: bounds.scale(scale_factor),
             content_mask: content_mask.scale(scale_factor),
-            color: style.color.unwrap_or_default(),
+            color: style
+                .color
+                .unwrap_or_default()
+                .opacity(self.element_opacity()),
             thickness: style.thickness.scale(scale_factor),
             wavy: style.wavy,
         });
@@ -2365,7 +3190,10 @@ impl<'a> WindowContext<'a> {
             bounds: bounds.scale(scale_factor),
             content_mask: content_mask.scale(scale_factor),
             thickness: style.thickness.scale(scale_factor),
-            color: style.color.unwrap_or_default(),
+            color: style
+                .color
+                .unwrap_or_default()
+                .opacity(self.element_opacity()),
             wavy: false,
         });
     }
@@ -2422,6 +3250,7 @@ impl<'a> WindowContext<'a> {
                 size: tile.bounds.size.map(Into::into),
             };
             let content_mask = self.content_mask().scale(scale_factor);
+            let color = color.opacity(self.element_opacity());
             self.window
                 .next_frame
                 .scene
@@ -2542,6 +3371,7 @@ impl<'a> WindowContext<'a> {
             return Ok(());
         };
         let content_mask = self.content_mask().scale(scale_factor);
+        let color = color.opacity(self.element_opacity());
 
         self.window
             .next_frame
@@ -2575,6 +3405,9 @@ impl<'a> WindowContext<'a> {
             "this method can only be called during paint"
         );
 
+        self.window
+            .frame_diagnostics
+            .record_primitive(PaintPrimitive::Image);
         let scale_factor = self.scale_factor();
         let bounds = bounds.scale(scale_factor);
         let params = RenderImageParams { image_id: data.id };
@@ -2603,6 +3436,61 @@ impl<'a> WindowContext<'a> {
         Ok(())
     }
 
+    /// Paint the `source` region of an image, given in the pixels of the image data rather than
+    /// in logical pixels, stretched to fill `bounds` in the scene for the next frame at the
+    /// current z-index.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_image_region(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        source: Bounds<DevicePixels>,
+        data: Arc<ImageData>,
+        grayscale: bool,
+    ) -> Result<()> {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+
+        self.window
+            .frame_diagnostics
+            .record_primitive(PaintPrimitive::Image);
+        let scale_factor = self.scale_factor();
+        let bounds = bounds.scale(scale_factor);
+        let params = RenderImageParams { image_id: data.id };
+
+        let mut tile = self
+            .window
+            .sprite_atlas
+            .get_or_insert_with(&params.clone().into(), &mut || {
+                Ok(Some((data.size(), Cow::Borrowed(data.as_bytes()))))
+            })?
+            .expect("Callback above only returns Some");
+        tile.bounds = Bounds {
+            origin: point(
+                tile.bounds.origin.x + source.origin.x,
+                tile.bounds.origin.y + source.origin.y,
+            ),
+            size: source.size,
+        };
+        let content_mask = self.content_mask().scale(scale_factor);
+
+        self.window
+            .next_frame
+            .scene
+            .insert_primitive(PolychromeSprite {
+                order: 0,
+                grayscale,
+                bounds,
+                content_mask,
+                corner_radii: Corners::default(),
+                tile,
+            });
+        Ok(())
+    }
+
     /// Paint a surface into the scene for the next frame at the current z-index.
     ///
     /// This method should only be called as part of the paint phase of element drawing.
@@ -2731,6 +3619,21 @@ impl<'a> WindowContext<'a> {
     ///
     /// This method should only be called as part of the prepaint phase of element drawing.
     pub fn insert_hitbox(&mut self, bounds: Bounds<Pixels>, opaque: bool) -> Hitbox {
+        self.insert_hitbox_with_slop(bounds, opaque, Edges::default())
+    }
+
+    /// Like [Self::insert_hitbox], but the hitbox can also be hit up to the given distance
+    /// beyond each edge of its bounds, without affecting what's painted there. Where this slop
+    /// overlaps other hitboxes, those whose bounds contain the mouse win, and otherwise the
+    /// hitbox with the nearest bounds does.
+    ///
+    /// This method should only be called as part of the prepaint phase of element drawing.
+    pub fn insert_hitbox_with_slop(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        opaque: bool,
+        slop: Edges<Pixels>,
+    ) -> Hitbox {
         debug_assert_eq!(
             self.window.draw_phase,
             DrawPhase::Prepaint,
@@ -2746,6 +3649,8 @@ impl<'a> WindowContext<'a> {
             bounds,
             content_mask,
             opaque,
+            z_index: window.z_index,
+            slop,
         };
         window.next_frame.hitboxes.push(hitbox.clone());
         hitbox
@@ -3009,6 +3914,24 @@ impl<'a> WindowContext<'a> {
     #[profiling::function]
     pub fn dispatch_event(&mut self, event: PlatformInput) -> DispatchEventResult {
         self.window.last_input_timestamp.set(Instant::now());
+        self.window.frame_diagnostics.record_input(&event);
+
+        let input_modality = match &event {
+            PlatformInput::KeyDown(_) => Some(InputModality::Keyboard),
+            PlatformInput::MouseDown(_)
+            | PlatformInput::MouseMove(_)
+            | PlatformInput::ScrollWheel(_) => Some(InputModality::Pointer),
+            _ => None,
+        };
+        if let Some(input_modality) = input_modality {
+            if input_modality != self.window.input_modality {
+                self.window.input_modality = input_modality;
+                // Focus-visible styles depend on the modality, so redraw to show or hide them.
+                if self.window.focus.is_some() {
+                    self.refresh();
+                }
+            }
+        }
         // Handlers may set this to false by calling `stop_propagation`.
         self.app.propagate_event = true;
         // Handlers may set this to true by calling `prevent_default`.
@@ -3089,6 +4012,27 @@ impl<'a> WindowContext<'a> {
             PlatformInput::KeyDown(_) | PlatformInput::KeyUp(_) => event,
         };
 
+        // The topmost dismissable sees clicks and escape presses before anything else, so that
+        // they're handled even when the focus is elsewhere.
+        match &event {
+            PlatformInput::MouseDown(mouse_down) => {
+                self.dismiss_topmost(Some(mouse_down.position));
+            }
+            PlatformInput::KeyDown(key_down)
+                if key_down.keystroke.key == "escape"
+                    && !key_down.keystroke.modifiers.modified() =>
+            {
+                if self.dismiss_topmost(None) {
+                    self.app.propagate_event = false;
+                    return DispatchEventResult {
+                        propagate: false,
+                        default_prevented: self.window.default_prevented,
+                    };
+                }
+            }
+            _ => {}
+        }
+
         if let Some(any_mouse_event) = event.mouse_event() {
             self.dispatch_mouse_event(any_mouse_event);
         } else if let Some(any_key_event) = event.keyboard_event() {
@@ -3107,6 +4051,13 @@ impl<'a> WindowContext<'a> {
             self.window.mouse_hit_test = hit_test;
             self.reset_cursor_style();
         }
+        if !self.window.mouse_hit_test.0.is_empty() {
+            self.track_interactive_regions();
+        }
+
+        if let Some(event) = event.downcast_ref::<MouseDownEvent>() {
+            self.begin_press(event.position);
+        }
 
         let mut mouse_listeners = mem::take(&mut self.window.rendered_frame.mouse_listeners);
 
@@ -3132,6 +4083,10 @@ impl<'a> WindowContext<'a> {
         }
 
         self.window.rendered_frame.mouse_listeners = mouse_listeners;
+        if event.is::<MouseUpEvent>() {
+            self.end_press();
+        }
+        self.update_delayed_hovers(event.is::<MouseDownEvent>());
 
         if self.has_active_drag() {
             if event.is::<MouseMoveEvent>() {
@@ -3596,6 +4551,68 @@ impl<'a> WindowContext<'a> {
         receiver
     }
 
+    /// The frame times recorded for this window. Frames are only recorded once they're enabled,
+    /// with [`FrameDiagnostics::set_enabled`] or by showing the overlay.
+    pub fn frame_diagnostics(&self) -> &FrameDiagnostics {
+        &self.window.frame_diagnostics
+    }
+
+    /// Configure the recording of frame times for this window.
+    pub fn frame_diagnostics_mut(&mut self) -> &mut FrameDiagnostics {
+        &mut self.window.frame_diagnostics
+    }
+
+    /// Show or hide a graph of the most recent frame times in the corner of the window. Showing
+    /// it starts recording frame times.
+    pub fn toggle_frame_diagnostics_overlay(&mut self) {
+        if let Some(id) = self.window.frame_diagnostics_overlay.take() {
+            self.unmount(id);
+        } else {
+            self.window.frame_diagnostics.set_enabled(true);
+            self.window.frame_diagnostics_overlay = Some(
+                self.mount_overlay(FrameDiagnosticsOverlay::REGION, |_| FrameDiagnosticsOverlay),
+            );
+        }
+    }
+
+    /// Whether the frame time graph is shown, see [`Self::toggle_frame_diagnostics_overlay`].
+    pub fn frame_diagnostics_overlay_visible(&self) -> bool {
+        self.window.frame_diagnostics_overlay.is_some()
+    }
+
+    /// Show or hide a brief highlight over the areas of the window that changed in each frame, see
+    /// [`FrameTiming::damage`].
+    pub fn toggle_damage_overlay(&mut self) {
+        if let Some(id) = self.window.damage_overlay.take() {
+            self.unmount(id);
+        } else {
+            self.window.damage_overlay =
+                Some(self.mount_overlay(MountRegion::Fullscreen, |_| DamageOverlay));
+        }
+        self.window.damage_flash = None;
+        self.window.damage_flash_task = None;
+    }
+
+    /// Whether changed areas are highlighted, see [`Self::toggle_damage_overlay`].
+    pub fn damage_overlay_visible(&self) -> bool {
+        self.window.damage_overlay.is_some()
+    }
+
+    /// Show a toast in the window's [`toast_host`](crate::toast_host), returning an id that can
+    /// be used to dismiss it before it dismisses itself.
+    pub fn push_toast(&mut self, toast: Toast) -> ToastId {
+        let id = self.window.toasts.push(toast);
+        self.refresh();
+        id
+    }
+
+    /// Dismiss a toast pushed with [`Self::push_toast`], if it's still shown.
+    pub fn dismiss_toast(&mut self, id: ToastId) {
+        if self.window.toasts.dismiss(id) {
+            self.refresh();
+        }
+    }
+
     fn build_custom_prompt(
         &mut self,
         prompt_builder: &PromptBuilder,
@@ -4162,6 +5179,47 @@ impl<'a, V: 'static> ViewContext<'a, V> {
         subscription
     }
 
+    /// Register a callback to be invoked whenever the bounds of the element with the given id in
+    /// this view change, where the element has no ancestors with ids within the view. Changes are
+    /// collected once a frame has been drawn and are delivered in the order the elements were
+    /// painted, including when the element first appears.
+    ///
+    /// The callback runs after the frame is complete, so anything it changes in response is drawn
+    /// in the next frame rather than laying out the current one again.
+    pub fn observe_bounds(
+        &mut self,
+        element_id: impl Into<ElementId>,
+        callback: impl FnMut(&mut V, &BoundsChangeEvent, &mut ViewContext<V>) + 'static,
+    ) -> Subscription {
+        self.observe_bounds_at([element_id.into()], callback)
+    }
+
+    /// Like [`Self::observe_bounds`], for an element within other elements with ids or
+    /// [id scopes](WindowContext::with_id_scope) in this view. The path is the ids of those
+    /// elements from the view down, followed by the id of the observed element.
+    pub fn observe_bounds_at(
+        &mut self,
+        path: impl IntoIterator<Item = ElementId>,
+        mut callback: impl FnMut(&mut V, &BoundsChangeEvent, &mut ViewContext<V>) + 'static,
+    ) -> Subscription {
+        let mut element_id = GlobalElementId(SmallVec::new());
+        element_id.push(ElementId::View(self.entity_id()));
+        element_id.extend(path);
+        let view = self.view.downgrade();
+        let (subscription, activate) = self.window.element_bounds_observers.insert(
+            element_id,
+            Box::new(move |event, cx| {
+                view.update(cx, |view, cx| callback(view, event, cx))
+                    .is_ok()
+            }),
+        );
+        activate();
+        // Make sure the element's bounds are recorded, in case the frame that contains it was
+        // already drawn.
+        self.window_cx.refresh();
+        subscription
+    }
+
     /// Register a callback to be invoked when the window is activated or deactivated.
     pub fn observe_window_activation(
         &mut self,
@@ -4728,7 +5786,7 @@ impl AnyWindowHandle {
 ///
 /// Can be constructed with a string, a number, or both, as well
 /// as other internal representations.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum ElementId {
     /// The ID of a View element
     View(EntityId),
@@ -4742,6 +5800,18 @@ pub enum ElementId {
     FocusHandle(FocusId),
     /// A combination of a name and an integer.
     NamedInteger(SharedString, usize),
+    /// A key within the scope of another id, such as the index of a row in a list, built with
+    /// [`ElementId::scoped`] or from a tuple of an id and a key. Only the pair is allocated, so
+    /// these are cheaper to build on every frame than formatted names, and can't collide with
+    /// the ids of other scopes.
+    Scoped(Arc<(ElementId, ElementId)>),
+}
+
+impl ElementId {
+    /// An id for the given key within the scope of this id.
+    pub fn scoped(self, key: impl Into<ElementId>) -> Self {
+        ElementId::Scoped(Arc::new((self, key.into())))
+    }
 }
 
 impl Display for ElementId {
@@ -4753,6 +5823,7 @@ impl Display for ElementId {
             ElementId::FocusHandle(_) => write!(f, "FocusHandle")?,
             ElementId::NamedInteger(s, i) => write!(f, "{}-{}", s, i)?,
             ElementId::Uuid(uuid) => write!(f, "{}", uuid)?,
+            ElementId::Scoped(scoped) => write!(f, "{}/{}", scoped.0, scoped.1)?,
         }
 
         Ok(())
@@ -4831,6 +5902,30 @@ impl From<(&'static str, u32)> for ElementId {
     }
 }
 
+impl From<(ElementId, usize)> for ElementId {
+    fn from((scope, ix): (ElementId, usize)) -> Self {
+        scope.scoped(ix)
+    }
+}
+
+impl From<(ElementId, u64)> for ElementId {
+    fn from((scope, id): (ElementId, u64)) -> Self {
+        scope.scoped(id as usize)
+    }
+}
+
+impl From<(ElementId, &'static str)> for ElementId {
+    fn from((scope, name): (ElementId, &'static str)) -> Self {
+        scope.scoped(name)
+    }
+}
+
+impl From<(ElementId, EntityId)> for ElementId {
+    fn from((scope, id): (ElementId, EntityId)) -> Self {
+        scope.scoped(id.as_u64() as usize)
+    }
+}
+
 /// A rectangle to be rendered in the window at the given position and size.
 /// Passed as an argument [`WindowContext::paint_quad`].
 #[derive(Clone)]
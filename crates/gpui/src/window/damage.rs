@@ -0,0 +1,343 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    fill, point, size, Bounds, Element, ElementId, GlobalElementId, Hsla, IntoElement, LayoutId,
+    PaintOperation, Pixels, Primitive, ScaledPixels, Size, Style, TransformationMatrix,
+    WindowContext,
+};
+
+/// A damage region that would need more separate rects than this is reported as a full repaint.
+const MAX_DAMAGE_RECTS: usize = 16;
+/// How long the damage overlay highlights the areas that changed in a frame.
+pub(crate) const DAMAGE_FLASH_DURATION: Duration = Duration::from_millis(250);
+
+/// The parts of a window that changed in a frame, compared with the frame before it.
+///
+/// This is only reported for diagnostics. The renderers still redraw the whole window each frame,
+/// since they don't keep the previous frame's output to draw the unchanged areas from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FrameDamage {
+    /// Nothing changed.
+    #[default]
+    None,
+    /// Only these areas changed. They don't overlap.
+    Partial(Vec<Bounds<Pixels>>),
+    /// The whole window has to be repainted, because its size changed or because some of the
+    /// changes can't be confined to an area.
+    Full,
+}
+
+impl FrameDamage {
+    /// The fraction of a window of the given size that changed, and that a renderer redrawing only
+    /// the changed areas would have to repaint, between 0 and 1.
+    pub fn repainted_fraction(&self, viewport_size: Size<Pixels>) -> f32 {
+        let viewport_area = viewport_size.width.0 * viewport_size.height.0;
+        match self {
+            FrameDamage::None => 0.,
+            FrameDamage::Full => 1.,
+            FrameDamage::Partial(_) if viewport_area <= 0. => 1.,
+            FrameDamage::Partial(rects) => {
+                let area = rects
+                    .iter()
+                    .map(|rect| rect.size.width.0 * rect.size.height.0)
+                    .sum::<f32>();
+                (area / viewport_area).min(1.)
+            }
+        }
+    }
+}
+
+/// The areas of a scene that differ from the previous frame's scene, merged into rects that
+/// don't overlap.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct DamageRegion {
+    rects: Vec<Bounds<ScaledPixels>>,
+    full: bool,
+}
+
+impl DamageRegion {
+    pub(crate) fn full() -> Self {
+        Self {
+            rects: Vec::new(),
+            full: true,
+        }
+    }
+
+    /// Compare the paint operations of two scenes of the same size. Operations that are equal at
+    /// the start and the end of both are unchanged. If the same number of operations differ in
+    /// between, the ones that changed in place are damaged, otherwise all of them are.
+    pub(crate) fn between(
+        previous: &[PaintOperation],
+        current: &[PaintOperation],
+        viewport: Bounds<ScaledPixels>,
+    ) -> Self {
+        let prefix = previous
+            .iter()
+            .zip(current)
+            .take_while(|(previous, current)| previous == current)
+            .count();
+        let suffix = previous[prefix..]
+            .iter()
+            .rev()
+            .zip(current[prefix..].iter().rev())
+            .take_while(|(previous, current)| previous == current)
+            .count();
+        let previous = &previous[prefix..previous.len() - suffix];
+        let current = &current[prefix..current.len() - suffix];
+
+        let mut region = Self::default();
+        let mut damage = |operation: &PaintOperation| match operation {
+            PaintOperation::Primitive(primitive) => match primitive_damage(primitive) {
+                Some(bounds) => region.add(bounds.intersect(&viewport)),
+                None => region.full = true,
+            },
+            // Layers and z-indices change how the primitives in them are ordered, wherever they
+            // are.
+            PaintOperation::StartLayer(_)
+            | PaintOperation::EndLayer
+            | PaintOperation::StartZIndex(_)
+            | PaintOperation::EndZIndex => region.full = true,
+        };
+        if previous.len() == current.len() {
+            for (previous, current) in previous.iter().zip(current) {
+                if previous != current {
+                    damage(previous);
+                    damage(current);
+                }
+            }
+        } else {
+            previous.iter().chain(current).for_each(damage);
+        }
+        if region.full {
+            region.rects.clear();
+        }
+        region
+    }
+
+    /// Add an area to the region, merging it with the rects it overlaps.
+    pub(crate) fn add(&mut self, mut bounds: Bounds<ScaledPixels>) {
+        if self.full || bounds.is_empty() {
+            return;
+        }
+        while let Some(ix) = self.rects.iter().position(|rect| rect.intersects(&bounds)) {
+            bounds = bounds.union(&self.rects.swap_remove(ix));
+        }
+        self.rects.push(bounds);
+        if self.rects.len() > MAX_DAMAGE_RECTS {
+            *self = Self::full();
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        !self.full && self.rects.is_empty()
+    }
+
+    pub(crate) fn to_frame_damage(&self, scale_factor: f32) -> FrameDamage {
+        if self.full {
+            return FrameDamage::Full;
+        }
+        if self.rects.is_empty() {
+            return FrameDamage::None;
+        }
+        FrameDamage::Partial(
+            self.rects
+                .iter()
+                .map(|rect| Bounds {
+                    origin: point(
+                        Pixels(rect.origin.x.0 / scale_factor),
+                        Pixels(rect.origin.y.0 / scale_factor),
+                    ),
+                    size: size(
+                        Pixels(rect.size.width.0 / scale_factor),
+                        Pixels(rect.size.height.0 / scale_factor),
+                    ),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The area a primitive covers on screen, or `None` if it can't be known from the primitive,
+/// e.g. for surfaces, whose content changes independently of the scene.
+fn primitive_damage(primitive: &Primitive) -> Option<Bounds<ScaledPixels>> {
+    let bounds = match primitive {
+        Primitive::Shadow(shadow) => {
+            // The blur extends past the shadow's bounds.
+            let mut bounds = shadow.bounds;
+            bounds.dilate(ScaledPixels(shadow.blur_radius.0 * 3.));
+            bounds
+        }
+        Primitive::MonochromeSprite(sprite)
+            if sprite.transformation != TransformationMatrix::unit() =>
+        {
+            return None;
+        }
+        Primitive::Surface(_) => return None,
+        _ => *primitive.bounds(),
+    };
+    Some(bounds.intersect(&primitive.content_mask().bounds))
+}
+
+/// The areas that changed in a recent frame, which the damage overlay highlights until `until`.
+pub(crate) struct DamageFlash {
+    pub(crate) rects: Vec<Bounds<Pixels>>,
+    pub(crate) until: Instant,
+}
+
+/// Highlights the changed areas of the last frame that had changes, while the damage overlay
+/// is shown with [`WindowContext::toggle_damage_overlay`].
+pub(crate) struct DamageOverlay;
+
+impl Element for DamageOverlay {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        (cx.request_layout(Style::default(), None), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _cx: &mut WindowContext,
+    ) {
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        let Some(flash) = cx
+            .window
+            .damage_flash
+            .as_ref()
+            .filter(|flash| flash.until > Instant::now())
+        else {
+            return;
+        };
+        let color: Hsla = crate::red().opacity(0.25);
+        for rect in flash.rects.clone() {
+            cx.paint_quad(fill(rect, color));
+        }
+    }
+}
+
+impl IntoElement for DamageOverlay {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        self as gpui, div, prelude::*, px, rgb, Render, TestAppContext, ViewContext,
+        VisualTestContext,
+    };
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Bounds<ScaledPixels> {
+        Bounds {
+            origin: point(ScaledPixels(x), ScaledPixels(y)),
+            size: size(ScaledPixels(width), ScaledPixels(height)),
+        }
+    }
+
+    #[test]
+    fn test_overlapping_rects_are_merged() {
+        let mut region = DamageRegion::default();
+        region.add(rect(0., 0., 10., 10.));
+        region.add(rect(20., 0., 10., 10.));
+        assert_eq!(region.rects.len(), 2);
+
+        // Bridges the two rects, so all three become one.
+        region.add(rect(5., 0., 20., 5.));
+        assert_eq!(region.rects, [rect(0., 0., 30., 10.)]);
+
+        for ix in 0..=MAX_DAMAGE_RECTS {
+            region.add(rect(0., 20. * (ix + 1) as f32, 10., 10.));
+        }
+        assert!(region.full);
+    }
+
+    /// A column of 100 rows, one of which can be highlighted.
+    struct Rows {
+        highlighted: Option<usize>,
+    }
+
+    impl Render for Rows {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().flex().flex_col().children((0..100).map(|ix| {
+                let color = if self.highlighted == Some(ix) {
+                    rgb(0xff0000)
+                } else {
+                    rgb(0x0000ff)
+                };
+                div().w(px(100.)).h(px(20.)).bg(color)
+            }))
+        }
+    }
+
+    fn highlight(
+        view: &gpui::View<Rows>,
+        highlighted: Option<usize>,
+        cx: &mut VisualTestContext,
+    ) -> FrameDamage {
+        view.update(cx, |view, cx| {
+            view.highlighted = highlighted;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.update(|cx| cx.frame_diagnostics().last_frame().unwrap().damage.clone())
+    }
+
+    #[gpui::test]
+    fn test_damage_covers_changed_elements(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| Rows { highlighted: None });
+        cx.update(|cx| cx.frame_diagnostics_mut().set_enabled(true));
+
+        let row = |ix: usize| Bounds::new(point(px(0.), px(20.) * ix), size(px(100.), px(20.)));
+        assert_eq!(
+            highlight(&view, Some(5), cx),
+            FrameDamage::Partial(vec![row(5)])
+        );
+        let FrameDamage::Partial(rects) = highlight(&view, Some(7), cx) else {
+            panic!("expected a partial repaint");
+        };
+        assert_eq!(rects.len(), 2);
+        assert!(rects.contains(&row(5)) && rects.contains(&row(7)));
+
+        // Redrawing without changes doesn't repaint anything.
+        assert_eq!(highlight(&view, Some(7), cx), FrameDamage::None);
+    }
+
+    #[gpui::test]
+    fn test_resizing_repaints_the_whole_window(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| Rows { highlighted: None });
+        cx.update(|cx| cx.frame_diagnostics_mut().set_enabled(true));
+
+        cx.simulate_resize(size(px(300.), px(300.)));
+        cx.run_until_parked();
+        cx.update(|cx| {
+            let damage = &cx.frame_diagnostics().last_frame().unwrap().damage;
+            assert_eq!(*damage, FrameDamage::Full);
+        });
+        assert_eq!(highlight(&view, None, cx), FrameDamage::None);
+    }
+}
@@ -0,0 +1,64 @@
+use collections::{FxHashMap, FxHashSet};
+use itertools::Itertools;
+
+use crate::{GlobalElementId, WindowContext};
+
+/// The ids of the elements prepainted in the frame being drawn, which catches elements that were
+/// given the same id as another in debug builds. Elements with the same id share their state, so
+/// one of them usually misbehaves, e.g. by showing the other's hover.
+#[derive(Default)]
+pub(crate) struct ElementIds {
+    prepainted: FxHashMap<GlobalElementId, &'static str>,
+    collisions: Vec<ElementIdCollision>,
+    reported: FxHashSet<GlobalElementId>,
+}
+
+/// Two elements that were prepainted with the same id in one frame.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ElementIdCollision {
+    pub(crate) path: String,
+    pub(crate) first: &'static str,
+    pub(crate) second: &'static str,
+}
+
+impl<'a> WindowContext<'a> {
+    /// Record that an element of the given type is being prepainted with the id at the top of
+    /// the element id stack, and report it if another element already was in this frame.
+    pub(crate) fn register_element_id(&mut self, element_type: &'static str) {
+        let element_ids = &mut self.window.element_ids;
+        let id = GlobalElementId(self.window.element_id_stack.clone());
+        let Some(&first) = element_ids.prepainted.get(&id) else {
+            element_ids.prepainted.insert(id, element_type);
+            return;
+        };
+
+        let collision = ElementIdCollision {
+            path: id.iter().join(" > "),
+            first,
+            second: element_type,
+        };
+        if element_ids.reported.insert(id) {
+            log::error!(
+                "two elements were drawn with the id `{}` in one frame, a {} and a {}. Give them \
+                 distinct ids, or draw them within different scopes with \
+                 `WindowContext::with_id_scope`",
+                collision.path,
+                collision.first,
+                collision.second
+            );
+        }
+        element_ids.collisions.push(collision);
+    }
+
+    /// The elements that were drawn with the same id as another in the last frame.
+    #[cfg(test)]
+    pub(crate) fn element_id_collisions(&self) -> &[ElementIdCollision] {
+        &self.window.element_ids.collisions
+    }
+
+    pub(crate) fn begin_element_id_frame(&mut self) {
+        let element_ids = &mut self.window.element_ids;
+        element_ids.prepainted.clear();
+        element_ids.collisions.clear();
+    }
+}
@@ -0,0 +1,906 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use collections::FxHashMap;
+
+use crate::{
+    fill, point, px, size, AnchorCorner, Bounds, Element, ElementId, EntityId, FrameDamage,
+    FrameRequest, GlobalElementId, Hsla, ImageCacheStats, IntoElement, LayoutId, MountRegion,
+    Pixels, PlatformInput, SharedString, Style, TextLayoutCacheStats, WindowContext,
+};
+
+/// Frames taking longer than this to draw are recorded as long frames by default.
+pub const DEFAULT_LONG_FRAME_THRESHOLD: Duration = Duration::from_millis(8);
+/// The number of frames kept by default.
+pub const DEFAULT_FRAME_HISTORY: usize = 120;
+/// The number of long frames kept.
+const LONG_FRAME_HISTORY: usize = 32;
+/// The number of views recorded for each long frame.
+const LONG_FRAME_SLOWEST_VIEWS: usize = 5;
+/// The number of elements whose paint counts are recorded for each frame.
+const PAINT_COUNTS_TOP_ELEMENTS: usize = 10;
+
+const OVERLAY_SIZE: crate::Size<Pixels> = size(px(180.), px(48.));
+const OVERLAY_MARGIN: Pixels = px(8.);
+const OVERLAY_BAR_WIDTH: Pixels = px(2.);
+
+/// The time a window spent drawing a frame, split into its phases.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameTiming {
+    /// Rendering views, laying out the element tree and prepainting it.
+    pub layout: Duration,
+    /// Painting the element tree into the scene.
+    pub paint: Duration,
+    /// Handing the scene to the platform to be drawn. This is zero for frames that were drawn
+    /// without being presented.
+    pub submit: Duration,
+    /// The last input event dispatched to the window before the frame was drawn, if there was
+    /// one since the previous frame.
+    pub trigger: Option<SharedString>,
+    /// The requests for the frame made with [`WindowContext::request_frame`], if any.
+    pub requests: Vec<FrameRequest>,
+    /// How many of the text layouts needed for the frame were found in the window's text layout
+    /// cache, and how many had to be shaped.
+    pub text_layouts: TextLayoutCacheStats,
+    /// How many of the images needed for the frame were found decoded in the image cache, how
+    /// many started loading, and how much the cache held afterwards.
+    pub images: ImageCacheStats,
+    /// The parts of the window that changed since the previous frame. The whole window is still
+    /// redrawn.
+    pub damage: FrameDamage,
+    /// How many elements were painted.
+    pub painted_elements: usize,
+    /// How many elements weren't painted because they were out of view. The elements within
+    /// them aren't counted.
+    pub culled_elements: usize,
+    /// The primitives painted into the scene, and the elements that painted the most quads.
+    pub paint_counts: FramePaintCounts,
+}
+
+impl FrameTiming {
+    /// The total time spent on the frame.
+    pub fn total(&self) -> Duration {
+        self.layout + self.paint + self.submit
+    }
+}
+
+/// How many of each kind of primitive were painted into the scene.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PaintCounts {
+    /// Quads, including the backgrounds and borders of elements.
+    pub quads: usize,
+    /// Runs of glyphs painted in a single font.
+    pub glyph_runs: usize,
+    /// Images, including regions of images.
+    pub images: usize,
+    /// Content masks pushed while painting.
+    pub clips: usize,
+    /// Layers pushed into the scene.
+    pub layers: usize,
+}
+
+impl PaintCounts {
+    fn add(&mut self, primitive: PaintPrimitive) {
+        match primitive {
+            PaintPrimitive::Quad => self.quads += 1,
+            PaintPrimitive::GlyphRun => self.glyph_runs += 1,
+            PaintPrimitive::Image => self.images += 1,
+            PaintPrimitive::Clip => self.clips += 1,
+            PaintPrimitive::Layer => self.layers += 1,
+        }
+    }
+}
+
+/// The kinds of primitive counted in [`PaintCounts`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PaintPrimitive {
+    Quad,
+    GlyphRun,
+    Image,
+    Clip,
+    Layer,
+}
+
+/// The primitives painted by the elements of one type and id during a frame. They only include
+/// what the elements painted themselves, not what the elements within them painted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElementPaintCounts {
+    /// The name of the elements' type.
+    pub element_type: &'static str,
+    /// The elements' id, or `None` for the elements of this type without one.
+    pub element_id: Option<ElementId>,
+    /// What the elements painted.
+    pub counts: PaintCounts,
+}
+
+/// The primitives painted during a frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FramePaintCounts {
+    /// Everything painted in the frame.
+    pub total: PaintCounts,
+    /// The elements that painted the most quads, most first.
+    pub top_elements: Vec<ElementPaintCounts>,
+}
+
+impl std::fmt::Display for FramePaintCounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let total = &self.total;
+        writeln!(
+            f,
+            "{} quads, {} glyph runs, {} images, {} clips, {} layers",
+            total.quads, total.glyph_runs, total.images, total.clips, total.layers
+        )?;
+        writeln!(
+            f,
+            "{:>6} {:>6} {:>6} {:>6} {:>6}  element",
+            "quads", "glyphs", "images", "clips", "layers"
+        )?;
+        for element in &self.top_elements {
+            let counts = &element.counts;
+            write!(
+                f,
+                "{:>6} {:>6} {:>6} {:>6} {:>6}  {}",
+                counts.quads,
+                counts.glyph_runs,
+                counts.images,
+                counts.clips,
+                counts.layers,
+                element.element_type
+            )?;
+            if let Some(element_id) = &element.element_id {
+                write!(f, " `{element_id}`")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// The element a primitive is attributed to: its type and id.
+pub(crate) type PaintSource = (&'static str, Option<ElementId>);
+
+/// The time spent in a single view during a frame, excluding the views it contains.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewTiming {
+    /// The view's entity.
+    pub entity_id: EntityId,
+    /// The name of the view's type.
+    pub view_type: &'static str,
+    /// The time spent rendering, laying out, prepainting and painting the view.
+    pub duration: Duration,
+}
+
+/// A frame that took longer than the long frame threshold, along with the views that took the
+/// most time to draw.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LongFrame {
+    /// The timing of the frame.
+    pub timing: FrameTiming,
+    /// The views that took the most time during the frame, slowest first.
+    pub slowest_views: Vec<ViewTiming>,
+}
+
+/// Collects the time spent drawing a window's frames, see [`WindowContext::frame_diagnostics`].
+///
+/// Collection is disabled until [`FrameDiagnostics::set_enabled`] is called or the overlay is
+/// shown with [`WindowContext::toggle_frame_diagnostics_overlay`].
+pub struct FrameDiagnostics {
+    enabled: bool,
+    capacity: usize,
+    long_frame_threshold: Duration,
+    frames: VecDeque<FrameTiming>,
+    long_frames: VecDeque<LongFrame>,
+    phase_start: Option<Instant>,
+    excluded: Duration,
+    pending_layout: Duration,
+    pending_text_layouts: TextLayoutCacheStats,
+    pending_images: ImageCacheStats,
+    pending_damage: FrameDamage,
+    pending_painted_elements: usize,
+    pending_culled_elements: usize,
+    pending_paint_counts: PaintCounts,
+    element_paint_counts: Vec<ElementPaintCounts>,
+    element_paint_count_indices: FxHashMap<PaintSource, usize>,
+    /// Indices into `element_paint_counts` of the elements being drawn, innermost last.
+    paint_stack: Vec<usize>,
+    /// Whether the last frame in `frames` is waiting for its scene to be presented.
+    awaiting_submit: bool,
+    /// Whether the last long frame was recorded before its scene was presented.
+    long_frame_awaiting_submit: bool,
+    trigger: Option<SharedString>,
+    requests: Vec<FrameRequest>,
+    view_stack: Vec<ViewFrame>,
+    view_timings: FxHashMap<EntityId, ViewTiming>,
+}
+
+/// A view that's being drawn, and the time spent in the views it contains so far.
+struct ViewFrame {
+    start: Instant,
+    children: Duration,
+}
+
+impl Default for FrameDiagnostics {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            capacity: DEFAULT_FRAME_HISTORY,
+            long_frame_threshold: DEFAULT_LONG_FRAME_THRESHOLD,
+            frames: VecDeque::new(),
+            long_frames: VecDeque::new(),
+            phase_start: None,
+            excluded: Duration::ZERO,
+            pending_layout: Duration::ZERO,
+            pending_text_layouts: TextLayoutCacheStats::default(),
+            pending_images: ImageCacheStats::default(),
+            pending_damage: FrameDamage::default(),
+            pending_painted_elements: 0,
+            pending_culled_elements: 0,
+            pending_paint_counts: PaintCounts::default(),
+            element_paint_counts: Vec::new(),
+            element_paint_count_indices: FxHashMap::default(),
+            paint_stack: Vec::new(),
+            awaiting_submit: false,
+            long_frame_awaiting_submit: false,
+            trigger: None,
+            requests: Vec::new(),
+            view_stack: Vec::new(),
+            view_timings: FxHashMap::default(),
+        }
+    }
+}
+
+impl FrameDiagnostics {
+    /// Whether frames are being recorded.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Start or stop recording frames. Stopping keeps the frames recorded so far.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.phase_start = None;
+        self.view_stack.clear();
+        self.paint_stack.clear();
+    }
+
+    /// The number of frames kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Set the number of frames kept, dropping the oldest ones if there are more.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.frames.len() > self.capacity {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Frames taking longer than this are recorded as long frames.
+    pub fn long_frame_threshold(&self) -> Duration {
+        self.long_frame_threshold
+    }
+
+    /// Set the time after which a frame is recorded as a long frame.
+    pub fn set_long_frame_threshold(&mut self, threshold: Duration) {
+        self.long_frame_threshold = threshold;
+    }
+
+    /// The recorded frames, oldest first.
+    pub fn frames(&self) -> impl ExactSizeIterator<Item = &FrameTiming> + DoubleEndedIterator {
+        self.frames.iter()
+    }
+
+    /// The most recently recorded frame.
+    pub fn last_frame(&self) -> Option<&FrameTiming> {
+        self.frames.back()
+    }
+
+    /// The recorded long frames, oldest first.
+    pub fn long_frames(&self) -> impl ExactSizeIterator<Item = &LongFrame> + DoubleEndedIterator {
+        self.long_frames.iter()
+    }
+
+    /// Forget all recorded frames.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.long_frames.clear();
+        self.awaiting_submit = false;
+    }
+
+    pub(crate) fn record_input(&mut self, event: &PlatformInput) {
+        if !self.enabled {
+            return;
+        }
+        self.trigger = Some(match event {
+            PlatformInput::KeyDown(event) => format!("key down {}", event.keystroke).into(),
+            PlatformInput::KeyUp(event) => format!("key up {}", event.keystroke).into(),
+            PlatformInput::ModifiersChanged(_) => "modifiers changed".into(),
+            PlatformInput::MouseDown(event) => format!("{:?} mouse down", event.button).into(),
+            PlatformInput::MouseUp(event) => format!("{:?} mouse up", event.button).into(),
+            PlatformInput::MouseMove(_) => "mouse move".into(),
+            PlatformInput::MouseExited(_) => "mouse exited".into(),
+            PlatformInput::ScrollWheel(_) => "scroll wheel".into(),
+            PlatformInput::FileDrop(_) => "file drop".into(),
+        });
+    }
+
+    pub(crate) fn record_frame_requests(
+        &mut self,
+        requests: impl IntoIterator<Item = FrameRequest>,
+    ) {
+        if self.enabled {
+            self.requests.extend(requests);
+        }
+    }
+
+    pub(crate) fn begin_frame(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        self.awaiting_submit = false;
+        self.excluded = Duration::ZERO;
+        self.view_stack.clear();
+        self.view_timings.clear();
+        self.element_paint_counts.clear();
+        self.element_paint_count_indices.clear();
+        self.paint_stack.clear();
+        self.phase_start = Some(Instant::now());
+    }
+
+    pub(crate) fn begin_paint(&mut self) {
+        let Some(phase_start) = self.phase_start else {
+            return;
+        };
+        let now = Instant::now();
+        self.pending_layout = self.phase_duration(phase_start, now);
+        self.phase_start = Some(now);
+    }
+
+    pub(crate) fn end_frame(&mut self) {
+        let Some(phase_start) = self.phase_start.take() else {
+            return;
+        };
+        let paint = self.phase_duration(phase_start, Instant::now());
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(FrameTiming {
+            layout: self.pending_layout,
+            paint,
+            submit: Duration::ZERO,
+            trigger: self.trigger.take(),
+            requests: std::mem::take(&mut self.requests),
+            text_layouts: std::mem::take(&mut self.pending_text_layouts),
+            images: std::mem::take(&mut self.pending_images),
+            damage: std::mem::take(&mut self.pending_damage),
+            painted_elements: std::mem::take(&mut self.pending_painted_elements),
+            culled_elements: std::mem::take(&mut self.pending_culled_elements),
+            paint_counts: self.take_paint_counts(),
+        });
+        self.awaiting_submit = true;
+        self.long_frame_awaiting_submit = false;
+        self.check_long_frame();
+    }
+
+    pub(crate) fn record_text_layouts(&mut self, stats: TextLayoutCacheStats) {
+        if self.phase_start.is_some() {
+            self.pending_text_layouts = stats;
+        }
+    }
+
+    pub(crate) fn record_images(&mut self, stats: ImageCacheStats) {
+        if self.phase_start.is_some() {
+            self.pending_images = stats;
+        }
+    }
+
+    pub(crate) fn record_damage(&mut self, damage: FrameDamage) {
+        if self.phase_start.is_some() {
+            self.pending_damage = damage;
+        }
+    }
+
+    pub(crate) fn record_paint(&mut self, culled: bool) {
+        if self.phase_start.is_some() {
+            if culled {
+                self.pending_culled_elements += 1;
+            } else {
+                self.pending_painted_elements += 1;
+            }
+        }
+    }
+
+    /// Attribute the primitives painted from now on to the element of the given type being drawn
+    /// with the given id, until [`Self::end_element`] is called. Returns whether the element was
+    /// begun, which it isn't while no frame is being recorded, and only then should it be ended.
+    #[must_use]
+    pub(crate) fn begin_element(
+        &mut self,
+        element_type: &'static str,
+        global_id: Option<&GlobalElementId>,
+    ) -> bool {
+        let element_id = global_id.and_then(|global_id| global_id.last().cloned());
+        self.push_paint_source((element_type, element_id))
+    }
+
+    pub(crate) fn end_element(&mut self) {
+        self.paint_stack.pop();
+    }
+
+    /// The element the primitives painted now are attributed to.
+    pub(crate) fn paint_source(&self) -> Option<PaintSource> {
+        let ix = *self.paint_stack.last()?;
+        let counts = &self.element_paint_counts[ix];
+        Some((counts.element_type, counts.element_id.clone()))
+    }
+
+    /// Attribute the primitives painted from now on to the given element, until
+    /// [`Self::end_element`] is called. Returns whether the element was begun, like
+    /// [`Self::begin_element`].
+    #[must_use]
+    pub(crate) fn push_paint_source(&mut self, source: PaintSource) -> bool {
+        if self.phase_start.is_none() {
+            return false;
+        }
+        let ix = match self.element_paint_count_indices.get(&source) {
+            Some(ix) => *ix,
+            None => {
+                let ix = self.element_paint_counts.len();
+                self.element_paint_counts.push(ElementPaintCounts {
+                    element_type: source.0,
+                    element_id: source.1.clone(),
+                    counts: PaintCounts::default(),
+                });
+                self.element_paint_count_indices.insert(source, ix);
+                ix
+            }
+        };
+        self.paint_stack.push(ix);
+        true
+    }
+
+    pub(crate) fn record_primitive(&mut self, primitive: PaintPrimitive) {
+        if self.phase_start.is_none() {
+            return;
+        }
+        self.pending_paint_counts.add(primitive);
+        if let Some(ix) = self.paint_stack.last() {
+            self.element_paint_counts[*ix].counts.add(primitive);
+        }
+    }
+
+    fn take_paint_counts(&mut self) -> FramePaintCounts {
+        let mut top_elements = self
+            .element_paint_counts
+            .drain(..)
+            .filter(|element| element.counts != PaintCounts::default())
+            .collect::<Vec<_>>();
+        top_elements.sort_by(|a, b| b.counts.quads.cmp(&a.counts.quads));
+        top_elements.truncate(PAINT_COUNTS_TOP_ELEMENTS);
+        self.element_paint_count_indices.clear();
+        FramePaintCounts {
+            total: std::mem::take(&mut self.pending_paint_counts),
+            top_elements,
+        }
+    }
+
+    pub(crate) fn record_submit(&mut self, submit: Duration) {
+        if !self.awaiting_submit {
+            return;
+        }
+        self.awaiting_submit = false;
+        if let Some(frame) = self.frames.back_mut() {
+            frame.submit = submit;
+        }
+        self.check_long_frame();
+        self.long_frame_awaiting_submit = false;
+    }
+
+    /// Leave the given time out of the phase that's being measured, for work done on behalf of
+    /// the diagnostics themselves.
+    pub(crate) fn exclude(&mut self, duration: Duration) {
+        if self.phase_start.is_some() {
+            self.excluded += duration;
+        }
+    }
+
+    pub(crate) fn begin_view(&mut self) {
+        if self.phase_start.is_some() {
+            self.view_stack.push(ViewFrame {
+                start: Instant::now(),
+                children: Duration::ZERO,
+            });
+        }
+    }
+
+    pub(crate) fn end_view(&mut self, entity_id: EntityId, view_type: &'static str) {
+        let Some(view) = self.view_stack.pop() else {
+            return;
+        };
+        let elapsed = view.start.elapsed();
+        if let Some(parent) = self.view_stack.last_mut() {
+            parent.children += elapsed;
+        }
+        self.view_timings
+            .entry(entity_id)
+            .or_insert_with(|| ViewTiming {
+                entity_id,
+                view_type,
+                duration: Duration::ZERO,
+            })
+            .duration += elapsed.saturating_sub(view.children);
+    }
+
+    fn phase_duration(&mut self, start: Instant, end: Instant) -> Duration {
+        end.saturating_duration_since(start)
+            .saturating_sub(std::mem::take(&mut self.excluded))
+    }
+
+    /// Record the last frame as a long frame if it exceeded the threshold, or update its timing if
+    /// it was already recorded before its scene was presented.
+    fn check_long_frame(&mut self) {
+        let Some(frame) = self.frames.back() else {
+            return;
+        };
+        if frame.total() <= self.long_frame_threshold {
+            return;
+        }
+
+        if self.long_frame_awaiting_submit {
+            if let Some(long_frame) = self.long_frames.back_mut() {
+                long_frame.timing = frame.clone();
+            }
+            return;
+        }
+
+        let mut slowest_views = self.view_timings.values().cloned().collect::<Vec<_>>();
+        slowest_views.sort_by(|a, b| b.duration.cmp(&a.duration));
+        slowest_views.truncate(LONG_FRAME_SLOWEST_VIEWS);
+
+        if self.long_frames.len() == LONG_FRAME_HISTORY {
+            self.long_frames.pop_front();
+        }
+        self.long_frames.push_back(LongFrame {
+            timing: frame.clone(),
+            slowest_views,
+        });
+        self.long_frame_awaiting_submit = self.awaiting_submit;
+    }
+}
+
+/// A small graph of the most recent frame times, drawn in the bottom right corner of the window
+/// while the frame diagnostics overlay is shown. Each bar is a frame, with the long frames drawn
+/// in red, and the line across the graph marks the long frame threshold.
+pub(crate) struct FrameDiagnosticsOverlay;
+
+impl Element for FrameDiagnosticsOverlay {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut style = Style::default();
+        style.size.width = OVERLAY_SIZE.width.into();
+        style.size.height = OVERLAY_SIZE.height.into();
+        (cx.request_layout(style, None), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _cx: &mut WindowContext,
+    ) {
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        let diagnostics = &cx.window.frame_diagnostics;
+        let threshold = diagnostics
+            .long_frame_threshold
+            .max(Duration::from_millis(1));
+        // The graph spans twice the threshold, so the threshold line sits in the middle.
+        let scale = bounds.size.height.0 / (threshold.as_secs_f32() * 2.);
+        let max_bars = (bounds.size.width / OVERLAY_BAR_WIDTH) as usize;
+        let bars = diagnostics
+            .frames
+            .iter()
+            .rev()
+            .take(max_bars)
+            .map(|frame| {
+                let total = frame.total();
+                let height = px((total.as_secs_f32() * scale).min(bounds.size.height.0));
+                (height, total > diagnostics.long_frame_threshold)
+            })
+            .collect::<Vec<_>>();
+
+        let background: Hsla = crate::black().opacity(0.6);
+        let threshold_color: Hsla = crate::white().opacity(0.5);
+        let bar_color: Hsla = crate::green().opacity(0.8);
+        let long_bar_color: Hsla = crate::red();
+
+        cx.paint_quad(fill(bounds, background));
+        for (ix, (height, is_long)) in bars.into_iter().enumerate() {
+            let x = bounds.right() - OVERLAY_BAR_WIDTH * (ix + 1) as f32;
+            let bar = Bounds::new(
+                point(x, bounds.bottom() - height),
+                size(OVERLAY_BAR_WIDTH, height),
+            );
+            cx.paint_quad(fill(bar, if is_long { long_bar_color } else { bar_color }));
+        }
+        let threshold_y = bounds.bottom() - bounds.size.height / 2.;
+        cx.paint_quad(fill(
+            Bounds::new(
+                point(bounds.left(), threshold_y),
+                size(bounds.size.width, px(1.)),
+            ),
+            threshold_color,
+        ));
+    }
+}
+
+impl IntoElement for FrameDiagnosticsOverlay {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl FrameDiagnosticsOverlay {
+    /// Where the overlay is mounted in the window.
+    pub(crate) const REGION: MountRegion = MountRegion::Corner {
+        corner: AnchorCorner::BottomRight,
+        size: OVERLAY_SIZE,
+        margin: OVERLAY_MARGIN,
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::FrameDiagnostics;
+    use crate::{
+        self as gpui, deferred, div, prelude::FluentBuilder, px, red, ElementId, FocusHandle,
+        InteractiveElement, IntoElement, KeyDownEvent, ParentElement, Render, Styled,
+        TestAppContext, ViewContext, VisualTestContext,
+    };
+    use std::time::Duration;
+
+    struct Counter {
+        count: usize,
+        focus_handle: FocusHandle,
+    }
+
+    impl Render for Counter {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .track_focus(&self.focus_handle)
+                .on_key_down(cx.listener(|this, _: &KeyDownEvent, cx| {
+                    this.count += 1;
+                    cx.notify();
+                }))
+                .child(format!("{}", self.count))
+        }
+    }
+
+    #[gpui::test]
+    fn test_frames_are_recorded(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|cx| Counter {
+            count: 0,
+            focus_handle: cx.focus_handle(),
+        });
+        cx.update(|cx| {
+            cx.frame_diagnostics_mut().set_enabled(true);
+            cx.frame_diagnostics_mut().set_capacity(3);
+        });
+        view.update(cx, |view, cx| cx.focus(&view.focus_handle));
+
+        for _ in 0..5 {
+            cx.simulate_keystrokes("a");
+        }
+        cx.update(|cx| {
+            let diagnostics = cx.frame_diagnostics();
+            assert_eq!(diagnostics.frames().len(), 3);
+            assert_eq!(
+                diagnostics.last_frame().unwrap().trigger.as_deref(),
+                Some("key down a")
+            );
+            // None of the frames were long enough to be recorded.
+            assert_eq!(diagnostics.long_frames().len(), 0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_long_frames_record_slowest_views(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|cx| Counter {
+            count: 0,
+            focus_handle: cx.focus_handle(),
+        });
+        cx.update(|cx| {
+            let diagnostics = cx.frame_diagnostics_mut();
+            diagnostics.set_enabled(true);
+            diagnostics.set_long_frame_threshold(Duration::ZERO);
+        });
+        view.update(cx, |view, cx| cx.focus(&view.focus_handle));
+
+        cx.simulate_keystrokes("b");
+        cx.update(|cx| {
+            let diagnostics = cx.frame_diagnostics();
+            let long_frame = diagnostics.long_frames().last().unwrap();
+            assert_eq!(long_frame.timing.trigger.as_deref(), Some("key down b"));
+            assert_eq!(long_frame.slowest_views.len(), 1);
+            assert_eq!(long_frame.slowest_views[0].entity_id, view.entity_id());
+            assert!(long_frame.slowest_views[0].view_type.ends_with("Counter"));
+        });
+    }
+
+    struct Label {
+        visible: bool,
+    }
+
+    impl Render for Label {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().when(self.visible, |this| this.child("Hello"))
+        }
+    }
+
+    #[gpui::test]
+    fn test_text_layout_cache_hits_are_recorded(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| Label { visible: false });
+        cx.update(|cx| cx.frame_diagnostics_mut().set_enabled(true));
+
+        let draw = |visible: bool, cx: &mut VisualTestContext| {
+            view.update(cx, |view, cx| {
+                view.visible = visible;
+                cx.notify();
+            });
+            cx.run_until_parked();
+            cx.update(|cx| cx.frame_diagnostics().last_frame().unwrap().text_layouts)
+        };
+
+        let first = draw(true, cx);
+        assert!(first.misses > 0);
+        let hidden = draw(false, cx);
+        assert_eq!(hidden.hits + hidden.misses, 0);
+        // The label went unused for a frame, but its layout was retained.
+        let shown_again = draw(true, cx);
+        assert!(shown_again.hits > 0);
+        assert_eq!(shown_again.misses, 0);
+    }
+
+    #[gpui::test]
+    fn test_overlay_enables_collection(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|cx| Counter {
+            count: 0,
+            focus_handle: cx.focus_handle(),
+        });
+        cx.update(|cx| {
+            cx.toggle_frame_diagnostics_overlay();
+            assert!(cx.frame_diagnostics_overlay_visible());
+            assert!(cx.frame_diagnostics().enabled());
+        });
+        cx.run_until_parked();
+        cx.update(|cx| {
+            assert_eq!(cx.frame_diagnostics().frames().len(), 1);
+            cx.toggle_frame_diagnostics_overlay();
+            assert!(!cx.frame_diagnostics_overlay_visible());
+        });
+    }
+
+    struct Filled;
+
+    impl Render for Filled {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size(px(10.)).bg(red())
+        }
+    }
+
+    #[gpui::test]
+    fn test_a_filled_div_paints_one_quad(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| Filled);
+        cx.update(|cx| cx.frame_diagnostics_mut().set_enabled(true));
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+
+        cx.update(|cx| {
+            let paint_counts = &cx.frame_diagnostics().last_frame().unwrap().paint_counts;
+            assert_eq!(paint_counts.total.quads, 1);
+            assert_eq!(paint_counts.top_elements.len(), 1);
+            assert!(paint_counts.top_elements[0].element_type.ends_with("Div"));
+            assert_eq!(paint_counts.top_elements[0].counts.quads, 1);
+        });
+    }
+
+    struct Nested;
+
+    impl Render for Nested {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("outer")
+                .size(px(100.))
+                .bg(red())
+                .child(
+                    div()
+                        .id("inner")
+                        .size(px(50.))
+                        .bg(red())
+                        .child(div().size(px(10.)).bg(red())),
+                )
+                .child(deferred(
+                    div().id("overlay").size(px(10.)).bg(red()).child("Hi"),
+                ))
+        }
+    }
+
+    #[test]
+    fn test_elements_are_only_ended_when_they_were_begun() {
+        let mut diagnostics = FrameDiagnostics::default();
+        // The outer element is drawn before recording starts, so it isn't begun.
+        let outer = diagnostics.begin_element("Outer", None);
+        assert!(!outer);
+        diagnostics.set_enabled(true);
+        diagnostics.begin_frame();
+        let inner = diagnostics.begin_element("Inner", None);
+        assert!(inner);
+        assert_eq!(diagnostics.paint_source(), Some(("Inner", None)));
+        diagnostics.end_element();
+        // Nothing is attributed to the inner element once it has ended.
+        assert_eq!(diagnostics.paint_source(), None);
+    }
+
+    #[gpui::test]
+    fn test_paint_counts_are_attributed_to_the_innermost_element(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| Nested);
+        cx.update(|cx| cx.frame_diagnostics_mut().set_enabled(true));
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+
+        cx.update(|cx| {
+            let paint_counts = &cx.frame_diagnostics().last_frame().unwrap().paint_counts;
+            let quads = |id: Option<&str>| {
+                let id = id.map(ElementId::from);
+                paint_counts
+                    .top_elements
+                    .iter()
+                    .find(|element| {
+                        element.element_type.contains("Div") && element.element_id == id
+                    })
+                    .map_or(0, |element| element.counts.quads)
+            };
+            assert_eq!(quads(Some("outer")), 1);
+            // The children's quads count toward the children rather than the parents.
+            assert_eq!(quads(Some("inner")), 1);
+            assert_eq!(quads(None), 1);
+            // The deferred overlay is painted after the rest of the window, but its quad and its
+            // text are still counted.
+            assert_eq!(quads(Some("overlay")), 1);
+            assert_eq!(paint_counts.total.quads, 4);
+            assert_eq!(paint_counts.total.glyph_runs, 1);
+            assert_eq!(
+                paint_counts
+                    .top_elements
+                    .iter()
+                    .map(|element| element.counts.glyph_runs)
+                    .sum::<usize>(),
+                1
+            );
+        });
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use collections::FxHashMap;
+
+use crate::{ElementId, EntityId, ViewContext, WindowContext};
+
+/// How far back [`WindowContext::frame_requesters`] looks.
+pub const FRAME_REQUESTER_WINDOW: Duration = Duration::from_secs(1);
+/// The number of requesters logged by [`WindowContext::log_frame_requesters`].
+const LOGGED_FRAME_REQUESTERS: usize = 10;
+
+/// Why a frame was requested with [`WindowContext::request_frame`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FrameReason {
+    /// An animation or transition is playing.
+    Animation,
+    /// A scroll container is animating or coasting to its offset.
+    Scroll,
+    /// Something is being dragged, or a drag is scrolling its container.
+    Drag,
+    /// An element's layout depends on the frame that was just drawn, e.g. one that rearranges
+    /// itself at a width it only learns in prepaint.
+    Layout,
+    /// A cursor blinked.
+    CursorBlink,
+    /// Work the frame was waiting on, such as loading an asset, has finished.
+    AsyncCompletion,
+}
+
+/// A request for the next frame, made with [`WindowContext::request_frame`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FrameRequest {
+    /// Why the frame was requested.
+    pub reason: FrameReason,
+    /// The id of the element being drawn when the frame was requested, if it had one.
+    pub element_id: Option<ElementId>,
+}
+
+/// Something that's been requesting frames, see [`WindowContext::frame_requesters`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameRequester {
+    /// What was requested.
+    pub request: FrameRequest,
+    /// The number of frames it was requested for.
+    pub frames: usize,
+}
+
+/// The frames requested of a window. Requests are held until the platform asks for the next
+/// frame, so that duplicates coalesce into drawing it once.
+#[derive(Default)]
+pub(crate) struct FrameScheduler {
+    /// The requests for the next frame, and the views to draw again for them, or `None` to draw
+    /// the whole window.
+    pending: Vec<(FrameRequest, Option<EntityId>)>,
+    /// The requests flushed into frames, oldest first.
+    history: VecDeque<(Instant, FrameRequest)>,
+}
+
+impl<'a> WindowContext<'a> {
+    /// Draw the view being drawn again in the next frame, for the given reason. Requests for the
+    /// same view from the same element are coalesced until the frame is drawn, and the requests
+    /// that caused each frame are recorded in the [frame diagnostics](Self::frame_diagnostics).
+    ///
+    /// Request frames only while something on screen is changing: a window that's settled
+    /// shouldn't request any, which [`Self::frame_requesters`] helps check.
+    pub fn request_frame(&mut self, reason: FrameReason) {
+        let request = FrameRequest {
+            reason,
+            element_id: self.window.element_id_stack.last().cloned(),
+        };
+        let view_id = self.parent_view_id();
+        self.schedule_frame(request, view_id);
+    }
+
+    /// Request the next frame, in which the given view is drawn again, or the whole window if
+    /// there isn't one.
+    pub(crate) fn schedule_frame(&mut self, request: FrameRequest, view_id: Option<EntityId>) {
+        let scheduler = &mut self.window.frame_scheduler;
+        if scheduler
+            .pending
+            .iter()
+            .any(|pending| pending.0 == request && pending.1 == view_id)
+        {
+            return;
+        }
+        let first = scheduler.pending.is_empty();
+        scheduler.pending.push((request, view_id));
+        if first {
+            self.on_next_frame(|cx| cx.flush_frame_requests());
+        }
+    }
+
+    fn flush_frame_requests(&mut self) {
+        let pending = std::mem::take(&mut self.window.frame_scheduler.pending);
+        let now = self.background_executor().now();
+        let scheduler = &mut self.window.frame_scheduler;
+        scheduler
+            .history
+            .extend(pending.iter().map(|(request, _)| (now, request.clone())));
+        prune_history(&mut scheduler.history, now);
+        self.window
+            .frame_diagnostics
+            .record_frame_requests(pending.iter().map(|(request, _)| request.clone()));
+
+        for (_, view_id) in pending {
+            if let Some(view_id) = view_id {
+                self.notify(view_id)
+            } else {
+                self.refresh()
+            }
+        }
+    }
+
+    /// What's requested frames of this window over the last [`FRAME_REQUESTER_WINDOW`], and how
+    /// many, most first. This is empty while the window is settled.
+    pub fn frame_requesters(&mut self) -> Vec<FrameRequester> {
+        let now = self.background_executor().now();
+        let history = &mut self.window.frame_scheduler.history;
+        prune_history(history, now);
+
+        let mut frames = FxHashMap::<&FrameRequest, usize>::default();
+        for (_, request) in history.iter() {
+            *frames.entry(request).or_default() += 1;
+        }
+        let mut requesters = frames
+            .into_iter()
+            .map(|(request, frames)| FrameRequester {
+                request: request.clone(),
+                frames,
+            })
+            .collect::<Vec<_>>();
+        requesters.sort_by(|a, b| {
+            b.frames
+                .cmp(&a.frames)
+                .then_with(|| format!("{:?}", a.request).cmp(&format!("{:?}", b.request)))
+        });
+        requesters
+    }
+
+    /// Log what's been requesting frames of this window, to find out why it keeps drawing.
+    pub fn log_frame_requesters(&mut self) {
+        let requesters = self.frame_requesters();
+        if requesters.is_empty() {
+            log::info!(
+                "no frames were requested in the last {:?}",
+                FRAME_REQUESTER_WINDOW
+            );
+            return;
+        }
+        let mut report = format!("frames requested in the last {FRAME_REQUESTER_WINDOW:?}:");
+        for requester in requesters.iter().take(LOGGED_FRAME_REQUESTERS) {
+            report.push_str(&format!(
+                "\n{:>5}  {:?}",
+                requester.frames, requester.request.reason
+            ));
+            if let Some(element_id) = &requester.request.element_id {
+                report.push_str(&format!(" `{element_id}`"));
+            }
+        }
+        log::info!("{report}");
+    }
+}
+
+impl<'a, V: 'static> ViewContext<'a, V> {
+    /// Draw this view again in the next frame, for the given reason, see
+    /// [`WindowContext::request_frame`].
+    pub fn request_frame(&mut self, reason: FrameReason) {
+        let request = FrameRequest {
+            reason,
+            element_id: None,
+        };
+        let view_id = self.entity_id();
+        self.window_cx.schedule_frame(request, Some(view_id));
+    }
+}
+
+fn prune_history(history: &mut VecDeque<(Instant, FrameRequest)>, now: Instant) {
+    while history.front().map_or(false, |(time, _)| {
+        now.duration_since(*time) > FRAME_REQUESTER_WINDOW
+    }) {
+        history.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::{
+        self as gpui, div, prelude::*, px, Animation, AnimationExt, ElementId, TestAppContext,
+        ViewContext, VisualTestContext,
+    };
+
+    use super::{FrameReason, FrameRequest, FrameRequester, FRAME_REQUESTER_WINDOW};
+
+    /// A view with the kinds of elements that request frames while they change, none of which
+    /// are changing.
+    struct Settled;
+
+    impl Render for Settled {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("list")
+                .h(px(100.))
+                .overflow_y_scroll()
+                .child(
+                    div()
+                        .id("panel")
+                        .hover(|style| style.bg(gpui::red()))
+                        .child("Settled")
+                        .with_transition(
+                            "panel-width",
+                            100.,
+                            Animation::new(Duration::from_millis(200)),
+                            |panel, width| panel.w(px(width)),
+                        ),
+                )
+                .children((0..20usize).map(|ix| div().id(("item", ix)).h(px(20.))))
+        }
+    }
+
+    /// Draw frames 16ms apart for a second, as the platform would while frames are requested.
+    fn run_for_a_second(cx: &mut VisualTestContext) {
+        let frame = Duration::from_millis(16);
+        let mut elapsed = Duration::ZERO;
+        while elapsed < FRAME_REQUESTER_WINDOW {
+            cx.executor().advance_clock(frame);
+            cx.simulate_frame();
+            elapsed += frame;
+        }
+    }
+
+    #[gpui::test]
+    fn test_a_settled_window_requests_no_frames(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| Settled);
+        cx.run_until_parked();
+
+        run_for_a_second(cx);
+        let requesters = cx.update(|cx| cx.frame_requesters());
+        assert!(requesters.is_empty(), "{requesters:?}");
+        assert!(!cx.has_pending_frame_callbacks());
+    }
+
+    struct Pulsing;
+
+    impl Render for Pulsing {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size(px(10.)).with_animation(
+                "pulse",
+                Animation::new(Duration::from_millis(500)).repeat(),
+                |this, delta| this.opacity(delta),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_frame_requests_are_coalesced_and_recorded(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| Pulsing);
+        cx.update(|cx| cx.frame_diagnostics_mut().set_enabled(true));
+        cx.run_until_parked();
+
+        // Requests for the same view coalesce into the frame the animation asked for.
+        view.update(cx, |_, cx| {
+            cx.request_frame(FrameReason::Layout);
+            cx.request_frame(FrameReason::Layout);
+        });
+        cx.executor().advance_clock(Duration::from_millis(16));
+        cx.simulate_frame();
+
+        let animation = FrameRequest {
+            reason: FrameReason::Animation,
+            element_id: Some(ElementId::from("pulse")),
+        };
+        let layout = FrameRequest {
+            reason: FrameReason::Layout,
+            element_id: None,
+        };
+        cx.update(|cx| {
+            let requests = &cx.frame_diagnostics().last_frame().unwrap().requests;
+            assert_eq!(requests.len(), 2);
+            assert!(requests.contains(&animation) && requests.contains(&layout));
+        });
+
+        run_for_a_second(cx);
+        let requesters = cx.update(|cx| cx.frame_requesters());
+        assert_eq!(requesters.len(), 1);
+        assert_eq!(requesters[0].request, animation);
+        assert!(requesters[0].frames > 50);
+
+        view.update(cx, |_, cx| cx.request_frame(FrameReason::Layout));
+        cx.executor().advance_clock(Duration::from_millis(16));
+        cx.simulate_frame();
+        assert!(cx
+            .update(|cx| cx.frame_requesters())
+            .contains(&FrameRequester {
+                request: layout,
+                frames: 1,
+            }));
+    }
+}
@@ -0,0 +1,352 @@
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use collections::FxHashMap;
+
+use crate::{DrawPhase, GlobalElementId, Hitbox, HitboxId, Task, WindowContext};
+
+/// How long after the mouse leaves an element whose delayed hover fired that the handlers of
+/// other elements fire as soon as the mouse enters them, like moving along a menu bar.
+pub const HOVER_WARM_DURATION: Duration = Duration::from_millis(500);
+
+/// A handler registered with [`WindowContext::on_hover_delayed`].
+pub(crate) struct DelayedHover {
+    pub(crate) element_id: GlobalElementId,
+    pub(crate) hitbox_id: HitboxId,
+    pub(crate) delay: Duration,
+    pub(crate) handler: Rc<dyn Fn(&mut WindowContext)>,
+}
+
+impl Clone for DelayedHover {
+    fn clone(&self) -> Self {
+        Self {
+            element_id: GlobalElementId(self.element_id.0.clone()),
+            hitbox_id: self.hitbox_id,
+            delay: self.delay,
+            handler: self.handler.clone(),
+        }
+    }
+}
+
+/// How far along a stay of the mouse on an element with a delayed hover is.
+enum Residency {
+    /// Waiting out the delay, which is cancelled when the task is dropped.
+    Waiting(Task<()>),
+    Fired,
+    /// A mouse button was pressed, so the handler won't fire until the mouse leaves and comes
+    /// back.
+    Suppressed,
+}
+
+/// Tracks which of the elements with delayed hovers the mouse is resting on, across frames.
+#[derive(Default)]
+pub(crate) struct HoverScheduler {
+    residencies: FxHashMap<GlobalElementId, Residency>,
+    warm_until: Option<Instant>,
+}
+
+impl<'a> WindowContext<'a> {
+    /// Call the handler once the mouse has rested on the hitbox for the given delay, without
+    /// leaving it or pressing a button. The wait is cancelled if the mouse leaves or the element
+    /// stops being painted, and the handler won't be called again until the mouse comes back.
+    ///
+    /// While the mouse is on an element whose handler fired, and for [`HOVER_WARM_DURATION`]
+    /// after it leaves, the handlers of other elements fire without waiting as soon as the mouse
+    /// enters them.
+    ///
+    /// The handler is identified across frames by the id of the element being painted. Use
+    /// [`Self::with_element_namespace`] to register more than one for the same element.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn on_hover_delayed(
+        &mut self,
+        hitbox: &Hitbox,
+        delay: Duration,
+        handler: impl Fn(&mut WindowContext) + 'static,
+    ) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        let element_id = GlobalElementId(self.window.element_id_stack.clone());
+        self.window.next_frame.delayed_hovers.push(DelayedHover {
+            element_id,
+            hitbox_id: hitbox.id,
+            delay,
+            handler: Rc::new(handler),
+        });
+    }
+
+    /// Start waiting on the delayed hovers of the rendered frame that the mouse has entered, and
+    /// cancel the ones it left or that are gone. Pressing a mouse button suppresses the hovers of
+    /// everything under the mouse.
+    pub(crate) fn update_delayed_hovers(&mut self, pressed: bool) {
+        let now = self.background_executor().now();
+        let dragging = self.has_active_drag();
+        let window = &mut *self.window;
+        let hovered = window
+            .rendered_frame
+            .delayed_hovers
+            .iter()
+            .filter(|hover| !dragging && window.mouse_hit_test.0.contains(&hover.hitbox_id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let scheduler = &mut window.hover_scheduler;
+        let mut left_fired = false;
+        scheduler.residencies.retain(|element_id, residency| {
+            let still_hovered = hovered.iter().any(|hover| hover.element_id == *element_id);
+            if !still_hovered && matches!(residency, Residency::Fired) {
+                left_fired = true;
+            }
+            still_hovered
+        });
+        if left_fired {
+            scheduler.warm_until = Some(now + HOVER_WARM_DURATION);
+        }
+
+        if pressed {
+            for hover in &hovered {
+                scheduler.residencies.insert(
+                    GlobalElementId(hover.element_id.0.clone()),
+                    Residency::Suppressed,
+                );
+            }
+            return;
+        }
+
+        let warm = scheduler
+            .residencies
+            .values()
+            .any(|residency| matches!(residency, Residency::Fired))
+            || scheduler.warm_until.map_or(false, |until| now < until);
+        let mut fired = Vec::new();
+        for hover in hovered {
+            if self
+                .window
+                .hover_scheduler
+                .residencies
+                .contains_key(&hover.element_id)
+            {
+                continue;
+            }
+
+            let residency = if warm || hover.delay.is_zero() {
+                fired.push(hover.handler);
+                Residency::Fired
+            } else {
+                let element_id = GlobalElementId(hover.element_id.0.clone());
+                let delay = hover.delay;
+                Residency::Waiting(self.spawn(|mut cx| async move {
+                    cx.background_executor().timer(delay).await;
+                    cx.update(|cx| cx.fire_delayed_hover(element_id)).ok();
+                }))
+            };
+            self.window
+                .hover_scheduler
+                .residencies
+                .insert(hover.element_id, residency);
+        }
+
+        for handler in fired {
+            handler(self);
+        }
+    }
+
+    fn fire_delayed_hover(&mut self, element_id: GlobalElementId) {
+        let Some(residency) = self.window.hover_scheduler.residencies.get_mut(&element_id) else {
+            return;
+        };
+        if !matches!(residency, Residency::Waiting(_)) {
+            return;
+        }
+        *residency = Residency::Fired;
+
+        // Use the handler painted most recently, which sees the element's latest state.
+        let handler = self
+            .window
+            .rendered_frame
+            .delayed_hovers
+            .iter()
+            .find(|hover| hover.element_id == element_id)
+            .map(|hover| hover.handler.clone());
+        if let Some(handler) = handler {
+            handler(self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    use crate::{
+        self as gpui, div, point, prelude::*, px, Modifiers, MouseButton, Pixels, Point,
+        TestAppContext, ViewContext, VisualTestContext,
+    };
+
+    use super::HOVER_WARM_DURATION;
+
+    const DELAY: Duration = Duration::from_millis(300);
+
+    struct Buttons {
+        show_first: bool,
+        fired: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl Render for Buttons {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let button = |name: &'static str| {
+                let fired = self.fired.clone();
+                div()
+                    .id(name)
+                    .size(px(100.))
+                    .on_hover_delayed(DELAY, move |_| fired.borrow_mut().push(name))
+            };
+            div()
+                .flex()
+                .when(self.show_first, |this| this.child(button("first")))
+                .when(!self.show_first, |this| this.child(div().size(px(100.))))
+                .child(button("second"))
+        }
+    }
+
+    const FIRST: Point<Pixels> = point(px(50.), px(50.));
+    const SECOND: Point<Pixels> = point(px(150.), px(50.));
+    const OUTSIDE: Point<Pixels> = point(px(50.), px(300.));
+
+    fn add_buttons(
+        cx: &mut TestAppContext,
+    ) -> (
+        gpui::View<Buttons>,
+        &mut VisualTestContext,
+        Rc<RefCell<Vec<&'static str>>>,
+    ) {
+        let fired = Rc::new(RefCell::new(Vec::new()));
+        let (view, cx) = cx.add_window_view({
+            let fired = fired.clone();
+            |_| Buttons {
+                show_first: true,
+                fired,
+            }
+        });
+        cx.run_until_parked();
+        (view, cx, fired)
+    }
+
+    fn advance_clock(cx: &mut VisualTestContext, duration: Duration) {
+        cx.executor().advance_clock(duration);
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn test_hover_fires_once_after_the_delay(cx: &mut TestAppContext) {
+        let (_, cx, fired) = add_buttons(cx);
+
+        cx.simulate_mouse_move(FIRST, None, Modifiers::none());
+        advance_clock(cx, DELAY - Duration::from_millis(1));
+        assert!(fired.borrow().is_empty());
+        // Moving within the element doesn't restart the wait.
+        cx.simulate_mouse_move(FIRST + point(px(10.), px(0.)), None, Modifiers::none());
+        advance_clock(cx, Duration::from_millis(1));
+        assert_eq!(*fired.borrow(), ["first"]);
+
+        cx.simulate_mouse_move(FIRST, None, Modifiers::none());
+        advance_clock(cx, DELAY * 2);
+        assert_eq!(*fired.borrow(), ["first"]);
+    }
+
+    #[gpui::test]
+    fn test_hover_is_cancelled_by_leaving_or_disappearing(cx: &mut TestAppContext) {
+        let (view, cx, fired) = add_buttons(cx);
+
+        cx.simulate_mouse_move(FIRST, None, Modifiers::none());
+        advance_clock(cx, DELAY / 2);
+        cx.simulate_mouse_move(OUTSIDE, None, Modifiers::none());
+        advance_clock(cx, DELAY);
+        assert!(fired.borrow().is_empty());
+
+        cx.simulate_mouse_move(FIRST, None, Modifiers::none());
+        advance_clock(cx, DELAY / 2);
+        view.update(cx, |view, cx| {
+            view.show_first = false;
+            cx.notify();
+        });
+        advance_clock(cx, DELAY);
+        assert!(fired.borrow().is_empty());
+
+        // Pressing a button suppresses the hover until the mouse comes back.
+        cx.simulate_mouse_move(SECOND, None, Modifiers::none());
+        cx.simulate_mouse_down(SECOND, MouseButton::Left, Modifiers::none());
+        advance_clock(cx, DELAY);
+        assert!(fired.borrow().is_empty());
+        cx.simulate_mouse_move(OUTSIDE, None, Modifiers::none());
+        cx.simulate_mouse_move(SECOND, None, Modifiers::none());
+        advance_clock(cx, DELAY);
+        assert_eq!(*fired.borrow(), ["second"]);
+    }
+
+    #[gpui::test]
+    fn test_hovers_skip_the_delay_while_warm(cx: &mut TestAppContext) {
+        let (_, cx, fired) = add_buttons(cx);
+
+        cx.simulate_mouse_move(FIRST, None, Modifiers::none());
+        advance_clock(cx, DELAY);
+        assert_eq!(*fired.borrow(), ["first"]);
+
+        cx.simulate_mouse_move(SECOND, None, Modifiers::none());
+        assert_eq!(*fired.borrow(), ["first", "second"]);
+
+        // The window stays warm for a while after the mouse leaves.
+        cx.simulate_mouse_move(OUTSIDE, None, Modifiers::none());
+        advance_clock(cx, HOVER_WARM_DURATION / 2);
+        cx.simulate_mouse_move(FIRST, None, Modifiers::none());
+        assert_eq!(*fired.borrow(), ["first", "second", "first"]);
+
+        cx.simulate_mouse_move(OUTSIDE, None, Modifiers::none());
+        advance_clock(cx, HOVER_WARM_DURATION);
+        cx.simulate_mouse_move(SECOND, None, Modifiers::none());
+        assert_eq!(*fired.borrow(), ["first", "second", "first"]);
+        advance_clock(cx, DELAY);
+        assert_eq!(*fired.borrow(), ["first", "second", "first", "second"]);
+    }
+
+    struct Tooltip;
+
+    impl Render for Tooltip {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size(px(20.))
+        }
+    }
+
+    struct TooltipButton;
+
+    impl Render for TooltipButton {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("button")
+                .size(px(100.))
+                .tooltip(|cx| cx.new_view(|_| Tooltip).into())
+        }
+    }
+
+    #[gpui::test]
+    fn test_tooltips_wait_for_the_delay(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| TooltipButton);
+        cx.run_until_parked();
+        let tooltip_shown =
+            |cx: &mut VisualTestContext| cx.update(|cx| cx.window.tooltip_bounds.is_some());
+
+        cx.simulate_mouse_move(FIRST, None, Modifiers::none());
+        advance_clock(cx, crate::TOOLTIP_DELAY / 2);
+        assert!(!tooltip_shown(cx));
+        advance_clock(cx, crate::TOOLTIP_DELAY / 2);
+        assert!(tooltip_shown(cx));
+
+        cx.simulate_mouse_move(OUTSIDE, None, Modifiers::none());
+        assert!(!tooltip_shown(cx));
+    }
+}
@@ -0,0 +1,300 @@
+use std::rc::Rc;
+
+use collections::FxHashMap;
+use smallvec::SmallVec;
+
+use crate::{
+    DrawPhase, GlobalElementId, Hitbox, HitboxId, Pixels, Point, WindowContext, DRAG_THRESHOLD,
+};
+
+/// The hitbox of an element with an id, registered with
+/// [`WindowContext::register_interactive_region`].
+pub(crate) struct InteractiveRegion {
+    pub(crate) element_id: GlobalElementId,
+    pub(crate) hitbox_id: HitboxId,
+    pub(crate) on_cancel: Option<Rc<dyn Fn(&mut WindowContext)>>,
+}
+
+impl Clone for InteractiveRegion {
+    fn clone(&self) -> Self {
+        Self {
+            element_id: GlobalElementId(self.element_id.0.clone()),
+            hitbox_id: self.hitbox_id,
+            on_cancel: self.on_cancel.clone(),
+        }
+    }
+}
+
+/// The elements a mouse button was pressed on, and where.
+struct Press {
+    element_ids: SmallVec<[GlobalElementId; 4]>,
+    position: Point<Pixels>,
+}
+
+/// Follows the elements that handle the mouse from frame to frame by their ids rather than by
+/// their hitboxes, which change in every frame.
+#[derive(Default)]
+pub(crate) struct InteractiveRegions {
+    /// The cancel handlers of the regions in the rendered frame, while they're tracked.
+    registered: FxHashMap<GlobalElementId, Option<Rc<dyn Fn(&mut WindowContext)>>>,
+    /// Whether the mouse has been over an element or pressed since the regions stopped being
+    /// tracked, so that an element may be hovered or pressed and need cancelling if it vanishes.
+    tracking: bool,
+    press: Option<Press>,
+}
+
+impl<'a> WindowContext<'a> {
+    /// Register the hitbox of the element being painted as its interactive region, which
+    /// identifies the element across frames by its id, whatever its bounds or the order it's
+    /// painted in. This lets a press on the element be released on it even if it's moved since.
+    ///
+    /// If the element stops being painted, the cancel handler is called after the frame is drawn
+    /// so that the element can end what the mouse was doing with it, such as hovering it.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn register_interactive_region(
+        &mut self,
+        hitbox: &Hitbox,
+        on_cancel: Option<Rc<dyn Fn(&mut WindowContext)>>,
+    ) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        let element_id = GlobalElementId(self.window.element_id_stack.clone());
+        self.window
+            .next_frame
+            .interactive_regions
+            .push(InteractiveRegion {
+                element_id,
+                hitbox_id: hitbox.id,
+                on_cancel,
+            });
+    }
+
+    /// Whether releasing the mouse at the given position completes a press on the element with
+    /// the given hitbox: either the hitbox is hovered, or the element was pressed and the mouse
+    /// has stayed where it was pressed, even if the element has moved away from under it.
+    pub fn is_press_released_on(&self, hitbox: &Hitbox, position: Point<Pixels>) -> bool {
+        if hitbox.is_hovered(self) {
+            return true;
+        }
+        let Some(press) = self.window.interactive_regions.press.as_ref() else {
+            return false;
+        };
+        if (position - press.position).magnitude() > DRAG_THRESHOLD {
+            return false;
+        }
+        self.window
+            .rendered_frame
+            .interactive_regions
+            .iter()
+            .find(|region| region.hitbox_id == hitbox.id)
+            .map_or(false, |region| {
+                press.element_ids.contains(&region.element_id)
+            })
+    }
+
+    /// Start following the regions of the rendered frame from frame to frame, once the mouse is
+    /// over one of them or pressed. Until then no element can be hovered or pressed, so there's
+    /// nothing to cancel and the regions aren't compared between frames.
+    pub(crate) fn track_interactive_regions(&mut self) {
+        let window = &mut *self.window;
+        if window.interactive_regions.tracking {
+            return;
+        }
+        window.interactive_regions.tracking = true;
+        window.interactive_regions.registered.clear();
+        for region in &window.rendered_frame.interactive_regions {
+            window.interactive_regions.registered.insert(
+                GlobalElementId(region.element_id.0.clone()),
+                region.on_cancel.clone(),
+            );
+        }
+    }
+
+    /// Remember the elements under the mouse when a button is pressed.
+    pub(crate) fn begin_press(&mut self, position: Point<Pixels>) {
+        self.track_interactive_regions();
+        let window = &mut *self.window;
+        let element_ids = window
+            .rendered_frame
+            .interactive_regions
+            .iter()
+            .filter(|region| window.mouse_hit_test.0.contains(&region.hitbox_id))
+            .map(|region| GlobalElementId(region.element_id.0.clone()))
+            .collect();
+        window.interactive_regions.press = Some(Press {
+            element_ids,
+            position,
+        });
+    }
+
+    pub(crate) fn end_press(&mut self) {
+        self.window.interactive_regions.press = None;
+    }
+
+    /// Compare the interactive regions of the frame that was just drawn with those of the previous
+    /// frame, calling the cancel handlers of the elements that are gone. The regions stop being
+    /// tracked once no button is pressed and the mouse isn't over any element.
+    pub(crate) fn cancel_vanished_interactions(&mut self) {
+        let window = &mut *self.window;
+        if !window.interactive_regions.tracking {
+            return;
+        }
+        let keep_tracking =
+            window.interactive_regions.press.is_some() || !window.mouse_hit_test.0.is_empty();
+        window.interactive_regions.tracking = keep_tracking;
+        let mut previous = std::mem::take(&mut window.interactive_regions.registered);
+        for region in &window.rendered_frame.interactive_regions {
+            previous.remove(&region.element_id);
+            if keep_tracking {
+                window.interactive_regions.registered.insert(
+                    GlobalElementId(region.element_id.0.clone()),
+                    region.on_cancel.clone(),
+                );
+            }
+        }
+        if previous.is_empty() {
+            return;
+        }
+
+        if let Some(press) = window.interactive_regions.press.as_mut() {
+            press
+                .element_ids
+                .retain(|element_id| !previous.contains_key(element_id));
+        }
+        for on_cancel in previous.into_values().flatten() {
+            on_cancel(self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        self as gpui, div, point, prelude::*, px, Modifiers, MouseButton, TestAppContext, View,
+        ViewContext, VisualTestContext,
+    };
+
+    struct Row {
+        show_sibling: bool,
+        show_button: bool,
+        clicks: Rc<RefCell<usize>>,
+        hovers: Rc<RefCell<Vec<bool>>>,
+    }
+
+    impl Render for Row {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let clicks = self.clicks.clone();
+            let hovers = self.hovers.clone();
+            div()
+                .flex()
+                .when(self.show_sibling, |this| {
+                    this.child(div().id("sibling").size(px(50.)).on_click(|_, _| {}))
+                })
+                .when(self.show_button, |this| {
+                    this.child(
+                        div()
+                            .id("button")
+                            .size(px(50.))
+                            .on_click(move |_, _| *clicks.borrow_mut() += 1)
+                            .on_hover(move |hovered, _| hovers.borrow_mut().push(*hovered)),
+                    )
+                })
+        }
+    }
+
+    fn add_row(
+        cx: &mut TestAppContext,
+    ) -> (
+        View<Row>,
+        &mut VisualTestContext,
+        Rc<RefCell<usize>>,
+        Rc<RefCell<Vec<bool>>>,
+    ) {
+        let clicks = Rc::new(RefCell::new(0));
+        let hovers = Rc::new(RefCell::new(Vec::new()));
+        let (view, cx) = cx.add_window_view({
+            let clicks = clicks.clone();
+            let hovers = hovers.clone();
+            |_| Row {
+                show_sibling: false,
+                show_button: true,
+                clicks,
+                hovers,
+            }
+        });
+        cx.run_until_parked();
+        (view, cx, clicks, hovers)
+    }
+
+    #[gpui::test]
+    fn test_press_is_released_on_the_element_that_moved(cx: &mut TestAppContext) {
+        let (view, cx, clicks, _) = add_row(cx);
+        let position = point(px(25.), px(25.));
+
+        cx.simulate_mouse_down(position, MouseButton::Left, Modifiers::none());
+        view.update(cx, |view, cx| {
+            view.show_sibling = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        // The sibling is now under the mouse, but the button was pressed.
+        cx.simulate_mouse_up(position, MouseButton::Left, Modifiers::none());
+        assert_eq!(*clicks.borrow(), 1);
+
+        // Pressing where the button isn't and releasing after it moves under the mouse isn't a
+        // click on it.
+        cx.simulate_mouse_down(position, MouseButton::Left, Modifiers::none());
+        view.update(cx, |view, cx| {
+            view.show_sibling = false;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.simulate_mouse_up(position, MouseButton::Left, Modifiers::none());
+        assert_eq!(*clicks.borrow(), 1);
+    }
+
+    #[gpui::test]
+    fn test_hover_exits_when_the_element_disappears(cx: &mut TestAppContext) {
+        let (view, cx, _, hovers) = add_row(cx);
+
+        cx.simulate_mouse_move(point(px(25.), px(25.)), None, Modifiers::none());
+        assert_eq!(*hovers.borrow(), [true]);
+        view.update(cx, |view, cx| {
+            view.show_button = false;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        assert_eq!(*hovers.borrow(), [true, false]);
+    }
+
+    #[gpui::test]
+    fn test_regions_are_only_tracked_while_the_mouse_is_over_an_element(cx: &mut TestAppContext) {
+        let (view, cx, _, hovers) = add_row(cx);
+        let is_tracking = |cx: &mut VisualTestContext| {
+            cx.update(|cx| {
+                let regions = &cx.window.interactive_regions;
+                assert_eq!(regions.tracking, !regions.registered.is_empty());
+                regions.tracking
+            })
+        };
+        assert!(!is_tracking(cx));
+
+        cx.simulate_mouse_move(point(px(25.), px(25.)), None, Modifiers::none());
+        assert!(is_tracking(cx));
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        assert!(is_tracking(cx));
+
+        cx.simulate_mouse_move(point(px(200.), px(200.)), None, Modifiers::none());
+        assert_eq!(*hovers.borrow(), [true, false]);
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        assert!(!is_tracking(cx));
+    }
+}
@@ -0,0 +1,244 @@
+use collections::FxHashMap;
+
+use crate::{Bounds, DrawPhase, ElementId, EntityId, GlobalElementId, Pixels, WindowContext};
+
+/// The layout of an element in the last frame it was drawn in, see
+/// [`WindowContext::last_layout`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayoutSnapshot {
+    /// The bounds the element was painted in.
+    pub bounds: Bounds<Pixels>,
+    /// The position of the element among the recorded elements, in the order they were painted.
+    pub order: usize,
+    /// Whether the element's content was wider than the element, which is how text that doesn't
+    /// wrap gets cut off. The views that asked for the element's layout are notified when this
+    /// changes, so that they can be rendered again with the new value.
+    pub text_truncated: bool,
+    /// The number of the frame the element was laid out in, see [`WindowContext::frame_number`].
+    /// This is older than the last frame for the elements of views that weren't redrawn in it.
+    pub frame: u64,
+}
+
+/// A layout recorded while painting a frame, with the id of its element.
+#[derive(Clone)]
+pub(crate) struct RecordedLayout {
+    element_id: GlobalElementId,
+    bounds: Bounds<Pixels>,
+    text_truncated: bool,
+    frame: u64,
+}
+
+/// The layouts of the elements in the rendered frame, by element id.
+#[derive(Default)]
+pub(crate) struct LayoutSnapshots {
+    frame_number: u64,
+    last_frame: FxHashMap<GlobalElementId, LayoutSnapshot>,
+    /// The elements whose layouts are recorded, which are those asked for with
+    /// [`WindowContext::last_layout`], with the views that asked for them. An element stays
+    /// watched for as long as it's drawn, so the elements of views that weren't redrawn keep
+    /// their layouts.
+    watched: FxHashMap<GlobalElementId, Vec<Option<EntityId>>>,
+}
+
+impl<'a> WindowContext<'a> {
+    /// The number of the frame being drawn, or of the last frame drawn outside of drawing. The
+    /// first frame is 1.
+    pub fn frame_number(&self) -> u64 {
+        self.window.layout_snapshots.frame_number
+    }
+
+    /// Whether the layout of the element with the given id has been asked for with
+    /// [`Self::last_layout`], so that it should be recorded with [`Self::record_layout`].
+    pub fn is_layout_watched(&self, global_id: &GlobalElementId) -> bool {
+        self.window.layout_snapshots.watched.contains_key(global_id)
+    }
+
+    /// Record the layout of the element being painted so that it can be read while the next
+    /// frame is built with [`Self::last_layout`]. [`Div`](crate::Div)s with ids record their
+    /// layouts when [`Self::is_layout_watched`], and layouts of other elements are ignored.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn record_layout(
+        &mut self,
+        global_id: &GlobalElementId,
+        bounds: Bounds<Pixels>,
+        text_truncated: bool,
+    ) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        if !self.is_layout_watched(global_id) {
+            return;
+        }
+        let frame = self.window.layout_snapshots.frame_number;
+        self.window
+            .next_frame
+            .recorded_layouts
+            .push(RecordedLayout {
+                element_id: GlobalElementId(global_id.0.clone()),
+                bounds,
+                text_truncated,
+                frame,
+            });
+    }
+
+    /// The layout recorded for the child with the given id of the element being built, in the
+    /// last frame that was drawn. This is `None` while the first frame is drawn, and when the
+    /// element wasn't drawn in the last frame, so it's up to the caller to decide what to do until
+    /// there's a layout to go on.
+    ///
+    /// The element's layout is recorded from the frame being built on, for as long as it's drawn,
+    /// and the view being built is notified when its text becomes truncated or stops being.
+    pub fn last_layout(&mut self, id: impl Into<ElementId>) -> Option<LayoutSnapshot> {
+        let mut element_id = GlobalElementId(self.window.element_id_stack.clone());
+        element_id.0.push(id.into());
+        let view_id = self.parent_view_id();
+        let layout_snapshots = &mut self.window.layout_snapshots;
+        let layout = layout_snapshots.last_frame.get(&element_id).copied();
+        let views = layout_snapshots.watched.entry(element_id).or_default();
+        if !views.contains(&view_id) {
+            views.push(view_id);
+        }
+        layout
+    }
+
+    pub(crate) fn begin_layout_snapshot_frame(&mut self) {
+        self.window.layout_snapshots.frame_number += 1;
+    }
+
+    /// Index the layouts recorded in the frame that was just drawn, for the next one, and notify
+    /// the views that asked for the elements whose text became truncated or stopped being
+    /// truncated. Elements that weren't drawn stop being watched.
+    pub(crate) fn snapshot_layouts(&mut self) {
+        let window = &mut *self.window;
+        let previous_frame = std::mem::take(&mut window.layout_snapshots.last_frame);
+        let mut previously_watched = std::mem::take(&mut window.layout_snapshots.watched);
+        let last_frame = &mut window.layout_snapshots.last_frame;
+        let watched = &mut window.layout_snapshots.watched;
+        let mut changed_views = Vec::new();
+        for (order, layout) in window.rendered_frame.recorded_layouts.iter().enumerate() {
+            let Some(views) = previously_watched.remove(&layout.element_id) else {
+                continue;
+            };
+            let was_truncated = previous_frame
+                .get(&layout.element_id)
+                .map_or(false, |previous| previous.text_truncated);
+            if layout.text_truncated != was_truncated {
+                for view_id in &views {
+                    if !changed_views.contains(view_id) {
+                        changed_views.push(*view_id);
+                    }
+                }
+            }
+            watched.insert(GlobalElementId(layout.element_id.0.clone()), views);
+            last_frame.insert(
+                GlobalElementId(layout.element_id.0.clone()),
+                LayoutSnapshot {
+                    bounds: layout.bounds,
+                    order,
+                    text_truncated: layout.text_truncated,
+                    frame: layout.frame,
+                },
+            );
+        }
+
+        for view_id in changed_views {
+            match view_id {
+                Some(view_id) => self.notify(view_id),
+                None => self.refresh(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{self as gpui, div, prelude::*, px, LayoutSnapshot, TestAppContext, ViewContext};
+
+    struct Row {
+        show_first: bool,
+        long_label: bool,
+        snapshots: Rc<RefCell<Vec<(u64, Option<LayoutSnapshot>, Option<LayoutSnapshot>)>>>,
+    }
+
+    impl Render for Row {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            self.snapshots.borrow_mut().push((
+                cx.frame_number(),
+                cx.last_layout("first"),
+                cx.last_layout("label"),
+            ));
+            let label = if self.long_label {
+                "a label too long for its box"
+            } else {
+                "short"
+            };
+            div()
+                .id("row")
+                .flex()
+                .when(self.show_first, |this| {
+                    this.child(div().id("first").w(px(10.)).h(px(10.)))
+                })
+                .child(
+                    div()
+                        .id("label")
+                        .flex()
+                        .w(px(80.))
+                        .overflow_x_hidden()
+                        .whitespace_nowrap()
+                        .child(div().child(label)),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_last_layout_is_read_while_building_the_next_frame(cx: &mut TestAppContext) {
+        let snapshots = Rc::new(RefCell::new(Vec::new()));
+        let (view, cx) = cx.add_window_view({
+            let snapshots = snapshots.clone();
+            |_| Row {
+                show_first: true,
+                long_label: false,
+                snapshots,
+            }
+        });
+        cx.run_until_parked();
+        let last = || snapshots.borrow().last().copied().unwrap();
+
+        // Nothing has been laid out while the first frame is built.
+        let (frame, first, label) = snapshots.borrow()[0];
+        assert_eq!((first, label), (None, None));
+
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        let (next_frame, first, label) = last();
+        assert!(next_frame > frame);
+        let first = first.unwrap();
+        let label = label.unwrap();
+        assert_eq!(first.frame, next_frame - 1);
+        assert_eq!(first.bounds.size.width, px(10.));
+        assert_eq!(label.bounds.origin.x, px(10.));
+        assert!(first.order < label.order);
+        assert!(!label.text_truncated);
+        // Only the layouts that were asked for are recorded.
+        cx.update(|cx| assert_eq!(cx.window.rendered_frame.recorded_layouts.len(), 2));
+
+        // An element that wasn't drawn in the last frame has no layout.
+        view.update(cx, |view, cx| {
+            view.show_first = false;
+            view.long_label = true;
+            cx.notify();
+        });
+        // The view is rendered again on its own once it's drawn with its label truncated.
+        cx.run_until_parked();
+        let (_, first, label) = last();
+        assert_eq!(first, None);
+        let label = label.unwrap();
+        assert_eq!(label.bounds.origin.x, px(0.));
+        assert!(label.text_truncated);
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use crate::{Animation, WindowContext};
+
+/// The longest a one-shot animation runs for when motion is reduced.
+pub const REDUCED_MOTION_MAX_DURATION: Duration = Duration::from_millis(150);
+
+/// How much the animations in a window move, see [`WindowContext::motion_preference`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MotionPreference {
+    /// Animations play as they're defined.
+    #[default]
+    Full,
+    /// Animations that move things fade them in where they end up instead, and other one-shot
+    /// animations are shortened to [`REDUCED_MOTION_MAX_DURATION`].
+    Reduced,
+    /// Animations jump to their end. Repeating animations hold still.
+    None,
+}
+
+/// How an animation plays under the motion preference of the window it's drawn in, see
+/// [`WindowContext::animation_timing`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnimationTiming {
+    /// How long the animated value takes to get to its end, which is zero when it should jump
+    /// there.
+    pub duration: Duration,
+    /// When motion is reduced, an animation that moves things jumps to its end and fades the
+    /// animated element in over this duration instead.
+    pub fade: Option<Duration>,
+}
+
+/// The motion preference of a window.
+pub(crate) struct Motion {
+    preference: Option<MotionPreference>,
+    platform_reduces_motion: bool,
+}
+
+impl Motion {
+    pub(crate) fn new(platform_reduces_motion: bool) -> Self {
+        Self {
+            preference: None,
+            platform_reduces_motion,
+        }
+    }
+}
+
+impl<'a> WindowContext<'a> {
+    /// How much the animations in this window should move. This is the preference set with
+    /// [`Self::set_motion_preference`], or [`MotionPreference::Reduced`] when the user has asked
+    /// the system to reduce motion.
+    pub fn motion_preference(&self) -> MotionPreference {
+        let motion = &self.window.motion;
+        motion
+            .preference
+            .unwrap_or(if motion.platform_reduces_motion {
+                MotionPreference::Reduced
+            } else {
+                MotionPreference::Full
+            })
+    }
+
+    /// Override the motion preference of this window, e.g. to turn animations off for
+    /// screenshots, or go back to following the system's setting with `None`.
+    pub fn set_motion_preference(&mut self, preference: Option<MotionPreference>) {
+        if self.window.motion.preference != preference {
+            self.window.motion.preference = preference;
+            self.refresh();
+        }
+    }
+
+    /// How the given animation should play in this window. Animations consult this rather than
+    /// the motion preference, so that every animation reduces its motion the same way.
+    pub fn animation_timing(&self, animation: &Animation) -> AnimationTiming {
+        let reduced_duration = if animation.oneshot {
+            animation.duration.min(REDUCED_MOTION_MAX_DURATION)
+        } else {
+            // Shortening a repeating animation would only make it busier.
+            animation.duration
+        };
+        match self.motion_preference() {
+            MotionPreference::Full => AnimationTiming {
+                duration: animation.duration,
+                fade: None,
+            },
+            MotionPreference::Reduced if animation.movement => AnimationTiming {
+                duration: Duration::ZERO,
+                fade: Some(reduced_duration),
+            },
+            MotionPreference::Reduced => AnimationTiming {
+                duration: reduced_duration,
+                fade: None,
+            },
+            MotionPreference::None => AnimationTiming {
+                duration: Duration::ZERO,
+                fade: None,
+            },
+        }
+    }
+
+    /// Read whether the user has asked the system to reduce motion again, as they may have
+    /// changed it while the window was in the background.
+    pub(crate) fn update_platform_motion_preference(&mut self) {
+        let platform_reduces_motion = self.should_reduce_motion();
+        if self.window.motion.platform_reduces_motion != platform_reduces_motion {
+            self.window.motion.platform_reduces_motion = platform_reduces_motion;
+            self.refresh();
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use std::{rc::Rc, time::Instant};
+
+use crate::{
+    point, AnchorCorner, AnyElement, Bounds, ContentMask, ElementId, IntoElement, Pixels, Size,
+    WindowContext, TOP_Z_INDEX,
+};
+
+/// Identifies an element tree mounted into a window with [`WindowContext::mount`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MountId(usize);
+
+/// The part of a window that a mounted element tree is laid out in and clipped to.
+#[derive(Clone, Copy, PartialEq)]
+pub enum MountRegion {
+    /// The whole window.
+    Fullscreen,
+    /// Fixed bounds in the window.
+    Bounds(Bounds<Pixels>),
+    /// An area of the given size in a corner of the window, inset from its edges by the margin,
+    /// which follows the corner as the window is resized.
+    Corner {
+        /// The corner of the window.
+        corner: AnchorCorner,
+        /// The size of the area.
+        size: Size<Pixels>,
+        /// The distance from the edges of the window.
+        margin: Pixels,
+    },
+}
+
+impl MountRegion {
+    /// The bounds of the region in a window of the given size.
+    pub fn bounds(&self, viewport_size: Size<Pixels>) -> Bounds<Pixels> {
+        match *self {
+            MountRegion::Fullscreen => Bounds::new(Default::default(), viewport_size),
+            MountRegion::Bounds(bounds) => bounds,
+            MountRegion::Corner {
+                corner,
+                size,
+                margin,
+            } => {
+                let left = margin;
+                let right = viewport_size.width - size.width - margin;
+                let top = margin;
+                let bottom = viewport_size.height - size.height - margin;
+                let origin = match corner {
+                    AnchorCorner::TopLeft => point(left, top),
+                    AnchorCorner::TopRight => point(right, top),
+                    AnchorCorner::BottomLeft => point(left, bottom),
+                    AnchorCorner::BottomRight => point(right, bottom),
+                };
+                Bounds::new(origin, size)
+            }
+        }
+    }
+}
+
+struct Mount {
+    id: MountId,
+    region: MountRegion,
+    render: Rc<dyn Fn(&mut WindowContext) -> AnyElement>,
+    /// Debugging overlays are drawn above everything else after the window's damage is recorded,
+    /// and their time is left out of the frame diagnostics.
+    overlay: bool,
+}
+
+/// The element trees mounted into a window besides its root view, in the order they're painted.
+#[derive(Default)]
+pub(crate) struct Mounts {
+    mounts: Vec<Mount>,
+    next_id: usize,
+}
+
+/// A mounted element tree that has been prepainted and is waiting to be painted.
+pub(crate) struct PrepaintedMount {
+    id: MountId,
+    bounds: Bounds<Pixels>,
+    element: AnyElement,
+}
+
+impl<'a> WindowContext<'a> {
+    /// Mount an element tree into a region of the window, alongside the root view. The tree is
+    /// rendered by calling the given function on every frame, laid out against the bounds of the
+    /// region and clipped to them. Mounts are painted above the root view in the order they were
+    /// mounted, and receive mouse events wherever their elements are hit.
+    pub fn mount<E: IntoElement>(
+        &mut self,
+        region: MountRegion,
+        render: impl Fn(&mut WindowContext) -> E + 'static,
+    ) -> MountId {
+        self.insert_mount(region, render, false)
+    }
+
+    /// Mount a debugging overlay, which is drawn above everything else in the window, isn't
+    /// compared for damage and isn't counted in the frame times.
+    pub(crate) fn mount_overlay<E: IntoElement>(
+        &mut self,
+        region: MountRegion,
+        render: impl Fn(&mut WindowContext) -> E + 'static,
+    ) -> MountId {
+        self.insert_mount(region, render, true)
+    }
+
+    fn insert_mount<E: IntoElement>(
+        &mut self,
+        region: MountRegion,
+        render: impl Fn(&mut WindowContext) -> E + 'static,
+        overlay: bool,
+    ) -> MountId {
+        let mounts = &mut self.window.mounts;
+        let id = MountId(mounts.next_id);
+        mounts.next_id += 1;
+        mounts.mounts.push(Mount {
+            id,
+            region,
+            render: Rc::new(move |cx| render(cx).into_any_element()),
+            overlay,
+        });
+        self.refresh();
+        id
+    }
+
+    /// Remove an element tree mounted with [`Self::mount`]. Its layout and the state of its
+    /// elements are dropped with the next frame.
+    pub fn unmount(&mut self, id: MountId) {
+        let mounts = &mut self.window.mounts.mounts;
+        let len = mounts.len();
+        mounts.retain(|mount| mount.id != id);
+        if mounts.len() != len {
+            self.refresh();
+        }
+    }
+
+    /// Whether the element tree with the given id is still mounted.
+    pub fn is_mounted(&self, id: MountId) -> bool {
+        self.window.mounts.mounts.iter().any(|mount| mount.id == id)
+    }
+
+    /// Render and prepaint either the regular mounts or the overlays, in mount order.
+    pub(crate) fn prepaint_mounts(&mut self, overlays: bool) -> Vec<PrepaintedMount> {
+        let viewport_size = self.window.viewport_size;
+        let mounts = self
+            .window
+            .mounts
+            .mounts
+            .iter()
+            .filter(|mount| mount.overlay == overlays)
+            .map(|mount| {
+                (
+                    mount.id,
+                    mount.region.bounds(viewport_size),
+                    mount.render.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        if mounts.is_empty() {
+            return Vec::new();
+        }
+
+        let start = Instant::now();
+        let prepainted = self.with_mount_layer(overlays, |cx| {
+            mounts
+                .into_iter()
+                .map(|(id, bounds, render)| {
+                    let element = cx.with_mount_scope(id, bounds, |cx| {
+                        let mut element = render(cx);
+                        element.prepaint_as_root(bounds.origin, bounds.size.into(), cx);
+                        element
+                    });
+                    PrepaintedMount {
+                        id,
+                        bounds,
+                        element,
+                    }
+                })
+                .collect()
+        });
+        if overlays {
+            self.window.frame_diagnostics.exclude(start.elapsed());
+        }
+        prepainted
+    }
+
+    /// Paint mounts prepainted with [`Self::prepaint_mounts`].
+    pub(crate) fn paint_mounts(&mut self, mounts: Vec<PrepaintedMount>, overlays: bool) {
+        if mounts.is_empty() {
+            return;
+        }
+
+        let start = Instant::now();
+        self.with_mount_layer(overlays, |cx| {
+            for mut mount in mounts {
+                cx.with_mount_scope(mount.id, mount.bounds, |cx| mount.element.paint(cx));
+            }
+        });
+        if overlays {
+            self.window.frame_diagnostics.exclude(start.elapsed());
+        }
+    }
+
+    fn with_mount_layer<R>(&mut self, overlays: bool, f: impl FnOnce(&mut Self) -> R) -> R {
+        if overlays {
+            self.with_z_index(TOP_Z_INDEX, f)
+        } else {
+            f(self)
+        }
+    }
+
+    /// Keep the state of each mount's elements apart from the root view's and other mounts', and
+    /// clip the mount to its region.
+    fn with_mount_scope<R>(
+        &mut self,
+        id: MountId,
+        bounds: Bounds<Pixels>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.with_element_namespace(ElementId::from(("mount", id.0)), |cx| {
+            cx.with_content_mask(Some(ContentMask { bounds }), f)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        self as gpui, div, point, prelude::*, px, size, AnchorCorner, Bounds, ElementId,
+        TestAppContext, ViewContext, VisualTestContext,
+    };
+
+    use super::MountRegion;
+
+    struct Root {
+        clicks: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl Render for Root {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let clicks = self.clicks.clone();
+            div()
+                .id("root")
+                .size_full()
+                .on_click(move |_, _| clicks.borrow_mut().push("root"))
+        }
+    }
+
+    fn add_root(
+        cx: &mut TestAppContext,
+    ) -> (&mut VisualTestContext, Rc<RefCell<Vec<&'static str>>>) {
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view({
+            let clicks = clicks.clone();
+            |_| Root { clicks }
+        });
+        cx.run_until_parked();
+        (cx, clicks)
+    }
+
+    #[gpui::test]
+    fn test_mouse_events_are_routed_to_mounts_by_hit_test(cx: &mut TestAppContext) {
+        let (cx, clicks) = add_root(cx);
+        let region =
+            MountRegion::Bounds(Bounds::new(point(px(10.), px(10.)), size(px(40.), px(40.))));
+        let id = cx.update(|cx| {
+            let clicks = clicks.clone();
+            cx.mount(region, move |_| {
+                let clicks = clicks.clone();
+                div()
+                    .id("mounted")
+                    .size_full()
+                    .occlude()
+                    .debug_selector(|| "MOUNTED".into())
+                    .on_click(move |_, _| clicks.borrow_mut().push("mount"))
+            })
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            cx.debug_bounds("MOUNTED"),
+            Some(region.bounds(Default::default()))
+        );
+
+        cx.simulate_click(point(px(20.), px(20.)), Default::default());
+        cx.simulate_click(point(px(80.), px(80.)), Default::default());
+        assert_eq!(*clicks.borrow(), ["mount", "root"]);
+
+        cx.update(|cx| cx.unmount(id));
+        cx.run_until_parked();
+        cx.simulate_click(point(px(20.), px(20.)), Default::default());
+        assert_eq!(*clicks.borrow(), ["mount", "root", "root"]);
+    }
+
+    #[gpui::test]
+    fn test_mounts_are_painted_in_mount_order(cx: &mut TestAppContext) {
+        let (cx, clicks) = add_root(cx);
+        for name in ["first", "second"] {
+            let clicks = clicks.clone();
+            cx.update(|cx| {
+                cx.mount(MountRegion::Fullscreen, move |_| {
+                    let clicks = clicks.clone();
+                    div()
+                        .id(name)
+                        .size_full()
+                        .occlude()
+                        .on_click(move |_, _| clicks.borrow_mut().push(name))
+                })
+            });
+        }
+        cx.run_until_parked();
+
+        cx.simulate_click(point(px(20.), px(20.)), Default::default());
+        assert_eq!(*clicks.borrow(), ["second"]);
+    }
+
+    #[gpui::test]
+    fn test_unmounting_drops_element_state(cx: &mut TestAppContext) {
+        let (cx, _) = add_root(cx);
+        let id = cx.update(|cx| {
+            cx.mount(MountRegion::Fullscreen, |_| {
+                div().id("stateful").size_full().overflow_scroll()
+            })
+        });
+        cx.run_until_parked();
+
+        let has_mounted_state = |cx: &mut VisualTestContext| {
+            let namespace = ElementId::from(("mount", id.0));
+            cx.update(|cx| {
+                cx.window
+                    .rendered_frame
+                    .element_states
+                    .keys()
+                    .any(|(element_id, _)| element_id.0.contains(&namespace))
+            })
+        };
+        assert!(has_mounted_state(cx));
+
+        cx.update(|cx| cx.unmount(id));
+        cx.run_until_parked();
+        assert!(!cx.update(|cx| cx.is_mounted(id)));
+        assert!(!has_mounted_state(cx));
+    }
+
+    #[test]
+    fn test_corner_regions_follow_the_window_size() {
+        let region = MountRegion::Corner {
+            corner: AnchorCorner::BottomRight,
+            size: size(px(100.), px(50.)),
+            margin: px(8.),
+        };
+        assert_eq!(
+            region.bounds(size(px(800.), px(600.))),
+            Bounds::new(point(px(692.), px(542.)), size(px(100.), px(50.)))
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::rc::Rc;
+
+use collections::FxHashMap;
+
+use crate::{Bounds, DrawPhase, NativeViewFrame, Pixels, PlatformNativeView, WindowContext};
+
+/// A handle to a [`PlatformNativeView`], which is shown where the
+/// [`native_view`](crate::native_view) element constructed with it is painted.
+#[derive(Clone)]
+pub struct NativeViewHandle(Rc<dyn PlatformNativeView>);
+
+impl NativeViewHandle {
+    /// Construct a handle to the given view.
+    pub fn new(view: impl PlatformNativeView) -> Self {
+        Self(Rc::new(view))
+    }
+
+    fn key(&self) -> usize {
+        Rc::as_ptr(&self.0) as *const () as usize
+    }
+}
+
+/// A native view painted in a frame, and where.
+#[derive(Clone)]
+pub(crate) struct PaintedNativeView {
+    handle: NativeViewHandle,
+    frame: NativeViewFrame,
+}
+
+impl<'a> WindowContext<'a> {
+    /// Show the native view with the given handle in the given bounds, clipped to the current
+    /// content mask. The view is moved once the frame is painted, before it's presented, so it
+    /// keeps up with scrolling and resizing. It's hidden in the frames it isn't painted in.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_native_view(&mut self, handle: &NativeViewHandle, bounds: Bounds<Pixels>) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        let clip = self.content_mask().bounds.intersect(&bounds);
+        self.window.next_frame.native_views.push(PaintedNativeView {
+            handle: handle.clone(),
+            frame: NativeViewFrame { bounds, clip },
+        });
+    }
+
+    /// Move the native views painted in the frame that was just drawn to where they were painted,
+    /// and hide the ones that weren't painted or were clipped away entirely.
+    pub(crate) fn sync_native_views(&mut self) {
+        let window = &mut *self.window;
+        let mut previous = std::mem::take(&mut window.native_views);
+        for painted in &window.rendered_frame.native_views {
+            let key = painted.handle.key();
+            let frame = (!painted.frame.clip.is_empty()).then_some(painted.frame);
+            let shown = previous.remove(&key).and_then(|(_, frame)| frame);
+            if frame != shown {
+                painted.handle.0.set_frame(frame);
+            }
+            window
+                .native_views
+                .insert(key, (painted.handle.clone(), frame));
+        }
+
+        for (handle, shown) in previous.into_values() {
+            if shown.is_some() {
+                handle.0.set_frame(None);
+            }
+        }
+    }
+}
+
+/// The frames the native views of a window were last shown in, by the address of their views.
+pub(crate) type NativeViews = FxHashMap<usize, (NativeViewHandle, Option<NativeViewFrame>)>;
@@ -0,0 +1,87 @@
+use std::any::{Any, TypeId};
+
+use collections::FxHashMap;
+
+use crate::{Global, WindowContext};
+
+/// The globals of a window, see [`WindowContext::default_window_global`].
+#[derive(Default)]
+pub(crate) struct WindowGlobals(FxHashMap<TypeId, Box<dyn Any>>);
+
+impl<'a> WindowContext<'a> {
+    /// Access this window's global of the given type, if one has been assigned. Window globals
+    /// are like the app's [globals](crate::AppContext::try_global), except that each window has
+    /// its own, which are dropped when the window closes.
+    pub fn try_window_global<G: Global>(&self) -> Option<&G> {
+        self.window
+            .globals
+            .0
+            .get(&TypeId::of::<G>())
+            .map(|global| global.downcast_ref::<G>().unwrap())
+    }
+
+    /// Access this window's global of the given type mutably, assigning its default value first
+    /// if it hasn't been assigned.
+    pub fn default_window_global<G: Global + Default>(&mut self) -> &mut G {
+        self.window
+            .globals
+            .0
+            .entry(TypeId::of::<G>())
+            .or_insert_with(|| Box::<G>::default())
+            .downcast_mut::<G>()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::{Rc, Weak};
+
+    use crate::{self as gpui, div, prelude::*, Global, TestAppContext, ViewContext};
+
+    #[derive(Default)]
+    struct Counter(usize, Rc<()>);
+
+    impl Global for Counter {}
+
+    struct Root;
+
+    impl Render for Root {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+        }
+    }
+
+    #[gpui::test]
+    fn test_each_window_has_its_own_globals(cx: &mut TestAppContext) {
+        let first = cx.add_window(|_| Root);
+        let second = cx.add_window(|_| Root);
+
+        first
+            .update(cx, |_, cx| cx.default_window_global::<Counter>().0 += 2)
+            .unwrap();
+        second
+            .update(cx, |_, cx| {
+                assert!(cx.try_window_global::<Counter>().is_none());
+                cx.default_window_global::<Counter>().0 += 1;
+            })
+            .unwrap();
+        let counter: Weak<()> = first
+            .update(cx, |_, cx| {
+                let counter = cx.try_window_global::<Counter>().unwrap();
+                assert_eq!(counter.0, 2);
+                Rc::downgrade(&counter.1)
+            })
+            .unwrap();
+
+        // Closing the window drops its globals.
+        first.update(cx, |_, cx| cx.remove_window()).unwrap();
+        cx.run_until_parked();
+        assert!(counter.upgrade().is_none());
+        second
+            .update(cx, |_, cx| {
+                assert_eq!(cx.try_window_global::<Counter>().unwrap().0, 1)
+            })
+            .unwrap();
+    }
+}
@@ -1,7 +1,7 @@
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
     relative, AppContext, BackgroundExecutor, FontStyle, FontWeight, HighlightStyle, StyledText,
-    TextStyle, WhiteSpace,
+    TextAlign, TextStyle, WhiteSpace,
 };
 use settings::Settings;
 use std::ops::Range;
@@ -158,6 +158,7 @@ pub fn render_item<T>(
         color: cx.theme().colors().text,
         font_family: settings.buffer_font.family.clone(),
         font_features: settings.buffer_font.features.clone(),
+        font_fallbacks: settings.buffer_font.fallbacks.clone(),
         font_size: settings.buffer_font_size(cx).into(),
         font_weight: FontWeight::NORMAL,
         font_style: FontStyle::Normal,
@@ -166,6 +167,7 @@ pub fn render_item<T>(
         underline: None,
         strikethrough: None,
         white_space: WhiteSpace::Normal,
+        text_align: TextAlign::Start,
     };
     let highlights = gpui::combine_highlights(
         custom_highlights,
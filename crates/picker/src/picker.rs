@@ -3,8 +3,8 @@ use editor::{scroll::Autoscroll, Editor};
 use gpui::{
     actions, div, impl_actions, list, prelude::*, uniform_list, AnyElement, AppContext, ClickEvent,
     DismissEvent, EventEmitter, FocusHandle, FocusableView, Length, ListSizingBehavior, ListState,
-    MouseButton, MouseUpEvent, Render, Task, UniformListScrollHandle, View, ViewContext,
-    WindowContext,
+    MouseButton, MouseUpEvent, Render, RovingFocusHandle, RovingMovement, Task,
+    UniformListScrollHandle, View, ViewContext, WindowContext,
 };
 use head::Head;
 use serde::Deserialize;
@@ -39,6 +39,9 @@ struct PendingUpdateMatches {
 pub struct Picker<D: PickerDelegate> {
     pub delegate: D,
     element_container: ElementContainer,
+    /// Follows the delegate's selected index, which is moved as the keys of a `roving_focus`
+    /// container would move it.
+    selection: RovingFocusHandle,
     head: Head,
     pending_update_matches: Option<PendingUpdateMatches>,
     confirm_on_update: Option<bool>,
@@ -185,6 +188,7 @@ impl<D: PickerDelegate> Picker<D> {
             delegate,
             head,
             element_container: Self::create_element_container(container, cx),
+            selection: RovingFocusHandle::new(),
             pending_update_matches: None,
             confirm_on_update: None,
             width: None,
@@ -269,40 +273,40 @@ impl<D: PickerDelegate> Picker<D> {
         }
     }
 
-    pub fn select_next(&mut self, _: &menu::SelectNext, cx: &mut ViewContext<Self>) {
+    /// Moves the selection among the matches, which wraps around at either end.
+    fn move_selection(&mut self, movement: RovingMovement, cx: &mut ViewContext<Self>) {
         let count = self.delegate.match_count();
-        if count > 0 {
-            let index = self.delegate.selected_index();
-            let ix = if index == count - 1 { 0 } else { index + 1 };
+        if count == 0 {
+            return;
+        }
+        // The delegate owns the selection, which it can change as the matches are updated.
+        let index = self.delegate.selected_index();
+        self.selection.set_active_index(Some(index));
+        let movement = match movement {
+            RovingMovement::Next if index + 1 == count => RovingMovement::First,
+            RovingMovement::Previous if index == 0 => RovingMovement::Last,
+            movement => movement,
+        };
+        if let Some(ix) = self.selection.move_active(movement, count) {
             self.set_selected_index(ix, true, cx);
             cx.notify();
         }
     }
 
+    pub fn select_next(&mut self, _: &menu::SelectNext, cx: &mut ViewContext<Self>) {
+        self.move_selection(RovingMovement::Next, cx);
+    }
+
     fn select_prev(&mut self, _: &menu::SelectPrev, cx: &mut ViewContext<Self>) {
-        let count = self.delegate.match_count();
-        if count > 0 {
-            let index = self.delegate.selected_index();
-            let ix = if index == 0 { count - 1 } else { index - 1 };
-            self.set_selected_index(ix, true, cx);
-            cx.notify();
-        }
+        self.move_selection(RovingMovement::Previous, cx);
     }
 
     fn select_first(&mut self, _: &menu::SelectFirst, cx: &mut ViewContext<Self>) {
-        let count = self.delegate.match_count();
-        if count > 0 {
-            self.set_selected_index(0, true, cx);
-            cx.notify();
-        }
+        self.move_selection(RovingMovement::First, cx);
     }
 
     fn select_last(&mut self, _: &menu::SelectLast, cx: &mut ViewContext<Self>) {
-        let count = self.delegate.match_count();
-        if count > 0 {
-            self.set_selected_index(count - 1, true, cx);
-            cx.notify();
-        }
+        self.move_selection(RovingMovement::Last, cx);
     }
 
     pub fn cycle_selection(&mut self, cx: &mut ViewContext<Self>) {
@@ -468,6 +472,7 @@ impl<D: PickerDelegate> Picker<D> {
     }
 
     fn scroll_to_item_index(&mut self, ix: usize) {
+        self.selection.set_active_index(Some(ix));
         match &mut self.element_container {
             ElementContainer::List(state) => state.scroll_to_reveal_item(ix),
             ElementContainer::UniformList(scroll_handle) => scroll_handle.scroll_to_item(ix),
@@ -529,6 +534,7 @@ impl<D: PickerDelegate> Picker<D> {
             .flex_grow()
             .py_2()
             .track_scroll(scroll_handle.clone())
+            .track_active(&self.selection)
             .into_any_element(),
             ElementContainer::List(state) => list(state.clone())
                 .with_sizing_behavior(sizing_behavior)
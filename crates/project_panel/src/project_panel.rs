@@ -2065,7 +2065,7 @@ impl ProjectPanel {
                     cx.stop_propagation();
                 }),
             )
-            .on_drag(dragged_selection, move |selection, cx| {
+            .on_drag(dragged_selection, move |selection, _, cx| {
                 cx.new_view(|_| DraggedProjectEntryView {
                     details: details.clone(),
                     width,
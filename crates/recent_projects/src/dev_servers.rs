@@ -1053,11 +1053,6 @@ impl Render for DevServerProjects {
             .capture_any_mouse_down(cx.listener(|this, _, cx| {
                 this.focus_handle(cx).focus(cx);
             }))
-            .on_mouse_down_out(cx.listener(|this, _, cx| {
-                if matches!(this.mode, Mode::Default(None)) {
-                    cx.emit(DismissEvent)
-                }
-            }))
             .w(rems(34.))
             .max_h(rems(40.))
             .child(match &self.mode {
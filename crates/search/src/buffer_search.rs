@@ -15,8 +15,8 @@ use futures::channel::oneshot;
 use gpui::{
     actions, div, impl_actions, Action, AppContext, ClickEvent, EventEmitter, FocusableView,
     FontStyle, FontWeight, Hsla, InteractiveElement as _, IntoElement, KeyContext,
-    ParentElement as _, Render, ScrollHandle, Styled, Subscription, Task, TextStyle, View,
-    ViewContext, VisualContext as _, WhiteSpace, WindowContext,
+    ParentElement as _, Render, ScrollHandle, Styled, Subscription, Task, TextAlign, TextStyle,
+    View, ViewContext, VisualContext as _, WhiteSpace, WindowContext,
 };
 use project::{
     search::SearchQuery,
@@ -115,6 +115,7 @@ impl BufferSearchBar {
             },
             font_family: settings.buffer_font.family.clone(),
             font_features: settings.buffer_font.features.clone(),
+            font_fallbacks: settings.buffer_font.fallbacks.clone(),
             font_size: rems(0.875).into(),
             font_weight: FontWeight::NORMAL,
             font_style: FontStyle::Normal,
@@ -123,6 +124,7 @@ impl BufferSearchBar {
             underline: None,
             strikethrough: None,
             white_space: WhiteSpace::Normal,
+            text_align: TextAlign::Start,
         };
 
         EditorElement::new(
@@ -0,0 +1,108 @@
+use gpui::SharedString;
+
+/// The parameters of a registered story that can be changed from the storybook.
+///
+/// A story declares its knobs while rendering: the first call registers the knob with its
+/// default value, and every call returns the value currently picked in the storybook.
+#[derive(Default)]
+pub struct Knobs {
+    knobs: Vec<Knob>,
+}
+
+/// A single named parameter of a story, see [`Knobs`].
+pub struct Knob {
+    pub name: &'static str,
+    pub value: KnobValue,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KnobValue {
+    Bool(bool),
+    Text(SharedString),
+    Choice {
+        options: Vec<&'static str>,
+        selected: usize,
+    },
+}
+
+impl Knobs {
+    /// A boolean knob, shown as a checkbox.
+    pub fn bool(&mut self, name: &'static str, default: bool) -> bool {
+        match self.get_or_insert(name, || KnobValue::Bool(default)) {
+            KnobValue::Bool(value) => *value,
+            _ => default,
+        }
+    }
+
+    /// A text knob, shown as a single line editor.
+    pub fn text(&mut self, name: &'static str, default: impl Into<SharedString>) -> SharedString {
+        let default = default.into();
+        match self.get_or_insert(name, || KnobValue::Text(default.clone())) {
+            KnobValue::Text(value) => value.clone(),
+            _ => default,
+        }
+    }
+
+    /// A knob that picks one of the given labelled options, shown as a row of buttons.
+    ///
+    /// The first option is selected by default.
+    pub fn choice<T: Copy>(&mut self, name: &'static str, options: &[(&'static str, T)]) -> T {
+        assert!(!options.is_empty(), "knob {name:?} has no options");
+
+        let labels = options.iter().map(|(label, _)| *label).collect::<Vec<_>>();
+        let value = self.get_or_insert(name, || KnobValue::Choice {
+            options: labels.clone(),
+            selected: 0,
+        });
+        match value {
+            KnobValue::Choice {
+                options: previous_labels,
+                selected,
+            } => {
+                if *previous_labels != labels {
+                    *selected = labels
+                        .iter()
+                        .position(|label| *label == previous_labels[*selected])
+                        .unwrap_or(0);
+                    *previous_labels = labels;
+                }
+                options[*selected].1
+            }
+            _ => options[0].1,
+        }
+    }
+
+    /// Changes the value of a knob that was previously declared by the story.
+    pub fn set(&mut self, name: &str, value: KnobValue) {
+        if let Some(knob) = self.knobs.iter_mut().find(|knob| knob.name == name) {
+            knob.value = value;
+        }
+    }
+
+    /// The knobs declared so far, in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = &Knob> {
+        self.knobs.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.knobs.is_empty()
+    }
+
+    fn get_or_insert(
+        &mut self,
+        name: &'static str,
+        default: impl FnOnce() -> KnobValue,
+    ) -> &mut KnobValue {
+        let ix = match self.knobs.iter().position(|knob| knob.name == name) {
+            Some(ix) => ix,
+            None => {
+                self.knobs.push(Knob {
+                    name,
+                    value: default(),
+                });
+                self.knobs.len() - 1
+            }
+        };
+        &mut self.knobs[ix].value
+    }
+}
@@ -1,3 +1,14 @@
+mod knobs;
+mod registry;
 mod story;
 
+pub use knobs::*;
+pub use registry::*;
 pub use story::*;
+
+/// Used by the `register_story!` macro, not meant to be used directly.
+#[doc(hidden)]
+pub mod private {
+    pub use gpui;
+    pub use linkme;
+}
@@ -0,0 +1,94 @@
+use gpui::{AnyElement, IntoElement, WindowContext};
+
+use crate::Knobs;
+
+/// A story registered with [`register_story!`](crate::register_story).
+pub struct StoryEntry {
+    /// The name of the story, e.g. `button/variants`.
+    ///
+    /// The part before the first `/` is used to group stories in the storybook.
+    pub name: &'static str,
+    /// The file the story was registered in, relative to the workspace root.
+    pub file: &'static str,
+    pub render: fn(&mut Knobs, &mut WindowContext) -> AnyElement,
+}
+
+impl StoryEntry {
+    /// The group this story is listed under in the storybook.
+    pub fn group(&self) -> &'static str {
+        self.name
+            .split_once('/')
+            .map_or(self.name, |(group, _)| group)
+    }
+}
+
+/// This constant must be public to be accessible from other crates.
+/// But its existence is an implementation detail and should not be used directly.
+#[doc(hidden)]
+#[linkme::distributed_slice]
+pub static __STORIES: [StoryEntry];
+
+/// All registered stories, sorted by name.
+pub fn all_stories() -> Vec<&'static StoryEntry> {
+    let mut stories = __STORIES.iter().collect::<Vec<_>>();
+    stories.sort_by_key(|story| story.name);
+    for pair in stories.windows(2) {
+        assert_ne!(
+            pair[0].name, pair[1].name,
+            "story registered twice, in {} and {}",
+            pair[0].file, pair[1].file
+        );
+    }
+    stories
+}
+
+/// Finds a registered story by name.
+pub fn find_story(name: &str) -> Option<&'static StoryEntry> {
+    __STORIES.iter().find(|story| story.name == name)
+}
+
+/// Used by [`register_story!`](crate::register_story) to infer the argument types of the
+/// render closure.
+#[doc(hidden)]
+pub fn __story_render<F, E>(render: F) -> F
+where
+    F: Fn(&mut Knobs, &mut WindowContext) -> E,
+    E: IntoElement,
+{
+    render
+}
+
+/// Registers a story so that it shows up in the storybook's gallery.
+///
+/// The story is rendered by a closure that receives the story's [`Knobs`] and the window
+/// context, and returns an element:
+///
+/// ```ignore
+/// story::register_story!("button/variants", |knobs, _cx| {
+///     let label = knobs.text("Label", "Click me");
+///     Button::new("button", label).disabled(knobs.bool("Disabled", false))
+/// });
+/// ```
+#[macro_export]
+macro_rules! register_story {
+    ($name:literal, $render:expr $(,)?) => {
+        const _: () = {
+            fn render(
+                knobs: &mut $crate::Knobs,
+                cx: &mut $crate::private::gpui::WindowContext,
+            ) -> $crate::private::gpui::AnyElement {
+                $crate::private::gpui::IntoElement::into_any_element($crate::__story_render(
+                    $render,
+                )(knobs, cx))
+            }
+
+            #[$crate::private::linkme::distributed_slice($crate::__STORIES)]
+            #[linkme(crate = $crate::private::linkme)]
+            static STORY: $crate::StoryEntry = $crate::StoryEntry {
+                name: $name,
+                file: file!(),
+                render,
+            };
+        };
+    };
+}
@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+
+use editor::{Editor, EditorEvent};
+use gpui::{
+    canvas, fill, hsla, point, size, Bounds, Hsla, Pixels, Render, Subscription, View,
+    VisualContext,
+};
+use settings::Settings;
+use story::{all_stories, KnobValue, Knobs, StoryEntry};
+use theme::{ThemeRegistry, ThemeSettings, UiDensity};
+use ui::{prelude::*, Checkbox, ListItem};
+
+const CHECKERBOARD_CELL_SIZE: Pixels = px(8.);
+
+/// Browses the stories registered with [`story::register_story!`], letting their knobs be
+/// changed while they're displayed.
+pub struct StoryGallery {
+    stories: Vec<&'static StoryEntry>,
+    selected: usize,
+    knobs: HashMap<&'static str, Knobs>,
+    /// The density the story is drawn at, independently of the `ui_density` setting.
+    density: UiDensity,
+    text_knob_editors: HashMap<(&'static str, &'static str), View<Editor>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl StoryGallery {
+    pub fn view(initial_story: Option<&str>, cx: &mut WindowContext) -> View<Self> {
+        let stories = all_stories();
+        let selected = initial_story
+            .and_then(|name| stories.iter().position(|story| story.name == name))
+            .unwrap_or(0);
+
+        cx.new_view(|_| Self {
+            stories,
+            selected,
+            knobs: HashMap::default(),
+            density: UiDensity::default(),
+            text_knob_editors: HashMap::default(),
+            _subscriptions: Vec::new(),
+        })
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        self.selected = ix;
+        cx.notify();
+    }
+
+    fn set_knob(
+        &mut self,
+        story: &'static str,
+        knob: &'static str,
+        value: KnobValue,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.knobs.entry(story).or_default().set(knob, value);
+        cx.notify();
+    }
+
+    fn toggle_appearance(cx: &mut WindowContext) {
+        let theme_name = if cx.theme().appearance().is_light() {
+            "One Dark"
+        } else {
+            "One Light"
+        };
+        let Ok(theme) = ThemeRegistry::global(cx).get(theme_name) else {
+            log::error!("theme {theme_name:?} not found");
+            return;
+        };
+
+        let mut theme_settings = ThemeSettings::get_global(cx).clone();
+        theme_settings.active_theme = theme;
+        ThemeSettings::override_global(theme_settings, cx);
+        cx.refresh();
+    }
+
+    fn render_sidebar(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let mut sidebar = v_flex()
+            .id("stories")
+            .flex_none()
+            .w_56()
+            .h_full()
+            .p_1()
+            .overflow_y_scroll()
+            .border_r_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().panel_background);
+
+        let mut current_group = None;
+        for (ix, story) in self.stories.iter().enumerate() {
+            if current_group != Some(story.group()) {
+                current_group = Some(story.group());
+                sidebar = sidebar.child(
+                    div().px_2().pt_2().child(
+                        Label::new(story.group())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+                );
+            }
+
+            sidebar = sidebar.child(
+                ListItem::new(ix)
+                    .inset(true)
+                    .selected(ix == self.selected)
+                    .on_click(cx.listener(move |this, _, cx| this.select(ix, cx)))
+                    .child(Label::new(story.name)),
+            );
+        }
+
+        sidebar
+    }
+
+    fn render_knobs(
+        &mut self,
+        story: &'static StoryEntry,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let knobs = self.knobs.entry(story.name).or_default();
+        let mut new_text_knobs = Vec::new();
+        for knob in knobs.iter() {
+            if let KnobValue::Text(text) = &knob.value {
+                if !self
+                    .text_knob_editors
+                    .contains_key(&(story.name, knob.name))
+                {
+                    new_text_knobs.push((knob.name, text.clone()));
+                }
+            }
+        }
+        for (knob, text) in new_text_knobs {
+            let editor = cx.new_view(|cx| {
+                let mut editor = Editor::single_line(cx);
+                editor.set_text(text.to_string(), cx);
+                editor
+            });
+            self._subscriptions.push(cx.subscribe(
+                &editor,
+                move |this, editor, event: &EditorEvent, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        let text = editor.read(cx).text(cx);
+                        this.set_knob(story.name, knob, KnobValue::Text(text.into()), cx);
+                    }
+                },
+            ));
+            self.text_knob_editors.insert((story.name, knob), editor);
+        }
+
+        let knobs = &self.knobs[story.name];
+        v_flex()
+            .id("knobs")
+            .flex_none()
+            .w_64()
+            .h_full()
+            .p_2()
+            .gap_3()
+            .overflow_y_scroll()
+            .border_l_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().panel_background)
+            .child(
+                Button::new(
+                    "toggle-appearance",
+                    if cx.theme().appearance().is_light() {
+                        "Dark Theme"
+                    } else {
+                        "Light Theme"
+                    },
+                )
+                .style(ButtonStyle::Filled)
+                .on_click(|_, cx| Self::toggle_appearance(cx)),
+            )
+            .child(
+                h_flex().gap_1().children(
+                    [
+                        (UiDensity::Compact, "Compact"),
+                        (UiDensity::Default, "Default"),
+                        (UiDensity::Comfortable, "Comfortable"),
+                    ]
+                    .into_iter()
+                    .map(|(density, label)| {
+                        Button::new(label, label)
+                            .style(ButtonStyle::Filled)
+                            .selected(density == self.density)
+                            .on_click(cx.listener(move |this, _, cx| {
+                                this.density = density;
+                                cx.notify();
+                            }))
+                    }),
+                ),
+            )
+            .when(knobs.is_empty(), |this| {
+                this.child(
+                    Label::new("This story has no knobs")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .children(knobs.iter().map(|knob| {
+                let name = knob.name;
+                let control = match &knob.value {
+                    KnobValue::Bool(value) => {
+                        Checkbox::new(SharedString::from(format!("knob-{name}")), (*value).into())
+                            .on_click(cx.listener(move |this, selection, cx| {
+                                let value = *selection == Selection::Selected;
+                                this.set_knob(story.name, name, KnobValue::Bool(value), cx);
+                            }))
+                            .into_any_element()
+                    }
+                    KnobValue::Text(_) => div()
+                        .px_1()
+                        .rounded_md()
+                        .border_1()
+                        .border_color(cx.theme().colors().border)
+                        .bg(cx.theme().colors().editor_background)
+                        .child(self.text_knob_editors[&(story.name, name)].clone())
+                        .into_any_element(),
+                    KnobValue::Choice { options, selected } => h_flex()
+                        .flex_wrap()
+                        .gap_1()
+                        .children(options.iter().enumerate().map(|(ix, label)| {
+                            let options = options.clone();
+                            Button::new(SharedString::from(format!("knob-{name}-{ix}")), *label)
+                                .style(ButtonStyle::Filled)
+                                .selected(ix == *selected)
+                                .on_click(cx.listener(move |this, _, cx| {
+                                    let value = KnobValue::Choice {
+                                        options: options.clone(),
+                                        selected: ix,
+                                    };
+                                    this.set_knob(story.name, name, value, cx);
+                                }))
+                        }))
+                        .into_any_element(),
+                };
+
+                v_flex()
+                    .gap_1()
+                    .child(Label::new(name).size(LabelSize::Small).color(Color::Muted))
+                    .child(control)
+            }))
+    }
+}
+
+impl Render for StoryGallery {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let Some(story) = self.stories.get(self.selected).copied() else {
+            return h_flex()
+                .size_full()
+                .justify_center()
+                .bg(cx.theme().colors().background)
+                .child(Label::new("No stories are registered"))
+                .into_any_element();
+        };
+
+        let content = (story.render)(self.knobs.entry(story.name).or_default(), cx);
+
+        h_flex()
+            .size_full()
+            .bg(cx.theme().colors().background)
+            .text_color(cx.theme().colors().text)
+            .child(self.render_sidebar(cx))
+            .child(
+                div()
+                    .relative()
+                    .flex_1()
+                    .h_full()
+                    .overflow_hidden()
+                    .child(checkerboard(cx))
+                    .child(
+                        h_flex()
+                            .id("story")
+                            .size_full()
+                            .p_8()
+                            .justify_center()
+                            .overflow_y_scroll()
+                            .child(ui::density(self.density, content)),
+                    ),
+            )
+            .child(self.render_knobs(story, cx))
+            .into_any_element()
+    }
+}
+
+/// A checkerboard filling its parent, which makes the bounds and transparency of the story
+/// being displayed visible.
+fn checkerboard(cx: &WindowContext) -> impl IntoElement {
+    let (light, dark): (Hsla, Hsla) = if cx.theme().appearance().is_light() {
+        (hsla(0., 0., 1., 1.), hsla(0., 0., 0.92, 1.))
+    } else {
+        (hsla(0., 0., 0.12, 1.), hsla(0., 0., 0.16, 1.))
+    };
+
+    canvas(
+        |_, _| {},
+        move |bounds, _, cx| {
+            cx.paint_quad(fill(bounds, light));
+
+            let mut row = 0;
+            let mut y = bounds.origin.y;
+            while y < bounds.bottom() {
+                let mut x = bounds.origin.x;
+                if row % 2 == 1 {
+                    x += CHECKERBOARD_CELL_SIZE;
+                }
+                while x < bounds.right() {
+                    let cell = Bounds::new(
+                        point(x, y),
+                        size(CHECKERBOARD_CELL_SIZE, CHECKERBOARD_CELL_SIZE),
+                    );
+                    cx.paint_quad(fill(cell.intersect(&bounds), dark));
+                    x += CHECKERBOARD_CELL_SIZE * 2.;
+                }
+                y += CHECKERBOARD_CELL_SIZE;
+                row += 1;
+            }
+        },
+    )
+    .absolute()
+    .size_full()
+}
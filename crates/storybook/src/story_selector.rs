@@ -1,6 +1,7 @@
 use std::str::FromStr;
 use std::sync::OnceLock;
 
+use crate::gallery::StoryGallery;
 use crate::stories::*;
 use anyhow::anyhow;
 use clap::builder::PossibleValue;
@@ -83,6 +84,8 @@ impl ComponentStory {
 pub enum StorySelector {
     Component(ComponentStory),
     KitchenSink,
+    /// The gallery of registered stories, optionally opened on the story with the given name.
+    Gallery(Option<&'static str>),
 }
 
 impl FromStr for StorySelector {
@@ -97,6 +100,17 @@ impl FromStr for StorySelector {
             return Ok(Self::KitchenSink);
         }
 
+        if story == "gallery" {
+            return Ok(Self::Gallery(None));
+        }
+
+        if let Some((_, story)) = story.split_once("gallery/") {
+            let story = story::find_story(story)
+                .with_context(|| format!("no story registered with the name '{story}'"))?;
+
+            return Ok(Self::Gallery(Some(story.name)));
+        }
+
         if let Some((_, story)) = story.split_once("components/") {
             let component_story = ComponentStory::from_str(story)
                 .with_context(|| format!("story not found for component '{story}'"))?;
@@ -113,6 +127,7 @@ impl StorySelector {
         match self {
             Self::Component(component_story) => component_story.story(cx),
             Self::KitchenSink => KitchenSinkStory::view(cx).into(),
+            Self::Gallery(story) => StoryGallery::view(*story, cx).into(),
         }
     }
 }
@@ -125,8 +140,14 @@ impl ValueEnum for StorySelector {
         let stories = ALL_STORY_SELECTORS.get_or_init(|| {
             let component_stories = ComponentStory::iter().map(StorySelector::Component);
 
+            let gallery_stories = story::all_stories()
+                .into_iter()
+                .map(|story| StorySelector::Gallery(Some(story.name)));
+
             component_stories
                 .chain(std::iter::once(StorySelector::KitchenSink))
+                .chain(std::iter::once(StorySelector::Gallery(None)))
+                .chain(gallery_stories)
                 .collect::<Vec<_>>()
         });
 
@@ -137,6 +158,8 @@ impl ValueEnum for StorySelector {
         let value = match self {
             Self::Component(story) => format!("components/{story}"),
             Self::KitchenSink => "kitchen_sink".to_string(),
+            Self::Gallery(None) => "gallery".to_string(),
+            Self::Gallery(Some(story)) => format!("gallery/{story}"),
         };
 
         Some(PossibleValue::new(value))
@@ -1,6 +1,7 @@
 mod actions;
 mod app_menus;
 mod assets;
+mod gallery;
 mod stories;
 mod story_selector;
 
@@ -4,8 +4,8 @@ use gpui::{
     FocusHandle, Font, FontStyle, FontWeight, GlobalElementId, HighlightStyle, Hitbox, Hsla,
     InputHandler, InteractiveElement, Interactivity, IntoElement, LayoutId, Model, ModelContext,
     ModifiersChangedEvent, MouseButton, MouseMoveEvent, Pixels, Point, ShapedLine,
-    StatefulInteractiveElement, StrikethroughStyle, Styled, TextRun, TextStyle, UnderlineStyle,
-    WeakView, WhiteSpace, WindowContext, WindowTextSystem,
+    StatefulInteractiveElement, StrikethroughStyle, Styled, TextAlign, TextRun, TextStyle,
+    UnderlineStyle, WeakView, WhiteSpace, WindowContext, WindowTextSystem,
 };
 use itertools::Itertools;
 use language::CursorShape;
@@ -618,12 +618,14 @@ impl Element for TerminalElement {
                 let text_style = TextStyle {
                     font_family,
                     font_features,
+                    font_fallbacks: settings.buffer_font.fallbacks.clone(),
                     font_weight,
                     font_size: font_size.into(),
                     font_style: FontStyle::Normal,
                     line_height: line_height.into(),
                     background_color: None,
                     white_space: WhiteSpace::Normal,
+                    text_align: TextAlign::Start,
                     // These are going to be overridden per-cell
                     underline: None,
                     strikethrough: None,
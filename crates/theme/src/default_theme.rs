@@ -5,7 +5,7 @@ use gpui::WindowBackgroundAppearance;
 use crate::AccentColors;
 
 use crate::{
-    default_color_scales,
+    default_color_scales, default_elevation_styles,
     one_themes::{one_dark, one_family},
     Appearance, PlayerColors, StatusColors, SyntaxTheme, SystemColors, Theme, ThemeColors,
     ThemeFamily, ThemeStyles,
@@ -24,6 +24,7 @@ fn zed_pro_daylight() -> Theme {
             player: PlayerColors::light(),
             syntax: Arc::new(SyntaxTheme::default()),
             accents: AccentColors::light(),
+            elevation: default_elevation_styles(Appearance::Light),
         },
     }
 }
@@ -41,6 +42,7 @@ pub(crate) fn zed_pro_moonlight() -> Theme {
             player: PlayerColors::dark(),
             syntax: Arc::new(SyntaxTheme::default()),
             accents: AccentColors::dark(),
+            elevation: default_elevation_styles(Appearance::Dark),
         },
     }
 }
@@ -3,8 +3,8 @@ use std::sync::Arc;
 use gpui::{hsla, FontStyle, FontWeight, HighlightStyle, WindowBackgroundAppearance};
 
 use crate::{
-    default_color_scales, AccentColors, Appearance, PlayerColors, StatusColors, SyntaxTheme,
-    SystemColors, Theme, ThemeColors, ThemeFamily, ThemeStyles,
+    default_color_scales, default_elevation_styles, AccentColors, Appearance, PlayerColors,
+    StatusColors, SyntaxTheme, SystemColors, Theme, ThemeColors, ThemeFamily, ThemeStyles,
 };
 
 // Note: This theme family is not the one you see in Zed at the moment.
@@ -252,6 +252,7 @@ pub(crate) fn one_dark() -> Theme {
                     ("variant".into(), HighlightStyle::default()),
                 ],
             }),
+            elevation: default_elevation_styles(Appearance::Dark),
         },
     }
 }
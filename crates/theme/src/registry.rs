@@ -12,9 +12,9 @@ use refineable::Refineable;
 use util::ResultExt;
 
 use crate::{
-    try_parse_color, AccentColors, Appearance, AppearanceContent, PlayerColors, StatusColors,
-    SyntaxTheme, SystemColors, Theme, ThemeColors, ThemeContent, ThemeFamily, ThemeFamilyContent,
-    ThemeStyles,
+    default_elevation_styles, try_parse_color, AccentColors, Appearance, AppearanceContent,
+    PlayerColors, StatusColors, SyntaxTheme, SystemColors, Theme, ThemeColors, ThemeContent,
+    ThemeFamily, ThemeFamilyContent, ThemeStyles,
 };
 
 #[derive(Debug, Clone)]
@@ -157,13 +157,15 @@ impl ThemeRegistry {
                 .map(Into::into)
                 .unwrap_or_default();
 
+            let appearance = match user_theme.appearance {
+                AppearanceContent::Light => Appearance::Light,
+                AppearanceContent::Dark => Appearance::Dark,
+            };
+
             Theme {
                 id: uuid::Uuid::new_v4().to_string(),
                 name: user_theme.name.into(),
-                appearance: match user_theme.appearance {
-                    AppearanceContent::Light => Appearance::Light,
-                    AppearanceContent::Dark => Appearance::Dark,
-                },
+                appearance,
                 styles: ThemeStyles {
                     system: SystemColors::default(),
                     window_background_appearance,
@@ -172,6 +174,7 @@ impl ThemeRegistry {
                     status: status_colors,
                     player: player_colors,
                     syntax: syntax_theme,
+                    elevation: default_elevation_styles(appearance),
                 },
             }
         }));
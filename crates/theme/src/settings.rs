@@ -3,8 +3,8 @@ use crate::{Appearance, SyntaxTheme, Theme, ThemeRegistry, ThemeStyleContent};
 use anyhow::Result;
 use derive_more::{Deref, DerefMut};
 use gpui::{
-    px, AppContext, Font, FontFeatures, FontStyle, FontWeight, Global, Pixels, Subscription,
-    ViewContext, WindowContext,
+    px, AppContext, Font, FontFallbacks, FontFeatures, FontStyle, FontWeight, Global, Pixels,
+    Subscription, ViewContext, WindowContext,
 };
 use refineable::Refineable;
 use schemars::{
@@ -232,6 +232,9 @@ pub struct ThemeSettingsContent {
     /// The OpenType features to enable for text in the UI.
     #[serde(default)]
     pub ui_font_features: Option<FontFeatures>,
+    /// The fonts to fall back to for characters the UI font doesn't cover, in order.
+    #[serde(default)]
+    pub ui_font_fallbacks: Option<Vec<String>>,
     /// The weight of the UI font in CSS units from 100 to 900.
     #[serde(default)]
     pub ui_font_weight: Option<f32>,
@@ -250,6 +253,9 @@ pub struct ThemeSettingsContent {
     /// The OpenType features to enable for rendering in text buffers.
     #[serde(default)]
     pub buffer_font_features: Option<FontFeatures>,
+    /// The fonts to fall back to for characters the buffer font doesn't cover, in order.
+    #[serde(default)]
+    pub buffer_font_fallbacks: Option<Vec<String>>,
     /// The name of the Zed theme to use.
     #[serde(default)]
     pub theme: Option<ThemeSelection>,
@@ -439,12 +445,20 @@ impl settings::Settings for ThemeSettings {
             ui_font: Font {
                 family: defaults.ui_font_family.clone().unwrap().into(),
                 features: defaults.ui_font_features.clone().unwrap(),
+                fallbacks: defaults
+                    .ui_font_fallbacks
+                    .as_ref()
+                    .map(|fallbacks| FontFallbacks::from_fonts(fallbacks.clone())),
                 weight: defaults.ui_font_weight.map(FontWeight).unwrap(),
                 style: Default::default(),
             },
             buffer_font: Font {
                 family: defaults.buffer_font_family.clone().unwrap().into(),
                 features: defaults.buffer_font_features.clone().unwrap(),
+                fallbacks: defaults
+                    .buffer_font_fallbacks
+                    .as_ref()
+                    .map(|fallbacks| FontFallbacks::from_fonts(fallbacks.clone())),
                 weight: defaults.buffer_font_weight.map(FontWeight).unwrap(),
                 style: FontStyle::default(),
             },
@@ -470,6 +484,9 @@ impl settings::Settings for ThemeSettings {
             if let Some(value) = value.buffer_font_features.clone() {
                 this.buffer_font.features = value;
             }
+            if let Some(value) = value.buffer_font_fallbacks.clone() {
+                this.buffer_font.fallbacks = Some(FontFallbacks::from_fonts(value));
+            }
 
             if let Some(value) = value.buffer_font_weight {
                 this.buffer_font.weight = FontWeight(value);
@@ -481,6 +498,9 @@ impl settings::Settings for ThemeSettings {
             if let Some(value) = value.ui_font_features.clone() {
                 this.ui_font.features = value;
             }
+            if let Some(value) = value.ui_font_fallbacks.clone() {
+                this.ui_font.fallbacks = Some(FontFallbacks::from_fonts(value));
+            }
             if let Some(value) = value.ui_font_weight {
                 this.ui_font.weight = FontWeight(value);
             }
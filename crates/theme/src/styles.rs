@@ -1,5 +1,6 @@
 mod accents;
 mod colors;
+mod elevation;
 mod players;
 mod status;
 mod syntax;
@@ -10,6 +11,7 @@ mod stories;
 
 pub use accents::*;
 pub use colors::*;
+pub use elevation::*;
 pub use players::*;
 pub use status::*;
 pub use syntax::*;
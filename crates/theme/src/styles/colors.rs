@@ -1,4 +1,4 @@
-use gpui::{Hsla, WindowBackgroundAppearance};
+use gpui::{ElevationStyles, Hsla, WindowBackgroundAppearance};
 use refineable::Refineable;
 use std::sync::Arc;
 
@@ -257,6 +257,9 @@ pub struct ThemeStyles {
     pub player: PlayerColors,
 
     pub syntax: Arc<SyntaxTheme>,
+
+    /// The shadow, z-index and layer of each elevation level.
+    pub elevation: ElevationStyles,
 }
 
 #[cfg(test)]
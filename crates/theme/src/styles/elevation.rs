@@ -0,0 +1,52 @@
+use gpui::{hsla, point, px, BoxShadow, ElevationStyle, ElevationStyles};
+use smallvec::{smallvec, SmallVec};
+
+use crate::Appearance;
+
+/// The [`ElevationStyles`] of the built-in themes. Dark themes get denser shadows, as faint ones
+/// disappear against a dark background.
+pub fn default_elevation_styles(appearance: Appearance) -> ElevationStyles {
+    let density = match appearance {
+        Appearance::Light => 1.,
+        Appearance::Dark => 2.,
+    };
+    let shadow = |alpha: f32, y: f32, blur: f32| BoxShadow {
+        color: hsla(0., 0., 0., alpha * density),
+        offset: point(px(0.), px(y)),
+        blur_radius: px(blur),
+        spread_radius: px(0.),
+    };
+    let raised: SmallVec<[BoxShadow; 2]> = smallvec![shadow(0.12, 2., 3.)];
+
+    ElevationStyles {
+        surface: ElevationStyle {
+            shadow: SmallVec::new(),
+            z_index: 0,
+            overlay: false,
+        },
+        raised: ElevationStyle {
+            shadow: raised.clone(),
+            z_index: 1,
+            overlay: false,
+        },
+        overlay: ElevationStyle {
+            shadow: smallvec![shadow(0.12, 2., 3.), shadow(0.08, 3., 6.)],
+            z_index: 2,
+            overlay: true,
+        },
+        modal: ElevationStyle {
+            shadow: smallvec![
+                shadow(0.12, 2., 3.),
+                shadow(0.08, 3., 6.),
+                shadow(0.04, 6., 12.)
+            ],
+            z_index: 3,
+            overlay: true,
+        },
+        tooltip: ElevationStyle {
+            shadow: raised,
+            z_index: 4,
+            overlay: true,
+        },
+    }
+}
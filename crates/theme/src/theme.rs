@@ -28,7 +28,8 @@ pub use settings::*;
 pub use styles::*;
 
 use gpui::{
-    AppContext, AssetSource, Hsla, SharedString, WindowAppearance, WindowBackgroundAppearance,
+    AppContext, AssetSource, ElevationStyles, Hsla, SharedString, WindowAppearance,
+    WindowBackgroundAppearance,
 };
 use serde::Deserialize;
 
@@ -79,6 +80,10 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut AppContext) {
 
     ThemeSettings::register(cx);
 
+    // Elements are elevated through a global in GPUI, which follows the active theme.
+    let elevation = cx.theme().elevation().clone();
+    cx.set_global(elevation);
+
     let mut prev_buffer_font_size = ThemeSettings::get_global(cx).buffer_font_size;
     cx.observe_global::<SettingsStore>(move |cx| {
         let buffer_font_size = ThemeSettings::get_global(cx).buffer_font_size;
@@ -86,6 +91,11 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut AppContext) {
             prev_buffer_font_size = buffer_font_size;
             reset_buffer_font_size(cx);
         }
+
+        if cx.global::<ElevationStyles>() != cx.theme().elevation() {
+            let elevation = cx.theme().elevation().clone();
+            cx.set_global(elevation);
+        }
     })
     .detach();
 }
@@ -167,6 +177,12 @@ impl Theme {
         self.appearance
     }
 
+    /// Returns the [`ElevationStyles`] for the theme.
+    #[inline(always)]
+    pub fn elevation(&self) -> &ElevationStyles {
+        &self.styles.elevation
+    }
+
     /// Returns the [`WindowBackgroundAppearance`] for the theme.
     #[inline(always)]
     pub fn window_background_appearance(&self) -> WindowBackgroundAppearance {
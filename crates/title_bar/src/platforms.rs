@@ -1,3 +1,2 @@
 pub mod platform_linux;
-pub mod platform_mac;
 pub mod platform_windows;
@@ -2,15 +2,15 @@ mod call_controls;
 mod collab;
 mod platforms;
 
-use crate::platforms::{platform_linux, platform_mac, platform_windows};
+use crate::platforms::{platform_linux, platform_windows};
 use auto_update::AutoUpdateStatus;
 use call::{ActiveCall, ParticipantLocation};
 use client::{Client, UserStore};
 use collab::render_color_ribbon;
 use gpui::{
-    actions, div, px, Action, AnyElement, AppContext, Element, InteractiveElement, Interactivity,
-    IntoElement, Model, ParentElement, Render, Stateful, StatefulInteractiveElement, Styled,
-    Subscription, ViewContext, VisualContext, WeakView,
+    actions, div, px, safe_area, Action, AnyElement, AppContext, Element, InteractiveElement,
+    Interactivity, IntoElement, Model, ParentElement, Render, Stateful, StatefulInteractiveElement,
+    Styled, Subscription, ViewContext, VisualContext, WeakView,
 };
 use project::{Project, RepositoryEntry};
 use recent_projects::RecentProjects;
@@ -72,20 +72,12 @@ impl Render for TitleBar {
         let platform_supported = cfg!(target_os = "macos");
         let height = Self::height(cx);
 
-        h_flex()
+        let title_bar = h_flex()
             .id("titlebar")
             .w_full()
             .pt(Self::top_padding(cx))
             .h(height + Self::top_padding(cx))
-            .map(|this| {
-                if cx.is_fullscreen() {
-                    this.pl_2()
-                } else if self.platform_style == PlatformStyle::Mac {
-                    this.pl(px(platform_mac::TRAFFIC_LIGHT_PADDING))
-                } else {
-                    this.pl_2()
-                }
-            })
+            .pl_2()
             .bg(cx.theme().colors().title_bar_background)
             .content_stretch()
             .child(
@@ -392,7 +384,10 @@ impl Render for TitleBar {
                             }
                         })
                 },
-            )
+            );
+
+        // Keeps the title bar content clear of the macOS traffic lights.
+        safe_area(title_bar)
     }
 }
 
@@ -1,11 +1,15 @@
 mod avatar;
+mod banner;
+mod breadcrumbs;
 mod button;
 mod checkbox;
+mod clamped_text;
 mod context_menu;
 mod disclosure;
 mod divider;
 mod dropdown_menu;
 mod facepile;
+mod form_field;
 mod icon;
 mod indicator;
 mod keybinding;
@@ -14,9 +18,12 @@ mod list;
 mod modal;
 mod popover;
 mod popover_menu;
+mod progress_bar;
 mod radio;
+mod radio_group;
 mod right_click_menu;
 mod setting;
+mod spinner;
 mod stack;
 mod tab;
 mod tab_bar;
@@ -27,13 +34,17 @@ mod tooltip;
 mod stories;
 
 pub use avatar::*;
+pub use banner::*;
+pub use breadcrumbs::*;
 pub use button::*;
 pub use checkbox::*;
+pub use clamped_text::*;
 pub use context_menu::*;
 pub use disclosure::*;
 pub use divider::*;
-use dropdown_menu::*;
+pub use dropdown_menu::*;
 pub use facepile::*;
+pub use form_field::*;
 pub use icon::*;
 pub use indicator::*;
 pub use keybinding::*;
@@ -42,9 +53,12 @@ pub use list::*;
 pub use modal::*;
 pub use popover::*;
 pub use popover_menu::*;
+pub use progress_bar::*;
 pub use radio::*;
+pub use radio_group::*;
 pub use right_click_menu::*;
 pub use setting::*;
+pub use spinner::*;
 pub use stack::*;
 pub use tab::*;
 pub use tab_bar::*;
@@ -1,7 +1,9 @@
 mod avatar;
 mod avatar_audio_status_indicator;
 mod avatar_availability_indicator;
+mod avatar_stack;
 
 pub use avatar::*;
 pub use avatar_audio_status_indicator::*;
 pub use avatar_availability_indicator::*;
+pub use avatar_stack::*;
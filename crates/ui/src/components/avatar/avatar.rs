@@ -1,6 +1,6 @@
-use crate::prelude::*;
+use crate::{prelude::*, Availability, AvatarAvailabilityIndicator};
 
-use gpui::{img, AnyElement, Hsla, ImageSource, Img, IntoElement, Styled};
+use gpui::{img, svg, AnyElement, Hsla, ImageSource, IntoElement, Styled};
 
 /// The shape of an [`Avatar`].
 #[derive(Debug, Default, PartialEq, Clone)]
@@ -12,6 +12,66 @@ pub enum AvatarShape {
     RoundedRectangle,
 }
 
+impl AvatarShape {
+    fn apply<E: Styled>(&self, element: E) -> E {
+        match self {
+            AvatarShape::Circle => element.rounded_full(),
+            AvatarShape::RoundedRectangle => element.rounded_md(),
+        }
+    }
+}
+
+/// The preset sizes of an [`Avatar`], which can be passed to [`Avatar::size`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum AvatarSize {
+    /// 12px
+    XSmall,
+    /// 16px
+    #[default]
+    Small,
+    /// 24px
+    Medium,
+    /// 32px
+    Large,
+}
+
+impl AvatarSize {
+    pub fn rems(self) -> Rems {
+        match self {
+            AvatarSize::XSmall => rems(0.75),
+            AvatarSize::Small => rems(1.),
+            AvatarSize::Medium => rems(1.5),
+            AvatarSize::Large => rems(2.),
+        }
+    }
+}
+
+impl From<AvatarSize> for AbsoluteLength {
+    fn from(size: AvatarSize) -> Self {
+        size.rems().into()
+    }
+}
+
+/// What an [`Avatar`] shows when it has no image, or while its image fails to load.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AvatarFallback {
+    /// A few letters standing for the name of the user, such as "AB".
+    Initials(SharedString),
+    Icon(IconName),
+}
+
+impl Default for AvatarFallback {
+    fn default() -> Self {
+        Self::Icon(IconName::Person)
+    }
+}
+
+impl From<IconName> for AvatarFallback {
+    fn from(icon: IconName) -> Self {
+        Self::Icon(icon)
+    }
+}
+
 /// An element that renders a user avatar with customizable appearance options.
 ///
 /// # Examples
@@ -26,22 +86,58 @@ pub enum AvatarShape {
 /// ```
 #[derive(IntoElement)]
 pub struct Avatar {
-    image: Img,
+    source: Option<ImageSource>,
+    fallback: AvatarFallback,
+    shape: AvatarShape,
+    grayscale: bool,
     size: Option<AbsoluteLength>,
     border_color: Option<Hsla>,
     indicator: Option<AnyElement>,
+    status: Option<Availability>,
 }
 
 impl Avatar {
     pub fn new(src: impl Into<ImageSource>) -> Self {
         Avatar {
-            image: img(src),
+            source: Some(src.into()),
+            fallback: AvatarFallback::default(),
+            shape: AvatarShape::default(),
+            grayscale: false,
+            size: None,
+            border_color: None,
+            indicator: None,
+            status: None,
+        }
+    }
+
+    /// An avatar without an image, which shows the given fallback instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ui::{Avatar, AvatarFallback};
+    ///
+    /// Avatar::placeholder(AvatarFallback::Initials("AB".into()));
+    /// ```
+    pub fn placeholder(fallback: impl Into<AvatarFallback>) -> Self {
+        Avatar {
+            source: None,
+            fallback: fallback.into(),
+            shape: AvatarShape::default(),
+            grayscale: false,
             size: None,
             border_color: None,
             indicator: None,
+            status: None,
         }
     }
 
+    /// Sets what's shown in place of the image when it fails to load. Defaults to a person icon.
+    pub fn fallback(mut self, fallback: impl Into<AvatarFallback>) -> Self {
+        self.fallback = fallback.into();
+        self
+    }
+
     /// Sets the shape of the avatar image.
     ///
     /// This method allows the shape of the avatar to be specified using an [`AvatarShape`].
@@ -55,10 +151,7 @@ impl Avatar {
     /// Avatar::new("path/to/image.png").shape(AvatarShape::Circle);
     /// ```
     pub fn shape(mut self, shape: AvatarShape) -> Self {
-        self.image = match shape {
-            AvatarShape::Circle => self.image.rounded_full(),
-            AvatarShape::RoundedRectangle => self.image.rounded_md(),
-        };
+        self.shape = shape;
         self
     }
 
@@ -72,7 +165,7 @@ impl Avatar {
     /// let avatar = Avatar::new("path/to/image.png").grayscale(true);
     /// ```
     pub fn grayscale(mut self, grayscale: bool) -> Self {
-        self.image = self.image.grayscale(grayscale);
+        self.grayscale = grayscale;
         self
     }
 
@@ -81,7 +174,8 @@ impl Avatar {
         self
     }
 
-    /// Size overrides the avatar size. By default they are 1rem.
+    /// Size overrides the avatar size, which is either one of the [`AvatarSize`] presets or
+    /// any other length. By default they are 1rem.
     pub fn size<L: Into<AbsoluteLength>>(mut self, size: impl Into<Option<L>>) -> Self {
         self.size = size.into().map(Into::into);
         self
@@ -91,37 +185,86 @@ impl Avatar {
         self.indicator = indicator.into().map(IntoElement::into_any_element);
         self
     }
+
+    /// Shows a dot in the bottom right corner of the avatar for the availability of the user,
+    /// sized to match the avatar.
+    pub fn status(mut self, availability: impl Into<Option<Availability>>) -> Self {
+        self.status = availability.into();
+        self
+    }
 }
 
 impl RenderOnce for Avatar {
-    fn render(mut self, cx: &mut WindowContext) -> impl IntoElement {
-        if self.image.style().corner_radii.top_left.is_none() {
-            self = self.shape(AvatarShape::Circle);
-        }
-
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         let border_width = if self.border_color.is_some() {
             px(2.)
         } else {
             px(0.)
         };
 
-        let image_size = self.size.unwrap_or_else(|| rems(1.).into());
-        let container_size = image_size.to_pixels(cx.rem_size()) + border_width * 2.;
+        let image_size = self
+            .size
+            .unwrap_or_else(|| AvatarSize::default().into())
+            .to_pixels(cx.rem_size());
+        let container_size = image_size + border_width * 2.;
 
-        div()
-            .size(container_size)
-            .map(|mut div| {
-                div.style().corner_radii = self.image.style().corner_radii.clone();
-                div
+        // The fallback takes the place of the image at the same size, so that an image failing to
+        // load doesn't move anything around.
+        let image = self
+            .source
+            .filter(|source| !source.failed_to_load(cx))
+            .map(|source| {
+                self.shape
+                    .apply(img(source))
+                    .size(image_size)
+                    .grayscale(self.grayscale)
+                    .bg(cx.theme().colors().ghost_element_background)
+                    .into_any_element()
             })
+            .unwrap_or_else(|| {
+                let content = match self.fallback {
+                    AvatarFallback::Initials(initials) => div()
+                        .text_size(image_size * 0.4)
+                        .line_height(image_size)
+                        .text_color(cx.theme().colors().text_muted)
+                        .child(initials)
+                        .into_any_element(),
+                    AvatarFallback::Icon(icon) => svg()
+                        .path(icon.path())
+                        .size(image_size * 0.6)
+                        .text_color(cx.theme().colors().icon_muted)
+                        .into_any_element(),
+                };
+                self.shape
+                    .apply(div())
+                    .flex()
+                    .flex_none()
+                    .items_center()
+                    .justify_center()
+                    .size(image_size)
+                    .overflow_hidden()
+                    .bg(cx.theme().colors().element_background)
+                    .child(content)
+                    .into_any_element()
+            });
+
+        let indicator = self.indicator.or_else(|| {
+            self.status.map(|availability| {
+                AvatarAvailabilityIndicator::new(availability)
+                    .avatar_size(image_size)
+                    .into_any_element()
+            })
+        });
+
+        self.shape
+            .apply(div())
+            .relative()
+            .flex_none()
+            .size(container_size)
             .when_some(self.border_color, |this, color| {
                 this.border(border_width).border_color(color)
             })
-            .child(
-                self.image
-                    .size(image_size)
-                    .bg(cx.theme().colors().ghost_element_background),
-            )
-            .children(self.indicator.map(|indicator| div().child(indicator)))
+            .child(image)
+            .children(indicator.map(|indicator| div().child(indicator)))
     }
 }
@@ -0,0 +1,189 @@
+use std::rc::Rc;
+
+use gpui::ClickEvent;
+
+use crate::{prelude::*, Avatar, AvatarSize};
+
+/// A row of overlapping [`Avatar`]s, such as the collaborators in a project, with the first
+/// avatar in front.
+///
+/// Only the first few avatars are shown, followed by a "+N" chip counting the others.
+///
+/// # Examples
+///
+/// ```
+/// use ui::{Avatar, AvatarStack};
+///
+/// AvatarStack::new("collaborators")
+///     .max_visible(2)
+///     .child(Avatar::new("path/to/first.png"))
+///     .child(Avatar::new("path/to/second.png"))
+///     .child(Avatar::new("path/to/third.png"));
+/// ```
+#[derive(IntoElement)]
+pub struct AvatarStack {
+    id: ElementId,
+    avatars: Vec<Avatar>,
+    max_visible: usize,
+    size: AvatarSize,
+    on_click: Option<Rc<dyn Fn(usize, &ClickEvent, &mut WindowContext)>>,
+}
+
+impl AvatarStack {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            avatars: Vec::new(),
+            max_visible: 4,
+            size: AvatarSize::default(),
+            on_click: None,
+        }
+    }
+
+    pub fn child(mut self, avatar: Avatar) -> Self {
+        self.avatars.push(avatar);
+        self
+    }
+
+    pub fn children(mut self, avatars: impl IntoIterator<Item = Avatar>) -> Self {
+        self.avatars.extend(avatars);
+        self
+    }
+
+    /// Sets how many avatars are shown before the rest are counted in an overflow chip.
+    /// Defaults to 4.
+    pub fn max_visible(mut self, max_visible: usize) -> Self {
+        self.max_visible = max_visible;
+        self
+    }
+
+    /// Sets the size of all of the avatars in the stack.
+    pub fn size(mut self, size: AvatarSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Called with the index of the avatar that was clicked. Where two avatars overlap, the
+    /// click goes to the one in front.
+    pub fn on_click(
+        mut self,
+        handler: impl Fn(usize, &ClickEvent, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl RenderOnce for AvatarStack {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let size = self.size.rems().to_pixels(cx.rem_size());
+        let overlap = (size * 0.25).round();
+        let overflow = self.avatars.len().saturating_sub(self.max_visible);
+
+        let overflow_chip = (overflow > 0).then(|| {
+            div()
+                .flex()
+                .flex_none()
+                .items_center()
+                .justify_center()
+                .h(size)
+                .min_w(size)
+                .px(overlap / 2.)
+                .ml(-overlap)
+                .rounded_full()
+                .bg(cx.theme().colors().element_background)
+                .text_size(size * 0.4)
+                .text_color(cx.theme().colors().text_muted)
+                .child(format!("+{overflow}"))
+        });
+
+        let on_click = self.on_click;
+        let avatars = self
+            .avatars
+            .into_iter()
+            .take(self.max_visible)
+            .enumerate()
+            .map(|(ix, avatar)| {
+                div()
+                    .id(ix)
+                    .flex_none()
+                    .rounded_full()
+                    .when(ix > 0, |this| this.ml(-overlap))
+                    .debug_selector(|| format!("AVATAR-{ix}"))
+                    // Keep the avatars behind this one from being hovered or clicked through it.
+                    .occlude()
+                    .when_some(on_click.clone(), |this, on_click| {
+                        this.cursor_pointer()
+                            .on_click(move |event, cx| on_click(ix, event, cx))
+                    })
+                    .child(avatar.size(self.size))
+            })
+            .collect::<Vec<_>>();
+
+        // Lay the avatars out in reverse so that each is painted over the ones after it, as
+        // painted order decides both which is visible and which is hit where they overlap.
+        div()
+            .id(self.id)
+            .flex()
+            .flex_row_reverse()
+            .justify_end()
+            .items_center()
+            .children(overflow_chip)
+            .children(avatars.into_iter().rev())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use gpui::{point, Modifiers, Render, TestAppContext, ViewContext};
+
+    use super::*;
+    use crate::AvatarFallback;
+
+    struct Collaborators {
+        clicked: Rc<RefCell<Vec<usize>>>,
+    }
+
+    impl Render for Collaborators {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let clicked = self.clicked.clone();
+            div().flex().child(
+                AvatarStack::new("collaborators")
+                    .children(["AB", "CD", "EF"].into_iter().map(|initials| {
+                        Avatar::placeholder(AvatarFallback::Initials(initials.into()))
+                    }))
+                    .on_click(move |ix, _, _| clicked.borrow_mut().push(ix)),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_clicks_go_to_the_avatar_in_front(cx: &mut TestAppContext) {
+        crate::test::init(cx);
+        let clicked = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view({
+            let clicked = clicked.clone();
+            |_| Collaborators { clicked }
+        });
+        cx.run_until_parked();
+
+        let first = cx.debug_bounds("AVATAR-0").unwrap();
+        let second = cx.debug_bounds("AVATAR-1").unwrap();
+        assert!(second.left() < first.right());
+
+        // Where the first two avatars overlap.
+        cx.simulate_click(
+            point(second.left() + px(1.), second.center().y),
+            Modifiers::none(),
+        );
+        assert_eq!(*clicked.borrow(), [0]);
+
+        cx.simulate_click(
+            point(first.right() + px(1.), second.center().y),
+            Modifiers::none(),
+        );
+        assert_eq!(*clicked.borrow(), [0, 1]);
+    }
+}
@@ -0,0 +1,395 @@
+use std::{cell::Cell, rc::Rc};
+
+use gpui::{
+    canvas, AnyElement, Bounds, Element, GlobalElementId, Hsla, IntoElement, LayoutId, Pixels,
+    Point,
+};
+use smallvec::SmallVec;
+
+use crate::prelude::*;
+
+/// How much room a [`Banner`] must leave for its content beside its actions. Any narrower and
+/// the actions are stacked below the content.
+const MIN_INLINE_CONTENT_WIDTH: Pixels = px(240.);
+/// How much more room a stacked banner must have for its content before its actions move back
+/// beside it, so that it doesn't flip back and forth while resized around the breakpoint.
+const BREAKPOINT_HYSTERESIS: Pixels = px(32.);
+
+/// How important the message of a [`Banner`] is, which determines its colors and icon.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BannerSeverity {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl BannerSeverity {
+    fn icon(self) -> IconName {
+        match self {
+            BannerSeverity::Info => IconName::Info,
+            BannerSeverity::Success => IconName::CheckCircle,
+            BannerSeverity::Warning => IconName::ExclamationTriangle,
+            BannerSeverity::Error => IconName::XCircle,
+        }
+    }
+
+    fn icon_color(self) -> Color {
+        match self {
+            BannerSeverity::Info => Color::Info,
+            BannerSeverity::Success => Color::Success,
+            BannerSeverity::Warning => Color::Warning,
+            BannerSeverity::Error => Color::Error,
+        }
+    }
+
+    /// The background and border colors.
+    fn colors(self, cx: &WindowContext) -> (Hsla, Hsla) {
+        let status = cx.theme().status();
+        match self {
+            BannerSeverity::Info => (status.info_background, status.info_border),
+            BannerSeverity::Success => (status.success_background, status.success_border),
+            BannerSeverity::Warning => (status.warning_background, status.warning_border),
+            BannerSeverity::Error => (status.error_background, status.error_border),
+        }
+    }
+}
+
+/// # Banner
+///
+/// A message that stays in place among the content it's about, unlike a toast, with an icon
+/// and colors for its severity.
+///
+/// The actions are shown beside the content, aligned to the trailing edge, or below it when
+/// the banner is too narrow for them to leave enough room. Layout decides which from the width
+/// the banner is given, so the arrangement always matches it.
+///
+/// # Examples
+///
+/// ```
+/// use ui::{prelude::*, Banner, BannerSeverity};
+///
+/// Banner::new("update", BannerSeverity::Warning)
+///     .child(Label::new("A new version is available."))
+///     .action(Button::new("restart", "Restart"))
+///     .on_dismiss(|_| {});
+/// ```
+pub struct Banner {
+    id: ElementId,
+    severity: BannerSeverity,
+    children: SmallVec<[AnyElement; 2]>,
+    actions: SmallVec<[AnyElement; 2]>,
+    on_dismiss: Option<Rc<dyn Fn(&mut WindowContext)>>,
+}
+
+impl Banner {
+    pub fn new(id: impl Into<ElementId>, severity: BannerSeverity) -> Self {
+        Self {
+            id: id.into(),
+            severity,
+            children: SmallVec::new(),
+            actions: SmallVec::new(),
+            on_dismiss: None,
+        }
+    }
+
+    /// Adds an element, usually a button, to the actions shown at the trailing edge.
+    pub fn action(mut self, action: impl IntoElement) -> Self {
+        self.actions.push(action.into_any_element());
+        self
+    }
+
+    /// Shows a button that dismisses the banner by calling the given handler.
+    pub fn on_dismiss(mut self, handler: impl Fn(&mut WindowContext) + 'static) -> Self {
+        self.on_dismiss = Some(Rc::new(handler));
+        self
+    }
+
+    fn render_body(
+        &mut self,
+        min_content_width: Pixels,
+        origins: &ArrangementOrigins,
+        cx: &WindowContext,
+    ) -> Div {
+        let (background, border) = self.severity.colors(cx);
+        let dismiss = self.on_dismiss.clone().map(|on_dismiss| {
+            IconButton::new("dismiss", IconName::Close)
+                .icon_size(IconSize::Small)
+                .on_click(move |_, cx| on_dismiss(cx))
+        });
+        let record_origin = |origin: &Rc<Cell<Option<Point<Pixels>>>>| {
+            let origin = origin.clone();
+            canvas(
+                move |bounds, _| origin.set(Some(bounds.origin)),
+                |_, _, _| {},
+            )
+            .absolute()
+            .size_full()
+        };
+        let content_row = h_flex()
+            .relative()
+            .items_start()
+            .flex_grow()
+            .flex_basis(min_content_width)
+            .min_w_0()
+            .gap(Spacing::Medium.rems(cx))
+            .debug_selector(|| "BANNER-CONTENT".into())
+            .child(
+                Icon::new(self.severity.icon())
+                    .size(IconSize::Small)
+                    .color(self.severity.icon_color()),
+            )
+            .child(div().flex_1().min_w_0().children(self.children.drain(..)))
+            .child(record_origin(&origins.content));
+        let actions = (!self.actions.is_empty()).then(|| {
+            h_flex()
+                .relative()
+                .flex_none()
+                .ml_auto()
+                .gap(Spacing::Small.rems(cx))
+                .debug_selector(|| "BANNER-ACTIONS".into())
+                .children(self.actions.drain(..))
+                .child(record_origin(&origins.actions))
+        });
+
+        // The actions wrap onto a line of their own once the content would have less than its
+        // minimum width beside them.
+        h_flex()
+            .w_full()
+            .items_start()
+            .gap(Spacing::Medium.rems(cx))
+            .px(Spacing::Large.rems(cx))
+            .py(Spacing::Medium.rems(cx))
+            .rounded_md()
+            .border_1()
+            .border_color(border)
+            .bg(background)
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_start()
+                    .flex_1()
+                    .min_w_0()
+                    .gap_x(Spacing::Medium.rems(cx))
+                    .gap_y(Spacing::Small.rems(cx))
+                    .child(content_row)
+                    .children(actions),
+            )
+            .children(dismiss)
+    }
+}
+
+/// Persisted across frames to decide how to arrange a [`Banner`].
+#[derive(Clone, Copy, Default)]
+struct BannerState {
+    stacked: bool,
+}
+
+/// Where the content and the actions of a [`Banner`] were placed, to tell whether the actions
+/// were stacked.
+#[derive(Default)]
+struct ArrangementOrigins {
+    content: Rc<Cell<Option<Point<Pixels>>>>,
+    actions: Rc<Cell<Option<Point<Pixels>>>>,
+}
+
+pub struct BannerLayout {
+    body: AnyElement,
+    origins: ArrangementOrigins,
+}
+
+impl Element for Banner {
+    type RequestLayoutState = BannerLayout;
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn request_layout(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let stacked = cx.with_element_state(id.unwrap(), |state: Option<BannerState>, _| {
+            let state = state.unwrap_or_default();
+            (state.stacked, state)
+        });
+        // A stacked banner needs more room to unstack than an inline one needs to stay inline.
+        let min_content_width = if stacked {
+            MIN_INLINE_CONTENT_WIDTH + BREAKPOINT_HYSTERESIS
+        } else {
+            MIN_INLINE_CONTENT_WIDTH
+        };
+
+        let origins = ArrangementOrigins::default();
+        let mut body = self
+            .render_body(min_content_width, &origins, cx)
+            .into_any_element();
+        let layout_id = body.request_layout(cx);
+        (layout_id, BannerLayout { body, origins })
+    }
+
+    fn prepaint(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) {
+        layout.body.prepaint(cx);
+        let (Some(content), Some(actions)) =
+            (layout.origins.content.get(), layout.origins.actions.get())
+        else {
+            return;
+        };
+        cx.with_element_state(id.unwrap(), |_: Option<BannerState>, _| {
+            let stacked = actions.y > content.y;
+            ((), BannerState { stacked })
+        });
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        layout: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        layout.body.paint(cx);
+    }
+}
+
+impl IntoElement for Banner {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl ParentElement for Banner {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use gpui::{Modifiers, Render, TestAppContext, View, VisualTestContext};
+
+    use super::*;
+
+    struct Notice {
+        width: Pixels,
+        dismissed: Rc<RefCell<usize>>,
+    }
+
+    impl Render for Notice {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let dismissed = self.dismissed.clone();
+            div().w(self.width).child(
+                Banner::new("notice", BannerSeverity::Warning)
+                    .child(Label::new("This file was changed on disk."))
+                    .action(Button::new("reload", "Reload"))
+                    .action(Button::new("keep", "Keep"))
+                    .on_dismiss(move |_| *dismissed.borrow_mut() += 1),
+            )
+        }
+    }
+
+    fn add_notice(
+        width: Pixels,
+        cx: &mut TestAppContext,
+    ) -> (View<Notice>, &mut VisualTestContext, Rc<RefCell<usize>>) {
+        crate::test::init(cx);
+        let dismissed = Rc::new(RefCell::new(0));
+        let (view, cx) = cx.add_window_view({
+            let dismissed = dismissed.clone();
+            |_| Notice { width, dismissed }
+        });
+        cx.run_until_parked();
+        cx.simulate_frame();
+        (view, cx, dismissed)
+    }
+
+    fn set_width(view: &View<Notice>, width: Pixels, cx: &mut VisualTestContext) {
+        view.update(cx, |view, cx| {
+            view.width = width;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.simulate_frame();
+    }
+
+    fn is_stacked(cx: &mut VisualTestContext) -> bool {
+        let content = cx.debug_bounds("BANNER-CONTENT").unwrap();
+        let actions = cx.debug_bounds("BANNER-ACTIONS").unwrap();
+        actions.top() >= content.bottom()
+    }
+
+    #[gpui::test]
+    fn test_actions_stack_below_the_content_when_narrow(cx: &mut TestAppContext) {
+        let (view, cx, _) = add_notice(px(640.), cx);
+        assert!(!is_stacked(cx));
+        let content = cx.debug_bounds("BANNER-CONTENT").unwrap();
+        let actions = cx.debug_bounds("BANNER-ACTIONS").unwrap();
+        assert!(actions.left() >= content.right());
+
+        set_width(&view, px(200.), cx);
+        assert!(is_stacked(cx));
+        let content = cx.debug_bounds("BANNER-CONTENT").unwrap();
+        let actions = cx.debug_bounds("BANNER-ACTIONS").unwrap();
+        // Still aligned to the trailing edge.
+        assert!(actions.right() > content.center().x);
+    }
+
+    #[gpui::test]
+    fn test_breakpoint_has_hysteresis(cx: &mut TestAppContext) {
+        let (view, cx, _) = add_notice(px(640.), cx);
+        // The width at which the content is left with its minimum width beside the actions.
+        let content_width = cx.debug_bounds("BANNER-CONTENT").unwrap().size.width;
+        let breakpoint = px(640.) - content_width + MIN_INLINE_CONTENT_WIDTH;
+        let within_hysteresis = breakpoint + BREAKPOINT_HYSTERESIS / 2.;
+
+        set_width(&view, within_hysteresis, cx);
+        assert!(!is_stacked(cx));
+        set_width(&view, breakpoint - px(1.), cx);
+        assert!(is_stacked(cx));
+
+        // Growing back past the breakpoint isn't enough to unstack the actions...
+        set_width(&view, within_hysteresis, cx);
+        assert!(is_stacked(cx));
+        // ...until the banner has grown past the hysteresis too.
+        set_width(&view, breakpoint + BREAKPOINT_HYSTERESIS + px(1.), cx);
+        assert!(!is_stacked(cx));
+    }
+
+    #[gpui::test]
+    fn test_arrangement_matches_the_width_in_the_same_frame(cx: &mut TestAppContext) {
+        let (view, cx, _) = add_notice(px(640.), cx);
+
+        // Each width is drawn in its arrangement straight away, without waiting for a frame.
+        for (width, stacked) in [(px(200.), true), (px(640.), false), (px(200.), true)] {
+            view.update(cx, |view, cx| {
+                view.width = width;
+                cx.notify();
+            });
+            cx.run_until_parked();
+            assert_eq!(is_stacked(cx), stacked, "at {width:?}");
+        }
+    }
+
+    #[gpui::test]
+    fn test_dismiss_button(cx: &mut TestAppContext) {
+        let (_, cx, dismissed) = add_notice(px(640.), cx);
+        let dismiss = cx.debug_bounds("ICON-Close").unwrap();
+        cx.simulate_click(dismiss.center(), Modifiers::none());
+        assert_eq!(*dismissed.borrow(), 1);
+    }
+}
@@ -0,0 +1,439 @@
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+use gpui::{
+    point, px, AnyElement, AvailableSpace, Bounds, ContentMask, Element, ElementId,
+    GlobalElementId, IntoElement, LayoutId, Pixels, Size, Style, WindowContext,
+};
+
+use crate::{prelude::*, ContextMenu, PopoverMenu};
+
+/// One of the segments shown by [`Breadcrumbs`].
+pub struct BreadcrumbSegment {
+    label: SharedString,
+    on_click: Option<Rc<dyn Fn(&mut WindowContext) + 'static>>,
+}
+
+impl BreadcrumbSegment {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            on_click: None,
+        }
+    }
+
+    pub fn on_click(mut self, handler: impl Fn(&mut WindowContext) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+}
+
+/// # Breadcrumbs
+///
+/// Shows a path of segments separated by a separator element.
+///
+/// When the segments don't fit in the available width, the segments between the first
+/// and the last ones are collapsed, starting from the first, into a "…" item that opens a
+/// menu listing them. If the first and last segments still don't fit, the last one is
+/// truncated with an ellipsis.
+pub struct Breadcrumbs {
+    id: ElementId,
+    segments: Vec<BreadcrumbSegment>,
+    separator: Rc<dyn Fn(&mut WindowContext) -> AnyElement>,
+}
+
+impl Breadcrumbs {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            segments: Vec::new(),
+            separator: Rc::new(|_| Label::new("/").color(Color::Muted).into_any_element()),
+        }
+    }
+
+    pub fn segment(mut self, segment: BreadcrumbSegment) -> Self {
+        self.segments.push(segment);
+        self
+    }
+
+    pub fn segments(mut self, segments: impl IntoIterator<Item = BreadcrumbSegment>) -> Self {
+        self.segments.extend(segments);
+        self
+    }
+
+    /// Sets the element rendered between segments. Defaults to a muted "/".
+    pub fn separator<E: IntoElement>(
+        mut self,
+        separator: impl Fn(&mut WindowContext) -> E + 'static,
+    ) -> Self {
+        self.separator = Rc::new(move |cx| separator(cx).into_any_element());
+        self
+    }
+
+    fn render_segment(&self, ix: usize, label: SharedString, cx: &WindowContext) -> AnyElement {
+        match self.segments[ix].on_click.clone() {
+            Some(on_click) => Button::new(("segment", ix), label)
+                .style(ButtonStyle::Subtle)
+                .on_click(move |_, cx| on_click(cx))
+                .into_any_element(),
+            None => h_flex()
+                .px(Spacing::Small.rems(cx))
+                .child(Label::new(label))
+                .into_any_element(),
+        }
+    }
+
+    fn render_overflow(&self, hidden: Rc<RefCell<Range<usize>>>) -> AnyElement {
+        let hidden_segments = self
+            .segments
+            .iter()
+            .map(|segment| (segment.label.clone(), segment.on_click.clone()))
+            .collect::<Rc<[_]>>();
+
+        PopoverMenu::new("overflow")
+            .trigger(Button::new("overflow-trigger", "…").style(ButtonStyle::Subtle))
+            .menu(move |cx| {
+                let hidden = hidden.borrow().clone();
+                let hidden_segments = hidden_segments.clone();
+                Some(ContextMenu::build(cx, move |menu, _| {
+                    hidden_segments[hidden]
+                        .iter()
+                        .fold(menu, |menu, (label, on_click)| match on_click.clone() {
+                            Some(on_click) => {
+                                menu.entry(label.clone(), None, move |cx| on_click(cx))
+                            }
+                            None => menu.label(label.clone()),
+                        })
+                }))
+            })
+            .into_any_element()
+    }
+
+    /// Finds the longest prefix of the segment's label that fits in `max_width` once an
+    /// ellipsis is appended, and returns its element.
+    fn render_truncated(
+        &self,
+        ix: usize,
+        max_width: Pixels,
+        cx: &mut WindowContext,
+    ) -> (AnyElement, Size<Pixels>) {
+        let label = self.segments[ix].label.clone();
+        let boundaries = label
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain([label.len()])
+            .collect::<Vec<_>>();
+
+        let measure = |prefix_len: usize, cx: &mut WindowContext| {
+            let truncated = format!("{}…", &label[..boundaries[prefix_len]]);
+            let mut element = self.render_segment(ix, truncated.into(), cx);
+            let size = element.layout_as_root(AvailableSpace::min_size(), cx);
+            (element, size)
+        };
+
+        let (mut lo, mut hi) = (0, boundaries.len() - 1);
+        let mut best = measure(0, cx);
+        while lo < hi {
+            let mid = (lo + hi + 1) / 2;
+            let candidate = measure(mid, cx);
+            if candidate.1.width <= max_width {
+                best = candidate;
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        best
+    }
+}
+
+pub struct BreadcrumbsLayout {
+    segments: Vec<(AnyElement, Size<Pixels>)>,
+    separators: Vec<(AnyElement, Size<Pixels>)>,
+    overflow: Option<(AnyElement, Size<Pixels>)>,
+    hidden: Rc<RefCell<Range<usize>>>,
+    height: Pixels,
+}
+
+impl Element for Breadcrumbs {
+    type RequestLayoutState = BreadcrumbsLayout;
+    type PrepaintState = Vec<AnyElement>;
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let measure = |mut element: AnyElement, cx: &mut WindowContext| {
+            let size = element.layout_as_root(AvailableSpace::min_size(), cx);
+            (element, size)
+        };
+
+        let segments = (0..self.segments.len())
+            .map(|ix| {
+                measure(
+                    self.render_segment(ix, self.segments[ix].label.clone(), cx),
+                    cx,
+                )
+            })
+            .collect::<Vec<_>>();
+        let separators = (1..self.segments.len())
+            .map(|_| measure((self.separator)(cx), cx))
+            .collect::<Vec<_>>();
+        let hidden = Rc::new(RefCell::new(0..0));
+        let overflow = measure(self.render_overflow(hidden.clone()), cx);
+
+        let height = segments
+            .iter()
+            .chain(&separators)
+            .chain([&overflow])
+            .map(|(_, size)| size.height)
+            .fold(px(0.), Pixels::max);
+        let natural_width = segments
+            .iter()
+            .chain(&separators)
+            .map(|(_, size)| size.width)
+            .fold(px(0.), |total, width| total + width);
+
+        let layout_id = cx.request_measured_layout(Style::default(), move |known, available, _| {
+            let width = known.width.unwrap_or(match available.width {
+                AvailableSpace::Definite(width) => natural_width.min(width),
+                AvailableSpace::MinContent => px(0.),
+                AvailableSpace::MaxContent => natural_width,
+            });
+            Size { width, height }
+        });
+
+        (
+            layout_id,
+            BreadcrumbsLayout {
+                segments,
+                separators,
+                overflow: Some(overflow),
+                hidden,
+                height,
+            },
+        )
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        let widths = layout
+            .segments
+            .iter()
+            .map(|(_, size)| size.width)
+            .collect::<Vec<_>>();
+        let separator_width = layout
+            .separators
+            .first()
+            .map_or(px(0.), |(_, size)| size.width);
+        let (hidden, last_width) = collapse(
+            &widths,
+            separator_width,
+            layout
+                .overflow
+                .as_ref()
+                .map_or(px(0.), |(_, size)| size.width),
+            bounds.size.width,
+        );
+        *layout.hidden.borrow_mut() = hidden.clone();
+
+        let mut items = Vec::new();
+        let mut segments = layout.segments.drain(..).enumerate();
+        let mut separators = layout.separators.drain(..);
+        while let Some((ix, segment)) = segments.next() {
+            if ix > 0 {
+                items.extend(separators.next());
+            }
+            if ix == hidden.start && !hidden.is_empty() {
+                items.extend(layout.overflow.take());
+                segments.by_ref().take(hidden.len() - 1).for_each(drop);
+                continue;
+            }
+            if ix == widths.len() - 1 {
+                if let Some(max_width) = last_width.filter(|width| *width < segment.1.width) {
+                    items.push(self.render_truncated(ix, max_width, cx));
+                    continue;
+                }
+            }
+            items.push(segment);
+        }
+
+        cx.with_content_mask(Some(ContentMask { bounds }), |cx| {
+            let mut x = bounds.origin.x;
+            items
+                .into_iter()
+                .map(|(mut element, size)| {
+                    let y = bounds.origin.y + (layout.height - size.height) / 2.;
+                    element.prepaint_at(point(x, y), cx);
+                    x += size.width;
+                    element
+                })
+                .collect()
+        })
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _layout: &mut Self::RequestLayoutState,
+        items: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        cx.with_content_mask(Some(ContentMask { bounds }), |cx| {
+            for item in items {
+                item.paint(cx);
+            }
+        })
+    }
+}
+
+impl IntoElement for Breadcrumbs {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+/// Decides which segments to hide behind the overflow item so that the row fits in
+/// `available`, returning the hidden range and, if the last segment still doesn't fit,
+/// the width left for it.
+fn collapse(
+    widths: &[Pixels],
+    separator: Pixels,
+    overflow: Pixels,
+    available: Pixels,
+) -> (Range<usize>, Option<Pixels>) {
+    let row_width = |hidden: &Range<usize>| {
+        let visible = widths
+            .iter()
+            .enumerate()
+            .filter(|(ix, _)| !hidden.contains(ix))
+            .map(|(_, width)| *width)
+            .fold(px(0.), |total, width| total + width);
+        let mut items = widths.len() - hidden.len();
+        let mut width = visible;
+        if !hidden.is_empty() {
+            items += 1;
+            width += overflow;
+        }
+        width + separator * items.saturating_sub(1) as f32
+    };
+
+    if widths.is_empty() {
+        return (0..0, None);
+    }
+
+    let last = widths.len() - 1;
+    let mut hidden = 1..1;
+    while row_width(&hidden) > available {
+        if hidden.end >= last {
+            let remaining = available - (row_width(&hidden) - widths[last]);
+            return (hidden, Some(remaining.max(px(0.))));
+        }
+        hidden.end += 1;
+    }
+    (hidden, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{Modifiers, Render, TestAppContext, ViewContext};
+
+    use super::*;
+
+    const WIDTHS: [Pixels; 4] = [px(30.), px(40.), px(50.), px(60.)];
+
+    #[test]
+    fn test_collapse_keeps_a_row_that_fits() {
+        // 180px of segments and three 10px separators.
+        assert_eq!(collapse(&WIDTHS, px(10.), px(20.), px(210.)), (1..1, None));
+        assert_eq!(collapse(&[], px(10.), px(20.), px(0.)), (0..0, None));
+    }
+
+    #[test]
+    fn test_collapse_hides_segments_from_the_first() {
+        // Hiding the second segment leaves 140px of segments, the 20px overflow item and
+        // three separators.
+        assert_eq!(collapse(&WIDTHS, px(10.), px(20.), px(200.)), (1..2, None));
+        assert_eq!(collapse(&WIDTHS, px(10.), px(20.), px(190.)), (1..2, None));
+        assert_eq!(collapse(&WIDTHS, px(10.), px(20.), px(189.)), (1..3, None));
+        assert_eq!(collapse(&WIDTHS, px(10.), px(20.), px(130.)), (1..3, None));
+    }
+
+    #[test]
+    fn test_collapse_truncates_the_last_segment() {
+        // With every middle segment hidden, the first segment, the overflow item and two
+        // separators take 70px.
+        assert_eq!(
+            collapse(&WIDTHS, px(10.), px(20.), px(100.)),
+            (1..3, Some(px(30.)))
+        );
+        assert_eq!(
+            collapse(&WIDTHS, px(10.), px(20.), px(50.)),
+            (1..3, Some(px(0.)))
+        );
+        assert_eq!(
+            collapse(&[px(80.)], px(10.), px(20.), px(50.)),
+            (1..1, Some(px(50.)))
+        );
+    }
+
+    const PATH: [&str; 5] = [
+        "zed",
+        "a-rather-long-directory-name",
+        "another-rather-long-directory",
+        "and-one-more-long-directory",
+        "main.rs",
+    ];
+
+    struct Path {
+        clicked: Vec<&'static str>,
+    }
+
+    impl Render for Path {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let view = cx.view().downgrade();
+            div()
+                .w(px(150.))
+                .child(Breadcrumbs::new("path").segments(PATH.map(|label| {
+                    let view = view.clone();
+                    BreadcrumbSegment::new(label).on_click(move |cx| {
+                        view.update(cx, |path, _| path.clicked.push(label)).ok();
+                    })
+                })))
+        }
+    }
+
+    #[gpui::test]
+    fn test_clicking_a_hidden_segment(cx: &mut TestAppContext) {
+        crate::test::init(cx);
+        let (path, cx) = cx.add_window_view(|_| Path {
+            clicked: Vec::new(),
+        });
+        cx.run_until_parked();
+
+        cx.driver().click("overflow-trigger");
+        let item = cx
+            .debug_bounds("MENU_ITEM-another-rather-long-directory")
+            .expect("hidden segments are listed in the overflow menu");
+        cx.simulate_mouse_move(item.center(), None, Modifiers::none());
+        cx.simulate_click(item.center(), Modifiers::none());
+        cx.run_until_parked();
+
+        assert_eq!(
+            path.update(cx, |path, _| path.clicked.clone()),
+            ["another-rather-long-directory"]
+        );
+    }
+}
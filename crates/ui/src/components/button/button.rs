@@ -1,4 +1,4 @@
-use gpui::{AnyView, DefiniteLength};
+use gpui::{AnyView, DefiniteLength, FocusHandle};
 
 use crate::{prelude::*, ElevationIndex, IconPosition, KeyBinding, Spacing};
 use crate::{
@@ -350,6 +350,11 @@ impl ButtonCommon for Button {
         self.base = self.base.layer(elevation);
         self
     }
+
+    fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.base = self.base.track_focus(focus_handle);
+        self
+    }
 }
 
 impl RenderOnce for Button {
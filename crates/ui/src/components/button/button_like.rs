@@ -1,8 +1,12 @@
-use gpui::{relative, CursorStyle, DefiniteLength, MouseButton};
-use gpui::{transparent_black, AnyElement, AnyView, ClickEvent, Hsla, Rems};
+use gpui::{
+    black, pick_readable_text, transparent_black, white, AnyElement, AnyView, ClickEvent, Hsla,
+    Rems,
+};
+use gpui::{relative, CursorStyle, DefiniteLength, FocusHandle, MouseButton};
 use smallvec::SmallVec;
+use theme::UiDensity;
 
-use crate::{prelude::*, Elevation, ElevationIndex, Spacing};
+use crate::{prelude::*, user_spacing_style, Elevation, ElevationIndex, Spacing};
 
 /// A trait for buttons that can be Selected. Enables setting the [`ButtonStyle`] of a button when it is selected.
 pub trait SelectableButton: Selectable {
@@ -35,6 +39,10 @@ pub trait ButtonCommon: Clickable + Disableable {
     fn tooltip(self, tooltip: impl Fn(&mut WindowContext) -> AnyView + 'static) -> Self;
 
     fn layer(self, elevation: ElevationIndex) -> Self;
+
+    /// Lets the button be focused with the given handle. While it's focused from the keyboard, it
+    /// shows a focus ring.
+    fn track_focus(self, focus_handle: &FocusHandle) -> Self;
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
@@ -54,25 +62,25 @@ pub enum TintColor {
 
 impl TintColor {
     fn button_like_style(self, cx: &mut WindowContext) -> ButtonLikeStyles {
-        match self {
-            TintColor::Accent => ButtonLikeStyles {
-                background: cx.theme().status().info_background,
-                border_color: cx.theme().status().info_border,
-                label_color: cx.theme().colors().text,
-                icon_color: cx.theme().colors().text,
-            },
-            TintColor::Negative => ButtonLikeStyles {
-                background: cx.theme().status().error_background,
-                border_color: cx.theme().status().error_border,
-                label_color: cx.theme().colors().text,
-                icon_color: cx.theme().colors().text,
-            },
-            TintColor::Warning => ButtonLikeStyles {
-                background: cx.theme().status().warning_background,
-                border_color: cx.theme().status().warning_border,
-                label_color: cx.theme().colors().text,
-                icon_color: cx.theme().colors().text,
-            },
+        let status = cx.theme().status();
+        let (background, border_color) = match self {
+            TintColor::Accent => (status.info_background, status.info_border),
+            TintColor::Negative => (status.error_background, status.error_border),
+            TintColor::Warning => (status.warning_background, status.warning_border),
+        };
+
+        // Tint backgrounds come straight from the theme, so make sure the text drawn on them
+        // stays readable, falling back to black or white if the theme's text color isn't.
+        let text_color = pick_readable_text(
+            background.blend_over(cx.theme().colors().background),
+            &[cx.theme().colors().text, black(), white()],
+        );
+
+        ButtonLikeStyles {
+            background,
+            border_color,
+            label_color: text_color,
+            icon_color: text_color,
         }
     }
 }
@@ -137,6 +145,13 @@ pub(crate) struct ButtonLikeStyles {
     pub icon_color: Hsla,
 }
 
+/// How much the perceptual lightness of a filled button changes when it's hovered. Filled
+/// buttons get lighter in dark themes and darker in light themes.
+const FILLED_HOVER_LIGHTNESS_CHANGE: f32 = 0.08;
+
+/// The opacity of a disabled filled button's background over the window background.
+const FILLED_DISABLED_OPACITY: f32 = 0.4;
+
 fn element_bg_from_elevation(elevation: Option<Elevation>, cx: &mut WindowContext) -> Hsla {
     match elevation {
         Some(Elevation::ElevationIndex(ElevationIndex::Background)) => {
@@ -191,8 +206,12 @@ impl ButtonStyle {
         elevation: Option<Elevation>,
         cx: &mut WindowContext,
     ) -> ButtonLikeStyles {
-        let mut filled_background = element_bg_from_elevation(elevation, cx);
-        filled_background.fade_out(0.92);
+        let filled_background = element_bg_from_elevation(elevation, cx);
+        let filled_background = if cx.theme().appearance().is_light() {
+            filled_background.darken(FILLED_HOVER_LIGHTNESS_CHANGE)
+        } else {
+            filled_background.lighten(FILLED_HOVER_LIGHTNESS_CHANGE)
+        };
 
         match self {
             ButtonStyle::Filled => ButtonLikeStyles {
@@ -276,11 +295,13 @@ impl ButtonStyle {
         elevation: Option<Elevation>,
         cx: &mut WindowContext,
     ) -> ButtonLikeStyles {
-        let filled_background = element_bg_from_elevation(elevation, cx).fade_out(0.82);
+        let filled_background = element_bg_from_elevation(elevation, cx)
+            .opacity(FILLED_DISABLED_OPACITY)
+            .blend_over(cx.theme().colors().background);
 
         match self {
             ButtonStyle::Filled => ButtonLikeStyles {
-                background: cx.theme().colors().element_disabled,
+                background: filled_background,
                 border_color: cx.theme().colors().border_disabled,
                 label_color: Color::Disabled.color(cx),
                 icon_color: Color::Disabled.color(cx),
@@ -323,6 +344,21 @@ impl ButtonSize {
             ButtonSize::None => rems_from_px(16.),
         }
     }
+
+    /// The height of a button of this size at the density of the elements being drawn.
+    pub fn height(self, cx: &WindowContext) -> Rems {
+        let px = match self {
+            ButtonSize::Large => 32.,
+            ButtonSize::Default => 22.,
+            ButtonSize::Compact => 18.,
+            ButtonSize::None => return self.rems(),
+        };
+        match user_spacing_style(cx) {
+            UiDensity::Compact => rems_from_px(px - 4.),
+            UiDensity::Default => rems_from_px(px),
+            UiDensity::Comfortable => rems_from_px(px + 4.),
+        }
+    }
 }
 
 /// A button-like element that can be used to create a custom button when
@@ -346,6 +382,7 @@ pub struct ButtonLike {
     tooltip: Option<Box<dyn Fn(&mut WindowContext) -> AnyView>>,
     cursor_style: CursorStyle,
     on_click: Option<Box<dyn Fn(&ClickEvent, &mut WindowContext) + 'static>>,
+    focus_handle: Option<FocusHandle>,
     children: SmallVec<[AnyElement; 2]>,
 }
 
@@ -366,6 +403,7 @@ impl ButtonLike {
             children: SmallVec::new(),
             cursor_style: CursorStyle::PointingHand,
             on_click: None,
+            focus_handle: None,
             layer: None,
         }
     }
@@ -450,6 +488,11 @@ impl ButtonCommon for ButtonLike {
         self.layer = Some(elevation.into());
         self
     }
+
+    fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
 }
 
 impl VisibleOnHover for ButtonLike {
@@ -472,12 +515,13 @@ impl RenderOnce for ButtonLike {
             .filter(|_| self.selected)
             .unwrap_or(self.style);
 
-        self.base
+        let button = self
+            .base
             .h_flex()
             .id(self.id.clone())
             .group("")
             .flex_none()
-            .h(self.height.unwrap_or(self.size.rems().into()))
+            .h(self.height.unwrap_or(self.size.height(cx).into()))
             .when_some(self.width, |this, width| this.w(width).justify_center())
             .when_some(self.rounding, |this, rounding| match rounding {
                 ButtonLikeRounding::All => this.rounded_md(),
@@ -510,6 +554,14 @@ impl RenderOnce for ButtonLike {
             .when_some(self.tooltip, |this, tooltip| {
                 this.tooltip(move |cx| tooltip(cx))
             })
-            .children(self.children)
+            .children(self.children);
+
+        match self.focus_handle {
+            Some(focus_handle) => button
+                .track_focus(&focus_handle)
+                .focus_visible(|style| style.focus_ring(cx))
+                .into_any_element(),
+            None => button.into_any_element(),
+        }
     }
 }
@@ -1,4 +1,4 @@
-use gpui::{AnyView, DefiniteLength};
+use gpui::{AnyView, DefiniteLength, FocusHandle};
 
 use crate::{prelude::*, ElevationIndex, SelectableButton, Spacing};
 use crate::{ButtonCommon, ButtonLike, ButtonSize, ButtonStyle, IconName, IconSize};
@@ -32,7 +32,12 @@ impl IconButton {
             icon_color: Color::Default,
             selected_icon: None,
         };
-        this.base.base = this.base.base.debug_selector(|| format!("ICON-{:?}", icon));
+        this.base.base = this
+            .base
+            .base
+            .debug_selector(|| format!("ICON-{:?}", icon))
+            // Icons are small, so let them be clicked a little outside of what's painted.
+            .min_click_size(px(24.));
         this
     }
 
@@ -129,6 +134,11 @@ impl ButtonCommon for IconButton {
         self.base = self.base.layer(elevation);
         self
     }
+
+    fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.base = self.base.track_focus(focus_handle);
+        self
+    }
 }
 
 impl VisibleOnHover for IconButton {
@@ -153,6 +163,7 @@ impl RenderOnce for IconButton {
                         IconSize::XSmall => Spacing::XSmall.px(cx),
                         IconSize::Small => Spacing::XSmall.px(cx),
                         IconSize::Medium => Spacing::XSmall.px(cx),
+                        IconSize::Large => Spacing::Small.px(cx),
                     };
 
                     this.width((icon_size + padding * 2.).into())
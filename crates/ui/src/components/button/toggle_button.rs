@@ -1,4 +1,4 @@
-use gpui::{AnyView, ClickEvent};
+use gpui::{AnyView, ClickEvent, FocusHandle};
 
 use crate::{prelude::*, ButtonLike, ButtonLikeRounding, ElevationIndex};
 
@@ -113,6 +113,11 @@ impl ButtonCommon for ToggleButton {
         self.base = self.base.layer(elevation);
         self
     }
+
+    fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.base = self.base.track_focus(focus_handle);
+        self
+    }
 }
 
 impl RenderOnce for ToggleButton {
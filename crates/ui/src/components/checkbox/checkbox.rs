@@ -1,7 +1,7 @@
-use gpui::{div, prelude::*, ElementId, IntoElement, Styled, WindowContext};
+use gpui::{div, prelude::*, ElementId, FocusHandle, IntoElement, Styled, WindowContext};
 
 use crate::prelude::*;
-use crate::{Color, Icon, IconName, Selection};
+use crate::{Color, FormField, Icon, IconName, Selection};
 
 /// # Checkbox
 ///
@@ -14,6 +14,7 @@ pub struct Checkbox {
     checked: Selection,
     disabled: bool,
     on_click: Option<Box<dyn Fn(&Selection, &mut WindowContext) + 'static>>,
+    focus_handle: Option<FocusHandle>,
 }
 
 impl Checkbox {
@@ -23,6 +24,7 @@ impl Checkbox {
             checked,
             disabled: false,
             on_click: None,
+            focus_handle: None,
         }
     }
 
@@ -35,11 +37,20 @@ impl Checkbox {
         self.on_click = Some(Box::new(handler));
         self
     }
+
+    /// Lets the checkbox be focused with the given handle. While it's focused from the keyboard,
+    /// it shows a focus ring.
+    pub fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
 }
 
 impl RenderOnce for Checkbox {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
-        let group_id = format!("checkbox_group_{:?}", self.id);
+        // Hover groups resolve to the innermost enclosing group with the name, so checkboxes
+        // within each other's groups needn't name theirs apart.
+        let group_id = "checkbox";
 
         let icon = match self.checked {
             Selection::Selected => Some(Icon::new(IconName::Check).size(IconSize::Small).color(
@@ -78,13 +89,18 @@ impl RenderOnce for Checkbox {
                 cx.theme().colors().border,
             ),
         };
+        let border_color = if !self.disabled && FormField::is_invalid(cx) {
+            cx.theme().status().error
+        } else {
+            border_color
+        };
 
-        h_flex()
+        let checkbox = h_flex()
             .id(self.id)
             .justify_center()
             .items_center()
             .size(crate::styles::custom_spacing(cx, 20.))
-            .group(group_id.clone())
+            .group(group_id)
             .child(
                 div()
                     .flex()
@@ -98,15 +114,21 @@ impl RenderOnce for Checkbox {
                     .border_1()
                     .border_color(border_color)
                     .when(!self.disabled, |this| {
-                        this.group_hover(group_id.clone(), |el| {
-                            el.bg(cx.theme().colors().element_hover)
-                        })
+                        this.group_hover(group_id, |el| el.bg(cx.theme().colors().element_hover))
                     })
                     .children(icon),
             )
             .when_some(
                 self.on_click.filter(|_| !self.disabled),
                 |this, on_click| this.on_click(move |_, cx| on_click(&self.checked.inverse(), cx)),
-            )
+            );
+
+        match self.focus_handle {
+            Some(focus_handle) => checkbox
+                .track_focus(&focus_handle)
+                .focus_visible(|style| style.focus_ring(cx).rounded_sm())
+                .into_any_element(),
+            None => checkbox.into_any_element(),
+        }
     }
 }
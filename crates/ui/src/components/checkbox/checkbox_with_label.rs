@@ -39,7 +39,7 @@ impl RenderOnce for CheckboxWithLabel {
             }))
             .child(
                 div()
-                    .id(SharedString::from(format!("{}-label", self.id)))
+                    .id(self.id.clone().scoped("label"))
                     .on_click(move |_event, cx| {
                         (self.on_click)(&self.checked.inverse(), cx);
                     })
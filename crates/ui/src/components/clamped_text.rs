@@ -0,0 +1,365 @@
+use std::{cell::Cell, rc::Rc};
+
+use gpui::{
+    canvas, fill, point, size, AnyElement, AvailableSpace, Bounds, GlobalElementId, Hsla,
+    IntoElement, LayoutId, Pixels, Point, Size, StyledText, TextLayout,
+};
+
+use crate::prelude::*;
+
+/// The number of lines a [`ClampedText`] shows until it's expanded, unless it's given another.
+const DEFAULT_LINES: usize = 3;
+/// How wide the fade into the "Show more" control is.
+const FADE_WIDTH: Pixels = px(48.);
+/// The number of bands the fade is painted in.
+const FADE_STEPS: usize = 8;
+
+/// # ClampedText
+///
+/// A block of text, such as a chat message or a description, that's cut off after a few lines
+/// with a "Show more" control over the end of the last line, which fades out beneath it. The
+/// control expands the text inline, and is then shown below it as "Show less".
+///
+/// Whether the text is cut off depends on the width it's laid out at, so "Show more" is only
+/// drawn in frames where the text didn't fit, including while the text is resized. Expanded text
+/// keeps "Show less" below it until it's clicked, even once it's widened enough to fit.
+///
+/// # Examples
+///
+/// ```
+/// use ui::{prelude::*, ClampedText};
+///
+/// ClampedText::new("description", "A long description of the extension.").lines(2);
+/// ```
+pub struct ClampedText {
+    id: ElementId,
+    text: SharedString,
+    lines: usize,
+    fade_color: Option<Hsla>,
+}
+
+impl ClampedText {
+    pub fn new(id: impl Into<ElementId>, text: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+            lines: DEFAULT_LINES,
+            fade_color: None,
+        }
+    }
+
+    /// Sets the number of lines shown until the text is expanded, three by default.
+    pub fn lines(mut self, lines: usize) -> Self {
+        self.lines = lines.max(1);
+        self
+    }
+
+    /// Sets the color the last line fades into beneath the "Show more" control, which should
+    /// match the background the text is shown on. This is the panel background by default.
+    pub fn fade_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.fade_color = Some(color.into());
+        self
+    }
+}
+
+/// Persisted across frames to keep a [`ClampedText`] expanded.
+#[derive(Clone, Default)]
+struct ClampedTextState {
+    expanded: Rc<Cell<bool>>,
+}
+
+pub struct ClampedTextLayout {
+    body: AnyElement,
+    text: TextLayout,
+    expanded: bool,
+    toggle: AnyElement,
+    toggle_size: Size<Pixels>,
+    toggle_origin: Rc<Cell<Option<Point<Pixels>>>>,
+}
+
+impl Element for ClampedText {
+    type RequestLayoutState = ClampedTextLayout;
+    /// Whether the toggle is drawn.
+    type PrepaintState = bool;
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn request_layout(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let expanded = cx.with_element_state(id.unwrap(), |state: Option<ClampedTextState>, _| {
+            let state = state.unwrap_or_default();
+            (state.expanded.clone(), state)
+        });
+        let is_expanded = expanded.get();
+
+        // The toggle is measured on its own, and drawn over the end of the last line or over a
+        // placeholder below the text, once it's known whether the text was cut off.
+        let parent_id = cx.parent_view_id();
+        let mut toggle = h_flex()
+            .debug_selector(|| "CLAMPED-TEXT-TOGGLE".into())
+            .child(
+                Button::new(
+                    "toggle",
+                    if is_expanded {
+                        "Show less"
+                    } else {
+                        "Show more"
+                    },
+                )
+                .label_size(LabelSize::Small)
+                .color(Color::Accent)
+                .on_click(move |_, cx| {
+                    expanded.set(!expanded.get());
+                    if let Some(parent_id) = parent_id {
+                        cx.notify(parent_id)
+                    } else {
+                        cx.refresh()
+                    }
+                }),
+            )
+            .into_any_element();
+        let toggle_size = toggle.layout_as_root(AvailableSpace::min_size(), cx);
+
+        let mut text = StyledText::new(self.text.clone());
+        if !is_expanded {
+            text = text.line_clamp(self.lines);
+        }
+        let text_layout = text.layout().clone();
+        let toggle_origin = Rc::new(Cell::new(None));
+        let mut body = v_flex()
+            .w_full()
+            .debug_selector(|| "CLAMPED-TEXT".into())
+            .child(text)
+            .when(is_expanded, |this| {
+                let toggle_origin = toggle_origin.clone();
+                this.child(
+                    h_flex().justify_end().child(
+                        canvas(
+                            move |bounds, _| toggle_origin.set(Some(bounds.origin)),
+                            |_, _, _| {},
+                        )
+                        .flex_none()
+                        .w(toggle_size.width)
+                        .h(toggle_size.height),
+                    ),
+                )
+            })
+            .into_any_element();
+        let layout_id = body.request_layout(cx);
+        (
+            layout_id,
+            ClampedTextLayout {
+                body,
+                text: text_layout,
+                expanded: is_expanded,
+                toggle,
+                toggle_size,
+                toggle_origin,
+            },
+        )
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> bool {
+        layout.body.prepaint(cx);
+
+        // Collapsing expanded text that fits would change its layout after this frame, so it's
+        // only collapsed with its toggle, and both are decided from this frame's layout alone.
+        if !layout.expanded && !layout.text.is_clamped() {
+            return false;
+        }
+
+        let origin = if layout.expanded {
+            layout.toggle_origin.get().unwrap_or(bounds.origin)
+        } else {
+            let text_bounds = layout.text.bounds();
+            let line_height = layout.text.line_height();
+            point(
+                text_bounds.right() - layout.toggle_size.width,
+                text_bounds.bottom() - (line_height + layout.toggle_size.height) / 2.,
+            )
+        };
+        layout.toggle.prepaint_at(origin, cx);
+        true
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        layout: &mut Self::RequestLayoutState,
+        show_toggle: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        layout.body.paint(cx);
+        if !*show_toggle {
+            return;
+        }
+
+        if !layout.expanded {
+            let text_bounds = layout.text.bounds();
+            let line_height = layout.text.line_height();
+            let fade_color = self
+                .fade_color
+                .unwrap_or(cx.theme().colors().panel_background);
+            let toggle_left = text_bounds.right() - layout.toggle_size.width;
+            let top = text_bounds.bottom() - line_height;
+            let step = FADE_WIDTH / FADE_STEPS as f32;
+            for ix in 0..FADE_STEPS {
+                let left = toggle_left - FADE_WIDTH + step * ix as f32;
+                let opacity = (ix + 1) as f32 / (FADE_STEPS + 1) as f32;
+                cx.paint_quad(fill(
+                    Bounds::new(point(left, top), size(step, line_height)),
+                    fade_color.opacity(opacity),
+                ));
+            }
+            cx.paint_quad(fill(
+                Bounds::from_corners(point(toggle_left, top), text_bounds.lower_right()),
+                fade_color,
+            ));
+        }
+        layout.toggle.paint(cx);
+    }
+}
+
+impl IntoElement for ClampedText {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{Modifiers, Render, TestAppContext, View, VisualTestContext};
+
+    use super::*;
+
+    struct Message {
+        width: Pixels,
+    }
+
+    impl Render for Message {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .w(self.width)
+                .font_family("Zed Plex Mono")
+                .text_size(px(16.))
+                .line_height(px(20.))
+                .child(ClampedText::new(
+                    "message",
+                    "aaa bb cc dddd e f gggggg ".repeat(3),
+                ))
+        }
+    }
+
+    fn add_message(
+        width: Pixels,
+        cx: &mut TestAppContext,
+    ) -> (View<Message>, &mut VisualTestContext) {
+        crate::test::init(cx);
+        cx.text_system()
+            .add_fonts(vec![std::fs::read(
+                "../../assets/fonts/plex-mono/ZedPlexMono-Regular.ttf",
+            )
+            .unwrap()
+            .into()])
+            .unwrap();
+        let (view, cx) = cx.add_window_view(|_| Message { width });
+        cx.run_until_parked();
+        (view, cx)
+    }
+
+    fn set_width(view: &View<Message>, width: Pixels, cx: &mut VisualTestContext) {
+        view.update(cx, |view, cx| {
+            view.width = width;
+            cx.notify();
+        });
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn test_narrow_text_is_clamped_with_a_toggle(cx: &mut TestAppContext) {
+        let (_, cx) = add_message(px(200.), cx);
+        assert_eq!(
+            cx.debug_bounds("CLAMPED-TEXT").unwrap().size.height,
+            px(60.)
+        );
+        let toggle = cx.debug_bounds("CLAMPED-TEXT-TOGGLE").unwrap();
+        // Over the end of the last line.
+        assert!(toggle.center().y > px(40.) && toggle.center().y < px(60.));
+
+        cx.simulate_click(toggle.center(), Modifiers::none());
+        cx.run_until_parked();
+        let text = cx.debug_bounds("CLAMPED-TEXT").unwrap();
+        assert!(text.size.height > px(100.));
+        // "Show less" is below the text.
+        let toggle = cx.debug_bounds("CLAMPED-TEXT-TOGGLE").unwrap();
+        assert_eq!(toggle.bottom(), text.bottom());
+
+        cx.simulate_click(toggle.center(), Modifiers::none());
+        cx.run_until_parked();
+        assert_eq!(
+            cx.debug_bounds("CLAMPED-TEXT").unwrap().size.height,
+            px(60.)
+        );
+    }
+
+    #[gpui::test]
+    fn test_wide_text_is_not_clamped_or_given_a_toggle(cx: &mut TestAppContext) {
+        let (view, cx) = add_message(px(200.), cx);
+        assert!(cx.debug_bounds("CLAMPED-TEXT-TOGGLE").is_some());
+
+        // The text fits on one line in the first frame drawn at the new width.
+        set_width(&view, px(1000.), cx);
+        assert_eq!(
+            cx.debug_bounds("CLAMPED-TEXT").unwrap().size.height,
+            px(20.)
+        );
+
+        // Nothing is drawn over the end of the line to expand the text.
+        cx.simulate_click(point(px(990.), px(10.)), Modifiers::none());
+        cx.run_until_parked();
+        assert_eq!(
+            cx.debug_bounds("CLAMPED-TEXT").unwrap().size.height,
+            px(20.)
+        );
+    }
+
+    #[gpui::test]
+    fn test_widening_expanded_text_keeps_it_expanded(cx: &mut TestAppContext) {
+        let (view, cx) = add_message(px(200.), cx);
+        let toggle = cx.debug_bounds("CLAMPED-TEXT-TOGGLE").unwrap();
+        cx.simulate_click(toggle.center(), Modifiers::none());
+        cx.run_until_parked();
+
+        // The first frame at the new width is drawn as it'll stay, with "Show less" below the
+        // text, and no other frame is needed to settle it.
+        set_width(&view, px(1000.), cx);
+        assert!(!cx.has_pending_frame_callbacks());
+        let text = cx.debug_bounds("CLAMPED-TEXT").unwrap();
+        let toggle = cx.debug_bounds("CLAMPED-TEXT-TOGGLE").unwrap();
+        assert_eq!(text.size.height, px(20.) + toggle.size.height);
+        assert_eq!(toggle.bottom(), text.bottom());
+
+        // Collapsing the text that fits removes the toggle.
+        cx.simulate_click(toggle.center(), Modifiers::none());
+        cx.run_until_parked();
+        assert_eq!(
+            cx.debug_bounds("CLAMPED-TEXT").unwrap().size.height,
+            px(20.)
+        );
+        assert!(cx.debug_bounds("CLAMPED-TEXT-TOGGLE").is_none());
+    }
+}
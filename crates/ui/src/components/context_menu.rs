@@ -1,16 +1,22 @@
 use crate::{
-    h_flex, prelude::*, v_flex, Icon, IconName, KeyBinding, Label, List, ListItem, ListSeparator,
+    h_flex, prelude::*, v_flex, Icon, IconName, KeyBinding, Label, ListItem, ListSeparator,
     ListSubHeader, WithRemSize,
 };
 use gpui::{
-    px, Action, AnyElement, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView,
-    IntoElement, Render, Subscription, View, VisualContext,
+    anchored, canvas, deferred, dismissable, px, roving_focus, Action, AnyElement, AppContext,
+    Bounds, DismissEvent, Empty, EventEmitter, FocusHandle, FocusableView, Global, IntoElement,
+    KeyDownEvent, Pixels, Point, Render, RovingFocusHandle, Subscription, View, VisualContext,
+    WeakView,
 };
 use menu::{SelectFirst, SelectLast, SelectNext, SelectPrev};
 use settings::Settings;
-use std::{rc::Rc, time::Duration};
+use std::{cell::RefCell, rc::Rc, time::Duration};
 use theme::ThemeSettings;
 
+/// How long the mouse has to rest on an entry before its submenu opens, or before the open
+/// submenu closes when the mouse moves on to another entry.
+const SUBMENU_HOVER_DELAY: Duration = Duration::from_millis(200);
+
 enum ContextMenuItem {
     Separator,
     Header(SharedString),
@@ -21,21 +27,92 @@ enum ContextMenuItem {
         icon: Option<IconName>,
         handler: Rc<dyn Fn(Option<&FocusHandle>, &mut WindowContext)>,
         action: Option<Box<dyn Action>>,
+        disabled: bool,
     },
     CustomEntry {
         entry_render: Box<dyn Fn(&mut WindowContext) -> AnyElement>,
         handler: Rc<dyn Fn(Option<&FocusHandle>, &mut WindowContext)>,
         selectable: bool,
     },
+    Submenu {
+        label: SharedString,
+        icon: Option<IconName>,
+        builder: Rc<dyn Fn(ContextMenu, &mut WindowContext) -> ContextMenu>,
+    },
+}
+
+/// An entry of a [`ContextMenu`], added with [`ContextMenu::item`].
+pub struct ContextMenuEntry {
+    label: SharedString,
+    icon: Option<IconName>,
+    toggled: Option<bool>,
+    action: Option<Box<dyn Action>>,
+    handler: Option<Rc<dyn Fn(Option<&FocusHandle>, &mut WindowContext)>>,
+    disabled: bool,
+}
+
+impl ContextMenuEntry {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            icon: None,
+            toggled: None,
+            action: None,
+            handler: None,
+            disabled: false,
+        }
+    }
+
+    pub fn icon(mut self, icon: IconName) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn toggled(mut self, toggled: bool) -> Self {
+        self.toggled = Some(toggled);
+        self
+    }
+
+    /// Shows the keybinding of the action next to the label. Unless the entry has a handler,
+    /// selecting it dispatches the action in the menu's context.
+    pub fn action(mut self, action: Box<dyn Action>) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    pub fn handler(mut self, handler: impl Fn(&mut WindowContext) + 'static) -> Self {
+        self.handler = Some(Rc::new(move |_, cx| handler(cx)));
+        self
+    }
+
+    /// Shows the entry as unavailable. Disabled entries can't be clicked, and keyboard
+    /// navigation skips over them.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// A submenu opened from one of the entries of a [`ContextMenu`].
+struct OpenSubmenu {
+    ix: usize,
+    menu: View<ContextMenu>,
+    _dismiss_subscription: Subscription,
 }
 
 pub struct ContextMenu {
     items: Vec<ContextMenuItem>,
     focus_handle: FocusHandle,
     action_context: Option<FocusHandle>,
-    selected_index: Option<usize>,
+    selection: RovingFocusHandle,
     delayed: bool,
     clicked: bool,
+    /// The menu this one was opened from, for submenus.
+    parent: Option<WeakView<ContextMenu>>,
+    submenu: Option<OpenSubmenu>,
+    /// Where the entries opening submenus were drawn in the last frame, so that their submenus
+    /// can be placed beside them.
+    item_bounds: Rc<RefCell<Vec<Option<Bounds<Pixels>>>>>,
     _on_blur_subscription: Subscription,
 }
 
@@ -49,6 +126,18 @@ impl EventEmitter<DismissEvent> for ContextMenu {}
 
 impl FluentBuilder for ContextMenu {}
 
+/// The menu opened in a window with [`ContextMenu::open_at`], kept as a window global.
+#[derive(Default)]
+struct OpenContextMenus(Option<OpenContextMenu>);
+
+impl Global for OpenContextMenus {}
+
+struct OpenContextMenu {
+    menu: View<ContextMenu>,
+    position: Point<Pixels>,
+    previous_focus: Option<FocusHandle>,
+}
+
 impl ContextMenu {
     pub fn build(
         cx: &mut WindowContext,
@@ -57,7 +146,7 @@ impl ContextMenu {
         cx.new_view(|cx| {
             let focus_handle = cx.focus_handle();
             let _on_blur_subscription = cx.on_blur(&focus_handle, |this: &mut ContextMenu, cx| {
-                this.cancel(&menu::Cancel, cx)
+                this.handle_blur(cx)
             });
             cx.refresh();
             f(
@@ -65,9 +154,12 @@ impl ContextMenu {
                     items: Default::default(),
                     focus_handle,
                     action_context: None,
-                    selected_index: None,
+                    selection: RovingFocusHandle::new(),
                     delayed: false,
                     clicked: false,
+                    parent: None,
+                    submenu: None,
+                    item_bounds: Rc::default(),
                     _on_blur_subscription,
                 },
                 cx,
@@ -75,11 +167,58 @@ impl ContextMenu {
         })
     }
 
+    /// Builds a menu and opens it at the given position in the window, such as where the mouse
+    /// went down, and focuses it. The menu is drawn by the window's [`context_menu_host`].
+    ///
+    /// Only one menu opened this way is shown in a window at a time: opening another closes
+    /// this one. Once the menu is dismissed, focus goes back to where it was before it opened.
+    pub fn open_at(
+        position: Point<Pixels>,
+        cx: &mut WindowContext,
+        f: impl FnOnce(Self, &mut WindowContext) -> Self,
+    ) -> View<Self> {
+        let previous_focus = match cx.default_window_global::<OpenContextMenus>().0.take() {
+            // Replacing a menu, whose focus is still the one to go back to.
+            Some(replaced) => replaced.previous_focus,
+            None => cx.focused(),
+        };
+        let menu = Self::build(cx, f);
+        cx.subscribe(&menu, move |menu, _: &DismissEvent, cx| {
+            let open_menu = &mut cx.default_window_global::<OpenContextMenus>().0;
+            if open_menu.as_ref().map_or(true, |open| open.menu != menu) {
+                return;
+            }
+            let closed = open_menu.take().unwrap();
+            if menu.read(cx).contains_focus(cx) {
+                if let Some(previous_focus) = closed.previous_focus {
+                    cx.focus(&previous_focus);
+                }
+            }
+            cx.refresh();
+        })
+        .detach();
+        cx.focus_view(&menu);
+        cx.default_window_global::<OpenContextMenus>().0 = Some(OpenContextMenu {
+            menu: menu.clone(),
+            position,
+            previous_focus,
+        });
+        cx.refresh();
+        menu
+    }
+
     pub fn context(mut self, focus: FocusHandle) -> Self {
         self.action_context = Some(focus);
         self
     }
 
+    /// Makes the item at the given index, counting headers and separators, the active one when
+    /// the menu opens, such as the current option of a select.
+    pub fn active_item(self, ix: usize) -> Self {
+        self.selection.set_active_index(Some(ix));
+        self
+    }
+
     pub fn header(mut self, title: impl Into<SharedString>) -> Self {
         self.items.push(ContextMenuItem::Header(title.into()));
         self
@@ -102,6 +241,7 @@ impl ContextMenu {
             handler: Rc::new(move |_, cx| handler(cx)),
             icon: None,
             action,
+            disabled: false,
         });
         self
     }
@@ -119,6 +259,51 @@ impl ContextMenu {
             handler: Rc::new(move |_, cx| handler(cx)),
             icon: None,
             action,
+            disabled: false,
+        });
+        self
+    }
+
+    /// Adds an entry built with [`ContextMenuEntry`], which can have an icon or be disabled.
+    pub fn item(mut self, entry: ContextMenuEntry) -> Self {
+        let handler = match (entry.handler, &entry.action) {
+            (Some(handler), _) => handler,
+            (None, Some(action)) => {
+                let action = action.boxed_clone();
+                Rc::new(
+                    move |context: Option<&FocusHandle>, cx: &mut WindowContext| {
+                        if let Some(context) = &context {
+                            cx.focus(context);
+                        }
+                        cx.dispatch_action(action.boxed_clone());
+                    },
+                )
+            }
+            (None, None) => Rc::new(|_: Option<&FocusHandle>, _: &mut WindowContext| {}),
+        };
+        self.items.push(ContextMenuItem::Entry {
+            toggled: entry.toggled,
+            label: entry.label,
+            icon: entry.icon,
+            handler,
+            action: entry.action,
+            disabled: entry.disabled,
+        });
+        self
+    }
+
+    /// Adds an entry which opens another menu beside this one, built by `builder` each time
+    /// it opens. Submenus open when their entry is hovered for a moment or when it's active
+    /// and the right arrow is pressed, and close with the left arrow or escape.
+    pub fn submenu(
+        mut self,
+        label: impl Into<SharedString>,
+        builder: impl Fn(ContextMenu, &mut WindowContext) -> ContextMenu + 'static,
+    ) -> Self {
+        self.items.push(ContextMenuItem::Submenu {
+            label: label.into(),
+            icon: None,
+            builder: Rc::new(builder),
         });
         self
     }
@@ -167,6 +352,7 @@ impl ContextMenu {
                 cx.dispatch_action(action.boxed_clone());
             }),
             icon: None,
+            disabled: false,
         });
         self
     }
@@ -179,21 +365,16 @@ impl ContextMenu {
             action: Some(action.boxed_clone()),
             handler: Rc::new(move |_, cx| cx.dispatch_action(action.boxed_clone())),
             icon: Some(IconName::Link),
+            disabled: false,
         });
         self
     }
 
     pub fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
-        let context = self.action_context.as_ref();
-        match self.selected_index.and_then(|ix| self.items.get(ix)) {
-            Some(
-                ContextMenuItem::Entry { handler, .. }
-                | ContextMenuItem::CustomEntry { handler, .. },
-            ) => (handler)(context, cx),
-            _ => {}
+        match self.selection.active_index() {
+            Some(ix) => self.activate(ix, cx),
+            None => cx.emit(DismissEvent),
         }
-
-        cx.emit(DismissEvent);
     }
 
     pub fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
@@ -201,15 +382,157 @@ impl ContextMenu {
         cx.emit(DismissEvent);
     }
 
+    /// Selects the item at the given index: submenus are opened and focused, and entries close
+    /// this menu along with the menus it was opened from before their handler runs.
+    fn activate(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let handler = match self.items.get(ix) {
+            Some(
+                ContextMenuItem::Entry {
+                    handler,
+                    disabled: false,
+                    ..
+                }
+                | ContextMenuItem::CustomEntry {
+                    handler,
+                    selectable: true,
+                    ..
+                },
+            ) => handler.clone(),
+            Some(ContextMenuItem::Submenu { .. }) => return self.open_submenu(ix, true, cx),
+            _ => return,
+        };
+        let context = self.action_context.clone();
+        self.clicked = true;
+        self.dismiss_chain(cx);
+        // Deferred until the menus are gone and focus has gone back to where it was, so that
+        // the handler sees the window as it was before the menu opened.
+        cx.window_context()
+            .defer(move |cx| handler(context.as_ref(), cx));
+    }
+
+    /// Dismisses the menu that isn't a submenu of another, closing all of its submenus.
+    fn dismiss_chain(&mut self, cx: &mut ViewContext<Self>) {
+        match self.parent.as_ref().and_then(|parent| parent.upgrade()) {
+            Some(parent) => parent.update(cx, |parent, cx| parent.dismiss_chain(cx)),
+            None => cx.emit(DismissEvent),
+        }
+    }
+
+    fn handle_blur(&mut self, cx: &mut ViewContext<Self>) {
+        // Focus moved into one of this menu's submenus.
+        if self.contains_focus(cx) {
+            return;
+        }
+        match self.parent.as_ref().and_then(|parent| parent.upgrade()) {
+            // Focus went back to the menu this one was opened from, which stays open.
+            Some(parent) if parent.focus_handle(cx).is_focused(cx) => cx.emit(DismissEvent),
+            _ => self.dismiss_chain(cx),
+        }
+    }
+
+    /// Whether this menu or one of its open submenus is focused.
+    fn contains_focus(&self, cx: &WindowContext) -> bool {
+        self.focus_handle.is_focused(cx)
+            || self
+                .submenu
+                .as_ref()
+                .map_or(false, |submenu| submenu.menu.read(cx).contains_focus(cx))
+    }
+
+    fn open_submenu(&mut self, ix: usize, focus: bool, cx: &mut ViewContext<Self>) {
+        if self.submenu.as_ref().map(|submenu| submenu.ix) != Some(ix) {
+            let Some(ContextMenuItem::Submenu { builder, .. }) = self.items.get(ix) else {
+                return;
+            };
+            let builder = builder.clone();
+            self.close_submenu(cx);
+
+            let parent = cx.view().downgrade();
+            let action_context = self.action_context.clone();
+            let menu = ContextMenu::build(cx, move |mut menu, cx| {
+                menu.parent = Some(parent);
+                menu.action_context = action_context;
+                builder(menu, cx)
+            });
+            let _dismiss_subscription = cx.subscribe(&menu, |this, _, _: &DismissEvent, cx| {
+                this.close_submenu(cx)
+            });
+            self.submenu = Some(OpenSubmenu {
+                ix,
+                menu,
+                _dismiss_subscription,
+            });
+            cx.notify();
+        }
+        if focus {
+            let Some(menu) = self.submenu.as_ref().map(|submenu| submenu.menu.clone()) else {
+                return;
+            };
+            menu.update(cx, |menu, cx| menu.select_first(&SelectFirst, cx));
+            cx.focus_view(&menu);
+        }
+    }
+
+    fn close_submenu(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(submenu) = self.submenu.take() {
+            if submenu.menu.read(cx).contains_focus(cx) {
+                cx.focus(&self.focus_handle);
+            }
+            cx.notify();
+        }
+    }
+
+    /// Opens the submenu of the entry the mouse rested on, or closes the open one when the mouse
+    /// rested on another entry. The entry registers this with the window's delayed hovers only
+    /// when resting on it would change something, see [`Self::item_hover_changes_submenu`].
+    fn hover_item(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if matches!(self.items.get(ix), Some(ContextMenuItem::Submenu { .. })) {
+            self.open_submenu(ix, false, cx);
+        } else {
+            self.close_submenu(cx);
+        }
+    }
+
+    /// Whether the mouse resting on the entry opens its submenu or closes the open one.
+    fn item_hover_changes_submenu(&self, ix: usize) -> bool {
+        let open_ix = self.submenu.as_ref().map(|submenu| submenu.ix);
+        match self.items.get(ix) {
+            Some(ContextMenuItem::Submenu { .. }) => open_ix != Some(ix),
+            _ => open_ix.is_some(),
+        }
+    }
+
+    fn handle_key_down(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        if event.keystroke.modifiers.modified() {
+            return;
+        }
+        match event.keystroke.key.as_str() {
+            "right" => {
+                if let Some(ix) = self.selection.active_index().filter(|ix| {
+                    matches!(self.items.get(*ix), Some(ContextMenuItem::Submenu { .. }))
+                }) {
+                    cx.stop_propagation();
+                    self.open_submenu(ix, true, cx);
+                }
+            }
+            "left" if self.parent.is_some() => {
+                cx.stop_propagation();
+                cx.emit(DismissEvent);
+            }
+            _ => {}
+        }
+    }
+
     fn select_first(&mut self, _: &SelectFirst, cx: &mut ViewContext<Self>) {
-        self.selected_index = self.items.iter().position(|item| item.is_selectable());
+        self.selection
+            .set_active_index(self.items.iter().position(|item| item.is_selectable()));
         cx.notify();
     }
 
     pub fn select_last(&mut self) -> Option<usize> {
         for (ix, item) in self.items.iter().enumerate().rev() {
             if item.is_selectable() {
-                self.selected_index = Some(ix);
+                self.selection.set_active_index(Some(ix));
                 return Some(ix);
             }
         }
@@ -223,10 +546,10 @@ impl ContextMenu {
     }
 
     fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
-        if let Some(ix) = self.selected_index {
+        if let Some(ix) = self.selection.active_index() {
             for (ix, item) in self.items.iter().enumerate().skip(ix + 1) {
                 if item.is_selectable() {
-                    self.selected_index = Some(ix);
+                    self.selection.set_active_index(Some(ix));
                     cx.notify();
                     break;
                 }
@@ -237,10 +560,10 @@ impl ContextMenu {
     }
 
     pub fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
-        if let Some(ix) = self.selected_index {
+        if let Some(ix) = self.selection.active_index() {
             for (ix, item) in self.items.iter().enumerate().take(ix).rev() {
                 if item.is_selectable() {
-                    self.selected_index = Some(ix);
+                    self.selection.set_active_index(Some(ix));
                     cx.notify();
                     break;
                 }
@@ -259,6 +582,7 @@ impl ContextMenu {
         if let Some(ix) = self.items.iter().position(|item| {
             if let ContextMenuItem::Entry {
                 action: Some(action),
+                disabled: false,
                 ..
             } = item
             {
@@ -267,7 +591,7 @@ impl ContextMenu {
                 false
             }
         }) {
-            self.selected_index = Some(ix);
+            self.selection.set_active_index(Some(ix));
             self.delayed = true;
             cx.notify();
             let action = dispatched.boxed_clone();
@@ -276,7 +600,7 @@ impl ContextMenu {
                     .timer(Duration::from_millis(50))
                     .await;
                 this.update(&mut cx, |this, cx| {
-                    this.cancel(&menu::Cancel, cx);
+                    this.dismiss_chain(cx);
                     cx.dispatch_action(action);
                 })
             })
@@ -293,8 +617,130 @@ impl ContextMenuItem {
             ContextMenuItem::Separator => false,
             ContextMenuItem::Label { .. } => false,
             ContextMenuItem::Header(_) => false,
-            ContextMenuItem::Entry { .. } => true,
+            ContextMenuItem::Entry { disabled, .. } => !disabled,
             ContextMenuItem::CustomEntry { selectable, .. } => *selectable,
+            ContextMenuItem::Submenu { .. } => true,
+        }
+    }
+}
+
+fn label_with_icon(label: &SharedString, icon: Option<IconName>, disabled: bool) -> AnyElement {
+    let color = if disabled {
+        Color::Disabled
+    } else {
+        Color::Default
+    };
+    if let Some(icon) = icon {
+        h_flex()
+            .gap_1()
+            .child(Label::new(label.clone()).color(color))
+            .child(Icon::new(icon).color(color))
+            .into_any_element()
+    } else {
+        Label::new(label.clone()).color(color).into_any_element()
+    }
+}
+
+impl ContextMenu {
+    fn render_item(&self, ix: usize, is_active: bool, cx: &mut ViewContext<Self>) -> AnyElement {
+        match &self.items[ix] {
+            ContextMenuItem::Separator => ListSeparator.into_any_element(),
+            ContextMenuItem::Header(header) => ListSubHeader::new(header.clone())
+                .inset(true)
+                .into_any_element(),
+            ContextMenuItem::Label(label) => ListItem::new(ix)
+                .inset(true)
+                .disabled(true)
+                .child(Label::new(label.clone()))
+                .into_any_element(),
+            ContextMenuItem::Entry {
+                toggled,
+                label,
+                handler: _,
+                icon,
+                action,
+                disabled,
+            } => {
+                let menu = cx.view().downgrade();
+
+                ListItem::new(ix)
+                    .inset(true)
+                    .selected(is_active)
+                    .disabled(*disabled)
+                    .when_some(*toggled, |list_item, toggled| {
+                        list_item.start_slot(if toggled {
+                            v_flex().flex_none().child(Icon::new(IconName::Check).color(
+                                if *disabled {
+                                    Color::Disabled
+                                } else {
+                                    Color::Accent
+                                },
+                            ))
+                        } else {
+                            v_flex().flex_none().size(IconSize::default().rems())
+                        })
+                    })
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .justify_between()
+                            .child(label_with_icon(label, *icon, *disabled))
+                            .debug_selector(|| format!("MENU_ITEM-{}", label))
+                            .children(action.as_ref().and_then(|action| {
+                                self.action_context
+                                    .as_ref()
+                                    .map(|focus| KeyBinding::for_action_in(&**action, focus, cx))
+                                    .unwrap_or_else(|| KeyBinding::for_action(&**action, cx))
+                                    .map(|binding| div().ml_4().child(binding))
+                            })),
+                    )
+                    .when(!disabled, |list_item| {
+                        list_item.on_click(move |_, cx| {
+                            menu.update(cx, |menu, cx| menu.activate(ix, cx)).ok();
+                        })
+                    })
+                    .into_any_element()
+            }
+            ContextMenuItem::CustomEntry {
+                entry_render,
+                handler: _,
+                selectable,
+            } => {
+                let menu = cx.view().downgrade();
+                ListItem::new(ix)
+                    .inset(true)
+                    .selected(*selectable && is_active)
+                    .selectable(*selectable)
+                    .on_click(move |_, cx| {
+                        menu.update(cx, |menu, cx| menu.activate(ix, cx)).ok();
+                    })
+                    .child(entry_render(cx))
+                    .into_any_element()
+            }
+            ContextMenuItem::Submenu { label, icon, .. } => {
+                let menu = cx.view().downgrade();
+                let open = self.submenu.as_ref().map(|submenu| submenu.ix) == Some(ix);
+                ListItem::new(ix)
+                    .inset(true)
+                    .selected(is_active || open)
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .justify_between()
+                            .child(label_with_icon(label, *icon, false))
+                            .debug_selector(|| format!("MENU_ITEM-{}", label))
+                            .child(
+                                Icon::new(IconName::ChevronRight)
+                                    .size(IconSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .on_click(move |_, cx| {
+                        menu.update(cx, |menu, cx| menu.open_submenu(ix, true, cx))
+                            .ok();
+                    })
+                    .into_any_element()
+            }
         }
     }
 }
@@ -302,153 +748,425 @@ impl ContextMenuItem {
 impl Render for ContextMenu {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let ui_font_size = ThemeSettings::get_global(cx).ui_font_size;
+        let selectable = self
+            .items
+            .iter()
+            .map(ContextMenuItem::is_selectable)
+            .collect::<Vec<_>>();
+        let labels =
+            self.items
+                .iter()
+                .map(|item| match item {
+                    ContextMenuItem::Entry { label, .. }
+                    | ContextMenuItem::Submenu { label, .. } => Some(label.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+        let menu = cx.view().clone();
+        self.item_bounds.borrow_mut().resize(self.items.len(), None);
 
-        div().occlude().elevation_2(cx).flex().flex_row().child(
-            WithRemSize::new(ui_font_size).flex().child(
-                v_flex()
-                    .min_w(px(200.))
-                    .track_focus(&self.focus_handle)
-                    .on_mouse_down_out(cx.listener(|this, _, cx| this.cancel(&menu::Cancel, cx)))
-                    .key_context("menu")
-                    .on_action(cx.listener(ContextMenu::select_first))
-                    .on_action(cx.listener(ContextMenu::handle_select_last))
-                    .on_action(cx.listener(ContextMenu::select_next))
-                    .on_action(cx.listener(ContextMenu::select_prev))
-                    .on_action(cx.listener(ContextMenu::confirm))
-                    .on_action(cx.listener(ContextMenu::cancel))
-                    .when(!self.delayed, |mut el| {
-                        for item in self.items.iter() {
-                            if let ContextMenuItem::Entry {
-                                action: Some(action),
-                                ..
-                            } = item
-                            {
-                                el = el.on_boxed_action(
-                                    &**action,
-                                    cx.listener(ContextMenu::on_action_dispatch),
-                                );
-                            }
-                        }
-                        el
-                    })
-                    .flex_none()
-                    .child(List::new().children(self.items.iter_mut().enumerate().map(
-                        |(ix, item)| {
-                            match item {
-                                ContextMenuItem::Separator => ListSeparator.into_any_element(),
-                                ContextMenuItem::Header(header) => {
-                                    ListSubHeader::new(header.clone())
-                                        .inset(true)
-                                        .into_any_element()
+        let submenu = self.submenu.as_ref().and_then(|submenu| {
+            let item_bounds = self
+                .item_bounds
+                .borrow()
+                .get(submenu.ix)
+                .copied()
+                .flatten()?;
+            Some(anchored().beside(item_bounds).child(submenu.menu.clone()))
+        });
+
+        let content = div()
+            .occlude()
+            .elevation_overlay(cx)
+            .flex()
+            .flex_row()
+            .child(
+                WithRemSize::new(ui_font_size).flex().child(
+                    v_flex()
+                        .min_w(px(200.))
+                        .key_context("menu")
+                        .on_key_down(cx.listener(ContextMenu::handle_key_down))
+                        .on_action(cx.listener(ContextMenu::select_first))
+                        .on_action(cx.listener(ContextMenu::handle_select_last))
+                        .on_action(cx.listener(ContextMenu::select_next))
+                        .on_action(cx.listener(ContextMenu::select_prev))
+                        .on_action(cx.listener(ContextMenu::confirm))
+                        .on_action(cx.listener(ContextMenu::cancel))
+                        .when(!self.delayed, |mut el| {
+                            for item in self.items.iter() {
+                                if let ContextMenuItem::Entry {
+                                    action: Some(action),
+                                    disabled: false,
+                                    ..
+                                } = item
+                                {
+                                    el = el.on_boxed_action(
+                                        &**action,
+                                        cx.listener(ContextMenu::on_action_dispatch),
+                                    );
                                 }
-                                ContextMenuItem::Label(label) => ListItem::new(ix)
-                                    .inset(true)
-                                    .disabled(true)
-                                    .child(Label::new(label.clone()))
-                                    .into_any_element(),
-                                ContextMenuItem::Entry {
-                                    toggled,
-                                    label,
-                                    handler,
-                                    icon,
-                                    action,
-                                } => {
-                                    let handler = handler.clone();
-                                    let menu = cx.view().downgrade();
-
-                                    let label_element = if let Some(icon) = icon {
-                                        h_flex()
-                                            .gap_1()
-                                            .child(Label::new(label.clone()))
-                                            .child(Icon::new(*icon))
-                                            .into_any_element()
-                                    } else {
-                                        Label::new(label.clone()).into_any_element()
-                                    };
-
-                                    ListItem::new(ix)
-                                        .inset(true)
-                                        .selected(Some(ix) == self.selected_index)
-                                        .when_some(*toggled, |list_item, toggled| {
-                                            list_item.start_slot(if toggled {
-                                                v_flex().flex_none().child(
-                                                    Icon::new(IconName::Check).color(Color::Accent),
+                            }
+                            el
+                        })
+                        .flex_none()
+                        .child(
+                            roving_focus("context-menu-items", self.items.len(), {
+                                let menu = menu.clone();
+                                move |ix, is_active, cx| {
+                                    menu.update(cx, |menu, cx| {
+                                        let item = menu.render_item(ix, is_active, cx);
+                                        let opens_submenu = matches!(
+                                            menu.items[ix],
+                                            ContextMenuItem::Submenu { .. }
+                                        );
+                                        let hover_changes_submenu =
+                                            menu.item_hover_changes_submenu(ix);
+                                        let item_bounds = menu.item_bounds.clone();
+                                        let view = cx.view().downgrade();
+                                        div()
+                                            .id(ix)
+                                            .relative()
+                                            .when(hover_changes_submenu, |this| {
+                                                this.on_hover_delayed(
+                                                    SUBMENU_HOVER_DELAY,
+                                                    move |cx| {
+                                                        view.update(cx, |menu, cx| {
+                                                            menu.hover_item(ix, cx)
+                                                        })
+                                                        .ok();
+                                                    },
                                                 )
-                                            } else {
-                                                v_flex()
-                                                    .flex_none()
-                                                    .size(IconSize::default().rems())
                                             })
-                                        })
-                                        .child(
-                                            h_flex()
-                                                .w_full()
-                                                .justify_between()
-                                                .child(label_element)
-                                                .debug_selector(|| format!("MENU_ITEM-{}", label))
-                                                .children(action.as_ref().and_then(|action| {
-                                                    self.action_context
-                                                        .as_ref()
-                                                        .map(|focus| {
-                                                            KeyBinding::for_action_in(
-                                                                &**action, focus, cx,
-                                                            )
-                                                        })
-                                                        .unwrap_or_else(|| {
-                                                            KeyBinding::for_action(&**action, cx)
-                                                        })
-                                                        .map(|binding| div().ml_4().child(binding))
-                                                })),
-                                        )
-                                        .on_click({
-                                            let context = self.action_context.clone();
-                                            move |_, cx| {
-                                                handler(context.as_ref(), cx);
-                                                menu.update(cx, |menu, cx| {
-                                                    menu.clicked = true;
-                                                    cx.emit(DismissEvent);
-                                                })
-                                                .ok();
-                                            }
-                                        })
-                                        .into_any_element()
+                                            .child(item)
+                                            .when(opens_submenu, |this| {
+                                                this.child(
+                                                    canvas(
+                                                        move |bounds, _| {
+                                                            item_bounds.borrow_mut()[ix] =
+                                                                Some(bounds)
+                                                        },
+                                                        |_, _, _| {},
+                                                    )
+                                                    .absolute()
+                                                    .size_full(),
+                                                )
+                                            })
+                                    })
                                 }
-                                ContextMenuItem::CustomEntry {
-                                    entry_render,
-                                    handler,
-                                    selectable,
-                                } => {
-                                    let handler = handler.clone();
-                                    let menu = cx.view().downgrade();
-                                    ListItem::new(ix)
-                                        .inset(true)
-                                        .selected(if *selectable {
-                                            Some(ix) == self.selected_index
-                                        } else {
-                                            false
-                                        })
-                                        .selectable(*selectable)
-                                        .on_click({
-                                            let context = self.action_context.clone();
-                                            let selectable = *selectable;
-                                            move |_, cx| {
-                                                if selectable {
-                                                    handler(context.as_ref(), cx);
-                                                    menu.update(cx, |menu, cx| {
-                                                        menu.clicked = true;
-                                                        cx.emit(DismissEvent);
-                                                    })
-                                                    .ok();
-                                                }
-                                            }
+                            })
+                            .track_active(&self.selection)
+                            .selectable(move |ix| selectable[ix])
+                            .item_label(move |ix| labels[ix].clone())
+                            .on_activate(move |ix, cx| {
+                                menu.update(cx, |menu, cx| {
+                                    menu.selection.set_active_index(Some(ix));
+                                    menu.confirm(&menu::Confirm, cx);
+                                })
+                            })
+                            .w_full()
+                            .py_1()
+                            .track_focus(&self.focus_handle),
+                        ),
+                ),
+            )
+            .children(submenu);
+
+        let menu = cx.view().downgrade();
+        let dismissable = dismissable(
+            "context-menu",
+            move |cx| {
+                menu.update(cx, |menu, cx| menu.cancel(&menu::Cancel, cx))
+                    .ok();
+            },
+            content,
+        );
+        // A click away from a submenu and the menus it was opened from closes all of them.
+        if self.parent.is_some() {
+            dismissable.dismiss_parents_on_click()
+        } else {
+            dismissable
+        }
+    }
+}
+
+/// Draws the menu opened in this window with [`ContextMenu::open_at`], if there is one, above
+/// the rest of the window. Place one at the root of the window.
+pub fn context_menu_host() -> ContextMenuHost {
+    ContextMenuHost
+}
+
+/// Shows the menu opened with [`ContextMenu::open_at`], see [`context_menu_host`].
+#[derive(IntoElement)]
+pub struct ContextMenuHost;
+
+impl RenderOnce for ContextMenuHost {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let Some((menu, position)) = cx
+            .try_window_global::<OpenContextMenus>()
+            .and_then(|open_menus| open_menus.0.as_ref())
+            .map(|open| (open.menu.clone(), open.position))
+        else {
+            return Empty.into_any_element();
+        };
+        deferred(anchored().position(position).child(menu))
+            .with_priority(1)
+            .into_any_element()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, Modifiers, Render, TestAppContext, VisualTestContext};
+
+    use super::*;
+
+    struct Root;
+
+    impl Render for Root {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_full().child(context_menu_host())
+        }
+    }
+
+    fn window(cx: &mut TestAppContext) -> &mut VisualTestContext {
+        crate::test::init(cx);
+        let (_, cx) = cx.add_window_view(|_| Root);
+        cx
+    }
+
+    fn open_menu(cx: &WindowContext) -> Option<View<ContextMenu>> {
+        cx.try_window_global::<OpenContextMenus>()
+            .and_then(|open_menus| open_menus.0.as_ref())
+            .map(|open| open.menu.clone())
+    }
+
+    #[gpui::test]
+    fn test_keyboard_navigation_skips_disabled_entries(cx: &mut TestAppContext) {
+        let cx = window(cx);
+        let menu = cx.update(|cx| {
+            ContextMenu::open_at(point(px(10.), px(10.)), cx, |menu, _| {
+                menu.entry("One", None, |_| {})
+                    .item(ContextMenuEntry::new("Two").disabled(true))
+                    .separator()
+                    .entry("Three", None, |_| {})
+            })
+        });
+        cx.run_until_parked();
+
+        menu.update(cx, |menu, cx| {
+            menu.select_first(&SelectFirst, cx);
+            menu.select_next(&SelectNext, cx);
+            assert_eq!(menu.selection.active_index(), Some(3));
+            menu.select_prev(&SelectPrev, cx);
+            assert_eq!(menu.selection.active_index(), Some(0));
+        });
+    }
+
+    #[gpui::test]
+    fn test_handlers_run_after_the_menu_is_dismissed(cx: &mut TestAppContext) {
+        let cx = window(cx);
+        let menu_was_open = Rc::new(RefCell::new(Vec::new()));
+        let menu = cx.update(|cx| {
+            let menu_was_open = menu_was_open.clone();
+            ContextMenu::open_at(point(px(10.), px(10.)), cx, move |menu, _| {
+                menu.submenu("More", move |menu, _| {
+                    let menu_was_open = menu_was_open.clone();
+                    menu.entry("Nested", None, move |cx| {
+                        menu_was_open.borrow_mut().push(open_menu(cx).is_some())
+                    })
+                })
+            })
+        });
+        cx.run_until_parked();
+
+        menu.update(cx, |menu, cx| menu.activate(0, cx));
+        cx.run_until_parked();
+        let submenu = cx.update(|cx| menu.read(cx).submenu.as_ref().unwrap().menu.clone());
+        assert!(cx.update(|cx| submenu.focus_handle(cx).is_focused(cx)));
+
+        submenu.update(cx, |submenu, cx| submenu.activate(0, cx));
+        cx.run_until_parked();
+        assert_eq!(*menu_was_open.borrow(), [false]);
+        assert!(cx.update(|cx| open_menu(cx)).is_none());
+    }
+
+    #[gpui::test]
+    fn test_opening_a_menu_closes_the_open_one(cx: &mut TestAppContext) {
+        let cx = window(cx);
+        cx.update(|cx| ContextMenu::open_at(point(px(10.), px(10.)), cx, |menu, _| menu));
+        cx.run_until_parked();
+        let second =
+            cx.update(|cx| ContextMenu::open_at(point(px(20.), px(20.)), cx, |menu, _| menu));
+        cx.run_until_parked();
+
+        assert!(cx.update(|cx| open_menu(cx)) == Some(second));
+    }
+
+    /// Opens a menu whose second entry opens a submenu, whose second entry opens another one.
+    fn open_menu_with_submenus(
+        position: Point<Pixels>,
+        cx: &mut VisualTestContext,
+    ) -> View<ContextMenu> {
+        let menu = cx.update(|cx| {
+            ContextMenu::open_at(position, cx, |menu, _| {
+                menu.entry("One", None, |_| {}).submenu("More", |menu, _| {
+                    menu.entry("Nested", None, |_| {})
+                        .submenu("Deeper", |menu, _| menu)
+                })
+            })
+        });
+        cx.run_until_parked();
+        menu
+    }
+
+    fn open_submenu_ix(menu: &View<ContextMenu>, cx: &mut VisualTestContext) -> Option<usize> {
+        cx.update(|cx| menu.read(cx).submenu.as_ref().map(|submenu| submenu.ix))
+    }
+
+    fn item_bounds(
+        menu: &View<ContextMenu>,
+        ix: usize,
+        cx: &mut VisualTestContext,
+    ) -> Bounds<Pixels> {
+        cx.update(|cx| menu.read(cx).item_bounds.borrow()[ix].unwrap())
+    }
+
+    #[gpui::test]
+    fn test_resting_on_an_entry_opens_its_submenu(cx: &mut TestAppContext) {
+        let cx = window(cx);
+        let menu = open_menu_with_submenus(point(px(10.), px(10.)), cx);
+
+        let more = item_bounds(&menu, 1, cx).center();
+        cx.simulate_mouse_move(more, None, Modifiers::none());
+        cx.executor().advance_clock(SUBMENU_HOVER_DELAY / 2);
+        cx.run_until_parked();
+        assert_eq!(open_submenu_ix(&menu, cx), None);
+
+        cx.executor().advance_clock(SUBMENU_HOVER_DELAY);
+        cx.run_until_parked();
+        assert_eq!(open_submenu_ix(&menu, cx), Some(1));
+
+        // Resting on another entry closes the submenu again.
+        let one = point(more.x, more.y - item_bounds(&menu, 1, cx).size.height);
+        cx.simulate_mouse_move(one, None, Modifiers::none());
+        cx.executor().advance_clock(SUBMENU_HOVER_DELAY);
+        cx.run_until_parked();
+        assert_eq!(open_submenu_ix(&menu, cx), None);
+    }
+
+    #[gpui::test]
+    fn test_right_arrow_opens_the_active_submenu(cx: &mut TestAppContext) {
+        let cx = window(cx);
+        let menu = open_menu_with_submenus(point(px(10.), px(10.)), cx);
+
+        menu.update(cx, |menu, cx| {
+            menu.select_first(&SelectFirst, cx);
+            menu.select_next(&SelectNext, cx);
+        });
+        cx.simulate_keystrokes("right");
+        assert_eq!(open_submenu_ix(&menu, cx), Some(1));
+        let submenu = cx.update(|cx| menu.read(cx).submenu.as_ref().unwrap().menu.clone());
+        assert!(cx.update(|cx| submenu.focus_handle(cx).is_focused(cx)));
+
+        // Left goes back to the parent menu.
+        cx.simulate_keystrokes("left");
+        assert_eq!(open_submenu_ix(&menu, cx), None);
+        assert!(cx.update(|cx| menu.focus_handle(cx).is_focused(cx)));
+    }
+
+    #[gpui::test]
+    fn test_submenus_open_on_the_left_near_the_window_edge(cx: &mut TestAppContext) {
+        let cx = window(cx);
+        let menu = open_menu_with_submenus(point(px(10.), px(10.)), cx);
+        menu.update(cx, |menu, cx| menu.open_submenu(1, false, cx));
+        cx.run_until_parked();
+        let more = item_bounds(&menu, 1, cx);
+        let deeper = cx.debug_bounds("MENU_ITEM-Deeper").unwrap();
+        assert!(deeper.left() >= more.right());
+
+        // There's no room for the submenu to the right of a menu at the right of the window.
+        let viewport_width = cx.update(|cx| cx.viewport_size().width);
+        let menu = open_menu_with_submenus(point(viewport_width - px(250.), px(10.)), cx);
+        menu.update(cx, |menu, cx| menu.open_submenu(1, false, cx));
+        cx.run_until_parked();
+        let more = item_bounds(&menu, 1, cx);
+        let deeper = cx.debug_bounds("MENU_ITEM-Deeper").unwrap();
+        assert!(more.right() <= viewport_width);
+        assert!(deeper.right() <= more.left());
+    }
+
+    #[gpui::test]
+    fn test_clicks_inside_a_submenu_keep_its_parent_open(cx: &mut TestAppContext) {
+        let cx = window(cx);
+        let menu = open_menu_with_submenus(point(px(10.), px(10.)), cx);
+        menu.update(cx, |menu, cx| menu.open_submenu(1, true, cx));
+        cx.run_until_parked();
+
+        // Opening a submenu of the submenu doesn't activate anything that closes the menus.
+        let deeper = cx.debug_bounds("MENU_ITEM-Deeper").unwrap();
+        cx.simulate_click(deeper.center(), Modifiers::none());
+        cx.run_until_parked();
+        assert!(cx.update(|cx| open_menu(cx)) == Some(menu.clone()));
+        assert_eq!(open_submenu_ix(&menu, cx), Some(1));
+        let submenu = cx.update(|cx| menu.read(cx).submenu.as_ref().unwrap().menu.clone());
+        assert_eq!(open_submenu_ix(&submenu, cx), Some(1));
+    }
+
+    /// A button that picks a color from a menu, like a select.
+    struct ColorSelect {
+        selected: Option<&'static str>,
+    }
+
+    impl Render for ColorSelect {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let this = cx.view().downgrade();
+            div()
+                .size_full()
+                .child(
+                    Button::new("select", self.selected.unwrap_or("None")).on_click(
+                        move |event, cx| {
+                            let this = this.clone();
+                            ContextMenu::open_at(event.up.position, cx, move |menu, _| {
+                                ["Red", "Green", "Blue"]
+                                    .into_iter()
+                                    .fold(menu, |menu, color| {
+                                        let this = this.clone();
+                                        menu.entry(color, None, move |cx| {
+                                            this.update(cx, |this, cx| {
+                                                this.selected = Some(color);
+                                                cx.notify();
+                                            })
+                                            .ok();
                                         })
-                                        .child(entry_render(cx))
-                                        .into_any_element()
-                                }
-                            }
+                                    })
+                            });
                         },
-                    ))),
-            ),
-        )
+                    ),
+                )
+                .child(context_menu_host())
+        }
+    }
+
+    #[gpui::test]
+    fn test_driving_a_select_with_the_keyboard(cx: &mut TestAppContext) {
+        crate::test::init(cx);
+        cx.update(|cx| {
+            cx.bind_keys([
+                gpui::KeyBinding::new("down", SelectNext, Some("menu")),
+                gpui::KeyBinding::new("enter", menu::Confirm, Some("menu")),
+            ]);
+        });
+        let (select, cx) = cx.add_window_view(|_| ColorSelect { selected: None });
+        cx.run_until_parked();
+
+        cx.driver().click("select");
+        assert!(cx.update(|cx| open_menu(cx)).is_some());
+        cx.driver().press("down down enter");
+
+        assert!(cx.update(|cx| open_menu(cx)).is_none());
+        assert_eq!(
+            select.update(cx, |select, _| select.selected),
+            Some("Green")
+        );
     }
 }
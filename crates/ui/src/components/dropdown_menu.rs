@@ -1,15 +1,18 @@
-use crate::prelude::*;
-
-/// !!don't use this yet – it's not functional!!
-///
-/// pub crate until this is functional
-///
-/// just a placeholder for now for filling out the settings menu stories.
-#[derive(Debug, Clone, IntoElement)]
-pub(crate) struct DropdownMenu {
+use std::rc::Rc;
+
+use gpui::{ClickEvent, CursorStyle};
+
+use crate::{prelude::*, ContextMenu, PopoverMenu};
+
+/// A select: a button showing the current option, which opens a menu of the options to pick
+/// from. The menu opens with the current option active, and is driven with the keyboard like
+/// any other [`ContextMenu`].
+#[derive(IntoElement)]
+pub struct DropdownMenu {
     pub id: ElementId,
     current_item: Option<SharedString>,
-    // items: Vec<SharedString>,
+    items: Vec<SharedString>,
+    on_select: Option<Rc<dyn Fn(usize, &mut WindowContext) + 'static>>,
     full_width: bool,
     disabled: bool,
 }
@@ -19,7 +22,8 @@ impl DropdownMenu {
         Self {
             id: id.into(),
             current_item: None,
-            // items: Vec::new(),
+            items: Vec::new(),
+            on_select: None,
             full_width: false,
             disabled: false,
         }
@@ -30,6 +34,18 @@ impl DropdownMenu {
         self
     }
 
+    /// Sets the options to pick from.
+    pub fn items(mut self, items: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        self.items = items.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Called with the index of the option that was picked.
+    pub fn on_select(mut self, handler: impl Fn(usize, &mut WindowContext) + 'static) -> Self {
+        self.on_select = Some(Rc::new(handler));
+        self
+    }
+
     pub fn full_width(mut self, full_width: bool) -> Self {
         self.full_width = full_width;
         self
@@ -42,6 +58,84 @@ impl DropdownMenu {
 }
 
 impl RenderOnce for DropdownMenu {
+    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+        let trigger = DropdownMenuTrigger {
+            id: self.id.clone(),
+            label: self.current_item.clone().unwrap_or_default(),
+            full_width: self.full_width,
+            disabled: self.disabled,
+            selected: false,
+            cursor_style: CursorStyle::PointingHand,
+            on_click: None,
+        };
+        if self.disabled || self.items.is_empty() {
+            return trigger.into_any_element();
+        }
+
+        let current_ix = self
+            .current_item
+            .as_ref()
+            .and_then(|current| self.items.iter().position(|item| item == current));
+        let items = self.items;
+        let on_select = self.on_select;
+        PopoverMenu::new(self.id)
+            .menu(move |cx| {
+                let items = items.clone();
+                let on_select = on_select.clone();
+                Some(ContextMenu::build(cx, move |menu, _| {
+                    let menu = items.iter().enumerate().fold(menu, |menu, (ix, item)| {
+                        let on_select = on_select.clone();
+                        menu.toggleable_entry(
+                            item.clone(),
+                            Some(ix) == current_ix,
+                            None,
+                            move |cx| {
+                                if let Some(on_select) = on_select.as_ref() {
+                                    on_select(ix, cx);
+                                }
+                            },
+                        )
+                    });
+                    menu.when_some(current_ix, |menu, ix| menu.active_item(ix))
+                }))
+            })
+            .trigger(trigger)
+            .into_any_element()
+    }
+}
+
+/// The button which opens a [`DropdownMenu`], showing its current option.
+#[derive(IntoElement)]
+struct DropdownMenuTrigger {
+    id: ElementId,
+    label: SharedString,
+    full_width: bool,
+    disabled: bool,
+    selected: bool,
+    cursor_style: CursorStyle,
+    on_click: Option<Box<dyn Fn(&ClickEvent, &mut WindowContext) + 'static>>,
+}
+
+impl Clickable for DropdownMenuTrigger {
+    fn on_click(mut self, handler: impl Fn(&ClickEvent, &mut WindowContext) + 'static) -> Self {
+        self.on_click = Some(Box::new(handler));
+        self
+    }
+
+    fn cursor_style(mut self, cursor_style: CursorStyle) -> Self {
+        self.cursor_style = cursor_style;
+        self
+    }
+}
+
+impl Selectable for DropdownMenuTrigger {
+    fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+}
+
+impl RenderOnce for DropdownMenuTrigger {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         let disabled = self.disabled;
 
@@ -49,7 +143,11 @@ impl RenderOnce for DropdownMenu {
             .id(self.id)
             .justify_between()
             .rounded_md()
-            .bg(cx.theme().colors().editor_background)
+            .bg(if self.selected {
+                cx.theme().colors().element_selected
+            } else {
+                cx.theme().colors().editor_background
+            })
             .pl_2()
             .pr_1p5()
             .py_0p5()
@@ -63,15 +161,13 @@ impl RenderOnce for DropdownMenu {
             .when_else(
                 disabled,
                 |disabled| disabled.cursor_not_allowed(),
-                |enabled| enabled.cursor_pointer(),
-            )
-            .child(
-                Label::new(self.current_item.unwrap_or("".into())).color(if disabled {
-                    Color::Disabled
-                } else {
-                    Color::Default
-                }),
+                |enabled| enabled.cursor(self.cursor_style),
             )
+            .child(Label::new(self.label).color(if disabled {
+                Color::Disabled
+            } else {
+                Color::Default
+            }))
             .child(
                 Icon::new(IconName::ChevronUpDown)
                     .size(IconSize::XSmall)
@@ -81,5 +177,78 @@ impl RenderOnce for DropdownMenu {
                         Color::Muted
                     }),
             )
+            .when_some(self.on_click.filter(|_| !disabled), |this, on_click| {
+                this.on_click(on_click)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{Render, TestAppContext, View, ViewContext};
+
+    use super::*;
+
+    struct ColorSelect {
+        selected: usize,
+    }
+
+    const COLORS: [&str; 3] = ["Red", "Green", "Blue"];
+
+    impl Render for ColorSelect {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let this = cx.view().downgrade();
+            div().size_full().child(
+                DropdownMenu::new("color", cx)
+                    .current_item(Some(COLORS[self.selected].into()))
+                    .items(COLORS)
+                    .on_select(move |ix, cx| {
+                        this.update(cx, |this, cx| {
+                            this.selected = ix;
+                            cx.notify();
+                        })
+                        .ok();
+                    }),
+            )
+        }
+    }
+
+    fn select(cx: &mut TestAppContext) -> (View<ColorSelect>, &mut gpui::VisualTestContext) {
+        crate::test::init(cx);
+        cx.update(|cx| {
+            cx.bind_keys([
+                gpui::KeyBinding::new("down", menu::SelectNext, Some("menu")),
+                gpui::KeyBinding::new("enter", menu::Confirm, Some("menu")),
+            ]);
+        });
+        cx.add_window_view(|_| ColorSelect { selected: 1 })
+    }
+
+    #[gpui::test]
+    fn test_picking_an_option_with_the_keyboard(cx: &mut TestAppContext) {
+        let (select, cx) = select(cx);
+        cx.run_until_parked();
+
+        // The menu opens on the current option, so the next one is a single step away.
+        cx.driver().click("color").press("down enter");
+        assert_eq!(select.update(cx, |select, _| select.selected), 2);
+
+        // Picking an option closes the menu, which the keys no longer reach.
+        cx.driver().press("down enter");
+        assert_eq!(select.update(cx, |select, _| select.selected), 2);
+    }
+
+    #[gpui::test]
+    fn test_clicking_away_or_pressing_escape_closes_the_menu(cx: &mut TestAppContext) {
+        let (select, cx) = select(cx);
+        cx.run_until_parked();
+
+        cx.driver().click("color").press("escape down enter");
+        assert_eq!(select.update(cx, |select, _| select.selected), 1);
+
+        cx.driver().click("color");
+        cx.simulate_click(gpui::point(px(500.), px(500.)), gpui::Modifiers::none());
+        cx.driver().press("down enter");
+        assert_eq!(select.update(cx, |select, _| select.selected), 1);
     }
 }
@@ -0,0 +1,194 @@
+use gpui::{provide_context, AnyElement, FocusHandle};
+
+use crate::prelude::*;
+
+/// Provided by a [`FormField`] to the input it wraps, see [`FormField::is_invalid`].
+struct FieldValidation {
+    invalid: bool,
+}
+
+/// # FormField
+///
+/// Lays out an input with its label above it and an optional description and error message
+/// below it.
+///
+/// While the field has an error, the input is drawn as invalid: inputs that support it,
+/// such as [`Checkbox`](crate::Checkbox), look this up with [`FormField::is_invalid`] and
+/// use the theme's error color for their border.
+#[derive(IntoElement)]
+pub struct FormField {
+    id: ElementId,
+    label: SharedString,
+    input: AnyElement,
+    description: Option<SharedString>,
+    error: Option<SharedString>,
+    reserve_error_space: bool,
+    focus_handle: Option<FocusHandle>,
+}
+
+impl FormField {
+    pub fn new(
+        id: impl Into<ElementId>,
+        label: impl Into<SharedString>,
+        input: impl IntoElement,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            input: input.into_any_element(),
+            description: None,
+            error: None,
+            reserve_error_space: false,
+            focus_handle: None,
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<SharedString>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the error shown under the input. The field is invalid while it has one.
+    pub fn error(mut self, error: Option<impl Into<SharedString>>) -> Self {
+        self.error = error.map(Into::into);
+        self
+    }
+
+    /// Keeps the space for an error message even when there isn't one, so that fields laid out
+    /// after this one don't move when errors come and go.
+    pub fn reserve_error_space(mut self, reserve_error_space: bool) -> Self {
+        self.reserve_error_space = reserve_error_space;
+        self
+    }
+
+    /// Focuses the input with the given handle when the label is clicked.
+    pub fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
+
+    /// Whether the element being drawn is the input of a [`FormField`] that has an error.
+    pub fn is_invalid(cx: &WindowContext) -> bool {
+        cx.context::<FieldValidation>()
+            .map_or(false, |validation| validation.invalid)
+    }
+}
+
+impl RenderOnce for FormField {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let invalid = self.error.is_some();
+        let show_error_row = invalid || self.reserve_error_space;
+
+        v_flex()
+            .id(self.id)
+            .gap(Spacing::Small.rems(cx))
+            .child(div().id("label").child(Label::new(self.label)).when_some(
+                self.focus_handle,
+                |this, focus_handle| {
+                    this.cursor_pointer()
+                        .on_click(move |_, cx| focus_handle.focus(cx))
+                },
+            ))
+            .child(provide_context(FieldValidation { invalid }, self.input))
+            .when_some(self.description, |this, description| {
+                this.child(
+                    Label::new(description)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .when(show_error_row, |this| {
+                // Without an error, the row holds a blank, hidden label of the same height.
+                this.child(
+                    div().when(!invalid, |this| this.invisible()).child(
+                        Label::new(self.error.unwrap_or_else(|| " ".into()))
+                            .size(LabelSize::Small)
+                            .color(Color::Error),
+                    ),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use gpui::{px, Render, TestAppContext, View, VisualTestContext};
+
+    use super::*;
+
+    /// An input that records whether it was drawn as invalid.
+    #[derive(IntoElement)]
+    struct Probe(Rc<RefCell<Vec<bool>>>);
+
+    impl RenderOnce for Probe {
+        fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+            self.0.borrow_mut().push(FormField::is_invalid(cx));
+            div().h(px(20.))
+        }
+    }
+
+    struct Form {
+        error: Option<SharedString>,
+        reserve_error_space: bool,
+        seen: Rc<RefCell<Vec<bool>>>,
+    }
+
+    impl Render for Form {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            v_flex()
+                .child(
+                    FormField::new("name", "Name", Probe(self.seen.clone()))
+                        .error(self.error.clone())
+                        .reserve_error_space(self.reserve_error_space),
+                )
+                .child(div().h(px(20.)).debug_selector(|| "next".into()))
+        }
+    }
+
+    fn init(
+        cx: &mut TestAppContext,
+        reserve_error_space: bool,
+    ) -> (View<Form>, &mut VisualTestContext) {
+        crate::test::init(cx);
+        cx.add_window_view(|_| Form {
+            error: None,
+            reserve_error_space,
+            seen: Rc::default(),
+        })
+    }
+
+    fn set_error(view: &View<Form>, error: Option<&'static str>, cx: &mut VisualTestContext) {
+        view.update(cx, |view, cx| {
+            view.error = error.map(Into::into);
+            cx.notify();
+        });
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn test_toggling_the_error_marks_the_input_invalid(cx: &mut TestAppContext) {
+        let (view, cx) = init(cx, false);
+        cx.run_until_parked();
+        set_error(&view, Some("Required"), cx);
+        set_error(&view, None, cx);
+
+        let mut seen = view.update(cx, |view, _| view.seen.borrow().clone());
+        seen.dedup();
+        assert_eq!(seen, [false, true, false]);
+    }
+
+    #[gpui::test]
+    fn test_reserved_error_space_keeps_later_fields_in_place(cx: &mut TestAppContext) {
+        let (view, cx) = init(cx, true);
+        cx.run_until_parked();
+        let without_error = cx.debug_bounds("next").unwrap();
+        set_error(&view, Some("Required"), cx);
+        assert_eq!(cx.debug_bounds("next").unwrap(), without_error);
+
+        view.update(cx, |view, _| view.reserve_error_space = false);
+        set_error(&view, None, cx);
+        assert!(cx.debug_bounds("next").unwrap().origin.y < without_error.origin.y);
+    }
+}
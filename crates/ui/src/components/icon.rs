@@ -1,4 +1,8 @@
-use gpui::{svg, AnimationElement, Hsla, IntoElement, Rems, Transformation};
+use std::collections::HashMap;
+
+use gpui::{
+    size, svg, AnimationElement, Global, Hsla, IntoElement, Rems, TextDirection, Transformation,
+};
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
@@ -53,7 +57,9 @@ pub enum IconDecoration {
     X,
 }
 
-#[derive(Default, PartialEq, Copy, Clone)]
+/// The standard sizes of an [`Icon`]. They scale with the UI, and are rounded to whole device
+/// pixels when they're drawn so that icons stay sharp at any scale.
+#[derive(Debug, Default, PartialEq, Copy, Clone, EnumIter)]
 pub enum IconSize {
     /// 10px
     Indicator,
@@ -64,6 +70,8 @@ pub enum IconSize {
     #[default]
     /// 16px
     Medium,
+    /// 24px
+    Large,
 }
 
 impl IconSize {
@@ -73,10 +81,17 @@ impl IconSize {
             IconSize::XSmall => rems_from_px(12.),
             IconSize::Small => rems_from_px(14.),
             IconSize::Medium => rems_from_px(16.),
+            IconSize::Large => rems_from_px(24.),
         }
     }
 }
 
+/// Converts an icon size to pixels, rounded to a whole number of device pixels.
+fn device_aligned_size(size: Rems, cx: &WindowContext) -> Pixels {
+    let scale_factor = cx.scale_factor();
+    px((size.to_pixels(cx.rem_size()).0 * scale_factor).round() / scale_factor)
+}
+
 #[derive(Debug, PartialEq, Copy, Clone, EnumIter, Serialize, Deserialize)]
 pub enum IconName {
     Ai,
@@ -102,6 +117,7 @@ pub enum IconName {
     BookPlus,
     CaseSensitive,
     Check,
+    CheckCircle,
     ChevronDown,
     ChevronLeft,
     ChevronRight,
@@ -150,6 +166,7 @@ pub enum IconName {
     HistoryRerun,
     Indicator,
     IndicatorX,
+    Info,
     InlayHint,
     Library,
     LineHeight,
@@ -221,6 +238,22 @@ pub enum IconName {
 }
 
 impl IconName {
+    /// Whether the icon points in a direction, and so should be mirrored in right-to-left
+    /// layouts, see [`Icon::flip_for_rtl`].
+    pub fn is_directional(self) -> bool {
+        matches!(
+            self,
+            IconName::ArrowLeft
+                | IconName::ArrowRight
+                | IconName::ArrowUpRight
+                | IconName::ChevronLeft
+                | IconName::ChevronRight
+                | IconName::ReplyArrowRight
+                | IconName::TriangleRight
+        )
+    }
+
+    /// The asset path of the icon's SVG.
     pub fn path(self) -> &'static str {
         match self {
             IconName::Ai => "icons/ai.svg",
@@ -246,6 +279,7 @@ impl IconName {
             IconName::BookPlus => "icons/book_plus.svg",
             IconName::CaseSensitive => "icons/case_insensitive.svg",
             IconName::Check => "icons/check.svg",
+            IconName::CheckCircle => "icons/check_circle.svg",
             IconName::ChevronDown => "icons/chevron_down.svg",
             IconName::ChevronLeft => "icons/chevron_left.svg",
             IconName::ChevronRight => "icons/chevron_right.svg",
@@ -294,6 +328,7 @@ impl IconName {
             IconName::HistoryRerun => "icons/history_rerun.svg",
             IconName::Indicator => "icons/indicator.svg",
             IconName::IndicatorX => "icons/indicator_x.svg",
+            IconName::Info => "icons/info.svg",
             IconName::InlayHint => "icons/inlay_hint.svg",
             IconName::Library => "icons/library.svg",
             IconName::LineHeight => "icons/line_height.svg",
@@ -366,35 +401,53 @@ impl IconName {
     }
 }
 
+/// An icon from the app's assets, usually one of the [`IconName`]s.
+///
+/// Icons take the color of the text around them unless given one with [`Icon::color`].
+///
+/// # Examples
+///
+/// ```
+/// use ui::{prelude::*, Icon, IconName, IconSize};
+///
+/// Icon::new(IconName::Check).size(IconSize::Small).color(Color::Success);
+/// ```
 #[derive(IntoElement)]
 pub struct Icon {
+    name: Option<IconName>,
     path: SharedString,
-    color: Color,
+    color: Option<Color>,
     size: Rems,
     transformation: Transformation,
+    flip_for_rtl: bool,
 }
 
 impl Icon {
     pub fn new(icon: IconName) -> Self {
         Self {
+            name: Some(icon),
             path: icon.path().into(),
-            color: Color::default(),
+            color: None,
             size: IconSize::default().rems(),
             transformation: Transformation::default(),
+            flip_for_rtl: false,
         }
     }
 
     pub fn from_path(path: impl Into<SharedString>) -> Self {
         Self {
+            name: None,
             path: path.into(),
-            color: Color::default(),
+            color: None,
             size: IconSize::default().rems(),
             transformation: Transformation::default(),
+            flip_for_rtl: false,
         }
     }
 
+    /// Sets the color of the icon. Defaults to the color of the surrounding text.
     pub fn color(mut self, color: Color) -> Self {
-        self.color = color;
+        self.color = Some(color);
         self
     }
 
@@ -415,16 +468,72 @@ impl Icon {
         self.transformation = transformation;
         self
     }
+
+    /// Mirrors the icon horizontally in right-to-left text, see [`TextDirection`], if it's one
+    /// of the [directional](IconName::is_directional) icons. Mirroring replaces any scaling set
+    /// with [`Icon::transform`].
+    pub fn flip_for_rtl(mut self) -> Self {
+        self.flip_for_rtl = self.name.map_or(false, IconName::is_directional);
+        self
+    }
+}
+
+/// The icon paths that have been looked up in the app's assets, and whether they were found.
+#[derive(Default)]
+struct IconAssets(HashMap<SharedString, bool>);
+
+impl Global for IconAssets {}
+
+/// Whether the asset at the path exists. Each path is only looked up once, and logged the first
+/// time it's found to be missing. Without any icons to look in, such as with the empty `()`
+/// asset source, every icon is taken to exist.
+fn icon_asset_exists(path: &SharedString, cx: &mut WindowContext) -> bool {
+    if let Some(exists) = cx.default_global::<IconAssets>().0.get(path) {
+        return *exists;
+    }
+    let assets = cx.asset_source();
+    let exists = matches!(assets.load(path), Ok(Some(_)))
+        || assets.list("icons").map_or(true, |icons| icons.is_empty());
+    if !exists {
+        log::error!("icon {path:?} is missing from the assets");
+    }
+    cx.global_mut::<IconAssets>().0.insert(path.clone(), exists);
+    exists
 }
 
 impl RenderOnce for Icon {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let icon_size = device_aligned_size(self.size, cx);
+
+        if !icon_asset_exists(&self.path, cx) {
+            // Make missing icons easy to spot during development, without showing users a
+            // placeholder.
+            return div()
+                .flex_none()
+                .size(icon_size)
+                .when(cfg!(debug_assertions), |this| {
+                    let error = cx.theme().status().error;
+                    this.border_1().border_color(error).bg(error.opacity(0.3))
+                })
+                .into_any_element();
+        }
+
+        let mirrored = self.flip_for_rtl && TextDirection::current(cx) == TextDirection::Rtl;
+        let transformation = if mirrored {
+            self.transformation.with_scaling(size(-1., 1.))
+        } else {
+            self.transformation
+        };
+        let color = self
+            .color
+            .map_or_else(|| cx.text_style().color, |color| color.color(cx));
         svg()
-            .with_transformation(self.transformation)
-            .size(self.size)
+            .with_transformation(transformation)
+            .size(icon_size)
             .flex_none()
             .path(self.path)
-            .text_color(self.color.color(cx))
+            .text_color(color)
+            .into_any_element()
     }
 }
 
@@ -561,3 +670,90 @@ impl RenderOnce for IconWithIndicator {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, io, path::Path};
+
+    use gpui::{AssetSource, Render, TestAppContext, ViewContext};
+
+    use super::*;
+
+    /// Loads the assets from the repository's `assets` directory.
+    struct RepositoryAssets;
+
+    impl AssetSource for RepositoryAssets {
+        fn load(&self, path: &str) -> gpui::Result<Option<Cow<'static, [u8]>>> {
+            match std::fs::read(Path::new("../../assets").join(path)) {
+                Ok(data) => Ok(Some(data.into())),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(error) => Err(error.into()),
+            }
+        }
+
+        fn list(&self, path: &str) -> gpui::Result<Vec<SharedString>> {
+            let mut paths = Vec::new();
+            for entry in std::fs::read_dir(Path::new("../../assets").join(path))? {
+                let name = entry?.file_name().to_string_lossy().into_owned();
+                paths.push(format!("{path}/{name}").into());
+            }
+            Ok(paths)
+        }
+    }
+
+    struct Icons;
+
+    impl Render for Icons {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            h_flex()
+                .items_start()
+                .child(
+                    div()
+                        .debug_selector(|| "SMALL".into())
+                        .child(Icon::new(IconName::Check).size(IconSize::Small)),
+                )
+                .child(
+                    div()
+                        .debug_selector(|| "MISSING".into())
+                        .child(Icon::from_path("icons/missing.svg")),
+                )
+        }
+    }
+
+    fn found(path: &str, cx: &mut TestAppContext) -> Option<bool> {
+        cx.update(|cx| {
+            cx.try_global::<IconAssets>()
+                .and_then(|assets| assets.0.get(path).copied())
+        })
+    }
+
+    #[gpui::test]
+    fn test_icon_sizes_align_to_device_pixels(cx: &mut TestAppContext) {
+        crate::test::init(cx);
+        cx.set_asset_source(RepositoryAssets);
+        let (_, window) = cx.add_window_view(|_| Icons);
+        window.update(|cx| cx.set_rem_size(px(18.)));
+        window.run_until_parked();
+
+        // 14px at this scale is 15.75px, which rounds to 32 device pixels at a scale factor of 2.
+        let small = window.debug_bounds("SMALL").unwrap();
+        assert_eq!(small.size, size(px(16.), px(16.)));
+
+        // Missing icons still take up their space.
+        let missing = window.debug_bounds("MISSING").unwrap();
+        assert_eq!(missing.size, size(px(18.), px(18.)));
+
+        // The icon that exists was drawn as an SVG, and the other as a placeholder.
+        assert_eq!(found("icons/check.svg", cx), Some(true));
+        assert_eq!(found("icons/missing.svg", cx), Some(false));
+    }
+
+    #[gpui::test]
+    fn test_icons_are_not_missing_without_assets(cx: &mut TestAppContext) {
+        crate::test::init(cx);
+        let (_, window) = cx.add_window_view(|_| Icons);
+        window.run_until_parked();
+
+        assert_eq!(found("icons/missing.svg", cx), Some(true));
+    }
+}
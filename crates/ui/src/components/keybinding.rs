@@ -7,7 +7,7 @@ pub struct KeyBinding {
     /// More then one keybinding produces a chord.
     ///
     /// This should always contain at least one element.
-    key_binding: gpui::KeyBinding,
+    keystrokes: Vec<Keystroke>,
 
     /// The [`PlatformStyle`] to use when displaying this keybinding.
     platform_style: PlatformStyle,
@@ -30,6 +30,13 @@ impl KeyBinding {
         Some(Self::new(key_binding))
     }
 
+    // like for_action(), for the action registered under the given name, e.g.
+    // "editor::Save". Returns `None` when no action has that name.
+    pub fn for_action_name(name: &str, cx: &mut WindowContext) -> Option<Self> {
+        let action = cx.build_action(name, None).ok()?;
+        Self::for_action(action.as_ref(), cx)
+    }
+
     fn icon_for_key(&self, keystroke: &Keystroke) -> Option<IconName> {
         match keystroke.key.as_str() {
             "left" => Some(IconName::ArrowLeft),
@@ -54,9 +61,50 @@ impl KeyBinding {
         }
     }
 
+    /// The parts a keystroke is shown as, modifiers first: icons on macOS, and names joined
+    /// with `+` elsewhere.
+    fn keystroke_parts(&self, keystroke: &Keystroke) -> Vec<KeyPart> {
+        let cmd = match self.platform_style {
+            PlatformStyle::Windows => "Win",
+            PlatformStyle::Mac | PlatformStyle::Linux => "Super",
+        };
+        let modifiers = &keystroke.modifiers;
+        let modifiers = [
+            (modifiers.function, KeyPart::Text("fn".into()), "Fn"),
+            (modifiers.control, KeyPart::Icon(IconName::Control), "Ctrl"),
+            (modifiers.alt, KeyPart::Icon(IconName::Option), "Alt"),
+            (modifiers.platform, KeyPart::Icon(IconName::Command), cmd),
+            (modifiers.shift, KeyPart::Icon(IconName::Shift), "Shift"),
+        ];
+
+        let mut parts = Vec::new();
+        for (pressed, mac_part, name) in modifiers {
+            if !pressed {
+                continue;
+            }
+            match self.platform_style {
+                PlatformStyle::Mac => parts.push(mac_part),
+                PlatformStyle::Linux | PlatformStyle::Windows => {
+                    parts.push(KeyPart::Text(name.into()));
+                    parts.push(KeyPart::Text("+".into()));
+                }
+            }
+        }
+        parts.push(match self.icon_for_key(keystroke) {
+            Some(icon) => KeyPart::Icon(icon),
+            None => KeyPart::Text(keystroke.key.to_uppercase().into()),
+        });
+        parts
+    }
+
     pub fn new(key_binding: gpui::KeyBinding) -> Self {
+        Self::from_keystrokes(key_binding.keystrokes().to_vec())
+    }
+
+    /// Shows the given keystrokes, which aren't necessarily bound to anything.
+    pub fn from_keystrokes(keystrokes: Vec<Keystroke>) -> Self {
         Self {
-            key_binding,
+            keystrokes,
             platform_style: PlatformStyle::platform(),
         }
     }
@@ -68,14 +116,20 @@ impl KeyBinding {
     }
 }
 
+/// A modifier or key of a keystroke, or the `+` between them.
+#[derive(Clone, Debug, PartialEq)]
+enum KeyPart {
+    Icon(IconName),
+    Text(SharedString),
+}
+
 impl RenderOnce for KeyBinding {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         h_flex()
             .debug_selector(|| {
                 format!(
                     "KEY_BINDING-{}",
-                    self.key_binding
-                        .keystrokes()
+                    self.keystrokes
                         .iter()
                         .map(|k| k.key.to_string())
                         .collect::<Vec<_>>()
@@ -84,57 +138,20 @@ impl RenderOnce for KeyBinding {
             })
             .gap(Spacing::Small.rems(cx))
             .flex_none()
-            .children(self.key_binding.keystrokes().iter().map(|keystroke| {
-                let key_icon = self.icon_for_key(keystroke);
-
+            .children(self.keystrokes.iter().map(|keystroke| {
                 h_flex()
                     .flex_none()
                     .py_0p5()
                     .rounded_sm()
                     .text_color(cx.theme().colors().text_muted)
-                    .when(keystroke.modifiers.function, |el| {
-                        match self.platform_style {
-                            PlatformStyle::Mac => el.child(Key::new("fn")),
-                            PlatformStyle::Linux | PlatformStyle::Windows => {
-                                el.child(Key::new("Fn")).child(Key::new("+"))
-                            }
-                        }
-                    })
-                    .when(keystroke.modifiers.control, |el| {
-                        match self.platform_style {
-                            PlatformStyle::Mac => el.child(KeyIcon::new(IconName::Control)),
-                            PlatformStyle::Linux | PlatformStyle::Windows => {
-                                el.child(Key::new("Ctrl")).child(Key::new("+"))
-                            }
-                        }
-                    })
-                    .when(keystroke.modifiers.alt, |el| match self.platform_style {
-                        PlatformStyle::Mac => el.child(KeyIcon::new(IconName::Option)),
-                        PlatformStyle::Linux | PlatformStyle::Windows => {
-                            el.child(Key::new("Alt")).child(Key::new("+"))
-                        }
-                    })
-                    .when(keystroke.modifiers.platform, |el| {
-                        match self.platform_style {
-                            PlatformStyle::Mac => el.child(KeyIcon::new(IconName::Command)),
-                            PlatformStyle::Linux => {
-                                el.child(Key::new("Super")).child(Key::new("+"))
-                            }
-                            PlatformStyle::Windows => {
-                                el.child(Key::new("Win")).child(Key::new("+"))
-                            }
-                        }
-                    })
-                    .when(keystroke.modifiers.shift, |el| match self.platform_style {
-                        PlatformStyle::Mac => el.child(KeyIcon::new(IconName::Shift)),
-                        PlatformStyle::Linux | PlatformStyle::Windows => {
-                            el.child(Key::new("Shift")).child(Key::new("+"))
-                        }
-                    })
-                    .map(|el| match key_icon {
-                        Some(icon) => el.child(KeyIcon::new(icon)),
-                        None => el.child(Key::new(keystroke.key.to_uppercase())),
-                    })
+                    .children(
+                        self.keystroke_parts(keystroke)
+                            .into_iter()
+                            .map(|part| match part {
+                                KeyPart::Icon(icon) => KeyIcon::new(icon).into_any_element(),
+                                KeyPart::Text(text) => Key::new(text).into_any_element(),
+                            }),
+                    )
             }))
     }
 }
@@ -192,3 +209,89 @@ impl KeyIcon {
         Self { icon }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::{actions, Render, TestAppContext, ViewContext};
+
+    use super::*;
+
+    actions!(keybinding_test, [Bound, Unbound]);
+
+    fn parts(source: &str, platform_style: PlatformStyle) -> Vec<KeyPart> {
+        let keystroke = Keystroke::parse(source).unwrap();
+        KeyBinding::from_keystrokes(vec![keystroke.clone()])
+            .platform_style(platform_style)
+            .keystroke_parts(&keystroke)
+    }
+
+    fn text(text: &'static str) -> KeyPart {
+        KeyPart::Text(text.into())
+    }
+
+    #[test]
+    fn test_keystrokes_on_mac() {
+        assert_eq!(
+            parts("cmd-shift-s", PlatformStyle::Mac),
+            [
+                KeyPart::Icon(IconName::Command),
+                KeyPart::Icon(IconName::Shift),
+                text("S")
+            ]
+        );
+        assert_eq!(
+            parts("ctrl-alt-left", PlatformStyle::Mac),
+            [
+                KeyPart::Icon(IconName::Control),
+                KeyPart::Icon(IconName::Option),
+                KeyPart::Icon(IconName::ArrowLeft)
+            ]
+        );
+        assert_eq!(parts("f5", PlatformStyle::Mac), [text("F5")]);
+    }
+
+    #[test]
+    fn test_keystrokes_on_linux_and_windows() {
+        assert_eq!(
+            parts("ctrl-shift-s", PlatformStyle::Linux),
+            [text("Ctrl"), text("+"), text("Shift"), text("+"), text("S")]
+        );
+        assert_eq!(
+            parts("cmd-f5", PlatformStyle::Linux),
+            [text("Super"), text("+"), text("F5")]
+        );
+        assert_eq!(
+            parts("cmd-f5", PlatformStyle::Windows),
+            [text("Win"), text("+"), text("F5")]
+        );
+    }
+
+    struct Menu;
+
+    impl Render for Menu {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            h_flex()
+                .children(KeyBinding::for_action(&Unbound, cx))
+                .children(KeyBinding::for_action_name("keybinding_test::Unbound", cx))
+                .children(KeyBinding::for_action_name("keybinding_test::Missing", cx))
+                .child(
+                    div()
+                        .w(px(10.))
+                        .h(px(10.))
+                        .debug_selector(|| "after".into()),
+                )
+                .children(KeyBinding::for_action_name("keybinding_test::Bound", cx))
+        }
+    }
+
+    #[gpui::test]
+    fn test_only_bound_actions_are_shown(cx: &mut TestAppContext) {
+        crate::test::init(cx);
+        cx.update(|cx| cx.bind_keys([gpui::KeyBinding::new("ctrl-b", Bound, None)]));
+        let (_, cx) = cx.add_window_view(|_| Menu);
+        cx.run_until_parked();
+
+        assert_eq!(cx.debug_bounds("after").unwrap().origin.x, px(0.));
+        assert!(cx.debug_bounds("KEY_BINDING-b").is_some());
+    }
+}
@@ -1,6 +1,6 @@
-use gpui::WindowContext;
+use gpui::{ElementId, WindowContext};
 
-use crate::{prelude::*, LabelCommon, LabelLike, LabelSize, LineHeightStyle};
+use crate::{prelude::*, LabelCommon, LabelLike, LabelSize, LineHeightStyle, Tooltip};
 
 /// A struct representing a label element in the UI.
 ///
@@ -35,6 +35,7 @@ pub struct Label {
     base: LabelLike,
     label: SharedString,
     single_line: bool,
+    truncation_tooltip: Option<ElementId>,
 }
 
 impl Label {
@@ -52,6 +53,7 @@ impl Label {
             base: LabelLike::new(),
             label: label.into(),
             single_line: false,
+            truncation_tooltip: None,
         }
     }
 
@@ -68,6 +70,22 @@ impl Label {
         self.single_line = true;
         self
     }
+
+    /// Keep the label on one line that's cut off where it doesn't fit, and show the whole label
+    /// in a tooltip when it's cut off. Whether it's cut off is read from the last frame's layout,
+    /// so the tooltip is added a frame after the label is first drawn truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ui::prelude::*;
+    ///
+    /// let my_label = Label::new("A long file name.rs").tooltip_when_truncated("file-name");
+    /// ```
+    pub fn tooltip_when_truncated(mut self, id: impl Into<ElementId>) -> Self {
+        self.truncation_tooltip = Some(id.into());
+        self
+    }
 }
 
 impl LabelCommon for Label {
@@ -143,12 +161,80 @@ impl LabelCommon for Label {
 }
 
 impl RenderOnce for Label {
-    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         let target_label = if self.single_line {
             SharedString::from(self.label.replace('\n', "␤"))
         } else {
-            self.label
+            self.label.clone()
         };
-        self.base.child(target_label)
+        let Some(id) = self.truncation_tooltip else {
+            return self.base.child(target_label).into_any_element();
+        };
+
+        let truncated = cx
+            .last_layout(id.clone())
+            .map_or(false, |layout| layout.text_truncated);
+        let label = self.label;
+        div()
+            .id(id)
+            .flex()
+            .overflow_x_hidden()
+            .whitespace_nowrap()
+            .child(self.base.child(target_label))
+            .when(truncated, |this| {
+                this.tooltip(move |cx| Tooltip::text(label.clone(), cx))
+            })
+            .into_any_element()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gpui::{point, px, Modifiers, Render, TestAppContext, View, VisualTestContext};
+
+    use super::*;
+
+    struct FileName {
+        name: &'static str,
+    }
+
+    impl Render for FileName {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .w(px(80.))
+                .child(Label::new(self.name).tooltip_when_truncated("name"))
+        }
+    }
+
+    fn hover_for_tooltip(cx: &mut VisualTestContext) -> bool {
+        cx.simulate_mouse_move(point(px(200.), px(200.)), None, Modifiers::none());
+        cx.simulate_mouse_move(point(px(10.), px(5.)), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_secs(1));
+        cx.run_until_parked();
+        cx.debug_bounds("TOOLTIP").is_some()
+    }
+
+    fn set_name(view: &View<FileName>, name: &'static str, cx: &mut VisualTestContext) {
+        view.update(cx, |view, cx| {
+            view.name = name;
+            cx.notify();
+        });
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn test_tooltip_is_shown_only_when_truncated(cx: &mut TestAppContext) {
+        crate::test::init(cx);
+        let (view, cx) = cx.add_window_view(|_| FileName { name: "main.rs" });
+        cx.run_until_parked();
+        assert!(!hover_for_tooltip(cx));
+
+        set_name(&view, "a_very_long_module_name.rs", cx);
+        assert!(hover_for_tooltip(cx));
+
+        set_name(&view, "lib.rs", cx);
+        assert!(!hover_for_tooltip(cx));
     }
 }
@@ -162,7 +162,7 @@ impl RenderOnce for ListItem {
             // When an item is inset draw the indent spacing outside of the item
             .when(self.inset, |this| {
                 this.ml(self.indent_level as f32 * self.indent_step_size)
-                    .px_2()
+                    .px(Spacing::Large.rems(cx))
             })
             .when(!self.inset && !self.disabled, |this| {
                 this
@@ -184,11 +184,11 @@ impl RenderOnce for ListItem {
                     .id("inner_list_item")
                     .w_full()
                     .relative()
-                    .gap_1()
-                    .px_2()
+                    .gap(Spacing::Small.rems(cx))
+                    .px(Spacing::Large.rems(cx))
                     .map(|this| match self.spacing {
                         ListItemSpacing::Dense => this,
-                        ListItemSpacing::Sparse => this.py_1(),
+                        ListItemSpacing::Sparse => this.py(Spacing::Small.rems(cx)),
                     })
                     .group("list_item")
                     .when(self.inset && !self.disabled, |this| {
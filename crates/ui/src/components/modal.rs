@@ -20,7 +20,7 @@ impl Modal {
     pub fn new(id: impl Into<SharedString>, scroll_handle: Option<ScrollHandle>) -> Self {
         let id = id.into();
 
-        let container_id = ElementId::Name(format!("{}_container", id.clone()).into());
+        let container_id = ElementId::Name(id.clone()).scoped("container");
         Self {
             id: ElementId::Name(id),
             header: ModalHeader::new(),
@@ -43,11 +43,16 @@ impl RenderOnce for Popover {
         div()
             .flex()
             .gap_1()
-            .child(v_flex().elevation_2(cx).px_1().children(self.children))
+            .child(
+                v_flex()
+                    .elevation_overlay(cx)
+                    .px_1()
+                    .children(self.children),
+            )
             .when_some(self.aside, |this, aside| {
                 this.child(
                     v_flex()
-                        .elevation_2(cx)
+                        .elevation_overlay(cx)
                         .bg(cx.theme().colors().surface_background)
                         .px_1()
                         .child(aside),
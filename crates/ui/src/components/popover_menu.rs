@@ -1,10 +1,10 @@
 use std::{cell::RefCell, rc::Rc};
 
 use gpui::{
-    anchored, deferred, div, point, prelude::FluentBuilder, px, AnchorCorner, AnyElement, Bounds,
-    DismissEvent, DispatchPhase, Element, ElementId, GlobalElementId, HitboxId, InteractiveElement,
-    IntoElement, LayoutId, ManagedView, MouseDownEvent, ParentElement, Pixels, Point, View,
-    VisualContext, WindowContext,
+    anchored, deferred, dismissable, div, point, prelude::FluentBuilder, px, AnchorCorner,
+    AnyElement, Bounds, DismissEvent, DispatchPhase, Element, ElementId, GlobalElementId, HitboxId,
+    InteractiveElement, IntoElement, LayoutId, ManagedView, MouseDownEvent, ParentElement, Pixels,
+    Point, View, VisualContext, WindowContext,
 };
 
 use crate::prelude::*;
@@ -233,7 +233,19 @@ impl<M: ManagedView> Element for PopoverMenu<M> {
                             self.resolved_attach().corner(child_bounds) + self.resolved_offset(cx),
                         );
                     }
-                    let mut element = deferred(anchored.child(div().occlude().child(menu.clone())))
+                    // Clicking away from the menu or pressing escape cancels it, which closes it.
+                    let on_dismiss = {
+                        let menu = menu.clone();
+                        move |cx: &mut WindowContext| {
+                            menu.focus_handle(cx).dispatch_action(&menu::Cancel, cx)
+                        }
+                    };
+                    let dismissable_menu = dismissable(
+                        "popover-menu",
+                        on_dismiss,
+                        div().occlude().child(menu.clone()),
+                    );
+                    let mut element = deferred(anchored.child(dismissable_menu))
                         .with_priority(1)
                         .into_any();
 
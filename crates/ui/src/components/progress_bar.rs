@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use gpui::{ease_in_out, Animation, AnimationExt};
+
+use crate::prelude::*;
+
+/// # ProgressBar
+///
+/// Shows how far along a task is, as a rounded fill over a track.
+///
+/// Changes to the fraction are animated. When the progress of a task isn't known,
+/// [`ProgressBar::indeterminate`] shows a segment sweeping across the track instead, which
+/// only asks for animation frames while the bar is rendered.
+#[derive(IntoElement)]
+pub struct ProgressBar {
+    id: ElementId,
+    fraction: Option<f32>,
+}
+
+impl ProgressBar {
+    /// Returns a progress bar filled up to `fraction`, which is clamped between 0 and 1.
+    pub fn new(id: impl Into<ElementId>, fraction: f32) -> Self {
+        Self {
+            id: id.into(),
+            fraction: Some(fraction.clamp(0., 1.)),
+        }
+    }
+
+    pub fn indeterminate(mut self) -> Self {
+        self.fraction = None;
+        self
+    }
+}
+
+impl RenderOnce for ProgressBar {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let fill = div()
+            .absolute()
+            .top_0()
+            .bottom_0()
+            .rounded_full()
+            .bg(cx.theme().colors().icon_accent);
+
+        let fill = match self.fraction {
+            Some(fraction) => fill
+                .left_0()
+                .with_transition(
+                    self.id,
+                    fraction,
+                    Animation::new(Duration::from_millis(200)).with_easing(ease_in_out),
+                    |fill, fraction| fill.w(relative(fraction)),
+                )
+                .into_any_element(),
+            None => fill
+                .w(relative(0.3))
+                .with_animation(
+                    self.id,
+                    Animation::new(Duration::from_millis(1500))
+                        .repeat()
+                        .with_easing(ease_in_out)
+                        .movement(),
+                    |fill, delta| fill.left(relative(delta * 1.3 - 0.3)),
+                )
+                .into_any_element(),
+        };
+
+        div()
+            .relative()
+            .w_full()
+            .h(rems_from_px(4.))
+            .rounded_full()
+            .overflow_hidden()
+            .bg(cx.theme().colors().element_background)
+            .child(fill)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{Render, TestAppContext, View, VisualContext};
+
+    use super::*;
+
+    struct ProgressView {
+        loading: bool,
+    }
+
+    impl Render for ProgressView {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div().w(px(200.)).when(self.loading, |this| {
+                this.child(ProgressBar::new("progress", 0.).indeterminate())
+            })
+        }
+    }
+
+    #[gpui::test]
+    fn test_indeterminate_progress_bar_requests_frames_while_mounted(cx: &mut TestAppContext) {
+        crate::test::init(cx);
+        let (view, cx): (View<ProgressView>, _) =
+            cx.add_window_view(|_| ProgressView { loading: true });
+
+        cx.run_until_parked();
+        assert!(cx.has_pending_frame_callbacks());
+        cx.simulate_frame();
+        assert!(cx.has_pending_frame_callbacks());
+
+        view.update(cx, |view, cx| {
+            view.loading = false;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.simulate_frame();
+        assert!(!cx.has_pending_frame_callbacks());
+    }
+}
@@ -0,0 +1,297 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gpui::{roving_focus, AnyElement, Axis, FocusHandle, RovingFocusHandle};
+
+use crate::{prelude::*, FormField};
+
+/// One of the options shown by a [`RadioGroup`].
+pub struct RadioOption<T> {
+    value: T,
+    label: AnyElement,
+    disabled: bool,
+}
+
+impl<T> RadioOption<T> {
+    pub fn new(value: T, label: impl IntoElement) -> Self {
+        Self {
+            value,
+            label: label.into_any_element(),
+            disabled: false,
+        }
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// # RadioGroup
+///
+/// Radio groups are used for mutually exclusive choices: selecting an option
+/// deselects whichever option was selected before.
+///
+/// When given a focus handle, the whole group is a single focus target, built on
+/// [`roving_focus`]. The arrow keys then move the selection to the previous or next
+/// enabled option, wrapping around at the ends, and home and end move it to the first
+/// and last.
+#[derive(IntoElement)]
+pub struct RadioGroup<T: Clone + PartialEq + 'static> {
+    id: ElementId,
+    options: Vec<RadioOption<T>>,
+    selected: Option<T>,
+    on_change: Option<Rc<dyn Fn(&T, &mut WindowContext) + 'static>>,
+    axis: Axis,
+    focus_handle: Option<FocusHandle>,
+}
+
+impl<T: Clone + PartialEq + 'static> RadioGroup<T> {
+    pub fn new(id: impl Into<ElementId>, selected: Option<T>) -> Self {
+        Self {
+            id: id.into(),
+            options: Vec::new(),
+            selected,
+            on_change: None,
+            axis: Axis::Vertical,
+            focus_handle: None,
+        }
+    }
+
+    pub fn option(mut self, option: RadioOption<T>) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    pub fn options(mut self, options: impl IntoIterator<Item = RadioOption<T>>) -> Self {
+        self.options.extend(options);
+        self
+    }
+
+    /// Lays the options out in a row rather than a column.
+    pub fn horizontal(mut self) -> Self {
+        self.axis = Axis::Horizontal;
+        self
+    }
+
+    pub fn on_change(mut self, handler: impl Fn(&T, &mut WindowContext) + 'static) -> Self {
+        self.on_change = Some(Rc::new(handler));
+        self
+    }
+
+    /// Lets the group be focused with the given handle, enabling keyboard navigation.
+    pub fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> RenderOnce for RadioGroup<T> {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let inner_diameter = rems_from_px(6.);
+        let outer_diameter = rems_from_px(16.);
+        let border_width = rems_from_px(1.);
+
+        let selected_ix = self
+            .options
+            .iter()
+            .position(|option| Some(&option.value) == self.selected.as_ref());
+        let values = self
+            .options
+            .iter()
+            .map(|option| option.value.clone())
+            .collect::<Rc<[_]>>();
+        let enabled = self
+            .options
+            .iter()
+            .map(|option| !option.disabled)
+            .collect::<Vec<_>>();
+        let invalid = FormField::is_invalid(cx);
+        let spacing = match self.axis {
+            Axis::Horizontal => Spacing::XLarge.rems(cx),
+            Axis::Vertical => Spacing::Medium.rems(cx),
+        };
+        // The selected option is the active one, which the arrow keys move along with the
+        // selection.
+        let selection = RovingFocusHandle::new();
+        selection.set_active_index(selected_ix);
+
+        let option_count = self.options.len();
+        let labels = RefCell::new(
+            self.options
+                .into_iter()
+                .map(|option| Some(option.label))
+                .collect::<Vec<_>>(),
+        );
+        let group = roving_focus(self.id.clone(), option_count, {
+            let values = values.clone();
+            let enabled = enabled.clone();
+            let on_change = self.on_change.clone();
+            let focus_handle = self.focus_handle.clone();
+            move |ix, _, cx| {
+                // Hover groups resolve to the innermost enclosing group with the name, so each
+                // option's group only needs a name within the option.
+                let group_id = "radio_option";
+                let is_selected = Some(ix) == selected_ix;
+                let disabled = !enabled[ix];
+                let (border_color, dot_color) = if disabled {
+                    (
+                        cx.theme().colors().border_disabled,
+                        cx.theme().colors().icon_disabled,
+                    )
+                } else if invalid {
+                    (cx.theme().status().error, cx.theme().colors().icon_accent)
+                } else {
+                    (cx.theme().colors().border, cx.theme().colors().icon_accent)
+                };
+
+                h_flex()
+                    .id(ix)
+                    .gap(Spacing::Large.rems(cx))
+                    .group(group_id)
+                    .child(
+                        div()
+                            .flex_none()
+                            .size(outer_diameter)
+                            .rounded(outer_diameter / 2.)
+                            .border_color(border_color)
+                            .border(border_width)
+                            .when(!disabled, |el| {
+                                el.group_hover(group_id, |el| {
+                                    el.bg(cx.theme().colors().element_hover)
+                                })
+                            })
+                            .when(is_selected, |el| {
+                                el.child(
+                                    div()
+                                        .m((outer_diameter - inner_diameter) / 2. - border_width)
+                                        .size(inner_diameter)
+                                        .rounded(inner_diameter / 2.)
+                                        .bg(dot_color),
+                                )
+                            }),
+                    )
+                    .children(labels.borrow_mut()[ix].take())
+                    .when_some(
+                        on_change.clone().filter(|_| !disabled),
+                        |this, on_change| {
+                            let focus_handle = focus_handle.clone();
+                            let value = values[ix].clone();
+                            this.on_click(move |_, cx| {
+                                if let Some(focus_handle) = focus_handle.as_ref() {
+                                    focus_handle.focus(cx);
+                                }
+                                if !is_selected {
+                                    on_change(&value, cx);
+                                }
+                            })
+                        },
+                    )
+            }
+        })
+        .map(|this| match self.axis {
+            Axis::Horizontal => this.horizontal(),
+            Axis::Vertical => this,
+        })
+        .gap(spacing)
+        .wrap_around()
+        .track_active(&selection)
+        .selectable(move |ix| enabled[ix])
+        .when_some(self.on_change, |this, on_change| {
+            this.on_active_change(move |ix, cx| {
+                if Some(ix) != selected_ix {
+                    on_change(&values[ix], cx);
+                }
+            })
+        });
+
+        match self.focus_handle {
+            Some(focus_handle) => group
+                .track_focus(&focus_handle)
+                .focus_visible(|style| style.focus_ring(cx).rounded_sm())
+                .into_any_element(),
+            None => group.into_any_element(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{Render, TestAppContext, View, ViewContext, VisualTestContext};
+
+    use super::*;
+
+    const SIZES: [&str; 4] = ["Small", "Medium", "Large", "Huge"];
+
+    struct SizePicker {
+        selected: &'static str,
+        focus_handle: FocusHandle,
+    }
+
+    impl Render for SizePicker {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let this = cx.view().downgrade();
+            div().size_full().child(
+                RadioGroup::new("size", Some(self.selected))
+                    .options(SIZES.into_iter().map(|size| {
+                        RadioOption::new(size, Label::new(size)).disabled(size == "Large")
+                    }))
+                    .track_focus(&self.focus_handle)
+                    .on_change(move |size, cx| {
+                        this.update(cx, |this, cx| {
+                            this.selected = *size;
+                            cx.notify();
+                        })
+                        .ok();
+                    }),
+            )
+        }
+    }
+
+    fn size_picker(cx: &mut TestAppContext) -> (View<SizePicker>, &mut VisualTestContext) {
+        crate::test::init(cx);
+        let (picker, cx) = cx.add_window_view(|cx| SizePicker {
+            selected: "Small",
+            focus_handle: cx.focus_handle(),
+        });
+        picker.update(cx, |picker, cx| cx.focus(&picker.focus_handle));
+        cx.run_until_parked();
+        (picker, cx)
+    }
+
+    fn selected(picker: &View<SizePicker>, cx: &mut VisualTestContext) -> &'static str {
+        picker.update(cx, |picker, _| picker.selected)
+    }
+
+    #[gpui::test]
+    fn test_arrow_keys_move_the_selection(cx: &mut TestAppContext) {
+        let (picker, cx) = size_picker(cx);
+
+        cx.driver().press("down");
+        assert_eq!(selected(&picker, cx), "Medium");
+        // Disabled options are skipped over.
+        cx.driver().press("down");
+        assert_eq!(selected(&picker, cx), "Huge");
+        // The selection wraps around at both ends.
+        cx.driver().press("down");
+        assert_eq!(selected(&picker, cx), "Small");
+        cx.driver().press("up");
+        assert_eq!(selected(&picker, cx), "Huge");
+        cx.driver().press("up home");
+        assert_eq!(selected(&picker, cx), "Small");
+        cx.driver().press("end");
+        assert_eq!(selected(&picker, cx), "Huge");
+    }
+
+    #[gpui::test]
+    fn test_clicking_an_option_selects_it(cx: &mut TestAppContext) {
+        let (picker, cx) = size_picker(cx);
+
+        cx.driver().click(3usize);
+        assert_eq!(selected(&picker, cx), "Huge");
+        // Navigation continues from the clicked option.
+        cx.driver().press("up");
+        assert_eq!(selected(&picker, cx), "Medium");
+        cx.driver().click(2usize);
+        assert_eq!(selected(&picker, cx), "Medium");
+    }
+}
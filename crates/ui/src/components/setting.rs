@@ -260,7 +260,7 @@ impl RenderOnce for SettingsItem {
         };
 
         let checkbox = Checkbox::new(
-            ElementId::Name(format!("toggle-{}", self.id.0).to_string().into()),
+            ElementId::from(self.id.clone()).scoped("toggle"),
             self.toggled.into(),
         )
         .disabled(self.disabled);
@@ -332,7 +332,7 @@ impl Render for SettingsMenu {
         let is_empty = self.groups.is_empty();
         v_flex()
             .id(ElementId::Name(self.name.clone()))
-            .elevation_2(cx)
+            .elevation_overlay(cx)
             .min_w_56()
             .max_w_96()
             .max_h_2_3()
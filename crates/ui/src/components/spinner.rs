@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use gpui::{percentage, Animation, AnimationExt, Transformation};
+
+use crate::prelude::*;
+
+/// # Spinner
+///
+/// A rotating arc for inline loading states.
+///
+/// The spinner only asks for animation frames while it's rendered, so removing it from the
+/// tree stops it from keeping the window redrawing.
+#[derive(IntoElement)]
+pub struct Spinner {
+    id: ElementId,
+    size: IconSize,
+    color: Color,
+}
+
+impl Spinner {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            size: IconSize::default(),
+            color: Color::Muted,
+        }
+    }
+
+    pub fn size(mut self, size: IconSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl RenderOnce for Spinner {
+    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+        Icon::new(IconName::Spinner)
+            .size(self.size)
+            .color(self.color)
+            .with_animation(
+                self.id,
+                Animation::new(Duration::from_secs(1)).repeat(),
+                |icon, delta| icon.transform(Transformation::rotate(percentage(delta))),
+            )
+    }
+}
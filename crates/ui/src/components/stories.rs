@@ -1,8 +1,11 @@
 mod avatar;
+mod banner;
+mod breadcrumbs;
 mod button;
 mod checkbox;
 mod context_menu;
 mod disclosure;
+mod form_field;
 mod icon;
 mod icon_button;
 mod keybinding;
@@ -10,6 +13,7 @@ mod label;
 mod list;
 mod list_header;
 mod list_item;
+mod radio_group;
 mod setting;
 mod tab;
 mod tab_bar;
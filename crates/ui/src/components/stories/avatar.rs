@@ -2,7 +2,7 @@ use gpui::Render;
 use story::{StoryContainer, StoryItem, StorySection};
 
 use crate::{prelude::*, AudioStatus, Availability, AvatarAvailabilityIndicator};
-use crate::{Avatar, AvatarAudioStatusIndicator};
+use crate::{Avatar, AvatarAudioStatusIndicator, AvatarFallback, AvatarSize, AvatarStack};
 
 pub struct AvatarStory;
 
@@ -59,5 +59,36 @@ impl Render for AvatarStory {
                             .indicator(AvatarAudioStatusIndicator::new(AudioStatus::Deafened)),
                     )),
             )
+            .child(
+                StorySection::new()
+                    .child(StoryItem::new(
+                        "With initials",
+                        Avatar::placeholder(AvatarFallback::Initials("NS".into()))
+                            .size(AvatarSize::Large),
+                    ))
+                    .child(StoryItem::new(
+                        "Failing to load",
+                        Avatar::new("https://example.com/missing.png")
+                            .size(AvatarSize::Large)
+                            .status(Availability::Free),
+                    )),
+            )
+            .child(
+                StorySection::new().child(StoryItem::new(
+                    "Stack with overflow",
+                    AvatarStack::new("avatar-stack")
+                        .size(AvatarSize::Medium)
+                        .max_visible(3)
+                        .child(Avatar::new(
+                            "https://avatars.githubusercontent.com/u/1714999?v=4",
+                        ))
+                        .child(Avatar::new(
+                            "https://avatars.githubusercontent.com/u/326587?v=4",
+                        ))
+                        .child(Avatar::placeholder(AvatarFallback::Initials("NS".into())))
+                        .child(Avatar::placeholder(IconName::Person))
+                        .child(Avatar::placeholder(IconName::Person)),
+                )),
+            )
     }
 }
@@ -0,0 +1,40 @@
+use crate::prelude::*;
+use crate::{Banner, BannerSeverity};
+
+story::register_story!("banner/severities", |knobs, _cx| {
+    let dismissable = knobs.bool("Dismissable", true);
+
+    let banners = |width: Pixels| {
+        v_flex().w(width).gap_2().children(
+            [
+                (
+                    BannerSeverity::Info,
+                    "Indexing the project, search results may be incomplete.",
+                ),
+                (BannerSeverity::Success, "Connected to the remote server."),
+                (
+                    BannerSeverity::Warning,
+                    "This file was changed on disk since it was opened.",
+                ),
+                (
+                    BannerSeverity::Error,
+                    "The language server crashed and could not be restarted.",
+                ),
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(ix, (severity, message))| {
+                Banner::new(ix, severity)
+                    .child(Label::new(message))
+                    .action(Button::new("details", "Details").style(ButtonStyle::Subtle))
+                    .action(Button::new("retry", "Retry").style(ButtonStyle::Filled))
+                    .when(dismissable, |this| this.on_dismiss(|_| {}))
+            }),
+        )
+    };
+
+    v_flex()
+        .gap_6()
+        .child(div().id("wide").child(banners(px(640.))))
+        .child(div().id("narrow").child(banners(px(320.))))
+});
@@ -0,0 +1,17 @@
+use crate::prelude::*;
+use crate::{BreadcrumbSegment, Breadcrumbs};
+
+story::register_story!("breadcrumbs/overflow", |knobs, _cx| {
+    let width = knobs.choice(
+        "Width",
+        &[("Wide", px(640.)), ("Narrow", px(240.)), ("Tiny", px(120.))],
+    );
+
+    div().w(width).child(
+        Breadcrumbs::new("breadcrumbs").segments(
+            ["zed", "crates", "ui", "src", "components", "breadcrumbs.rs"]
+                .into_iter()
+                .map(|label| BreadcrumbSegment::new(label).on_click(|_| {})),
+        ),
+    )
+});
@@ -36,3 +36,31 @@ impl Render for ButtonStory {
             .child(Button::new("default_transparent", "Click me").style(ButtonStyle::Transparent))
     }
 }
+
+story::register_story!("button/variants", |knobs, _cx| {
+    let label = knobs.text("Label", "Click me");
+    let style = knobs.choice(
+        "Style",
+        &[
+            ("Filled", ButtonStyle::Filled),
+            ("Subtle", ButtonStyle::Subtle),
+            ("Transparent", ButtonStyle::Transparent),
+        ],
+    );
+    let size = knobs.choice(
+        "Size",
+        &[
+            ("Default", ButtonSize::Default),
+            ("Large", ButtonSize::Large),
+            ("Compact", ButtonSize::Compact),
+        ],
+    );
+    let icon = knobs.bool("Icon", false);
+
+    Button::new("button", label)
+        .style(style)
+        .size(size)
+        .selected(knobs.bool("Selected", false))
+        .disabled(knobs.bool("Disabled", false))
+        .when(icon, |this| this.icon(IconName::FileGit))
+});
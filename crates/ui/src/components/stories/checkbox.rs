@@ -45,3 +45,16 @@ impl Render for CheckboxStory {
             )
     }
 }
+
+story::register_story!("checkbox/states", |knobs, _cx| {
+    let checked = knobs.choice(
+        "Checked",
+        &[
+            ("Unselected", Selection::Unselected),
+            ("Indeterminate", Selection::Indeterminate),
+            ("Selected", Selection::Selected),
+        ],
+    );
+
+    Checkbox::new("checkbox", checked).disabled(knobs.bool("Disabled", false))
+});
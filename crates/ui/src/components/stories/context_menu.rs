@@ -2,7 +2,7 @@ use gpui::{actions, AnchorCorner, Render, View};
 use story::Story;
 
 use crate::prelude::*;
-use crate::{right_click_menu, ContextMenu, Label};
+use crate::{right_click_menu, ContextMenu, ContextMenuEntry, Label};
 
 actions!(context_menu, [PrintCurrentDate, PrintBestFood]);
 
@@ -14,6 +14,14 @@ fn build_menu(cx: &mut WindowContext, header: impl Into<SharedString>) -> View<C
             .entry("Print best food", Some(Box::new(PrintBestFood)), |cx| {
                 cx.dispatch_action(Box::new(PrintBestFood))
             })
+            .separator()
+            .submenu("More", |menu, _| {
+                menu.action("Print current time", Box::new(PrintCurrentDate))
+                    .item(ContextMenuEntry::new("Unavailable").disabled(true))
+                    .submenu("Even more", |menu, _| {
+                        menu.action("Print best food", Box::new(PrintBestFood))
+                    })
+            })
     })
 }
 
@@ -0,0 +1,26 @@
+use crate::prelude::*;
+use crate::{Checkbox, FormField, Selection};
+
+story::register_story!("form_field/validation", |knobs, _cx| {
+    let error = knobs
+        .bool("Error", true)
+        .then_some("You need to accept the terms to continue.");
+
+    v_flex()
+        .gap_4()
+        .child(
+            FormField::new(
+                "terms",
+                "Terms of service",
+                Checkbox::new("accept", Selection::Unselected),
+            )
+            .description("Read them before accepting.")
+            .error(error)
+            .reserve_error_space(knobs.bool("Reserve error space", true)),
+        )
+        .child(FormField::new(
+            "newsletter",
+            "Newsletter",
+            Checkbox::new("subscribe", Selection::Selected),
+        ))
+});
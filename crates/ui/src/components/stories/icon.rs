@@ -3,7 +3,7 @@ use story::Story;
 use strum::IntoEnumIterator;
 
 use crate::{prelude::*, DecoratedIcon, IconDecoration};
-use crate::{Icon, IconName};
+use crate::{Icon, IconName, IconSize};
 
 pub struct IconStory;
 
@@ -31,6 +31,19 @@ impl Render for IconStory {
                     .decoration_color(Color::Error),
             )
             .child(Story::label("All Icons"))
-            .child(div().flex().gap_3().children(icons.map(Icon::new)))
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .gap_3()
+                    .children(icons.map(Icon::new)),
+            )
+            .child(Story::label("Sizes"))
+            .child(v_flex().gap_1().children(IconName::iter().map(|icon| {
+                h_flex()
+                    .gap_3()
+                    .child(div().w_48().child(Label::new(format!("{icon:?}"))))
+                    .children(IconSize::iter().map(|size| Icon::new(icon).size(size)))
+            })))
     }
 }
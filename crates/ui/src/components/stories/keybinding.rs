@@ -1,3 +1,4 @@
+use gpui::Keystroke;
 use gpui::NoAction;
 use gpui::Render;
 use itertools::Itertools;
@@ -78,3 +79,45 @@ impl Render for KeybindingStory {
         .child(KeyBinding::new(binding("fn-s")).platform_style(PlatformStyle::Windows))
     }
 }
+
+story::register_story!("keybinding/platforms", |knobs, cx| {
+    let platform_style = knobs.choice(
+        "Platform",
+        &[
+            ("macOS", PlatformStyle::Mac),
+            ("Linux", PlatformStyle::Linux),
+            ("Windows", PlatformStyle::Windows),
+        ],
+    );
+    let keystroke = knobs.text("Keystroke", "cmd-shift-s");
+    let action = knobs.text("Action", "menu::Confirm");
+
+    let row = |label: &'static str, key_binding: Option<KeyBinding>| {
+        h_flex()
+            .gap_2()
+            .child(Label::new(label).color(Color::Muted))
+            .children(key_binding.map(|key_binding| key_binding.platform_style(platform_style)))
+    };
+
+    v_flex()
+        .gap_4()
+        .child(row(
+            "Keystroke",
+            Keystroke::parse(&keystroke)
+                .ok()
+                .map(|keystroke| KeyBinding::from_keystrokes(vec![keystroke])),
+        ))
+        .child(row(
+            "Chord",
+            ["cmd-k", "cmd-s"]
+                .iter()
+                .map(|source| Keystroke::parse(source))
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+                .map(KeyBinding::from_keystrokes),
+        ))
+        .child(row(
+            "Bound action",
+            KeyBinding::for_action_name(&action, cx),
+        ))
+});
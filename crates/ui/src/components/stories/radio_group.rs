@@ -0,0 +1,20 @@
+use crate::prelude::*;
+use crate::{RadioGroup, RadioOption};
+
+story::register_story!("radio_group/orientation", |knobs, _cx| {
+    let selected = knobs.choice(
+        "Selected",
+        &[("None", None), ("Left", Some(0)), ("Center", Some(1))],
+    );
+    let group = RadioGroup::new("alignment", selected).options([
+        RadioOption::new(0, Label::new("Left")),
+        RadioOption::new(1, Label::new("Center")),
+        RadioOption::new(2, Label::new("Right")).disabled(knobs.bool("Disable Right", true)),
+    ]);
+
+    if knobs.bool("Horizontal", false) {
+        group.horizontal()
+    } else {
+        group
+    }
+});
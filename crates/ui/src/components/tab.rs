@@ -2,8 +2,9 @@ use std::cmp::Ordering;
 
 use gpui::{AnyElement, IntoElement, Stateful};
 use smallvec::SmallVec;
+use theme::UiDensity;
 
-use crate::{prelude::*, BASE_REM_SIZE_IN_PX};
+use crate::{prelude::*, user_spacing_style, BASE_REM_SIZE_IN_PX};
 
 /// The position of a [`Tab`] within a list of tabs.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -57,6 +58,24 @@ impl Tab {
 
     const CONTENT_HEIGHT_IN_REMS: f32 = 28. / BASE_REM_SIZE_IN_PX;
 
+    /// The height of a tab at the density of the elements being drawn.
+    /// [`Self::CONTAINER_HEIGHT_IN_REMS`] is the height at the default density.
+    pub fn container_height(cx: &WindowContext) -> Rems {
+        match user_spacing_style(cx) {
+            UiDensity::Compact => rems_from_px(24.),
+            UiDensity::Default => rems(Self::CONTAINER_HEIGHT_IN_REMS),
+            UiDensity::Comfortable => rems_from_px(32.),
+        }
+    }
+
+    fn content_height(cx: &WindowContext) -> Rems {
+        match user_spacing_style(cx) {
+            UiDensity::Compact => rems_from_px(23.),
+            UiDensity::Default => rems(Self::CONTENT_HEIGHT_IN_REMS),
+            UiDensity::Comfortable => rems_from_px(31.),
+        }
+    }
+
     pub fn position(mut self, position: TabPosition) -> Self {
         self.position = position;
         self
@@ -118,7 +137,7 @@ impl RenderOnce for Tab {
         };
 
         self.div
-            .h(rems(Self::CONTAINER_HEIGHT_IN_REMS))
+            .h(Self::container_height(cx))
             .bg(tab_bg)
             .border_color(cx.theme().colors().border)
             .map(|this| match self.position {
@@ -145,7 +164,7 @@ impl RenderOnce for Tab {
                 h_flex()
                     .group("")
                     .relative()
-                    .h(rems(Self::CONTENT_HEIGHT_IN_REMS))
+                    .h(Self::content_height(cx))
                     .px(crate::custom_spacing(cx, 20.))
                     .gap(Spacing::Small.rems(cx))
                     .text_color(text_color)
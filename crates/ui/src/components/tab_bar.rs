@@ -1,7 +1,7 @@
 use gpui::{AnyElement, ScrollHandle};
 use smallvec::SmallVec;
 
-use crate::prelude::*;
+use crate::{prelude::*, Tab};
 
 #[derive(IntoElement)]
 pub struct TabBar {
@@ -96,11 +96,7 @@ impl RenderOnce for TabBar {
             .flex()
             .flex_none()
             .w_full()
-            .h(
-                // TODO: This should scale with [UiDensity], however tabs,
-                // and other tab bar tools need to scale dynamically first.
-                rems_from_px(29.),
-            )
+            .h(Tab::container_height(cx))
             .bg(cx.theme().colors().tab_bar_background)
             .when(!self.start_children.is_empty(), |this| {
                 this.child(
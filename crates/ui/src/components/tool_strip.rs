@@ -34,11 +34,9 @@ impl ToolStrip {
 
 impl RenderOnce for ToolStrip {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
-        let group = format!("tool_strip_{}", self.id.clone());
-
         div()
             .id(self.id.clone())
-            .group(group)
+            .group("tool_strip")
             .map(|element| match self.axis {
                 Axis::Vertical => element.v_flex(),
                 Axis::Horizontal => element.h_flex(),
@@ -92,16 +92,20 @@ pub fn tooltip_container<V>(
     let ui_font = ThemeSettings::get_global(cx).ui_font.clone();
 
     // padding to avoid tooltip appearing right below the mouse cursor
-    div().pl_2().pt_2p5().child(
-        v_flex()
-            .elevation_2(cx)
-            .font(ui_font)
-            .text_ui(cx)
-            .text_color(cx.theme().colors().text)
-            .py_1()
-            .px_2()
-            .map(|el| f(el, cx)),
-    )
+    div()
+        .debug_selector(|| "TOOLTIP".into())
+        .pl_2()
+        .pt_2p5()
+        .child(
+            v_flex()
+                .elevation_tooltip(cx)
+                .font(ui_font)
+                .text_ui(cx)
+                .text_color(cx.theme().colors().text)
+                .py_1()
+                .px_2()
+                .map(|el| f(el, cx)),
+        )
 }
 
 pub struct LinkPreview {
@@ -1,14 +1,14 @@
-use gpui::{hsla, px, Styled, WindowContext};
+use gpui::{hsla, px, ElevationLevel, Styled, WindowContext};
 
 use crate::prelude::*;
 use crate::ElevationIndex;
 
-fn elevated<E: Styled>(this: E, cx: &mut WindowContext, index: ElevationIndex) -> E {
+fn elevated<E: Styled>(this: E, cx: &mut WindowContext, level: ElevationLevel) -> E {
     this.bg(cx.theme().colors().elevated_surface_background)
         .rounded(px(8.))
         .border_1()
         .border_color(cx.theme().colors().border_variant)
-        .shadow(index.shadow())
+        .elevation(level)
 }
 
 /// Extends [`gpui::Styled`] with Zed-specific styling methods.
@@ -29,20 +29,20 @@ pub trait StyledExt: Styled + Sized {
 
     /// The [`Surface`](ElevationIndex::Surface) elevation level, located above the app background, is the standard level for all elements
     ///
-    /// Sets `bg()`, `rounded_lg()`, `border()`, `border_color()`, `shadow()`
+    /// Sets `bg()`, `rounded_lg()`, `border()`, `border_color()`, `elevation()`
     ///
     /// Example Elements: Title Bar, Panel, Tab Bar, Editor
     fn elevation_1(self, cx: &mut WindowContext) -> Self {
-        elevated(self, cx, ElevationIndex::Surface)
+        elevated(self, cx, ElevationIndex::Surface.level())
     }
 
     /// Non-Modal Elevated Surfaces appear above the [`Surface`](ElevationIndex::Surface) layer and is used for things that should appear above most UI elements like an editor or panel, but not elements like popovers, context menus, modals, etc.
     ///
-    /// Sets `bg()`, `rounded_lg()`, `border()`, `border_color()`, `shadow()`
+    /// Sets `bg()`, `rounded_lg()`, `border()`, `border_color()`, `elevation()`
     ///
     /// Examples: Notifications, Palettes, Detached/Floating Windows, Detached/Floating Panels
     fn elevation_2(self, cx: &mut WindowContext) -> Self {
-        elevated(self, cx, ElevationIndex::ElevatedSurface)
+        elevated(self, cx, ElevationIndex::ElevatedSurface.level())
     }
 
     /// Modal Surfaces are used for elements that should appear above all other UI elements and are located above the wash layer. This is the maximum elevation at which UI elements can be rendered in their default state.
@@ -51,11 +51,28 @@ pub trait StyledExt: Styled + Sized {
     ///
     /// If the element does not have this behavior, it should be rendered at the [`Elevated Surface`](ElevationIndex::ElevatedSurface) layer.
     ///
-    /// Sets `bg()`, `rounded_lg()`, `border()`, `border_color()`, `shadow()`
+    /// Sets `bg()`, `rounded_lg()`, `border()`, `border_color()`, `elevation()`
     ///
     /// Examples: Settings Modal, Channel Management, Wizards/Setup UI, Dialogs
     fn elevation_3(self, cx: &mut WindowContext) -> Self {
-        elevated(self, cx, ElevationIndex::ModalSurface)
+        elevated(self, cx, ElevationIndex::ModalSurface.level())
+    }
+
+    /// Overlays are drawn over the rest of the window and aren't clipped by the elements they're
+    /// drawn within, but stay below modals.
+    ///
+    /// Sets `bg()`, `rounded_lg()`, `border()`, `border_color()`, `elevation()`
+    ///
+    /// Examples: Context Menus, Popovers, Dropdowns
+    fn elevation_overlay(self, cx: &mut WindowContext) -> Self {
+        elevated(self, cx, ElevationLevel::Overlay)
+    }
+
+    /// Tooltips are drawn over everything else, including modals.
+    ///
+    /// Sets `bg()`, `rounded_lg()`, `border()`, `border_color()`, `elevation()`
+    fn elevation_tooltip(self, cx: &mut WindowContext) -> Self {
+        elevated(self, cx, ElevationLevel::Tooltip)
     }
 
     /// The theme's primary border color.
@@ -68,6 +85,15 @@ pub trait StyledExt: Styled + Sized {
         self.border_color(cx.theme().colors().border_variant)
     }
 
+    /// Draws the 2px ring that shows which control has keyboard focus, outside of the element's
+    /// bounds so it doesn't move anything when focus changes.
+    ///
+    /// Usually applied through [`FocusableElement::focus_visible`](gpui::FocusableElement::focus_visible),
+    /// so it's only shown when the element was focused from the keyboard.
+    fn focus_ring(self, cx: &WindowContext) -> Self {
+        self.outline(px(2.), cx.theme().colors().border_focused)
+    }
+
     /// Sets the background color to red for debugging when building UI.
     fn debug_bg_red(self) -> Self {
         self.bg(hsla(0. / 360., 1., 0.5, 1.))
@@ -1,5 +1,4 @@
-use gpui::{hsla, point, px, BoxShadow};
-use smallvec::{smallvec, SmallVec};
+use gpui::ElevationLevel;
 
 #[doc = include_str!("docs/elevation.md")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,39 +25,14 @@ pub enum ElevationIndex {
 }
 
 impl ElevationIndex {
-    pub fn shadow(self) -> SmallVec<[BoxShadow; 2]> {
+    /// The GPUI elevation level of elements at this index, which gives them the shadow and z-index
+    /// the theme sets for it.
+    pub fn level(self) -> ElevationLevel {
         match self {
-            ElevationIndex::Surface => smallvec![],
-
-            ElevationIndex::ElevatedSurface => smallvec![BoxShadow {
-                color: hsla(0., 0., 0., 0.12),
-                offset: point(px(0.), px(2.)),
-                blur_radius: px(3.),
-                spread_radius: px(0.),
-            }],
-
-            ElevationIndex::ModalSurface => smallvec![
-                BoxShadow {
-                    color: hsla(0., 0., 0., 0.12),
-                    offset: point(px(0.), px(2.)),
-                    blur_radius: px(3.),
-                    spread_radius: px(0.),
-                },
-                BoxShadow {
-                    color: hsla(0., 0., 0., 0.08),
-                    offset: point(px(0.), px(3.)),
-                    blur_radius: px(6.),
-                    spread_radius: px(0.),
-                },
-                BoxShadow {
-                    color: hsla(0., 0., 0., 0.04),
-                    offset: point(px(0.), px(6.)),
-                    blur_radius: px(12.),
-                    spread_radius: px(0.),
-                },
-            ],
-
-            _ => smallvec![],
+            ElevationIndex::Background | ElevationIndex::Surface => ElevationLevel::Surface,
+            ElevationIndex::ElevatedSurface => ElevationLevel::Raised,
+            ElevationIndex::Wash | ElevationIndex::ModalSurface => ElevationLevel::Modal,
+            ElevationIndex::DraggedElement => ElevationLevel::Tooltip,
         }
     }
 }
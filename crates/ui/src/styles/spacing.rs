@@ -1,4 +1,4 @@
-use gpui::{px, rems, Pixels, Rems, WindowContext};
+use gpui::{provide_context, px, rems, IntoElement, Pixels, ProvideContext, Rems, WindowContext};
 use settings::Settings;
 use theme::{ThemeSettings, UiDensity};
 
@@ -39,7 +39,7 @@ pub enum Spacing {
 
 impl Spacing {
     pub fn spacing_ratio(self, cx: &WindowContext) -> f32 {
-        match ThemeSettings::get_global(cx).ui_density {
+        match user_spacing_style(cx) {
             UiDensity::Compact => match self {
                 Spacing::None => 0.,
                 Spacing::XXSmall => 1. / BASE_REM_SIZE_IN_PX,
@@ -84,10 +84,66 @@ impl Spacing {
     }
 }
 
+/// The density of the elements being drawn: the one given to the nearest enclosing
+/// [`density`] element, or the user's `ui_density` setting outside of one.
 pub fn user_spacing_style(cx: &WindowContext) -> UiDensity {
-    ThemeSettings::get_global(cx).ui_density
+    cx.context::<UiDensity>()
+        .copied()
+        .unwrap_or_else(|| ThemeSettings::get_global(cx).ui_density)
+}
+
+/// Draws `child` and everything in it at the given density instead of the user's setting,
+/// e.g. to make an information-dense panel compact. Spacing, component heights and text
+/// sizes all follow the density.
+pub fn density(density: UiDensity, child: impl IntoElement) -> ProvideContext<UiDensity> {
+    provide_context(density, child)
 }
 
 pub fn custom_spacing(cx: &WindowContext, size: f32) -> Rems {
     rems_from_px(size * user_spacing_style(cx).spacing_ratio())
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::{Render, TestAppContext, ViewContext};
+
+    use super::*;
+    use crate::prelude::*;
+
+    /// A box whose height is a spacing preset, resolved when it's drawn.
+    #[derive(IntoElement)]
+    struct Probe(&'static str);
+
+    impl RenderOnce for Probe {
+        fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+            div()
+                .h(Spacing::XLarge.rems(cx))
+                .debug_selector(|| self.0.into())
+        }
+    }
+
+    struct Panels;
+
+    impl Render for Panels {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            v_flex().child(Probe("setting")).child(density(
+                UiDensity::Compact,
+                v_flex()
+                    .child(Probe("compact"))
+                    .child(density(UiDensity::Comfortable, Probe("comfortable"))),
+            ))
+        }
+    }
+
+    #[gpui::test]
+    fn test_density_applies_to_its_subtree(cx: &mut TestAppContext) {
+        crate::test::init(cx);
+        let (_, cx) = cx.add_window_view(|_| Panels);
+        cx.run_until_parked();
+
+        let mut height = |selector| cx.debug_bounds(selector).unwrap().size.height;
+        assert_eq!(height("setting"), px(12.));
+        assert_eq!(height("compact"), px(8.));
+        assert_eq!(height("comfortable"), px(16.));
+    }
+}
@@ -2,9 +2,9 @@ use gpui::{
     div, rems, IntoElement, ParentElement, Rems, RenderOnce, SharedString, Styled, WindowContext,
 };
 use settings::Settings;
-use theme::{ActiveTheme, ThemeSettings};
+use theme::{ActiveTheme, ThemeSettings, UiDensity};
 
-use crate::{rems_from_px, Color};
+use crate::{rems_from_px, user_spacing_style, Color};
 
 /// Extends [`gpui::Styled`] with typography-related styling methods.
 pub trait StyledTypography: Styled + Sized {
@@ -112,12 +112,18 @@ pub enum TextSize {
 }
 
 impl TextSize {
+    /// The size of text at the density of the elements being drawn. Compact UI text is a step
+    /// smaller, e.g. `12px` rather than `14px` for the default size.
     pub fn rems(self, cx: &WindowContext) -> Rems {
         let theme_settings = ThemeSettings::get_global(cx);
+        let compact = user_spacing_style(cx) == UiDensity::Compact;
 
         match self {
+            Self::Large if compact => rems_from_px(14.),
             Self::Large => rems_from_px(16.),
+            Self::Default if compact => rems_from_px(12.),
             Self::Default => rems_from_px(14.),
+            Self::Small if compact => rems_from_px(11.),
             Self::Small => rems_from_px(12.),
             Self::XSmall => rems_from_px(10.),
             Self::UI => rems_from_px(theme_settings.ui_font_size.into()),
@@ -0,0 +1,11 @@
+use gpui::TestAppContext;
+use settings::SettingsStore;
+
+/// Sets up the settings and the base theme that components read while they're drawn.
+pub(crate) fn init(cx: &mut TestAppContext) {
+    cx.update(|cx| {
+        let settings = SettingsStore::test(cx);
+        cx.set_global(settings);
+        theme::init(theme::LoadThemes::JustBase, cx);
+    });
+}
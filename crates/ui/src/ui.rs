@@ -11,6 +11,8 @@ pub mod prelude;
 mod selectable;
 mod styled_ext;
 mod styles;
+#[cfg(test)]
+mod test;
 pub mod utils;
 mod visible_on_hover;
 mod with_rem_size;
@@ -1,6 +1,6 @@
 use gpui::{
-    div, prelude::*, px, AnyView, DismissEvent, FocusHandle, ManagedView, Render, Subscription,
-    View, ViewContext, WindowContext,
+    dismissable, div, prelude::*, px, AnyView, DismissEvent, FocusHandle, ManagedView, Render,
+    Subscription, View, ViewContext, WindowContext,
 };
 use theme::ActiveTheme as _;
 use ui::{h_flex, v_flex};
@@ -23,6 +23,7 @@ pub trait ModalView: ManagedView {
 trait ModalViewHandle {
     fn on_before_dismiss(&mut self, cx: &mut WindowContext) -> DismissDecision;
     fn view(&self) -> AnyView;
+    fn focus_handle(&self, cx: &WindowContext) -> FocusHandle;
     fn fade_out_background(&self, cx: &WindowContext) -> bool;
 }
 
@@ -35,6 +36,10 @@ impl<V: ModalView> ModalViewHandle for View<V> {
         self.clone().into()
     }
 
+    fn focus_handle(&self, cx: &WindowContext) -> FocusHandle {
+        self.read(cx).focus_handle(cx)
+    }
+
     fn fade_out_background(&self, cx: &WindowContext) -> bool {
         self.read(cx).fade_out_background()
     }
@@ -151,6 +156,22 @@ impl Render for ModalLayer {
             return div();
         };
 
+        // Clicking away from the modal or pressing escape cancels it, as its own cancel action
+        // would, so that modals which go back a step before closing still do.
+        let layer = cx.view().downgrade();
+        let on_dismiss = move |cx: &mut WindowContext| {
+            let focus_handle = layer
+                .update(cx, |this, cx| {
+                    let active_modal = this.active_modal.as_ref()?;
+                    Some(active_modal.modal.focus_handle(cx))
+                })
+                .ok()
+                .flatten();
+            if let Some(focus_handle) = focus_handle {
+                focus_handle.dispatch_action(&menu::Cancel, cx);
+            }
+        };
+
         div()
             .absolute()
             .size_full()
@@ -159,11 +180,7 @@ impl Render for ModalLayer {
             .when(active_modal.modal.fade_out_background(cx), |el| {
                 let mut background = cx.theme().colors().elevated_surface_background;
                 background.fade_out(0.2);
-                el.bg(background)
-                    .occlude()
-                    .on_mouse_down_out(cx.listener(|this, _, cx| {
-                        this.hide_modal(cx);
-                    }))
+                el.bg(background).occlude()
             })
             .child(
                 v_flex()
@@ -173,7 +190,11 @@ impl Render for ModalLayer {
                     .flex_col()
                     .items_center()
                     .track_focus(&active_modal.focus_handle)
-                    .child(h_flex().occlude().child(active_modal.modal.view())),
+                    .child(dismissable(
+                        "modal",
+                        on_dismiss,
+                        h_flex().occlude().child(active_modal.modal.view()),
+                    )),
             )
     }
 }
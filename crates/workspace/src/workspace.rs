@@ -140,6 +140,8 @@ actions!(
     ]
 );
 
+actions!(debug, [WhyIsTheWindowHot]);
+
 #[derive(Clone, PartialEq)]
 pub struct OpenPaths {
     pub paths: Vec<PathBuf>,
@@ -3865,6 +3867,7 @@ impl Workspace {
                     .save_active_item(SaveIntent::SaveAs, cx)
                     .detach_and_log_err(cx);
             }))
+            .on_action(cx.listener(|_, _: &WhyIsTheWindowHot, cx| cx.log_frame_requesters()))
             .on_action(cx.listener(|workspace, _: &ActivatePreviousPane, cx| {
                 workspace.activate_previous_pane(cx)
             }))